@@ -0,0 +1,190 @@
+#![allow(unused_imports)]
+///! Generates a campaign packet: a habitable home system, its five nearest
+///! neighbors, a gazetteer, a sky chart, and Orbiter configs for the home
+///! system.
+use plotters::prelude::*;
+use std::fmt::Write as FmtWrite;
+use std::fs;
+use std::path::Path;
+
+use breakwater::astronomy::galaxy::constraints::Constraints as GalaxyConstraints;
+use breakwater::astronomy::galaxy::Galaxy;
+use breakwater::astronomy::planetary_system::PlanetarySystem;
+use breakwater::astronomy::star_system::constraints::Constraints as StarSystemConstraints;
+use breakwater::astronomy::star_system::interest::InterestWeights;
+use breakwater::astronomy::star_system::StarSystem;
+use breakwater::astronomy::stellar_neighbor::StellarNeighbor;
+use breakwater::export::orbiter;
+use breakwater::export::ExportOptions;
+use breakwater::*;
+
+const OUT_DIRECTORY: &str = "examples/output-campaign";
+const NEIGHBOR_COUNT: usize = 5;
+
+/// Where a neighbor appears in the home sky, as (longitude, latitude) in degrees.
+fn get_sky_position(neighbor: &StellarNeighbor) -> (f64, f64) {
+  let (x, y, z) = neighbor.coordinates;
+  let longitude = y.atan2(x).to_degrees();
+  let latitude = (z / neighbor.distance).asin().to_degrees();
+  (longitude, latitude)
+}
+
+/// The name of a star system's brightest star.
+///
+/// `StarSystem::name` isn't meaningful yet, so go by the host star instead.
+fn get_system_name(star_system: &StarSystem) -> String {
+  star_system.get_planetary_systems()[0].host_star.get_name()
+}
+
+/// Write a short description of a planetary system.
+fn write_planetary_system(output: &mut String, planetary_system: &PlanetarySystem) -> std::fmt::Result {
+  for satellite_system in planetary_system.satellite_systems.satellite_systems.iter() {
+    let planet = &satellite_system.planet;
+    writeln!(
+      output,
+      "    {}: {:.2} AU, {:.2} year orbit, {} moon(s){}",
+      planet.get_name(),
+      planet.get_semi_major_axis(),
+      planet.get_orbital_period(),
+      satellite_system.moons.moons.len(),
+      match planet.is_habitable() {
+        true => ", habitable",
+        false => "",
+      },
+    )?;
+  }
+  if let Some(asteroid_belt) = &planetary_system.asteroid_belt {
+    writeln!(
+      output,
+      "    Asteroid belt: {:.2}-{:.2} AU",
+      asteroid_belt.inner_edge, asteroid_belt.outer_edge
+    )?;
+  }
+  Ok(())
+}
+
+/// Write the gazetteer: the home system in detail, then each neighbor.
+fn write_gazetteer(
+  galaxy: &Galaxy,
+  home: &StarSystem,
+  neighbors: &[&StellarNeighbor],
+) -> Result<String, std::fmt::Error> {
+  let weights = InterestWeights::default();
+  let mut output = String::new();
+  writeln!(output, "GAZETTEER")?;
+  writeln!(output, "{}", galaxy.get_location())?;
+  writeln!(output)?;
+  writeln!(output, "{} (home)", get_system_name(home))?;
+  for planetary_system in home.get_planetary_systems() {
+    writeln!(output, "  {}", planetary_system.host_star.get_name())?;
+    write_planetary_system(&mut output, planetary_system)?;
+  }
+  for neighbor in neighbors.iter() {
+    let (longitude, latitude) = get_sky_position(neighbor);
+    writeln!(output)?;
+    writeln!(
+      output,
+      "{}: {:.1} ly, bearing {:.0}/{:+.0}, magnitude {:.1}, {} star(s), interest {:.1}",
+      get_system_name(&neighbor.star_system),
+      neighbor.distance,
+      longitude,
+      latitude,
+      neighbor.get_apparent_magnitude(),
+      neighbor.get_stellar_count(),
+      neighbor.star_system.get_interest_score(&weights),
+    )?;
+    for planetary_system in neighbor.star_system.get_planetary_systems() {
+      writeln!(output, "  {}", planetary_system.host_star.get_name())?;
+      write_planetary_system(&mut output, planetary_system)?;
+    }
+  }
+  Ok(output)
+}
+
+/// Draw the neighbors as they appear in the home sky.
+fn draw_sky_chart(path: &Path, neighbors: &[StellarNeighbor]) -> Result<(), Box<dyn std::error::Error>> {
+  let area = SVGBackend::new(path, (1200, 640)).into_drawing_area();
+  area.fill(&BLACK)?;
+  let mut chart = ChartBuilder::on(&area)
+    .caption("The Home Sky", ("sans-serif", 20).into_font().color(&WHITE))
+    .margin(20)
+    .x_label_area_size(30)
+    .y_label_area_size(30)
+    .build_cartesian_2d(-180.0..180.0, -90.0..90.0)?;
+  chart
+    .configure_mesh()
+    .axis_style(&WHITE)
+    .bold_line_style(&WHITE.mix(0.2))
+    .light_line_style(&WHITE.mix(0.05))
+    .label_style(("sans-serif", 12).into_font().color(&WHITE))
+    .draw()?;
+  for neighbor in neighbors.iter() {
+    let magnitude = neighbor.get_apparent_magnitude();
+    // Too faint to see without a telescope.
+    if magnitude > 6.5 {
+      continue;
+    }
+    let size = (7.0 - magnitude).max(1.0) as i32;
+    chart.draw_series(std::iter::once(
+      EmptyElement::at(get_sky_position(neighbor))
+        + Circle::new((0, 0), size, WHITE.filled())
+        + Text::new(
+          get_system_name(&neighbor.star_system),
+          (size + 4, -4),
+          ("sans-serif", 11).into_font().color(&WHITE),
+        ),
+    ))?;
+  }
+  area.present()?;
+  Ok(())
+}
+
+#[named]
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  init_pretty_env_logger();
+  trace_enter!();
+  let mut rng = rand::thread_rng();
+  let home = StarSystemConstraints::habitable()
+    .generate(&mut rng)
+    .map_err(|error| format!("{:?}", error))?;
+  let galaxy = loop {
+    let galaxy = GalaxyConstraints::default()
+      .generate(&mut rng)
+      .map_err(|error| format!("{:?}", error))?;
+    if galaxy.stellar_neighborhood.neighbors.len() >= NEIGHBOR_COUNT {
+      break galaxy;
+    }
+  };
+  let mut neighbors = galaxy
+    .stellar_neighborhood
+    .neighbors
+    .iter()
+    .collect::<Vec<&StellarNeighbor>>();
+  neighbors.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap());
+  neighbors.truncate(NEIGHBOR_COUNT);
+  let directory = Path::new(OUT_DIRECTORY);
+  fs::create_dir_all(directory)?;
+  let gazetteer = write_gazetteer(&galaxy, &home, &neighbors)?;
+  fs::write(directory.join("gazetteer.txt"), &gazetteer)?;
+  println!("{}", gazetteer);
+  draw_sky_chart(&directory.join("sky-chart.svg"), &galaxy.stellar_neighborhood.neighbors)?;
+  let options = ExportOptions {
+    location: Some(galaxy.get_location()),
+    ..ExportOptions::default()
+  };
+  for planetary_system in home.get_planetary_systems() {
+    for file in orbiter::export(planetary_system, &options) {
+      let path = directory.join("orbiter").join(&file.path);
+      fs::create_dir_all(path.parent().unwrap())?;
+      fs::write(path, file.contents)?;
+    }
+  }
+  println!("Campaign packet has been saved to {}", OUT_DIRECTORY);
+  trace_exit!();
+  Ok(())
+}
+
+#[test]
+fn entry_point() {
+  main().unwrap()
+}
@@ -0,0 +1,105 @@
+#![allow(unused_imports)]
+///! Generates a 20-ly sector of space and renders a top-down map of it.
+use plotters::prelude::*;
+
+use breakwater::astronomy::galaxy::constraints::Constraints as GalaxyConstraints;
+use breakwater::astronomy::galaxy::error::Error;
+use breakwater::astronomy::host_star::HostStar;
+use breakwater::astronomy::star::temperature_to_rgb;
+use breakwater::astronomy::stellar_neighbor::constraints::Constraints as StellarNeighborConstraints;
+use breakwater::astronomy::stellar_neighbor::StellarNeighbor;
+use breakwater::astronomy::stellar_neighborhood::constraints::Constraints as StellarNeighborhoodConstraints;
+use breakwater::*;
+
+const OUT_FILE_NAME: &str = "examples/output-sector-map.svg";
+const SECTOR_RADIUS: f64 = 20.0;
+
+/// The color of the brightest star in a neighbor, for plotting.
+fn get_neighbor_color(neighbor: &StellarNeighbor) -> RGBColor {
+  let planetary_systems = neighbor.star_system.get_planetary_systems();
+  let (r, g, b) = match &planetary_systems[0].host_star {
+    HostStar::Star(star) => star.absolute_rgb,
+    HostStar::CloseBinaryStar(close_binary_star) => close_binary_star.primary.absolute_rgb,
+    HostStar::StellarRemnant(stellar_remnant) => temperature_to_rgb(stellar_remnant.temperature),
+    HostStar::BrownDwarf(brown_dwarf) => temperature_to_rgb(brown_dwarf.temperature),
+  };
+  RGBColor(r, g, b)
+}
+
+/// The name of a neighbor's brightest star.
+fn get_neighbor_name(neighbor: &StellarNeighbor) -> String {
+  neighbor.star_system.get_planetary_systems()[0].host_star.get_name()
+}
+
+#[named]
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  init_pretty_env_logger();
+  trace_enter!();
+  let mut rng = rand::thread_rng();
+  let constraints = GalaxyConstraints {
+    stellar_neighborhood_constraints: Some(StellarNeighborhoodConstraints {
+      radius: Some(SECTOR_RADIUS),
+      neighbor_constraints: Some(StellarNeighborConstraints {
+        radius: Some(SECTOR_RADIUS),
+        ..StellarNeighborConstraints::default()
+      }),
+      ..StellarNeighborhoodConstraints::default()
+    }),
+    ..GalaxyConstraints::default()
+  };
+  let galaxy = constraints
+    .generate(&mut rng)
+    .map_err(|error: Error| format!("{:?}", error))?;
+  let stellar_neighborhood = &galaxy.stellar_neighborhood;
+  println!("{}", galaxy.get_location());
+  let area = SVGBackend::new(OUT_FILE_NAME, (1024, 1024)).into_drawing_area();
+  area.fill(&BLACK)?;
+  let mut chart = ChartBuilder::on(&area)
+    .caption(galaxy.get_location(), ("sans-serif", 20).into_font().color(&WHITE))
+    .margin(20)
+    .x_label_area_size(30)
+    .y_label_area_size(30)
+    .build_cartesian_2d(-SECTOR_RADIUS..SECTOR_RADIUS, -SECTOR_RADIUS..SECTOR_RADIUS)?;
+  chart
+    .configure_mesh()
+    .axis_style(&WHITE)
+    .bold_line_style(&WHITE.mix(0.2))
+    .light_line_style(&WHITE.mix(0.05))
+    .label_style(("sans-serif", 12).into_font().color(&WHITE))
+    .x_desc("x (ly)")
+    .y_desc("y (ly)")
+    .draw()?;
+  // Our own system sits at the origin.
+  chart.draw_series(std::iter::once(Cross::new((0.0, 0.0), 6, &WHITE)))?;
+  for neighbor in stellar_neighborhood.neighbors.iter() {
+    let (x, y, z) = neighbor.coordinates;
+    let color = get_neighbor_color(neighbor);
+    let size = 3 + neighbor.get_stellar_count() as i32;
+    chart.draw_series(std::iter::once(
+      EmptyElement::at((x, y))
+        + Circle::new((0, 0), size, color.filled())
+        + Text::new(
+          format!("{} ({:+.1})", get_neighbor_name(neighbor), z),
+          (8, -4),
+          ("sans-serif", 11).into_font().color(&WHITE),
+        ),
+    ))?;
+  }
+  for interaction in stellar_neighborhood.interactions.iter() {
+    let first = stellar_neighborhood.neighbors[interaction.neighbors.0].coordinates;
+    let second = stellar_neighborhood.neighbors[interaction.neighbors.1].coordinates;
+    chart.draw_series(LineSeries::new(
+      vec![(first.0, first.1), (second.0, second.1)],
+      &YELLOW.mix(0.5),
+    ))?;
+  }
+  area.present()?;
+  println!("Sector map has been saved to {}", OUT_FILE_NAME);
+  trace_exit!();
+  Ok(())
+}
+
+#[test]
+fn entry_point() {
+  main().unwrap()
+}
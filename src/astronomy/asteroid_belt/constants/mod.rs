@@ -0,0 +1,74 @@
+use crate::math::probability::Probability;
+
+/// Inner edge of an asteroid belt, as a fraction of the giant's orbit.
+///
+/// The main belt starts around 2.1 AU, or about 0.4 of Jupiter's orbit.
+pub const INNER_EDGE_RATIO: f64 = 0.40;
+
+/// Outer edge of an asteroid belt, as a fraction of the giant's orbit.
+///
+/// The main belt is bounded by the 2:1 resonance with Jupiter, at about 0.63
+/// of its orbit.
+pub const OUTER_EDGE_RATIO: f64 = 0.64;
+
+/// How much room to leave around a planet inside the belt region, as a
+/// multiple of its semi-major axis.
+pub const PLANET_CLEARANCE: f64 = 1.25;
+
+/// Minimum mass of an asteroid belt, in Mearth.
+pub const MINIMUM_MASS: f64 = 0.0001;
+
+/// Maximum mass of an asteroid belt, in Mearth.
+///
+/// The main belt is about 0.0004 Mearth, but it has lost most of what it
+/// started with.
+pub const MAXIMUM_MASS: f64 = 0.01;
+
+/// Mass of Jupiter, in Msol.
+pub const MSOL_PER_JUPITER_MASS: f64 = 9.547_9E-4;
+
+/// Interior mean-motion resonances with a giant that clear gaps in a belt,
+/// as (asteroid orbits, giant orbits).
+pub const KIRKWOOD_RESONANCES: [(u8, u8); 5] = [(4, 1), (3, 1), (5, 2), (7, 3), (2, 1)];
+
+/// Gap width relative to its location, per square root of the giant's mass
+/// ratio to the host star.
+///
+/// The 3:1 gap is about 0.05 AU wide at 2.5 AU.
+pub const GAP_WIDTH_FACTOR: f64 = 0.65;
+
+/// The probability that an asteroid belt holds a dwarf planet.
+pub const DWARF_PLANET_PROBABILITY: Probability = Probability::from_const(0.5);
+
+/// The most of a belt's mass that its dwarf planet can hold.
+///
+/// Ceres is about 40% of the main belt.
+pub const MAXIMUM_DWARF_PLANET_MASS_FRACTION: f64 = 0.4;
+
+/// The mass fraction of a belt that's metal.
+///
+/// M-type asteroids are somewhere around a tenth of the main belt.
+pub const METAL_FRACTION: f64 = 0.1;
+
+/// The most of a belt's non-metal, non-ice mass that can be carbonaceous.
+///
+/// C-types dominate the outer main belt, about three-quarters of it.
+pub const MAXIMUM_CARBONACEOUS_SHARE: f64 = 0.8;
+
+/// Where carbonaceous bodies start to appear, as a fraction of the distance
+/// to the frost line.
+pub const CARBONACEOUS_ONSET: f64 = 0.4;
+
+/// Where carbonaceous bodies reach their greatest share, as a fraction of
+/// the distance to the frost line.
+pub const CARBONACEOUS_SATURATION: f64 = 0.7;
+
+/// The most of a belt's mass that can be ice, well beyond the frost line.
+pub const MAXIMUM_ICE_FRACTION: f64 = 0.5;
+
+/// The variance of the natural logarithm of each material's share, from one
+/// asteroid to the next.
+///
+/// Asteroids are far from uniform; some are nearly pure metal, and some are
+/// rubble piles of ordinary chondrite.
+pub const COMPOSITION_VARIANCE: f64 = 0.5;
@@ -0,0 +1,187 @@
+use rand::prelude::*;
+
+use crate::astronomy::asteroid_belt::constants::*;
+use crate::astronomy::asteroid_belt::AsteroidBelt;
+use crate::astronomy::dwarf_planet::constants::MINIMUM_MASS as MINIMUM_DWARF_PLANET_MASS;
+use crate::astronomy::dwarf_planet::constraints::Constraints as DwarfPlanetConstraints;
+use crate::astronomy::dwarf_planet::DwarfPlanetPopulation;
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::satellite_systems::SatelliteSystems;
+use crate::math::probability::Probability;
+use crate::merge::Merge;
+
+/// Constraints for creating an asteroid belt.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+  /// The minimum mass, in Mearth.
+  pub minimum_mass: Option<f64>,
+  /// The maximum mass, in Mearth.
+  pub maximum_mass: Option<f64>,
+  /// The probability that the belt holds a dwarf planet.
+  pub dwarf_planet_probability: Option<Probability>,
+  /// Dwarf planet constraints.
+  pub dwarf_planet_constraints: Option<DwarfPlanetConstraints>,
+}
+
+impl Constraints {
+  /// Generate.
+  ///
+  /// A belt only forms inside the innermost gas giant, and only if there's
+  /// room for it between that giant and the planets within.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: &HostStar,
+    satellite_systems: &SatelliteSystems,
+  ) -> Option<AsteroidBelt> {
+    trace_enter!();
+    let planets: Vec<&Planet> = satellite_systems
+      .satellite_systems
+      .iter()
+      .map(|satellite_system| &satellite_system.planet)
+      .collect();
+    let giant = planets
+      .iter()
+      .filter_map(|planet| match planet {
+        Planet::GasGiantPlanet(gas_giant_planet) => Some(gas_giant_planet),
+        Planet::IceGiantPlanet(_) | Planet::TerrestrialPlanet(_) => None,
+      })
+      .min_by(|a, b| a.orbit.semi_major_axis.partial_cmp(&b.orbit.semi_major_axis).unwrap())?;
+    trace_var!(giant);
+    let outer_edge = OUTER_EDGE_RATIO * giant.orbit.semi_major_axis;
+    trace_var!(outer_edge);
+    let inner_edge = planets
+      .iter()
+      .map(|planet| planet.get_semi_major_axis())
+      .filter(|&semi_major_axis| semi_major_axis < outer_edge)
+      .map(|semi_major_axis| PLANET_CLEARANCE * semi_major_axis)
+      .fold(INNER_EDGE_RATIO * giant.orbit.semi_major_axis, f64::max)
+      // Around a close binary, nothing survives inside the stable limit.
+      .max(host_star.get_stability_limit());
+    trace_var!(inner_edge);
+    if inner_edge >= outer_edge {
+      trace_exit!();
+      return None;
+    }
+    let minimum_mass = self.minimum_mass.unwrap_or(MINIMUM_MASS);
+    trace_var!(minimum_mass);
+    let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
+    trace_var!(maximum_mass);
+    let mass = rng.gen_range(minimum_mass..=maximum_mass);
+    trace_var!(mass);
+    let mass_ratio = giant.mass * MSOL_PER_JUPITER_MASS / host_star.get_stellar_mass();
+    trace_var!(mass_ratio);
+    let mut result = AsteroidBelt::from_giant(
+      inner_edge,
+      outer_edge,
+      mass,
+      giant.orbit.semi_major_axis,
+      mass_ratio,
+      host_star.get_frost_line(),
+    );
+    let dwarf_planet_probability = self.dwarf_planet_probability.unwrap_or(DWARF_PLANET_PROBABILITY);
+    trace_var!(dwarf_planet_probability);
+    let dwarf_planet_constraints = self.dwarf_planet_constraints.unwrap_or_default();
+    trace_var!(dwarf_planet_constraints);
+    // A belt too light to have built a dwarf planet won't have one.
+    let maximum_dwarf_planet_mass = MAXIMUM_DWARF_PLANET_MASS_FRACTION * mass;
+    trace_var!(maximum_dwarf_planet_mass);
+    let minimum_dwarf_planet_mass = dwarf_planet_constraints
+      .minimum_mass
+      .unwrap_or(MINIMUM_DWARF_PLANET_MASS);
+    trace_var!(minimum_dwarf_planet_mass);
+    if maximum_dwarf_planet_mass > minimum_dwarf_planet_mass && dwarf_planet_probability.sample(rng) {
+      let dwarf_planet_constraints = DwarfPlanetConstraints {
+        maximum_mass: Some(
+          dwarf_planet_constraints
+            .maximum_mass
+            .map_or(maximum_dwarf_planet_mass, |maximum_mass| {
+              maximum_mass.min(maximum_dwarf_planet_mass)
+            }),
+        ),
+        ..dwarf_planet_constraints
+      };
+      let orbit = OrbitalElements {
+        inclination: rng.gen_range(0.0..10.0),
+        ..OrbitalElements::new(rng.gen_range(inner_edge..outer_edge), rng.gen_range(0.0..0.1))
+          .with_random_orientation(rng)
+      };
+      trace_var!(orbit);
+      let dwarf_planet = dwarf_planet_constraints.generate(rng, host_star, DwarfPlanetPopulation::Belt, orbit);
+      result.dwarf_planets.push(dwarf_planet);
+    }
+    trace_var!(result);
+    trace_exit!();
+    Some(result)
+  }
+}
+
+impl Default for Constraints {
+  /// No constraints, just let it all hang out.
+  fn default() -> Self {
+    let minimum_mass = None;
+    let maximum_mass = None;
+    let dwarf_planet_probability = None;
+    let dwarf_planet_constraints = None;
+    Self {
+      minimum_mass,
+      maximum_mass,
+      dwarf_planet_probability,
+      dwarf_planet_constraints,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+      dwarf_planet_probability: overrides.dwarf_planet_probability.or(self.dwarf_planet_probability),
+      dwarf_planet_constraints: self
+        .dwarf_planet_constraints
+        .merged_with(&overrides.dwarf_planet_constraints),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+  use crate::astronomy::satellite_systems::constraints::Constraints as SatelliteSystemsConstraints;
+  use crate::astronomy::satellite_systems::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = HostStarConstraints::habitable().generate(&mut rng)?;
+    let satellite_systems = SatelliteSystemsConstraints::habitable().generate(&mut rng, &host_star)?;
+    let asteroid_belt = Constraints::default().generate(&mut rng, &host_star, &satellite_systems);
+    print_var!(asteroid_belt);
+    if let Some(asteroid_belt) = asteroid_belt {
+      assert!(asteroid_belt.inner_edge < asteroid_belt.outer_edge);
+      for planet in satellite_systems.satellite_systems.iter().map(|system| &system.planet) {
+        let semi_major_axis = planet.get_semi_major_axis();
+        assert!(semi_major_axis < asteroid_belt.inner_edge || semi_major_axis > asteroid_belt.outer_edge);
+      }
+      for dwarf_planet in &asteroid_belt.dwarf_planets {
+        assert_eq!(dwarf_planet.population, DwarfPlanetPopulation::Belt);
+        assert!(dwarf_planet.mass <= MAXIMUM_DWARF_PLANET_MASS_FRACTION * asteroid_belt.mass);
+      }
+    }
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,52 @@
+use crate::astronomy::asteroid_belt::constants::*;
+
+/// Calculate where an interior mean-motion resonance with a giant falls.
+///
+/// The resonance is (asteroid orbits, giant orbits); the giant's semi-major
+/// axis and the result are in AU.
+#[named]
+pub fn get_resonance_location(giant_semi_major_axis: f64, resonance: (u8, u8)) -> f64 {
+  trace_enter!();
+  trace_var!(giant_semi_major_axis);
+  trace_var!(resonance);
+  let result = giant_semi_major_axis * (resonance.1 as f64 / resonance.0 as f64).powf(2.0 / 3.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the width of a gap cleared by a resonance, in AU.
+///
+/// Resonance widths scale with the square root of the perturber's mass ratio
+/// to the primary.
+#[named]
+pub fn get_gap_width(location: f64, mass_ratio: f64) -> f64 {
+  trace_enter!();
+  trace_var!(location);
+  trace_var!(mass_ratio);
+  let result = GAP_WIDTH_FACTOR * mass_ratio.sqrt() * location;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_jupiter() {
+    init();
+    trace_enter!();
+    assert_approx_eq!(get_resonance_location(5.2, (4, 1)), 2.06, 0.01);
+    assert_approx_eq!(get_resonance_location(5.2, (3, 1)), 2.50, 0.01);
+    assert_approx_eq!(get_resonance_location(5.2, (5, 2)), 2.82, 0.01);
+    assert_approx_eq!(get_resonance_location(5.2, (7, 3)), 2.96, 0.01);
+    assert_approx_eq!(get_resonance_location(5.2, (2, 1)), 3.28, 0.01);
+    assert_approx_eq!(get_gap_width(2.5, MSOL_PER_JUPITER_MASS), 0.05, 0.005);
+    trace_exit!();
+  }
+}
@@ -0,0 +1 @@
+pub mod kirkwood_gap;
@@ -0,0 +1,207 @@
+use rand::prelude::*;
+use rand_distr::{Distribution, LogNormal};
+use serde::Serialize;
+
+use crate::astronomy::asteroid_belt::constants::*;
+use crate::generator::seeded_rng;
+use crate::math::noise::get_seed;
+
+/// An amount of each class of material an asteroid might be mined for.
+///
+/// Depending on context, these are mass fractions or masses in KG.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct Materials {
+  /// Iron, nickel, and the platinum-group metals that come with them.
+  pub metal: f64,
+  /// Stony silicates.
+  pub silicate: f64,
+  /// Carbon-rich material, with its hydrated minerals and organics.
+  pub carbonaceous: f64,
+  /// Water ice and other volatiles.
+  pub ice: f64,
+}
+
+impl Materials {
+  /// Get the total of all materials.
+  #[named]
+  pub fn get_total(&self) -> f64 {
+    trace_enter!();
+    let result = self.metal + self.silicate + self.carbonaceous + self.ice;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Multiply every material by a factor.
+  #[named]
+  pub fn scaled(&self, factor: f64) -> Self {
+    trace_enter!();
+    trace_var!(factor);
+    let result = Self {
+      metal: self.metal * factor,
+      silicate: self.silicate * factor,
+      carbonaceous: self.carbonaceous * factor,
+      ice: self.ice * factor,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Scale so that the materials add up to one.
+  #[named]
+  pub fn normalized(&self) -> Self {
+    trace_enter!();
+    let result = self.scaled(1.0 / self.get_total());
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+/// The makeup of an asteroid belt, as a distribution that individual
+/// asteroids are drawn from.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct BeltComposition {
+  /// The average mass fractions.
+  pub mean: Materials,
+  /// The variance of the natural logarithm of each material's share, from
+  /// one asteroid to the next.
+  ///
+  /// Anything below zero is taken as zero, i.e. every asteroid is average.
+  pub variance: f64,
+}
+
+impl BeltComposition {
+  /// Work out the composition of a belt from where it lies relative to the
+  /// frost line.
+  ///
+  /// All distances are in AU.  Metal is found throughout; carbonaceous
+  /// bodies take over from stony ones further out, and ice survives only
+  /// beyond the frost line.
+  #[named]
+  pub fn from_edges(inner_edge: f64, outer_edge: f64, frost_line: f64) -> Self {
+    trace_enter!();
+    trace_var!(inner_edge);
+    trace_var!(outer_edge);
+    trace_var!(frost_line);
+    let distance = (inner_edge + outer_edge) / 2.0 / frost_line;
+    trace_var!(distance);
+    let carbonaceous_progress =
+      ((distance - CARBONACEOUS_ONSET) / (CARBONACEOUS_SATURATION - CARBONACEOUS_ONSET)).clamp(0.0, 1.0);
+    trace_var!(carbonaceous_progress);
+    let ice_progress = (distance - 1.0).clamp(0.0, 1.0);
+    trace_var!(ice_progress);
+    let metal = METAL_FRACTION;
+    let ice = MAXIMUM_ICE_FRACTION * ice_progress;
+    let remainder = 1.0 - metal - ice;
+    let carbonaceous = remainder * MAXIMUM_CARBONACEOUS_SHARE * carbonaceous_progress;
+    let silicate = remainder - carbonaceous;
+    let mean = Materials {
+      metal,
+      silicate,
+      carbonaceous,
+      ice,
+    };
+    trace_var!(mean);
+    let variance = COMPOSITION_VARIANCE;
+    trace_var!(variance);
+    let result = Self { mean, variance };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Draw the mass fractions of a single asteroid.
+  #[named]
+  pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Materials {
+    trace_enter!();
+    let log_normal = LogNormal::new(0.0, self.variance.max(0.0).sqrt()).unwrap();
+    let result = Materials {
+      metal: self.mean.metal * log_normal.sample(rng),
+      silicate: self.mean.silicate * log_normal.sample(rng),
+      carbonaceous: self.mean.carbonaceous * log_normal.sample(rng),
+      ice: self.mean.ice * log_normal.sample(rng),
+    }
+    .normalized();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the mass fractions of the asteroid with the specified ID.
+  ///
+  /// The same ID always gives the same asteroid, so anything built on top of
+  /// this can refer to asteroids by name without storing them.
+  #[named]
+  pub fn get_asteroid_composition(&self, id: &str) -> Materials {
+    trace_enter!();
+    trace_var!(id);
+    let mut rng = seeded_rng(get_seed(id));
+    let result = self.sample(&mut rng);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the mass of each material in the asteroid with the specified ID
+  /// and mass.
+  ///
+  /// Mass is in KG; so is the yield.
+  #[named]
+  pub fn get_asteroid_yield(&self, id: &str, mass: f64) -> Materials {
+    trace_enter!();
+    trace_var!(id);
+    trace_var!(mass);
+    let result = self.get_asteroid_composition(id).scaled(mass);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_main_belt() {
+    init();
+    trace_enter!();
+    let composition = BeltComposition::from_edges(2.1, 3.3, 4.85);
+    print_var!(composition);
+    assert_approx_eq!(composition.mean.get_total(), 1.0);
+    assert_eq!(composition.mean.ice, 0.0);
+    assert!(composition.mean.carbonaceous > 0.0);
+    let icy = BeltComposition::from_edges(6.0, 8.0, 4.85);
+    print_var!(icy);
+    assert!(icy.mean.ice > 0.0);
+    assert!(icy.mean.carbonaceous > composition.mean.carbonaceous);
+    let uniform = BeltComposition {
+      variance: -1.0,
+      ..composition
+    };
+    let asteroid = uniform.get_asteroid_composition("Uniform");
+    print_var!(asteroid);
+    assert_approx_eq!(asteroid.silicate, composition.mean.silicate);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_asteroid_yield() {
+    init();
+    trace_enter!();
+    let composition = BeltComposition::from_edges(2.1, 3.3, 4.85);
+    let first = composition.get_asteroid_yield("Sol:16 Psyche", 2.3E19);
+    print_var!(first);
+    assert_eq!(first, composition.get_asteroid_yield("Sol:16 Psyche", 2.3E19));
+    assert_ne!(first, composition.get_asteroid_yield("Sol:433 Eros", 2.3E19));
+    assert_approx_eq!(first.get_total() / 2.3E19, 1.0);
+    assert_eq!(first.ice, 0.0);
+    trace_exit!();
+  }
+}
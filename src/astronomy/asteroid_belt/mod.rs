@@ -0,0 +1,145 @@
+use serde::Serialize;
+
+use crate::astronomy::dwarf_planet::DwarfPlanet;
+
+pub mod constants;
+use constants::*;
+pub mod constraints;
+pub(crate) mod math;
+pub mod mining;
+use math::kirkwood_gap::{get_gap_width, get_resonance_location};
+use mining::BeltComposition;
+
+/// A gap cleared in an asteroid belt by a resonance with a giant planet.
+///
+/// These are analogous to the Kirkwood gaps that Jupiter carves in our own
+/// main belt.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct KirkwoodGap {
+  /// The resonance, as (asteroid orbits, giant orbits).
+  pub resonance: (u8, u8),
+  /// The center of the gap, in AU.
+  pub semi_major_axis: f64,
+  /// The full width of the gap, in AU.
+  pub width: f64,
+}
+
+/// An `AsteroidBelt` is a ring of minor bodies shepherded by a giant planet.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AsteroidBelt {
+  /// The inner edge, in AU.
+  pub inner_edge: f64,
+  /// The outer edge, in AU.
+  pub outer_edge: f64,
+  /// The total mass, in Mearth.
+  pub mass: f64,
+  /// What the belt is made of, and how much that varies.
+  pub composition: BeltComposition,
+  /// The gaps within the belt, innermost first.
+  pub gaps: Vec<KirkwoodGap>,
+  /// The dwarf planets, if the belt has gathered itself into any.
+  pub dwarf_planets: Vec<DwarfPlanet>,
+}
+
+impl AsteroidBelt {
+  /// Sculpt a belt between the specified edges using the resonances of a
+  /// giant planet.
+  ///
+  /// The giant's semi-major axis, the edges, and the host star's frost line
+  /// are in AU; the mass ratio is that of the giant to the host star.
+  #[named]
+  pub fn from_giant(
+    inner_edge: f64,
+    outer_edge: f64,
+    mass: f64,
+    giant_semi_major_axis: f64,
+    mass_ratio: f64,
+    frost_line: f64,
+  ) -> Self {
+    trace_enter!();
+    trace_var!(inner_edge);
+    trace_var!(outer_edge);
+    trace_var!(mass);
+    trace_var!(giant_semi_major_axis);
+    trace_var!(mass_ratio);
+    trace_var!(frost_line);
+    let composition = BeltComposition::from_edges(inner_edge, outer_edge, frost_line);
+    trace_var!(composition);
+    let mut gaps: Vec<KirkwoodGap> = KIRKWOOD_RESONANCES
+      .iter()
+      .map(|&resonance| {
+        let semi_major_axis = get_resonance_location(giant_semi_major_axis, resonance);
+        let width = get_gap_width(semi_major_axis, mass_ratio);
+        KirkwoodGap {
+          resonance,
+          semi_major_axis,
+          width,
+        }
+      })
+      .filter(|gap| gap.semi_major_axis > inner_edge && gap.semi_major_axis < outer_edge)
+      .collect();
+    gaps.sort_by(|a, b| a.semi_major_axis.partial_cmp(&b.semi_major_axis).unwrap());
+    trace_var!(gaps);
+    let dwarf_planets = vec![];
+    let result = Self {
+      inner_edge,
+      outer_edge,
+      mass,
+      composition,
+      gaps,
+      dwarf_planets,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Find the gap, if any, at the specified distance in AU.
+  #[named]
+  pub fn get_gap_at(&self, distance: f64) -> Option<&KirkwoodGap> {
+    trace_enter!();
+    trace_var!(distance);
+    let result = self
+      .gaps
+      .iter()
+      .find(|gap| (distance - gap.semi_major_axis).abs() <= gap.width / 2.0);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether the specified distance in AU lies within the belt and
+  /// outside of any gap.
+  #[named]
+  pub fn is_populated_at(&self, distance: f64) -> bool {
+    trace_enter!();
+    trace_var!(distance);
+    let result = distance >= self.inner_edge && distance <= self.outer_edge && self.get_gap_at(distance).is_none();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_main_belt() {
+    init();
+    trace_enter!();
+    let belt = AsteroidBelt::from_giant(2.1, 3.3, 0.0004, 5.2, MSOL_PER_JUPITER_MASS, 4.85);
+    print_var!(belt);
+    let resonances: Vec<(u8, u8)> = belt.gaps.iter().map(|gap| gap.resonance).collect();
+    assert_eq!(resonances, vec![(3, 1), (5, 2), (7, 3), (2, 1)]);
+    assert_eq!(belt.get_gap_at(2.5).unwrap().resonance, (3, 1));
+    assert!(belt.is_populated_at(2.7));
+    assert!(!belt.is_populated_at(2.5));
+    assert!(!belt.is_populated_at(4.0));
+    trace_exit!();
+  }
+}
@@ -0,0 +1,42 @@
+/// Minimum mass of a brown dwarf, in Msol.
+///
+/// About 13 Mjupiter; anything lighter can't burn deuterium, which makes it
+/// a planet.
+pub const MINIMUM_MASS: f64 = 0.013;
+
+/// Maximum mass of a brown dwarf, in Msol.
+///
+/// Anything heavier burns hydrogen, which makes it a star.
+pub const MAXIMUM_MASS: f64 = 0.075;
+
+/// Minimum age of a brown dwarf, in Gyr.
+pub const MINIMUM_AGE: f64 = 0.01;
+
+/// Maximum age of a brown dwarf, in Gyr.
+pub const MAXIMUM_AGE: f64 = 13.0;
+
+/// The radius of a brown dwarf, in Rsol.
+///
+/// Degeneracy pressure keeps them all about the size of Jupiter, whatever
+/// their mass.
+pub const RADIUS: f64 = 0.1;
+
+/// The luminosity of a brown dwarf of the reference mass at one Gyr, in Lsol.
+pub const REFERENCE_LUMINOSITY: f64 = 4.0E-5;
+
+/// The reference mass for the cooling law, in Msol.
+pub const REFERENCE_MASS: f64 = 0.05;
+
+/// The hottest a brown dwarf gets, in Kelvin.
+///
+/// Even the youngest and heaviest come out no earlier than about M7.
+pub const MAXIMUM_TEMPERATURE: f64 = 2_900.0;
+
+/// The hottest L dwarf, in Kelvin; anything hotter looks like a late M.
+pub const MAXIMUM_L_TEMPERATURE: f64 = 2_200.0;
+
+/// The hottest T dwarf, in Kelvin.
+pub const MAXIMUM_T_TEMPERATURE: f64 = 1_300.0;
+
+/// The hottest Y dwarf, in Kelvin.
+pub const MAXIMUM_Y_TEMPERATURE: f64 = 550.0;
@@ -0,0 +1,123 @@
+use rand::prelude::*;
+
+use crate::astronomy::brown_dwarf::constants::*;
+use crate::astronomy::brown_dwarf::error::Error;
+use crate::astronomy::brown_dwarf::BrownDwarf;
+use crate::astronomy::star::name::generate_star_name;
+use crate::generator::Generator;
+use crate::merge::Merge;
+
+/// Constraints for creating a brown dwarf.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+  /// The minimum mass, in Msol.
+  pub minimum_mass: Option<f64>,
+  /// The maximum mass, in Msol.
+  pub maximum_mass: Option<f64>,
+  /// The minimum age, in Gyr.
+  pub minimum_age: Option<f64>,
+  /// The maximum age, in Gyr.
+  pub maximum_age: Option<f64>,
+}
+
+impl Constraints {
+  /// Generate.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<BrownDwarf, Error> {
+    trace_enter!();
+    let minimum_mass = self.minimum_mass.unwrap_or(MINIMUM_MASS);
+    trace_var!(minimum_mass);
+    let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
+    trace_var!(maximum_mass);
+    if minimum_mass > maximum_mass {
+      return Err(Error::MinimumMassExceedsMaximumMass);
+    }
+    let minimum_age = self.minimum_age.unwrap_or(MINIMUM_AGE);
+    trace_var!(minimum_age);
+    let maximum_age = self.maximum_age.unwrap_or(MAXIMUM_AGE);
+    trace_var!(maximum_age);
+    if minimum_age > maximum_age {
+      return Err(Error::MinimumAgeExceedsMaximumAge);
+    }
+    let mass = rng.gen_range(minimum_mass..=maximum_mass);
+    trace_var!(mass);
+    let current_age = rng.gen_range(minimum_age..=maximum_age);
+    trace_var!(current_age);
+    let name = generate_star_name(rng);
+    trace_var!(name);
+    let result = Ok(BrownDwarf::from_mass_and_age(name, mass, current_age));
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl Generator for Constraints {
+  type Output = BrownDwarf;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<BrownDwarf, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
+impl Default for Constraints {
+  /// No constraints, just let it all hang out.
+  fn default() -> Self {
+    let minimum_mass = None;
+    let maximum_mass = None;
+    let minimum_age = None;
+    let maximum_age = None;
+    Self {
+      minimum_mass,
+      maximum_mass,
+      minimum_age,
+      maximum_age,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+      minimum_age: overrides.minimum_age.or(self.minimum_age),
+      maximum_age: overrides.maximum_age.or(self.maximum_age),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let brown_dwarf = Constraints::default().generate(&mut rng)?;
+    print_var!(brown_dwarf);
+    assert!(brown_dwarf.mass >= MINIMUM_MASS && brown_dwarf.mass <= MAXIMUM_MASS);
+    let error = Constraints {
+      minimum_mass: Some(0.05),
+      maximum_mass: Some(0.02),
+      ..Constraints::default()
+    }
+    .generate(&mut rng);
+    assert_eq!(error, Err(Error::MinimumMassExceedsMaximumMass));
+    trace_exit!();
+    Ok(())
+  }
+}
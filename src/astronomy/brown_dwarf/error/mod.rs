@@ -0,0 +1,19 @@
+/// BrownDwarf errors.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+  /// The minimum mass is greater than the maximum mass.
+  MinimumMassExceedsMaximumMass,
+  /// The minimum age is greater than the maximum age.
+  MinimumAgeExceedsMaximumAge,
+  /// Too faint to warm anything worth living on.
+  TooDimToSupportLife,
+}
+
+honeyholt_define_brief!(Error, |error: &Error| {
+  use Error::*;
+  match error {
+    MinimumMassExceedsMaximumMass => "the minimum mass is greater than the maximum mass".to_string(),
+    MinimumAgeExceedsMaximumAge => "the minimum age is greater than the maximum age".to_string(),
+    TooDimToSupportLife => "the brown dwarf is too dim to support life".to_string(),
+  }
+});
@@ -0,0 +1,81 @@
+use crate::astronomy::brown_dwarf::constants::*;
+use crate::astronomy::star::math::evolution::SOLAR_TEMPERATURE;
+
+/// Calculate the luminosity of a brown dwarf, in Lsol, from its mass in Msol
+/// and its age in Gyr.
+///
+/// Brown dwarfs have no fusion to speak of, so they just cool off and fade;
+/// this is the power-law fit from Burrows et al. (2001).  The fit runs far
+/// too hot for the young and heavy, so it's capped at the luminosity of a
+/// dwarf at the maximum temperature.
+#[named]
+pub fn get_luminosity(mass: f64, age: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(age);
+  let maximum_luminosity = RADIUS.powf(2.0) * (MAXIMUM_TEMPERATURE / SOLAR_TEMPERATURE).powf(4.0);
+  trace_var!(maximum_luminosity);
+  let result = (REFERENCE_LUMINOSITY * age.powf(-1.3) * (mass / REFERENCE_MASS).powf(2.64)).min(maximum_luminosity);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the spectral class of a brown dwarf from its effective temperature,
+/// in Kelvin.
+///
+/// The young and heavy are still hot enough to pass for late M dwarfs.
+#[named]
+pub fn brown_dwarf_temperature_to_spectral_class(temperature: f64) -> String {
+  trace_enter!();
+  trace_var!(temperature);
+  let (spectral_type, hottest, coolest) = match temperature {
+    temperature if temperature >= MAXIMUM_L_TEMPERATURE => ('M', MAXIMUM_TEMPERATURE, MAXIMUM_L_TEMPERATURE),
+    temperature if temperature >= MAXIMUM_T_TEMPERATURE => ('L', MAXIMUM_L_TEMPERATURE, MAXIMUM_T_TEMPERATURE),
+    temperature if temperature >= MAXIMUM_Y_TEMPERATURE => ('T', MAXIMUM_T_TEMPERATURE, MAXIMUM_Y_TEMPERATURE),
+    _ => ('Y', MAXIMUM_Y_TEMPERATURE, 250.0),
+  };
+  trace_var!(spectral_type);
+  let decile = match spectral_type {
+    // The M dwarfs we pass for are all late ones.
+    'M' => 7.0 + 2.0 * ((hottest - temperature) / (hottest - coolest)).clamp(0.0, 1.0),
+    _ => 9.0 * ((hottest - temperature) / (hottest - coolest)).clamp(0.0, 1.0),
+  };
+  trace_var!(decile);
+  let result = format!("{}{:.0}", spectral_type, decile);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::astronomy::star::math::evolution::get_temperature_from_luminosity_and_radius;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_cooling() {
+    init();
+    trace_enter!();
+    // Gliese 229 B is a T7 of about 0.03 Msol and a few Gyr.
+    let luminosity = get_luminosity(0.03, 3.0);
+    print_var!(luminosity);
+    let temperature = get_temperature_from_luminosity_and_radius(luminosity, RADIUS);
+    print_var!(temperature);
+    assert_approx_eq!(temperature, 900.0, 300.0);
+    assert!(brown_dwarf_temperature_to_spectral_class(temperature).starts_with('T'));
+    assert_eq!(brown_dwarf_temperature_to_spectral_class(2_500.0), "M8");
+    assert_eq!(brown_dwarf_temperature_to_spectral_class(1_700.0), "L5");
+    assert_eq!(brown_dwarf_temperature_to_spectral_class(350.0), "Y6");
+    for (mass, age) in [(0.075, 0.01), (0.05, 0.01), (0.075, 0.1)] {
+      let temperature = get_temperature_from_luminosity_and_radius(get_luminosity(mass, age), RADIUS);
+      print_var!(temperature);
+      assert!(temperature <= MAXIMUM_TEMPERATURE + 1.0);
+      assert_eq!(brown_dwarf_temperature_to_spectral_class(temperature), "M7");
+    }
+    trace_exit!();
+  }
+}
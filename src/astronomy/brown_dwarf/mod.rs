@@ -0,0 +1,153 @@
+use serde::Serialize;
+
+use crate::astronomy::star::math::evolution::get_temperature_from_luminosity_and_radius;
+use crate::astronomy::star::math::frost_line::star_luminosity_to_frost_line;
+use crate::astronomy::star::math::habitable_zone::{get_habitable_zone, HabitableZoneModel};
+use crate::astronomy::star::math::satellite_zone::{get_approximate_innermost_orbit, get_approximate_outermost_orbit};
+
+pub mod constants;
+use constants::*;
+pub mod constraints;
+pub mod error;
+use error::Error;
+pub(crate) mod math;
+use math::*;
+
+/// A `BrownDwarf` is too heavy to be a planet and too light to be a star.
+///
+/// With no hydrogen fusion to keep them going, brown dwarfs shine only by
+/// the heat of their own contraction, and cool from late M through L and T
+/// into the Y class as they age.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BrownDwarf {
+  /// The name.
+  pub name: String,
+  /// Mass, in Msol.
+  pub mass: f64,
+  /// Radius, in Rsol.
+  pub radius: f64,
+  /// Measured in Gyr.
+  pub current_age: f64,
+  /// Surface temperature, in Kelvin.
+  pub temperature: f64,
+  /// Luminosity, in Lsol.
+  pub luminosity: f64,
+  /// Spectral class, e.g. "L3" or "T7".
+  pub spectral_class: String,
+  /// Habitable zone, measured in AU.
+  pub habitable_zone: (f64, f64),
+  /// Minimum and maximum sustainable distance for satellites, measured in AU.
+  pub satellite_zone: (f64, f64),
+  /// The frost line, measured in AU.
+  pub frost_line: f64,
+}
+
+impl BrownDwarf {
+  /// Create a brown dwarf of the given mass (in Msol) and age (in Gyr).
+  #[named]
+  pub fn from_mass_and_age(name: String, mass: f64, current_age: f64) -> Self {
+    trace_enter!();
+    trace_var!(name);
+    trace_var!(mass);
+    trace_var!(current_age);
+    let luminosity = get_luminosity(mass, current_age);
+    trace_var!(luminosity);
+    let radius = RADIUS;
+    trace_var!(radius);
+    let temperature = get_temperature_from_luminosity_and_radius(luminosity, radius);
+    trace_var!(temperature);
+    let spectral_class = brown_dwarf_temperature_to_spectral_class(temperature);
+    trace_var!(spectral_class);
+    let habitable_zone = get_habitable_zone(luminosity, temperature, HabitableZoneModel::Classic);
+    trace_var!(habitable_zone);
+    let satellite_zone = (
+      get_approximate_innermost_orbit(mass),
+      get_approximate_outermost_orbit(mass),
+    );
+    trace_var!(satellite_zone);
+    let frost_line = star_luminosity_to_frost_line(luminosity);
+    trace_var!(frost_line);
+    let result = Self {
+      name,
+      mass,
+      radius,
+      current_age,
+      temperature,
+      luminosity,
+      spectral_class,
+      habitable_zone,
+      satellite_zone,
+      frost_line,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Evolve this brown dwarf forward by the given time, in Gyr.
+  ///
+  /// It has no fuel to speak of, so it just cools and fades.
+  #[named]
+  pub fn evolve(&self, delta: f64) -> Self {
+    trace_enter!();
+    trace_var!(delta);
+    let current_age = (self.current_age + delta).max(0.0);
+    trace_var!(current_age);
+    let result = Self::from_mass_and_age(self.name.clone(), self.mass, current_age);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this brown dwarf is capable of supporting conventional
+  /// life.
+  ///
+  /// It never is; the habitable zone is a sliver hugging the dwarf, and it
+  /// keeps shrinking inward as the dwarf fades, so nothing stays in it long.
+  #[named]
+  pub fn check_habitable(&self) -> Result<(), Error> {
+    trace_enter!();
+    let result = Err(Error::TooDimToSupportLife);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this brown dwarf is capable of supporting conventional
+  /// life.
+  #[named]
+  pub fn is_habitable(&self) -> bool {
+    trace_enter!();
+    let result = match self.check_habitable() {
+      Ok(()) => true,
+      Err(_) => false,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_from_mass_and_age() {
+    init();
+    trace_enter!();
+    let young = BrownDwarf::from_mass_and_age("Young".to_string(), 0.07, 0.1);
+    print_var!(young);
+    let old = BrownDwarf::from_mass_and_age("Old".to_string(), 0.02, 10.0);
+    print_var!(old);
+    assert!(old.temperature < young.temperature);
+    assert!(young.spectral_class.starts_with('M') || young.spectral_class.starts_with('L'));
+    assert!(old.spectral_class.starts_with('Y'));
+    assert!(young.habitable_zone.1 < 0.1);
+    assert!(!young.is_habitable());
+    trace_exit!();
+  }
+}
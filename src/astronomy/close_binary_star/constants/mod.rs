@@ -72,3 +72,13 @@ pub const MINIMUM_HABITABLE_INDIVIDUAL_MASS: f64 = 0.1;
 /// Above this is probably too high to support conventional life.
 /// Measured in Msol, or solar mass equivalents.
 pub const MAXIMUM_HABITABLE_INDIVIDUAL_MASS: f64 = 1.25;
+
+/// The mean of the log of the orbital period of binary stars, in days.
+///
+/// This is for solar-type stars in the solar neighborhood (Raghavan et al.
+/// 2010); the peak is at about 300 years, so close binaries are the tail.
+pub const LOG_PERIOD_MEAN: f64 = 5.03;
+
+/// The standard deviation of the log of the orbital period of binary stars,
+/// in days.
+pub const LOG_PERIOD_STANDARD_DEVIATION: f64 = 2.28;
@@ -0,0 +1,167 @@
+use std::ops::Range;
+
+use crate::astronomy::close_binary_star::constants::*;
+use crate::astronomy::close_binary_star::constraints::Constraints;
+use crate::astronomy::close_binary_star::math::separation::SeparationDistribution;
+use crate::astronomy::star::constraints::Constraints as StarConstraints;
+use crate::math::range::check_range;
+use crate::math::range::error::Error;
+
+/// A fluent builder for close binary star constraints.
+///
+/// Nothing is checked until `build()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Builder {
+  constraints: Constraints,
+}
+
+impl Builder {
+  /// Restrict the combined mass of the stars, in Msol.
+  pub fn combined_mass_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_combined_mass = Some(range.start);
+    self.constraints.maximum_combined_mass = Some(range.end);
+    self
+  }
+
+  /// Restrict the mass of each star, in Msol.
+  pub fn individual_mass_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_individual_mass = Some(range.start);
+    self.constraints.maximum_individual_mass = Some(range.end);
+    self
+  }
+
+  /// Restrict the average separation of the stars, in AU.
+  pub fn separation_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_average_separation = Some(range.start);
+    self.constraints.maximum_average_separation = Some(range.end);
+    self
+  }
+
+  /// Draw the separation of the stars this way.
+  pub fn separation_distribution(mut self, separation_distribution: SeparationDistribution) -> Self {
+    self.constraints.separation_distribution = Some(separation_distribution);
+    self
+  }
+
+  /// Restrict the orbital eccentricity.
+  pub fn eccentricity_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_orbital_eccentricity = Some(range.start);
+    self.constraints.maximum_orbital_eccentricity = Some(range.end);
+    self
+  }
+
+  /// Restrict the age of the stars, in Gyr.
+  pub fn age_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_age = Some(range.start);
+    self.constraints.maximum_age = Some(range.end);
+    self
+  }
+
+  /// Use these constraints for each of the stars.
+  pub fn star_constraints(mut self, star_constraints: StarConstraints) -> Self {
+    self.constraints.star_constraints = Some(star_constraints);
+    self
+  }
+
+  /// Ensure the binary is habitable.
+  ///
+  /// Anything not already given is filled in from `Constraints::habitable()`.
+  pub fn habitable(mut self) -> Self {
+    let habitable = Constraints::habitable();
+    let constraints = &mut self.constraints;
+    constraints.minimum_combined_mass = constraints.minimum_combined_mass.or(habitable.minimum_combined_mass);
+    constraints.maximum_combined_mass = constraints.maximum_combined_mass.or(habitable.maximum_combined_mass);
+    constraints.minimum_individual_mass = constraints
+      .minimum_individual_mass
+      .or(habitable.minimum_individual_mass);
+    constraints.maximum_individual_mass = constraints
+      .maximum_individual_mass
+      .or(habitable.maximum_individual_mass);
+    constraints.minimum_average_separation = constraints
+      .minimum_average_separation
+      .or(habitable.minimum_average_separation);
+    constraints.maximum_average_separation = constraints
+      .maximum_average_separation
+      .or(habitable.maximum_average_separation);
+    constraints.minimum_orbital_eccentricity = constraints
+      .minimum_orbital_eccentricity
+      .or(habitable.minimum_orbital_eccentricity);
+    constraints.maximum_orbital_eccentricity = constraints
+      .maximum_orbital_eccentricity
+      .or(habitable.maximum_orbital_eccentricity);
+    constraints.minimum_age = constraints.minimum_age.or(habitable.minimum_age);
+    constraints.maximum_age = constraints.maximum_age.or(habitable.maximum_age);
+    constraints.star_constraints = constraints.star_constraints.or(habitable.star_constraints);
+    constraints.enforce_habitability = Some(true);
+    self
+  }
+
+  /// Check the constraints and return them.
+  #[named]
+  pub fn build(self) -> Result<Constraints, Error> {
+    trace_enter!();
+    let constraints = self.constraints;
+    trace_var!(constraints);
+    check_range(
+      "combined mass",
+      constraints.minimum_combined_mass,
+      constraints.maximum_combined_mass,
+      (MINIMUM_COMBINED_MASS, MAXIMUM_COMBINED_MASS),
+    )?;
+    check_range(
+      "individual mass",
+      constraints.minimum_individual_mass,
+      constraints.maximum_individual_mass,
+      (MINIMUM_INDIVIDUAL_MASS, MAXIMUM_INDIVIDUAL_MASS),
+    )?;
+    check_range(
+      "average separation",
+      constraints.minimum_average_separation,
+      constraints.maximum_average_separation,
+      (MINIMUM_SEPARATION, f64::INFINITY),
+    )?;
+    check_range(
+      "orbital eccentricity",
+      constraints.minimum_orbital_eccentricity,
+      constraints.maximum_orbital_eccentricity,
+      (0.0, 1.0),
+    )?;
+    check_range(
+      "age",
+      constraints.minimum_age,
+      constraints.maximum_age,
+      (0.0, f64::INFINITY),
+    )?;
+    trace_exit!();
+    Ok(constraints)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_build() {
+    init();
+    trace_enter!();
+    let constraints = Constraints::builder()
+      .separation_range(0.2..0.3)
+      .habitable()
+      .build()
+      .unwrap();
+    print_var!(constraints);
+    assert_eq!(constraints.minimum_average_separation, Some(0.2));
+    assert_eq!(constraints.maximum_average_separation, Some(0.3));
+    assert_eq!(constraints.minimum_age, Some(MINIMUM_HABITABLE_AGE));
+    assert_eq!(constraints.enforce_habitability, Some(true));
+    assert_eq!(
+      Constraints::builder().eccentricity_range(0.5..1.5).build(),
+      Err(Error::RangeAboveBound("orbital eccentricity"))
+    );
+    trace_exit!();
+  }
+}
@@ -3,8 +3,13 @@ use std::default::Default;
 
 use crate::astronomy::close_binary_star::constants::*;
 use crate::astronomy::close_binary_star::error::Error;
+use crate::astronomy::close_binary_star::math::separation::SeparationDistribution;
 use crate::astronomy::close_binary_star::CloseBinaryStar;
 use crate::astronomy::star::constraints::Constraints as StarConstraints;
+use crate::generator::Generator;
+use crate::merge::Merge;
+pub mod builder;
+use builder::Builder;
 
 /// Constraints for creating a binary star.
 ///
@@ -31,6 +36,8 @@ pub struct Constraints {
   pub minimum_average_separation: Option<f64>,
   /// The maximum separation between the stars, in Msol.
   pub maximum_average_separation: Option<f64>,
+  /// How the separation between the stars is drawn.
+  pub separation_distribution: Option<SeparationDistribution>,
   /// The minimum orbital eccentricity.
   pub minimum_orbital_eccentricity: Option<f64>,
   /// The maximum orbital_eccentricity.
@@ -40,12 +47,17 @@ pub struct Constraints {
   /// The maximum age of the stars, in Gyr.
   pub maximum_age: Option<f64>,
   /// Enforce habitability.
-  pub enforce_habitability: bool,
+  pub enforce_habitability: Option<bool>,
   /// Star constraints.
   pub star_constraints: Option<StarConstraints>,
 }
 
 impl Constraints {
+  /// Start building constraints fluently.
+  pub fn builder() -> Builder {
+    Builder::default()
+  }
+
   /// Generate a habitable binary star.
   #[named]
   pub fn habitable() -> Self {
@@ -58,7 +70,7 @@ impl Constraints {
     let maximum_orbital_eccentricity = Some(MAXIMUM_HABITABLE_ORBITAL_ECCENTRICITY);
     let maximum_average_separation = Some(MAXIMUM_HABITABLE_AVERAGE_SEPARATION);
     let minimum_age = Some(MINIMUM_HABITABLE_AGE);
-    let enforce_habitability = true;
+    let enforce_habitability = Some(true);
     let star_constraints = Some(StarConstraints::habitable());
     let result = Self {
       minimum_combined_mass,
@@ -102,16 +114,18 @@ impl Constraints {
     trace_var!(minimum_average_separation);
     let maximum_average_separation = self.maximum_average_separation.unwrap_or(MAXIMUM_AVERAGE_SEPARATION);
     trace_var!(maximum_average_separation);
-    let orbital_eccentricity = rng.gen_range(minimum_orbital_eccentricity..maximum_orbital_eccentricity);
+    let orbital_eccentricity = rng.gen_range(minimum_orbital_eccentricity..=maximum_orbital_eccentricity);
     trace_var!(orbital_eccentricity);
-    let average_separation = rng.gen_range(minimum_average_separation..maximum_average_separation);
-    trace_var!(average_separation);
+    let separation_distribution = self.separation_distribution.unwrap_or_default();
+    trace_var!(separation_distribution);
     let combined_mass;
     let primary_mass;
     let secondary_mass;
     let mut primary_constraints;
     let mut secondary_constraints;
-    if self.enforce_habitability {
+    let enforce_habitability = self.enforce_habitability.unwrap_or(false);
+    trace_var!(enforce_habitability);
+    if enforce_habitability {
       let bare_minimum =
         (1.1 * (4.0 * maximum_average_separation * (1.0 + orbital_eccentricity)).powf(2.0)).powf(1.0 / 4.0);
       if minimum_combined_mass < bare_minimum {
@@ -124,10 +138,10 @@ impl Constraints {
       secondary_constraints = self.star_constraints.unwrap_or(StarConstraints::default());
     }
     let (primary, secondary) = {
-      combined_mass = rng.gen_range(minimum_combined_mass..maximum_combined_mass);
+      combined_mass = rng.gen_range(minimum_combined_mass..=maximum_combined_mass);
       let half = combined_mass / 2.0;
       let mut top = combined_mass - MINIMUM_HABITABLE_INDIVIDUAL_MASS;
-      if self.enforce_habitability && top > maximum_individual_mass {
+      if enforce_habitability && top > maximum_individual_mass {
         top = maximum_individual_mass;
       }
       primary_mass = rng.gen_range(half..top);
@@ -136,23 +150,33 @@ impl Constraints {
       primary_constraints.maximum_mass = Some(1.001 * primary_mass);
       secondary_constraints.minimum_mass = Some(0.999 * secondary_mass);
       secondary_constraints.maximum_mass = Some(1.001 * secondary_mass);
-      let mut primary = primary_constraints.generate(rng)?;
+      let primary = primary_constraints.generate(rng)?;
       let mut secondary = secondary_constraints.generate(rng)?;
-      let minimum_age = match self.enforce_habitability {
-        true => MINIMUM_HABITABLE_AGE,
-        false => 0.1 * primary.life_expectancy,
-      };
-      trace_var!(minimum_age);
-      let maximum_age = 0.9 * primary.life_expectancy;
+      // They formed from the same cloud, so they share the primary's [Fe/H].
+      secondary.metallicity = primary.metallicity;
+      let latest_age = 0.9 * primary.life_expectancy;
+      trace_var!(latest_age);
+      let maximum_age = self.maximum_age.map_or(latest_age, |age| age.min(latest_age));
       trace_var!(maximum_age);
-      let current_age = rng.gen_range(minimum_age..maximum_age);
+      let minimum_age = match enforce_habitability {
+        true => self.minimum_age.unwrap_or(MINIMUM_HABITABLE_AGE),
+        false => self.minimum_age.unwrap_or(0.1 * primary.life_expectancy),
+      }
+      .min(maximum_age);
+      trace_var!(minimum_age);
+      let current_age = rng.gen_range(minimum_age..=maximum_age);
       trace_var!(current_age);
-      primary.current_age = current_age;
-      secondary.current_age = current_age;
-      (primary, secondary)
+      (primary.at_age(current_age), secondary.at_age(current_age))
     };
     trace_var!(primary);
     trace_var!(secondary);
+    let average_separation = separation_distribution.get_random_separation(
+      rng,
+      combined_mass,
+      minimum_average_separation,
+      maximum_average_separation,
+    );
+    trace_var!(average_separation);
     let result = CloseBinaryStar::from_stars(rng, primary, secondary, average_separation, orbital_eccentricity)?;
     trace_var!(result);
     trace_exit!();
@@ -160,6 +184,19 @@ impl Constraints {
   }
 }
 
+impl Generator for Constraints {
+  type Output = CloseBinaryStar;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<CloseBinaryStar, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
 impl Default for Constraints {
   /// No constraints, just let it all hang out.
   #[named]
@@ -171,11 +208,12 @@ impl Default for Constraints {
     let maximum_individual_mass = Some(MAXIMUM_INDIVIDUAL_MASS);
     let minimum_average_separation = None;
     let maximum_average_separation = None;
+    let separation_distribution = None;
     let minimum_orbital_eccentricity = Some(MINIMUM_ORBITAL_ECCENTRICITY);
     let maximum_orbital_eccentricity = Some(MAXIMUM_ORBITAL_ECCENTRICITY);
     let minimum_age = None;
     let maximum_age = None;
-    let enforce_habitability = false;
+    let enforce_habitability = None;
     let star_constraints = None;
     let result = Self {
       minimum_combined_mass,
@@ -184,6 +222,7 @@ impl Default for Constraints {
       maximum_individual_mass,
       minimum_average_separation,
       maximum_average_separation,
+      separation_distribution,
       minimum_orbital_eccentricity,
       maximum_orbital_eccentricity,
       minimum_age,
@@ -197,6 +236,30 @@ impl Default for Constraints {
   }
 }
 
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_combined_mass: overrides.minimum_combined_mass.or(self.minimum_combined_mass),
+      maximum_combined_mass: overrides.maximum_combined_mass.or(self.maximum_combined_mass),
+      minimum_individual_mass: overrides.minimum_individual_mass.or(self.minimum_individual_mass),
+      maximum_individual_mass: overrides.maximum_individual_mass.or(self.maximum_individual_mass),
+      minimum_average_separation: overrides.minimum_average_separation.or(self.minimum_average_separation),
+      maximum_average_separation: overrides.maximum_average_separation.or(self.maximum_average_separation),
+      separation_distribution: overrides.separation_distribution.or(self.separation_distribution),
+      minimum_orbital_eccentricity: overrides
+        .minimum_orbital_eccentricity
+        .or(self.minimum_orbital_eccentricity),
+      maximum_orbital_eccentricity: overrides
+        .maximum_orbital_eccentricity
+        .or(self.maximum_orbital_eccentricity),
+      minimum_age: overrides.minimum_age.or(self.minimum_age),
+      maximum_age: overrides.maximum_age.or(self.maximum_age),
+      enforce_habitability: overrides.enforce_habitability.or(self.enforce_habitability),
+      star_constraints: self.star_constraints.merged_with(&overrides.star_constraints),
+    }
+  }
+}
+
 #[cfg(test)]
 pub mod test {
 
@@ -233,6 +296,45 @@ pub mod test {
     Ok(())
   }
 
+  #[named]
+  #[test]
+  pub fn test_age_range() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints::builder().age_range(2.0..3.0).habitable().build().unwrap();
+    let binary = &constraints.generate(&mut rng)?;
+    print_var!(binary);
+    assert!((2.0..=3.0).contains(&binary.primary.current_age));
+    assert_eq!(binary.primary.current_age, binary.secondary.current_age);
+    assert_eq!(binary.primary.metallicity, binary.secondary.metallicity);
+    let constraints = Constraints::builder().age_range(50.0..60.0).build().unwrap();
+    let binary = &constraints.generate(&mut rng)?;
+    print_var!(binary);
+    assert!(binary.primary.current_age <= 0.9 * binary.primary.life_expectancy);
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_log_normal_period() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints {
+      separation_distribution: Some(SeparationDistribution::LogNormalPeriod),
+      ..Constraints::habitable()
+    };
+    let binary = &constraints.generate(&mut rng)?;
+    print_var!(binary);
+    assert!(binary.orbit.semi_major_axis <= MAXIMUM_HABITABLE_AVERAGE_SEPARATION);
+    trace_exit!();
+    Ok(())
+  }
+
   #[named]
   #[test]
   pub fn test_default_bulk() -> Result<(), Error> {
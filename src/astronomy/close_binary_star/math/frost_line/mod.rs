@@ -1,3 +1,4 @@
+use crate::astronomy::star::math::frost_line::star_luminosity_to_frost_line;
 use crate::astronomy::star::Star;
 
 /// Calculate the frost line of a close binary system.
@@ -6,7 +7,7 @@ pub fn get_frost_line(star1: &Star, star2: &Star) -> f64 {
   trace_enter!();
   let luminosity = star1.luminosity + star2.luminosity;
   trace_var!(luminosity);
-  let result = 4.85 * luminosity.sqrt();
+  let result = star_luminosity_to_frost_line(luminosity);
   trace_var!(result);
   trace_exit!();
   result
@@ -1,16 +1,18 @@
+use crate::astronomy::star::math::habitable_zone::get_habitable_zone as get_luminosity_habitable_zone;
 use crate::astronomy::star::Star;
 
 /// Calculate the habitable zone of a close binary system.
+///
+/// This is drawn with the primary star's habitable zone model.
 #[named]
 pub fn get_habitable_zone(star1: &Star, star2: &Star) -> (f64, f64) {
   trace_enter!();
   let luminosity = star1.luminosity + star2.luminosity;
   trace_var!(luminosity);
-  let inner_bound = (luminosity / 1.1).sqrt();
-  trace_var!(inner_bound);
-  let outer_bound = (luminosity / 0.53).sqrt();
-  trace_var!(outer_bound);
-  let result = (inner_bound, outer_bound);
+  // Weigh each star's temperature by how much of the light it contributes.
+  let temperature = (star1.temperature * star1.luminosity + star2.temperature * star2.luminosity) / luminosity;
+  trace_var!(temperature);
+  let result = get_luminosity_habitable_zone(luminosity, temperature, star1.habitable_zone_model);
   trace_var!(result);
   trace_exit!();
   result
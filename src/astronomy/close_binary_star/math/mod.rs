@@ -2,3 +2,4 @@ pub mod barycenter;
 pub mod frost_line;
 pub mod habitable_zone;
 pub mod separation;
+pub mod stability;
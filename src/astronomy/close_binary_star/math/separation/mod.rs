@@ -1,3 +1,90 @@
+use rand::prelude::*;
+use serde::Serialize;
+
+use crate::astronomy::close_binary_star::constants::{LOG_PERIOD_MEAN, LOG_PERIOD_STANDARD_DEVIATION};
+use crate::astronomy::orbit::constants::DAYS_PER_YEAR;
+
+/// How the average separation of a binary is drawn.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
+pub enum SeparationDistribution {
+  /// Uniformly between the minimum and maximum separation.
+  #[default]
+  Uniform,
+  /// Log-normally in orbital period (Duquennoy & Mayor 1991; Raghavan et al.
+  /// 2010), cut off at the minimum and maximum separation.
+  LogNormalPeriod,
+}
+
+impl SeparationDistribution {
+  /// Get a random average separation, in AU, for stars with the specified
+  /// combined mass, in Msol, between the given bounds, also in AU.
+  #[named]
+  pub fn get_random_separation<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    combined_mass: f64,
+    minimum_separation: f64,
+    maximum_separation: f64,
+  ) -> f64 {
+    trace_enter!();
+    trace_var!(combined_mass);
+    trace_var!(minimum_separation);
+    trace_var!(maximum_separation);
+    use SeparationDistribution::*;
+    let result = match self {
+      Uniform => rng.gen_range(minimum_separation..=maximum_separation),
+      LogNormalPeriod => {
+        let minimum_log_period = get_period(minimum_separation, combined_mass).log10();
+        let maximum_log_period = get_period(maximum_separation, combined_mass).log10();
+        // Sample uniformly in the log of the period and keep each draw in
+        // proportion to the density there; the mode, clamped to the range,
+        // is as dense as it gets.
+        let get_density = |log_period: f64| {
+          (-(log_period - LOG_PERIOD_MEAN).powf(2.0) / (2.0 * LOG_PERIOD_STANDARD_DEVIATION.powf(2.0))).exp()
+        };
+        let maximum_density = get_density(LOG_PERIOD_MEAN.clamp(minimum_log_period, maximum_log_period));
+        let log_period = loop {
+          let log_period = rng.gen_range(minimum_log_period..=maximum_log_period);
+          if rng.gen_range(0.0..maximum_density) <= get_density(log_period) {
+            break log_period;
+          }
+        };
+        trace_var!(log_period);
+        get_separation(10.0_f64.powf(log_period), combined_mass).clamp(minimum_separation, maximum_separation)
+      },
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+/// Get the orbital period, in days, of stars with the specified average
+/// separation, in AU, and combined mass, in Msol.
+#[named]
+pub fn get_period(average_separation: f64, combined_mass: f64) -> f64 {
+  trace_enter!();
+  trace_var!(average_separation);
+  trace_var!(combined_mass);
+  let result = (average_separation.powf(3.0) / combined_mass).sqrt() * DAYS_PER_YEAR;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the average separation, in AU, of stars with the specified orbital
+/// period, in days, and combined mass, in Msol.
+#[named]
+pub fn get_separation(period: f64, combined_mass: f64) -> f64 {
+  trace_enter!();
+  trace_var!(period);
+  trace_var!(combined_mass);
+  let result = (combined_mass * (period / DAYS_PER_YEAR).powf(2.0)).cbrt();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
 #[named]
 pub fn get_minimum_separation(min_distances: (f64, f64)) -> f64 {
   trace_enter!();
@@ -17,3 +104,42 @@ pub fn get_maximum_separation(max_distances: (f64, f64)) -> f64 {
   trace_exit!();
   result
 }
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_period() {
+    init();
+    trace_enter!();
+    // Earth and the Sun, more or less.
+    assert_approx_eq!(get_period(1.0, 1.0), DAYS_PER_YEAR);
+    assert_approx_eq!(get_separation(get_period(0.3, 1.7), 1.7), 0.3);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_random_separation() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let separations = (0..1_000)
+      .map(|_| SeparationDistribution::LogNormalPeriod.get_random_separation(&mut rng, 1.0, 0.1, 6.0))
+      .collect::<Vec<_>>();
+    assert!(separations.iter().all(|separation| (0.1..=6.0).contains(separation)));
+    // A uniform draw would put about 15% of these inside 1 AU; going by
+    // period, about 40% land there.
+    let tight = separations.iter().filter(|&&separation| separation < 1.0).count();
+    print_var!(tight);
+    assert!(tight > 300);
+    trace_exit!();
+  }
+}
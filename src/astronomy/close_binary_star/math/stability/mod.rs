@@ -0,0 +1,72 @@
+/// Calculate the critical semi-major axis for an S-type orbit, i.e. an orbit
+/// around one component of a binary.
+///
+/// This is the fit from Holman & Wiegert (1999); orbits wider than this are
+/// disrupted by the other component.
+///
+/// * `separation` - Average separation of the binary, in AU.
+/// * `mass_ratio` - Mass of the _other_ component over the combined mass.
+/// * `eccentricity` - Orbital eccentricity of the binary.
+///
+/// Returns distance in AU from the component being orbited.
+#[named]
+pub fn get_s_type_critical_orbit(separation: f64, mass_ratio: f64, eccentricity: f64) -> f64 {
+  trace_enter!();
+  trace_var!(separation);
+  trace_var!(mass_ratio);
+  trace_var!(eccentricity);
+  let (mu, e) = (mass_ratio, eccentricity);
+  let result =
+    separation * (0.464 - 0.380 * mu - 0.631 * e + 0.586 * mu * e + 0.150 * e.powf(2.0) - 0.198 * mu * e.powf(2.0));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the critical semi-major axis for a P-type orbit, i.e. a
+/// circumbinary orbit.
+///
+/// This is the fit from Holman & Wiegert (1999); orbits tighter than this
+/// are disrupted by the binary.
+///
+/// * `separation` - Average separation of the binary, in AU.
+/// * `mass_ratio` - Mass of the secondary over the combined mass.
+/// * `eccentricity` - Orbital eccentricity of the binary.
+///
+/// Returns distance in AU from the barycenter.
+#[named]
+pub fn get_p_type_critical_orbit(separation: f64, mass_ratio: f64, eccentricity: f64) -> f64 {
+  trace_enter!();
+  trace_var!(separation);
+  trace_var!(mass_ratio);
+  trace_var!(eccentricity);
+  let (mu, e) = (mass_ratio, eccentricity);
+  let result = separation
+    * (1.60 + 5.10 * e - 2.22 * e.powf(2.0) + 4.12 * mu - 4.27 * e * mu - 5.09 * mu.powf(2.0)
+      + 4.61 * e.powf(2.0) * mu.powf(2.0));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_critical_orbits() {
+    init();
+    trace_enter!();
+    // Equal masses on a circular orbit.
+    assert_approx_eq!(get_s_type_critical_orbit(1.0, 0.5, 0.0), 0.274, 1e-3f64);
+    assert_approx_eq!(get_p_type_critical_orbit(1.0, 0.5, 0.0), 2.388, 1e-3f64);
+    // Alpha Centauri A and B: about 23.4 AU apart, e = 0.52.
+    let alpha_centauri_a = get_s_type_critical_orbit(23.4, 0.9 / 2.0, 0.52);
+    print_var!(alpha_centauri_a);
+    assert_approx_eq!(alpha_centauri_a, 2.8, 0.3f64);
+    trace_exit!();
+  }
+}
@@ -1,5 +1,7 @@
 use rand::prelude::*;
+use serde::Serialize;
 
+use crate::astronomy::orbit::OrbitalElements;
 use crate::astronomy::star::error::Error as StarError;
 use crate::astronomy::star::Star;
 
@@ -8,7 +10,7 @@ use constants::*;
 pub mod constraints;
 pub mod error;
 use error::Error;
-pub mod math;
+pub(crate) mod math;
 use math::barycenter::get_average_distances_from_barycenter;
 use math::barycenter::get_maximum_distances_from_barycenter;
 use math::barycenter::get_minimum_distances_from_barycenter;
@@ -16,6 +18,9 @@ use math::frost_line::get_frost_line;
 use math::habitable_zone::get_habitable_zone;
 use math::separation::get_maximum_separation;
 use math::separation::get_minimum_separation;
+use math::separation::get_period;
+pub use math::separation::SeparationDistribution;
+use math::stability::{get_p_type_critical_orbit, get_s_type_critical_orbit};
 
 /// A `CloseBinaryStar` is a system of two `Star` objects.
 ///
@@ -25,34 +30,47 @@ use math::separation::get_minimum_separation;
 /// `CloseBinaryStar`, but can only be in an orbit around one member of a
 /// `DistantBinaryStar`.  As a result, we handle `DistantBinaryStar` objects
 /// with a different class.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct CloseBinaryStar {
   /// The primary star is the one with greater mass.
   pub primary: Star,
   /// The secondary star has less mass.
   pub secondary: Star,
-  /// Average separation of the binary components, in AU.
-  pub average_separation: f64,
-  /// Orbital eccentricity of the components.
-  pub orbital_eccentricity: f64,
+  /// The orbit of the secondary around the primary, in AU.
+  ///
+  /// The semi-major axis is the average separation of the components.
+  pub orbit: OrbitalElements,
+  /// The orbital period, in days.
+  pub orbital_period: f64,
   /// Average distance from barycenter of the components.
   pub average_distances_from_barycenter: (f64, f64),
-  /// Minimum distance from barycenter of the components.
+  /// Minimum distance from barycenter of the components, reached at
+  /// periapsis.
   pub minimum_distances_from_barycenter: (f64, f64),
-  /// Maximum distance from barycenter of the components.
+  /// Maximum distance from barycenter of the components, reached at
+  /// apoapsis.
   pub maximum_distances_from_barycenter: (f64, f64),
-  /// Minimum separation of the components, in AU.
-  pub minimum_separation: f64,
-  /// Maximum separation of the components, in AU.
-  pub maximum_separation: f64,
   /// Area in which nothing can exist.
   pub forbidden_zone: (f64, f64),
   /// Area in which nothing _habitable_ can exist.
   pub danger_zone: (f64, f64),
   /// Habitable zone.
   pub habitable_zone: (f64, f64),
-  /// Satellite bounds.
-  pub satellite_zone: (f64, f64),
+  /// The innermost stable circumbinary (P-type) orbit, in AU from the
+  /// barycenter.
+  ///
+  /// Anything tighter is flung out or into the stars within a few thousand
+  /// years.
+  pub p_type_critical_orbit: f64,
+  /// Satellite bounds for circumbinary orbits, if there's any room for them.
+  ///
+  /// A light, wide, eccentric pair can keep clear everything out to where
+  /// the stars' gravity gives out.
+  pub satellite_zone: Option<(f64, f64)>,
+  /// Satellite bounds for orbits around the primary alone, if there's room.
+  pub primary_satellite_zone: Option<(f64, f64)>,
+  /// Satellite bounds for orbits around the secondary alone, if there's room.
+  pub secondary_satellite_zone: Option<(f64, f64)>,
   /// The frost line.
   pub frost_line: f64,
   /// Whether the habitable zone is contained within the forbidden zone.
@@ -65,16 +83,34 @@ impl CloseBinaryStar {
   /// Create from a pair of stars, average separation, and orbital eccentricity.
   #[named]
   pub fn from_stars<R: Rng + ?Sized>(
-    _rng: &mut R,
+    rng: &mut R,
     primary: Star,
     secondary: Star,
     average_separation: f64,
     orbital_eccentricity: f64,
   ) -> Result<Self, Error> {
+    trace_enter!();
+    trace_var!(average_separation);
+    trace_var!(orbital_eccentricity);
+    let orbit = OrbitalElements::new(average_separation, orbital_eccentricity).with_random_orientation(rng);
+    trace_var!(orbit);
+    let result = Self::from_stars_and_orbit(primary, secondary, orbit);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Create from a pair of stars and the orbit of the secondary around the
+  /// primary.
+  #[named]
+  pub fn from_stars_and_orbit(primary: Star, secondary: Star, orbit: OrbitalElements) -> Result<Self, Error> {
     trace_enter!();
     trace_var!(primary);
     trace_var!(secondary);
+    trace_var!(orbit);
+    let average_separation = orbit.semi_major_axis;
     trace_var!(average_separation);
+    let orbital_eccentricity = orbit.eccentricity;
     trace_var!(orbital_eccentricity);
     let average_distances_from_barycenter =
       get_average_distances_from_barycenter(average_separation, primary.mass, secondary.mass);
@@ -99,8 +135,35 @@ impl CloseBinaryStar {
     let habitable_zone = get_habitable_zone(&primary, &secondary);
     trace_var!(habitable_zone);
     let combined_mass = primary.mass + secondary.mass;
-    let satellite_zone = (0.1 * combined_mass, 40.0 * combined_mass);
+    let orbital_period = get_period(average_separation, combined_mass);
+    trace_var!(orbital_period);
+    let secondary_mass_ratio = secondary.mass / combined_mass;
+    trace_var!(secondary_mass_ratio);
+    let p_type_critical_orbit =
+      get_p_type_critical_orbit(average_separation, secondary_mass_ratio, orbital_eccentricity);
+    trace_var!(p_type_critical_orbit);
+    let satellite_zone = {
+      let inner = (0.1 * combined_mass).max(p_type_critical_orbit);
+      let outer = 40.0 * combined_mass;
+      match inner < outer {
+        true => Some((inner, outer)),
+        false => None,
+      }
+    };
     trace_var!(satellite_zone);
+    let get_s_type_zone = |star: &Star, mass_ratio: f64| {
+      let outer =
+        get_s_type_critical_orbit(average_separation, mass_ratio, orbital_eccentricity).min(star.satellite_zone.1);
+      let inner = star.satellite_zone.0;
+      match inner < outer {
+        true => Some((inner, outer)),
+        false => None,
+      }
+    };
+    let primary_satellite_zone = get_s_type_zone(&primary, secondary_mass_ratio);
+    trace_var!(primary_satellite_zone);
+    let secondary_satellite_zone = get_s_type_zone(&secondary, 1.0 - secondary_mass_ratio);
+    trace_var!(secondary_satellite_zone);
     let frost_line = get_frost_line(&primary, &secondary);
     trace_var!(frost_line);
     let habitable_zone_is_forbidden = habitable_zone.1 <= forbidden_zone.1;
@@ -110,17 +173,18 @@ impl CloseBinaryStar {
     let result = CloseBinaryStar {
       primary,
       secondary,
-      average_separation,
-      orbital_eccentricity,
+      orbit,
+      orbital_period,
       average_distances_from_barycenter,
       minimum_distances_from_barycenter,
       maximum_distances_from_barycenter,
-      minimum_separation,
-      maximum_separation,
       forbidden_zone,
       danger_zone,
       habitable_zone,
+      p_type_critical_orbit,
       satellite_zone,
+      primary_satellite_zone,
+      secondary_satellite_zone,
       frost_line,
       habitable_zone_is_forbidden,
       habitable_zone_is_dangerous,
@@ -130,6 +194,59 @@ impl CloseBinaryStar {
     Ok(result)
   }
 
+  /// Evolve both stars forward by the given time, in Gyr.
+  ///
+  /// The orbit is left as it is.  Should either star swell or shrink enough,
+  /// the zones around the pair change with it.
+  #[named]
+  pub fn evolve(&self, delta: f64) -> Result<Self, Error> {
+    trace_enter!();
+    trace_var!(delta);
+    let primary = self.primary.evolve(delta);
+    trace_var!(primary);
+    let secondary = self.secondary.evolve(delta);
+    trace_var!(secondary);
+    let result = Self::from_stars_and_orbit(primary, secondary, self.orbit);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the innermost stable circumbinary orbit, in AU from the barycenter.
+  ///
+  /// This is the Holman-Wiegert criterion for P-type orbits; planets are only
+  /// ever placed outside it.
+  #[named]
+  pub fn stable_circumbinary_inner_radius(&self) -> f64 {
+    trace_enter!();
+    let result = self.p_type_critical_orbit;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the distances of the components from the barycenter at periapsis,
+  /// in AU.
+  #[named]
+  pub fn get_periapsis_distances(&self) -> (f64, f64) {
+    trace_enter!();
+    let result = self.minimum_distances_from_barycenter;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the distances of the components from the barycenter at apoapsis,
+  /// in AU.
+  #[named]
+  pub fn get_apoapsis_distances(&self) -> (f64, f64) {
+    trace_enter!();
+    let result = self.maximum_distances_from_barycenter;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Retrieve or calculate the age of the stars.
   ///
   /// Calculated in Gyr.
@@ -232,4 +349,79 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_satellite_zones() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let primary = Star::from_mass(&mut rng, 1.0)?;
+    let secondary = Star::from_mass(&mut rng, 0.5)?;
+    let wide = CloseBinaryStar::from_stars(&mut rng, primary.clone(), secondary.clone(), 20.0, 0.1)?;
+    print_var!(wide);
+    let (wide_inner, _) = wide.satellite_zone.unwrap();
+    assert!(wide_inner > 40.0);
+    assert!(wide.stable_circumbinary_inner_radius() <= wide_inner);
+    let (_, primary_outer) = wide.primary_satellite_zone.unwrap();
+    assert!(primary_outer < 20.0 * 0.5);
+    assert!(wide.secondary_satellite_zone.is_some());
+    let tight = CloseBinaryStar::from_stars(&mut rng, primary, secondary, 0.2, 0.5)?;
+    print_var!(tight);
+    let (_, secondary_outer) = tight.secondary_satellite_zone.unwrap();
+    assert!(secondary_outer < 0.2 * 0.3);
+    // A pair this light, wide, and eccentric clears out everything it could
+    // hold on to.
+    let primary = Star::from_mass(&mut rng, 0.3)?;
+    let secondary = Star::from_mass(&mut rng, 0.2)?;
+    let barren = CloseBinaryStar::from_stars(&mut rng, primary, secondary, 6.0, 0.7)?;
+    print_var!(barren);
+    assert_eq!(barren.satellite_zone, None);
+    assert!(barren.primary_satellite_zone.is_some());
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_orbital_period() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let primary = Star::from_mass(&mut rng, 1.5)?;
+    let secondary = Star::from_mass(&mut rng, 0.5)?;
+    let close_binary_star = CloseBinaryStar::from_stars(&mut rng, primary, secondary, 2.0, 0.5)?;
+    print_var!(close_binary_star);
+    // Two solar masses two AU apart go round once every two years.
+    assert_approx_eq!(close_binary_star.orbital_period, 2.0 * 365.25, 0.01);
+    let (primary_periapsis, secondary_periapsis) = close_binary_star.get_periapsis_distances();
+    let (primary_apoapsis, secondary_apoapsis) = close_binary_star.get_apoapsis_distances();
+    assert_approx_eq!(primary_periapsis + secondary_periapsis, 1.0);
+    assert_approx_eq!(primary_apoapsis + secondary_apoapsis, 3.0);
+    assert_approx_eq!(secondary_apoapsis, 3.0 * primary_apoapsis);
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_evolve() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let primary = Star::from_mass(&mut rng, 1.0)?;
+    let secondary = Star::from_mass(&mut rng, 0.5)?;
+    let close_binary_star = CloseBinaryStar::from_stars(&mut rng, primary, secondary, 1.0, 0.1)?;
+    let evolved = close_binary_star.evolve(1.0)?;
+    print_var!(evolved);
+    assert_eq!(evolved.orbit, close_binary_star.orbit);
+    assert_approx_eq!(evolved.get_current_age(), close_binary_star.get_current_age() + 1.0);
+    assert!(evolved.get_luminosity() > close_binary_star.get_luminosity());
+    assert!(evolved.habitable_zone.1 > close_binary_star.habitable_zone.1);
+    trace_exit!();
+    Ok(())
+  }
 }
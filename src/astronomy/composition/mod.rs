@@ -0,0 +1,134 @@
+use serde::Serialize;
+use std::ops::Range;
+
+/// Below this uncompressed density, a body is mostly ices, in g/cm³.
+pub const MAXIMUM_ICY_DENSITY: f64 = 2.6;
+
+/// Above this uncompressed density, a body is mostly iron, in g/cm³.
+pub const MINIMUM_METAL_RICH_DENSITY: f64 = 4.6;
+
+/// The bulk composition of a solid body, inferred from its density.
+///
+/// Albedo and density aren't independent; a bright icy surface on an
+/// iron-dense body is a contradiction, so anything that picks an albedo
+/// should do so from within the range its composition allows.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum Composition {
+  /// Mostly water ice and other volatiles, like Ganymede or Enceladus.
+  Icy,
+  /// Mostly silicate rock, like the Moon or Io.
+  Rocky,
+  /// A large iron core with a thin mantle, like Mercury.
+  MetalRich,
+}
+
+impl Composition {
+  /// Infer a composition from the uncompressed density, in g/cm³.
+  #[named]
+  pub fn from_density(density: f64) -> Self {
+    trace_enter!();
+    trace_var!(density);
+    use Composition::*;
+    let result = match density {
+      density if density < MAXIMUM_ICY_DENSITY => Icy,
+      density if density < MINIMUM_METAL_RICH_DENSITY => Rocky,
+      _ => MetalRich,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Infer a composition from the bulk density (in g/cm³) and mass (in
+  /// Mearth) of a planet.
+  ///
+  /// Large bodies squeeze themselves; Earth's bulk density is 5.51 but it
+  /// would be closer to 4.05 without the weight of itself on top of it.
+  #[named]
+  pub fn from_compressed_density(density: f64, mass: f64) -> Self {
+    trace_enter!();
+    trace_var!(density);
+    trace_var!(mass);
+    let uncompressed_density = density / (1.0 + 0.36 * mass.sqrt());
+    trace_var!(uncompressed_density);
+    let result = Self::from_density(uncompressed_density);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// The range of Bond albedos consistent with this composition.
+  #[named]
+  pub fn get_albedo_range(&self) -> Range<f64> {
+    trace_enter!();
+    use Composition::*;
+    let result = match self {
+      Icy => 0.40..0.90,
+      Rocky => 0.05..0.35,
+      MetalRich => 0.05..0.15,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// A typical Bond albedo for this composition.
+  #[named]
+  pub fn get_typical_albedo(&self) -> f64 {
+    trace_enter!();
+    let range = self.get_albedo_range();
+    trace_var!(range);
+    let result = (range.start + range.end) / 2.0;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether an albedo is plausible for this composition.
+  #[named]
+  pub fn is_albedo_consistent(&self, albedo: f64) -> bool {
+    trace_enter!();
+    trace_var!(albedo);
+    let result = self.get_albedo_range().contains(&albedo);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_from_density() {
+    init();
+    trace_enter!();
+    // Ganymede
+    assert_eq!(Composition::from_density(1.94), Composition::Icy);
+    // The Moon
+    assert_eq!(Composition::from_density(3.34), Composition::Rocky);
+    // Earth
+    assert_eq!(Composition::from_compressed_density(5.51, 1.0), Composition::Rocky);
+    // Mercury
+    assert_eq!(
+      Composition::from_compressed_density(5.43, 0.055),
+      Composition::MetalRich
+    );
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_is_albedo_consistent() {
+    init();
+    trace_enter!();
+    assert!(Composition::Icy.is_albedo_consistent(0.67));
+    assert!(!Composition::MetalRich.is_albedo_consistent(0.67));
+    assert!(Composition::Rocky.is_albedo_consistent(Composition::Rocky.get_typical_albedo()));
+    trace_exit!();
+  }
+}
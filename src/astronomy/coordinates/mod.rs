@@ -0,0 +1,233 @@
+use serde::Serialize;
+
+/// The rotation from equatorial (J2000) to galactic Cartesian coordinates.
+///
+/// Rows are the galactic axes, expressed in equatorial coordinates; the
+/// transpose rotates the other way.
+pub const EQUATORIAL_TO_GALACTIC: [[f64; 3]; 3] = [
+  [-0.054_875_560_4, -0.873_437_090_2, -0.483_835_015_5],
+  [0.494_109_427_9, -0.444_829_630_0, 0.746_982_244_5],
+  [-0.867_666_149_0, -0.198_076_373_4, 0.455_983_776_2],
+];
+
+/// A position relative to the origin, in Ly.
+///
+/// The axes are galactic: x points toward the galactic center, y in the
+/// direction the disk rotates, and z toward the north galactic pole.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct CartesianCoordinates {
+  pub x: f64,
+  pub y: f64,
+  pub z: f64,
+}
+
+/// A position on the sky in galactic coordinates, with a distance.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct GalacticCoordinates {
+  /// Galactic longitude, l, in degrees from 0 to 360, measured from the
+  /// galactic center.
+  pub longitude: f64,
+  /// Galactic latitude, b, in degrees from -90 to 90.
+  pub latitude: f64,
+  /// The distance, in Ly.
+  pub distance: f64,
+}
+
+/// A position on the sky in equatorial (J2000) coordinates, with a distance.
+///
+/// This is the sky as divided up by Earth's equator, which makes it useful
+/// for comparison, if not for much else on other worlds.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct EquatorialCoordinates {
+  /// Right ascension, α, in degrees from 0 to 360.
+  pub right_ascension: f64,
+  /// Declination, δ, in degrees from -90 to 90.
+  pub declination: f64,
+  /// The distance, in Ly.
+  pub distance: f64,
+}
+
+impl CartesianCoordinates {
+  /// Get the distance from the origin, in Ly.
+  #[named]
+  pub fn get_distance(&self) -> f64 {
+    trace_enter!();
+    let result = (self.x.powf(2.0) + self.y.powf(2.0) + self.z.powf(2.0)).sqrt();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Convert to galactic coordinates.
+  #[named]
+  pub fn to_galactic(&self) -> GalacticCoordinates {
+    trace_enter!();
+    let (longitude, latitude, distance) = to_spherical(self.x, self.y, self.z);
+    let result = GalacticCoordinates {
+      longitude,
+      latitude,
+      distance,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Convert to equatorial coordinates.
+  #[named]
+  pub fn to_equatorial(&self) -> EquatorialCoordinates {
+    trace_enter!();
+    let m = EQUATORIAL_TO_GALACTIC;
+    let x = m[0][0] * self.x + m[1][0] * self.y + m[2][0] * self.z;
+    let y = m[0][1] * self.x + m[1][1] * self.y + m[2][1] * self.z;
+    let z = m[0][2] * self.x + m[1][2] * self.y + m[2][2] * self.z;
+    let (right_ascension, declination, distance) = to_spherical(x, y, z);
+    let result = EquatorialCoordinates {
+      right_ascension,
+      declination,
+      distance,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl From<(f64, f64, f64)> for CartesianCoordinates {
+  fn from((x, y, z): (f64, f64, f64)) -> Self {
+    Self { x, y, z }
+  }
+}
+
+impl GalacticCoordinates {
+  /// Convert to Cartesian coordinates.
+  #[named]
+  pub fn to_cartesian(&self) -> CartesianCoordinates {
+    trace_enter!();
+    let (x, y, z) = from_spherical(self.longitude, self.latitude, self.distance);
+    let result = CartesianCoordinates { x, y, z };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Convert to equatorial coordinates.
+  #[named]
+  pub fn to_equatorial(&self) -> EquatorialCoordinates {
+    trace_enter!();
+    let result = self.to_cartesian().to_equatorial();
+    trace_exit!();
+    result
+  }
+}
+
+impl EquatorialCoordinates {
+  /// Convert to Cartesian coordinates.
+  #[named]
+  pub fn to_cartesian(&self) -> CartesianCoordinates {
+    trace_enter!();
+    let (x, y, z) = from_spherical(self.right_ascension, self.declination, self.distance);
+    let m = EQUATORIAL_TO_GALACTIC;
+    let result = CartesianCoordinates {
+      x: m[0][0] * x + m[0][1] * y + m[0][2] * z,
+      y: m[1][0] * x + m[1][1] * y + m[1][2] * z,
+      z: m[2][0] * x + m[2][1] * y + m[2][2] * z,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Convert to galactic coordinates.
+  #[named]
+  pub fn to_galactic(&self) -> GalacticCoordinates {
+    trace_enter!();
+    let result = self.to_cartesian().to_galactic();
+    trace_exit!();
+    result
+  }
+}
+
+/// Get the longitude and latitude (in degrees) and distance of a point.
+fn to_spherical(x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+  let distance = (x.powf(2.0) + y.powf(2.0) + z.powf(2.0)).sqrt();
+  if distance == 0.0 {
+    return (0.0, 0.0, 0.0);
+  }
+  let longitude = y.atan2(x).to_degrees().rem_euclid(360.0);
+  let latitude = (z / distance).clamp(-1.0, 1.0).asin().to_degrees();
+  (longitude, latitude, distance)
+}
+
+/// Get the point at a longitude and latitude (in degrees) and distance.
+fn from_spherical(longitude: f64, latitude: f64, distance: f64) -> (f64, f64, f64) {
+  let (longitude, latitude) = (longitude.to_radians(), latitude.to_radians());
+  (
+    distance * latitude.cos() * longitude.cos(),
+    distance * latitude.cos() * longitude.sin(),
+    distance * latitude.sin(),
+  )
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_landmarks() {
+    init();
+    trace_enter!();
+    // The galactic center and north galactic pole.
+    let center = GalacticCoordinates {
+      longitude: 0.0,
+      latitude: 0.0,
+      distance: 26_700.0,
+    }
+    .to_equatorial();
+    print_var!(center);
+    assert_approx_eq!(center.right_ascension, 266.405, 0.01);
+    assert_approx_eq!(center.declination, -28.936, 0.01);
+    let pole = EquatorialCoordinates {
+      right_ascension: 192.859,
+      declination: 27.128,
+      distance: 1.0,
+    }
+    .to_galactic();
+    print_var!(pole);
+    assert_approx_eq!(pole.latitude, 90.0, 0.01);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_round_trip() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    for _ in 0..20 {
+      let cartesian = CartesianCoordinates::from((
+        rng.gen_range(-10.0..10.0),
+        rng.gen_range(-10.0..10.0),
+        rng.gen_range(-10.0..10.0),
+      ));
+      let galactic = cartesian.to_galactic();
+      let equatorial = cartesian.to_equatorial();
+      assert_approx_eq!(galactic.distance, cartesian.get_distance());
+      assert_approx_eq!(equatorial.distance, cartesian.get_distance());
+      for other in [galactic.to_cartesian(), equatorial.to_cartesian()] {
+        assert_approx_eq!(other.x, cartesian.x, 1e-6);
+        assert_approx_eq!(other.y, cartesian.y, 1e-6);
+        assert_approx_eq!(other.z, cartesian.z, 1e-6);
+      }
+      let round_trip = equatorial.to_galactic();
+      assert_approx_eq!(round_trip.longitude, galactic.longitude, 1e-6);
+      assert_approx_eq!(round_trip.latitude, galactic.latitude, 1e-6);
+    }
+    trace_exit!();
+  }
+}
@@ -9,3 +9,13 @@ pub const MINIMUM_ORBITAL_ECCENTRICITY: f64 = 0.4;
 
 /// The maximum orbital eccentricity of "distant" binary stars (unitless).
 pub const MAXIMUM_ORBITAL_ECCENTRICITY: f64 = 0.7;
+
+/// The smallest period ratio between the outer and inner orbits of a
+/// hierarchical multiple star that's stable over the long term (unitless).
+///
+/// This is corrected for the eccentricity of the outer orbit; see
+/// `get_period_ratio()`.
+pub const MINIMUM_PERIOD_RATIO: f64 = 5.0;
+
+/// How many outer orbits we'll try before giving up on a stable hierarchy.
+pub const MAXIMUM_ORBIT_ATTEMPTS: usize = 10;
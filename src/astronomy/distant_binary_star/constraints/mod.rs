@@ -1,10 +1,18 @@
 use rand::prelude::*;
 use std::default::Default;
 
+use crate::astronomy::close_binary_star::math::stability::get_s_type_critical_orbit;
 use crate::astronomy::distant_binary_star::constants::*;
 use crate::astronomy::distant_binary_star::error::Error;
+use crate::astronomy::distant_binary_star::math::stability::is_hierarchy_stable;
 use crate::astronomy::distant_binary_star::DistantBinaryStar;
+use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+use crate::astronomy::orbit::OrbitalElements;
 use crate::astronomy::planetary_system::constraints::Constraints as PlanetarySystemConstraints;
+use crate::astronomy::satellite_systems::constraints::Constraints as SatelliteSystemsConstraints;
+use crate::astronomy::star_subsystem::hierarchy::Hierarchy;
+use crate::generator::Generator;
+use crate::merge::Merge;
 
 /// Constraints for creating a main-sequence star subsystem.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -17,6 +25,13 @@ pub struct Constraints {
   pub minimum_orbital_eccentricity: Option<f64>,
   /// The maximum orbital eccentricity.
   pub maximum_orbital_eccentricity: Option<f64>,
+  /// Constraints for the host star of the first component.
+  ///
+  /// Whichever component turns out to be heavier becomes the primary, so
+  /// this and the next are only "first" and "second" until then.
+  pub first_host_star_constraints: Option<HostStarConstraints>,
+  /// Constraints for the host star of the second component.
+  pub second_host_star_constraints: Option<HostStarConstraints>,
 }
 
 impl Constraints {
@@ -33,24 +48,113 @@ impl Constraints {
   }
 
   /// Generate.
+  ///
+  /// Each component gets its own planets, out as far as the other component
+  /// lets them orbit stably.
   #[named]
   pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<DistantBinaryStar, Error> {
     trace_enter!();
-    let primary_constraints = PlanetarySystemConstraints::default();
-    trace_var!(primary_constraints);
-    let primary = primary_constraints.generate(rng)?;
+    let minimum_average_separation = self.minimum_average_separation.unwrap_or(MINIMUM_AVERAGE_SEPARATION);
+    trace_var!(minimum_average_separation);
+    let maximum_average_separation = self.maximum_average_separation.unwrap_or(MAXIMUM_AVERAGE_SEPARATION);
+    trace_var!(maximum_average_separation);
+    let minimum_orbital_eccentricity = self
+      .minimum_orbital_eccentricity
+      .unwrap_or(MINIMUM_ORBITAL_ECCENTRICITY);
+    trace_var!(minimum_orbital_eccentricity);
+    let maximum_orbital_eccentricity = self
+      .maximum_orbital_eccentricity
+      .unwrap_or(MAXIMUM_ORBITAL_ECCENTRICITY);
+    trace_var!(maximum_orbital_eccentricity);
+    let first_host_star = self.first_host_star_constraints.unwrap_or_default().generate(rng)?;
+    trace_var!(first_host_star);
+    let second_host_star = self.second_host_star_constraints.unwrap_or_default().generate(rng)?;
+    trace_var!(second_host_star);
+    let (primary_host_star, mut secondary_host_star) =
+      match first_host_star.get_stellar_mass() >= second_host_star.get_stellar_mass() {
+        true => (first_host_star, second_host_star),
+        false => (second_host_star, first_host_star),
+      };
+    // Both components were born together, so the secondary takes on the
+    // primary's [Fe/H] whatever it drew.
+    secondary_host_star.set_metallicity(primary_host_star.get_metallicity());
+    trace_var!(primary_host_star);
+    trace_var!(secondary_host_star);
+    let primary_mass = primary_host_star.get_stellar_mass();
+    let secondary_mass = secondary_host_star.get_stellar_mass();
+    let combined_mass = primary_mass + secondary_mass;
+    trace_var!(combined_mass);
+    // Either component may itself be a close binary, which the outer orbit
+    // mustn't disrupt; the wider pair is the more vulnerable.
+    let inner_period = [&primary_host_star, &secondary_host_star]
+      .iter()
+      .filter_map(|host_star| Hierarchy::from_host_star(host_star).get_orbital_period())
+      .fold(0.0f64, f64::max);
+    trace_var!(inner_period);
+    let mut attempts = 0;
+    let (average_separation, orbital_eccentricity, orbital_period) = loop {
+      if attempts >= MAXIMUM_ORBIT_ATTEMPTS {
+        return Err(Error::UnstableHierarchy);
+      }
+      attempts += 1;
+      let average_separation = rng.gen_range(minimum_average_separation..=maximum_average_separation);
+      let orbital_eccentricity = rng.gen_range(minimum_orbital_eccentricity..=maximum_orbital_eccentricity);
+      let orbital_period = (average_separation.powf(3.0) / combined_mass).sqrt();
+      if inner_period == 0.0 || is_hierarchy_stable(inner_period, orbital_period, orbital_eccentricity) {
+        break (average_separation, orbital_eccentricity, orbital_period);
+      }
+    };
+    trace_var!(average_separation);
+    trace_var!(orbital_eccentricity);
+    trace_var!(orbital_period);
+    let orbit = OrbitalElements::new(average_separation, orbital_eccentricity).with_random_orientation(rng);
+    trace_var!(orbit);
+    let primary_stability_limit =
+      get_s_type_critical_orbit(average_separation, secondary_mass / combined_mass, orbital_eccentricity);
+    trace_var!(primary_stability_limit);
+    let secondary_stability_limit =
+      get_s_type_critical_orbit(average_separation, primary_mass / combined_mass, orbital_eccentricity);
+    trace_var!(secondary_stability_limit);
+    let get_planetary_system_constraints = |maximum_orbit: f64| PlanetarySystemConstraints {
+      satellite_systems_constraints: Some(SatelliteSystemsConstraints {
+        maximum_orbit: Some(maximum_orbit),
+        ..SatelliteSystemsConstraints::default()
+      }),
+      ..PlanetarySystemConstraints::default()
+    };
+    let primary =
+      get_planetary_system_constraints(primary_stability_limit).generate_from_host_star(rng, primary_host_star)?;
     trace_var!(primary);
-    let secondary_constraints = PlanetarySystemConstraints::default();
-    trace_var!(secondary_constraints);
-    let secondary = secondary_constraints.generate(rng)?;
+    let secondary =
+      get_planetary_system_constraints(secondary_stability_limit).generate_from_host_star(rng, secondary_host_star)?;
     trace_var!(secondary);
-    let result = DistantBinaryStar { primary, secondary };
+    let result = DistantBinaryStar {
+      primary,
+      secondary,
+      orbit,
+      orbital_period,
+      primary_stability_limit,
+      secondary_stability_limit,
+    };
     trace_var!(result);
     trace_exit!();
     Ok(result)
   }
 }
 
+impl Generator for Constraints {
+  type Output = DistantBinaryStar;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<DistantBinaryStar, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
 impl Default for Constraints {
   /// No constraints, just let it all hang out.
   fn default() -> Self {
@@ -58,11 +162,36 @@ impl Default for Constraints {
     let maximum_average_separation = Some(MAXIMUM_AVERAGE_SEPARATION);
     let minimum_orbital_eccentricity = Some(MINIMUM_ORBITAL_ECCENTRICITY);
     let maximum_orbital_eccentricity = Some(MAXIMUM_ORBITAL_ECCENTRICITY);
+    let first_host_star_constraints = None;
+    let second_host_star_constraints = None;
     Self {
       minimum_average_separation,
       maximum_average_separation,
       minimum_orbital_eccentricity,
       maximum_orbital_eccentricity,
+      first_host_star_constraints,
+      second_host_star_constraints,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_average_separation: overrides.minimum_average_separation.or(self.minimum_average_separation),
+      maximum_average_separation: overrides.maximum_average_separation.or(self.maximum_average_separation),
+      minimum_orbital_eccentricity: overrides
+        .minimum_orbital_eccentricity
+        .or(self.minimum_orbital_eccentricity),
+      maximum_orbital_eccentricity: overrides
+        .maximum_orbital_eccentricity
+        .or(self.maximum_orbital_eccentricity),
+      first_host_star_constraints: self
+        .first_host_star_constraints
+        .merged_with(&overrides.first_host_star_constraints),
+      second_host_star_constraints: self
+        .second_host_star_constraints
+        .merged_with(&overrides.second_host_star_constraints),
     }
   }
 }
@@ -73,6 +202,9 @@ pub mod test {
   use rand::prelude::*;
 
   use super::*;
+  use crate::astronomy::host_star::HostStar;
+  use crate::astronomy::star_subsystem::StarSubsystem;
+  use crate::math::probability::Probability;
   use crate::test::*;
 
   #[named]
@@ -88,4 +220,88 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_stability_limits() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let distant_binary_star = Constraints::default().generate(&mut rng)?;
+    print_var!(distant_binary_star);
+    assert!(distant_binary_star.primary.get_stellar_mass() >= distant_binary_star.secondary.get_stellar_mass());
+    // The more massive component holds onto its planets further out.
+    assert!(distant_binary_star.primary_stability_limit >= distant_binary_star.secondary_stability_limit);
+    assert!(distant_binary_star.primary_stability_limit < distant_binary_star.orbit.semi_major_axis);
+    let (primary_limit, secondary_limit) = distant_binary_star.stable_circumstellar_outer_radius();
+    let limits = [primary_limit, secondary_limit];
+    for (planetary_system, limit) in distant_binary_star.get_planetary_systems().into_iter().zip(limits) {
+      assert!(planetary_system
+        .satellite_systems
+        .get_semi_major_axes()
+        .iter()
+        .all(|&semi_major_axis| semi_major_axis < limit));
+    }
+    assert_eq!(
+      distant_binary_star.get_planet_count(),
+      distant_binary_star.primary.satellite_systems.satellite_systems.len()
+        + distant_binary_star.secondary.satellite_systems.satellite_systems.len()
+    );
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_quadruple() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let close_binary = HostStarConstraints {
+      binary_probability: Some(Probability::ALWAYS),
+      ..HostStarConstraints::default()
+    };
+    let constraints = Constraints {
+      first_host_star_constraints: Some(close_binary),
+      second_host_star_constraints: Some(close_binary),
+      ..Constraints::default()
+    };
+    let distant_binary_star = loop {
+      match constraints.generate(&mut rng) {
+        Err(Error::UnstableHierarchy) => continue,
+        result => break result?,
+      }
+    };
+    let metallicities = [&distant_binary_star.primary, &distant_binary_star.secondary]
+      .iter()
+      .flat_map(|planetary_system| match &planetary_system.host_star {
+        HostStar::CloseBinaryStar(close_binary_star) => {
+          vec![
+            close_binary_star.primary.metallicity,
+            close_binary_star.secondary.metallicity,
+          ]
+        },
+        _ => unreachable!(),
+      })
+      .collect::<Vec<f64>>();
+    print_var!(metallicities);
+    assert!(metallicities.iter().all(|&metallicity| metallicity == metallicities[0]));
+    let hierarchy = Hierarchy::from_star_subsystem(&StarSubsystem::DistantBinaryStar(distant_binary_star));
+    print_var!(hierarchy);
+    assert_eq!(hierarchy.get_stellar_count(), 4);
+    assert_eq!(hierarchy.get_depth(), 2);
+    assert!(hierarchy.is_stable());
+    let inner_periods = hierarchy
+      .get_children()
+      .iter()
+      .map(|child| child.get_orbital_period().unwrap())
+      .collect::<Vec<f64>>();
+    assert!(inner_periods
+      .iter()
+      .all(|&inner_period| inner_period < hierarchy.get_orbital_period().unwrap()));
+    trace_exit!();
+    Ok(())
+  }
 }
@@ -1,8 +1,13 @@
+use crate::astronomy::host_star::error::Error as HostStarError;
 use crate::astronomy::planetary_system::error::Error as PlanetarySystemError;
 
 /// Star system errors.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Error {
+  /// Host Star Error
+  HostStarError(HostStarError),
+  /// No outer orbit we tried left the inner pairs alone.
+  UnstableHierarchy,
   /// Planetary System Error
   PlanetarySystemError(PlanetarySystemError),
 }
@@ -10,6 +15,11 @@ pub enum Error {
 honeyholt_define_brief!(Error, |error: &Error| {
   use Error::*;
   match error {
+    HostStarError(host_star_error) => format!(
+      "an error occurred in the host star ({})",
+      honeyholt_brief!(host_star_error)
+    ),
+    UnstableHierarchy => "no stable orbit was found for the distant binary".to_string(),
     PlanetarySystemError(planetary_system_error) => format!(
       "an error occurred in the planetary system ({})",
       honeyholt_brief!(planetary_system_error)
@@ -17,6 +27,13 @@ honeyholt_define_brief!(Error, |error: &Error| {
   }
 });
 
+impl From<HostStarError> for Error {
+  #[named]
+  fn from(error: HostStarError) -> Self {
+    Error::HostStarError(error)
+  }
+}
+
 impl From<PlanetarySystemError> for Error {
   #[named]
   fn from(error: PlanetarySystemError) -> Self {
@@ -0,0 +1,54 @@
+use crate::astronomy::distant_binary_star::constants::MINIMUM_PERIOD_RATIO;
+
+/// Calculate the period ratio of a hierarchical multiple star, corrected for
+/// the closest approach of the outer orbit.
+///
+/// This is the measure from Tokovinin (2014); eccentric outer orbits swing in
+/// close to the inner pair, so they need much longer periods to be safe.
+///
+/// * `inner_period` - Orbital period of the inner pair.
+/// * `outer_period` - Orbital period of the outer orbit, in the same units.
+/// * `outer_eccentricity` - Orbital eccentricity of the outer orbit.
+#[named]
+pub fn get_period_ratio(inner_period: f64, outer_period: f64, outer_eccentricity: f64) -> f64 {
+  trace_enter!();
+  trace_var!(inner_period);
+  trace_var!(outer_period);
+  trace_var!(outer_eccentricity);
+  let result = outer_period * (1.0 - outer_eccentricity).powf(3.0) / inner_period;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Indicate whether an outer orbit leaves an inner pair alone.
+///
+/// Units are as for `get_period_ratio()`.
+#[named]
+pub fn is_hierarchy_stable(inner_period: f64, outer_period: f64, outer_eccentricity: f64) -> bool {
+  trace_enter!();
+  let result = get_period_ratio(inner_period, outer_period, outer_eccentricity) > MINIMUM_PERIOD_RATIO;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_is_hierarchy_stable() {
+    init();
+    trace_enter!();
+    // Alpha Centauri AB (80 years) and Proxima (about 550,000 years).
+    assert!(is_hierarchy_stable(79.9, 550_000.0, 0.5));
+    // The same pair, with the outer orbit far too eccentric.
+    assert!(!is_hierarchy_stable(79.9, 550_000.0, 0.99));
+    assert_approx_eq!(get_period_ratio(1.0, 40.0, 0.5), 5.0);
+    trace_exit!();
+  }
+}
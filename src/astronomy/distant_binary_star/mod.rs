@@ -1,9 +1,15 @@
+use serde::Serialize;
+
+use crate::astronomy::orbit::OrbitalElements;
 use crate::astronomy::planetary_system::PlanetarySystem;
+use crate::astronomy::satellite_system::SatelliteSystem;
+use crate::astronomy::star::name::NameKind;
 
 pub mod constants;
 pub mod constraints;
 pub mod error;
 use error::Error;
+pub(crate) mod math;
 
 /// A `DistantBinaryStar` is actually a pair of `PlanetarySystem` objects.
 ///
@@ -16,15 +22,35 @@ use error::Error;
 /// And let's not get started on how disappointing it is to call something a
 /// planetary system when it may not actually have any planets.  But I don't
 /// think we have a better word or phrase for the idea.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct DistantBinaryStar {
   /// The primary planetary system is the one with greater mass.
   pub primary: PlanetarySystem,
   /// The secondary planetary system has less mass.
   pub secondary: PlanetarySystem,
+  /// The orbit of the secondary around the primary, in AU.
+  pub orbit: OrbitalElements,
+  /// The orbital period, in Earth years.
+  pub orbital_period: f64,
+  /// The widest stable (S-type) orbit around the primary, in AU.
+  ///
+  /// The secondary disrupts anything further out, so the primary's planets
+  /// stop here.
+  pub primary_stability_limit: f64,
+  /// The widest stable (S-type) orbit around the secondary, in AU.
+  pub secondary_stability_limit: f64,
 }
 
 impl DistantBinaryStar {
+  /// Rename the stars and planets with the given function.
+  #[named]
+  pub fn rename<F: FnMut(&str, NameKind) -> String>(&mut self, rename: &mut F) {
+    trace_enter!();
+    self.primary.rename(rename);
+    self.secondary.rename(rename);
+    trace_exit!();
+  }
+
   /// Indicate whether this star is capable of supporting conventional life.
   #[named]
   pub fn check_habitable(&self) -> Result<(), Error> {
@@ -50,6 +76,19 @@ impl DistantBinaryStar {
     result
   }
 
+  /// Get the widest stable orbits around the primary and secondary, in AU.
+  ///
+  /// This is the Holman-Wiegert criterion for S-type orbits; each component's
+  /// planets are kept inside its own limit.
+  #[named]
+  pub fn stable_circumstellar_outer_radius(&self) -> (f64, f64) {
+    trace_enter!();
+    let result = (self.primary_stability_limit, self.secondary_stability_limit);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Retrieve or calculate the total mass of the stars.
   ///
   /// Calculated in Msol.
@@ -62,6 +101,18 @@ impl DistantBinaryStar {
     result
   }
 
+  /// Retrieve or calculate the total luminosity of the stars.
+  ///
+  /// Calculated in Lsol.
+  #[named]
+  pub fn get_luminosity(&self) -> f64 {
+    trace_enter!();
+    let result = self.primary.get_luminosity() + self.secondary.get_luminosity();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Retrieve or calculate the total number of stars in the system.
   #[named]
   pub fn get_stellar_count(&self) -> u8 {
@@ -71,4 +122,47 @@ impl DistantBinaryStar {
     trace_exit!();
     result
   }
+
+  /// Retrieve the satellite systems of both components, primary first.
+  #[named]
+  pub fn get_satellite_systems(&self) -> Vec<&SatelliteSystem> {
+    trace_enter!();
+    let result = self
+      .get_planetary_systems()
+      .into_iter()
+      .flat_map(|planetary_system| planetary_system.satellite_systems.satellite_systems.iter())
+      .collect::<Vec<&SatelliteSystem>>();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve the total number of planets orbiting either component.
+  #[named]
+  pub fn get_planet_count(&self) -> usize {
+    trace_enter!();
+    let result = self.get_satellite_systems().len();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve both planetary systems, primary first.
+  #[named]
+  pub fn get_planetary_systems(&self) -> Vec<&PlanetarySystem> {
+    trace_enter!();
+    let result = vec![&self.primary, &self.secondary];
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve both planetary systems mutably, primary first.
+  #[named]
+  pub fn get_planetary_systems_mut(&mut self) -> Vec<&mut PlanetarySystem> {
+    trace_enter!();
+    let result = vec![&mut self.primary, &mut self.secondary];
+    trace_exit!();
+    result
+  }
 }
@@ -0,0 +1,29 @@
+/// Minimum mass of a dwarf planet, in Mearth.
+///
+/// Ceres, the smallest we know of, is about 0.00016 Mearth.
+pub const MINIMUM_MASS: f64 = 0.00015;
+
+/// Maximum mass of a dwarf planet, in Mearth.
+///
+/// Eris is about 0.0028 Mearth.
+pub const MAXIMUM_MASS: f64 = 0.003;
+
+/// Minimum density of a dwarf planet, in g/cm³.
+///
+/// Pluto is about 1.85.
+pub const MINIMUM_DENSITY: f64 = 1.8;
+
+/// Maximum density of a dwarf planet, in g/cm³.
+///
+/// Eris is about 2.43.
+pub const MAXIMUM_DENSITY: f64 = 2.5;
+
+/// Minimum rotational period, in Dearth.
+///
+/// Haumea spins in under four hours.
+pub const MINIMUM_ROTATIONAL_PERIOD: f64 = 0.15;
+
+/// Maximum rotational period, in Dearth.
+///
+/// Pluto, locked to Charon, takes 6.4 days.
+pub const MAXIMUM_ROTATIONAL_PERIOD: f64 = 6.5;
@@ -0,0 +1,146 @@
+use rand::prelude::*;
+use std::f64::consts::PI;
+
+use crate::astronomy::composition::Composition;
+use crate::astronomy::dwarf_planet::constants::*;
+use crate::astronomy::dwarf_planet::{DwarfPlanet, DwarfPlanetPopulation};
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::planet::math::mass::earth_mass_to_kg;
+use crate::astronomy::star::name::generate_star_name;
+use crate::merge::Merge;
+
+/// Constraints for creating a dwarf planet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+  /// The minimum mass, in Mearth.
+  pub minimum_mass: Option<f64>,
+  /// The maximum mass, in Mearth.
+  pub maximum_mass: Option<f64>,
+  /// The minimum density, in g/cm³.
+  pub minimum_density: Option<f64>,
+  /// The maximum density, in g/cm³.
+  pub maximum_density: Option<f64>,
+}
+
+impl Constraints {
+  /// Generate a dwarf planet on the specified orbit.
+  ///
+  /// Where it sits is up to the belt it belongs to; this just fills in the
+  /// body itself.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: &HostStar,
+    population: DwarfPlanetPopulation,
+    orbit: OrbitalElements,
+  ) -> DwarfPlanet {
+    trace_enter!();
+    trace_var!(population);
+    trace_var!(orbit);
+    let minimum_mass = self.minimum_mass.unwrap_or(MINIMUM_MASS);
+    trace_var!(minimum_mass);
+    let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
+    trace_var!(maximum_mass);
+    // Smaller bodies are more common, so pick log-uniformly.
+    let mass = rng.gen_range(minimum_mass.ln()..=maximum_mass.ln()).exp();
+    trace_var!(mass);
+    let minimum_density = self.minimum_density.unwrap_or(MINIMUM_DENSITY);
+    trace_var!(minimum_density);
+    let maximum_density = self.maximum_density.unwrap_or(MAXIMUM_DENSITY);
+    trace_var!(maximum_density);
+    let density = rng.gen_range(minimum_density..=maximum_density);
+    trace_var!(density);
+    let composition = Composition::from_density(density);
+    trace_var!(composition);
+    let volume = earth_mass_to_kg(mass) / (density * 1_000.0);
+    trace_var!(volume);
+    let radius = (3.0 * volume / (4.0 * PI)).powf(1.0 / 3.0) / 1_000.0;
+    trace_var!(radius);
+    let rotation_period = rng
+      .gen_range(MINIMUM_ROTATIONAL_PERIOD.ln()..MAXIMUM_ROTATIONAL_PERIOD.ln())
+      .exp();
+    trace_var!(rotation_period);
+    let orbital_period = (orbit.semi_major_axis.powf(3.0) / host_star.get_stellar_mass()).sqrt();
+    trace_var!(orbital_period);
+    let name = loop {
+      let name = generate_star_name(rng);
+      if !name.is_empty() {
+        break name;
+      }
+    };
+    trace_var!(name);
+    let result = DwarfPlanet {
+      name,
+      population,
+      mass,
+      density,
+      composition,
+      radius,
+      rotation_period,
+      orbit,
+      orbital_period,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl Default for Constraints {
+  /// No constraints, just let it all hang out.
+  fn default() -> Self {
+    let minimum_mass = None;
+    let maximum_mass = None;
+    let minimum_density = None;
+    let maximum_density = None;
+    Self {
+      minimum_mass,
+      maximum_mass,
+      minimum_density,
+      maximum_density,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+      minimum_density: overrides.minimum_density.or(self.minimum_density),
+      maximum_density: overrides.maximum_density.or(self.maximum_density),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+  use crate::astronomy::host_star::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = HostStarConstraints::default().generate(&mut rng)?;
+    let orbit = OrbitalElements::new(39.5, 0.25);
+    let dwarf_planet = Constraints::default().generate(&mut rng, &host_star, DwarfPlanetPopulation::Resonant, orbit);
+    print_var!(dwarf_planet);
+    assert!(dwarf_planet.radius > 300.0 && dwarf_planet.radius < 1_500.0);
+    assert_eq!(dwarf_planet.composition, Composition::Icy);
+    assert!(dwarf_planet.orbital_period > 0.0);
+    assert!(!dwarf_planet.is_habitable());
+    trace_exit!();
+    Ok(())
+  }
+}
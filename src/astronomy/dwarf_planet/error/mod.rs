@@ -0,0 +1,13 @@
+/// DwarfPlanet errors.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+  /// Too small to hold onto an atmosphere.
+  TooSmallToRetainAtmosphere,
+}
+
+honeyholt_define_brief!(Error, |error: &Error| {
+  use Error::*;
+  match error {
+    TooSmallToRetainAtmosphere => "the dwarf planet is too small to retain an atmosphere".to_string(),
+  }
+});
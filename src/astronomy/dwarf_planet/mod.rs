@@ -0,0 +1,77 @@
+use serde::Serialize;
+
+use crate::astronomy::composition::Composition;
+use crate::astronomy::orbit::OrbitalElements;
+
+pub mod constants;
+pub mod constraints;
+pub mod error;
+use error::Error;
+
+/// Which part of a planetary system a dwarf planet belongs to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum DwarfPlanetPopulation {
+  /// Within an asteroid belt, like Ceres.
+  Belt,
+  /// On a low, nearly circular orbit within a Kuiper belt, like Makemake.
+  Classical,
+  /// Locked in the 3:2 resonance with the outermost planet, like Pluto.
+  Resonant,
+  /// Flung out onto a long, eccentric, inclined orbit, like Eris.
+  Scattered,
+}
+
+/// A `DwarfPlanet` is massive enough to have pulled itself round, but not
+/// enough to have cleared its orbit of everything else.
+///
+/// These never support life, but they're worth a visit.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DwarfPlanet {
+  /// The name.
+  pub name: String,
+  /// Which population it belongs to.
+  pub population: DwarfPlanetPopulation,
+  /// Mass, in Mearth.
+  pub mass: f64,
+  /// Density, in g/cm³.
+  pub density: f64,
+  /// The bulk composition, inferred from density.
+  pub composition: Composition,
+  /// Radius, in KM.
+  pub radius: f64,
+  /// Rotation period, in Dearth.
+  pub rotation_period: f64,
+  /// The orbit about the host star, with the semi-major axis in AU.
+  pub orbit: OrbitalElements,
+  /// Orbital period, in Earth years.
+  pub orbital_period: f64,
+}
+
+impl DwarfPlanet {
+  /// Indicate whether this dwarf planet is capable of supporting
+  /// conventional life.
+  ///
+  /// It never is; even Pluto's wisp of an atmosphere is freezing out.
+  #[named]
+  pub fn check_habitable(&self) -> Result<(), Error> {
+    trace_enter!();
+    let result = Err(Error::TooSmallToRetainAtmosphere);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this dwarf planet is capable of supporting
+  /// conventional life.
+  #[named]
+  pub fn is_habitable(&self) -> bool {
+    trace_enter!();
+    let result = match self.check_habitable() {
+      Ok(()) => true,
+      Err(_) => false,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
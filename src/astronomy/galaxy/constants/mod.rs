@@ -1 +1,161 @@
+/// The distance of the Sun from the center of the Milky Way.
+///
+/// The densities below are chosen so that the density here matches the
+/// local stellar density.
+///
+/// Measured in Ly, or light years.
+pub const SOLAR_GALACTIC_RADIUS: f64 = 26_700.0;
 
+/// The radius of the Milky Way, where the stellar disk gives out.
+///
+/// Measured in Ly, or light years.
+pub const GALAXY_RADIUS: f64 = 50_000.0;
+
+/// The radius of the central bulge.
+///
+/// Measured in Ly, or light years.
+pub const BULGE_RADIUS: f64 = 3_300.0;
+
+/// The density at the center of the bulge.
+///
+/// Measured in s/ly^3, or stars per cubic light year.
+pub const BULGE_DENSITY: f64 = 0.4;
+
+/// The radial scale length of the thin disk.
+///
+/// Measured in Ly, or light years.
+pub const THIN_DISK_SCALE_LENGTH: f64 = 8_500.0;
+
+/// The vertical scale height of the thin disk.
+///
+/// Measured in Ly, or light years.
+pub const THIN_DISK_SCALE_HEIGHT: f64 = 980.0;
+
+/// The density at the center of the thin disk, were it not swamped by the
+/// bulge.
+///
+/// Measured in s/ly^3, or stars per cubic light year.
+pub const THIN_DISK_DENSITY: f64 = 0.0925;
+
+/// The radial scale length of the thick disk.
+///
+/// Measured in Ly, or light years.
+pub const THICK_DISK_SCALE_LENGTH: f64 = 11_700.0;
+
+/// The vertical scale height of the thick disk.
+///
+/// Measured in Ly, or light years.
+pub const THICK_DISK_SCALE_HEIGHT: f64 = 2_900.0;
+
+/// The density at the center of the thick disk.
+///
+/// Near the Sun, this works out to about an eighth of the thin disk.
+///
+/// Measured in s/ly^3, or stars per cubic light year.
+pub const THICK_DISK_DENSITY: f64 = 0.0047;
+
+/// The radius inside of which the density of the halo stops climbing.
+///
+/// Measured in Ly, or light years.
+pub const HALO_CORE_RADIUS: f64 = 3_300.0;
+
+/// The density of the halo within its core.
+///
+/// Near the Sun, this works out to about one star in two hundred.
+///
+/// Measured in s/ly^3, or stars per cubic light year.
+pub const HALO_DENSITY: f64 = 0.01;
+
+/// The number of major spiral arms.
+pub const ARM_COUNT: u8 = 4;
+
+/// The pitch angle of the spiral arms.
+///
+/// Measured in degrees.
+pub const ARM_PITCH_ANGLE: f64 = 12.0;
+
+/// The half-width of a spiral arm.
+///
+/// Measured in Ly, or light years.
+pub const ARM_WIDTH: f64 = 1_500.0;
+
+/// How much denser the thin disk is along the middle of an arm.
+///
+/// Stars are much less concentrated in the arms than gas and dust are.
+pub const ARM_CONTRAST: f64 = 0.3;
+
+/// The probability of each type of galaxy, in the order spiral, barred
+/// spiral, elliptical, irregular, and dwarf.
+///
+/// Dwarfs far outnumber everything else, but most of them are too faint to
+/// be worth living in.
+pub const GALAXY_TYPE_WEIGHTS: [f64; 5] = [0.25, 0.35, 0.15, 0.1, 0.15];
+
+/// The fewest arms a spiral galaxy has.
+pub const MINIMUM_ARM_COUNT: u8 = 2;
+
+/// The most arms a spiral galaxy has.
+pub const MAXIMUM_ARM_COUNT: u8 = 4;
+
+/// The most tightly wound spiral arms.
+///
+/// Measured in degrees.
+pub const MINIMUM_ARM_PITCH_ANGLE: f64 = 10.0;
+
+/// The most loosely wound spiral arms.
+///
+/// Measured in degrees.
+pub const MAXIMUM_ARM_PITCH_ANGLE: f64 = 25.0;
+
+/// The shortest half-length of the bar of a barred spiral.
+///
+/// Measured in Ly, or light years.
+pub const MINIMUM_BAR_LENGTH: f64 = 8_000.0;
+
+/// The longest half-length of the bar of a barred spiral.
+///
+/// The Milky Way's is about this long.
+///
+/// Measured in Ly, or light years.
+pub const MAXIMUM_BAR_LENGTH: f64 = 16_000.0;
+
+/// The flattest an elliptical galaxy gets, i.e. E7.
+pub const MAXIMUM_ELLIPTICITY: f64 = 0.7;
+
+/// The smallest radius of the body of an elliptical galaxy.
+///
+/// Measured in Ly, or light years.
+pub const MINIMUM_ELLIPTICAL_RADIUS: f64 = 15_000.0;
+
+/// The largest radius of the body of an elliptical galaxy.
+///
+/// Measured in Ly, or light years.
+pub const MAXIMUM_ELLIPTICAL_RADIUS: f64 = 25_000.0;
+
+/// The density at the center of an elliptical galaxy.
+///
+/// Measured in s/ly^3, or stars per cubic light year.
+pub const ELLIPTICAL_DENSITY: f64 = 0.05;
+
+/// How far the visible galaxy extends beyond the body of an elliptical.
+pub const ELLIPTICAL_EXTENT: f64 = 2.5;
+
+/// The smallest radius of an irregular galaxy.
+///
+/// Measured in Ly, or light years.
+pub const MINIMUM_IRREGULAR_RADIUS: f64 = 15_000.0;
+
+/// The largest radius of an irregular galaxy.
+///
+/// Measured in Ly, or light years.
+pub const MAXIMUM_IRREGULAR_RADIUS: f64 = 30_000.0;
+
+/// The smallest radius of a dwarf galaxy.
+///
+/// Measured in Ly, or light years.
+pub const MINIMUM_DWARF_RADIUS: f64 = 3_000.0;
+
+/// The largest radius of a dwarf galaxy.
+///
+/// Measured in Ly, or light years.
+pub const MAXIMUM_DWARF_RADIUS: f64 = 10_000.0;
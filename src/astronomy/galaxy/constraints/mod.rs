@@ -1,14 +1,35 @@
 use rand::prelude::*;
 
 use crate::astronomy::galaxy::error::*;
+use crate::astronomy::galaxy::location::GalacticLocation;
+use crate::astronomy::galaxy::morphology::GalaxyType;
+use crate::astronomy::galaxy::name::{generate_galaxy_designation, generate_galaxy_name};
+use crate::astronomy::galaxy::structure::GalacticStructure;
 use crate::astronomy::galaxy::Galaxy;
+use crate::astronomy::star::name::registry::NameRegistry;
+use crate::astronomy::stellar_neighbor::math::density::SpatialDensityModel;
 use crate::astronomy::stellar_neighborhood::constraints::Constraints as StellarNeighborhoodConstraints;
+use crate::astronomy::stellar_neighborhood::StellarNeighborhood;
+use crate::generator::{GenerationStrategy, Generator};
+use crate::merge::Merge;
 
 /// Constraints for creating a galaxy.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Constraints {
   /// Any constraints placed on the various neighbors.
   pub stellar_neighborhood_constraints: Option<StellarNeighborhoodConstraints>,
+  /// The distance of the neighborhood from the galactic center, in Ly.
+  pub galactic_radius: Option<f64>,
+  /// The height of the neighborhood above the galactic plane, in Ly.
+  pub galactic_height: Option<f64>,
+  /// The broad shape of the galaxy.
+  pub galaxy_type: Option<GalaxyType>,
+  /// The number of spiral arms, overriding the galaxy type's.
+  pub arm_count: Option<u8>,
+  /// The half-length of the bar, in Ly, overriding the galaxy type's.
+  pub bar_length: Option<f64>,
+  /// The flattening of the bulge and halo, overriding the galaxy type's.
+  pub ellipticity: Option<f64>,
 }
 
 impl Constraints {
@@ -23,28 +44,201 @@ impl Constraints {
 
   /// Generate a random stellar neighborhood with the specified constraints.
   ///
-  /// This may or may not be habitable.
+  /// This may or may not be habitable.  The galaxy's name follows the stellar
+  /// neighborhood's name theme, if it has one.
+  ///
+  /// If the neighborhood is placed within the galaxy, its density is taken
+  /// from the galaxy's structure there, and its stars thin out away from the
+  /// plane.
   #[named]
   pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Galaxy, Error> {
     trace_enter!();
-    let stellar_neighborhood_constraints = self
+    let (galaxy_type, structure) = self.get_structure(rng);
+    trace_var!(galaxy_type);
+    trace_var!(structure);
+    let location = self.get_location(rng, &structure);
+    trace_var!(location);
+    let mut stellar_neighborhood_constraints = self
       .stellar_neighborhood_constraints
       .unwrap_or(StellarNeighborhoodConstraints::default());
+    if self.galactic_radius.is_some() || self.galactic_height.is_some() {
+      stellar_neighborhood_constraints.density = Some(location.stellar_density);
+      let density_model = SpatialDensityModel::ExponentialDisk {
+        scale_height: structure.thin_disk_scale_height,
+        height: location.height,
+      };
+      stellar_neighborhood_constraints.density_model =
+        stellar_neighborhood_constraints.density_model.or(Some(density_model));
+    }
     trace_var!(stellar_neighborhood_constraints);
-    let stellar_neighborhood = stellar_neighborhood_constraints.generate(rng)?;
-    let result = Galaxy { stellar_neighborhood };
+    let mut result =
+      self.generate_at_location(rng, &stellar_neighborhood_constraints, galaxy_type, structure, location)?;
+    if let Some(name_theme) = stellar_neighborhood_constraints.name_theme {
+      result.name = name_theme.get_generator().replace_root(rng, &result.name);
+    }
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+
+  /// Generate, using another strategy for the stellar neighborhood.
+  ///
+  /// The stellar neighborhood constraints are ignored.
+  #[named]
+  pub fn generate_with_stellar_neighborhood_strategy<R, S>(&self, rng: &mut R, strategy: &S) -> Result<Galaxy, Error>
+  where
+    R: Rng + ?Sized,
+    S: GenerationStrategy<StellarNeighborhood>,
+    Error: From<S::Error>,
+  {
+    trace_enter!();
+    let (galaxy_type, structure) = self.get_structure(rng);
+    trace_var!(galaxy_type);
+    trace_var!(structure);
+    let location = self.get_location(rng, &structure);
+    trace_var!(location);
+    let result = self.generate_at_location(rng, strategy, galaxy_type, structure, location);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Pick the type of galaxy and generate its structure.
+  #[named]
+  pub fn get_structure<R: Rng + ?Sized>(&self, rng: &mut R) -> (GalaxyType, GalacticStructure) {
+    trace_enter!();
+    let galaxy_type = self.galaxy_type.unwrap_or_else(|| GalaxyType::get_random(rng));
+    trace_var!(galaxy_type);
+    let structure = galaxy_type.generate_structure(rng);
+    let structure = GalacticStructure {
+      arm_count: self.arm_count.unwrap_or(structure.arm_count),
+      bar_length: self.bar_length.unwrap_or(structure.bar_length),
+      ellipticity: self.ellipticity.unwrap_or(structure.ellipticity),
+      ..structure
+    };
+    trace_var!(structure);
+    trace_exit!();
+    (galaxy_type, structure)
+  }
+
+  /// Place the neighborhood within the structure.
+  ///
+  /// Unless told otherwise, it's placed in the plane at the equivalent of the
+  /// Sun's distance from the center, somewhere around it.
+  #[named]
+  pub fn get_location<R: Rng + ?Sized>(&self, rng: &mut R, structure: &GalacticStructure) -> GalacticLocation {
+    trace_enter!();
+    let radius = self.galactic_radius.unwrap_or(structure.get_reference_radius());
+    trace_var!(radius);
+    let height = self.galactic_height.unwrap_or(0.0);
+    trace_var!(height);
+    let azimuth = rng.gen_range(0.0..360.0);
+    trace_var!(azimuth);
+    let result = GalacticLocation::new(structure, radius, height, azimuth);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Generate the galaxy around a neighborhood at a known location.
+  #[named]
+  fn generate_at_location<R, S>(
+    &self,
+    rng: &mut R,
+    strategy: &S,
+    galaxy_type: GalaxyType,
+    structure: GalacticStructure,
+    location: GalacticLocation,
+  ) -> Result<Galaxy, Error>
+  where
+    R: Rng + ?Sized,
+    S: GenerationStrategy<StellarNeighborhood>,
+    Error: From<S::Error>,
+  {
+    trace_enter!();
+    let stellar_neighborhood = strategy.generate(rng)?;
+    trace_var!(stellar_neighborhood);
+    let name = generate_galaxy_name(rng);
+    trace_var!(name);
+    let designation = generate_galaxy_designation(rng);
+    trace_var!(designation);
+    let result = Galaxy {
+      name,
+      designation,
+      galaxy_type,
+      structure,
+      location,
+      stellar_neighborhood,
+    };
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+
+  /// Generate, giving every star and planet a name no other body in the
+  /// registry has.
+  #[named]
+  pub fn generate_with_name_registry<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    registry: &mut NameRegistry,
+  ) -> Result<Galaxy, Error> {
+    trace_enter!();
+    let mut result = self.generate(rng)?;
+    result.stellar_neighborhood.register_names(registry);
     trace_var!(result);
     trace_exit!();
     Ok(result)
   }
 }
 
+impl Generator for Constraints {
+  type Output = Galaxy;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Galaxy, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
 impl Default for Constraints {
   /// No constraints, just let it all hang out.
   fn default() -> Self {
     let stellar_neighborhood_constraints = Some(StellarNeighborhoodConstraints::default());
+    let galactic_radius = None;
+    let galactic_height = None;
+    let galaxy_type = None;
+    let arm_count = None;
+    let bar_length = None;
+    let ellipticity = None;
     Self {
       stellar_neighborhood_constraints,
+      galactic_radius,
+      galactic_height,
+      galaxy_type,
+      arm_count,
+      bar_length,
+      ellipticity,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      stellar_neighborhood_constraints: self
+        .stellar_neighborhood_constraints
+        .merged_with(&overrides.stellar_neighborhood_constraints),
+      galactic_radius: overrides.galactic_radius.or(self.galactic_radius),
+      galactic_height: overrides.galactic_height.or(self.galactic_height),
+      galaxy_type: overrides.galaxy_type.or(self.galaxy_type),
+      arm_count: overrides.arm_count.or(self.arm_count),
+      bar_length: overrides.bar_length.or(self.bar_length),
+      ellipticity: overrides.ellipticity.or(self.ellipticity),
     }
   }
 }
@@ -55,6 +249,8 @@ pub mod test {
   use rand::prelude::*;
 
   use super::*;
+  use crate::astronomy::galaxy::structure::GalacticComponent;
+  use crate::astronomy::stellar_neighborhood::constants::STELLAR_NEIGHBORHOOD_DENSITY;
   use crate::test::*;
 
   #[named]
@@ -68,6 +264,68 @@ pub mod test {
     let galaxy = constraints.generate(&mut rng)?;
     info_var!(galaxy);
     print_var!(galaxy);
+    let location = galaxy.get_location();
+    print_var!(location);
+    assert!(location.starts_with(&galaxy.stellar_neighborhood.name));
+    assert!(location.ends_with(&format!("({})", galaxy.designation)));
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_galactic_radius() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let stellar_neighborhood_constraints = StellarNeighborhoodConstraints {
+      radius: Some(5.0),
+      ..StellarNeighborhoodConstraints::default()
+    };
+    let constraints = Constraints {
+      stellar_neighborhood_constraints: Some(stellar_neighborhood_constraints),
+      galactic_radius: Some(15_000.0),
+      galaxy_type: Some(GalaxyType::Spiral),
+      ..Constraints::default()
+    };
+    let galaxy = constraints.generate(&mut rng)?;
+    print_var!(galaxy.location);
+    assert_eq!(galaxy.location.radius, 15_000.0);
+    assert_eq!(galaxy.location.population, GalacticComponent::ThinDisk);
+    assert_eq!(galaxy.stellar_neighborhood.density, galaxy.location.stellar_density);
+    assert!(galaxy.stellar_neighborhood.density > STELLAR_NEIGHBORHOOD_DENSITY);
+    let galaxy = Constraints::default().generate(&mut rng)?;
+    assert_eq!(galaxy.location.radius, galaxy.structure.get_reference_radius());
+    assert_eq!(galaxy.stellar_neighborhood.density, STELLAR_NEIGHBORHOOD_DENSITY);
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_galaxy_type() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let constraints = Constraints {
+      galaxy_type: Some(GalaxyType::BarredSpiral),
+      arm_count: Some(3),
+      ..Constraints::default()
+    };
+    let (galaxy_type, structure) = constraints.get_structure(&mut rng);
+    print_var!(structure);
+    assert_eq!(galaxy_type, GalaxyType::BarredSpiral);
+    assert_eq!(structure.arm_count, 3);
+    assert!(structure.bar_length > 0.0);
+    let constraints = Constraints {
+      galaxy_type: Some(GalaxyType::Elliptical),
+      ellipticity: Some(0.5),
+      ..Constraints::default()
+    };
+    let galaxy = constraints.generate(&mut rng)?;
+    assert_eq!(galaxy.galaxy_type, GalaxyType::Elliptical);
+    assert_eq!(galaxy.structure.ellipticity, 0.5);
+    assert_eq!(galaxy.structure.arm_count, 0);
     trace_exit!();
     Ok(())
   }
@@ -0,0 +1,145 @@
+use serde::Serialize;
+
+use crate::astronomy::moon::name::get_systematic_moon_name;
+use crate::astronomy::planet::name::get_systematic_planet_name;
+use crate::astronomy::star_subsystem::hierarchy::Hierarchy;
+use crate::astronomy::star_system::StarSystem;
+
+/// The prefix of our catalog.
+pub const CATALOG_PREFIX: &str = "BW";
+
+/// Letters for the stars of a system, from the primary down.
+pub const STAR_LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// The kind of body a designation refers to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum DesignationKind {
+  /// A star, brown dwarf, or stellar remnant.
+  Star,
+  /// A planet.
+  Planet,
+  /// A moon.
+  Moon,
+}
+
+/// A catalog entry for a single body.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Designation {
+  /// The catalog identifier, e.g. "BW-0042 A b I".
+  pub designation: String,
+  /// The proper name, if the body has one.
+  pub name: Option<String>,
+  /// The kind of body.
+  pub kind: DesignationKind,
+}
+
+/// Designate a star system by its number in the catalog, e.g. "BW-0042".
+#[named]
+pub fn get_star_system_designation(number: usize) -> String {
+  trace_enter!();
+  trace_var!(number);
+  let result = format!("{}-{:04}", CATALOG_PREFIX, number);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Designate every star, planet, and moon in a star system.
+///
+/// Stars are lettered from the primary down; planets circling a close
+/// binary share both letters, e.g. "BW-0042 AB b".  Planets and moons are
+/// lettered and numbered from the innermost out.
+#[named]
+pub fn get_designations(star_system_designation: &str, star_system: &StarSystem) -> Vec<Designation> {
+  trace_enter!();
+  trace_var!(star_system_designation);
+  let mut result = vec![];
+  let mut letters = STAR_LETTERS.chars().cycle();
+  for planetary_system in star_system.get_planetary_systems() {
+    let hierarchy = Hierarchy::from_host_star(&planetary_system.host_star);
+    let mut host_letters = String::new();
+    for star_name in hierarchy.get_star_names() {
+      let letter = letters.next().unwrap();
+      host_letters.push(letter);
+      result.push(Designation {
+        designation: format!("{} {}", star_system_designation, letter),
+        name: Some(star_name.to_string()),
+        kind: DesignationKind::Star,
+      });
+    }
+    trace_var!(host_letters);
+    let host_designation = format!("{} {}", star_system_designation, host_letters);
+    trace_var!(host_designation);
+    let mut satellite_systems = planetary_system
+      .satellite_systems
+      .satellite_systems
+      .iter()
+      .collect::<Vec<_>>();
+    satellite_systems.sort_by(|a, b| {
+      a.planet
+        .get_semi_major_axis()
+        .partial_cmp(&b.planet.get_semi_major_axis())
+        .unwrap()
+    });
+    for (planet_index, satellite_system) in satellite_systems.into_iter().enumerate() {
+      let planet_designation = get_systematic_planet_name(&host_designation, planet_index);
+      let mut moons = satellite_system.moons.moons.iter().collect::<Vec<_>>();
+      moons.sort_by(|a, b| a.orbit.semi_major_axis.partial_cmp(&b.orbit.semi_major_axis).unwrap());
+      let moon_designations = (0..moons.len())
+        .map(|moon_index| get_systematic_moon_name(&planet_designation, moon_index))
+        .collect::<Vec<_>>();
+      result.push(Designation {
+        designation: planet_designation,
+        name: Some(satellite_system.planet.get_name()),
+        kind: DesignationKind::Planet,
+      });
+      result.extend(moon_designations.into_iter().map(|designation| Designation {
+        designation,
+        name: None,
+        kind: DesignationKind::Moon,
+      }));
+    }
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+  use std::collections::HashSet;
+
+  use super::*;
+  use crate::astronomy::star_system::constraints::Constraints as StarSystemConstraints;
+  use crate::astronomy::star_system::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_designations() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let star_system = StarSystemConstraints::default().generate(&mut rng)?;
+    let designation = get_star_system_designation(42);
+    assert_eq!(designation, "BW-0042");
+    let designations = get_designations(&designation, &star_system);
+    print_var!(designations);
+    assert_eq!(designations[0].designation, "BW-0042 A");
+    assert_eq!(designations[0].kind, DesignationKind::Star);
+    let stars = designations
+      .iter()
+      .filter(|designation| designation.kind == DesignationKind::Star)
+      .count();
+    assert_eq!(stars, star_system.get_stellar_count() as usize);
+    let unique = designations
+      .iter()
+      .map(|designation| &designation.designation)
+      .collect::<HashSet<_>>();
+    assert_eq!(unique.len(), designations.len());
+    trace_exit!();
+    Ok(())
+  }
+}
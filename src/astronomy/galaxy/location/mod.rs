@@ -0,0 +1,45 @@
+use serde::Serialize;
+
+use crate::astronomy::galaxy::structure::{GalacticComponent, GalacticStructure};
+
+/// Where a stellar neighborhood sits within its galaxy.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct GalacticLocation {
+  /// The distance from the galactic center, in the plane, in Ly.
+  pub radius: f64,
+  /// The distance above (or below) the plane, in Ly.
+  pub height: f64,
+  /// The angle around the galactic center, in degrees.
+  pub azimuth: f64,
+  /// The density of stars here, in stars per cubic light year.
+  pub stellar_density: f64,
+  /// The component most of the stars here belong to.
+  pub population: GalacticComponent,
+  /// Whether this lies within a spiral arm.
+  pub is_in_spiral_arm: bool,
+}
+
+impl GalacticLocation {
+  /// Locate a position within the structure.
+  #[named]
+  pub fn new(structure: &GalacticStructure, radius: f64, height: f64, azimuth: f64) -> Self {
+    trace_enter!();
+    let stellar_density = structure.get_stellar_density(radius, height, azimuth);
+    trace_var!(stellar_density);
+    let population = structure.get_dominant_component(radius, height, azimuth);
+    trace_var!(population);
+    let is_in_spiral_arm = structure.get_arm_distance(radius, azimuth) <= structure.arm_width;
+    trace_var!(is_in_spiral_arm);
+    let result = Self {
+      radius,
+      height,
+      azimuth,
+      stellar_density,
+      population,
+      is_in_spiral_arm,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
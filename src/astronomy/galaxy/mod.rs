@@ -1,14 +1,72 @@
+use serde::Serialize;
+
 use crate::astronomy::stellar_neighborhood::StellarNeighborhood;
 
 pub mod constants;
 pub mod constraints;
+pub mod designation;
+use designation::{get_designations, get_star_system_designation, Designation};
 pub mod error;
+pub mod location;
+use location::GalacticLocation;
+pub mod morphology;
+use morphology::GalaxyType;
+pub mod name;
+pub mod structure;
+use structure::GalacticStructure;
 
 /// A `Galaxy` is the "outermost" or largest-scale object.
 ///
-/// It's a wrapper around `StellarNeighborhood`.
-#[derive(Clone, Debug, PartialEq)]
+/// It's a wrapper around `StellarNeighborhood`, placed somewhere within the
+/// galaxy's structure.
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Galaxy {
+  /// A proper name, e.g. "Cassoria Whirlpool".
+  pub name: String,
+  /// A catalog designation, e.g. "NGC 4414".
+  pub designation: String,
+  /// The broad shape of the galaxy.
+  pub galaxy_type: GalaxyType,
+  /// The bulge, disks, halo, and arms.
+  pub structure: GalacticStructure,
+  /// Where the stellar neighborhood sits.
+  pub location: GalacticLocation,
   /// This might be plural someday.  For now, we don't care.
   pub stellar_neighborhood: StellarNeighborhood,
 }
+
+impl Galaxy {
+  /// Describe where the stellar neighborhood sits, for reports and exports.
+  ///
+  /// e.g. "Tarsus Reach, Cassoria Whirlpool (NGC 4414)".
+  #[named]
+  pub fn get_location(&self) -> String {
+    trace_enter!();
+    let result = format!(
+      "{}, {} ({})",
+      self.stellar_neighborhood.name, self.name, self.designation
+    );
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Designate every star, planet, and moon in the galaxy.
+  ///
+  /// Star systems are numbered in the order of the neighbors, so the same
+  /// galaxy always gets the same catalog.
+  #[named]
+  pub fn get_designations(&self) -> Vec<Designation> {
+    trace_enter!();
+    let result = self
+      .stellar_neighborhood
+      .neighbors
+      .iter()
+      .enumerate()
+      .flat_map(|(index, neighbor)| get_designations(&get_star_system_designation(index + 1), &neighbor.star_system))
+      .collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
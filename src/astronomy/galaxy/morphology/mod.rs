@@ -0,0 +1,128 @@
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use serde::Serialize;
+
+use crate::astronomy::galaxy::constants::*;
+use crate::astronomy::galaxy::structure::GalacticStructure;
+
+/// The broad shape of a galaxy.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum GalaxyType {
+  /// A disk with spiral arms winding out from a round bulge.
+  Spiral,
+  /// A disk with spiral arms winding out from the ends of a bar.
+  BarredSpiral,
+  /// A featureless, more or less flattened ball of old stars.
+  Elliptical,
+  /// A disk with no particular order to it.
+  Irregular,
+  /// A small, faint disk.
+  Dwarf,
+}
+
+impl GalaxyType {
+  /// Pick a type of galaxy.
+  #[named]
+  pub fn get_random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+    trace_enter!();
+    use GalaxyType::*;
+    let types = [Spiral, BarredSpiral, Elliptical, Irregular, Dwarf];
+    let distribution = WeightedIndex::new(GALAXY_TYPE_WEIGHTS).unwrap();
+    let result = types[distribution.sample(rng)];
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Generate a structure with this type's shape.
+  ///
+  /// Smaller galaxies are scaled-down Milky Ways, so the density at the
+  /// reference radius stays about the same.
+  #[named]
+  pub fn generate_structure<R: Rng + ?Sized>(&self, rng: &mut R) -> GalacticStructure {
+    trace_enter!();
+    use GalaxyType::*;
+    let milky_way = GalacticStructure::default();
+    let result = match self {
+      Spiral => GalacticStructure {
+        arm_count: rng.gen_range(MINIMUM_ARM_COUNT..=MAXIMUM_ARM_COUNT),
+        arm_pitch_angle: rng.gen_range(MINIMUM_ARM_PITCH_ANGLE..MAXIMUM_ARM_PITCH_ANGLE),
+        ..milky_way
+      },
+      BarredSpiral => GalacticStructure {
+        arm_count: *[MINIMUM_ARM_COUNT, MAXIMUM_ARM_COUNT].choose(rng).unwrap(),
+        arm_pitch_angle: rng.gen_range(MINIMUM_ARM_PITCH_ANGLE..MAXIMUM_ARM_PITCH_ANGLE),
+        bar_length: rng.gen_range(MINIMUM_BAR_LENGTH..MAXIMUM_BAR_LENGTH),
+        ..milky_way
+      },
+      Elliptical => {
+        let bulge_radius = rng.gen_range(MINIMUM_ELLIPTICAL_RADIUS..MAXIMUM_ELLIPTICAL_RADIUS);
+        GalacticStructure {
+          radius: bulge_radius * ELLIPTICAL_EXTENT,
+          bulge_radius,
+          bulge_density: ELLIPTICAL_DENSITY,
+          ellipticity: rng.gen_range(0.0..MAXIMUM_ELLIPTICITY),
+          thin_disk_density: 0.0,
+          thick_disk_density: 0.0,
+          arm_count: 0,
+          ..milky_way
+        }
+      },
+      Irregular => GalacticStructure {
+        bulge_density: 0.0,
+        arm_count: 0,
+        ..milky_way.scaled_to(rng.gen_range(MINIMUM_IRREGULAR_RADIUS..MAXIMUM_IRREGULAR_RADIUS))
+      },
+      Dwarf => GalacticStructure {
+        bulge_density: 0.0,
+        arm_count: 0,
+        ..milky_way.scaled_to(rng.gen_range(MINIMUM_DWARF_RADIUS..MAXIMUM_DWARF_RADIUS))
+      },
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::galaxy::structure::GalacticComponent;
+  use crate::astronomy::stellar_neighborhood::constants::STELLAR_NEIGHBORHOOD_DENSITY;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate_structure() {
+    init();
+    trace_enter!();
+    use GalaxyType::*;
+    let mut rng = thread_rng();
+    for galaxy_type in [Spiral, BarredSpiral, Elliptical, Irregular, Dwarf] {
+      let structure = galaxy_type.generate_structure(&mut rng);
+      let radius = structure.get_reference_radius();
+      let density = structure.get_stellar_density(radius, 0.0, 90.0);
+      let population = structure.get_dominant_component(radius, 0.0, 90.0);
+      print_var!((galaxy_type, radius, density, population));
+      assert!(density > STELLAR_NEIGHBORHOOD_DENSITY / 4.0);
+      assert!(density < STELLAR_NEIGHBORHOOD_DENSITY * 4.0);
+      match galaxy_type {
+        Spiral => assert!(structure.arm_count > 0 && structure.bar_length == 0.0),
+        BarredSpiral => assert!(structure.arm_count > 0 && structure.bar_length > 0.0),
+        Elliptical => {
+          assert_eq!(structure.arm_count, 0);
+          assert_eq!(population, GalacticComponent::Bulge);
+        },
+        Irregular | Dwarf => {
+          assert_eq!(structure.arm_count, 0);
+          assert_eq!(population, GalacticComponent::ThinDisk);
+        },
+      }
+    }
+    trace_exit!();
+  }
+}
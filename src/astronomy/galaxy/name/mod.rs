@@ -0,0 +1,71 @@
+use rand::prelude::*;
+
+use crate::astronomy::star::name::generate_star_name;
+
+pub const CATALOGS: &[&str] = &["NGC", "IC", "UGC", "PGC", "ESO"];
+pub const EPITHETS: &[&str] = &[
+  "Cloud",
+  "Spiral",
+  "Whirlpool",
+  "Pinwheel",
+  "Sombrero",
+  "Wheel",
+  "Eye",
+  "Veil",
+  "Lantern",
+  "Crown",
+];
+
+/// Generate a catalog designation for a galaxy, e.g. "NGC 4414".
+#[named]
+pub fn generate_galaxy_designation<R: Rng + ?Sized>(rng: &mut R) -> String {
+  trace_enter!();
+  let catalog = CATALOGS[rng.gen_range(0..CATALOGS.len())];
+  trace_var!(catalog);
+  let number = rng.gen_range(1..8000);
+  trace_var!(number);
+  let result = format!("{} {}", catalog, number);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Generate a proper name for a galaxy, e.g. "Cassoria Whirlpool".
+#[named]
+pub fn generate_galaxy_name<R: Rng + ?Sized>(rng: &mut R) -> String {
+  trace_enter!();
+  let root = generate_star_name(rng);
+  trace_var!(root);
+  let epithet = EPITHETS[rng.gen_range(0..EPITHETS.len())];
+  trace_var!(epithet);
+  let result = format!("{} {}", root, epithet);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_name_galaxy() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let name = generate_galaxy_name(&mut rng);
+    print_var!(name);
+    let designation = generate_galaxy_designation(&mut rng);
+    print_var!(designation);
+    let (catalog, number) = designation.split_once(' ').unwrap();
+    assert!(CATALOGS.contains(&catalog));
+    assert!(number.parse::<u32>().is_ok());
+    trace_exit!();
+  }
+}
@@ -0,0 +1,306 @@
+use serde::Serialize;
+use std::f64::consts::PI;
+
+use crate::astronomy::galaxy::constants::*;
+
+/// The structural components of a galaxy, each with its own population of
+/// stars.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum GalacticComponent {
+  /// The dense, old, metal-rich knot of stars at the center.
+  Bulge,
+  /// The young, metal-rich disk, where the spiral arms are.
+  ThinDisk,
+  /// The older, metal-poor disk the thin disk is embedded in.
+  ThickDisk,
+  /// The ancient, metal-poor cloud of stars around everything else.
+  Halo,
+}
+
+impl GalacticComponent {
+  /// The typical metallicity, [Fe/H], of the component's stars, in dex.
+  #[named]
+  pub fn get_metallicity_mean(&self) -> f64 {
+    trace_enter!();
+    use GalacticComponent::*;
+    let result = match self {
+      Bulge => 0.0,
+      ThinDisk => -0.1,
+      ThickDisk => -0.6,
+      Halo => -1.5,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+/// The large-scale structure of a galaxy: a bulge (perhaps stretched into a
+/// bar), thin and thick disks, a halo, and spiral arms.
+///
+/// Positions are cylindrical, centered on the galactic core: a radius in the
+/// plane, a height above (or below) it, and an azimuth, in light years and
+/// degrees.  A bar lies along an azimuth of zero.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct GalacticStructure {
+  /// The radius of the visible galaxy, where the disks give out, in Ly.
+  pub radius: f64,
+  /// The radius of the bulge, in Ly.
+  pub bulge_radius: f64,
+  /// The density at the center of the bulge, in stars per cubic light year.
+  pub bulge_density: f64,
+  /// The half-length of the bar, in Ly, or zero if there isn't one.
+  pub bar_length: f64,
+  /// How flattened the bulge and halo are, from zero (round) to one (flat).
+  pub ellipticity: f64,
+  /// The density at the center of the thin disk, in stars per cubic light
+  /// year.
+  pub thin_disk_density: f64,
+  /// The radial scale length of the thin disk, in Ly.
+  pub thin_disk_scale_length: f64,
+  /// The vertical scale height of the thin disk, in Ly.
+  pub thin_disk_scale_height: f64,
+  /// The density at the center of the thick disk, in stars per cubic light
+  /// year.
+  pub thick_disk_density: f64,
+  /// The radial scale length of the thick disk, in Ly.
+  pub thick_disk_scale_length: f64,
+  /// The vertical scale height of the thick disk, in Ly.
+  pub thick_disk_scale_height: f64,
+  /// The density within the core of the halo, in stars per cubic light year.
+  pub halo_density: f64,
+  /// The radius of the core of the halo, in Ly.
+  pub halo_core_radius: f64,
+  /// The number of spiral arms.
+  pub arm_count: u8,
+  /// The pitch angle of the spiral arms, in degrees.
+  pub arm_pitch_angle: f64,
+  /// The half-width of a spiral arm, in Ly.
+  pub arm_width: f64,
+  /// How much denser the thin disk is along the middle of an arm.
+  pub arm_contrast: f64,
+}
+
+impl GalacticStructure {
+  /// Shrink (or grow) the structure to a new radius, keeping its densities.
+  #[named]
+  pub fn scaled_to(&self, radius: f64) -> Self {
+    trace_enter!();
+    let scale = radius / self.radius;
+    trace_var!(scale);
+    let result = Self {
+      radius,
+      bulge_radius: self.bulge_radius * scale,
+      bar_length: self.bar_length * scale,
+      thin_disk_scale_length: self.thin_disk_scale_length * scale,
+      thin_disk_scale_height: self.thin_disk_scale_height * scale,
+      thick_disk_scale_length: self.thick_disk_scale_length * scale,
+      thick_disk_scale_height: self.thick_disk_scale_height * scale,
+      halo_core_radius: self.halo_core_radius * scale,
+      arm_width: self.arm_width * scale,
+      ..*self
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// The equivalent of the Sun's distance from the center, in Ly.
+  #[named]
+  pub fn get_reference_radius(&self) -> f64 {
+    trace_enter!();
+    let result = SOLAR_GALACTIC_RADIUS * self.radius / GALAXY_RADIUS;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// The distance to the centerline of the nearest spiral arm, in Ly.
+  ///
+  /// The arms are logarithmic spirals, starting at the end of the bar or the
+  /// edge of the bulge.
+  #[named]
+  pub fn get_arm_distance(&self, radius: f64, azimuth: f64) -> f64 {
+    trace_enter!();
+    trace_var!(radius);
+    trace_var!(azimuth);
+    let arm_radius = self.bulge_radius.max(self.bar_length);
+    trace_var!(arm_radius);
+    if self.arm_count == 0 || radius <= arm_radius || radius > self.radius {
+      trace_exit!();
+      return f64::INFINITY;
+    }
+    let pitch_angle = self.arm_pitch_angle.to_radians();
+    trace_var!(pitch_angle);
+    let arm_azimuth = (radius / arm_radius).ln() / pitch_angle.tan();
+    trace_var!(arm_azimuth);
+    let arm_spacing = 2.0 * PI / self.arm_count as f64;
+    trace_var!(arm_spacing);
+    let offset = (azimuth.to_radians() - arm_azimuth).rem_euclid(arm_spacing);
+    let offset = offset.min(arm_spacing - offset);
+    trace_var!(offset);
+    let result = radius * offset * pitch_angle.sin();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// The density of one component, in stars per cubic light year.
+  #[named]
+  pub fn get_component_density(&self, component: GalacticComponent, radius: f64, height: f64, azimuth: f64) -> f64 {
+    trace_enter!();
+    trace_var!(component);
+    use GalacticComponent::*;
+    let height = height.abs();
+    let in_disk = radius <= self.radius;
+    let result = match component {
+      Bulge => {
+        let x = radius * azimuth.to_radians().cos() / self.bulge_radius.max(self.bar_length);
+        let y = radius * azimuth.to_radians().sin() / self.bulge_radius;
+        let z = height / (self.bulge_radius * (1.0 - self.ellipticity));
+        self.bulge_density * (-(x.powf(2.0) + y.powf(2.0) + z.powf(2.0))).exp()
+      },
+      ThinDisk if in_disk => {
+        let arm_distance = self.get_arm_distance(radius, azimuth);
+        let arm_factor = 1.0 + self.arm_contrast * (-(arm_distance / self.arm_width).powf(2.0)).exp();
+        arm_factor
+          * self.thin_disk_density
+          * (-radius / self.thin_disk_scale_length).exp()
+          * (-height / self.thin_disk_scale_height).exp()
+      },
+      ThickDisk if in_disk => {
+        self.thick_disk_density
+          * (-radius / self.thick_disk_scale_length).exp()
+          * (-height / self.thick_disk_scale_height).exp()
+      },
+      ThinDisk | ThickDisk => 0.0,
+      Halo => {
+        let flattened_height = height / (1.0 - self.ellipticity);
+        let spherical_radius = (radius.powf(2.0) + flattened_height.powf(2.0)).sqrt();
+        self.halo_density * (spherical_radius.max(self.halo_core_radius) / self.halo_core_radius).powf(-3.0)
+      },
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// The density of all components together, in stars per cubic light year.
+  #[named]
+  pub fn get_stellar_density(&self, radius: f64, height: f64, azimuth: f64) -> f64 {
+    trace_enter!();
+    use GalacticComponent::*;
+    let result = [Bulge, ThinDisk, ThickDisk, Halo]
+      .into_iter()
+      .map(|component| self.get_component_density(component, radius, height, azimuth))
+      .sum();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// The component most of the stars at this position belong to.
+  #[named]
+  pub fn get_dominant_component(&self, radius: f64, height: f64, azimuth: f64) -> GalacticComponent {
+    trace_enter!();
+    use GalacticComponent::*;
+    let result = [Bulge, ThinDisk, ThickDisk, Halo]
+      .into_iter()
+      .map(|component| {
+        (
+          component,
+          self.get_component_density(component, radius, height, azimuth),
+        )
+      })
+      .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+      .map(|(component, _)| component)
+      .unwrap();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl Default for GalacticStructure {
+  /// Something like the Milky Way.
+  fn default() -> Self {
+    Self {
+      radius: GALAXY_RADIUS,
+      bulge_radius: BULGE_RADIUS,
+      bulge_density: BULGE_DENSITY,
+      bar_length: 0.0,
+      ellipticity: 0.0,
+      thin_disk_density: THIN_DISK_DENSITY,
+      thin_disk_scale_length: THIN_DISK_SCALE_LENGTH,
+      thin_disk_scale_height: THIN_DISK_SCALE_HEIGHT,
+      thick_disk_density: THICK_DISK_DENSITY,
+      thick_disk_scale_length: THICK_DISK_SCALE_LENGTH,
+      thick_disk_scale_height: THICK_DISK_SCALE_HEIGHT,
+      halo_density: HALO_DENSITY,
+      halo_core_radius: HALO_CORE_RADIUS,
+      arm_count: ARM_COUNT,
+      arm_pitch_angle: ARM_PITCH_ANGLE,
+      arm_width: ARM_WIDTH,
+      arm_contrast: ARM_CONTRAST,
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::astronomy::stellar_neighborhood::constants::STELLAR_NEIGHBORHOOD_DENSITY;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_stellar_density() {
+    init();
+    trace_enter!();
+    use GalacticComponent::*;
+    let structure = GalacticStructure::default();
+    let local = structure.get_component_density(ThinDisk, SOLAR_GALACTIC_RADIUS, 0.0, 45.0);
+    print_var!(local);
+    assert!(local >= 0.99 * STELLAR_NEIGHBORHOOD_DENSITY);
+    assert!(local <= 1.01 * STELLAR_NEIGHBORHOOD_DENSITY * (1.0 + ARM_CONTRAST));
+    let inner = structure.get_stellar_density(10_000.0, 0.0, 45.0);
+    let outer = structure.get_stellar_density(40_000.0, 0.0, 45.0);
+    let above = structure.get_stellar_density(SOLAR_GALACTIC_RADIUS, 5_000.0, 45.0);
+    print_var!((inner, outer, above));
+    assert!(inner > local);
+    assert!(outer < local);
+    assert!(above < local);
+    assert_eq!(structure.get_component_density(ThinDisk, 60_000.0, 0.0, 0.0), 0.0);
+    assert_eq!(structure.get_dominant_component(0.0, 0.0, 0.0), Bulge);
+    assert_eq!(
+      structure.get_dominant_component(SOLAR_GALACTIC_RADIUS, 0.0, 0.0),
+      ThinDisk
+    );
+    assert_eq!(
+      structure.get_dominant_component(SOLAR_GALACTIC_RADIUS, 6_000.0, 0.0),
+      ThickDisk
+    );
+    assert_eq!(
+      structure.get_dominant_component(SOLAR_GALACTIC_RADIUS, 30_000.0, 0.0),
+      Halo
+    );
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_arm_distance() {
+    init();
+    trace_enter!();
+    let structure = GalacticStructure::default();
+    let arm_azimuth = ((SOLAR_GALACTIC_RADIUS / BULGE_RADIUS).ln() / ARM_PITCH_ANGLE.to_radians().tan()).to_degrees();
+    let on_arm = structure.get_arm_distance(SOLAR_GALACTIC_RADIUS, arm_azimuth);
+    let between_arms = structure.get_arm_distance(SOLAR_GALACTIC_RADIUS, arm_azimuth + 45.0);
+    print_var!((on_arm, between_arms));
+    assert!(on_arm < 1.0);
+    assert!(between_arms > ARM_WIDTH);
+    assert!(structure.get_arm_distance(1_000.0, 0.0).is_infinite());
+    trace_exit!();
+  }
+}
@@ -1,5 +1,56 @@
+use crate::math::probability::Probability;
+
 /// Minimum mass for a terrestrial planet, in Mjupiter.
 pub const MINIMUM_MASS: f64 = 0.1;
 
 /// Maximum mass for a terrestrial planet, in Mjupiter.
 pub const MAXIMUM_MASS: f64 = 14.0;
+
+/// The density of Jupiter, in g/cm³.
+pub const JUPITER_DENSITY: f64 = 1.326;
+
+/// The surface gravity of Jupiter, in Gearth.
+pub const JUPITER_SURFACE_GRAVITY: f64 = 2.528;
+
+/// The escape velocity of Jupiter, in km/s.
+pub const JUPITER_ESCAPE_VELOCITY: f64 = 60.20;
+
+/// The rotational period of Jupiter, in Dearth.
+pub const JUPITER_ROTATIONAL_PERIOD: f64 = 0.41354;
+
+/// The axial tilt of Jupiter, in degrees.
+pub const JUPITER_AXIAL_TILT: f64 = 3.13;
+
+/// Minimum axial tilt, in degrees.
+pub const MINIMUM_AXIAL_TILT: f64 = 0.0;
+
+/// Maximum axial tilt, in degrees.
+///
+/// Saturn and Neptune both sit near here; anything much more takes a giant
+/// impact, and that's an ice giant's game.
+pub const MAXIMUM_AXIAL_TILT: f64 = 30.0;
+
+/// Minimum rotational period, in Dearth.
+pub const MINIMUM_ROTATIONAL_PERIOD: f64 = 0.35;
+
+/// Maximum rotational period, in Dearth.
+pub const MAXIMUM_ROTATIONAL_PERIOD: f64 = 0.75;
+
+/// The probability that a planet inside the frost line is a gas giant that
+/// formed further out and migrated inward, around a star with the Sun's
+/// metallicity.
+///
+/// About one Sun-like star in a hundred has a hot Jupiter.
+pub const MIGRATION_PROBABILITY: Probability = Probability::from_const(0.01);
+
+/// The number of belts and zones across Jupiter, pole to pole.
+///
+/// Faster rotators have narrower jets, so more of them.
+pub const JUPITER_BAND_COUNT: f64 = 12.0;
+
+/// How far turbulence shifts the edges of the bands, in bands.
+pub const BAND_TURBULENCE: f64 = 0.35;
+
+/// How much more the bands are stretched along lines of latitude than across
+/// them.
+pub const BAND_STRETCH: f64 = 4.0;
@@ -0,0 +1,72 @@
+use std::ops::Range;
+
+use crate::astronomy::gas_giant_planet::constraints::Constraints;
+use crate::math::probability::Probability;
+use crate::math::range::check_range;
+use crate::math::range::error::Error;
+
+/// A fluent builder for gas giant constraints.
+///
+/// Nothing is checked until `build()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Builder {
+  constraints: Constraints,
+}
+
+impl Builder {
+  /// Restrict the mass, in Mjupiter.
+  pub fn mass_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_mass = Some(range.start);
+    self.constraints.maximum_mass = Some(range.end);
+    self
+  }
+
+  /// Restrict the rotational period, in Dearth.
+  pub fn rotational_period_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_rotational_period = Some(range.start);
+    self.constraints.maximum_rotational_period = Some(range.end);
+    self
+  }
+
+  /// Restrict the axial tilt, in degrees.
+  pub fn axial_tilt_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_axial_tilt = Some(range.start);
+    self.constraints.maximum_axial_tilt = Some(range.end);
+    self
+  }
+
+  /// Set the probability that a planet inside the frost line is a migrated
+  /// gas giant.
+  pub fn migration_probability(mut self, probability: Probability) -> Self {
+    self.constraints.migration_probability = Some(probability);
+    self
+  }
+
+  /// Check the constraints and return them.
+  #[named]
+  pub fn build(self) -> Result<Constraints, Error> {
+    trace_enter!();
+    let constraints = self.constraints;
+    trace_var!(constraints);
+    check_range(
+      "mass",
+      constraints.minimum_mass,
+      constraints.maximum_mass,
+      (0.0, f64::INFINITY),
+    )?;
+    check_range(
+      "rotational period",
+      constraints.minimum_rotational_period,
+      constraints.maximum_rotational_period,
+      (0.0, f64::INFINITY),
+    )?;
+    check_range(
+      "axial tilt",
+      constraints.minimum_axial_tilt,
+      constraints.maximum_axial_tilt,
+      (0.0, 180.0),
+    )?;
+    trace_exit!();
+    Ok(constraints)
+  }
+}
@@ -3,8 +3,18 @@ use rand_distr::{Distribution, LogNormal};
 
 use crate::astronomy::gas_giant_planet::constants::*;
 use crate::astronomy::gas_giant_planet::error::Error;
+use crate::astronomy::gas_giant_planet::math::radiation_belt::{get_magnetic_moment, get_magnetopause_distance};
 use crate::astronomy::gas_giant_planet::GasGiantPlanet;
 use crate::astronomy::host_star::HostStar;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::planet::math::oblateness::{
+  get_equatorial_and_polar_radii, get_j2, get_oblateness, get_rotational_parameter, GAS_GIANT_MOMENT_OF_INERTIA_FACTOR,
+};
+use crate::astronomy::planet::seasons::Seasons;
+use crate::math::probability::Probability;
+use crate::merge::Merge;
+pub mod builder;
+use builder::Builder;
 
 /// Constraints for creating a planet.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -13,9 +23,27 @@ pub struct Constraints {
   pub minimum_mass: Option<f64>,
   /// The maximum mass.
   pub maximum_mass: Option<f64>,
+  /// The minimum rotational period.
+  pub minimum_rotational_period: Option<f64>,
+  /// The maximum rotational period.
+  pub maximum_rotational_period: Option<f64>,
+  /// The minimum axial tilt.
+  pub minimum_axial_tilt: Option<f64>,
+  /// The maximum axial tilt.
+  pub maximum_axial_tilt: Option<f64>,
+  /// The probability that a planet inside the frost line is a gas giant
+  /// that migrated there.
+  ///
+  /// If this isn't given, it's worked out from the host star's metallicity.
+  pub migration_probability: Option<Probability>,
 }
 
 impl Constraints {
+  /// Start building constraints fluently.
+  pub fn builder() -> Builder {
+    Builder::default()
+  }
+
   /// Generate.
   #[named]
   pub fn generate<R: Rng + ?Sized>(
@@ -30,22 +58,46 @@ impl Constraints {
     let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
     trace_var!(maximum_mass);
     let log_normal = LogNormal::new(0.2, 0.5).unwrap();
-    let mass = log_normal.sample(rng);
+    let mass: f64 = log_normal.sample(rng);
+    let mass = mass.clamp(minimum_mass, maximum_mass);
     trace_var!(mass);
     let mut result = GasGiantPlanet::from_mass(mass)?;
-    result.semi_major_axis = distance;
-    let orbital_eccentricity = 0.0167;
-    result.orbital_eccentricity = orbital_eccentricity;
-    trace_var!(orbital_eccentricity);
-    let perihelion = (1.0 - orbital_eccentricity) * distance;
-    result.perihelion = perihelion;
-    trace_var!(perihelion);
-    let aphelion = (1.0 + orbital_eccentricity) * distance;
-    result.aphelion = aphelion;
-    trace_var!(aphelion);
+    let minimum_rotational_period = self.minimum_rotational_period.unwrap_or(MINIMUM_ROTATIONAL_PERIOD);
+    trace_var!(minimum_rotational_period);
+    let maximum_rotational_period = self.maximum_rotational_period.unwrap_or(MAXIMUM_ROTATIONAL_PERIOD);
+    trace_var!(maximum_rotational_period);
+    let rotation_period = rng.gen_range(minimum_rotational_period..=maximum_rotational_period);
+    trace_var!(rotation_period);
+    let minimum_axial_tilt = self.minimum_axial_tilt.unwrap_or(MINIMUM_AXIAL_TILT);
+    trace_var!(minimum_axial_tilt);
+    let maximum_axial_tilt = self.maximum_axial_tilt.unwrap_or(MAXIMUM_AXIAL_TILT);
+    trace_var!(maximum_axial_tilt);
+    let axial_tilt = rng.gen_range(minimum_axial_tilt..=maximum_axial_tilt);
+    trace_var!(axial_tilt);
+    result.axial_tilt = axial_tilt;
+    let rotational_parameter = get_rotational_parameter(rotation_period, result.density * JUPITER_DENSITY);
+    trace_var!(rotational_parameter);
+    let oblateness = get_oblateness(rotational_parameter, GAS_GIANT_MOMENT_OF_INERTIA_FACTOR);
+    trace_var!(oblateness);
+    let (equatorial_radius, polar_radius) = get_equatorial_and_polar_radii(result.radius, oblateness);
+    result.rotation_period = rotation_period;
+    result.oblateness = oblateness;
+    result.equatorial_radius = equatorial_radius;
+    result.polar_radius = polar_radius;
+    result.j2 = get_j2(oblateness, rotational_parameter);
+    let orbit = OrbitalElements::new(distance, result.orbit.eccentricity).with_random_orientation(rng);
+    result.orbit = orbit;
+    trace_var!(orbit);
     let orbital_period = distance.powf(3.0).sqrt();
     result.orbital_period = orbital_period;
     trace_var!(orbital_period);
+    result.seasons = Seasons::new(axial_tilt, orbit.eccentricity, orbital_period);
+    let magnetic_moment = get_magnetic_moment(result.mass, result.radius, rotation_period);
+    result.magnetic_moment = magnetic_moment;
+    trace_var!(magnetic_moment);
+    let magnetopause_distance = get_magnetopause_distance(magnetic_moment, distance);
+    result.magnetopause_distance = magnetopause_distance;
+    trace_var!(magnetopause_distance);
     trace_var!(result);
     trace_exit!();
     Ok(result)
@@ -57,9 +109,33 @@ impl Default for Constraints {
   fn default() -> Self {
     let minimum_mass = None;
     let maximum_mass = None;
+    let minimum_rotational_period = None;
+    let maximum_rotational_period = None;
+    let minimum_axial_tilt = None;
+    let maximum_axial_tilt = None;
+    let migration_probability = None;
     Self {
       minimum_mass,
       maximum_mass,
+      minimum_rotational_period,
+      maximum_rotational_period,
+      minimum_axial_tilt,
+      maximum_axial_tilt,
+      migration_probability,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+      minimum_rotational_period: overrides.minimum_rotational_period.or(self.minimum_rotational_period),
+      maximum_rotational_period: overrides.maximum_rotational_period.or(self.maximum_rotational_period),
+      minimum_axial_tilt: overrides.minimum_axial_tilt.or(self.minimum_axial_tilt),
+      maximum_axial_tilt: overrides.maximum_axial_tilt.or(self.maximum_axial_tilt),
+      migration_probability: overrides.migration_probability.or(self.migration_probability),
     }
   }
 }
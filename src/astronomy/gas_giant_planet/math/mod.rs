@@ -0,0 +1 @@
+pub mod radiation_belt;
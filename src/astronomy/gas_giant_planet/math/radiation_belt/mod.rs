@@ -0,0 +1,144 @@
+use crate::astronomy::gas_giant_planet::constants::JUPITER_ROTATIONAL_PERIOD;
+
+/// Jupiter's magnetopause, in Rjupiter.
+///
+/// This breathes between about 45 and 100 depending on the solar wind.
+pub const JUPITER_MAGNETOPAUSE_DISTANCE: f64 = 63.0;
+
+/// Jupiter's semi-major axis, in AU.
+pub const JUPITER_SEMI_MAJOR_AXIS: f64 = 5.2;
+
+/// The orbit of Europa, in Rjupiter; the reference point for dose rates.
+pub const EUROPA_ORBIT: f64 = 9.4;
+
+/// The surface dose rate on Europa, in Sv/day.
+pub const EUROPA_DOSE_RATE: f64 = 5.4;
+
+/// How quickly the dose rate falls off with distance, per Rjupiter.
+///
+/// Fitted to surface dose rates on the Galilean moons, which fall from tens
+/// of Sv/day at Io to a fraction of a mSv/day at Callisto.
+pub const RADIATION_BELT_FALLOFF: f64 = 0.63;
+
+/// The dose rate above which an unshielded human won't last long, in Sv/day.
+pub const LETHAL_DOSE_RATE: f64 = 1.0;
+
+/// Estimate the magnetic moment of a gas giant, relative to Jupiter's.
+///
+/// This leans on the "magnetic Bode's law", which says that the magnetic
+/// moment is roughly proportional to the angular momentum.  Mass is in
+/// Mjupiter, radius in Rjupiter, rotation period in Dearth.
+#[named]
+pub fn get_magnetic_moment(mass: f64, radius: f64, rotation_period: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(radius);
+  trace_var!(rotation_period);
+  let result = mass * radius.powf(2.0) * JUPITER_ROTATIONAL_PERIOD / rotation_period;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Estimate the distance to the magnetopause, in Rjupiter.
+///
+/// The stand-off distance grows with the cube root of the magnetic moment
+/// and shrinks with the sixth root of the solar wind pressure, which falls
+/// off with the square of the distance from the host star (in AU).
+#[named]
+pub fn get_magnetopause_distance(magnetic_moment: f64, distance: f64) -> f64 {
+  trace_enter!();
+  trace_var!(magnetic_moment);
+  trace_var!(distance);
+  let result = JUPITER_MAGNETOPAUSE_DISTANCE * (magnetic_moment * distance / JUPITER_SEMI_MAJOR_AXIS).powf(1.0 / 3.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Estimate the radiation dose rate at the surface of an unshielded body
+/// orbiting a gas giant, in Sv/day.
+///
+/// The belts scale in extent with the cube root of the magnetic moment and
+/// in intensity with the moment itself.  Nothing outside the magnetopause is
+/// trapped, so the dose there is taken to be zero.  Distances are in
+/// Rjupiter.
+#[named]
+pub fn get_radiation_dose_rate(magnetic_moment: f64, magnetopause_distance: f64, distance: f64) -> f64 {
+  trace_enter!();
+  trace_var!(magnetic_moment);
+  trace_var!(magnetopause_distance);
+  trace_var!(distance);
+  let result = match distance {
+    distance if distance > magnetopause_distance => 0.0,
+    distance => {
+      let scaled_distance = distance / magnetic_moment.powf(1.0 / 3.0);
+      trace_var!(scaled_distance);
+      EUROPA_DOSE_RATE * magnetic_moment * (-RADIATION_BELT_FALLOFF * (scaled_distance - EUROPA_ORBIT)).exp()
+    },
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Estimate how far out the radiation belts stay lethal, in Rjupiter.
+///
+/// This is where the dose rate falls to `LETHAL_DOSE_RATE`, or the
+/// magnetopause if that comes first; zero if the belts are nowhere lethal.
+#[named]
+pub fn get_lethal_radiation_distance(magnetic_moment: f64, magnetopause_distance: f64) -> f64 {
+  trace_enter!();
+  trace_var!(magnetic_moment);
+  trace_var!(magnetopause_distance);
+  let peak_ratio = EUROPA_DOSE_RATE * magnetic_moment / LETHAL_DOSE_RATE;
+  trace_var!(peak_ratio);
+  let scaled_distance = EUROPA_ORBIT + peak_ratio.ln() / RADIATION_BELT_FALLOFF;
+  trace_var!(scaled_distance);
+  let result = (scaled_distance * magnetic_moment.powf(1.0 / 3.0))
+    .min(magnetopause_distance)
+    .max(0.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_jupiter() {
+    init();
+    trace_enter!();
+    let magnetic_moment = get_magnetic_moment(1.0, 1.0, JUPITER_ROTATIONAL_PERIOD);
+    assert_approx_eq!(magnetic_moment, 1.0);
+    let magnetopause_distance = get_magnetopause_distance(magnetic_moment, JUPITER_SEMI_MAJOR_AXIS);
+    assert_approx_eq!(magnetopause_distance, JUPITER_MAGNETOPAUSE_DISTANCE);
+    // Io, Europa, Ganymede, Callisto.
+    let io = get_radiation_dose_rate(magnetic_moment, magnetopause_distance, 5.9);
+    let europa = get_radiation_dose_rate(magnetic_moment, magnetopause_distance, EUROPA_ORBIT);
+    let ganymede = get_radiation_dose_rate(magnetic_moment, magnetopause_distance, 15.0);
+    let callisto = get_radiation_dose_rate(magnetic_moment, magnetopause_distance, 26.3);
+    print_var!((io, europa, ganymede, callisto));
+    assert!(io > LETHAL_DOSE_RATE);
+    assert!(europa > LETHAL_DOSE_RATE);
+    assert!(ganymede < LETHAL_DOSE_RATE);
+    assert!(callisto < 0.001);
+    assert_approx_eq!(
+      get_radiation_dose_rate(magnetic_moment, magnetopause_distance, 100.0),
+      0.0
+    );
+    let lethal_distance = get_lethal_radiation_distance(magnetic_moment, magnetopause_distance);
+    print_var!(lethal_distance);
+    assert!(EUROPA_ORBIT < lethal_distance && lethal_distance < 15.0);
+    assert_approx_eq!(
+      get_radiation_dose_rate(magnetic_moment, magnetopause_distance, lethal_distance),
+      LETHAL_DOSE_RATE
+    );
+    trace_exit!();
+  }
+}
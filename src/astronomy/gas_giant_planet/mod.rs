@@ -1,27 +1,57 @@
+use serde::Serialize;
+
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::planet::math::oblateness::{
+  get_equatorial_and_polar_radii, get_j2, get_oblateness, get_rotational_parameter, GAS_GIANT_MOMENT_OF_INERTIA_FACTOR,
+};
+use crate::astronomy::planet::seasons::Seasons;
+use crate::math::noise::{FbmOptions, Noise};
+
 pub mod constants;
+use constants::*;
 pub mod constraints;
 pub mod error;
 use error::Error;
+pub(crate) mod math;
+use math::radiation_belt::{get_magnetic_moment, get_magnetopause_distance, get_radiation_dose_rate};
 
 /// The `GasGiantPlanet` type.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct GasGiantPlanet {
+  /// The name, given once the planet's place in its system is known.
+  pub name: String,
   /// Mass, in Mjupiter.
   pub mass: f64,
   /// Density, in Djupiter.
   pub density: f64,
   /// Radius, in Rjupiter.
   pub radius: f64,
-  /// Semi-Major Axis.
-  pub semi_major_axis: f64,
-  /// Orbital eccentricity.
-  pub orbital_eccentricity: f64,
-  /// Perihelion.
-  pub perihelion: f64,
-  /// Aphelion.
-  pub aphelion: f64,
+  /// Rotation period, in Dearth.
+  pub rotation_period: f64,
+  /// Axial tilt (0-180º).
+  pub axial_tilt: f64,
+  /// Flattening caused by rotation.
+  pub oblateness: f64,
+  /// Equatorial radius, in Rjupiter.
+  pub equatorial_radius: f64,
+  /// Polar radius, in Rjupiter.
+  pub polar_radius: f64,
+  /// The J2 gravitational harmonic.
+  pub j2: f64,
+  /// Surface gravity (at the 1-bar level), in Gearth.
+  pub surface_gravity: f64,
+  /// Escape velocity, in km/s.
+  pub escape_velocity: f64,
+  /// Magnetic moment, relative to Jupiter's.
+  pub magnetic_moment: f64,
+  /// Distance to the magnetopause, in Rjupiter.
+  pub magnetopause_distance: f64,
+  /// Orbit around the host star, in AU.
+  pub orbit: OrbitalElements,
   /// Orbital period, in Earth years.
   pub orbital_period: f64,
+  /// How much the seasons vary.
+  pub seasons: Seasons,
 }
 
 impl GasGiantPlanet {
@@ -35,28 +65,153 @@ impl GasGiantPlanet {
     // @todo: fix.
     let radius: f64 = 1.0;
     trace_var!(radius);
+    let rotation_period = JUPITER_ROTATIONAL_PERIOD;
+    trace_var!(rotation_period);
+    let axial_tilt = JUPITER_AXIAL_TILT;
+    trace_var!(axial_tilt);
+    let rotational_parameter = get_rotational_parameter(rotation_period, density * JUPITER_DENSITY);
+    trace_var!(rotational_parameter);
+    let oblateness = get_oblateness(rotational_parameter, GAS_GIANT_MOMENT_OF_INERTIA_FACTOR);
+    trace_var!(oblateness);
+    let (equatorial_radius, polar_radius) = get_equatorial_and_polar_radii(radius, oblateness);
+    trace_var!(equatorial_radius);
+    trace_var!(polar_radius);
+    let j2 = get_j2(oblateness, rotational_parameter);
+    trace_var!(j2);
+    let surface_gravity = JUPITER_SURFACE_GRAVITY * mass / radius.powf(2.0);
+    trace_var!(surface_gravity);
+    let escape_velocity = JUPITER_ESCAPE_VELOCITY * (mass / radius).sqrt();
+    trace_var!(escape_velocity);
     let semi_major_axis: f64 = 5.2;
     trace_var!(semi_major_axis);
-    let orbital_eccentricity = 0.0167;
-    trace_var!(orbital_eccentricity);
-    let perihelion = (1.0 - orbital_eccentricity) * semi_major_axis;
-    trace_var!(perihelion);
-    let aphelion = (1.0 + orbital_eccentricity) * semi_major_axis;
-    trace_var!(aphelion);
+    let orbit = OrbitalElements::new(semi_major_axis, 0.0167);
+    trace_var!(orbit);
     let orbital_period = semi_major_axis.powf(3.0).sqrt();
     trace_var!(orbital_period);
+    let seasons = Seasons::new(axial_tilt, orbit.eccentricity, orbital_period);
+    trace_var!(seasons);
+    let magnetic_moment = get_magnetic_moment(mass, radius, rotation_period);
+    trace_var!(magnetic_moment);
+    let magnetopause_distance = get_magnetopause_distance(magnetic_moment, semi_major_axis);
+    trace_var!(magnetopause_distance);
+    let name = String::new();
     let result = Self {
+      name,
       mass,
       density,
       radius,
-      semi_major_axis,
-      orbital_eccentricity,
-      perihelion,
-      aphelion,
+      rotation_period,
+      axial_tilt,
+      oblateness,
+      equatorial_radius,
+      polar_radius,
+      j2,
+      surface_gravity,
+      escape_velocity,
+      magnetic_moment,
+      magnetopause_distance,
+      orbit,
       orbital_period,
+      seasons,
     };
     trace_var!(result);
     trace_exit!();
     Ok(result)
   }
+
+  /// Get the radiation dose rate at a given distance, in Sv/day.
+  ///
+  /// The distance is measured in Rjupiter from the center of the planet.
+  #[named]
+  pub fn get_radiation_dose_rate(&self, distance: f64) -> f64 {
+    trace_enter!();
+    trace_var!(distance);
+    let result = get_radiation_dose_rate(self.magnetic_moment, self.magnetopause_distance, distance);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the number of belts and zones from pole to pole.
+  #[named]
+  pub fn get_band_count(&self) -> f64 {
+    trace_enter!();
+    let result = JUPITER_BAND_COUNT * JUPITER_ROTATIONAL_PERIOD / self.rotation_period;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the noise that churns the cloud bands, seeded by the planet's name.
+  ///
+  /// Build this once and reuse it for every point sampled.
+  #[named]
+  pub fn get_band_noise(&self) -> Noise {
+    trace_enter!();
+    let result = Noise::from_id(&format!("{}:bands", self.name));
+    trace_exit!();
+    result
+  }
+
+  /// Get the brightness of the cloud tops at a point, in 0.0..=1.0.
+  ///
+  /// The latitude and longitude are in degrees.  Bright zones alternate with
+  /// dark belts, and turbulence drawn from the band noise ripples the edges
+  /// between them.
+  #[named]
+  pub fn get_band_brightness(&self, noise: &Noise, latitude: f64, longitude: f64) -> f64 {
+    trace_enter!();
+    trace_var!(latitude);
+    trace_var!(longitude);
+    let band_count = self.get_band_count();
+    trace_var!(band_count);
+    let (latitude, longitude) = (latitude.to_radians(), longitude.to_radians());
+    // Bands are evenly spaced in the sine of the latitude, which keeps them
+    // from bunching up at the poles.
+    let band = (latitude.sin() + 1.0) / 2.0 * band_count;
+    trace_var!(band);
+    let x = latitude.cos() * longitude.cos();
+    let y = latitude.cos() * longitude.sin();
+    let turbulence = noise.get_fbm_3d(x, y, band * BAND_STRETCH, &FbmOptions::default());
+    trace_var!(turbulence);
+    let result = 0.5 + 0.5 * ((band + BAND_TURBULENCE * turbulence) * std::f64::consts::PI).cos();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_band_brightness() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut planet = GasGiantPlanet::from_mass(1.0)?;
+    planet.name = "Steve V".to_string();
+    planet.rotation_period = JUPITER_ROTATIONAL_PERIOD;
+    assert_approx_eq!(planet.get_band_count(), JUPITER_BAND_COUNT);
+    let noise = planet.get_band_noise();
+    let brightnesses = (-90..=90)
+      .map(|latitude| planet.get_band_brightness(&noise, latitude as f64, 0.0))
+      .collect::<Vec<f64>>();
+    print_var!(brightnesses);
+    assert!(brightnesses.iter().all(|brightness| (0.0..=1.0).contains(brightness)));
+    assert!(brightnesses.iter().any(|brightness| *brightness > 0.9));
+    assert!(brightnesses.iter().any(|brightness| *brightness < 0.1));
+    // Longitude wraps around.
+    assert_approx_eq!(
+      planet.get_band_brightness(&noise, 20.0, -170.0),
+      planet.get_band_brightness(&noise, 20.0, 190.0)
+    );
+    planet.rotation_period *= 2.0;
+    assert_approx_eq!(planet.get_band_count(), JUPITER_BAND_COUNT / 2.0);
+    trace_exit!();
+    Ok(())
+  }
 }
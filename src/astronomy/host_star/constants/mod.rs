@@ -1,4 +1,38 @@
-/// The probability that a given star subsystem will be binary.
+use crate::math::probability::Probability;
+
+/// The probability that an O-class star has a companion.
 ///
-/// This probability might be slightly lower than actual.
-pub const BINARY_STAR_PROBABILITY: f64 = 0.25;
+/// Massive stars are nearly always born in pairs or worse (Duchêne & Kraus
+/// 2013); the figures fall steadily with mass from there.
+pub const CLASS_O_BINARY_PROBABILITY: Probability = Probability::from_const(0.8);
+
+/// The probability that a B-class star has a companion.
+pub const CLASS_B_BINARY_PROBABILITY: Probability = Probability::from_const(0.6);
+
+/// The probability that an A-class star has a companion.
+pub const CLASS_A_BINARY_PROBABILITY: Probability = Probability::from_const(0.5);
+
+/// The probability that an F-class star has a companion.
+pub const CLASS_F_BINARY_PROBABILITY: Probability = Probability::from_const(0.5);
+
+/// The probability that a G-class star has a companion.
+pub const CLASS_G_BINARY_PROBABILITY: Probability = Probability::from_const(0.45);
+
+/// The probability that a K-class star has a companion.
+pub const CLASS_K_BINARY_PROBABILITY: Probability = Probability::from_const(0.4);
+
+/// The probability that an M-class star has a companion.
+///
+/// Most M dwarfs are single, and since most stars are M dwarfs, so are most
+/// stars.
+pub const CLASS_M_BINARY_PROBABILITY: Probability = Probability::from_const(0.25);
+
+/// The probability that a given host star will be a stellar remnant.
+///
+/// Left to chance, we'd rather not; these have to be asked for.
+pub const STELLAR_REMNANT_PROBABILITY: Probability = Probability::NEVER;
+
+/// The probability that a given host star will be a brown dwarf.
+///
+/// Plenty of these are out there, but they have to be asked for too.
+pub const BROWN_DWARF_PROBABILITY: Probability = Probability::NEVER;
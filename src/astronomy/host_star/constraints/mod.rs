@@ -1,19 +1,48 @@
 use rand::prelude::*;
 use std::default::Default;
 
+use crate::astronomy::brown_dwarf::constraints::Constraints as BrownDwarfConstraints;
+use crate::astronomy::close_binary_star::constants::{MAXIMUM_COMBINED_MASS, MINIMUM_COMBINED_MASS};
 use crate::astronomy::close_binary_star::constraints::Constraints as CloseBinaryStarConstraints;
 use crate::astronomy::host_star::constants::*;
 use crate::astronomy::host_star::error::Error;
+use crate::astronomy::host_star::math::multiplicity::get_binary_probability;
 use crate::astronomy::host_star::HostStar;
 use crate::astronomy::star::constraints::Constraints as StarConstraints;
+use crate::astronomy::star::math::habitable_zone::HabitableZoneModel;
+use crate::astronomy::stellar_remnant::constraints::Constraints as StellarRemnantConstraints;
+use crate::generator::Generator;
+use crate::math::probability::Probability;
+use crate::merge::Merge;
 
 /// Constraints for creating a main-sequence host star.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Constraints {
+  /// The probability that we generate a close binary star.
+  ///
+  /// If this isn't given, it depends on the mass of the primary star.
+  pub binary_probability: Option<Probability>,
   /// Star constraints.
   pub star_constraints: Option<StarConstraints>,
   /// Close Binary Star constraints.
   pub close_binary_star_constraints: Option<CloseBinaryStarConstraints>,
+  /// The probability that we generate a stellar remnant.
+  ///
+  /// This is checked before anything else.
+  pub stellar_remnant_probability: Option<Probability>,
+  /// Stellar Remnant constraints.
+  pub stellar_remnant_constraints: Option<StellarRemnantConstraints>,
+  /// The probability that we generate a brown dwarf.
+  ///
+  /// This is checked after stellar remnants, but before anything else.
+  pub brown_dwarf_probability: Option<Probability>,
+  /// Brown Dwarf constraints.
+  pub brown_dwarf_constraints: Option<BrownDwarfConstraints>,
+  /// How the habitable zone of a star or close binary is drawn.
+  ///
+  /// This takes precedence over any model in the star constraints.
+  /// Stellar remnants and brown dwarfs always use the classic model.
+  pub habitable_zone_model: Option<HabitableZoneModel>,
 }
 
 impl Constraints {
@@ -33,36 +62,170 @@ impl Constraints {
     result
   }
 
+  /// Generate a stellar remnant host star.
+  #[named]
+  pub fn stellar_remnant() -> Self {
+    trace_enter!();
+    let stellar_remnant_probability = Some(Probability::ALWAYS);
+    let result = Self {
+      stellar_remnant_probability,
+      ..Constraints::default()
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Generate a brown dwarf host star.
+  #[named]
+  pub fn brown_dwarf() -> Self {
+    trace_enter!();
+    let brown_dwarf_probability = Some(Probability::ALWAYS);
+    let result = Self {
+      brown_dwarf_probability,
+      ..Constraints::default()
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Generate.
   #[named]
   pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<HostStar, Error> {
     trace_enter!();
     use HostStar::*;
-    let is_solitary: bool = rng.gen_range(0.0..=1.0) > BINARY_STAR_PROBABILITY;
-    let result;
-    if is_solitary {
-      let constraints = self.star_constraints.unwrap_or(StarConstraints::default());
-      result = Star(constraints.generate(rng)?);
-    } else {
-      let constraints = self
-        .close_binary_star_constraints
-        .unwrap_or(CloseBinaryStarConstraints::default());
-      result = CloseBinaryStar(constraints.generate(rng)?);
+    let stellar_remnant_probability = self.stellar_remnant_probability.unwrap_or(STELLAR_REMNANT_PROBABILITY);
+    trace_var!(stellar_remnant_probability);
+    if stellar_remnant_probability.sample(rng) {
+      let constraints = self.stellar_remnant_constraints.unwrap_or_default();
+      let result = StellarRemnant(constraints.generate(rng)?);
+      trace_var!(result);
+      trace_exit!();
+      return Ok(result);
+    }
+    let brown_dwarf_probability = self.brown_dwarf_probability.unwrap_or(BROWN_DWARF_PROBABILITY);
+    trace_var!(brown_dwarf_probability);
+    if brown_dwarf_probability.sample(rng) {
+      let constraints = self.brown_dwarf_constraints.unwrap_or_default();
+      let result = BrownDwarf(constraints.generate(rng)?);
+      trace_var!(result);
+      trace_exit!();
+      return Ok(result);
     }
+    // We draw the star first, since how likely it is to have a companion
+    // depends on its mass.
+    let habitable_zone_model_constraints = StarConstraints {
+      habitable_zone_model: self.habitable_zone_model,
+      ..StarConstraints::default()
+    };
+    trace_var!(habitable_zone_model_constraints);
+    let star_constraints = self
+      .star_constraints
+      .unwrap_or(StarConstraints::default())
+      .merged_with(&habitable_zone_model_constraints);
+    trace_var!(star_constraints);
+    let star = star_constraints.generate(rng)?;
+    trace_var!(star);
+    let binary_probability = self
+      .binary_probability
+      .unwrap_or_else(|| get_binary_probability(star.mass));
+    trace_var!(binary_probability);
+    let result = match binary_probability.sample(rng) {
+      false => Star(star),
+      true => {
+        // Keep the pair in the neighborhood of the star we drew, if the
+        // constraints allow it.  Habitable pairs are already held to a narrow
+        // range of masses, and can't spare any more.
+        let mut constraints = self
+          .close_binary_star_constraints
+          .unwrap_or(CloseBinaryStarConstraints::default());
+        let minimum_combined_mass = constraints
+          .minimum_combined_mass
+          .unwrap_or(MINIMUM_COMBINED_MASS)
+          .max(star.mass);
+        let maximum_combined_mass = constraints
+          .maximum_combined_mass
+          .unwrap_or(MAXIMUM_COMBINED_MASS)
+          .min(2.0 * star.mass);
+        let enforce_habitability = constraints.enforce_habitability.unwrap_or(false);
+        if !enforce_habitability && minimum_combined_mass < maximum_combined_mass {
+          constraints.minimum_combined_mass = Some(minimum_combined_mass);
+          constraints.maximum_combined_mass = Some(maximum_combined_mass);
+        }
+        if self.habitable_zone_model.is_some() {
+          let component_constraints = constraints.star_constraints.unwrap_or(match enforce_habitability {
+            true => StarConstraints::habitable(),
+            false => StarConstraints::default(),
+          });
+          constraints.star_constraints = Some(component_constraints.merged_with(&habitable_zone_model_constraints));
+        }
+        trace_var!(constraints);
+        CloseBinaryStar(constraints.generate(rng)?)
+      },
+    };
     trace_var!(result);
     trace_exit!();
     Ok(result)
   }
 }
 
+impl Generator for Constraints {
+  type Output = HostStar;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<HostStar, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
 impl Default for Constraints {
   /// No constraints, just let it all hang out.
   fn default() -> Self {
+    let binary_probability = None;
     let star_constraints = None;
     let close_binary_star_constraints = None;
+    let stellar_remnant_probability = None;
+    let stellar_remnant_constraints = None;
+    let brown_dwarf_probability = None;
+    let brown_dwarf_constraints = None;
+    let habitable_zone_model = None;
     Self {
+      binary_probability,
       star_constraints,
       close_binary_star_constraints,
+      stellar_remnant_probability,
+      stellar_remnant_constraints,
+      brown_dwarf_probability,
+      brown_dwarf_constraints,
+      habitable_zone_model,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      binary_probability: overrides.binary_probability.or(self.binary_probability),
+      star_constraints: self.star_constraints.merged_with(&overrides.star_constraints),
+      close_binary_star_constraints: self
+        .close_binary_star_constraints
+        .merged_with(&overrides.close_binary_star_constraints),
+      stellar_remnant_probability: overrides
+        .stellar_remnant_probability
+        .or(self.stellar_remnant_probability),
+      stellar_remnant_constraints: self
+        .stellar_remnant_constraints
+        .merged_with(&overrides.stellar_remnant_constraints),
+      brown_dwarf_probability: overrides.brown_dwarf_probability.or(self.brown_dwarf_probability),
+      brown_dwarf_constraints: self
+        .brown_dwarf_constraints
+        .merged_with(&overrides.brown_dwarf_constraints),
+      habitable_zone_model: overrides.habitable_zone_model.or(self.habitable_zone_model),
     }
   }
 }
@@ -135,4 +298,89 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_never_binary() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints {
+      binary_probability: Some(Probability::NEVER),
+      ..Constraints::default()
+    };
+    for _ in 0..10 {
+      let host_star = constraints.generate(&mut rng)?;
+      assert!(matches!(host_star, HostStar::Star(_)));
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_stellar_remnant() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = Constraints::stellar_remnant().generate(&mut rng)?;
+    print_var!(host_star);
+    assert!(matches!(host_star, HostStar::StellarRemnant(_)));
+    assert_eq!(host_star.get_stellar_count(), 1);
+    assert!(!host_star.is_habitable());
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_brown_dwarf() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = Constraints::brown_dwarf().generate(&mut rng)?;
+    print_var!(host_star);
+    assert!(matches!(host_star, HostStar::BrownDwarf(_)));
+    assert_eq!(host_star.get_stellar_count(), 1);
+    assert!(!host_star.is_habitable());
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_habitable_zone_model() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    for habitable_zone_model in [
+      HabitableZoneModel::ConservativeKopparapu,
+      HabitableZoneModel::OptimisticKopparapu,
+    ] {
+      let constraints = Constraints {
+        habitable_zone_model: Some(habitable_zone_model),
+        ..Constraints::habitable()
+      };
+      for binary_probability in [Probability::NEVER, Probability::ALWAYS] {
+        let host_star = Constraints {
+          binary_probability: Some(binary_probability),
+          ..constraints
+        }
+        .generate(&mut rng)?;
+        print_var!(host_star);
+        let star = match &host_star {
+          HostStar::Star(star) => star,
+          HostStar::CloseBinaryStar(close_binary_star) => &close_binary_star.primary,
+          _ => panic!("expected a star or a close binary"),
+        };
+        assert_eq!(star.habitable_zone_model, habitable_zone_model);
+      }
+    }
+    trace_exit!();
+    Ok(())
+  }
 }
@@ -1,18 +1,28 @@
+use crate::astronomy::brown_dwarf::error::Error as BrownDwarfError;
 use crate::astronomy::close_binary_star::error::Error as CloseBinaryStarError;
 use crate::astronomy::star::error::Error as StarError;
+use crate::astronomy::stellar_remnant::error::Error as StellarRemnantError;
 
 /// Moons errors.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Error {
+  /// Brown Dwarf Error
+  BrownDwarfError(BrownDwarfError),
   /// Close Binary Star Error
   CloseBinaryStarError(CloseBinaryStarError),
   /// Star Error
   StarError(StarError),
+  /// Stellar Remnant Error
+  StellarRemnantError(StellarRemnantError),
 }
 
 honeyholt_define_brief!(Error, |error: &Error| {
   use Error::*;
   match error {
+    BrownDwarfError(brown_dwarf_error) => format!(
+      "an error occurred in the brown dwarf ({})",
+      honeyholt_brief!(brown_dwarf_error)
+    ),
     CloseBinaryStarError(close_binary_star_error) => format!(
       "an error occurred in the close binary star ({})",
       honeyholt_brief!(close_binary_star_error)
@@ -21,6 +31,10 @@ honeyholt_define_brief!(Error, |error: &Error| {
       "an error occurred while generating the star ({})",
       honeyholt_brief!(star_error)
     ),
+    StellarRemnantError(stellar_remnant_error) => format!(
+      "an error occurred in the stellar remnant ({})",
+      honeyholt_brief!(stellar_remnant_error)
+    ),
   }
 });
 
@@ -37,3 +51,17 @@ impl From<CloseBinaryStarError> for Error {
     Error::CloseBinaryStarError(error)
   }
 }
+
+impl From<StellarRemnantError> for Error {
+  #[named]
+  fn from(error: StellarRemnantError) -> Self {
+    Error::StellarRemnantError(error)
+  }
+}
+
+impl From<BrownDwarfError> for Error {
+  #[named]
+  fn from(error: BrownDwarfError) -> Self {
+    Error::BrownDwarfError(error)
+  }
+}
@@ -0,0 +1,50 @@
+use crate::astronomy::host_star::constants::*;
+use crate::astronomy::star::math::spectral_class::spectral_class_to_mass_range;
+use crate::math::probability::Probability;
+
+/// Get the probability that a star of the specified mass, in Msol, has a
+/// close companion.
+#[named]
+pub fn get_binary_probability(mass: f64) -> Probability {
+  trace_enter!();
+  trace_var!(mass);
+  let spectral_class = ['O', 'B', 'A', 'F', 'G', 'K']
+    .into_iter()
+    .find(|&spectral_class| mass >= spectral_class_to_mass_range(spectral_class).start)
+    .unwrap_or('M');
+  trace_var!(spectral_class);
+  let result = match spectral_class {
+    'O' => CLASS_O_BINARY_PROBABILITY,
+    'B' => CLASS_B_BINARY_PROBABILITY,
+    'A' => CLASS_A_BINARY_PROBABILITY,
+    'F' => CLASS_F_BINARY_PROBABILITY,
+    'G' => CLASS_G_BINARY_PROBABILITY,
+    'K' => CLASS_K_BINARY_PROBABILITY,
+    _ => CLASS_M_BINARY_PROBABILITY,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_binary_probability() {
+    init();
+    trace_enter!();
+    assert_eq!(get_binary_probability(40.0), CLASS_O_BINARY_PROBABILITY);
+    assert_eq!(get_binary_probability(1.0), CLASS_G_BINARY_PROBABILITY);
+    assert_eq!(get_binary_probability(0.2), CLASS_M_BINARY_PROBABILITY);
+    // Heavier stars are never less likely to have company.
+    let probabilities = [0.1, 0.5, 0.9, 1.2, 1.8, 5.0, 30.0].map(|mass| get_binary_probability(mass).get());
+    print_var!(probabilities);
+    assert!(probabilities.windows(2).all(|pair| pair[0] <= pair[1]));
+    trace_exit!();
+  }
+}
@@ -1,12 +1,22 @@
+use serde::Serialize;
+
+use crate::astronomy::brown_dwarf::constants::MAXIMUM_AGE as BROWN_DWARF_MAXIMUM_AGE;
+use crate::astronomy::brown_dwarf::BrownDwarf;
 use crate::astronomy::close_binary_star::CloseBinaryStar;
+use crate::astronomy::star::name::NameKind;
 use crate::astronomy::star::Star;
+use crate::astronomy::stellar_remnant::StellarRemnant;
 
 pub mod constants;
 pub mod constraints;
 pub mod error;
+pub(crate) mod math;
 use error::Error;
+pub mod satellite_zones;
+use satellite_zones::SatelliteZones;
 
-/// A `HostStar` is either a `Star` or a `CloseBinaryStar`.
+/// A `HostStar` is either a `Star` or a `CloseBinaryStar`, or, for more
+/// exotic systems, a `StellarRemnant` or a `BrownDwarf`.
 ///
 /// This may seem counterintuitive, but a `CloseBinaryStar` is actually more
 /// closely related to a `Star` than a `DistantBinaryStar`.  The reason for
@@ -14,12 +24,16 @@ use error::Error;
 /// `CloseBinaryStar`, but can only be in an orbit around one member of a
 /// `DistantBinaryStar`.  As a result, we handle `DistantBinaryStar` objects
 /// with a distinct class.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum HostStar {
   /// A single star.
   Star(Star),
   /// A close binary star.
   CloseBinaryStar(CloseBinaryStar),
+  /// A neutron star or black hole.
+  StellarRemnant(StellarRemnant),
+  /// A brown dwarf.
+  BrownDwarf(BrownDwarf),
 }
 
 impl HostStar {
@@ -33,6 +47,32 @@ impl HostStar {
     let result = match &self {
       Star(star) => star.current_age,
       CloseBinaryStar(close_binary_star) => close_binary_star.get_current_age(),
+      StellarRemnant(stellar_remnant) => stellar_remnant.current_age,
+      BrownDwarf(brown_dwarf) => brown_dwarf.current_age,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve or calculate the main-sequence lifetime of the stars.
+  ///
+  /// For a close binary, this is the shorter of the two.
+  ///
+  /// Calculated in Gyr.
+  #[named]
+  pub fn get_life_expectancy(&self) -> f64 {
+    trace_enter!();
+    use HostStar::*;
+    let result = match &self {
+      Star(star) => star.life_expectancy,
+      CloseBinaryStar(close_binary_star) => close_binary_star
+        .primary
+        .life_expectancy
+        .min(close_binary_star.secondary.life_expectancy),
+      StellarRemnant(stellar_remnant) => stellar_remnant.progenitor_life_expectancy,
+      // Brown dwarfs have no main sequence to leave; they outlast the galaxy.
+      BrownDwarf(_) => BROWN_DWARF_MAXIMUM_AGE,
     };
     trace_var!(result);
     trace_exit!();
@@ -49,6 +89,8 @@ impl HostStar {
     let result = match &self {
       Star(star) => star.mass,
       CloseBinaryStar(close_binary_star) => close_binary_star.get_stellar_mass(),
+      StellarRemnant(stellar_remnant) => stellar_remnant.mass,
+      BrownDwarf(brown_dwarf) => brown_dwarf.mass,
     };
     trace_var!(result);
     trace_exit!();
@@ -61,7 +103,7 @@ impl HostStar {
     trace_enter!();
     use HostStar::*;
     let result = match &self {
-      Star(_) => 1,
+      Star(_) | StellarRemnant(_) | BrownDwarf(_) => 1,
       CloseBinaryStar(_) => 2,
     };
     trace_var!(result);
@@ -77,12 +119,70 @@ impl HostStar {
     let result = match &self {
       Star(star) => star.frost_line,
       CloseBinaryStar(close_binary_star) => close_binary_star.frost_line,
+      StellarRemnant(stellar_remnant) => stellar_remnant.frost_line,
+      BrownDwarf(brown_dwarf) => brown_dwarf.frost_line,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve or calculate the metallicity, [Fe/H], in dex.
+  ///
+  /// Stellar remnants and brown dwarfs keep no record of what they were born
+  /// from, so we take them to be like the Sun.
+  #[named]
+  pub fn get_metallicity(&self) -> f64 {
+    trace_enter!();
+    use HostStar::*;
+    let result = match &self {
+      Star(star) => star.metallicity,
+      CloseBinaryStar(close_binary_star) => close_binary_star.primary.metallicity,
+      StellarRemnant(_) | BrownDwarf(_) => 0.0,
     };
     trace_var!(result);
     trace_exit!();
     result
   }
 
+  /// Set the metallicity, [Fe/H], in dex, of every star in the host star.
+  ///
+  /// Stellar remnants and brown dwarfs don't keep one, so they're left alone.
+  #[named]
+  pub fn set_metallicity(&mut self, metallicity: f64) {
+    trace_enter!();
+    trace_var!(metallicity);
+    use HostStar::*;
+    match self {
+      Star(star) => star.metallicity = metallicity,
+      CloseBinaryStar(close_binary_star) => {
+        close_binary_star.primary.metallicity = metallicity;
+        close_binary_star.secondary.metallicity = metallicity;
+      },
+      StellarRemnant(_) | BrownDwarf(_) => {},
+    }
+    trace_exit!();
+  }
+
+  /// Evolve the host star forward by the given time, in Gyr.
+  ///
+  /// This can be used to see the same system at another epoch.
+  #[named]
+  pub fn evolve(&self, delta: f64) -> Result<HostStar, Error> {
+    trace_enter!();
+    trace_var!(delta);
+    use HostStar::*;
+    let result = match &self {
+      Star(star) => Star(star.evolve(delta)),
+      CloseBinaryStar(close_binary_star) => CloseBinaryStar(close_binary_star.evolve(delta)?),
+      StellarRemnant(stellar_remnant) => StellarRemnant(stellar_remnant.evolve(delta)),
+      BrownDwarf(brown_dwarf) => BrownDwarf(brown_dwarf.evolve(delta)),
+    };
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+
   /// Retrieve or calculate the habitable zone.
   #[named]
   pub fn get_habitable_zone(&self) -> (f64, f64) {
@@ -91,20 +191,46 @@ impl HostStar {
     let result = match &self {
       Star(star) => star.habitable_zone,
       CloseBinaryStar(close_binary_star) => close_binary_star.habitable_zone,
+      StellarRemnant(stellar_remnant) => stellar_remnant.habitable_zone,
+      BrownDwarf(brown_dwarf) => brown_dwarf.habitable_zone,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve or calculate the innermost orbit around the host star as a
+  /// whole that the host star itself doesn't disrupt.
+  ///
+  /// A single star disrupts nothing; a close binary clears out a region a few
+  /// times as wide as the stars' separation.  Measured in AU.
+  #[named]
+  pub fn get_stability_limit(&self) -> f64 {
+    trace_enter!();
+    use HostStar::*;
+    let result = match &self {
+      Star(_) | StellarRemnant(_) | BrownDwarf(_) => 0.0,
+      CloseBinaryStar(close_binary_star) => close_binary_star.stable_circumbinary_inner_radius(),
     };
     trace_var!(result);
     trace_exit!();
     result
   }
 
-  /// Retrieve or calculate the satellite zone.
+  /// Retrieve or calculate the satellite zones.
   #[named]
-  pub fn get_satellite_zone(&self) -> (f64, f64) {
+  pub fn get_satellite_zones(&self) -> SatelliteZones {
     trace_enter!();
     use HostStar::*;
     let result = match &self {
-      Star(star) => star.satellite_zone,
-      CloseBinaryStar(close_binary_star) => close_binary_star.satellite_zone,
+      Star(star) => SatelliteZones::from_zone(star.satellite_zone),
+      CloseBinaryStar(close_binary_star) => SatelliteZones {
+        system: close_binary_star.satellite_zone,
+        primary: close_binary_star.primary_satellite_zone,
+        secondary: close_binary_star.secondary_satellite_zone,
+      },
+      StellarRemnant(stellar_remnant) => SatelliteZones::from_zone(stellar_remnant.satellite_zone),
+      BrownDwarf(brown_dwarf) => SatelliteZones::from_zone(brown_dwarf.satellite_zone),
     };
     trace_var!(result);
     trace_exit!();
@@ -119,6 +245,41 @@ impl HostStar {
     let result = match &self {
       Star(star) => star.luminosity,
       CloseBinaryStar(close_binary_star) => close_binary_star.get_luminosity(),
+      StellarRemnant(stellar_remnant) => stellar_remnant.luminosity,
+      BrownDwarf(brown_dwarf) => brown_dwarf.luminosity,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Rename each star with the given function.
+  #[named]
+  pub fn rename<F: FnMut(&str, NameKind) -> String>(&mut self, rename: &mut F) {
+    trace_enter!();
+    use HostStar::*;
+    match self {
+      Star(star) => star.name = rename(&star.name, NameKind::Star),
+      CloseBinaryStar(close_binary_star) => {
+        close_binary_star.primary.name = rename(&close_binary_star.primary.name, NameKind::Star);
+        close_binary_star.secondary.name = rename(&close_binary_star.secondary.name, NameKind::Star);
+      },
+      StellarRemnant(stellar_remnant) => stellar_remnant.name = rename(&stellar_remnant.name, NameKind::Star),
+      BrownDwarf(brown_dwarf) => brown_dwarf.name = rename(&brown_dwarf.name, NameKind::Star),
+    }
+    trace_exit!();
+  }
+
+  /// Retrieve or calculate the name of the stars.
+  #[named]
+  pub fn get_name(&self) -> String {
+    trace_enter!();
+    use HostStar::*;
+    let result = match &self {
+      Star(star) => star.name.clone(),
+      CloseBinaryStar(close_binary_star) => close_binary_star.get_name(),
+      StellarRemnant(stellar_remnant) => stellar_remnant.name.clone(),
+      BrownDwarf(brown_dwarf) => brown_dwarf.name.clone(),
     };
     trace_var!(result);
     trace_exit!();
@@ -133,6 +294,8 @@ impl HostStar {
     match &self {
       Star(star) => star.check_habitable()?,
       CloseBinaryStar(close_binary_star) => close_binary_star.check_habitable()?,
+      StellarRemnant(stellar_remnant) => stellar_remnant.check_habitable()?,
+      BrownDwarf(brown_dwarf) => brown_dwarf.check_habitable()?,
     }
     let result = Ok(());
     trace_var!(result);
@@ -0,0 +1,39 @@
+use serde::Serialize;
+
+/// Where satellites (planets, mostly) can orbit a host star.
+///
+/// A single star has one zone.  A close binary has a circumbinary zone, and
+/// may also have room for tighter orbits around each component; whether it
+/// does depends on how far apart the components are.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct SatelliteZones {
+  /// Orbits around the host star as a whole, in AU.
+  ///
+  /// For a close binary, these are circumbinary (P-type) orbits around the
+  /// barycenter, and there may not be room for any.
+  pub system: Option<(f64, f64)>,
+  /// Orbits around the primary alone (S-type), in AU from the primary.
+  pub primary: Option<(f64, f64)>,
+  /// Orbits around the secondary alone (S-type), in AU from the secondary.
+  pub secondary: Option<(f64, f64)>,
+}
+
+impl SatelliteZones {
+  /// The zones of a single star.
+  #[named]
+  pub fn from_zone(system: (f64, f64)) -> Self {
+    trace_enter!();
+    trace_var!(system);
+    let system = Some(system);
+    let primary = None;
+    let secondary = None;
+    let result = Self {
+      system,
+      primary,
+      secondary,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
@@ -0,0 +1,79 @@
+use serde::Serialize;
+
+use crate::astronomy::ice_giant_planet::constants::*;
+
+/// The bulk composition of an ice giant, as mass fractions.
+///
+/// Despite the name, the "ices" are a hot, dense fluid of water, ammonia,
+/// and methane; they just would have been ices when the planet formed.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct IceGiantComposition {
+  /// Fraction of the mass in the hydrogen/helium envelope.
+  pub envelope_fraction: f64,
+  /// Fraction of the mass in the icy mantle.
+  pub ice_fraction: f64,
+  /// Fraction of the mass in the rocky core.
+  pub rock_fraction: f64,
+}
+
+impl IceGiantComposition {
+  /// Create from the envelope and rock fractions; the rest is ice.
+  #[named]
+  pub fn new(envelope_fraction: f64, rock_fraction: f64) -> Self {
+    trace_enter!();
+    trace_var!(envelope_fraction);
+    trace_var!(rock_fraction);
+    let ice_fraction = 1.0 - envelope_fraction - rock_fraction;
+    trace_var!(ice_fraction);
+    let result = Self {
+      envelope_fraction,
+      ice_fraction,
+      rock_fraction,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Calculate the bulk density, in g/cm³.
+  #[named]
+  pub fn get_density(&self) -> f64 {
+    trace_enter!();
+    let result = 1.0
+      / (self.envelope_fraction / ENVELOPE_DENSITY
+        + self.ice_fraction / ICE_DENSITY
+        + self.rock_fraction / ROCK_DENSITY);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl Default for IceGiantComposition {
+  /// Roughly Neptune.
+  fn default() -> Self {
+    Self::new(0.15, 0.2)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_density() {
+    init();
+    trace_enter!();
+    let neptune = IceGiantComposition::default();
+    print_var!(neptune);
+    assert_approx_eq!(neptune.ice_fraction, 0.65);
+    assert_approx_eq!(neptune.get_density(), 1.6, 0.15);
+    let uranus = IceGiantComposition::new(0.2, 0.1);
+    print_var!(uranus);
+    assert_approx_eq!(uranus.get_density(), 1.3, 0.15);
+    trace_exit!();
+  }
+}
@@ -0,0 +1,43 @@
+/// Minimum mass for an ice giant, in Mearth.
+pub const MINIMUM_MASS: f64 = 10.0;
+
+/// Maximum mass for an ice giant, in Mearth.
+///
+/// Much more than this and the core pulls in a runaway gas envelope.
+pub const MAXIMUM_MASS: f64 = 30.0;
+
+/// The mass of Neptune, in Mearth.
+pub const NEPTUNE_MASS: f64 = 17.15;
+
+/// The rotational period of Neptune, in Dearth.
+pub const NEPTUNE_ROTATIONAL_PERIOD: f64 = 0.6713;
+
+/// Minimum rotational period, in Dearth.
+pub const MINIMUM_ROTATIONAL_PERIOD: f64 = 0.6;
+
+/// Maximum rotational period, in Dearth.
+pub const MAXIMUM_ROTATIONAL_PERIOD: f64 = 0.8;
+
+/// Minimum fraction of the mass in the hydrogen/helium envelope.
+pub const MINIMUM_ENVELOPE_FRACTION: f64 = 0.1;
+
+/// Maximum fraction of the mass in the hydrogen/helium envelope.
+pub const MAXIMUM_ENVELOPE_FRACTION: f64 = 0.2;
+
+/// Minimum fraction of the mass in the rocky core.
+pub const MINIMUM_ROCK_FRACTION: f64 = 0.1;
+
+/// Maximum fraction of the mass in the rocky core.
+pub const MAXIMUM_ROCK_FRACTION: f64 = 0.25;
+
+/// Effective density of the hydrogen/helium envelope, in g/cm³.
+///
+/// These are all compressed densities, tuned so that Uranus and Neptune
+/// come out at roughly 1.3 and 1.6.
+pub const ENVELOPE_DENSITY: f64 = 0.5;
+
+/// Effective density of the water/ammonia/methane mantle, in g/cm³.
+pub const ICE_DENSITY: f64 = 2.0;
+
+/// Effective density of the rocky core, in g/cm³.
+pub const ROCK_DENSITY: f64 = 5.0;
@@ -0,0 +1,51 @@
+use std::ops::Range;
+
+use crate::astronomy::ice_giant_planet::constraints::Constraints;
+use crate::math::range::check_range;
+use crate::math::range::error::Error;
+
+/// A fluent builder for ice giant constraints.
+///
+/// Nothing is checked until `build()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Builder {
+  constraints: Constraints,
+}
+
+impl Builder {
+  /// Restrict the mass, in Mearth.
+  pub fn mass_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_mass = Some(range.start);
+    self.constraints.maximum_mass = Some(range.end);
+    self
+  }
+
+  /// Restrict the rotational period, in Dearth.
+  pub fn rotational_period_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_rotational_period = Some(range.start);
+    self.constraints.maximum_rotational_period = Some(range.end);
+    self
+  }
+
+  /// Check the constraints and return them.
+  #[named]
+  pub fn build(self) -> Result<Constraints, Error> {
+    trace_enter!();
+    let constraints = self.constraints;
+    trace_var!(constraints);
+    check_range(
+      "mass",
+      constraints.minimum_mass,
+      constraints.maximum_mass,
+      (0.0, f64::INFINITY),
+    )?;
+    check_range(
+      "rotational period",
+      constraints.minimum_rotational_period,
+      constraints.maximum_rotational_period,
+      (0.0, f64::INFINITY),
+    )?;
+    trace_exit!();
+    Ok(constraints)
+  }
+}
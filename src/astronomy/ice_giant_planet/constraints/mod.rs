@@ -0,0 +1,136 @@
+use rand::prelude::*;
+
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::ice_giant_planet::composition::IceGiantComposition;
+use crate::astronomy::ice_giant_planet::constants::*;
+use crate::astronomy::ice_giant_planet::error::Error;
+use crate::astronomy::ice_giant_planet::IceGiantPlanet;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::merge::Merge;
+pub mod builder;
+use builder::Builder;
+
+/// Constraints for creating an ice giant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+  /// The minimum mass, in Mearth.
+  pub minimum_mass: Option<f64>,
+  /// The maximum mass, in Mearth.
+  pub maximum_mass: Option<f64>,
+  /// The minimum rotational period.
+  pub minimum_rotational_period: Option<f64>,
+  /// The maximum rotational period.
+  pub maximum_rotational_period: Option<f64>,
+}
+
+impl Constraints {
+  /// Start building constraints fluently.
+  pub fn builder() -> Builder {
+    Builder::default()
+  }
+
+  /// Generate.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: &HostStar,
+    distance: f64,
+  ) -> Result<IceGiantPlanet, Error> {
+    trace_enter!();
+    let minimum_mass = self.minimum_mass.unwrap_or(MINIMUM_MASS);
+    trace_var!(minimum_mass);
+    let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
+    trace_var!(maximum_mass);
+    // Smaller ice giants are more common, so pick log-uniformly.
+    let mass = rng.gen_range(minimum_mass.ln()..=maximum_mass.ln()).exp();
+    trace_var!(mass);
+    let envelope_fraction = rng.gen_range(MINIMUM_ENVELOPE_FRACTION..=MAXIMUM_ENVELOPE_FRACTION);
+    trace_var!(envelope_fraction);
+    let rock_fraction = rng.gen_range(MINIMUM_ROCK_FRACTION..=MAXIMUM_ROCK_FRACTION);
+    trace_var!(rock_fraction);
+    let composition = IceGiantComposition::new(envelope_fraction, rock_fraction);
+    trace_var!(composition);
+    let minimum_rotational_period = self.minimum_rotational_period.unwrap_or(MINIMUM_ROTATIONAL_PERIOD);
+    trace_var!(minimum_rotational_period);
+    let maximum_rotational_period = self.maximum_rotational_period.unwrap_or(MAXIMUM_ROTATIONAL_PERIOD);
+    trace_var!(maximum_rotational_period);
+    let rotation_period = rng.gen_range(minimum_rotational_period..=maximum_rotational_period);
+    trace_var!(rotation_period);
+    let mut result = IceGiantPlanet::from_mass(mass, composition, rotation_period)?;
+    let orbit = OrbitalElements::new(distance, result.orbit.eccentricity).with_random_orientation(rng);
+    result.orbit = orbit;
+    trace_var!(orbit);
+    let orbital_period = (distance.powf(3.0) / host_star.get_stellar_mass()).sqrt();
+    result.orbital_period = orbital_period;
+    trace_var!(orbital_period);
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+}
+
+impl Default for Constraints {
+  /// No constraints, just let it all hang out.
+  fn default() -> Self {
+    let minimum_mass = None;
+    let maximum_mass = None;
+    let minimum_rotational_period = None;
+    let maximum_rotational_period = None;
+    Self {
+      minimum_mass,
+      maximum_mass,
+      minimum_rotational_period,
+      maximum_rotational_period,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+      minimum_rotational_period: overrides.minimum_rotational_period.or(self.minimum_rotational_period),
+      maximum_rotational_period: overrides.maximum_rotational_period.or(self.maximum_rotational_period),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = HostStarConstraints::default().generate(&mut rng)?;
+    trace_var!(host_star);
+    let distance = host_star.get_frost_line() * 5.0;
+    trace_var!(distance);
+    let planet = &Constraints::default().generate(&mut rng, &host_star, distance)?;
+    trace_var!(planet);
+    print_var!(planet);
+    assert!((MINIMUM_MASS..=MAXIMUM_MASS).contains(&planet.mass));
+    assert_approx_eq!(
+      planet.composition.envelope_fraction + planet.composition.ice_fraction + planet.composition.rock_fraction,
+      1.0
+    );
+    assert!(planet.density > 1.0 && planet.density < 2.0);
+    assert_approx_eq!(
+      planet.orbital_period,
+      (distance.powf(3.0) / host_star.get_stellar_mass()).sqrt()
+    );
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,25 @@
+use crate::astronomy::host_star::error::Error as HostStarError;
+
+/// IceGiantPlanet errors.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+  /// Host Star Error.
+  HostStarError(HostStarError),
+}
+
+honeyholt_define_brief!(Error, |error: &Error| {
+  use Error::*;
+  match error {
+    HostStarError(host_star_error) => format!(
+      "an error occurred in the host star ({})",
+      honeyholt_brief!(host_star_error)
+    ),
+  }
+});
+
+impl From<HostStarError> for Error {
+  #[named]
+  fn from(error: HostStarError) -> Self {
+    Error::HostStarError(error)
+  }
+}
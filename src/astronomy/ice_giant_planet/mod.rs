@@ -0,0 +1,132 @@
+use serde::Serialize;
+
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::planet::math::oblateness::{
+  get_equatorial_and_polar_radii, get_j2, get_oblateness, get_rotational_parameter, ICE_GIANT_MOMENT_OF_INERTIA_FACTOR,
+};
+use crate::astronomy::terrestrial_planet::constants::EARTH_ESCAPE_VELOCITY;
+use crate::astronomy::terrestrial_planet::math::escape_velocity::get_escape_velocity;
+use crate::astronomy::terrestrial_planet::math::gravity::get_gravity;
+use crate::astronomy::terrestrial_planet::math::radius::get_radius;
+
+pub mod composition;
+use composition::IceGiantComposition;
+pub mod constants;
+pub mod constraints;
+pub mod error;
+use error::Error;
+
+/// The `IceGiantPlanet` type, for Uranus and Neptune analogs.
+///
+/// These are mostly ices around a rocky core, with a comparatively thin
+/// hydrogen/helium envelope, so they're measured in Earth units rather than
+/// Jupiter units.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct IceGiantPlanet {
+  /// The name, given once the planet's place in its system is known.
+  pub name: String,
+  /// Mass, in Mearth.
+  pub mass: f64,
+  /// The bulk composition.
+  pub composition: IceGiantComposition,
+  /// Density, in g/cm³.
+  pub density: f64,
+  /// Radius, in Rearth.
+  pub radius: f64,
+  /// Rotation period, in Dearth.
+  pub rotation_period: f64,
+  /// Flattening caused by rotation.
+  pub oblateness: f64,
+  /// Equatorial radius, in Rearth.
+  pub equatorial_radius: f64,
+  /// Polar radius, in Rearth.
+  pub polar_radius: f64,
+  /// The J2 gravitational harmonic.
+  pub j2: f64,
+  /// Surface gravity (at the 1-bar level), in Gearth.
+  pub surface_gravity: f64,
+  /// Escape velocity, in km/s.
+  pub escape_velocity: f64,
+  /// Orbit around the host star, in AU.
+  pub orbit: OrbitalElements,
+  /// Orbital period, in Earth years.
+  pub orbital_period: f64,
+}
+
+impl IceGiantPlanet {
+  /// Create from a mass (in Mearth), composition, and rotation period (in
+  /// Dearth), in a Neptune-like orbit.
+  #[named]
+  pub fn from_mass(mass: f64, composition: IceGiantComposition, rotation_period: f64) -> Result<Self, Error> {
+    trace_enter!();
+    trace_var!(mass);
+    trace_var!(composition);
+    trace_var!(rotation_period);
+    let density = composition.get_density();
+    trace_var!(density);
+    let radius = get_radius(mass, density);
+    trace_var!(radius);
+    let rotational_parameter = get_rotational_parameter(rotation_period, density);
+    trace_var!(rotational_parameter);
+    let oblateness = get_oblateness(rotational_parameter, ICE_GIANT_MOMENT_OF_INERTIA_FACTOR);
+    trace_var!(oblateness);
+    let (equatorial_radius, polar_radius) = get_equatorial_and_polar_radii(radius, oblateness);
+    trace_var!(equatorial_radius);
+    trace_var!(polar_radius);
+    let j2 = get_j2(oblateness, rotational_parameter);
+    trace_var!(j2);
+    let surface_gravity = get_gravity(mass, radius);
+    trace_var!(surface_gravity);
+    let escape_velocity = get_escape_velocity(mass, radius) * EARTH_ESCAPE_VELOCITY;
+    trace_var!(escape_velocity);
+    let semi_major_axis: f64 = 30.07;
+    trace_var!(semi_major_axis);
+    let orbit = OrbitalElements::new(semi_major_axis, 0.0086);
+    trace_var!(orbit);
+    let orbital_period = semi_major_axis.powf(3.0).sqrt();
+    trace_var!(orbital_period);
+    let name = String::new();
+    let result = Self {
+      name,
+      mass,
+      composition,
+      density,
+      radius,
+      rotation_period,
+      oblateness,
+      equatorial_radius,
+      polar_radius,
+      j2,
+      surface_gravity,
+      escape_velocity,
+      orbit,
+      orbital_period,
+    };
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::constants::NEPTUNE_ROTATIONAL_PERIOD;
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_neptune() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let neptune = IceGiantPlanet::from_mass(17.15, IceGiantComposition::default(), NEPTUNE_ROTATIONAL_PERIOD)?;
+    print_var!(neptune);
+    assert_approx_eq!(neptune.radius, 3.88, 0.3);
+    assert_approx_eq!(neptune.oblateness, 0.017, 0.01);
+    assert_approx_eq!(neptune.surface_gravity, 1.14, 0.15);
+    assert_approx_eq!(neptune.escape_velocity, 23.5, 2.0);
+    trace_exit!();
+    Ok(())
+  }
+}
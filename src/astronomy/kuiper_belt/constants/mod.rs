@@ -0,0 +1,47 @@
+use crate::math::probability::Probability;
+
+/// The probability that a planetary system has a Kuiper belt.
+///
+/// Debris disks show up around a fair fraction of nearby stars; fainter ones
+/// surely go unseen.
+pub const PROBABILITY: Probability = Probability::from_const(0.75);
+
+/// Inner edge of a Kuiper belt, as a multiple of the outermost planet's
+/// orbit.
+///
+/// Ours starts around 35 AU, just beyond Neptune at 30.
+pub const INNER_EDGE_RATIO: f64 = 1.15;
+
+/// Innermost inner edge of a Kuiper belt, as a multiple of the frost line.
+///
+/// Without a planet to shepherd it, a belt still only forms well out among
+/// the ices; ours sits at about eleven times the Sun's frost line.
+pub const MINIMUM_FROST_LINE_RATIO: f64 = 11.0;
+
+/// Outer edge of a Kuiper belt, as a multiple of its inner edge.
+///
+/// Ours ends abruptly near the 2:1 resonance with Neptune, at 48 AU or so.
+pub const OUTER_EDGE_RATIO: f64 = 1.4;
+
+/// Outer edge of the scattered disc, as a multiple of the belt's outer edge.
+///
+/// Sedna, at the edge of the known scattered disc, reaches nearly 1,000 AU.
+pub const SCATTERED_DISC_RATIO: f64 = 20.0;
+
+/// The location of the 3:2 resonance with the outermost planet, as a
+/// multiple of its orbit; home to Pluto and the plutinos.
+pub const PLUTINO_RATIO: f64 = 1.31;
+
+/// Minimum mass of a Kuiper belt, in Mearth.
+pub const MINIMUM_MASS: f64 = 0.01;
+
+/// Maximum mass of a Kuiper belt, in Mearth.
+///
+/// Ours is about 0.02 to 0.1 Mearth, a sliver of what it started with.
+pub const MAXIMUM_MASS: f64 = 0.2;
+
+/// Minimum number of dwarf planets in a Kuiper belt.
+pub const MINIMUM_DWARF_PLANETS: usize = 1;
+
+/// Maximum number of dwarf planets in a Kuiper belt.
+pub const MAXIMUM_DWARF_PLANETS: usize = 6;
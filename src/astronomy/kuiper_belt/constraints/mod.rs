@@ -0,0 +1,255 @@
+use rand::prelude::*;
+
+use crate::astronomy::dwarf_planet::constraints::Constraints as DwarfPlanetConstraints;
+use crate::astronomy::dwarf_planet::{DwarfPlanet, DwarfPlanetPopulation};
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::kuiper_belt::constants::*;
+use crate::astronomy::kuiper_belt::KuiperBelt;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::satellite_systems::SatelliteSystems;
+use crate::math::probability::Probability;
+use crate::merge::Merge;
+
+/// Constraints for creating a Kuiper belt.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+  /// The probability that a system has a Kuiper belt at all.
+  pub probability: Option<Probability>,
+  /// The minimum mass, in Mearth.
+  pub minimum_mass: Option<f64>,
+  /// The maximum mass, in Mearth.
+  pub maximum_mass: Option<f64>,
+  /// The minimum number of dwarf planets.
+  pub minimum_dwarf_planets: Option<usize>,
+  /// The maximum number of dwarf planets.
+  pub maximum_dwarf_planets: Option<usize>,
+  /// Dwarf planet constraints.
+  pub dwarf_planet_constraints: Option<DwarfPlanetConstraints>,
+}
+
+impl Constraints {
+  /// Generate.
+  ///
+  /// The belt lies just beyond the outermost planet, and never inside the
+  /// icy reaches well past the frost line.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: &HostStar,
+    satellite_systems: &SatelliteSystems,
+  ) -> Option<KuiperBelt> {
+    trace_enter!();
+    let probability = self.probability.unwrap_or(PROBABILITY);
+    trace_var!(probability);
+    if !probability.sample(rng) {
+      trace_exit!();
+      return None;
+    }
+    let outermost_orbit = satellite_systems.get_semi_major_axes().into_iter().fold(0.0, f64::max);
+    trace_var!(outermost_orbit);
+    let minimum_inner_edge = MINIMUM_FROST_LINE_RATIO * host_star.get_frost_line();
+    trace_var!(minimum_inner_edge);
+    // A planet just inside the belt shepherds it, and traps plutinos.
+    let shepherd_orbit = match INNER_EDGE_RATIO * outermost_orbit >= minimum_inner_edge {
+      true => Some(outermost_orbit),
+      false => None,
+    };
+    trace_var!(shepherd_orbit);
+    let inner_edge = (INNER_EDGE_RATIO * outermost_orbit).max(minimum_inner_edge);
+    trace_var!(inner_edge);
+    let outer_edge = OUTER_EDGE_RATIO * inner_edge;
+    trace_var!(outer_edge);
+    let scattered_disc_outer_edge = SCATTERED_DISC_RATIO * outer_edge;
+    trace_var!(scattered_disc_outer_edge);
+    let minimum_mass = self.minimum_mass.unwrap_or(MINIMUM_MASS);
+    trace_var!(minimum_mass);
+    let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
+    trace_var!(maximum_mass);
+    let mass = rng.gen_range(minimum_mass..=maximum_mass);
+    trace_var!(mass);
+    let minimum_dwarf_planets = self.minimum_dwarf_planets.unwrap_or(MINIMUM_DWARF_PLANETS);
+    trace_var!(minimum_dwarf_planets);
+    let maximum_dwarf_planets = self.maximum_dwarf_planets.unwrap_or(MAXIMUM_DWARF_PLANETS);
+    trace_var!(maximum_dwarf_planets);
+    let count = rng.gen_range(minimum_dwarf_planets..=maximum_dwarf_planets);
+    trace_var!(count);
+    let mut dwarf_planets: Vec<DwarfPlanet> = (0..count)
+      .map(|_| {
+        self.generate_dwarf_planet(
+          rng,
+          host_star,
+          inner_edge,
+          outer_edge,
+          scattered_disc_outer_edge,
+          shepherd_orbit,
+        )
+      })
+      .collect();
+    dwarf_planets.sort_by(|a, b| a.orbit.semi_major_axis.partial_cmp(&b.orbit.semi_major_axis).unwrap());
+    trace_var!(dwarf_planets);
+    let result = KuiperBelt {
+      inner_edge,
+      outer_edge,
+      scattered_disc_outer_edge,
+      mass,
+      dwarf_planets,
+    };
+    trace_var!(result);
+    trace_exit!();
+    Some(result)
+  }
+
+  /// Generate a dwarf planet somewhere in the belt or the scattered disc.
+  ///
+  /// Only a belt shepherded by a planet (at the specified orbit, in AU) has a
+  /// resonance to trap plutinos in.
+  #[named]
+  pub fn generate_dwarf_planet<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: &HostStar,
+    inner_edge: f64,
+    outer_edge: f64,
+    scattered_disc_outer_edge: f64,
+    shepherd_orbit: Option<f64>,
+  ) -> DwarfPlanet {
+    trace_enter!();
+    use DwarfPlanetPopulation::*;
+    let (population, semi_major_axis, eccentricity, inclination) = match (rng.gen_range(0..3), shepherd_orbit) {
+      (0, Some(shepherd_orbit)) => (
+        Resonant,
+        PLUTINO_RATIO * shepherd_orbit,
+        rng.gen_range(0.1..0.3),
+        rng.gen_range(0.0..20.0),
+      ),
+      (1, _) => {
+        // Scattered objects still pass close to the belt at periapsis.
+        let semi_major_axis = rng.gen_range(outer_edge.ln()..scattered_disc_outer_edge.ln()).exp();
+        let periapsis = rng.gen_range(inner_edge..outer_edge);
+        (
+          Scattered,
+          semi_major_axis,
+          1.0 - periapsis / semi_major_axis,
+          rng.gen_range(0.0..45.0),
+        )
+      },
+      _ => (
+        Classical,
+        rng.gen_range(inner_edge..outer_edge),
+        rng.gen_range(0.0..0.1),
+        rng.gen_range(0.0..10.0),
+      ),
+    };
+    trace_var!(population);
+    trace_var!(semi_major_axis);
+    trace_var!(eccentricity);
+    trace_var!(inclination);
+    let orbit = OrbitalElements {
+      inclination,
+      ..OrbitalElements::new(semi_major_axis, eccentricity).with_random_orientation(rng)
+    };
+    trace_var!(orbit);
+    let dwarf_planet_constraints = self.dwarf_planet_constraints.unwrap_or_default();
+    trace_var!(dwarf_planet_constraints);
+    let result = dwarf_planet_constraints.generate(rng, host_star, population, orbit);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl Default for Constraints {
+  /// No constraints, just let it all hang out.
+  fn default() -> Self {
+    let probability = None;
+    let minimum_mass = None;
+    let maximum_mass = None;
+    let minimum_dwarf_planets = None;
+    let maximum_dwarf_planets = None;
+    let dwarf_planet_constraints = None;
+    Self {
+      probability,
+      minimum_mass,
+      maximum_mass,
+      minimum_dwarf_planets,
+      maximum_dwarf_planets,
+      dwarf_planet_constraints,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      probability: overrides.probability.or(self.probability),
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+      minimum_dwarf_planets: overrides.minimum_dwarf_planets.or(self.minimum_dwarf_planets),
+      maximum_dwarf_planets: overrides.maximum_dwarf_planets.or(self.maximum_dwarf_planets),
+      dwarf_planet_constraints: self
+        .dwarf_planet_constraints
+        .merged_with(&overrides.dwarf_planet_constraints),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::planetary_system::constraints::Constraints as PlanetarySystemConstraints;
+  use crate::astronomy::planetary_system::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let planetary_system = PlanetarySystemConstraints::default().generate(&mut rng)?;
+    let constraints = Constraints {
+      probability: Some(Probability::ALWAYS),
+      minimum_dwarf_planets: Some(4),
+      ..Constraints::default()
+    };
+    let kuiper_belt = constraints
+      .generate(
+        &mut rng,
+        &planetary_system.host_star,
+        &planetary_system.satellite_systems,
+      )
+      .unwrap();
+    print_var!(kuiper_belt);
+    let outermost_orbit = planetary_system
+      .satellite_systems
+      .get_semi_major_axes()
+      .into_iter()
+      .fold(0.0, f64::max);
+    assert!(kuiper_belt.inner_edge > outermost_orbit);
+    assert!(kuiper_belt.dwarf_planets.len() >= 4);
+    for dwarf_planet in &kuiper_belt.dwarf_planets {
+      assert!(!dwarf_planet.name.is_empty());
+      assert!(dwarf_planet.orbit.get_periapsis() >= kuiper_belt.inner_edge * 0.7);
+      assert!(dwarf_planet.orbit.semi_major_axis <= kuiper_belt.scattered_disc_outer_edge);
+      assert!(dwarf_planet.radius > 300.0 && dwarf_planet.radius < 1_500.0);
+    }
+    let constraints = Constraints {
+      probability: Some(Probability::NEVER),
+      ..Constraints::default()
+    };
+    assert!(constraints
+      .generate(
+        &mut rng,
+        &planetary_system.host_star,
+        &planetary_system.satellite_systems
+      )
+      .is_none());
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,39 @@
+use serde::Serialize;
+
+use crate::astronomy::dwarf_planet::{DwarfPlanet, DwarfPlanetPopulation};
+
+pub mod constants;
+pub mod constraints;
+
+/// A `KuiperBelt` is a ring of icy bodies beyond the outermost planet, and
+/// the scattered disc beyond that.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct KuiperBelt {
+  /// The inner edge, in AU.
+  pub inner_edge: f64,
+  /// The outer edge, in AU.
+  pub outer_edge: f64,
+  /// The outer edge of the scattered disc, in AU.
+  pub scattered_disc_outer_edge: f64,
+  /// The total mass, in Mearth.
+  pub mass: f64,
+  /// The dwarf planets, innermost first.
+  pub dwarf_planets: Vec<DwarfPlanet>,
+}
+
+impl KuiperBelt {
+  /// Get the dwarf planets belonging to a population.
+  #[named]
+  pub fn get_dwarf_planets(&self, population: DwarfPlanetPopulation) -> Vec<&DwarfPlanet> {
+    trace_enter!();
+    trace_var!(population);
+    let result = self
+      .dwarf_planets
+      .iter()
+      .filter(|dwarf_planet| dwarf_planet.population == population)
+      .collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
@@ -6,28 +6,50 @@
 /// MOONS = [MOON]
 /// TERRESTRIAL_PLANET = ()
 /// GAS_GIANT_PLANET = ()
-/// PLANET = GAS_GIANT_PLANET | TERRESTRIAL_PLANET
-/// SATELLITE_SYSTEM = (PLANET, MOONS)
+/// ICE_GIANT_PLANET = ()
+/// PLANET = GAS_GIANT_PLANET | ICE_GIANT_PLANET | TERRESTRIAL_PLANET
+/// RING_SYSTEM = ()
+/// TROJAN_SWARM = ()
+/// SATELLITE_SYSTEM = (PLANET, MOONS, RING_SYSTEM?, [TROJAN_SWARM])
 /// SATELLITE_SYSTEMS = [SATELLITE_SYSTEM]
 /// STAR = ()
 /// CLOSE_BINARY_STAR = (STAR, STAR)
-/// HOST_STAR = CLOSE_BINARY_STAR | STAR
-/// PLANETARY_SYSTEM = (HOST_STAR, SATELLITE_SYSTEMS)
+/// STELLAR_REMNANT = ()
+/// BROWN_DWARF = ()
+/// HOST_STAR = BROWN_DWARF | CLOSE_BINARY_STAR | STAR | STELLAR_REMNANT
+/// DWARF_PLANET = ()
+/// ASTEROID_BELT = ([DWARF_PLANET])
+/// ZODIACAL_DUST = ()
+/// KUIPER_BELT = ([DWARF_PLANET])
+/// OORT_CLOUD = ()
+/// PLANETARY_SYSTEM = (HOST_STAR, SATELLITE_SYSTEMS, ASTEROID_BELT?, ZODIACAL_DUST?, KUIPER_BELT?, OORT_CLOUD?)
 /// DISTANT_BINARY_STAR = (PLANETARY_SYSTEM, PLANETARY_SYSTEM)
 /// STAR_SUBSYSTEM = DISTANT_BINARY_STAR | PLANETARY_SYSTEM
 /// STAR_SYSTEM = (STAR_SUBSYSTEM)
 /// STELLAR_NEIGHBOR = (STAR_SYSTEM)
-/// STELLAR_NEIGHBORHOOD = [STELLAR_NEIGHBOR]
+/// ROGUE_PLANET = ()
+/// STELLAR_NEIGHBORHOOD = ([STELLAR_NEIGHBOR], [ROGUE_PLANET])
 /// GALAXY = (STELLAR_NEIGHBORHOOD)
+pub mod asteroid_belt;
+pub mod brown_dwarf;
 pub mod close_binary_star;
+pub mod composition;
+pub mod coordinates;
 pub mod distant_binary_star;
+pub mod dwarf_planet;
 pub mod galaxy;
 pub mod gas_giant_planet;
 pub mod host_star;
+pub mod ice_giant_planet;
+pub mod kuiper_belt;
 pub mod moon;
 pub mod moons;
+pub mod oort_cloud;
+pub mod orbit;
 pub mod planet;
 pub mod planetary_system;
+pub mod ring_system;
+pub mod rogue_planet;
 pub mod satellite_system;
 pub mod satellite_systems;
 pub mod star;
@@ -35,4 +57,8 @@ pub mod star_subsystem;
 pub mod star_system;
 pub mod stellar_neighbor;
 pub mod stellar_neighborhood;
+pub mod stellar_remnant;
 pub mod terrestrial_planet;
+pub mod time;
+pub mod trojan_swarm;
+pub mod zodiacal_dust;
@@ -1,11 +1,61 @@
+use crate::math::probability::Probability;
+
 /// Minimum mass.
 pub const MINIMUM_MASS: f64 = 0.05;
 
 /// Maximum mass.
 pub const MAXIMUM_MASS: f64 = 1.00;
 
+/// Minimum density, in g/cm³.
+pub const MINIMUM_DENSITY: f64 = 1.5;
+
+/// Maximum density, in g/cm³.
+pub const MAXIMUM_DENSITY: f64 = 5.5;
+
 /// Minimum albedo.
-pub const MINIMUM_ALBEDO: f64 = 0.25;
+pub const MINIMUM_ALBEDO: f64 = 0.05;
 
 /// Maximum albedo.
-pub const MAXIMUM_ALBEDO: f64 = 1.00;
+pub const MAXIMUM_ALBEDO: f64 = 0.90;
+
+/// Minimum mass of an irregular moon, in Mmoon.
+pub const MINIMUM_IRREGULAR_MASS: f64 = 1.0E-6;
+
+/// Maximum mass of an irregular moon, in Mmoon.
+///
+/// Himalia, the largest of Jupiter's, is under a hundredth of the Moon.
+pub const MAXIMUM_IRREGULAR_MASS: f64 = 0.01;
+
+/// Maximum orbital eccentricity of a regular moon.
+pub const MAXIMUM_REGULAR_ECCENTRICITY: f64 = 0.06;
+
+/// Maximum orbital inclination of a regular moon, in degrees.
+pub const MAXIMUM_REGULAR_INCLINATION: f64 = 6.0;
+
+/// Minimum orbital eccentricity of an irregular moon.
+pub const MINIMUM_IRREGULAR_ECCENTRICITY: f64 = 0.1;
+
+/// Maximum orbital eccentricity of an irregular moon.
+pub const MAXIMUM_IRREGULAR_ECCENTRICITY: f64 = 0.5;
+
+/// Minimum orbital inclination of a prograde irregular moon, in degrees.
+pub const MINIMUM_IRREGULAR_INCLINATION: f64 = 25.0;
+
+/// Maximum orbital inclination of a prograde irregular moon, in degrees.
+///
+/// Orbits much closer to perpendicular are pumped into eccentricities that
+/// crash them into the planet or throw them out; retrograde ones mirror
+/// these about 90º.
+pub const MAXIMUM_IRREGULAR_INCLINATION: f64 = 55.0;
+
+/// The probability that an irregular moon orbits retrograde.
+///
+/// Retrograde orbits are stable farther out, and most of Jupiter's and
+/// Saturn's irregulars are retrograde.
+pub const IRREGULAR_RETROGRADE_PROBABILITY: Probability = Probability::from_const(0.7);
+
+/// The mass of the Moon, in KG.
+pub const KG_PER_MOON_MASS: f64 = 7.342E22;
+
+/// The radius of the Moon, in M.
+pub const METERS_PER_MOON_RADIUS: f64 = 1.7374E6;
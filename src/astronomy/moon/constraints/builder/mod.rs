@@ -0,0 +1,93 @@
+use std::ops::Range;
+
+use crate::astronomy::moon::constraints::Constraints;
+use crate::astronomy::moon::population::Population;
+use crate::math::range::check_range;
+use crate::math::range::error::Error;
+
+/// A fluent builder for moon constraints.
+///
+/// Nothing is checked until `build()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Builder {
+  constraints: Constraints,
+}
+
+impl Builder {
+  /// Restrict the mass, in Mmoon.
+  pub fn mass_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_mass = Some(range.start);
+    self.constraints.maximum_mass = Some(range.end);
+    self
+  }
+
+  /// Restrict the density, in g/cm³.
+  pub fn density_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_density = Some(range.start);
+    self.constraints.maximum_density = Some(range.end);
+    self
+  }
+
+  /// Restrict the Bond albedo.
+  pub fn albedo_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_albedo = Some(range.start);
+    self.constraints.maximum_albedo = Some(range.end);
+    self
+  }
+
+  /// Generate moons of a particular population.
+  pub fn population(mut self, population: Population) -> Self {
+    self.constraints.population = Some(population);
+    self
+  }
+
+  /// Check the constraints and return them.
+  #[named]
+  pub fn build(self) -> Result<Constraints, Error> {
+    trace_enter!();
+    let constraints = self.constraints;
+    trace_var!(constraints);
+    check_range(
+      "mass",
+      constraints.minimum_mass,
+      constraints.maximum_mass,
+      (0.0, f64::INFINITY),
+    )?;
+    check_range(
+      "density",
+      constraints.minimum_density,
+      constraints.maximum_density,
+      (0.0, f64::INFINITY),
+    )?;
+    check_range(
+      "albedo",
+      constraints.minimum_albedo,
+      constraints.maximum_albedo,
+      (0.0, 1.0),
+    )?;
+    trace_exit!();
+    Ok(constraints)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_build() {
+    init();
+    trace_enter!();
+    let constraints = Constraints::builder().mass_range(0.1..0.5).build().unwrap();
+    print_var!(constraints);
+    assert_eq!(constraints.maximum_mass, Some(0.5));
+    assert_eq!(
+      Constraints::builder().density_range(3.0..2.0).build(),
+      Err(Error::RangeEmpty("density"))
+    );
+    trace_exit!();
+  }
+}
@@ -3,8 +3,13 @@ use rand::prelude::*;
 use crate::astronomy::host_star::HostStar;
 use crate::astronomy::moon::constants::*;
 use crate::astronomy::moon::error::Error;
+use crate::astronomy::moon::population::Population;
 use crate::astronomy::moon::Moon;
+use crate::astronomy::orbit::OrbitalElements;
 use crate::astronomy::planet::Planet;
+use crate::merge::Merge;
+pub mod builder;
+use builder::Builder;
 
 /// Constraints for creating a moon.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -13,9 +18,30 @@ pub struct Constraints {
   pub minimum_mass: Option<f64>,
   /// The maximum mass, in Mmoon.
   pub maximum_mass: Option<f64>,
+  /// The minimum density, in g/cm³.
+  pub minimum_density: Option<f64>,
+  /// The maximum density, in g/cm³.
+  pub maximum_density: Option<f64>,
+  /// The minimum Bond albedo.
+  ///
+  /// This is narrowed further to whatever the composition allows.
+  pub minimum_albedo: Option<f64>,
+  /// The maximum Bond albedo.
+  ///
+  /// This is narrowed further to whatever the composition allows.
+  pub maximum_albedo: Option<f64>,
+  /// The population the moon belongs to, which shapes its orbit.
+  ///
+  /// Regular, unless otherwise specified.
+  pub population: Option<Population>,
 }
 
 impl Constraints {
+  /// Start building constraints fluently.
+  pub fn builder() -> Builder {
+    Builder::default()
+  }
+
   /// Generate.
   #[named]
   pub fn generate<R: Rng + ?Sized>(
@@ -31,14 +57,61 @@ impl Constraints {
     trace_var!(star_distance);
     trace_var!(planet);
     trace_var!(planet_distance);
-    let minimum_mass = self.minimum_mass.unwrap_or(MINIMUM_MASS);
+    let population = self.population.unwrap_or(Population::Regular);
+    trace_var!(population);
+    let (default_minimum_mass, default_maximum_mass) = match population {
+      Population::Regular => (MINIMUM_MASS, MAXIMUM_MASS),
+      Population::Irregular => (MINIMUM_IRREGULAR_MASS, MAXIMUM_IRREGULAR_MASS),
+    };
+    let minimum_mass = self.minimum_mass.unwrap_or(default_minimum_mass);
     trace_var!(minimum_mass);
-    let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
+    let maximum_mass = self.maximum_mass.unwrap_or(default_maximum_mass);
     trace_var!(maximum_mass);
-    let mass = rng.gen_range(minimum_mass..maximum_mass);
+    let mass = rng.gen_range(minimum_mass..=maximum_mass);
     trace_var!(mass);
-    let result = Moon::from_environment(mass, host_star, star_distance, planet, planet_distance)?;
+    let minimum_density = self.minimum_density.unwrap_or(MINIMUM_DENSITY);
+    trace_var!(minimum_density);
+    let maximum_density = self.maximum_density.unwrap_or(MAXIMUM_DENSITY);
+    trace_var!(maximum_density);
+    let density = rng.gen_range(minimum_density..=maximum_density);
+    trace_var!(density);
+    let (eccentricity, inclination) = match population {
+      Population::Regular => (
+        rng.gen_range(0.0..=MAXIMUM_REGULAR_ECCENTRICITY),
+        rng.gen_range(0.0..=MAXIMUM_REGULAR_INCLINATION),
+      ),
+      Population::Irregular => {
+        let eccentricity = rng.gen_range(MINIMUM_IRREGULAR_ECCENTRICITY..=MAXIMUM_IRREGULAR_ECCENTRICITY);
+        let inclination = rng.gen_range(MINIMUM_IRREGULAR_INCLINATION..=MAXIMUM_IRREGULAR_INCLINATION);
+        match IRREGULAR_RETROGRADE_PROBABILITY.sample(rng) {
+          true => (eccentricity, 180.0 - inclination),
+          false => (eccentricity, inclination),
+        }
+      },
+    };
+    trace_var!(eccentricity);
+    trace_var!(inclination);
+    let orbit = OrbitalElements {
+      inclination,
+      ..OrbitalElements::new(planet_distance, eccentricity)
+    }
+    .with_random_orientation(rng);
+    trace_var!(orbit);
+    let mut result = Moon::from_environment(mass, density, host_star, star_distance, planet, orbit, population)?;
     trace_var!(result);
+    // The composition has the final say; if the requested albedo range doesn't
+    // overlap with it at all, we go with the composition.
+    let albedo_range = result.composition.get_albedo_range();
+    trace_var!(albedo_range);
+    let minimum_albedo = self.minimum_albedo.unwrap_or(MINIMUM_ALBEDO).max(albedo_range.start);
+    trace_var!(minimum_albedo);
+    let maximum_albedo = self.maximum_albedo.unwrap_or(MAXIMUM_ALBEDO).min(albedo_range.end);
+    trace_var!(maximum_albedo);
+    result.bond_albedo = match minimum_albedo < maximum_albedo {
+      true => rng.gen_range(minimum_albedo..maximum_albedo),
+      false => rng.gen_range(albedo_range),
+    };
+    trace_var!(result.bond_albedo);
     trace_exit!();
     Ok(result)
   }
@@ -49,9 +122,33 @@ impl Default for Constraints {
   fn default() -> Self {
     let minimum_mass = None;
     let maximum_mass = None;
+    let minimum_density = None;
+    let maximum_density = None;
+    let minimum_albedo = None;
+    let maximum_albedo = None;
+    let population = None;
     Self {
       minimum_mass,
       maximum_mass,
+      minimum_density,
+      maximum_density,
+      minimum_albedo,
+      maximum_albedo,
+      population,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+      minimum_density: overrides.minimum_density.or(self.minimum_density),
+      maximum_density: overrides.maximum_density.or(self.maximum_density),
+      minimum_albedo: overrides.minimum_albedo.or(self.minimum_albedo),
+      maximum_albedo: overrides.maximum_albedo.or(self.maximum_albedo),
+      population: overrides.population.or(self.population),
     }
   }
 }
@@ -84,6 +181,17 @@ pub mod test {
     let moon = &Constraints::default().generate(&mut rng, &host_star, star_distance, &planet, 400_000.0)?;
     trace_var!(moon);
     print_var!(moon);
+    assert!(moon.composition.is_albedo_consistent(moon.bond_albedo));
+    assert_eq!(moon.population, Population::Regular);
+    assert!(!moon.is_retrograde());
+    let constraints = Constraints {
+      population: Some(Population::Irregular),
+      ..Constraints::default()
+    };
+    let moon = &constraints.generate(&mut rng, &host_star, star_distance, &planet, 4_000_000.0)?;
+    print_var!(moon);
+    assert!(moon.mass <= MAXIMUM_IRREGULAR_MASS);
+    assert!(moon.orbit.eccentricity >= MINIMUM_IRREGULAR_ECCENTRICITY);
     trace_exit!();
     Ok(())
   }
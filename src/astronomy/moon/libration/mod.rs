@@ -0,0 +1,173 @@
+use serde::Serialize;
+
+use crate::astronomy::orbit::OrbitalElements;
+
+/// A point on the surface of a moon, in degrees.
+///
+/// Longitude is measured eastward from the mean sub-planet point, so the
+/// near side is centered on (0, 0) and the far side on (0, 180).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct SurfacePoint {
+  /// Latitude, in degrees north.
+  pub latitude: f64,
+  /// Longitude, in degrees east.
+  pub longitude: f64,
+}
+
+impl SurfacePoint {
+  /// Get the angle between this and another point, in degrees.
+  #[named]
+  pub fn get_angular_distance(&self, other: &SurfacePoint) -> f64 {
+    trace_enter!();
+    let (latitude_1, latitude_2) = (self.latitude.to_radians(), other.latitude.to_radians());
+    let delta_longitude = (other.longitude - self.longitude).to_radians();
+    let cosine = latitude_1.sin() * latitude_2.sin() + latitude_1.cos() * latitude_2.cos() * delta_longitude.cos();
+    let result = cosine.clamp(-1.0, 1.0).acos().to_degrees();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+/// Which side of a tidally locked moon a point is on, as seen from its
+/// planet.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum Face {
+  /// Always in view of the planet.
+  NearSide,
+  /// Rocked into and out of view by libration.
+  Limb,
+  /// Never in view of the planet.
+  FarSide,
+}
+
+/// The apparent wobble of a tidally locked moon, as seen from its planet.
+///
+/// A locked moon spins steadily but, on an eccentric orbit, moves unevenly,
+/// so it seems to rock east and west; its tilt to its orbit lets the planet
+/// peek over each pole in turn.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Libration {
+  /// The amplitude in longitude, in degrees.
+  pub longitude_amplitude: f64,
+  /// The amplitude in latitude, in degrees.
+  pub latitude_amplitude: f64,
+}
+
+impl Libration {
+  /// Calculate the libration of a moon locked on the specified orbit.
+  ///
+  /// The longitude amplitude is the equation of center, to first order; the
+  /// latitude amplitude takes the moon's equator to lie in its planet's, so
+  /// it's tilted by the inclination of the orbit.  Our Moon's work out to
+  /// about 6.3º and 5.2º.
+  #[named]
+  pub fn from_orbit(orbit: &OrbitalElements) -> Self {
+    trace_enter!();
+    trace_var!(orbit);
+    let longitude_amplitude = (2.0 * orbit.eccentricity).to_degrees();
+    trace_var!(longitude_amplitude);
+    let latitude_amplitude = orbit.inclination;
+    trace_var!(latitude_amplitude);
+    let result = Self {
+      longitude_amplitude,
+      latitude_amplitude,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the point on the moon directly beneath the planet at a mean
+  /// anomaly, in degrees.
+  #[named]
+  pub fn get_sub_planet_point(&self, mean_anomaly: f64, argument_of_periapsis: f64) -> SurfacePoint {
+    trace_enter!();
+    trace_var!(mean_anomaly);
+    trace_var!(argument_of_periapsis);
+    // The planet appears to lag when the moon speeds through periapsis.
+    let longitude = -self.longitude_amplitude * mean_anomaly.to_radians().sin();
+    trace_var!(longitude);
+    let latitude = self.latitude_amplitude * (mean_anomaly + argument_of_periapsis).to_radians().sin();
+    trace_var!(latitude);
+    let result = SurfacePoint { latitude, longitude };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the furthest the sub-planet point strays from its mean position,
+  /// in degrees.
+  #[named]
+  pub fn get_amplitude(&self) -> f64 {
+    trace_enter!();
+    let result = self.longitude_amplitude.hypot(self.latitude_amplitude);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the face of the moon a point lies on.
+  #[named]
+  pub fn get_face(&self, point: &SurfacePoint) -> Face {
+    trace_enter!();
+    trace_var!(point);
+    let distance = point.get_angular_distance(&SurfacePoint {
+      latitude: 0.0,
+      longitude: 0.0,
+    });
+    trace_var!(distance);
+    let amplitude = self.get_amplitude();
+    trace_var!(amplitude);
+    let result = match distance {
+      distance if distance <= 90.0 - amplitude => Face::NearSide,
+      distance if distance >= 90.0 + amplitude => Face::FarSide,
+      _ => Face::Limb,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the fraction of the surface that is ever in view of the planet.
+  #[named]
+  pub fn get_visible_fraction(&self) -> f64 {
+    trace_enter!();
+    let result = (1.0 + self.get_amplitude().to_radians().sin()) / 2.0;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_moon() {
+    init();
+    trace_enter!();
+    let orbit = OrbitalElements {
+      inclination: 5.15,
+      ..OrbitalElements::new(384_400.0, 0.0549)
+    };
+    let libration = Libration::from_orbit(&orbit);
+    print_var!(libration);
+    assert_approx_eq!(libration.longitude_amplitude, 6.29, 0.01f64);
+    // About 59% of the Moon can be seen from Earth; we're a bit low.
+    assert_approx_eq!(libration.get_visible_fraction(), 0.57, 0.02f64);
+    let point = libration.get_sub_planet_point(90.0, 0.0);
+    assert_approx_eq!(point.longitude, -libration.longitude_amplitude);
+    assert_approx_eq!(point.latitude, libration.latitude_amplitude);
+    let at = |latitude, longitude| SurfacePoint { latitude, longitude };
+    assert_eq!(libration.get_face(&at(10.0, 20.0)), Face::NearSide);
+    assert_eq!(libration.get_face(&at(0.0, 85.0)), Face::Limb);
+    assert_eq!(libration.get_face(&at(-80.0, 100.0)), Face::Limb);
+    assert_eq!(libration.get_face(&at(0.0, 180.0)), Face::FarSide);
+    trace_exit!();
+  }
+}
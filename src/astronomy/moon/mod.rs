@@ -1,15 +1,27 @@
+use serde::Serialize;
+
+use crate::astronomy::composition::Composition;
+use crate::astronomy::gas_giant_planet::math::radiation_belt::LETHAL_DOSE_RATE;
 use crate::astronomy::host_star::HostStar;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::planet::math::oblateness::get_nodal_precession_period;
 use crate::astronomy::planet::Planet;
+use crate::astronomy::time::Time;
 
 pub mod constants;
 pub mod constraints;
 pub mod error;
 use error::Error;
-pub mod math;
+pub mod libration;
+use libration::{Face, Libration, SurfacePoint};
+pub(crate) mod math;
+pub mod name;
 use math::tides::{
   get_lunar_tide, get_neap_tide, get_planetary_tide, get_solar_tide, get_spring_tide, is_moon_tidally_locked,
   is_planet_tidally_locked,
 };
+pub mod population;
+use population::Population;
 pub mod rotation_direction;
 use rotation_direction::RotationDirection;
 
@@ -21,30 +33,26 @@ use rotation_direction::RotationDirection;
 ///
 /// But for now, we're just staying with terrestrial planets, and we'll assume
 /// that moons are just celestial features.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Moon {
   /// The mass of this moon, in Mmoon.
   pub mass: f64,
-  /// The density of this moon, in Dmoon.
+  /// The density of this moon, in g/cm³.
   pub density: f64,
+  /// The bulk composition of this moon, inferred from its density.
+  pub composition: Composition,
   /// The radius of this moon, in Rmoon.
   pub radius: f64,
-  /// The gravity of this moon, in Gearth (not Gmoon).
-  pub gravity: f64,
+  /// The surface gravity of this moon, in Gearth (not Gmoon).
+  pub surface_gravity: f64,
   /// The escape velocity of this moon, in KM/sec.
   pub escape_velocity: f64,
-  /// The Bond albedo of this moon.
+  /// The Bond albedo of this moon, consistent with its composition.
   pub bond_albedo: f64,
-  /// Semi-major axis, in KM.
-  pub semi_major_axis: f64,
-  /// Orbital eccentricity.
-  pub orbital_eccentricity: f64,
-  /// Periapsis.
-  pub periapsis: f64,
-  /// Apoapsis.
-  pub apoapsis: f64,
-  /// Orbital inclination.
-  pub orbital_inclination: f64,
+  /// Whether this moon formed with the planet or was captured.
+  pub population: Population,
+  /// Orbit around the planet, in KM.
+  pub orbit: OrbitalElements,
   /// Rotation direction.
   pub rotation_direction: RotationDirection,
   /// Sidereal orbital period.
@@ -53,6 +61,9 @@ pub struct Moon {
   pub orbital_period: f64,
   /// Rotational period.
   pub rotation_period: f64,
+  /// Period of the nodal precession caused by the planet's oblateness, in
+  /// days.
+  pub nodal_precession_period: f64,
   /// Lunar tide.
   pub lunar_tide: f64,
   /// Solar tide.
@@ -67,48 +78,48 @@ pub struct Moon {
   pub is_planet_tidally_locked: bool,
   /// If the moon is tidally locked to the planet.
   pub is_moon_tidally_locked: bool,
+  /// How the moon appears to rock, if it's tidally locked.
+  pub libration: Option<Libration>,
+  /// Radiation dose rate at the surface from the planet's belts, in Sv/day.
+  pub radiation_dose_rate: f64,
+  /// If the moon orbits within a lethal radiation zone.
+  pub is_in_lethal_radiation_zone: bool,
 }
 
 impl Moon {
+  /// Create a moon on the specified orbit around the planet, in KM.
   #[named]
   pub fn from_environment(
     mass: f64,
+    density: f64,
     host_star: &HostStar,
     star_distance: f64,
     planet: &Planet,
-    planet_distance: f64,
+    orbit: OrbitalElements,
+    population: Population,
   ) -> Result<Moon, Error> {
     trace_enter!();
     trace_var!(host_star);
     trace_var!(star_distance);
     trace_var!(planet);
-    trace_var!(planet_distance);
-    let density = 3.34;
+    trace_var!(orbit);
+    trace_var!(population);
     trace_var!(density);
+    let composition = Composition::from_density(density);
+    trace_var!(composition);
     let radius = (mass / (density / 3.34)).powf(1.0 / 3.0);
     trace_var!(radius);
     // This gives gravity in Earth equivalents, since other units are relative
     // to the Moon, and Gmoon is 0.1654 * Gearth.
-    let gravity = (mass / radius.powf(2.0)) * 0.1654;
-    trace_var!(gravity);
+    let surface_gravity = (mass / radius.powf(2.0)) * 0.1654;
+    trace_var!(surface_gravity);
     // This is in KM/sec.
     let escape_velocity = (mass / radius).sqrt() * 2.380;
     trace_var!(escape_velocity);
-    // Peg this to the albedo of the Moon for the time being.
-    let bond_albedo = 0.136;
+    let bond_albedo = composition.get_typical_albedo();
     trace_var!(bond_albedo);
-    let semi_major_axis = planet_distance;
+    let semi_major_axis = orbit.semi_major_axis;
     trace_var!(semi_major_axis);
-    // Pegged for the time being.
-    let orbital_eccentricity = 0.05;
-    trace_var!(orbital_eccentricity);
-    let periapsis = (1.0 - orbital_eccentricity) * semi_major_axis;
-    trace_var!(periapsis);
-    let apoapsis = (1.0 + orbital_eccentricity) * semi_major_axis;
-    trace_var!(apoapsis);
-    // Pegged.
-    let orbital_inclination = 5.15;
-    trace_var!(orbital_inclination);
     let rotation_direction = RotationDirection::Prograde;
     trace_var!(rotation_direction);
     let sidereal_orbital_period =
@@ -116,7 +127,11 @@ impl Moon {
     trace_var!(sidereal_orbital_period);
     let earth_orbital_period = planet.get_orbital_period() * 365.265;
     trace_var!(earth_orbital_period);
-    let orbital_period = earth_orbital_period / (earth_orbital_period / sidereal_orbital_period - 1.0);
+    // A retrograde moon laps the star's direction the other way.
+    let orbital_period = match orbit.inclination > 90.0 {
+      true => earth_orbital_period / (earth_orbital_period / sidereal_orbital_period + 1.0),
+      false => earth_orbital_period / (earth_orbital_period / sidereal_orbital_period - 1.0),
+    };
     trace_var!(orbital_period);
     let lunar_tide = get_lunar_tide(mass, planet.get_radius(), semi_major_axis);
     trace_var!(lunar_tide);
@@ -133,6 +148,11 @@ impl Moon {
     trace_var!(is_planet_tidally_locked);
     let is_moon_tidally_locked = is_moon_tidally_locked(solar_tide, planetary_tide, host_star.get_current_age(), mass);
     trace_var!(is_moon_tidally_locked);
+    let libration = match is_moon_tidally_locked {
+      true => Some(Libration::from_orbit(&orbit)),
+      false => None,
+    };
+    trace_var!(libration);
     let rotation_period;
     if is_moon_tidally_locked {
       rotation_period = orbital_period;
@@ -140,22 +160,34 @@ impl Moon {
       rotation_period = 3.0;
     }
     trace_var!(rotation_period);
+    let nodal_precession_period = get_nodal_precession_period(
+      planet.get_j2(),
+      planet.get_equatorial_radius_in_km(),
+      semi_major_axis,
+      orbit.eccentricity,
+      orbit.inclination,
+      sidereal_orbital_period,
+    );
+    trace_var!(nodal_precession_period);
+    let radiation_dose_rate = planet.get_radiation_dose_rate(semi_major_axis);
+    trace_var!(radiation_dose_rate);
+    let is_in_lethal_radiation_zone = radiation_dose_rate >= LETHAL_DOSE_RATE;
+    trace_var!(is_in_lethal_radiation_zone);
     let result = Moon {
       mass,
       density,
+      composition,
       radius,
-      gravity,
+      surface_gravity,
       escape_velocity,
       bond_albedo,
-      semi_major_axis,
-      orbital_eccentricity,
-      periapsis,
-      apoapsis,
-      orbital_inclination,
+      population,
+      orbit,
       rotation_direction,
       sidereal_orbital_period,
       orbital_period,
       rotation_period,
+      nodal_precession_period,
       lunar_tide,
       solar_tide,
       planetary_tide,
@@ -163,9 +195,51 @@ impl Moon {
       neap_tide_magnitude,
       is_planet_tidally_locked,
       is_moon_tidally_locked,
+      libration,
+      radiation_dose_rate,
+      is_in_lethal_radiation_zone,
     };
     trace_var!(result);
     trace_exit!();
     Ok(result)
   }
+
+  /// Indicate whether this moon orbits against the planet's spin.
+  #[named]
+  pub fn is_retrograde(&self) -> bool {
+    trace_enter!();
+    let result = self.orbit.inclination > 90.0;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the point on the moon directly beneath its planet at a time after
+  /// epoch, if it's tidally locked.
+  #[named]
+  pub fn get_sub_planet_point(&self, time: Time) -> Option<SurfacePoint> {
+    trace_enter!();
+    trace_var!(time);
+    let period = Time::from_days(self.sidereal_orbital_period);
+    trace_var!(period);
+    let mean_anomaly = self.orbit.mean_anomaly + 360.0 * time.get_cycles(period);
+    trace_var!(mean_anomaly);
+    let result = self
+      .libration
+      .map(|libration| libration.get_sub_planet_point(mean_anomaly, self.orbit.argument_of_periapsis));
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the face of the moon a point lies on, if it's tidally locked.
+  #[named]
+  pub fn get_face(&self, point: &SurfacePoint) -> Option<Face> {
+    trace_enter!();
+    trace_var!(point);
+    let result = self.libration.map(|libration| libration.get_face(point));
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
 }
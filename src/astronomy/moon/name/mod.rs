@@ -0,0 +1,32 @@
+use crate::math::roman::get_roman_numeral;
+
+/// Name a moon by its planet and its position, e.g. "Rigel b II".
+#[named]
+pub fn get_systematic_moon_name(planet_name: &str, index: usize) -> String {
+  trace_enter!();
+  trace_var!(planet_name);
+  trace_var!(index);
+  let numeral = get_roman_numeral(index + 1);
+  trace_var!(numeral);
+  let result = format!("{} {}", planet_name, numeral);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_names() {
+    init();
+    trace_enter!();
+    assert_eq!(get_systematic_moon_name("Rigel b", 0), "Rigel b I");
+    assert_eq!(get_systematic_moon_name("Rigel b", 13), "Rigel b XIV");
+    trace_exit!();
+  }
+}
@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+/// The population a moon belongs to, which says a lot about where it came
+/// from and what its orbit looks like.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum Population {
+  /// Formed alongside the planet: close, prograde, and nearly circular.
+  Regular,
+  /// Captured long after: distant, eccentric, steeply inclined, and often
+  /// retrograde.
+  Irregular,
+}
@@ -1,5 +1,7 @@
+use serde::Serialize;
+
 /// The RotationDirection
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
 pub enum RotationDirection {
   /// Forwards.
   Prograde,
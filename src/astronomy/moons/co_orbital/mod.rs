@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+pub use crate::astronomy::moons::math::co_orbital::is_co_orbital;
+
+/// The ways in which two moons can share an orbit.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum CoOrbitalKind {
+  /// The moons are on slightly different orbits and swap places each time
+  /// the inner one catches up with the outer, as Janus and Epimetheus do.
+  Horseshoe,
+  /// The smaller moon sits 60° ahead of the larger, at its L4 point.
+  LeadingTrojan,
+  /// The smaller moon sits 60° behind the larger, at its L5 point.
+  TrailingTrojan,
+}
+
+/// A pair of moons sharing an orbit.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct CoOrbital {
+  /// How the moons share the orbit.
+  pub kind: CoOrbitalKind,
+  /// Indices of the moons in the `Moons` list, larger first.
+  pub moons: (usize, usize),
+}
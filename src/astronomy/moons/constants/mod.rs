@@ -1,3 +1,5 @@
+use crate::math::probability::Probability;
+
 /// The minimum number of moons we'll generate for a terrestrial planet.
 pub const MINIMUM_TERRESTRIAL_MOONS: usize = 0;
 
@@ -9,3 +11,65 @@ pub const MINIMUM_GAS_GIANT_MOONS: usize = 8;
 
 /// The maximum number of moons we'll generate for a gas giant plant.
 pub const MAXIMUM_GAS_GIANT_MOONS: usize = 20;
+
+/// The minimum number of moons we'll generate for an ice giant.
+pub const MINIMUM_ICE_GIANT_MOONS: usize = 4;
+
+/// The maximum number of moons we'll generate for an ice giant.
+pub const MAXIMUM_ICE_GIANT_MOONS: usize = 15;
+
+/// The probability that a gas giant has a pair of co-orbital moons.
+///
+/// Saturn has two kinds, but they're rare enough elsewhere to be memorable.
+pub const CO_ORBITAL_PROBABILITY: Probability = Probability::from_const(0.1);
+
+/// The minimum mass of the smaller moon of a horseshoe pair, relative to the
+/// larger.
+pub const MINIMUM_HORSESHOE_MASS_RATIO: f64 = 0.1;
+
+/// The maximum mass of the smaller moon of a horseshoe pair, relative to the
+/// larger.
+///
+/// Epimetheus is about a quarter of the mass of Janus.
+pub const MAXIMUM_HORSESHOE_MASS_RATIO: f64 = 0.5;
+
+/// The minimum mass of a Trojan moon, relative to the moon it shares an
+/// orbit with.
+pub const MINIMUM_TROJAN_MASS_RATIO: f64 = 0.001;
+
+/// The maximum mass of a Trojan moon, relative to the moon it shares an
+/// orbit with.
+///
+/// Anything much heavier would drag the pair out of the Lagrange point.
+pub const MAXIMUM_TROJAN_MASS_RATIO: f64 = 0.01;
+
+/// The density of a typical rocky moon, in g/cm³, used for the Roche limit.
+pub const ROCKY_MOON_DENSITY: f64 = 3.35;
+
+/// How far out in the Hill sphere a giant planet's regular moons form.
+///
+/// Beyond this, the planet's disk was too thin to build moons; Callisto
+/// orbits at about 0.04 of Jupiter's Hill radius, and Iapetus at about 0.05
+/// of Saturn's.
+pub const REGULAR_HILL_FRACTION: f64 = 0.06;
+
+/// The probability that any given moon of a giant planet is an irregular,
+/// captured moon.
+///
+/// The giants of our solar system have far more irregular moons than
+/// regular, but most are too small to matter.
+pub const IRREGULAR_MOON_PROBABILITY: Probability = Probability::from_const(0.4);
+
+/// How far out in the Hill sphere a prograde moon can orbit stably.
+///
+/// Beyond about half the Hill radius, the star's tug pulls moons away.
+pub const STABLE_HILL_FRACTION: f64 = 0.4;
+
+/// How many mutual Hill radii apart neighboring moons must be.
+///
+/// Much closer than this and they'd scatter each other; Io and Europa are
+/// about 16 apart.
+pub const MINIMUM_MOON_SPACING: f64 = 5.0;
+
+/// How many places to try for a moon before deciding there's no room.
+pub const MOON_PLACEMENT_ATTEMPTS: usize = 10;
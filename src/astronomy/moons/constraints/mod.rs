@@ -1,17 +1,54 @@
 use rand::prelude::*;
 
 use crate::astronomy::host_star::HostStar;
+use crate::astronomy::ice_giant_planet::constants::NEPTUNE_MASS;
+use crate::astronomy::moon::constants::KG_PER_MOON_MASS;
 use crate::astronomy::moon::constraints::Constraints as MoonConstraints;
+use crate::astronomy::moon::population::Population;
+use crate::astronomy::moon::Moon;
+use crate::astronomy::moons::co_orbital::{CoOrbital, CoOrbitalKind};
 use crate::astronomy::moons::constants::*;
 use crate::astronomy::moons::error::Error;
+use crate::astronomy::moons::math::co_orbital::get_horseshoe_separations;
+use crate::astronomy::moons::math::satellite_zone::{get_moon_count_range, get_satellite_zone, split_satellite_zone};
 use crate::astronomy::moons::Moons;
+use crate::astronomy::orbit::constants::KM_PER_AU;
 use crate::astronomy::planet::Planet;
+use crate::astronomy::star::math::mass::msol_to_kg;
+use crate::math::gravity::{get_hill_radius, get_mutual_hill_radius, get_rigid_roche_limit};
+use crate::math::probability::Probability;
+use crate::merge::Merge;
+
+/// Indicate whether two moons of a planet are far enough apart not to
+/// scatter each other.
+#[named]
+pub fn is_spaced(first: &Moon, second: &Moon, planet: &Planet) -> bool {
+  trace_enter!();
+  let mutual_hill_radius = get_mutual_hill_radius(
+    first.orbit.semi_major_axis,
+    second.orbit.semi_major_axis,
+    first.mass * KG_PER_MOON_MASS,
+    second.mass * KG_PER_MOON_MASS,
+    planet.get_mass_in_kg(),
+  );
+  trace_var!(mutual_hill_radius);
+  let separation = (first.orbit.semi_major_axis - second.orbit.semi_major_axis).abs();
+  trace_var!(separation);
+  let result = separation >= MINIMUM_MOON_SPACING * mutual_hill_radius;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
 
 /// Constraints for creating a moon.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Constraints {
   /// A constraint for moons.
   pub moon_constraints: Option<MoonConstraints>,
+  /// The probability that a gas giant has a pair of co-orbital moons.
+  pub co_orbital_probability: Option<Probability>,
+  /// The probability that any given moon of a giant planet is irregular.
+  pub irregular_probability: Option<Probability>,
 }
 
 impl Constraints {
@@ -28,57 +65,208 @@ impl Constraints {
     trace_var!(planet);
     trace_var!(host_star);
     trace_var!(star_distance);
-    let minimum_count;
-    let maximum_count;
     use Planet::*;
-    match planet {
-      TerrestrialPlanet(_) => {
-        minimum_count = MINIMUM_TERRESTRIAL_MOONS;
-        maximum_count = MAXIMUM_TERRESTRIAL_MOONS;
-      },
-      GasGiantPlanet(_) => {
-        minimum_count = MINIMUM_GAS_GIANT_MOONS;
-        maximum_count = MAXIMUM_GAS_GIANT_MOONS;
-      },
-    }
+    let (minimum_count, maximum_count, relative_mass) = match planet {
+      TerrestrialPlanet(_) => (MINIMUM_TERRESTRIAL_MOONS, MAXIMUM_TERRESTRIAL_MOONS, planet.get_mass()),
+      GasGiantPlanet(_) => (MINIMUM_GAS_GIANT_MOONS, MAXIMUM_GAS_GIANT_MOONS, planet.get_mass()),
+      IceGiantPlanet(_) => (
+        MINIMUM_ICE_GIANT_MOONS,
+        MAXIMUM_ICE_GIANT_MOONS,
+        planet.get_mass() / NEPTUNE_MASS,
+      ),
+    };
+    let (minimum_count, maximum_count) = get_moon_count_range(minimum_count, maximum_count, relative_mass);
     trace_var!(minimum_count);
     trace_var!(maximum_count);
     let moon_constraints = self.moon_constraints.unwrap_or(MoonConstraints::default());
     trace_var!(moon_constraints);
-    let rocky_moon_density = 3.35;
-    trace_var!(rocky_moon_density);
-    let satellite_zone = {
-      let inner = 2.44 * planet.get_radius() * 6_371.0 * (planet.get_density() / rocky_moon_density).powf(1.0 / 3.0);
-      // @todo: improve this.
-      let outer = 20.0 * inner;
-      (inner, outer)
-    };
+    // Moons are solid enough to hold together a little inside where rings
+    // would be torn apart.
+    let roche_limit = get_rigid_roche_limit(
+      planet.get_equatorial_radius_in_km(),
+      planet.get_density(),
+      ROCKY_MOON_DENSITY,
+    );
+    trace_var!(roche_limit);
+    let hill_radius = get_hill_radius(
+      star_distance * KM_PER_AU,
+      planet.get_orbital_eccentricity(),
+      planet.get_mass_in_kg(),
+      msol_to_kg(host_star.get_stellar_mass()),
+    );
+    trace_var!(hill_radius);
+    let satellite_zone = get_satellite_zone(roche_limit, hill_radius);
     trace_var!(satellite_zone);
-    let moons = {
-      let count = rng.gen_range(minimum_count..=maximum_count);
+    // Terrestrial planets' moons are born of giant impacts rather than disks,
+    // so they can orbit anywhere in the satellite zone.
+    let (regular_zone, irregular_zone) = match planet {
+      TerrestrialPlanet(_) => (satellite_zone, satellite_zone),
+      _ => split_satellite_zone(satellite_zone, hill_radius),
+    };
+    trace_var!(regular_zone);
+    trace_var!(irregular_zone);
+    let irregular_probability = match planet {
+      TerrestrialPlanet(_) => Probability::NEVER,
+      _ => self.irregular_probability.unwrap_or(IRREGULAR_MOON_PROBABILITY),
+    };
+    trace_var!(irregular_probability);
+    let mut moons = {
+      // A planet hugging its star may have no room for moons at all.
+      let count = match satellite_zone.0 < satellite_zone.1 {
+        true => rng.gen_range(minimum_count..=maximum_count),
+        false => 0,
+      };
       trace_var!(count);
-      let mut moons = vec![];
-      for _ in 1..count {
-        let planet_distance = rng.gen_range(satellite_zone.0..satellite_zone.1);
-        let moon = moon_constraints.generate(rng, host_star, star_distance, planet, planet_distance)?;
-        trace_var!(moon);
-        moons.push(moon);
+      let mut moons: Vec<Moon> = vec![];
+      for _ in 0..count {
+        let population = match irregular_probability.sample(rng) {
+          true => Population::Irregular,
+          false => Population::Regular,
+        };
+        trace_var!(population);
+        let zone = match population {
+          Population::Regular => regular_zone,
+          Population::Irregular => irregular_zone,
+        };
+        trace_var!(zone);
+        if zone.0 >= zone.1 {
+          continue;
+        }
+        let moon_constraints = MoonConstraints {
+          population: Some(population),
+          ..moon_constraints
+        };
+        // Moons packed too closely would scatter each other, so if we can't
+        // find this one some room, we do without it.
+        for _ in 0..MOON_PLACEMENT_ATTEMPTS {
+          // Moons are spaced out roughly evenly in the log of their distance.
+          let planet_distance = rng.gen_range(zone.0.ln()..zone.1.ln()).exp();
+          let moon = moon_constraints.generate(rng, host_star, star_distance, planet, planet_distance)?;
+          trace_var!(moon);
+          if moons.iter().all(|other| is_spaced(&moon, other, planet)) {
+            moons.push(moon);
+            break;
+          }
+        }
       }
       moons
     };
     trace_var!(moons);
-    let result = Moons { moons };
+    let mut co_orbitals = vec![];
+    let co_orbital_probability = self.co_orbital_probability.unwrap_or(CO_ORBITAL_PROBABILITY);
+    trace_var!(co_orbital_probability);
+    // Captured moons don't bring companions with them.
+    let regular_indices = moons
+      .iter()
+      .enumerate()
+      .filter(|(_, moon)| moon.population == Population::Regular)
+      .map(|(index, _)| index)
+      .collect::<Vec<_>>();
+    trace_var!(regular_indices);
+    if matches!(planet, GasGiantPlanet(_)) && !regular_indices.is_empty() && co_orbital_probability.sample(rng) {
+      let index = regular_indices[rng.gen_range(0..regular_indices.len())];
+      trace_var!(index);
+      let (companion, kind) = self.generate_co_orbital(rng, host_star, star_distance, planet, &moons[index])?;
+      trace_var!(companion);
+      trace_var!(kind);
+      moons.push(companion);
+      co_orbitals.push(CoOrbital {
+        kind,
+        moons: (index, moons.len() - 1),
+      });
+    }
+    trace_var!(co_orbitals);
+    let result = Moons { moons, co_orbitals };
     trace_var!(result);
     trace_exit!();
     Ok(result)
   }
+
+  /// Generate a smaller moon to share the orbit of the specified moon.
+  ///
+  /// Half the time it's a horseshoe companion, just inside or outside the
+  /// orbit, so long as the pair is light enough for one; otherwise it's a
+  /// Trojan at one of the Lagrange points.
+  #[named]
+  pub fn generate_co_orbital<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: &HostStar,
+    star_distance: f64,
+    planet: &Planet,
+    moon: &Moon,
+  ) -> Result<(Moon, CoOrbitalKind), Error> {
+    trace_enter!();
+    trace_var!(moon);
+    let horseshoe = match rng.gen_bool(0.5) {
+      true => {
+        let mass_ratio = rng.gen_range(MINIMUM_HORSESHOE_MASS_RATIO..=MAXIMUM_HORSESHOE_MASS_RATIO);
+        let planet_mass_ratio = moon.mass * (1.0 + mass_ratio) * KG_PER_MOON_MASS / planet.get_mass_in_kg();
+        trace_var!(planet_mass_ratio);
+        get_horseshoe_separations(moon.orbit.semi_major_axis, planet_mass_ratio).map(|(minimum, maximum)| {
+          let separation = rng.gen_range(minimum..=maximum);
+          let semi_major_axis = match rng.gen_bool(0.5) {
+            true => moon.orbit.semi_major_axis + separation,
+            false => moon.orbit.semi_major_axis - separation,
+          };
+          (CoOrbitalKind::Horseshoe, mass_ratio, semi_major_axis)
+        })
+      },
+      false => None,
+    };
+    trace_var!(horseshoe);
+    let (kind, mass_ratio, semi_major_axis) = match horseshoe {
+      Some(horseshoe) => horseshoe,
+      None => {
+        let kind = match rng.gen_bool(0.5) {
+          true => CoOrbitalKind::LeadingTrojan,
+          false => CoOrbitalKind::TrailingTrojan,
+        };
+        let mass_ratio = rng.gen_range(MINIMUM_TROJAN_MASS_RATIO..=MAXIMUM_TROJAN_MASS_RATIO);
+        (kind, mass_ratio, moon.orbit.semi_major_axis)
+      },
+    };
+    trace_var!(kind);
+    trace_var!(mass_ratio);
+    let mass = moon.mass * mass_ratio;
+    trace_var!(mass);
+    trace_var!(semi_major_axis);
+    let moon_constraints = MoonConstraints {
+      minimum_mass: Some(mass),
+      maximum_mass: Some(mass),
+      population: Some(moon.population),
+      ..self.moon_constraints.unwrap_or_default()
+    };
+    trace_var!(moon_constraints);
+    let companion = moon_constraints.generate(rng, host_star, star_distance, planet, semi_major_axis)?;
+    trace_var!(companion);
+    let result = (companion, kind);
+    trace_exit!();
+    Ok(result)
+  }
 }
 
 impl Default for Constraints {
   /// No constraints, just let it all hang out.
   fn default() -> Self {
     let moon_constraints = None;
-    Self { moon_constraints }
+    let co_orbital_probability = None;
+    let irregular_probability = None;
+    Self {
+      moon_constraints,
+      co_orbital_probability,
+      irregular_probability,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      moon_constraints: self.moon_constraints.merged_with(&overrides.moon_constraints),
+      co_orbital_probability: overrides.co_orbital_probability.or(self.co_orbital_probability),
+      irregular_probability: overrides.irregular_probability.or(self.irregular_probability),
+    }
   }
 }
 
@@ -90,6 +278,7 @@ pub mod test {
   use rand::prelude::*;
 
   use super::*;
+  use crate::astronomy::moons::math::co_orbital::is_co_orbital;
   use crate::test::*;
 
   #[named]
@@ -110,6 +299,98 @@ pub mod test {
     let moon = &Constraints::default().generate(&mut rng, &host_star, star_distance, &planet)?;
     trace_var!(moon);
     print_var!(moon);
+    for (inner, outer, _) in moon.get_resonances() {
+      assert!(moon.moons[inner].orbit.semi_major_axis <= moon.moons[outer].orbit.semi_major_axis);
+    }
+    // Co-orbital companions are the only moons allowed to crowd each other.
+    for (index, first) in moon.moons.iter().enumerate() {
+      for (other_index, second) in moon.moons.iter().enumerate().skip(index + 1) {
+        if !moon
+          .co_orbitals
+          .iter()
+          .any(|co_orbital| co_orbital.moons == (index, other_index))
+        {
+          assert!(is_spaced(first, second, planet));
+        }
+      }
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_co_orbital() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = &HostStarConstraints::default().generate(&mut rng)?;
+    let star_distance = 2.0 * host_star.get_frost_line();
+    let planet_constraints = PlanetConstraints {
+      ice_giant_probability: Some(Probability::NEVER),
+      giant_planet_probability: Some(Probability::ALWAYS),
+      ..PlanetConstraints::default()
+    };
+    let planet = &planet_constraints.generate(&mut rng, &host_star, star_distance)?;
+    let constraints = Constraints {
+      co_orbital_probability: Some(Probability::ALWAYS),
+      ..Constraints::default()
+    };
+    let moons = constraints.generate(&mut rng, &host_star, star_distance, &planet)?;
+    print_var!(moons.co_orbitals);
+    if moons.moons.is_empty() {
+      assert!(moons.co_orbitals.is_empty());
+    } else {
+      assert_eq!(moons.co_orbitals.len(), 1);
+      let (larger, smaller) = moons.co_orbitals[0].moons;
+      let (larger, smaller) = (&moons.moons[larger], &moons.moons[smaller]);
+      assert!(smaller.mass < larger.mass);
+      assert!(is_co_orbital(
+        larger.orbit.semi_major_axis,
+        smaller.orbit.semi_major_axis
+      ));
+      let separation = (larger.orbit.semi_major_axis - smaller.orbit.semi_major_axis).abs();
+      let mass_ratio = (larger.mass + smaller.mass) * KG_PER_MOON_MASS / planet.get_mass_in_kg();
+      match moons.co_orbitals[0].kind {
+        CoOrbitalKind::Horseshoe => {
+          let (minimum, maximum) = get_horseshoe_separations(larger.orbit.semi_major_axis, mass_ratio).unwrap();
+          assert!(minimum <= separation && separation <= maximum);
+        },
+        _ => assert_eq!(separation, 0.0),
+      }
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_irregular() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = &HostStarConstraints::default().generate(&mut rng)?;
+    let star_distance = 2.0 * host_star.get_frost_line();
+    let planet_constraints = PlanetConstraints {
+      ice_giant_probability: Some(Probability::NEVER),
+      giant_planet_probability: Some(Probability::ALWAYS),
+      ..PlanetConstraints::default()
+    };
+    let planet = &planet_constraints.generate(&mut rng, &host_star, star_distance)?;
+    let constraints = Constraints {
+      irregular_probability: Some(Probability::ALWAYS),
+      co_orbital_probability: Some(Probability::ALWAYS),
+      ..Constraints::default()
+    };
+    let moons = constraints.generate(&mut rng, &host_star, star_distance, &planet)?;
+    print_var!(moons);
+    assert!(moons.co_orbitals.is_empty());
+    for moon in moons.moons.iter() {
+      assert_eq!(moon.population, Population::Irregular);
+      assert!(moon.orbit.eccentricity >= 0.1);
+    }
     trace_exit!();
     Ok(())
   }
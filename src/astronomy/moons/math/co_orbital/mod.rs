@@ -0,0 +1,114 @@
+use crate::astronomy::planetary_system::math::resonance::{get_period_ratio, RESONANCE_TOLERANCE};
+
+/// Get the widest separation of horseshoe orbits.
+///
+/// Two moons whose semi-major axes differ by less than this can trade places
+/// rather than pass one another.  This is of the order of the Hill radius of
+/// the pair; the mass ratio is the combined mass of the moons over that of
+/// the planet.  The result is in the units of the semi-major axis.
+#[named]
+pub fn get_horseshoe_half_width(semi_major_axis: f64, mass_ratio: f64) -> f64 {
+  trace_enter!();
+  trace_var!(semi_major_axis);
+  trace_var!(mass_ratio);
+  let result = semi_major_axis * (mass_ratio / 3.0).powf(1.0 / 3.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the widest separation of tadpole orbits about L4 or L5.
+///
+/// Anything closer than this librates about one Lagrange point rather than
+/// sweeping out a horseshoe.  The result is in the units of the semi-major
+/// axis.
+#[named]
+pub fn get_tadpole_half_width(semi_major_axis: f64, mass_ratio: f64) -> f64 {
+  trace_enter!();
+  trace_var!(semi_major_axis);
+  trace_var!(mass_ratio);
+  let result = semi_major_axis * (8.0 * mass_ratio / 3.0).sqrt();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the widest separation that still counts as a 1:1 resonance.
+///
+/// Massive pairs have horseshoe regions wider than this, but their periods
+/// would differ too much for us to call them co-orbital.  The result is in
+/// the units of the semi-major axis.
+#[named]
+pub fn get_co_orbital_half_width(semi_major_axis: f64) -> f64 {
+  trace_enter!();
+  trace_var!(semi_major_axis);
+  // Moving inward shortens the period faster than moving outward lengthens
+  // it, so the inner side is the tighter limit.
+  let result = semi_major_axis * (1.0 - (1.0 + RESONANCE_TOLERANCE).powf(-2.0 / 3.0));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the range of separations that make a horseshoe orbit, if any.
+///
+/// It has to be wider than a tadpole, narrower than a horseshoe, and narrow
+/// enough to stay in resonance; a heavy enough pair leaves no room at all.
+#[named]
+pub fn get_horseshoe_separations(semi_major_axis: f64, mass_ratio: f64) -> Option<(f64, f64)> {
+  trace_enter!();
+  let minimum = get_tadpole_half_width(semi_major_axis, mass_ratio);
+  trace_var!(minimum);
+  let maximum = get_horseshoe_half_width(semi_major_axis, mass_ratio).min(get_co_orbital_half_width(semi_major_axis));
+  trace_var!(maximum);
+  let result = (minimum < maximum).then_some((minimum, maximum));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Indicate whether two orbits are in a 1:1 resonance.
+#[named]
+pub fn is_co_orbital(first_semi_major_axis: f64, second_semi_major_axis: f64) -> bool {
+  trace_enter!();
+  let inner = first_semi_major_axis.min(second_semi_major_axis);
+  let outer = first_semi_major_axis.max(second_semi_major_axis);
+  let period_ratio = get_period_ratio(inner, outer);
+  trace_var!(period_ratio);
+  let result = period_ratio - 1.0 <= RESONANCE_TOLERANCE;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_janus_and_epimetheus() {
+    init();
+    trace_enter!();
+    // Janus and Epimetheus, in KM and as a fraction of Saturn's mass.
+    let (janus, epimetheus) = (151_472.0, 151_422.0);
+    let mass_ratio = (1.898e18 + 5.264e17) / 5.683e26;
+    let separation: f64 = janus - epimetheus;
+    let horseshoe_half_width = get_horseshoe_half_width(janus, mass_ratio);
+    print_var!(horseshoe_half_width);
+    let tadpole_half_width = get_tadpole_half_width(janus, mass_ratio);
+    print_var!(tadpole_half_width);
+    assert!(separation > tadpole_half_width);
+    assert!(separation < horseshoe_half_width);
+    assert!(is_co_orbital(janus, epimetheus));
+    let (minimum, maximum) = get_horseshoe_separations(janus, mass_ratio).unwrap();
+    assert!(minimum < separation && separation < maximum);
+    // A pair a hundredth the mass of the planet is too heavy for one.
+    assert_eq!(get_horseshoe_separations(janus, 0.01), None);
+    // Io and Europa are resonant, but not 1:1.
+    assert!(!is_co_orbital(421_700.0, 671_034.0));
+    trace_exit!();
+  }
+}
@@ -0,0 +1,2 @@
+pub mod co_orbital;
+pub mod satellite_zone;
@@ -0,0 +1,113 @@
+use crate::astronomy::moons::constants::*;
+
+/// Get the range of distances from a planet at which moons can orbit, in KM.
+///
+/// This runs from the Roche limit to the outer edge of the stable part of
+/// the Hill sphere; a planet too close to its star may have no room at all,
+/// in which case the outer edge is inside the inner.
+#[named]
+pub fn get_satellite_zone(roche_limit: f64, hill_radius: f64) -> (f64, f64) {
+  trace_enter!();
+  trace_var!(roche_limit);
+  trace_var!(hill_radius);
+  let result = (roche_limit, STABLE_HILL_FRACTION * hill_radius);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Split the satellite zone of a giant planet into the ranges where regular
+/// and irregular moons orbit, in KM.
+///
+/// Regular moons stay close, where the planet's disk built them; irregular
+/// moons were captured into the outer reaches.  Either range may be empty,
+/// in which case its outer edge is inside its inner.
+#[named]
+pub fn split_satellite_zone(satellite_zone: (f64, f64), hill_radius: f64) -> ((f64, f64), (f64, f64)) {
+  trace_enter!();
+  trace_var!(satellite_zone);
+  trace_var!(hill_radius);
+  let boundary = (REGULAR_HILL_FRACTION * hill_radius).min(satellite_zone.1);
+  trace_var!(boundary);
+  let result = (
+    (satellite_zone.0, boundary),
+    (boundary.max(satellite_zone.0), satellite_zone.1),
+  );
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Scale a range of moon counts by the mass of the planet.
+///
+/// Mass is relative to the planet the range was drawn from (Earth, Jupiter,
+/// or Neptune); lighter planets have fewer moons, heavier ones no more.
+#[named]
+pub fn get_moon_count_range(minimum: usize, maximum: usize, mass: f64) -> (usize, usize) {
+  trace_enter!();
+  trace_var!(minimum);
+  trace_var!(maximum);
+  trace_var!(mass);
+  let scale = mass.powf(1.0 / 3.0).min(1.0);
+  trace_var!(scale);
+  let result = (
+    (minimum as f64 * scale).round() as usize,
+    (maximum as f64 * scale).round() as usize,
+  );
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::astronomy::orbit::constants::KM_PER_AU;
+  use crate::astronomy::star::math::mass::msol_to_kg;
+  use crate::math::gravity::{get_hill_radius, get_rigid_roche_limit};
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_earth() {
+    init();
+    trace_enter!();
+    let hill_radius = get_hill_radius(KM_PER_AU, 0.0167, 5.972E24, msol_to_kg(1.0));
+    let roche_limit = get_rigid_roche_limit(6_371.0, 5.514, ROCKY_MOON_DENSITY);
+    let (inner, outer) = get_satellite_zone(roche_limit, hill_radius);
+    print_var!((inner, outer));
+    assert!(inner < 384_400.0 && 384_400.0 < outer);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_split_satellite_zone() {
+    init();
+    trace_enter!();
+    // Jupiter.
+    let hill_radius = get_hill_radius(5.2 * KM_PER_AU, 0.0489, 1.898E27, msol_to_kg(1.0));
+    let roche_limit = get_rigid_roche_limit(71_492.0, 1.326, ROCKY_MOON_DENSITY);
+    let satellite_zone = get_satellite_zone(roche_limit, hill_radius);
+    let (regular, irregular) = split_satellite_zone(satellite_zone, hill_radius);
+    print_var!((regular, irregular));
+    // Callisto, and Himalia.
+    assert!(regular.0 < 1_882_700.0 && 1_882_700.0 < regular.1);
+    assert!(irregular.0 < 11_460_000.0 && 11_460_000.0 < irregular.1);
+    assert_eq!(regular.1, irregular.0);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_moon_count_range() {
+    init();
+    trace_enter!();
+    assert_eq!(get_moon_count_range(8, 20, 1.0), (8, 20));
+    assert_eq!(get_moon_count_range(8, 20, 3.0), (8, 20));
+    assert_eq!(get_moon_count_range(8, 20, 0.125), (4, 10));
+    assert_eq!(get_moon_count_range(0, 2, 0.001), (0, 0));
+    trace_exit!();
+  }
+}
@@ -1,11 +1,47 @@
+use serde::Serialize;
+
 use crate::astronomy::moon::Moon;
+use crate::astronomy::planetary_system::math::resonance::get_resonance;
 
+pub mod co_orbital;
+use co_orbital::CoOrbital;
 pub mod constants;
 pub mod constraints;
 pub mod error;
+pub(crate) mod math;
 
 /// The `Moons` object is a wrapper around a list of `Moon` objects.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Moons {
   pub moons: Vec<Moon>,
+  /// Pairs of moons that share an orbit.
+  pub co_orbitals: Vec<CoOrbital>,
+}
+
+impl Moons {
+  /// Find the resonances between moons on neighboring orbits.
+  ///
+  /// The moons aren't kept in any particular order, so each resonance comes
+  /// with the indices of the inner and outer moon, innermost pair first.
+  #[named]
+  pub fn get_resonances(&self) -> Vec<(usize, usize, (u8, u8))> {
+    trace_enter!();
+    let mut indices = (0..self.moons.len()).collect::<Vec<usize>>();
+    indices.sort_by(|&a, &b| {
+      let a = self.moons[a].orbit.semi_major_axis;
+      let b = self.moons[b].orbit.semi_major_axis;
+      a.partial_cmp(&b).unwrap()
+    });
+    let result = indices
+      .windows(2)
+      .filter_map(|pair| {
+        let inner = self.moons[pair[0]].orbit.semi_major_axis;
+        let outer = self.moons[pair[1]].orbit.semi_major_axis;
+        get_resonance(inner, outer).map(|resonance| (pair[0], pair[1], resonance))
+      })
+      .collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
 }
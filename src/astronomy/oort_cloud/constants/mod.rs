@@ -0,0 +1,39 @@
+use crate::math::probability::Probability;
+
+/// The probability that a planetary system has an Oort cloud.
+///
+/// Anything that formed with planetesimals to scatter should have one.
+pub const PROBABILITY: Probability = Probability::from_const(0.9);
+
+/// The inner edge of the Sun's Oort cloud, in AU.
+///
+/// This is the inner edge of the Hills cloud; the outer cloud proper begins
+/// around 20,000 AU.
+pub const SOLAR_INNER_RADIUS: f64 = 2_000.0;
+
+/// The outer edge of the Sun's Oort cloud, in AU.
+///
+/// Beyond this, the galactic tide strips comets away; the edge scales with
+/// the cube root of the stellar mass.
+pub const SOLAR_OUTER_RADIUS: f64 = 100_000.0;
+
+/// Minimum mass of an Oort cloud, in Mearth.
+pub const MINIMUM_MASS: f64 = 0.5;
+
+/// Maximum mass of an Oort cloud, in Mearth.
+///
+/// Estimates for ours run from about 1 to 5 Mearth.
+pub const MAXIMUM_MASS: f64 = 10.0;
+
+/// The mass of the Sun's Oort cloud, in Mearth.
+pub const SOLAR_MASS: f64 = 2.0;
+
+/// The rate at which the Sun's Oort cloud sends new comets inside the frost
+/// line, per year.
+pub const SOLAR_COMET_FLUX: f64 = 10.0;
+
+/// The fraction of new comets that become great comets, bright enough to
+/// stop people in the street.
+///
+/// The Sun gets one every decade or so.
+pub const GREAT_COMET_FRACTION: f64 = 0.01;
@@ -0,0 +1,122 @@
+use rand::prelude::*;
+
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::oort_cloud::constants::*;
+use crate::astronomy::oort_cloud::OortCloud;
+use crate::math::probability::Probability;
+use crate::merge::Merge;
+
+/// Constraints for creating an Oort cloud.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+  /// The probability that a system has an Oort cloud at all.
+  pub probability: Option<Probability>,
+  /// The minimum mass, in Mearth.
+  pub minimum_mass: Option<f64>,
+  /// The maximum mass, in Mearth.
+  pub maximum_mass: Option<f64>,
+}
+
+impl Constraints {
+  /// Generate.
+  ///
+  /// The cloud's size is set by the star's grip against the galactic tide;
+  /// its comet flux goes with its mass.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R, host_star: &HostStar) -> Option<OortCloud> {
+    trace_enter!();
+    let probability = self.probability.unwrap_or(PROBABILITY);
+    trace_var!(probability);
+    if !probability.sample(rng) {
+      trace_exit!();
+      return None;
+    }
+    let scale = host_star.get_stellar_mass().powf(1.0 / 3.0);
+    trace_var!(scale);
+    let inner_radius = SOLAR_INNER_RADIUS * scale;
+    trace_var!(inner_radius);
+    let outer_radius = SOLAR_OUTER_RADIUS * scale;
+    trace_var!(outer_radius);
+    let minimum_mass = self.minimum_mass.unwrap_or(MINIMUM_MASS);
+    trace_var!(minimum_mass);
+    let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
+    trace_var!(maximum_mass);
+    let mass = rng.gen_range(minimum_mass.ln()..=maximum_mass.ln()).exp();
+    trace_var!(mass);
+    let comet_flux = SOLAR_COMET_FLUX * mass / SOLAR_MASS;
+    trace_var!(comet_flux);
+    let result = OortCloud {
+      inner_radius,
+      outer_radius,
+      mass,
+      comet_flux,
+    };
+    trace_var!(result);
+    trace_exit!();
+    Some(result)
+  }
+}
+
+impl Default for Constraints {
+  /// No constraints, just let it all hang out.
+  fn default() -> Self {
+    let probability = None;
+    let minimum_mass = None;
+    let maximum_mass = None;
+    Self {
+      probability,
+      minimum_mass,
+      maximum_mass,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      probability: overrides.probability.or(self.probability),
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+  use crate::astronomy::host_star::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = HostStarConstraints::default().generate(&mut rng)?;
+    let constraints = Constraints {
+      probability: Some(Probability::ALWAYS),
+      minimum_mass: Some(SOLAR_MASS),
+      maximum_mass: Some(SOLAR_MASS),
+    };
+    let oort_cloud = constraints.generate(&mut rng, &host_star).unwrap();
+    print_var!(oort_cloud);
+    assert_approx_eq!(oort_cloud.comet_flux, SOLAR_COMET_FLUX);
+    assert_approx_eq!(oort_cloud.get_expected_great_comets(10.0), 1.0);
+    assert!(oort_cloud.contains(oort_cloud.inner_radius * 2.0));
+    assert!(!oort_cloud.contains(oort_cloud.outer_radius * 2.0));
+    assert_approx_eq!(oort_cloud.get_expected_comets(100.0), 100.0 * SOLAR_COMET_FLUX);
+    let constraints = Constraints {
+      probability: Some(Probability::NEVER),
+      ..Constraints::default()
+    };
+    assert!(constraints.generate(&mut rng, &host_star).is_none());
+    trace_exit!();
+    Ok(())
+  }
+}
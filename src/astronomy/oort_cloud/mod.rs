@@ -0,0 +1,54 @@
+use serde::Serialize;
+
+pub mod constants;
+use constants::*;
+pub mod constraints;
+
+/// An `OortCloud` is the distant shell of comets around a star, from which
+/// long-period comets fall inward.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OortCloud {
+  /// The inner edge, in AU.
+  pub inner_radius: f64,
+  /// The outer edge, in AU.
+  pub outer_radius: f64,
+  /// The total mass, in Mearth.
+  pub mass: f64,
+  /// New comets sent inside the frost line, per year.
+  pub comet_flux: f64,
+}
+
+impl OortCloud {
+  /// Get the expected number of new comets over a span of years.
+  #[named]
+  pub fn get_expected_comets(&self, years: f64) -> f64 {
+    trace_enter!();
+    trace_var!(years);
+    let result = self.comet_flux * years;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the expected number of great comets over a span of years.
+  #[named]
+  pub fn get_expected_great_comets(&self, years: f64) -> f64 {
+    trace_enter!();
+    trace_var!(years);
+    let result = self.get_expected_comets(years) * GREAT_COMET_FRACTION;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether the specified distance in AU lies within the cloud.
+  #[named]
+  pub fn contains(&self, distance: f64) -> bool {
+    trace_enter!();
+    trace_var!(distance);
+    let result = distance >= self.inner_radius && distance <= self.outer_radius;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
@@ -0,0 +1,11 @@
+/// Kilometers in an astronomical unit.
+pub const KM_PER_AU: f64 = 149_597_870.7;
+
+/// Days in a (Julian) year.
+pub const DAYS_PER_YEAR: f64 = 365.25;
+
+/// How closely we solve Kepler's equation, in radians.
+pub const KEPLER_TOLERANCE: f64 = 1e-12;
+
+/// The most iterations we'll spend solving Kepler's equation.
+pub const KEPLER_MAXIMUM_ITERATIONS: usize = 50;
@@ -0,0 +1,54 @@
+use std::f64::consts::PI;
+
+use crate::astronomy::orbit::constants::*;
+
+/// Solve Kepler's equation (M = E - e sin E) for the eccentric anomaly.
+///
+/// Both anomalies are in radians.  This is Newton's method, starting from M
+/// for gentle orbits and from π for eccentric ones, which converges quickly
+/// for anything short of a parabola.
+#[named]
+pub fn get_eccentric_anomaly(mean_anomaly: f64, eccentricity: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mean_anomaly);
+  trace_var!(eccentricity);
+  let mean_anomaly = mean_anomaly.rem_euclid(2.0 * PI);
+  let mut result = match eccentricity < 0.8 {
+    true => mean_anomaly,
+    false => PI,
+  };
+  for _ in 0..KEPLER_MAXIMUM_ITERATIONS {
+    let step = (result - eccentricity * result.sin() - mean_anomaly) / (1.0 - eccentricity * result.cos());
+    result -= step;
+    if step.abs() < KEPLER_TOLERANCE {
+      break;
+    }
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_eccentric_anomaly() {
+    init();
+    trace_enter!();
+    assert_approx_eq!(get_eccentric_anomaly(1.0, 0.0), 1.0);
+    assert_approx_eq!(get_eccentric_anomaly(PI, 0.5), PI);
+    for eccentricity in [0.1, 0.5, 0.9, 0.99] {
+      for mean_anomaly in [0.1, 1.0, 3.0, 5.0] {
+        let eccentric_anomaly = get_eccentric_anomaly(mean_anomaly, eccentricity);
+        let check = eccentric_anomaly - eccentricity * eccentric_anomaly.sin();
+        assert_approx_eq!(check, mean_anomaly, 1e-9);
+      }
+    }
+    trace_exit!();
+  }
+}
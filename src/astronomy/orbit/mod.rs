@@ -0,0 +1,240 @@
+use rand::prelude::*;
+use serde::Serialize;
+use std::f64::consts::PI;
+
+use crate::astronomy::time::Time;
+
+pub mod constants;
+pub(crate) mod math;
+use math::kepler::get_eccentric_anomaly;
+
+/// The six classical Keplerian elements of an orbit.
+///
+/// Distances are in whatever unit suits the orbiting body: AU for planets and
+/// binary companions, KM for moons.  Angles are in degrees, measured against
+/// the reference plane of whatever is being orbited (the ecliptic of a
+/// planetary system, or the equator of a planet).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct OrbitalElements {
+  /// Semi-major axis.
+  pub semi_major_axis: f64,
+  /// Eccentricity.
+  pub eccentricity: f64,
+  /// Inclination, in degrees.
+  pub inclination: f64,
+  /// Longitude of the ascending node, in degrees.
+  pub longitude_of_ascending_node: f64,
+  /// Argument of periapsis, in degrees.
+  pub argument_of_periapsis: f64,
+  /// Mean anomaly at epoch, in degrees.
+  pub mean_anomaly: f64,
+}
+
+impl OrbitalElements {
+  /// An orbit in the reference plane, at periapsis at epoch.
+  #[named]
+  pub fn new(semi_major_axis: f64, eccentricity: f64) -> Self {
+    trace_enter!();
+    trace_var!(semi_major_axis);
+    trace_var!(eccentricity);
+    let inclination = 0.0;
+    let longitude_of_ascending_node = 0.0;
+    let argument_of_periapsis = 0.0;
+    let mean_anomaly = 0.0;
+    let result = Self {
+      semi_major_axis,
+      eccentricity,
+      inclination,
+      longitude_of_ascending_node,
+      argument_of_periapsis,
+      mean_anomaly,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// The same orbit, turned to a random node and periapsis, with the body
+  /// somewhere random along it.
+  ///
+  /// This gives the body a random mean longitude at epoch, so a system drawn
+  /// at epoch has its planets scattered around their orbits.
+  #[named]
+  pub fn with_random_orientation<R: Rng + ?Sized>(self, rng: &mut R) -> Self {
+    trace_enter!();
+    let longitude_of_ascending_node = rng.gen_range(0.0..360.0);
+    trace_var!(longitude_of_ascending_node);
+    let argument_of_periapsis = rng.gen_range(0.0..360.0);
+    trace_var!(argument_of_periapsis);
+    let mean_anomaly = rng.gen_range(0.0..360.0);
+    trace_var!(mean_anomaly);
+    let result = Self {
+      longitude_of_ascending_node,
+      argument_of_periapsis,
+      mean_anomaly,
+      ..self
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the longitude of periapsis, in degrees.
+  ///
+  /// This is measured partly along the reference plane and partly along the
+  /// orbit, so it's only a true angle for orbits in the reference plane.
+  #[named]
+  pub fn get_longitude_of_periapsis(&self) -> f64 {
+    trace_enter!();
+    let result = (self.longitude_of_ascending_node + self.argument_of_periapsis).rem_euclid(360.0);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the mean longitude at epoch, in degrees.
+  #[named]
+  pub fn get_mean_longitude(&self) -> f64 {
+    trace_enter!();
+    let result = (self.get_longitude_of_periapsis() + self.mean_anomaly).rem_euclid(360.0);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// The same orbit, with the body moved along it to the specified mean
+  /// longitude (in degrees) at epoch.
+  #[named]
+  pub fn with_mean_longitude(self, mean_longitude: f64) -> Self {
+    trace_enter!();
+    trace_var!(mean_longitude);
+    let mean_anomaly = (mean_longitude - self.get_longitude_of_periapsis()).rem_euclid(360.0);
+    trace_var!(mean_anomaly);
+    let result = Self { mean_anomaly, ..self };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the closest approach to the body orbited.
+  #[named]
+  pub fn get_periapsis(&self) -> f64 {
+    trace_enter!();
+    let result = (1.0 - self.eccentricity) * self.semi_major_axis;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the position of the body at a time after epoch, given its orbital
+  /// period.
+  ///
+  /// The position is relative to the body orbited, in the units of the
+  /// semi-major axis, with the reference plane as x-y and x pointing toward
+  /// the reference direction.
+  #[named]
+  pub fn position_at(&self, time: Time, period: Time) -> (f64, f64, f64) {
+    trace_enter!();
+    trace_var!(time);
+    trace_var!(period);
+    let mean_anomaly = self.mean_anomaly.to_radians() + 2.0 * PI * time.get_cycles(period);
+    trace_var!(mean_anomaly);
+    let eccentric_anomaly = get_eccentric_anomaly(mean_anomaly, self.eccentricity);
+    trace_var!(eccentric_anomaly);
+    // In the plane of the orbit, with periapsis along x.
+    let x = self.semi_major_axis * (eccentric_anomaly.cos() - self.eccentricity);
+    let y = self.semi_major_axis * (1.0 - self.eccentricity.powf(2.0)).sqrt() * eccentric_anomaly.sin();
+    let (sin_node, cos_node) = self.longitude_of_ascending_node.to_radians().sin_cos();
+    let (sin_periapsis, cos_periapsis) = self.argument_of_periapsis.to_radians().sin_cos();
+    let (sin_inclination, cos_inclination) = self.inclination.to_radians().sin_cos();
+    let result = (
+      (cos_node * cos_periapsis - sin_node * sin_periapsis * cos_inclination) * x
+        - (cos_node * sin_periapsis + sin_node * cos_periapsis * cos_inclination) * y,
+      (sin_node * cos_periapsis + cos_node * sin_periapsis * cos_inclination) * x
+        + (cos_node * cos_periapsis * cos_inclination - sin_node * sin_periapsis) * y,
+      sin_periapsis * sin_inclination * x + cos_periapsis * sin_inclination * y,
+    );
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the furthest distance from the body orbited.
+  #[named]
+  pub fn get_apoapsis(&self) -> f64 {
+    trace_enter!();
+    let result = (1.0 + self.eccentricity) * self.semi_major_axis;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_orbital_elements() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let orbit = OrbitalElements::new(1.0, 0.0167);
+    assert_approx_eq!(orbit.get_periapsis(), 0.9833);
+    assert_approx_eq!(orbit.get_apoapsis(), 1.0167);
+    let turned = orbit.with_random_orientation(&mut rng);
+    print_var!(turned);
+    assert_eq!(turned.semi_major_axis, orbit.semi_major_axis);
+    assert_eq!(turned.eccentricity, orbit.eccentricity);
+    assert_eq!(turned.inclination, orbit.inclination);
+    assert!((0.0..360.0).contains(&turned.mean_anomaly));
+    let moved = turned.with_mean_longitude(123.0);
+    assert_approx_eq!(moved.get_mean_longitude(), 123.0);
+    assert_eq!(moved.get_longitude_of_periapsis(), turned.get_longitude_of_periapsis());
+    // In the reference plane, on a circular orbit, mean longitude is where
+    // the body actually is.
+    let circular = OrbitalElements::new(1.0, 0.0).with_mean_longitude(90.0);
+    let (x, y, _) = circular.position_at(Time::EPOCH, Time::from_years(1.0));
+    assert_approx_eq!(x, 0.0);
+    assert_approx_eq!(y, 1.0);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_position_at() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let orbit = OrbitalElements::new(1.0, 0.5);
+    let periapsis = orbit.position_at(Time::EPOCH, Time::from_years(1.0));
+    assert_approx_eq!(periapsis.0, 0.5);
+    assert_approx_eq!(periapsis.1, 0.0);
+    let apoapsis = orbit.position_at(Time::from_years(0.5), Time::from_years(1.0));
+    assert_approx_eq!(apoapsis.0, -1.5);
+    let orbit = OrbitalElements {
+      inclination: rng.gen_range(0.0..180.0),
+      ..orbit.with_random_orientation(&mut rng)
+    };
+    print_var!(orbit);
+    for time in [0.0, 0.1, 0.37, 0.8] {
+      let (x, y, z) = orbit.position_at(Time::from_years(time), Time::from_years(1.0));
+      let distance = (x * x + y * y + z * z).sqrt();
+      assert!(distance >= orbit.get_periapsis() - 1e-9);
+      assert!(distance <= orbit.get_apoapsis() + 1e-9);
+      // A full period later, we're back where we started.
+      let (x2, y2, z2) = orbit.position_at(Time::from_years(time + 1.0), Time::from_years(1.0));
+      assert_approx_eq!(x, x2);
+      assert_approx_eq!(y, y2);
+      assert_approx_eq!(z, z2);
+    }
+    trace_exit!();
+  }
+}
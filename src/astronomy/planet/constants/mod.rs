@@ -1 +1,16 @@
+use crate::math::probability::Probability;
 
+/// Ice giants form further out than gas giants, where the disk was too thin
+/// to build a core quickly enough to hold onto much gas.  This is how far
+/// out they start, as a multiple of the frost line.
+pub const ICE_GIANT_FROST_LINE_MULTIPLE: f64 = 2.0;
+
+/// The probability that a giant far enough out is an ice giant.
+pub const ICE_GIANT_PROBABILITY: Probability = Probability::from_const(0.5);
+
+/// The probability that a planet beyond the frost line is a giant, around a
+/// star with the Sun's metallicity.
+///
+/// Metal-rich stars build cores quickly enough to catch plenty of gas;
+/// metal-poor ones often leave only rock and ice out there.
+pub const GIANT_PLANET_PROBABILITY: Probability = Probability::from_const(0.9);
@@ -0,0 +1,96 @@
+use crate::astronomy::gas_giant_planet::constraints::builder::Builder as GasGiantPlanetBuilder;
+use crate::astronomy::ice_giant_planet::constraints::builder::Builder as IceGiantPlanetBuilder;
+use crate::astronomy::planet::constraints::Constraints;
+use crate::astronomy::terrestrial_planet::constraints::builder::Builder as TerrestrialPlanetBuilder;
+use crate::math::range::error::Error;
+
+/// A fluent builder for planet constraints.
+///
+/// The terrestrial, gas giant, and ice giant builders are checked along with this one,
+/// at `build()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Builder {
+  gas_giant_planet: Option<GasGiantPlanetBuilder>,
+  ice_giant_planet: Option<IceGiantPlanetBuilder>,
+  terrestrial_planet: Option<TerrestrialPlanetBuilder>,
+}
+
+impl Builder {
+  /// Use these constraints for planets beyond the frost line.
+  pub fn gas_giant_planet(mut self, builder: GasGiantPlanetBuilder) -> Self {
+    self.gas_giant_planet = Some(builder);
+    self
+  }
+
+  /// Use these constraints for ice giants, well beyond the frost line.
+  pub fn ice_giant_planet(mut self, builder: IceGiantPlanetBuilder) -> Self {
+    self.ice_giant_planet = Some(builder);
+    self
+  }
+
+  /// Use these constraints for planets within the frost line.
+  pub fn terrestrial_planet(mut self, builder: TerrestrialPlanetBuilder) -> Self {
+    self.terrestrial_planet = Some(builder);
+    self
+  }
+
+  /// Aim for a habitable planet.
+  pub fn habitable(mut self) -> Self {
+    self.terrestrial_planet = Some(self.terrestrial_planet.unwrap_or_default().habitable());
+    self
+  }
+
+  /// Check the constraints and return them.
+  #[named]
+  pub fn build(self) -> Result<Constraints, Error> {
+    trace_enter!();
+    let gas_giant_planet_constraints = self.gas_giant_planet.map(|builder| builder.build()).transpose()?;
+    trace_var!(gas_giant_planet_constraints);
+    let ice_giant_planet_constraints = self.ice_giant_planet.map(|builder| builder.build()).transpose()?;
+    trace_var!(ice_giant_planet_constraints);
+    let terrestrial_planet_constraints = self.terrestrial_planet.map(|builder| builder.build()).transpose()?;
+    trace_var!(terrestrial_planet_constraints);
+    let result = Constraints {
+      gas_giant_planet_constraints,
+      ice_giant_planet_constraints,
+      terrestrial_planet_constraints,
+      ..Constraints::default()
+    };
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::astronomy::gas_giant_planet::constraints::Constraints as GasGiantPlanetConstraints;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_build() {
+    init();
+    trace_enter!();
+    let constraints = Constraints::builder()
+      .gas_giant_planet(GasGiantPlanetConstraints::builder().mass_range(1.0..2.0))
+      .habitable()
+      .build()
+      .unwrap();
+    print_var!(constraints);
+    assert_eq!(
+      constraints.gas_giant_planet_constraints.unwrap().maximum_mass,
+      Some(2.0)
+    );
+    assert!(constraints.terrestrial_planet_constraints.is_some());
+    assert_eq!(
+      Constraints::builder()
+        .gas_giant_planet(GasGiantPlanetConstraints::builder().mass_range(2.0..1.0))
+        .build(),
+      Err(Error::RangeEmpty("mass"))
+    );
+    trace_exit!();
+  }
+}
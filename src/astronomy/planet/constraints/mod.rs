@@ -1,42 +1,98 @@
 use rand::prelude::*;
 
+use crate::astronomy::gas_giant_planet::constants::MIGRATION_PROBABILITY;
 use crate::astronomy::gas_giant_planet::constraints::Constraints as GasGiantPlanetConstraints;
 use crate::astronomy::host_star::HostStar;
+use crate::astronomy::ice_giant_planet::constraints::Constraints as IceGiantPlanetConstraints;
+use crate::astronomy::planet::constants::*;
 use crate::astronomy::planet::error::Error;
 use crate::astronomy::planet::Planet;
+use crate::astronomy::star::math::metallicity::get_giant_planet_occurrence_factor;
 use crate::astronomy::terrestrial_planet::constraints::Constraints as TerrestrialPlanetConstraints;
+use crate::math::probability::Probability;
+use crate::merge::Merge;
+pub mod builder;
+use builder::Builder;
 
 /// Constraints for creating a planet.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Constraints {
   /// Gas Giant planet constraints.
   pub gas_giant_planet_constraints: Option<GasGiantPlanetConstraints>,
+  /// Ice Giant planet constraints.
+  pub ice_giant_planet_constraints: Option<IceGiantPlanetConstraints>,
+  /// The probability that a giant far enough out is an ice giant.
+  pub ice_giant_probability: Option<Probability>,
+  /// The probability that a planet beyond the frost line is a giant.
+  ///
+  /// If this isn't given, it's worked out from the host star's metallicity.
+  pub giant_planet_probability: Option<Probability>,
   /// Terrestrial planet constraints.
   pub terrestrial_planet_constraints: Option<TerrestrialPlanetConstraints>,
 }
 
 impl Constraints {
+  /// Start building constraints fluently.
+  pub fn builder() -> Builder {
+    Builder::default()
+  }
+
   /// Aim for a habitable planet.
+  ///
+  /// A gas giant migrating inward would scatter a habitable planet, so we
+  /// don't let any.
   pub fn habitable() -> Self {
+    let gas_giant_planet_constraints = Some(GasGiantPlanetConstraints {
+      migration_probability: Some(Probability::NEVER),
+      ..GasGiantPlanetConstraints::default()
+    });
     let terrestrial_planet_constraints = Some(TerrestrialPlanetConstraints::habitable());
     Self {
+      gas_giant_planet_constraints,
       terrestrial_planet_constraints,
       ..Constraints::default()
     }
   }
 
   /// Generate.
+  ///
+  /// Beyond the frost line we usually get giants, more often around
+  /// metal-rich stars; beyond a multiple of it, some of those are ice giants
+  /// instead of gas giants.  Inside the frost line, once in a while, we get
+  /// a gas giant that formed further out and migrated inward.
   #[named]
   pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R, host_star: &HostStar, distance: f64) -> Result<Planet, Error> {
     trace_enter!();
     use Planet::*;
+    let frost_line = host_star.get_frost_line();
+    trace_var!(frost_line);
+    let ice_giant_probability = self.ice_giant_probability.unwrap_or(ICE_GIANT_PROBABILITY);
+    trace_var!(ice_giant_probability);
+    let giant_planet_probability = self.giant_planet_probability.unwrap_or_else(|| {
+      let factor = get_giant_planet_occurrence_factor(host_star.get_metallicity());
+      Probability::new((GIANT_PLANET_PROBABILITY.get() * factor).min(1.0)).unwrap()
+    });
+    trace_var!(giant_planet_probability);
+    let gas_giant_planet_constraints = self
+      .gas_giant_planet_constraints
+      .unwrap_or(GasGiantPlanetConstraints::default());
+    trace_var!(gas_giant_planet_constraints);
+    let migration_probability = gas_giant_planet_constraints.migration_probability.unwrap_or_else(|| {
+      let factor = get_giant_planet_occurrence_factor(host_star.get_metallicity());
+      Probability::new((MIGRATION_PROBABILITY.get() * factor).min(1.0)).unwrap()
+    });
+    trace_var!(migration_probability);
     let result = {
-      if distance >= host_star.get_frost_line() {
-        let constraints = self
-          .gas_giant_planet_constraints
-          .unwrap_or(GasGiantPlanetConstraints::default());
-        trace_var!(constraints);
-        GasGiantPlanet(constraints.generate(rng, host_star, distance)?)
+      if distance >= frost_line && giant_planet_probability.sample(rng) {
+        if distance >= frost_line * ICE_GIANT_FROST_LINE_MULTIPLE && ice_giant_probability.sample(rng) {
+          let constraints = self.ice_giant_planet_constraints.unwrap_or_default();
+          trace_var!(constraints);
+          IceGiantPlanet(constraints.generate(rng, host_star, distance)?)
+        } else {
+          GasGiantPlanet(gas_giant_planet_constraints.generate(rng, host_star, distance)?)
+        }
+      } else if distance < frost_line && migration_probability.sample(rng) {
+        GasGiantPlanet(gas_giant_planet_constraints.generate(rng, host_star, distance)?)
       } else {
         let constraints = self
           .terrestrial_planet_constraints
@@ -55,21 +111,49 @@ impl Default for Constraints {
   /// No constraints, just let it all hang out.
   fn default() -> Self {
     let gas_giant_planet_constraints = None;
+    let ice_giant_planet_constraints = None;
+    let ice_giant_probability = None;
+    let giant_planet_probability = None;
     let terrestrial_planet_constraints = None;
     Self {
       gas_giant_planet_constraints,
+      ice_giant_planet_constraints,
+      ice_giant_probability,
+      giant_planet_probability,
       terrestrial_planet_constraints,
     }
   }
 }
 
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      gas_giant_planet_constraints: self
+        .gas_giant_planet_constraints
+        .merged_with(&overrides.gas_giant_planet_constraints),
+      ice_giant_planet_constraints: self
+        .ice_giant_planet_constraints
+        .merged_with(&overrides.ice_giant_planet_constraints),
+      ice_giant_probability: overrides.ice_giant_probability.or(self.ice_giant_probability),
+      giant_planet_probability: overrides.giant_planet_probability.or(self.giant_planet_probability),
+      terrestrial_planet_constraints: self
+        .terrestrial_planet_constraints
+        .merged_with(&overrides.terrestrial_planet_constraints),
+    }
+  }
+}
+
 #[cfg(test)]
 pub mod test {
 
   use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+  use crate::astronomy::host_star::error::Error as HostStarError;
   use rand::prelude::*;
 
   use super::*;
+  use crate::astronomy::star::constants::{MAXIMUM_METALLICITY, MINIMUM_METALLICITY};
+  use crate::astronomy::star::constraints::Constraints as StarConstraints;
+  use crate::astronomy::star::Star;
   use crate::test::*;
 
   #[named]
@@ -89,6 +173,71 @@ pub mod test {
     Ok(())
   }
 
+  #[named]
+  #[test]
+  pub fn test_ice_giant() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = &HostStarConstraints::default().generate(&mut rng)?;
+    let frost_line = host_star.get_frost_line();
+    let always = Constraints {
+      ice_giant_probability: Some(Probability::ALWAYS),
+      giant_planet_probability: Some(Probability::ALWAYS),
+      ..Constraints::default()
+    };
+    let planet = always.generate(&mut rng, host_star, frost_line * ICE_GIANT_FROST_LINE_MULTIPLE)?;
+    print_var!(planet);
+    assert!(matches!(planet, Planet::IceGiantPlanet(_)));
+    let planet = always.generate(&mut rng, host_star, frost_line * 1.5)?;
+    assert!(matches!(planet, Planet::GasGiantPlanet(_)));
+    let never = Constraints {
+      ice_giant_probability: Some(Probability::NEVER),
+      giant_planet_probability: Some(Probability::ALWAYS),
+      ..Constraints::default()
+    };
+    let planet = never.generate(&mut rng, host_star, frost_line * 10.0)?;
+    assert!(matches!(planet, Planet::GasGiantPlanet(_)));
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_metallicity() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let star = StarConstraints::habitable()
+      .generate(&mut rng)
+      .map_err(HostStarError::from)?;
+    let distance = star.frost_line * 1.5;
+    let mut count_giants = |metallicity: f64| -> Result<usize, Error> {
+      let host_star = HostStar::Star(Star {
+        metallicity,
+        ..star.clone()
+      });
+      let mut result = 0;
+      for _ in 0..100 {
+        let planet = Constraints::default().generate(&mut rng, &host_star, distance)?;
+        if matches!(planet, Planet::GasGiantPlanet(_)) {
+          result += 1;
+        }
+      }
+      Ok(result)
+    };
+    let metal_poor = count_giants(MINIMUM_METALLICITY)?;
+    print_var!(metal_poor);
+    let metal_rich = count_giants(MAXIMUM_METALLICITY)?;
+    print_var!(metal_rich);
+    assert!(metal_poor < 20);
+    assert_eq!(metal_rich, 100);
+    trace_exit!();
+    Ok(())
+  }
+
   #[named]
   #[test]
   pub fn test_habitable() -> Result<(), Error> {
@@ -105,4 +254,36 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_migration() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = &HostStarConstraints::habitable().generate(&mut rng)?;
+    let distance = host_star.get_frost_line() * 0.1;
+    let migration = |probability: Probability| Constraints {
+      gas_giant_planet_constraints: Some(
+        GasGiantPlanetConstraints::builder()
+          .migration_probability(probability)
+          .build()
+          .unwrap(),
+      ),
+      ..Constraints::default()
+    };
+    let planet = migration(Probability::ALWAYS).generate(&mut rng, host_star, distance)?;
+    print_var!(planet);
+    assert!(matches!(planet, Planet::GasGiantPlanet(_)));
+    assert_eq!(planet.get_semi_major_axis(), distance);
+    let planet = migration(Probability::NEVER).generate(&mut rng, host_star, distance)?;
+    assert!(matches!(planet, Planet::TerrestrialPlanet(_)));
+    for _ in 0..10 {
+      let planet = Constraints::habitable().generate(&mut rng, host_star, distance)?;
+      assert!(matches!(planet, Planet::TerrestrialPlanet(_)));
+    }
+    trace_exit!();
+    Ok(())
+  }
 }
@@ -1,5 +1,6 @@
 use crate::astronomy::gas_giant_planet::error::Error as GasGiantPlanetError;
 use crate::astronomy::host_star::error::Error as HostStarError;
+use crate::astronomy::ice_giant_planet::error::Error as IceGiantPlanetError;
 use crate::astronomy::terrestrial_planet::error::Error as TerrestrialPlanetError;
 
 /// Planet errors.
@@ -9,6 +10,8 @@ pub enum Error {
   GasGiantPlanetError(GasGiantPlanetError),
   /// HostStar error.
   HostStarError(HostStarError),
+  /// IceGiantPlanet error.
+  IceGiantPlanetError(IceGiantPlanetError),
   /// TerrestrialPlanet error.
   TerrestrialPlanetError(TerrestrialPlanetError),
   /// The planet type is uninhabitable.
@@ -26,6 +29,10 @@ honeyholt_define_brief!(Error, |error: &Error| {
       "an error occurred in the host star ({})",
       honeyholt_brief!(host_star_error)
     ),
+    IceGiantPlanetError(ice_giant_planet_error) => format!(
+      "an error occurred in the ice giant planet ({})",
+      honeyholt_brief!(ice_giant_planet_error)
+    ),
     TerrestrialPlanetError(terrestrial_planet_error) => format!(
       "an error occurred in the terrestrial planet ({})",
       honeyholt_brief!(terrestrial_planet_error)
@@ -48,6 +55,13 @@ impl From<HostStarError> for Error {
   }
 }
 
+impl From<IceGiantPlanetError> for Error {
+  #[named]
+  fn from(error: IceGiantPlanetError) -> Self {
+    Error::IceGiantPlanetError(error)
+  }
+}
+
 impl From<TerrestrialPlanetError> for Error {
   #[named]
   fn from(error: TerrestrialPlanetError) -> Self {
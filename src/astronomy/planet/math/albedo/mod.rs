@@ -0,0 +1,26 @@
+/// Bond albedo of a typical gas giant.
+///
+/// Jupiter's is about 0.34, going by Cassini's flyby.
+pub const GAS_GIANT_BOND_ALBEDO: f64 = 0.343;
+
+/// Bond albedo of a typical ice giant.
+///
+/// Uranus's is about 0.30, and Neptune's about 0.29.
+pub const ICE_GIANT_BOND_ALBEDO: f64 = 0.300;
+
+/// The phase integral of a Lambertian sphere.
+pub const LAMBERTIAN_PHASE_INTEGRAL: f64 = 1.5;
+
+/// Estimate the geometric albedo from the Bond albedo.
+///
+/// This treats the planet as a Lambertian sphere; real planets scatter more
+/// light back toward the source, so it errs a little on the dim side.
+#[named]
+pub fn bond_to_geometric_albedo(bond_albedo: f64) -> f64 {
+  trace_enter!();
+  trace_var!(bond_albedo);
+  let result = bond_albedo / LAMBERTIAN_PHASE_INTEGRAL;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
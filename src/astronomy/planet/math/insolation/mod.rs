@@ -0,0 +1,52 @@
+use std::f64::consts::PI;
+
+/// Calculate the daily mean insolation at a given latitude, relative to the
+/// flux at the top of the atmosphere.
+///
+/// Latitude and solar declination are in degrees.  This is the usual
+/// hour-angle integral, including polar day and polar night; the equator at
+/// equinox gets 1/π.
+#[named]
+pub fn get_daily_insolation(latitude: f64, declination: f64) -> f64 {
+  trace_enter!();
+  trace_var!(latitude);
+  trace_var!(declination);
+  let latitude = latitude.to_radians();
+  let declination = declination.to_radians();
+  let product = latitude.tan() * declination.tan();
+  trace_var!(product);
+  let sines = latitude.sin() * declination.sin();
+  let cosines = latitude.cos() * declination.cos();
+  let result = match product {
+    // The sun never sets.
+    product if product >= 1.0 => PI * sines,
+    // The sun never rises.
+    product if product <= -1.0 => 0.0,
+    product => {
+      let sunset_hour_angle = (-product).acos();
+      sunset_hour_angle * sines + cosines * sunset_hour_angle.sin()
+    },
+  } / PI;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_daily_insolation() {
+    init();
+    trace_enter!();
+    assert_approx_eq!(get_daily_insolation(0.0, 0.0), 1.0 / PI);
+    assert_approx_eq!(get_daily_insolation(89.0, -23.44), 0.0);
+    // The summer pole gets more than the equator does.
+    assert!(get_daily_insolation(90.0, 23.44) > get_daily_insolation(0.0, 23.44));
+    trace_exit!();
+  }
+}
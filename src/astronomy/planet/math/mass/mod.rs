@@ -1,22 +1,23 @@
-pub const EARTH_MASS_PER_JUPITER_MASS: f64 = 317.8;
+pub const KG_PER_EARTH_MASS: f64 = 5.972E24;
+pub const KG_PER_JUPITER_MASS: f64 = 1.898E27;
 
-/// Convert from Mearth to Mjupiter.
+/// Convert from Mearth to KG.
 #[named]
-pub fn earth_mass_to_jupiter_mass(mass: f64) -> f64 {
+pub fn earth_mass_to_kg(mass: f64) -> f64 {
   trace_enter!();
   trace_var!(mass);
-  let result = mass / EARTH_MASS_PER_JUPITER_MASS;
+  let result = mass * KG_PER_EARTH_MASS;
   trace_var!(result);
   trace_exit!();
   result
 }
 
-/// Convert from Mjupiter to Mearth.
+/// Convert from Mjupiter to KG.
 #[named]
-pub fn jupiter_mass_to_earth_mass(mass: f64) -> f64 {
+pub fn jupiter_mass_to_kg(mass: f64) -> f64 {
   trace_enter!();
   trace_var!(mass);
-  let result = mass * EARTH_MASS_PER_JUPITER_MASS;
+  let result = mass * KG_PER_JUPITER_MASS;
   trace_var!(result);
   trace_exit!();
   result
@@ -1 +1,6 @@
+pub mod albedo;
+pub mod insolation;
 pub mod mass;
+pub mod oblateness;
+pub mod radius;
+pub mod tidal_braking;
@@ -0,0 +1,171 @@
+use std::f64::consts::PI;
+
+/// The gravitational constant, in m³/kg/s².
+pub const GRAVITATIONAL_CONSTANT: f64 = 6.674E-11;
+
+/// Seconds per Dearth.
+pub const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Moment of inertia factor (C/MR²) of a typical terrestrial planet.
+///
+/// Earth's is 0.3307; a uniform sphere's would be 0.4.
+pub const TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR: f64 = 0.3307;
+
+/// Moment of inertia factor (C/MR²) of a typical gas giant.
+///
+/// Jupiter's is about 0.254, Saturn's about 0.22.
+pub const GAS_GIANT_MOMENT_OF_INERTIA_FACTOR: f64 = 0.254;
+
+/// Moment of inertia factor (C/MR²) of a typical ice giant.
+///
+/// Uranus's is about 0.22, Neptune's about 0.23.
+pub const ICE_GIANT_MOMENT_OF_INERTIA_FACTOR: f64 = 0.23;
+
+/// Calculate the ratio of centrifugal to gravitational acceleration at the
+/// equator, usually written as q or m.
+///
+/// Rotation period is in Dearth, density is in g/cm³.  Earth's is 0.00345.
+#[named]
+pub fn get_rotational_parameter(rotation_period: f64, density: f64) -> f64 {
+  trace_enter!();
+  trace_var!(rotation_period);
+  trace_var!(density);
+  let angular_velocity = 2.0 * PI / (rotation_period * SECONDS_PER_DAY);
+  trace_var!(angular_velocity);
+  let density_kg_m3 = density * 1_000.0;
+  trace_var!(density_kg_m3);
+  let result = 3.0 * angular_velocity.powf(2.0) / (4.0 * PI * GRAVITATIONAL_CONSTANT * density_kg_m3);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the flattening (oblateness) of a rotating body.
+///
+/// This is the Darwin-Radau relation solved for flattening, which accounts
+/// for how centrally condensed the body is; with a moment of inertia factor
+/// of 0.4 it reduces to the Maclaurin result for a uniform body, 5q/4.
+#[named]
+pub fn get_oblateness(rotational_parameter: f64, moment_of_inertia_factor: f64) -> f64 {
+  trace_enter!();
+  trace_var!(rotational_parameter);
+  trace_var!(moment_of_inertia_factor);
+  let eta = 2.5 * (1.0 - 1.5 * moment_of_inertia_factor);
+  trace_var!(eta);
+  let result = 5.0 * rotational_parameter / (2.0 * (1.0 + eta.powf(2.0)));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate equatorial and polar radii from a mean radius and oblateness.
+///
+/// The mean radius is taken to be the radius of a sphere of equal volume.
+#[named]
+pub fn get_equatorial_and_polar_radii(radius: f64, oblateness: f64) -> (f64, f64) {
+  trace_enter!();
+  trace_var!(radius);
+  trace_var!(oblateness);
+  let equatorial_radius = radius * (1.0 - oblateness).powf(-1.0 / 3.0);
+  trace_var!(equatorial_radius);
+  let polar_radius = equatorial_radius * (1.0 - oblateness);
+  trace_var!(polar_radius);
+  let result = (equatorial_radius, polar_radius);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the J2 gravitational harmonic, to first order.
+///
+/// Earth's is 0.00108.
+#[named]
+pub fn get_j2(oblateness: f64, rotational_parameter: f64) -> f64 {
+  trace_enter!();
+  trace_var!(oblateness);
+  trace_var!(rotational_parameter);
+  let result = (2.0 * oblateness - rotational_parameter) / 3.0;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the period of nodal precession of a satellite's orbit caused by
+/// the oblateness of the body it orbits.
+///
+/// Equatorial radius and semi-major axis just need to share units; the
+/// result is in the same units as the orbital period.  Inclination is in
+/// degrees, relative to the equator.  A J2 of zero means no precession,
+/// which is reported as an infinite period.
+#[named]
+pub fn get_nodal_precession_period(
+  j2: f64,
+  equatorial_radius: f64,
+  semi_major_axis: f64,
+  orbital_eccentricity: f64,
+  orbital_inclination: f64,
+  orbital_period: f64,
+) -> f64 {
+  trace_enter!();
+  trace_var!(j2);
+  trace_var!(equatorial_radius);
+  trace_var!(semi_major_axis);
+  trace_var!(orbital_eccentricity);
+  trace_var!(orbital_inclination);
+  trace_var!(orbital_period);
+  let rate = 1.5 * j2 * (equatorial_radius / semi_major_axis).powf(2.0) * orbital_inclination.to_radians().cos().abs()
+    / (1.0 - orbital_eccentricity.powf(2.0)).powf(2.0);
+  trace_var!(rate);
+  let result = orbital_period / rate;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_earth() {
+    init();
+    trace_enter!();
+    let q = get_rotational_parameter(0.99727, 5.514);
+    assert_approx_eq!(q, 0.00345, 1e-4f64);
+    let oblateness = get_oblateness(q, TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR);
+    assert_approx_eq!(oblateness, 0.00335, 1e-4f64);
+    assert_approx_eq!(get_j2(oblateness, q), 0.00108, 1e-4f64);
+    let (equatorial_radius, polar_radius) = get_equatorial_and_polar_radii(6_371.0, oblateness);
+    assert_approx_eq!(equatorial_radius, 6_378.1, 1.0f64);
+    assert_approx_eq!(polar_radius, 6_356.8, 1.0f64);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_jupiter() {
+    init();
+    trace_enter!();
+    let q = get_rotational_parameter(0.41354, 1.326);
+    assert_approx_eq!(q, 0.089, 1e-2f64);
+    let oblateness = get_oblateness(q, GAS_GIANT_MOMENT_OF_INERTIA_FACTOR);
+    assert_approx_eq!(oblateness, 0.0649, 5e-3f64);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_nodal_precession_period() {
+    init();
+    trace_enter!();
+    // A low Earth orbit at 7,000 km and 28.5º precesses about 6.3º per day.
+    let orbital_period = 2.0 * PI * (7_000_000.0f64.powf(3.0) / 3.986E14).sqrt() / SECONDS_PER_DAY;
+    let period = get_nodal_precession_period(0.00108263, 6_378.1, 7_000.0, 0.0, 28.5, orbital_period);
+    print_var!(period);
+    assert_approx_eq!(360.0 / period, 6.3, 0.2f64);
+    trace_exit!();
+  }
+}
@@ -0,0 +1,24 @@
+pub const METERS_PER_EARTH_RADIUS: f64 = 6.371E6;
+pub const METERS_PER_JUPITER_RADIUS: f64 = 6.9911E7;
+
+/// Convert from Rearth to M.
+#[named]
+pub fn earth_radius_to_meters(radius: f64) -> f64 {
+  trace_enter!();
+  trace_var!(radius);
+  let result = radius * METERS_PER_EARTH_RADIUS;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Convert from Rjupiter to M.
+#[named]
+pub fn jupiter_radius_to_meters(radius: f64) -> f64 {
+  trace_enter!();
+  trace_var!(radius);
+  let result = radius * METERS_PER_JUPITER_RADIUS;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
@@ -0,0 +1,106 @@
+use std::f64::consts::PI;
+
+use crate::astronomy::planet::math::oblateness::GRAVITATIONAL_CONSTANT;
+
+/// Tidal lag (k2/Q) of a typical terrestrial planet.
+///
+/// Earth's is about 0.3/12; most of the dissipation happens in shallow seas.
+pub const TERRESTRIAL_TIDAL_LAG: f64 = 0.025;
+
+/// Tidal lag (k2/Q) of a typical gas giant.
+///
+/// Jupiter's is about 0.59/35,000, going by the outward drift of Io.
+pub const GAS_GIANT_TIDAL_LAG: f64 = 1.7E-5;
+
+/// Tidal lag (k2/Q) of a typical ice giant.
+///
+/// Uranus's is about 0.1/10,000, going by the orbits of its moons.
+pub const ICE_GIANT_TIDAL_LAG: f64 = 1.0E-5;
+
+/// Calculate the torque that a moon's tide exerts on the planet it raises it
+/// on, in N·m.
+///
+/// Mass is in KG; radius and semi-major axis are in meters.
+#[named]
+pub fn get_tidal_torque(tidal_lag: f64, moon_mass: f64, planet_radius: f64, semi_major_axis: f64) -> f64 {
+  trace_enter!();
+  trace_var!(tidal_lag);
+  trace_var!(moon_mass);
+  trace_var!(planet_radius);
+  trace_var!(semi_major_axis);
+  let result = 1.5 * tidal_lag * GRAVITATIONAL_CONSTANT * moon_mass.powf(2.0) * planet_radius.powf(5.0)
+    / semi_major_axis.powf(6.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate how quickly a torque lengthens a rotation period, in seconds
+/// per second.
+///
+/// Torque is in N·m, moment of inertia in kg·m², and rotation period in
+/// seconds.  Earth's day lengthens by about 2 ms per century.
+#[named]
+pub fn get_rotation_period_rate(torque: f64, moment_of_inertia: f64, rotation_period: f64) -> f64 {
+  trace_enter!();
+  trace_var!(torque);
+  trace_var!(moment_of_inertia);
+  trace_var!(rotation_period);
+  let result = rotation_period.powf(2.0) * torque / (2.0 * PI * moment_of_inertia);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the rotation period at another time, given its current rate of
+/// change, assuming a constant torque.
+///
+/// Rate is dimensionless; time just needs to share units with the rotation
+/// period, and may be negative.  A torque that would stop the planet's
+/// spinning altogether gives an infinite period.
+#[named]
+pub fn get_rotation_period_at(rotation_period: f64, rate: f64, time: f64) -> f64 {
+  trace_enter!();
+  trace_var!(rotation_period);
+  trace_var!(rate);
+  trace_var!(time);
+  // Angular velocity changes linearly under a constant torque.
+  let remaining = 1.0 - rate * time / rotation_period;
+  trace_var!(remaining);
+  let result = match remaining > 0.0 {
+    true => rotation_period / remaining,
+    false => f64::INFINITY,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::astronomy::planet::math::oblateness::{SECONDS_PER_DAY, TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR};
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_earth() {
+    init();
+    trace_enter!();
+    let torque = get_tidal_torque(TERRESTRIAL_TIDAL_LAG, 7.342E22, 6.371E6, 3.844E8);
+    print_var!(torque);
+    let moment_of_inertia = TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR * 5.972E24 * 6.371E6f64.powf(2.0);
+    let rate = get_rotation_period_rate(torque, moment_of_inertia, SECONDS_PER_DAY);
+    let milliseconds_per_century = rate * 36_525.0 * SECONDS_PER_DAY * 1_000.0;
+    print_var!(milliseconds_per_century);
+    assert_approx_eq!(milliseconds_per_century, 2.0, 0.5f64);
+    let past = get_rotation_period_at(1.0, rate, -1.0E9 * 365.25);
+    let future = get_rotation_period_at(1.0, rate, 1.0E9 * 365.25);
+    print_var!(past);
+    print_var!(future);
+    assert!(past < 1.0 && 1.0 < future);
+    assert_eq!(get_rotation_period_at(1.0, 1.0, 2.0), f64::INFINITY);
+    trace_exit!();
+  }
+}
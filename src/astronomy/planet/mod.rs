@@ -1,17 +1,32 @@
+use serde::Serialize;
+
 use crate::astronomy::gas_giant_planet::GasGiantPlanet;
+use crate::astronomy::ice_giant_planet::IceGiantPlanet;
+use crate::astronomy::orbit::OrbitalElements;
 use crate::astronomy::terrestrial_planet::TerrestrialPlanet;
 
 pub mod constants;
 pub mod constraints;
 pub mod error;
 use error::Error;
-pub mod math;
+pub(crate) mod math;
+pub mod name;
+pub mod seasons;
+use math::albedo::{GAS_GIANT_BOND_ALBEDO, ICE_GIANT_BOND_ALBEDO};
+use math::mass::{earth_mass_to_kg, jupiter_mass_to_kg};
+use math::oblateness::{
+  GAS_GIANT_MOMENT_OF_INERTIA_FACTOR, ICE_GIANT_MOMENT_OF_INERTIA_FACTOR, TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR,
+};
+use math::radius::{earth_radius_to_meters, jupiter_radius_to_meters, METERS_PER_JUPITER_RADIUS};
+use math::tidal_braking::{GAS_GIANT_TIDAL_LAG, ICE_GIANT_TIDAL_LAG, TERRESTRIAL_TIDAL_LAG};
 
 /// The `Planet` class.  This will get complicated.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum Planet {
   /// Gas Giant Planet.
   GasGiantPlanet(GasGiantPlanet),
+  /// Ice Giant Planet.
+  IceGiantPlanet(IceGiantPlanet),
   /// Terrestrial Planet.
   TerrestrialPlanet(TerrestrialPlanet),
 }
@@ -25,12 +40,42 @@ impl Planet {
     let result = match &self {
       TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.density,
       GasGiantPlanet(gas_giant_planet) => gas_giant_planet.density,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.density,
     };
     trace_var!(result);
     trace_exit!();
     result
   }
 
+  /// Get the name of the planet.
+  #[named]
+  pub fn get_name(&self) -> String {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.name.clone(),
+      GasGiantPlanet(gas_giant_planet) => gas_giant_planet.name.clone(),
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.name.clone(),
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Set the name of the planet.
+  #[named]
+  pub fn set_name(&mut self, name: String) {
+    trace_enter!();
+    trace_var!(name);
+    use Planet::*;
+    match self {
+      TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.name = name,
+      GasGiantPlanet(gas_giant_planet) => gas_giant_planet.name = name,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.name = name,
+    }
+    trace_exit!();
+  }
+
   /// Get mass of the planet.
   #[named]
   pub fn get_mass(&self) -> f64 {
@@ -39,6 +84,7 @@ impl Planet {
     let result = match &self {
       TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.mass,
       GasGiantPlanet(gas_giant_planet) => gas_giant_planet.mass,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.mass,
     };
     trace_var!(result);
     trace_exit!();
@@ -53,12 +99,231 @@ impl Planet {
     let result = match &self {
       TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.radius,
       GasGiantPlanet(gas_giant_planet) => gas_giant_planet.radius,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.radius,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the surface gravity of the planet, in Gearth.
+  #[named]
+  pub fn get_surface_gravity(&self) -> f64 {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.surface_gravity,
+      GasGiantPlanet(gas_giant_planet) => gas_giant_planet.surface_gravity,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.surface_gravity,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the escape velocity of the planet, in km/s.
+  #[named]
+  pub fn get_escape_velocity(&self) -> f64 {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.escape_velocity,
+      GasGiantPlanet(gas_giant_planet) => gas_giant_planet.escape_velocity,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.escape_velocity,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the rotation period of the planet, in Dearth.
+  #[named]
+  pub fn get_rotation_period(&self) -> f64 {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.rotation_period,
+      GasGiantPlanet(gas_giant_planet) => gas_giant_planet.rotation_period,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.rotation_period,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the oblateness of the planet.
+  #[named]
+  pub fn get_oblateness(&self) -> f64 {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.oblateness,
+      GasGiantPlanet(gas_giant_planet) => gas_giant_planet.oblateness,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.oblateness,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the J2 gravitational harmonic of the planet.
+  #[named]
+  pub fn get_j2(&self) -> f64 {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.j2,
+      GasGiantPlanet(gas_giant_planet) => gas_giant_planet.j2,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.j2,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the mass of the planet, in KG.
+  ///
+  /// Unlike `get_mass()`, this is in the same units regardless of planet
+  /// type, so it can be compared with the masses of moons.
+  #[named]
+  pub fn get_mass_in_kg(&self) -> f64 {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(terrestrial_planet) => earth_mass_to_kg(terrestrial_planet.mass),
+      GasGiantPlanet(gas_giant_planet) => jupiter_mass_to_kg(gas_giant_planet.mass),
+      IceGiantPlanet(ice_giant_planet) => earth_mass_to_kg(ice_giant_planet.mass),
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the moment of inertia of the planet about its spin axis, in kg·m².
+  #[named]
+  pub fn get_moment_of_inertia(&self) -> f64 {
+    trace_enter!();
+    use Planet::*;
+    let moment_of_inertia_factor = match &self {
+      TerrestrialPlanet(_) => TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR,
+      GasGiantPlanet(_) => GAS_GIANT_MOMENT_OF_INERTIA_FACTOR,
+      IceGiantPlanet(_) => ICE_GIANT_MOMENT_OF_INERTIA_FACTOR,
+    };
+    trace_var!(moment_of_inertia_factor);
+    let radius = self.get_equatorial_radius_in_km() * 1_000.0;
+    trace_var!(radius);
+    let result = moment_of_inertia_factor * self.get_mass_in_kg() * radius.powf(2.0);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the tidal lag (k2/Q) of the planet, which governs how much of the
+  /// tides raised on it is lost to friction.
+  #[named]
+  pub fn get_tidal_lag(&self) -> f64 {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(_) => TERRESTRIAL_TIDAL_LAG,
+      GasGiantPlanet(_) => GAS_GIANT_TIDAL_LAG,
+      IceGiantPlanet(_) => ICE_GIANT_TIDAL_LAG,
     };
     trace_var!(result);
     trace_exit!();
     result
   }
 
+  /// Get the Bond albedo of the planet.
+  ///
+  /// Only terrestrial planets track their own; giants use a typical value.
+  #[named]
+  pub fn get_bond_albedo(&self) -> f64 {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.bond_albedo,
+      GasGiantPlanet(_) => GAS_GIANT_BOND_ALBEDO,
+      IceGiantPlanet(_) => ICE_GIANT_BOND_ALBEDO,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the equatorial radius of the planet, in KM.
+  ///
+  /// Unlike `get_radius()`, this is in the same units regardless of planet
+  /// type, so it can be compared with the orbits of moons.
+  #[named]
+  pub fn get_equatorial_radius_in_km(&self) -> f64 {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(terrestrial_planet) => earth_radius_to_meters(terrestrial_planet.equatorial_radius),
+      GasGiantPlanet(gas_giant_planet) => jupiter_radius_to_meters(gas_giant_planet.equatorial_radius),
+      IceGiantPlanet(ice_giant_planet) => earth_radius_to_meters(ice_giant_planet.equatorial_radius),
+    } / 1_000.0;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the radiation dose rate at a given distance, in Sv/day.
+  ///
+  /// The distance is in KM, as with the orbits of moons.  Only gas giants
+  /// are considered to have radiation belts worth worrying about.
+  #[named]
+  pub fn get_radiation_dose_rate(&self, distance: f64) -> f64 {
+    trace_enter!();
+    trace_var!(distance);
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(_) | IceGiantPlanet(_) => 0.0,
+      GasGiantPlanet(gas_giant_planet) => {
+        gas_giant_planet.get_radiation_dose_rate(distance * 1_000.0 / METERS_PER_JUPITER_RADIUS)
+      },
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the planet's orbit around its host star, in AU.
+  #[named]
+  pub fn get_orbit(&self) -> OrbitalElements {
+    trace_enter!();
+    use Planet::*;
+    let result = match &self {
+      TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.orbit,
+      GasGiantPlanet(gas_giant_planet) => gas_giant_planet.orbit,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.orbit,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the semi-major axis of the planet's orbit, in AU.
+  #[named]
+  pub fn get_semi_major_axis(&self) -> f64 {
+    trace_enter!();
+    let result = self.get_orbit().semi_major_axis;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the eccentricity of the planet's orbit.
+  #[named]
+  pub fn get_orbital_eccentricity(&self) -> f64 {
+    trace_enter!();
+    let result = self.get_orbit().eccentricity;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Get the orbital period of the planet.
   #[named]
   pub fn get_orbital_period(&self) -> f64 {
@@ -67,6 +332,7 @@ impl Planet {
     let result = match &self {
       TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.orbital_period,
       GasGiantPlanet(gas_giant_planet) => gas_giant_planet.orbital_period,
+      IceGiantPlanet(ice_giant_planet) => ice_giant_planet.orbital_period,
     };
     trace_var!(result);
     trace_exit!();
@@ -0,0 +1,184 @@
+use rand::prelude::*;
+
+use crate::astronomy::star::name::markov::MarkovNameGenerator;
+
+/// Letters for planets, in order of distance from the host star.
+pub const PLANET_LETTERS: &str = "bcdefghijklmnopqrstuvwxyz";
+
+/// Names of gods, heroes, and monsters, for planets named the old way.
+pub const MYTHIC_NAMES: &[&str] = &[
+  "Aegir",
+  "Ajax",
+  "Amaterasu",
+  "Anansi",
+  "Anubis",
+  "Apollo",
+  "Ares",
+  "Artemis",
+  "Astraea",
+  "Athena",
+  "Atlas",
+  "Baldur",
+  "Bastet",
+  "Bellona",
+  "Brigid",
+  "Ceres",
+  "Chronos",
+  "Cybele",
+  "Dagda",
+  "Demeter",
+  "Eos",
+  "Freya",
+  "Frigg",
+  "Gaia",
+  "Hathor",
+  "Hecate",
+  "Helios",
+  "Hermes",
+  "Hestia",
+  "Horus",
+  "Hyperion",
+  "Idun",
+  "Inanna",
+  "Indra",
+  "Isis",
+  "Ishtar",
+  "Janus",
+  "Juno",
+  "Lugh",
+  "Maat",
+  "Marduk",
+  "Minerva",
+  "Mithra",
+  "Morrigan",
+  "Nemesis",
+  "Njord",
+  "Nut",
+  "Odin",
+  "Osiris",
+  "Pallas",
+  "Perun",
+  "Prometheus",
+  "Ra",
+  "Rhea",
+  "Selene",
+  "Sekhmet",
+  "Sif",
+  "Svarog",
+  "Thoth",
+  "Tyr",
+  "Vesta",
+  "Vulcan",
+];
+
+/// The order of the chain used for invented planet names.
+pub const INVENTED_NAME_ORDER: usize = 2;
+
+/// The shortest invented planet name.
+pub const MINIMUM_INVENTED_NAME_LENGTH: usize = 3;
+
+/// The longest invented planet name.
+pub const MAXIMUM_INVENTED_NAME_LENGTH: usize = 9;
+
+/// How a system's planets are named.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PlanetNaming {
+  /// After the host star, lettered by distance, e.g. "Rigel b".
+  Systematic,
+  /// After figures from myth, e.g. "Hyperion".
+  Mythic,
+  /// Made up to sound like figures from myth, e.g. "Hypheus".
+  Invented,
+}
+
+/// Name a planet by its host star and its position, e.g. "Rigel b".
+#[named]
+pub fn get_systematic_planet_name(star_name: &str, index: usize) -> String {
+  trace_enter!();
+  trace_var!(star_name);
+  trace_var!(index);
+  let letter = PLANET_LETTERS.chars().cycle().nth(index).unwrap();
+  let result = format!("{} {}", star_name, letter);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Name a system's planets, innermost first, after figures from myth.
+///
+/// No two planets in the system share a name; if we run out of figures,
+/// the rest are named systematically.
+#[named]
+pub fn generate_mythic_planet_names<R: Rng + ?Sized>(rng: &mut R, star_name: &str, count: usize) -> Vec<String> {
+  trace_enter!();
+  trace_var!(star_name);
+  trace_var!(count);
+  let mut result: Vec<String> = MYTHIC_NAMES
+    .choose_multiple(rng, count)
+    .map(|name| name.to_string())
+    .collect();
+  for index in result.len()..count {
+    result.push(get_systematic_planet_name(star_name, index));
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Name a system's planets with names made up to sound like figures from
+/// myth.
+///
+/// No two planets in the system share a name.
+#[named]
+pub fn generate_invented_planet_names<R: Rng + ?Sized>(rng: &mut R, count: usize) -> Vec<String> {
+  trace_enter!();
+  trace_var!(count);
+  let generator = MarkovNameGenerator::train(
+    INVENTED_NAME_ORDER,
+    MINIMUM_INVENTED_NAME_LENGTH,
+    MAXIMUM_INVENTED_NAME_LENGTH,
+    MYTHIC_NAMES,
+  );
+  let mut result: Vec<String> = Vec::with_capacity(count);
+  while result.len() < count {
+    let name = generator.generate(rng);
+    if !result.contains(&name) {
+      result.push(name);
+    }
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_names() {
+    init();
+    trace_enter!();
+    assert_eq!(get_systematic_planet_name("Rigel", 0), "Rigel b");
+    assert_eq!(get_systematic_planet_name("Rigel", 2), "Rigel d");
+    let mut rng = thread_rng();
+    let names = generate_mythic_planet_names(&mut rng, "Rigel", 8);
+    print_var!(names);
+    assert_eq!(names.len(), 8);
+    assert!(names.iter().all(|name| MYTHIC_NAMES.contains(&name.as_str())));
+    let names = generate_mythic_planet_names(&mut rng, "Rigel", MYTHIC_NAMES.len() + 1);
+    assert_eq!(
+      names.last().unwrap(),
+      &get_systematic_planet_name("Rigel", MYTHIC_NAMES.len())
+    );
+    let names = generate_invented_planet_names(&mut rng, 8);
+    print_var!(names);
+    assert_eq!(names.len(), 8);
+    trace_exit!();
+  }
+}
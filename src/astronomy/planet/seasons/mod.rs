@@ -0,0 +1,83 @@
+use serde::Serialize;
+
+use crate::astronomy::orbit::constants::DAYS_PER_YEAR;
+use crate::astronomy::planet::math::insolation::get_daily_insolation;
+
+/// The latitude at which we measure the seasonal swing, in degrees.
+///
+/// Temperate latitudes are where most people would notice it.
+pub const SEASONAL_REFERENCE_LATITUDE: f64 = 45.0;
+
+/// A summary of how much a planet's seasons vary.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Seasons {
+  /// The length of each season, in Dearth.
+  pub season_length: f64,
+  /// How much insolation swings between summer and winter at temperate
+  /// latitudes, from 0 (not at all) to 1 (winter is a polar night).
+  ///
+  /// This is driven by the axial tilt; Earth's is about 0.6.
+  pub insolation_amplitude: f64,
+  /// The ratio of the flux at periapsis to the flux at apoapsis.
+  ///
+  /// This is driven by the orbital eccentricity, and hits both hemispheres
+  /// at once; Earth's is about 1.07.
+  pub orbital_flux_ratio: f64,
+}
+
+impl Seasons {
+  /// Summarize the seasons from the axial tilt, in degrees, the orbital
+  /// eccentricity, and the orbital period, in Earth years.
+  #[named]
+  pub fn new(axial_tilt: f64, orbital_eccentricity: f64, orbital_period: f64) -> Self {
+    trace_enter!();
+    trace_var!(axial_tilt);
+    trace_var!(orbital_eccentricity);
+    trace_var!(orbital_period);
+    let season_length = orbital_period * DAYS_PER_YEAR / 4.0;
+    trace_var!(season_length);
+    // A retrograde spin has the same seasons as its mirror image.
+    let obliquity = axial_tilt.min(180.0 - axial_tilt);
+    trace_var!(obliquity);
+    let summer = get_daily_insolation(SEASONAL_REFERENCE_LATITUDE, obliquity);
+    trace_var!(summer);
+    let winter = get_daily_insolation(SEASONAL_REFERENCE_LATITUDE, -obliquity);
+    trace_var!(winter);
+    let insolation_amplitude = (summer - winter) / (summer + winter);
+    trace_var!(insolation_amplitude);
+    let orbital_flux_ratio = ((1.0 + orbital_eccentricity) / (1.0 - orbital_eccentricity)).powf(2.0);
+    trace_var!(orbital_flux_ratio);
+    let result = Self {
+      season_length,
+      insolation_amplitude,
+      orbital_flux_ratio,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_new() {
+    init();
+    trace_enter!();
+    let earth = Seasons::new(23.44, 0.0167, 1.0);
+    print_var!(earth);
+    assert_approx_eq!(earth.season_length, 91.3, 0.1);
+    assert_approx_eq!(earth.insolation_amplitude, 0.62, 0.01);
+    assert_approx_eq!(earth.orbital_flux_ratio, 1.07, 0.01);
+    let upright = Seasons::new(0.0, 0.0, 1.0);
+    assert_approx_eq!(upright.insolation_amplitude, 0.0);
+    let uranus = Seasons::new(97.8, 0.046, 84.0);
+    assert_approx_eq!(uranus.insolation_amplitude, 1.0);
+    trace_exit!();
+  }
+}
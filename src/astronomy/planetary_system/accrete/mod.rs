@@ -0,0 +1,484 @@
+use rand::prelude::*;
+use serde::Serialize;
+use std::f64::consts::PI;
+
+use crate::astronomy::gas_giant_planet::constants::MINIMUM_MASS as MINIMUM_GAS_GIANT_MASS;
+use crate::astronomy::gas_giant_planet::constraints::Constraints as GasGiantPlanetConstraints;
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::ice_giant_planet::constants::{
+  MAXIMUM_MASS as MAXIMUM_ICE_GIANT_MASS, MINIMUM_MASS as MINIMUM_ICE_GIANT_MASS,
+};
+use crate::astronomy::ice_giant_planet::constraints::Constraints as IceGiantPlanetConstraints;
+use crate::astronomy::planet::constraints::Constraints as PlanetConstraints;
+use crate::astronomy::planet::error::Error as PlanetError;
+use crate::astronomy::planet::math::mass::{KG_PER_EARTH_MASS, KG_PER_JUPITER_MASS};
+use crate::astronomy::planet::Planet;
+use crate::astronomy::star::math::mass::msol_to_kg;
+use crate::astronomy::terrestrial_planet::constants::{
+  MAXIMUM_MASS as MAXIMUM_TERRESTRIAL_MASS, MINIMUM_MASS as MINIMUM_TERRESTRIAL_MASS,
+};
+use crate::astronomy::terrestrial_planet::constraints::Constraints as TerrestrialPlanetConstraints;
+use crate::merge::Merge;
+
+/// The mass of a freshly injected nucleus, in Msol.
+pub const PROTOPLANET_MASS: f64 = 1.0E-15;
+
+/// The dust density at the center of the disk, in Msol/AU³, for a star of
+/// one solar mass.
+pub const DUST_DENSITY_COEFFICIENT: f64 = 2.0E-3;
+
+/// How quickly the dust density falls off with distance.
+pub const DUST_DENSITY_ALPHA: f64 = 5.0;
+
+/// The root of the distance that the dust density falls off with.
+pub const DUST_DENSITY_N: f64 = 3.0;
+
+/// The ratio of gas to dust in the disk.
+pub const GAS_DUST_RATIO: f64 = 50.0;
+
+/// The eccentricity of the particles in the dust cloud.
+pub const CLOUD_ECCENTRICITY: f64 = 0.2;
+
+/// Shapes the distribution of nuclei eccentricities; smaller is rounder.
+pub const ECCENTRICITY_COEFFICIENT: f64 = 0.077;
+
+/// The critical mass, in Msol, above which a nucleus at 1 AU from a Sun-like
+/// star starts to pull in gas as well as dust.
+pub const CRITICAL_MASS_COEFFICIENT: f64 = 1.2E-5;
+
+/// The most nuclei to inject before giving up on sweeping the disk clean.
+///
+/// A disk almost always runs out of dust after a few hundred.
+pub const MAXIMUM_NUCLEI: usize = 10_000;
+
+/// A body grown out of the disk.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Protoplanet {
+  /// Semi-major axis, in AU.
+  pub semi_major_axis: f64,
+  /// Orbital eccentricity.
+  pub eccentricity: f64,
+  /// Mass, in Msol.
+  pub mass: f64,
+  /// Whether it grew massive enough to pull in gas.
+  pub is_gas_giant: bool,
+}
+
+/// A ring of the disk, and what's left in it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct DustBand {
+  inner_edge: f64,
+  outer_edge: f64,
+  has_dust: bool,
+  has_gas: bool,
+}
+
+/// A Dole/Accrete-style simulation of a protoplanetary disk.
+///
+/// Nuclei are dropped into a disk of dust and gas one at a time.  Each sweeps
+/// up the dust within reach of its eccentric orbit, and past a critical mass
+/// the gas as well; nuclei whose orbits overlap merge.  This carries on until
+/// no dust is left where nuclei can form.
+///
+/// See Dole (1970), "Computer Simulation of the Formation of Planetary
+/// Systems", and Fogg (1985), "Extra-Solar Planetary Systems".
+#[derive(Clone, Debug, PartialEq)]
+pub struct Accretion {
+  /// The mass of the star, in Msol.
+  pub stellar_mass: f64,
+  /// The luminosity of the star, in Lsol.
+  pub luminosity: f64,
+  /// The innermost orbit on which to inject nuclei, in AU.
+  pub inner_bound: f64,
+  /// The outermost orbit on which to inject nuclei, in AU.
+  pub outer_bound: f64,
+  dust_bands: Vec<DustBand>,
+  protoplanets: Vec<Protoplanet>,
+}
+
+impl Accretion {
+  /// Lay out a fresh disk around a star.
+  ///
+  /// Nuclei form between 0.3 and 50 AU around a Sun-like star, scaling with
+  /// the cube root of the mass, and further limited to the given bounds.
+  #[named]
+  pub fn new(stellar_mass: f64, luminosity: f64, inner_limit: f64, outer_limit: f64) -> Self {
+    trace_enter!();
+    trace_var!(stellar_mass);
+    trace_var!(luminosity);
+    let mass_factor = stellar_mass.cbrt();
+    trace_var!(mass_factor);
+    let inner_bound = (0.3 * mass_factor).max(inner_limit);
+    trace_var!(inner_bound);
+    let outer_bound = (50.0 * mass_factor).min(outer_limit);
+    trace_var!(outer_bound);
+    let dust_bands = vec![DustBand {
+      inner_edge: 0.0,
+      outer_edge: 200.0 * mass_factor,
+      has_dust: true,
+      has_gas: true,
+    }];
+    let protoplanets = Vec::new();
+    let result = Self {
+      stellar_mass,
+      luminosity,
+      inner_bound,
+      outer_bound,
+      dust_bands,
+      protoplanets,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Run the simulation to completion, returning the bodies in order of
+  /// distance from the star.
+  #[named]
+  pub fn run<R: Rng + ?Sized>(mut self, rng: &mut R) -> Vec<Protoplanet> {
+    trace_enter!();
+    let mut nuclei = 0;
+    while self.inner_bound < self.outer_bound && self.has_dust_left() && nuclei < MAXIMUM_NUCLEI {
+      nuclei += 1;
+      let semi_major_axis = rng.gen_range(self.inner_bound..self.outer_bound);
+      let eccentricity = 1.0 - (1.0 - rng.gen::<f64>()).powf(ECCENTRICITY_COEFFICIENT);
+      self.inject(semi_major_axis, eccentricity);
+    }
+    trace_var!(nuclei);
+    let mut result = self.protoplanets;
+    result.sort_by(|a, b| a.semi_major_axis.partial_cmp(&b.semi_major_axis).unwrap());
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Drop a nucleus into the disk and let it grow.
+  #[named]
+  fn inject(&mut self, semi_major_axis: f64, eccentricity: f64) {
+    trace_enter!();
+    trace_var!(semi_major_axis);
+    trace_var!(eccentricity);
+    let inner_reach = get_inner_reach(semi_major_axis, eccentricity, PROTOPLANET_MASS);
+    let outer_reach = get_outer_reach(semi_major_axis, eccentricity, PROTOPLANET_MASS);
+    if self.has_dust_between(inner_reach, outer_reach) {
+      let critical_mass = self.get_critical_mass(semi_major_axis, eccentricity);
+      trace_var!(critical_mass);
+      let mass = self.accrete(PROTOPLANET_MASS, semi_major_axis, eccentricity, critical_mass);
+      trace_var!(mass);
+      if mass > PROTOPLANET_MASS {
+        self.coalesce(Protoplanet {
+          semi_major_axis,
+          eccentricity,
+          mass,
+          is_gas_giant: mass >= critical_mass,
+        });
+      }
+    }
+    trace_exit!();
+  }
+
+  /// Merge a new body with the first existing one whose orbit it crosses,
+  /// or else add it to the system.
+  #[named]
+  fn coalesce(&mut self, body: Protoplanet) {
+    trace_enter!();
+    trace_var!(body);
+    let crossed = self.protoplanets.iter().position(|other| do_orbits_cross(&body, other));
+    trace_var!(crossed);
+    match crossed {
+      Some(index) => {
+        let other = self.protoplanets.remove(index);
+        let mass = body.mass + other.mass;
+        let semi_major_axis = mass / (body.mass / body.semi_major_axis + other.mass / other.semi_major_axis);
+        // Angular momentum is conserved, which fixes the new eccentricity.
+        let angular_momentum = body.mass * (body.semi_major_axis * (1.0 - body.eccentricity.powf(2.0))).sqrt()
+          + other.mass * (other.semi_major_axis * (1.0 - other.eccentricity.powf(2.0))).sqrt();
+        let circularity = angular_momentum / (mass * semi_major_axis.sqrt());
+        let eccentricity = (1.0 - circularity.powf(2.0)).clamp(0.0, 0.81).sqrt();
+        let critical_mass = self.get_critical_mass(semi_major_axis, eccentricity);
+        let mass = self.accrete(mass, semi_major_axis, eccentricity, critical_mass);
+        self.coalesce(Protoplanet {
+          semi_major_axis,
+          eccentricity,
+          mass,
+          is_gas_giant: body.is_gas_giant || other.is_gas_giant || mass >= critical_mass,
+        });
+      },
+      None => self.protoplanets.push(body),
+    }
+    trace_exit!();
+  }
+
+  /// Grow a body until it stops finding material, clear its sweep of the
+  /// disk, and return its new mass.
+  #[named]
+  fn accrete(&mut self, mass: f64, semi_major_axis: f64, eccentricity: f64, critical_mass: f64) -> f64 {
+    trace_enter!();
+    let dust_density = DUST_DENSITY_COEFFICIENT
+      * self.stellar_mass.sqrt()
+      * (-DUST_DENSITY_ALPHA * semi_major_axis.powf(1.0 / DUST_DENSITY_N)).exp();
+    trace_var!(dust_density);
+    let mut swept = mass;
+    loop {
+      let last_swept = swept;
+      swept = self.collect(last_swept, semi_major_axis, eccentricity, dust_density, critical_mass);
+      if swept - last_swept < 1.0E-4 * last_swept {
+        break;
+      }
+    }
+    let result = mass + swept;
+    trace_var!(result);
+    let inner_reach = get_inner_reach(semi_major_axis, eccentricity, result);
+    let outer_reach = get_outer_reach(semi_major_axis, eccentricity, result);
+    self.clear(inner_reach, outer_reach, result >= critical_mass);
+    trace_exit!();
+    result
+  }
+
+  /// Work out how much material a body of the given mass could sweep up.
+  #[named]
+  fn collect(&self, mass: f64, semi_major_axis: f64, eccentricity: f64, dust_density: f64, critical_mass: f64) -> f64 {
+    trace_enter!();
+    let reduced_mass = get_reduced_mass(mass);
+    let inner_reach = get_inner_reach(semi_major_axis, eccentricity, mass).max(0.0);
+    let outer_reach = get_outer_reach(semi_major_axis, eccentricity, mass);
+    let reach = outer_reach - inner_reach;
+    let result = self
+      .dust_bands
+      .iter()
+      .filter(|band| band.outer_edge > inner_reach && band.inner_edge < outer_reach)
+      .map(|band| {
+        let band_dust_density = if band.has_dust { dust_density } else { 0.0 };
+        let density = match mass >= critical_mass && band.has_gas {
+          true => GAS_DUST_RATIO * band_dust_density / (1.0 + (critical_mass / mass).sqrt() * (GAS_DUST_RATIO - 1.0)),
+          false => band_dust_density,
+        };
+        let outside = (outer_reach - band.outer_edge).max(0.0);
+        let inside = (band.inner_edge - inner_reach).max(0.0);
+        let width = reach - outside - inside;
+        let area =
+          4.0 * PI * semi_major_axis.powf(2.0) * reduced_mass * (1.0 - eccentricity * (outside - inside) / reach);
+        area * width * density
+      })
+      .sum();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Sweep the dust, and if it's a giant the gas, out of part of the disk.
+  #[named]
+  fn clear(&mut self, inner_edge: f64, outer_edge: f64, is_gas_giant: bool) {
+    trace_enter!();
+    trace_var!(inner_edge);
+    trace_var!(outer_edge);
+    let mut dust_bands: Vec<DustBand> = Vec::new();
+    for band in self.dust_bands.iter() {
+      let cuts = [band.inner_edge, inner_edge, outer_edge, band.outer_edge];
+      for pair in cuts.windows(2) {
+        let inner = pair[0].max(band.inner_edge);
+        let outer = pair[1].min(band.outer_edge);
+        if inner >= outer {
+          continue;
+        }
+        let is_cleared = inner >= inner_edge && outer <= outer_edge;
+        let piece = DustBand {
+          inner_edge: inner,
+          outer_edge: outer,
+          has_dust: band.has_dust && !is_cleared,
+          has_gas: band.has_gas && !(is_cleared && is_gas_giant),
+        };
+        match dust_bands.last_mut() {
+          Some(last) if last.has_dust == piece.has_dust && last.has_gas == piece.has_gas => {
+            last.outer_edge = piece.outer_edge;
+          },
+          _ => dust_bands.push(piece),
+        }
+      }
+    }
+    self.dust_bands = dust_bands;
+    trace_exit!();
+  }
+
+  /// Whether any dust is left between the given distances.
+  fn has_dust_between(&self, inner_edge: f64, outer_edge: f64) -> bool {
+    self
+      .dust_bands
+      .iter()
+      .any(|band| band.has_dust && band.outer_edge > inner_edge && band.inner_edge < outer_edge)
+  }
+
+  /// Whether any dust is left where nuclei can form.
+  fn has_dust_left(&self) -> bool {
+    self.has_dust_between(self.inner_bound, self.outer_bound)
+  }
+
+  /// The mass, in Msol, at which a body starts to pull in gas.
+  ///
+  /// This is lower further from the star, where the gas is colder.
+  fn get_critical_mass(&self, semi_major_axis: f64, eccentricity: f64) -> f64 {
+    let periapsis = semi_major_axis * (1.0 - eccentricity);
+    CRITICAL_MASS_COEFFICIENT * (periapsis * self.luminosity.sqrt()).powf(-0.75)
+  }
+}
+
+impl Protoplanet {
+  /// Get the mass, in Mearth.
+  pub fn get_mass_in_earth_masses(&self) -> f64 {
+    msol_to_kg(self.mass) / KG_PER_EARTH_MASS
+  }
+
+  /// Generate a full planet from this body.
+  ///
+  /// Bodies too heavy to count as terrestrial but not gas giants become ice
+  /// giants; the rest are terrestrial, however much gas they caught, and
+  /// bodies too small to count as planets at all give `None`.  Any other
+  /// planet constraints still apply.
+  #[named]
+  pub fn generate_planet<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: &HostStar,
+    constraints: &PlanetConstraints,
+  ) -> Result<Option<Planet>, PlanetError> {
+    trace_enter!();
+    trace_var!(self);
+    let earth_masses = self.get_mass_in_earth_masses();
+    trace_var!(earth_masses);
+    let jupiter_masses = msol_to_kg(self.mass) / KG_PER_JUPITER_MASS;
+    trace_var!(jupiter_masses);
+    let distance = self.semi_major_axis;
+    let mut result = if self.is_gas_giant && jupiter_masses >= MINIMUM_GAS_GIANT_MASS {
+      let constraints = constraints
+        .gas_giant_planet_constraints
+        .unwrap_or_default()
+        .merged_with(&GasGiantPlanetConstraints {
+          minimum_mass: Some(jupiter_masses),
+          maximum_mass: Some(jupiter_masses),
+          ..GasGiantPlanetConstraints::default()
+        });
+      Some(Planet::GasGiantPlanet(constraints.generate(rng, host_star, distance)?))
+    } else if earth_masses > MAXIMUM_TERRESTRIAL_MASS {
+      let mass = earth_masses.clamp(MINIMUM_ICE_GIANT_MASS, MAXIMUM_ICE_GIANT_MASS);
+      let constraints = constraints
+        .ice_giant_planet_constraints
+        .unwrap_or_default()
+        .merged_with(&IceGiantPlanetConstraints {
+          minimum_mass: Some(mass),
+          maximum_mass: Some(mass),
+          ..IceGiantPlanetConstraints::default()
+        });
+      Some(Planet::IceGiantPlanet(constraints.generate(rng, host_star, distance)?))
+    } else if earth_masses >= MINIMUM_TERRESTRIAL_MASS {
+      let constraints = constraints
+        .terrestrial_planet_constraints
+        .unwrap_or_default()
+        .merged_with(&TerrestrialPlanetConstraints {
+          minimum_mass: Some(earth_masses),
+          maximum_mass: Some(earth_masses),
+          ..TerrestrialPlanetConstraints::default()
+        });
+      Some(Planet::TerrestrialPlanet(
+        constraints.generate(rng, host_star, distance)?,
+      ))
+    } else {
+      None
+    };
+    if let Some(planet) = &mut result {
+      use Planet::*;
+      match planet {
+        TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.orbit.eccentricity = self.eccentricity,
+        GasGiantPlanet(gas_giant_planet) => gas_giant_planet.orbit.eccentricity = self.eccentricity,
+        IceGiantPlanet(ice_giant_planet) => ice_giant_planet.orbit.eccentricity = self.eccentricity,
+      }
+    }
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+}
+
+/// The reduced mass used to find how far a body's gravity reaches.
+fn get_reduced_mass(mass: f64) -> f64 {
+  (mass / (1.0 + mass)).powf(0.25)
+}
+
+/// The innermost distance, in AU, from which a body draws material.
+fn get_inner_reach(semi_major_axis: f64, eccentricity: f64, mass: f64) -> f64 {
+  semi_major_axis * (1.0 - eccentricity) * (1.0 - get_reduced_mass(mass)) / (1.0 + CLOUD_ECCENTRICITY)
+}
+
+/// The outermost distance, in AU, from which a body draws material.
+fn get_outer_reach(semi_major_axis: f64, eccentricity: f64, mass: f64) -> f64 {
+  semi_major_axis * (1.0 + eccentricity) * (1.0 + get_reduced_mass(mass)) / (1.0 - CLOUD_ECCENTRICITY)
+}
+
+/// Whether two bodies come close enough at periapsis or apoapsis to merge.
+fn do_orbits_cross(body: &Protoplanet, other: &Protoplanet) -> bool {
+  let difference = other.semi_major_axis - body.semi_major_axis;
+  let body_reduced_mass = get_reduced_mass(body.mass);
+  let other_reduced_mass = get_reduced_mass(other.mass);
+  let (body_reach, other_reach) = match difference > 0.0 {
+    true => (
+      body.semi_major_axis * (1.0 + body.eccentricity) * (1.0 + body_reduced_mass) - body.semi_major_axis,
+      other.semi_major_axis - other.semi_major_axis * (1.0 - other.eccentricity) * (1.0 - other_reduced_mass),
+    ),
+    false => (
+      body.semi_major_axis - body.semi_major_axis * (1.0 - body.eccentricity) * (1.0 - body_reduced_mass),
+      other.semi_major_axis * (1.0 + other.eccentricity) * (1.0 + other_reduced_mass) - other.semi_major_axis,
+    ),
+  };
+  difference.abs() <= body_reach || difference.abs() <= other_reach
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::generator::seeded_rng;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_sun() {
+    init();
+    trace_enter!();
+    let mut gas_giant_count = 0;
+    for seed in 0..10 {
+      let protoplanets = Accretion::new(1.0, 1.0, 0.0, f64::INFINITY).run(&mut seeded_rng(seed));
+      print_var!(protoplanets);
+      assert!(!protoplanets.is_empty());
+      assert!(protoplanets
+        .windows(2)
+        .all(|pair| pair[0].semi_major_axis < pair[1].semi_major_axis));
+      assert!(protoplanets
+        .iter()
+        .all(|protoplanet| protoplanet.mass > PROTOPLANET_MASS && protoplanet.eccentricity < 1.0));
+      assert!(protoplanets
+        .iter()
+        .all(|protoplanet| protoplanet.semi_major_axis > 0.1 && protoplanet.semi_major_axis < 100.0));
+      gas_giant_count += protoplanets
+        .iter()
+        .filter(|protoplanet| protoplanet.is_gas_giant)
+        .count();
+    }
+    print_var!(gas_giant_count);
+    assert!(gas_giant_count > 0);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_bounds() {
+    init();
+    trace_enter!();
+    let accretion = Accretion::new(1.0, 1.0, 2.0, 10.0);
+    assert_eq!((accretion.inner_bound, accretion.outer_bound), (2.0, 10.0));
+    let protoplanets = accretion.run(&mut seeded_rng(1));
+    print_var!(protoplanets);
+    assert!(!protoplanets.is_empty());
+    let empty = Accretion::new(1.0, 1.0, 10.0, 2.0).run(&mut seeded_rng(1));
+    assert!(empty.is_empty());
+    trace_exit!();
+  }
+}
@@ -1,10 +1,21 @@
 use rand::prelude::*;
 use std::default::Default;
 
+use crate::astronomy::asteroid_belt::constraints::Constraints as AsteroidBeltConstraints;
 use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+use crate::astronomy::host_star::error::Error as HostStarError;
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::kuiper_belt::constraints::Constraints as KuiperBeltConstraints;
+use crate::astronomy::oort_cloud::constraints::Constraints as OortCloudConstraints;
+use crate::astronomy::planet::name::PlanetNaming;
 use crate::astronomy::planetary_system::error::Error;
 use crate::astronomy::planetary_system::PlanetarySystem;
 use crate::astronomy::satellite_systems::constraints::Constraints as SatelliteSystemsConstraints;
+use crate::astronomy::satellite_systems::math::spacing::SpacingLaw;
+use crate::astronomy::star::Star;
+use crate::astronomy::zodiacal_dust::ZodiacalDust;
+use crate::generator::{GenerationStrategy, Generator};
+use crate::merge::Merge;
 
 /// Constraints for creating a main-sequence star subsystem.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -13,6 +24,21 @@ pub struct Constraints {
   pub host_star_constraints: Option<HostStarConstraints>,
   /// Satellite Systems constraints.
   pub satellite_systems_constraints: Option<SatelliteSystemsConstraints>,
+  /// Asteroid Belt constraints.
+  pub asteroid_belt_constraints: Option<AsteroidBeltConstraints>,
+  /// Kuiper Belt constraints.
+  pub kuiper_belt_constraints: Option<KuiperBeltConstraints>,
+  /// Oort Cloud constraints.
+  pub oort_cloud_constraints: Option<OortCloudConstraints>,
+  /// How the planets' orbits are spaced out.
+  ///
+  /// This takes precedence over any spacing law in the satellite systems
+  /// constraints.
+  pub spacing_law: Option<SpacingLaw>,
+  /// How the planets are named.
+  ///
+  /// Systematically, unless otherwise specified.
+  pub planet_naming: Option<PlanetNaming>,
 }
 
 impl Constraints {
@@ -32,23 +58,135 @@ impl Constraints {
     result
   }
 
+  /// Generate a star subsystem whose planets form a resonant chain.
+  ///
+  /// Merge this with other constraints to, say, get a habitable chain.
+  #[named]
+  pub fn resonant_chain() -> Self {
+    trace_enter!();
+    let satellite_systems_constraints = Some(SatelliteSystemsConstraints {
+      generate_resonant_chain: Some(true),
+      ..SatelliteSystemsConstraints::default()
+    });
+    let result = Self {
+      satellite_systems_constraints,
+      ..Constraints::default()
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Generate a star subsystem whose planets grow out of a simulated
+  /// protoplanetary disk.
+  #[named]
+  pub fn accretion() -> Self {
+    trace_enter!();
+    let satellite_systems_constraints = Some(SatelliteSystemsConstraints {
+      generate_by_accretion: Some(true),
+      ..SatelliteSystemsConstraints::default()
+    });
+    let result = Self {
+      satellite_systems_constraints,
+      ..Constraints::default()
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Generate.
   #[named]
   pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<PlanetarySystem, Error> {
     trace_enter!();
     let host_star_constraints = self.host_star_constraints.unwrap_or(HostStarConstraints::default());
     trace_var!(host_star_constraints);
+    let host_star = host_star_constraints.generate(rng)?;
+    trace_var!(host_star);
+    let result = self.generate_from_host_star(rng, host_star);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Generate around a single star, using another strategy for the star.
+  ///
+  /// The host star constraints are ignored.
+  #[named]
+  pub fn generate_with_star_strategy<R, S>(&self, rng: &mut R, strategy: &S) -> Result<PlanetarySystem, Error>
+  where
+    R: Rng + ?Sized,
+    S: GenerationStrategy<Star>,
+    HostStarError: From<S::Error>,
+  {
+    trace_enter!();
+    let star = strategy.generate(rng).map_err(HostStarError::from)?;
+    trace_var!(star);
+    let result = self.generate_from_host_star(rng, HostStar::Star(star));
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Generate around a host star that's already been generated.
+  ///
+  /// The host star constraints are ignored.
+  #[named]
+  pub fn generate_from_host_star<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: HostStar,
+  ) -> Result<PlanetarySystem, Error> {
+    trace_enter!();
+    trace_var!(host_star);
     let satellite_systems_constraints = self
       .satellite_systems_constraints
-      .unwrap_or(SatelliteSystemsConstraints::default());
+      .unwrap_or(SatelliteSystemsConstraints::default())
+      .merged_with(&SatelliteSystemsConstraints {
+        spacing_law: self.spacing_law,
+        ..SatelliteSystemsConstraints::default()
+      });
     trace_var!(satellite_systems_constraints);
-    let host_star = host_star_constraints.generate(rng)?;
-    trace_var!(host_star);
-    let satellite_systems = satellite_systems_constraints.generate(rng, &host_star)?;
+    let asteroid_belt_constraints = self.asteroid_belt_constraints.unwrap_or_default();
+    trace_var!(asteroid_belt_constraints);
+    let kuiper_belt_constraints = self.kuiper_belt_constraints.unwrap_or_default();
+    trace_var!(kuiper_belt_constraints);
+    let oort_cloud_constraints = self.oort_cloud_constraints.unwrap_or_default();
+    trace_var!(oort_cloud_constraints);
+    let mut satellite_systems = satellite_systems_constraints.generate(rng, &host_star)?;
+    let planet_naming = self.planet_naming.unwrap_or(PlanetNaming::Systematic);
+    trace_var!(planet_naming);
+    satellite_systems.name_planets(rng, &host_star.get_name(), planet_naming);
     trace_var!(satellite_systems);
+    let asteroid_belt = asteroid_belt_constraints.generate(rng, &host_star, &satellite_systems);
+    trace_var!(asteroid_belt);
+    let zodiacal_dust = asteroid_belt.as_ref().map(|asteroid_belt| {
+      ZodiacalDust::from_belt(
+        asteroid_belt,
+        host_star.get_luminosity(),
+        &satellite_systems.get_semi_major_axes(),
+      )
+    });
+    trace_var!(zodiacal_dust);
+    // Anything beyond the widest orbit we'd place a planet on is no more
+    // stable for a belt of comets.
+    let kuiper_belt = kuiper_belt_constraints
+      .generate(rng, &host_star, &satellite_systems)
+      .filter(|kuiper_belt| {
+        satellite_systems_constraints
+          .maximum_orbit
+          .is_none_or(|maximum_orbit| kuiper_belt.outer_edge <= maximum_orbit)
+      });
+    trace_var!(kuiper_belt);
+    let oort_cloud = oort_cloud_constraints.generate(rng, &host_star);
+    trace_var!(oort_cloud);
     let result = PlanetarySystem {
       host_star,
       satellite_systems,
+      asteroid_belt,
+      zodiacal_dust,
+      kuiper_belt,
+      oort_cloud,
     };
     trace_var!(result);
     trace_exit!();
@@ -56,14 +194,59 @@ impl Constraints {
   }
 }
 
+impl Generator for Constraints {
+  type Output = PlanetarySystem;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<PlanetarySystem, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
 impl Default for Constraints {
   /// No constraints, just let it all hang out.
   fn default() -> Self {
     let host_star_constraints = None;
     let satellite_systems_constraints = None;
+    let asteroid_belt_constraints = None;
+    let kuiper_belt_constraints = None;
+    let oort_cloud_constraints = None;
+    let spacing_law = None;
+    let planet_naming = None;
     Self {
       host_star_constraints,
       satellite_systems_constraints,
+      asteroid_belt_constraints,
+      kuiper_belt_constraints,
+      oort_cloud_constraints,
+      spacing_law,
+      planet_naming,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      host_star_constraints: self.host_star_constraints.merged_with(&overrides.host_star_constraints),
+      satellite_systems_constraints: self
+        .satellite_systems_constraints
+        .merged_with(&overrides.satellite_systems_constraints),
+      asteroid_belt_constraints: self
+        .asteroid_belt_constraints
+        .merged_with(&overrides.asteroid_belt_constraints),
+      kuiper_belt_constraints: self
+        .kuiper_belt_constraints
+        .merged_with(&overrides.kuiper_belt_constraints),
+      oort_cloud_constraints: self
+        .oort_cloud_constraints
+        .merged_with(&overrides.oort_cloud_constraints),
+      spacing_law: overrides.spacing_law.or(self.spacing_law),
+      planet_naming: overrides.planet_naming.or(self.planet_naming),
     }
   }
 }
@@ -74,6 +257,7 @@ pub mod test {
   use rand::prelude::*;
 
   use super::*;
+  use crate::astronomy::planet::name::get_systematic_planet_name;
   use crate::test::*;
 
   #[named]
@@ -109,6 +293,119 @@ pub mod test {
     let planetary_system = Constraints::default().generate(&mut rng)?;
     trace_var!(planetary_system);
     print_var!(planetary_system);
+    let star_name = planetary_system.host_star.get_name();
+    let mut satellite_systems = planetary_system.satellite_systems.satellite_systems.clone();
+    satellite_systems.sort_by(|a, b| {
+      a.planet
+        .get_semi_major_axis()
+        .partial_cmp(&b.planet.get_semi_major_axis())
+        .unwrap()
+    });
+    for (index, satellite_system) in satellite_systems.iter().enumerate() {
+      assert_eq!(
+        satellite_system.planet.get_name(),
+        get_systematic_planet_name(&star_name, index)
+      );
+    }
+    let constraints = Constraints {
+      planet_naming: Some(PlanetNaming::Mythic),
+      ..Constraints::default()
+    };
+    let planetary_system = constraints.generate(&mut rng)?;
+    for satellite_system in planetary_system.satellite_systems.satellite_systems.iter() {
+      assert!(!satellite_system.planet.get_name().is_empty());
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_evolve() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let planetary_system = Constraints::habitable().generate(&mut rng)?;
+    let evolved = planetary_system.evolve(1.0)?;
+    print_var!(evolved);
+    assert!(evolved.get_luminosity() > planetary_system.get_luminosity());
+    assert_eq!(evolved.satellite_systems, planetary_system.satellite_systems);
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_lagrange_points() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let planetary_system = Constraints::habitable().generate(&mut rng)?;
+    let lagrange_points = planetary_system.get_lagrange_points();
+    print_var!(lagrange_points);
+    let semi_major_axes = planetary_system.satellite_systems.get_semi_major_axes();
+    assert_eq!(lagrange_points.len(), semi_major_axes.len());
+    for (points, semi_major_axis) in lagrange_points.iter().zip(semi_major_axes) {
+      assert!(points[0] < semi_major_axis && semi_major_axis < points[1]);
+      assert_eq!(points[3], semi_major_axis);
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_resonant_chain() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints::habitable().merged_with(&Constraints::resonant_chain());
+    let planetary_system = constraints.generate(&mut rng)?;
+    let resonances = planetary_system.get_resonances();
+    print_var!(resonances);
+    assert!(resonances.iter().all(Option::is_some));
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_spacing_law() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    for spacing_law in [SpacingLaw::TitiusBode, SpacingLaw::MutualHill, SpacingLaw::LogUniform] {
+      let constraints = Constraints {
+        spacing_law: Some(spacing_law),
+        ..Constraints::default()
+      };
+      let planetary_system = constraints.generate(&mut rng)?;
+      let semi_major_axes = planetary_system.satellite_systems.get_semi_major_axes();
+      print_var!(semi_major_axes);
+      assert!(semi_major_axes.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_accretion() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints {
+      host_star_constraints: Some(HostStarConstraints::habitable()),
+      ..Constraints::accretion()
+    };
+    let planetary_system = constraints.generate(&mut rng)?;
+    print_var!(planetary_system);
+    assert!(!planetary_system.satellite_systems.satellite_systems.is_empty());
     trace_exit!();
     Ok(())
   }
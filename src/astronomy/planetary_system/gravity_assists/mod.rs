@@ -0,0 +1,232 @@
+use serde::Serialize;
+
+use crate::astronomy::planet::Planet;
+use crate::astronomy::planetary_system::math::gravity_assist::*;
+
+/// The closest a flyby can skim a planet, as a multiple of its radius.
+///
+/// Any closer and the atmosphere, or the rings, get a vote.
+pub const MINIMUM_PERIAPSIS_RATIO: f64 = 1.1;
+
+/// The most flybys we'll chain together on the way to a destination.
+pub const MAXIMUM_FLYBYS: usize = 3;
+
+/// What a giant planet can do for a spacecraft sent to it from the origin.
+///
+/// Everything is approximate: orbits are treated as circular and coplanar,
+/// and the planets as always being in the right place.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GravityAssist {
+  /// The index of the giant.
+  pub planet: usize,
+  /// The speed relative to the giant on arrival by a Hohmann transfer from
+  /// the origin, in KM/s.
+  pub excess_speed: f64,
+  /// The most the giant can turn the spacecraft's course, in degrees.
+  pub maximum_turn_angle: f64,
+  /// The most heliocentric speed the giant can add, in KM/s.
+  pub maximum_speed_gain: f64,
+}
+
+/// A route from the origin to a destination by way of one or more flybys.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GravityAssistChain {
+  /// The index of the planet the spacecraft starts from.
+  pub origin: usize,
+  /// The indices of the giants flown by, in order.
+  pub flybys: Vec<usize>,
+  /// The index of the planet the spacecraft is bound for.
+  pub destination: usize,
+  /// The heliocentric departure burn a direct transfer needs, in KM/s.
+  pub direct_delta_v: f64,
+  /// The heliocentric departure burn this route needs, in KM/s.
+  pub delta_v: f64,
+  /// How much this route saves over a direct transfer, in KM/s.
+  pub delta_v_savings: f64,
+}
+
+/// Get the giants beyond the origin that can offer a gravity assist, most
+/// useful first.
+///
+/// Stellar mass is in Msol; the planets are as listed in the planetary
+/// system.  An origin that isn't one of them gets no assists.
+#[named]
+pub fn get_gravity_assists(stellar_mass: f64, planets: &[&Planet], origin: usize) -> Vec<GravityAssist> {
+  trace_enter!();
+  trace_var!(stellar_mass);
+  trace_var!(origin);
+  if origin >= planets.len() {
+    trace_exit!();
+    return vec![];
+  }
+  let origin_distance = planets[origin].get_semi_major_axis();
+  trace_var!(origin_distance);
+  let mut result: Vec<GravityAssist> = planets
+    .iter()
+    .enumerate()
+    .filter(|(_, planet)| is_giant(planet) && planet.get_semi_major_axis() > origin_distance)
+    .map(|(index, planet)| {
+      let distance = planet.get_semi_major_axis();
+      let (_, arrival_speed) = get_transfer_speeds(stellar_mass, origin_distance, distance);
+      let planet_speed = get_circular_speed(stellar_mass, distance);
+      let excess_speed = planet_speed - arrival_speed;
+      let maximum_turn_angle = get_maximum_turn_angle(
+        excess_speed,
+        planet.get_mass_in_kg(),
+        MINIMUM_PERIAPSIS_RATIO * planet.get_equatorial_radius_in_km(),
+      );
+      let maximum_speed_gain = get_maximum_speed_gain(planet_speed, excess_speed, maximum_turn_angle);
+      GravityAssist {
+        planet: index,
+        excess_speed,
+        maximum_turn_angle,
+        maximum_speed_gain,
+      }
+    })
+    .collect();
+  result.sort_by(|a, b| b.maximum_speed_gain.partial_cmp(&a.maximum_speed_gain).unwrap());
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the feasible routes from the origin to each planet further out that
+/// save fuel by way of giant flybys, best savings first.
+///
+/// Each leg is treated as a Hohmann transfer, with each flyby supplying the
+/// difference between arriving on one and departing on the next.  Only the
+/// departure burn is counted; arrivals after a flyby are usually faster, so
+/// a lander will pay some of the savings back.  An origin that isn't one of
+/// the planets gets no routes.
+#[named]
+pub fn get_gravity_assist_chains(stellar_mass: f64, planets: &[&Planet], origin: usize) -> Vec<GravityAssistChain> {
+  trace_enter!();
+  trace_var!(stellar_mass);
+  trace_var!(origin);
+  if origin >= planets.len() {
+    trace_exit!();
+    return vec![];
+  }
+  let origin_distance = planets[origin].get_semi_major_axis();
+  trace_var!(origin_distance);
+  let origin_speed = get_circular_speed(stellar_mass, origin_distance);
+  trace_var!(origin_speed);
+  let mut giants: Vec<usize> = (0..planets.len())
+    .filter(|&index| is_giant(planets[index]) && planets[index].get_semi_major_axis() > origin_distance)
+    .collect();
+  giants.sort_by(|&a, &b| {
+    planets[a]
+      .get_semi_major_axis()
+      .partial_cmp(&planets[b].get_semi_major_axis())
+      .unwrap()
+  });
+  trace_var!(giants);
+  // Every outward-ordered selection of giants, up to the limit.
+  let mut sequences: Vec<Vec<usize>> = vec![vec![]];
+  for &giant in &giants {
+    let extended: Vec<Vec<usize>> = sequences
+      .iter()
+      .filter(|sequence| sequence.len() < MAXIMUM_FLYBYS)
+      .map(|sequence| [sequence.as_slice(), &[giant]].concat())
+      .collect();
+    sequences.extend(extended);
+  }
+  trace_var!(sequences);
+  let mut result = vec![];
+  for flybys in sequences.into_iter().filter(|sequence| !sequence.is_empty()) {
+    let last_distance = planets[*flybys.last().unwrap()].get_semi_major_axis();
+    for (destination, planet) in planets.iter().enumerate() {
+      let destination_distance = planet.get_semi_major_axis();
+      if destination_distance <= last_distance {
+        continue;
+      }
+      let mut distances = vec![origin_distance];
+      distances.extend(flybys.iter().map(|&index| planets[index].get_semi_major_axis()));
+      distances.push(destination_distance);
+      let feasible = flybys.iter().enumerate().all(|(step, &index)| {
+        let (_, arrival_speed) = get_transfer_speeds(stellar_mass, distances[step], distances[step + 1]);
+        let (departure_speed, _) = get_transfer_speeds(stellar_mass, distances[step + 1], distances[step + 2]);
+        let planet_speed = get_circular_speed(stellar_mass, distances[step + 1]);
+        let excess_speed = planet_speed - arrival_speed;
+        let maximum_turn_angle = get_maximum_turn_angle(
+          excess_speed,
+          planets[index].get_mass_in_kg(),
+          MINIMUM_PERIAPSIS_RATIO * planets[index].get_equatorial_radius_in_km(),
+        );
+        get_maximum_speed_gain(planet_speed, excess_speed, maximum_turn_angle) >= departure_speed - arrival_speed
+      });
+      if !feasible {
+        continue;
+      }
+      let direct_delta_v = get_transfer_speeds(stellar_mass, origin_distance, destination_distance).0 - origin_speed;
+      let delta_v = get_transfer_speeds(stellar_mass, origin_distance, distances[1]).0 - origin_speed;
+      result.push(GravityAssistChain {
+        origin,
+        flybys: flybys.clone(),
+        destination,
+        direct_delta_v,
+        delta_v,
+        delta_v_savings: direct_delta_v - delta_v,
+      });
+    }
+  }
+  result.sort_by(|a, b| b.delta_v_savings.partial_cmp(&a.delta_v_savings).unwrap());
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Whether a planet is massive enough to be worth flying by.
+fn is_giant(planet: &Planet) -> bool {
+  matches!(planet, Planet::GasGiantPlanet(_) | Planet::IceGiantPlanet(_))
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::planetary_system::constraints::Constraints;
+  use crate::astronomy::planetary_system::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_gravity_assists() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let planetary_system = Constraints::habitable().generate(&mut rng)?;
+    let stellar_mass = planetary_system.get_stellar_mass();
+    let planets: Vec<&Planet> = planetary_system
+      .satellite_systems
+      .satellite_systems
+      .iter()
+      .map(|satellite_system| &satellite_system.planet)
+      .collect();
+    let assists = get_gravity_assists(stellar_mass, &planets, 0);
+    print_var!(assists);
+    for pair in assists.windows(2) {
+      assert!(pair[0].maximum_speed_gain >= pair[1].maximum_speed_gain);
+    }
+    for assist in &assists {
+      assert!(is_giant(planets[assist.planet]));
+      assert!(assist.maximum_turn_angle > 0.0 && assist.maximum_turn_angle < 180.0);
+    }
+    let chains = get_gravity_assist_chains(stellar_mass, &planets, 0);
+    print_var!(chains);
+    for chain in &chains {
+      assert!(!chain.flybys.is_empty() && chain.flybys.len() <= MAXIMUM_FLYBYS);
+      assert!(chain.delta_v_savings > 0.0);
+      assert_approx_eq!(chain.direct_delta_v - chain.delta_v, chain.delta_v_savings);
+    }
+    assert!(get_gravity_assists(stellar_mass, &planets, planets.len()).is_empty());
+    assert!(get_gravity_assist_chains(stellar_mass, &planets, planets.len()).is_empty());
+    assert!(get_gravity_assists(stellar_mass, &[], 0).is_empty());
+    assert!(get_gravity_assist_chains(stellar_mass, &[], 0).is_empty());
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,114 @@
+use crate::astronomy::planet::math::oblateness::GRAVITATIONAL_CONSTANT;
+use crate::astronomy::star::math::distance::au_to_meters;
+use crate::astronomy::star::math::mass::msol_to_kg;
+
+/// Calculate the speed of a circular orbit, in KM/s.
+///
+/// Stellar mass is in Msol; distance is in AU.
+#[named]
+pub fn get_circular_speed(stellar_mass: f64, distance: f64) -> f64 {
+  trace_enter!();
+  trace_var!(stellar_mass);
+  trace_var!(distance);
+  let result = (GRAVITATIONAL_CONSTANT * msol_to_kg(stellar_mass) / au_to_meters(distance)).sqrt() / 1_000.0;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the speeds at either end of a transfer orbit touching two
+/// circular orbits, in KM/s.
+///
+/// Stellar mass is in Msol; distances are in AU.  This is a Hohmann
+/// transfer; the speeds are at the departure and arrival distances
+/// respectively.
+#[named]
+pub fn get_transfer_speeds(stellar_mass: f64, departure: f64, arrival: f64) -> (f64, f64) {
+  trace_enter!();
+  trace_var!(stellar_mass);
+  trace_var!(departure);
+  trace_var!(arrival);
+  let semi_major_axis = (departure + arrival) / 2.0;
+  trace_var!(semi_major_axis);
+  // Vis-viva, in units of the circular speed at each end.
+  let get_speed =
+    |distance: f64| get_circular_speed(stellar_mass, distance) * (2.0 - distance / semi_major_axis).sqrt();
+  let result = (get_speed(departure), get_speed(arrival));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the most a flyby can turn a spacecraft's course, in degrees.
+///
+/// Speed is the hyperbolic excess speed in KM/s; planet mass is in KG, and
+/// periapsis in KM.
+#[named]
+pub fn get_maximum_turn_angle(excess_speed: f64, planet_mass: f64, periapsis: f64) -> f64 {
+  trace_enter!();
+  trace_var!(excess_speed);
+  trace_var!(planet_mass);
+  trace_var!(periapsis);
+  let gravitational_parameter = GRAVITATIONAL_CONSTANT * planet_mass / 1.0E9;
+  trace_var!(gravitational_parameter);
+  let eccentricity = 1.0 + periapsis * excess_speed.powf(2.0) / gravitational_parameter;
+  trace_var!(eccentricity);
+  let result = 2.0 * (1.0 / eccentricity).asin().to_degrees();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the most heliocentric speed a flyby can add, in KM/s.
+///
+/// The spacecraft is assumed to be overtaken by the planet, coming in at
+/// the hyperbolic excess speed directly from behind, as it would at the far
+/// end of a transfer from further in.  Speeds are in KM/s; the turn angle
+/// is in degrees.
+#[named]
+pub fn get_maximum_speed_gain(planet_speed: f64, excess_speed: f64, turn_angle: f64) -> f64 {
+  trace_enter!();
+  trace_var!(planet_speed);
+  trace_var!(excess_speed);
+  trace_var!(turn_angle);
+  let final_speed = (planet_speed.powf(2.0) + excess_speed.powf(2.0)
+    - 2.0 * planet_speed * excess_speed * turn_angle.to_radians().cos())
+  .sqrt();
+  trace_var!(final_speed);
+  let result = final_speed - (planet_speed - excess_speed);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_jupiter_flyby() {
+    init();
+    trace_enter!();
+    let earth_speed = get_circular_speed(1.0, 1.0);
+    print_var!(earth_speed);
+    assert_approx_eq!(earth_speed, 29.8, 0.1);
+    let (departure_speed, arrival_speed) = get_transfer_speeds(1.0, 1.0, 5.2);
+    print_var!(departure_speed);
+    // The classic 8.8 KM/s heliocentric burn to Jupiter.
+    assert_approx_eq!(departure_speed - earth_speed, 8.8, 0.1);
+    let jupiter_speed = get_circular_speed(1.0, 5.2);
+    let excess_speed = jupiter_speed - arrival_speed;
+    print_var!(excess_speed);
+    assert_approx_eq!(excess_speed, 5.6, 0.1);
+    let turn_angle = get_maximum_turn_angle(excess_speed, 1.898E27, 1.1 * 71_492.0);
+    print_var!(turn_angle);
+    assert!(turn_angle > 150.0);
+    let speed_gain = get_maximum_speed_gain(jupiter_speed, excess_speed, turn_angle);
+    print_var!(speed_gain);
+    assert!(speed_gain > 10.0 && speed_gain <= 2.0 * excess_speed);
+    trace_exit!();
+  }
+}
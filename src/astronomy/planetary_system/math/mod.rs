@@ -0,0 +1,3 @@
+pub mod gravity_assist;
+pub mod mutual_sky;
+pub mod resonance;
@@ -0,0 +1,185 @@
+use std::f64::consts::PI;
+
+use crate::astronomy::orbit::constants::KM_PER_AU;
+use crate::astronomy::star::math::magnitude::star_luminosity_to_absolute_magnitude;
+
+/// The number of AU in a parsec.
+pub const AU_PER_PARSEC: f64 = 206_264.806;
+
+/// The number of arcseconds in a degree.
+pub const ARCSECONDS_PER_DEGREE: f64 = 3_600.0;
+
+/// Calculate the distance between two planets on circular, coplanar orbits.
+///
+/// Distances are in AU; the angle between them, as seen from the star, is in
+/// degrees.
+#[named]
+pub fn get_distance(observer_distance: f64, target_distance: f64, angle: f64) -> f64 {
+  trace_enter!();
+  trace_var!(observer_distance);
+  trace_var!(target_distance);
+  trace_var!(angle);
+  let result = (observer_distance.powf(2.0) + target_distance.powf(2.0)
+    - 2.0 * observer_distance * target_distance * angle.to_radians().cos())
+  .sqrt();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the phase angle of a planet: the angle between the star and the
+/// observer, as seen from the planet, in degrees.
+///
+/// Distances are in AU; 0° is full, 180° is new.
+#[named]
+pub fn get_phase_angle(observer_distance: f64, target_distance: f64, distance: f64) -> f64 {
+  trace_enter!();
+  trace_var!(observer_distance);
+  trace_var!(target_distance);
+  trace_var!(distance);
+  let cosine =
+    (target_distance.powf(2.0) + distance.powf(2.0) - observer_distance.powf(2.0)) / (2.0 * target_distance * distance);
+  trace_var!(cosine);
+  let result = cosine.clamp(-1.0, 1.0).acos().to_degrees();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the elongation of a planet: the angle between the star and the
+/// planet, as seen from the observer, in degrees.
+#[named]
+pub fn get_elongation(observer_distance: f64, target_distance: f64, distance: f64) -> f64 {
+  trace_enter!();
+  trace_var!(observer_distance);
+  trace_var!(target_distance);
+  trace_var!(distance);
+  let cosine = (observer_distance.powf(2.0) + distance.powf(2.0) - target_distance.powf(2.0))
+    / (2.0 * observer_distance * distance);
+  trace_var!(cosine);
+  let result = cosine.clamp(-1.0, 1.0).acos().to_degrees();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the fraction of the visible disk that's lit, given the phase
+/// angle in degrees.
+#[named]
+pub fn get_illuminated_fraction(phase_angle: f64) -> f64 {
+  trace_enter!();
+  trace_var!(phase_angle);
+  let result = (1.0 + phase_angle.to_radians().cos()) / 2.0;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate how much light a Lambertian sphere reflects toward the
+/// observer, relative to when it's full, given the phase angle in degrees.
+#[named]
+pub fn get_lambert_phase_function(phase_angle: f64) -> f64 {
+  trace_enter!();
+  trace_var!(phase_angle);
+  let alpha = phase_angle.to_radians();
+  trace_var!(alpha);
+  let result = (alpha.sin() + (PI - alpha) * alpha.cos()) / PI;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the angular diameter of a planet, in arcseconds.
+///
+/// Radius is in KM; distance is in AU.
+#[named]
+pub fn get_angular_diameter(radius: f64, distance: f64) -> f64 {
+  trace_enter!();
+  trace_var!(radius);
+  trace_var!(distance);
+  let result = 2.0 * (radius / (distance * KM_PER_AU)).atan().to_degrees() * ARCSECONDS_PER_DEGREE;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the apparent bolometric magnitude of a planet by the light it
+/// reflects.
+///
+/// Luminosity is in Lsol; radius is in KM; the distances from the star and
+/// from the observer are in AU; the phase angle is in degrees.  A new planet
+/// is infinitely faint.
+#[named]
+pub fn get_apparent_magnitude(
+  luminosity: f64,
+  geometric_albedo: f64,
+  radius: f64,
+  star_distance: f64,
+  distance: f64,
+  phase_angle: f64,
+) -> f64 {
+  trace_enter!();
+  trace_var!(luminosity);
+  trace_var!(geometric_albedo);
+  trace_var!(radius);
+  trace_var!(star_distance);
+  trace_var!(distance);
+  trace_var!(phase_angle);
+  // The star as seen from the planet.
+  let star_magnitude =
+    star_luminosity_to_absolute_magnitude(luminosity) + 5.0 * (star_distance / AU_PER_PARSEC).log10() - 5.0;
+  trace_var!(star_magnitude);
+  let reflected =
+    geometric_albedo * get_lambert_phase_function(phase_angle) * (radius / (distance * KM_PER_AU)).powf(2.0);
+  trace_var!(reflected);
+  let result = star_magnitude - 2.5 * reflected.log10();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_jupiter_at_opposition() {
+    init();
+    trace_enter!();
+    let distance = get_distance(1.0, 5.2, 0.0);
+    print_var!(distance);
+    assert_approx_eq!(distance, 4.2);
+    let phase_angle = get_phase_angle(1.0, 5.2, distance);
+    print_var!(phase_angle);
+    assert_approx_eq!(phase_angle, 0.0);
+    assert_approx_eq!(get_illuminated_fraction(phase_angle), 1.0);
+    let angular_diameter = get_angular_diameter(69_911.0, distance);
+    print_var!(angular_diameter);
+    assert_approx_eq!(angular_diameter, 46.0, 1.0);
+    let magnitude = get_apparent_magnitude(1.0, 0.538, 69_911.0, 5.2, distance, phase_angle);
+    print_var!(magnitude);
+    assert_approx_eq!(magnitude, -2.8, 0.2);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_venus_at_quadrature() {
+    init();
+    trace_enter!();
+    // Greatest elongation puts the Sun and Earth at right angles from Venus.
+    let distance = (1.0f64 - 0.723f64.powf(2.0)).sqrt();
+    let phase_angle = get_phase_angle(1.0, 0.723, distance);
+    print_var!(phase_angle);
+    assert_approx_eq!(phase_angle, 90.0, 0.1);
+    assert_approx_eq!(get_illuminated_fraction(phase_angle), 0.5, 0.01);
+    assert_approx_eq!(get_lambert_phase_function(phase_angle), 1.0 / PI, 0.01);
+    let elongation = get_elongation(1.0, 0.723, distance);
+    print_var!(elongation);
+    assert_approx_eq!(elongation, 46.3, 0.1);
+    trace_exit!();
+  }
+}
@@ -0,0 +1,158 @@
+use rand::prelude::*;
+
+/// Mean-motion resonances worth noticing, as (outer, inner) orbit counts.
+///
+/// These are the first- and second-order resonances with small integers,
+/// which are the ones commonly seen in real resonant chains.
+pub const RESONANCES: [(u8, u8); 6] = [(2, 1), (3, 2), (4, 3), (5, 4), (3, 1), (5, 3)];
+
+/// How far a period ratio can stray from an exact resonance and still count.
+///
+/// Real resonant chains are rarely exact; TRAPPIST-1's sit within a percent
+/// or two of their nominal ratios.
+pub const RESONANCE_TOLERANCE: f64 = 0.02;
+
+/// Get the ratio of orbital periods of two bodies orbiting the same primary.
+///
+/// By Kepler's third law this depends only on the semi-major axes, so the
+/// mass of the primary cancels out.
+#[named]
+pub fn get_period_ratio(inner_semi_major_axis: f64, outer_semi_major_axis: f64) -> f64 {
+  trace_enter!();
+  trace_var!(inner_semi_major_axis);
+  trace_var!(outer_semi_major_axis);
+  let result = (outer_semi_major_axis / inner_semi_major_axis).powf(1.5);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Find the mean-motion resonance, if any, between two orbits.
+#[named]
+pub fn get_resonance(inner_semi_major_axis: f64, outer_semi_major_axis: f64) -> Option<(u8, u8)> {
+  trace_enter!();
+  let period_ratio = get_period_ratio(inner_semi_major_axis, outer_semi_major_axis);
+  trace_var!(period_ratio);
+  let result = RESONANCES.iter().copied().find(|(outer, inner)| {
+    let nominal = *outer as f64 / *inner as f64;
+    (period_ratio / nominal - 1.0).abs() <= RESONANCE_TOLERANCE
+  });
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Find the resonances between each adjacent pair of orbits.
+///
+/// The orbits must be sorted from innermost to outermost.  The result has one
+/// entry for each adjacent pair; a run of `Some` values is a resonant chain.
+#[named]
+pub fn get_resonances(semi_major_axes: &[f64]) -> Vec<Option<(u8, u8)>> {
+  trace_enter!();
+  trace_var!(semi_major_axes);
+  let result = semi_major_axes
+    .windows(2)
+    .map(|pair| get_resonance(pair[0], pair[1]))
+    .collect();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Build a resonant chain of orbits around the given one.
+///
+/// Each neighboring pair is in one of the resonances we know, give or take a
+/// little; the chain grows inward and outward in turn until it runs out of
+/// room between the bounds or reaches the maximum length.  The result is
+/// sorted from innermost to outermost.
+#[named]
+pub fn get_resonant_chain<R: Rng + ?Sized>(
+  rng: &mut R,
+  anchor: f64,
+  minimum: f64,
+  maximum: f64,
+  maximum_length: usize,
+) -> Vec<f64> {
+  trace_enter!();
+  trace_var!(anchor);
+  trace_var!(minimum);
+  trace_var!(maximum);
+  trace_var!(maximum_length);
+  let get_factor = |rng: &mut R| {
+    let (outer, inner) = RESONANCES[rng.gen_range(0..RESONANCES.len())];
+    // Stay well within the tolerance, so the chain is recognized as one.
+    let period_ratio = outer as f64 / inner as f64 * (1.0 + rng.gen_range(-0.5..0.5) * RESONANCE_TOLERANCE);
+    period_ratio.powf(2.0 / 3.0)
+  };
+  let mut result = vec![anchor];
+  let (mut innermost, mut outermost) = (anchor, anchor);
+  let (mut inward, mut outward) = (true, true);
+  while (inward || outward) && result.len() < maximum_length {
+    if outward {
+      let orbit = outermost * get_factor(rng);
+      match orbit <= maximum {
+        true => {
+          result.push(orbit);
+          outermost = orbit;
+        },
+        false => outward = false,
+      }
+    }
+    if inward && result.len() < maximum_length {
+      let orbit = innermost / get_factor(rng);
+      match orbit >= minimum {
+        true => {
+          result.push(orbit);
+          innermost = orbit;
+        },
+        false => inward = false,
+      }
+    }
+  }
+  result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_resonances() {
+    init();
+    trace_enter!();
+    // Io, Europa, Ganymede, Callisto (in thousands of KM).
+    let resonances = get_resonances(&[421.7, 671.0, 1_070.4, 1_882.7]);
+    print_var!(resonances);
+    assert_eq!(resonances, vec![Some((2, 1)), Some((2, 1)), None]);
+    // Neptune and Pluto.
+    assert_eq!(get_resonance(30.07, 39.48), Some((3, 2)));
+    // Earth and Mars.
+    assert_eq!(get_resonance(1.0, 1.524), None);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_resonant_chain() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let chain = get_resonant_chain(&mut rng, 0.03, 0.01, 0.1, 7);
+    print_var!(chain);
+    assert!(chain.len() > 2 && chain.len() <= 7);
+    assert!(chain.contains(&0.03));
+    assert!(chain.iter().all(|&orbit| (0.01..=0.1).contains(&orbit)));
+    assert!(get_resonances(&chain).iter().all(Option::is_some));
+    assert_eq!(get_resonant_chain(&mut rng, 0.03, 0.01, 10.0, 3).len(), 3);
+    assert_eq!(get_resonant_chain(&mut rng, 0.03, 0.03, 0.03, 7), vec![0.03]);
+    trace_exit!();
+  }
+}
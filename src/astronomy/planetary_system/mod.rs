@@ -1,19 +1,40 @@
+use serde::Serialize;
+
+use crate::astronomy::asteroid_belt::AsteroidBelt;
 use crate::astronomy::host_star::HostStar;
+use crate::astronomy::kuiper_belt::KuiperBelt;
+use crate::astronomy::oort_cloud::OortCloud;
+use crate::astronomy::planet::Planet;
 use crate::astronomy::satellite_systems::SatelliteSystems;
+use crate::astronomy::star::math::mass::msol_to_kg;
+use crate::astronomy::star::name::NameKind;
+use crate::astronomy::zodiacal_dust::ZodiacalDust;
+use crate::math::gravity::get_lagrange_points;
 
+pub mod accrete;
 pub mod constraints;
 pub mod error;
 use error::Error;
+pub mod gravity_assists;
+use gravity_assists::{get_gravity_assist_chains, get_gravity_assists, GravityAssist, GravityAssistChain};
+pub(crate) mod math;
+use math::resonance::get_resonances;
+pub mod sister_worlds;
+use sister_worlds::SisterWorldView;
 
 /// A `PlanetarySystem` is a `HostStar` and 0+ `SatelliteSystem` objects.
 ///
 /// So a `PlanetarySystem` does not necessarily include planets.  This is
 /// confusing and I don't really like it, but I don't have a better name
 /// for it.  Yet.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct PlanetarySystem {
   pub host_star: HostStar,
   pub satellite_systems: SatelliteSystems,
+  pub asteroid_belt: Option<AsteroidBelt>,
+  pub zodiacal_dust: Option<ZodiacalDust>,
+  pub kuiper_belt: Option<KuiperBelt>,
+  pub oort_cloud: Option<OortCloud>,
 }
 
 impl PlanetarySystem {
@@ -44,6 +65,56 @@ impl PlanetarySystem {
     result
   }
 
+  /// Rename the stars and planets with the given function.
+  ///
+  /// Planets named after their star follow it if it's renamed.
+  #[named]
+  pub fn rename<F: FnMut(&str, NameKind) -> String>(&mut self, rename: &mut F) {
+    trace_enter!();
+    let old_star_name = self.host_star.get_name();
+    trace_var!(old_star_name);
+    self.host_star.rename(rename);
+    let new_star_name = self.host_star.get_name();
+    trace_var!(new_star_name);
+    for satellite_system in self.satellite_systems.satellite_systems.iter_mut() {
+      let name = satellite_system.planet.get_name();
+      let name = match name.strip_prefix(&old_star_name) {
+        Some(rest) => rename(&format!("{}{}", new_star_name, rest), NameKind::SystematicPlanet),
+        None => rename(&name, NameKind::Planet),
+      };
+      satellite_system.planet.set_name(name);
+    }
+    trace_exit!();
+  }
+
+  /// Evolve the system forward by the given time, in Gyr.
+  ///
+  /// Only the host star changes; the planets and belts stay where they are,
+  /// though the zodiacal dust is pushed out or drawn in with the starlight.
+  #[named]
+  pub fn evolve(&self, delta: f64) -> Result<Self, Error> {
+    trace_enter!();
+    trace_var!(delta);
+    let host_star = self.host_star.evolve(delta)?;
+    trace_var!(host_star);
+    let zodiacal_dust = self.asteroid_belt.as_ref().map(|asteroid_belt| {
+      ZodiacalDust::from_belt(
+        asteroid_belt,
+        host_star.get_luminosity(),
+        &self.satellite_systems.get_semi_major_axes(),
+      )
+    });
+    trace_var!(zodiacal_dust);
+    let result = Self {
+      host_star,
+      zodiacal_dust,
+      ..self.clone()
+    };
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+
   /// Retrieve or calculate the total mass of the stars.
   ///
   /// Calculated in Msol.
@@ -56,6 +127,40 @@ impl PlanetarySystem {
     result
   }
 
+  /// Retrieve or calculate the total luminosity of the stars.
+  ///
+  /// Calculated in Lsol.
+  #[named]
+  pub fn get_luminosity(&self) -> f64 {
+    trace_enter!();
+    let result = self.host_star.get_luminosity();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the Lagrange points of each star-planet pair, in AU from the star.
+  ///
+  /// These are in the same order as the planets, each L1 through L5.
+  #[named]
+  pub fn get_lagrange_points(&self) -> Vec<[f64; 5]> {
+    trace_enter!();
+    let stellar_mass = msol_to_kg(self.get_stellar_mass());
+    trace_var!(stellar_mass);
+    let result = self
+      .satellite_systems
+      .satellite_systems
+      .iter()
+      .map(|satellite_system| {
+        let planet = &satellite_system.planet;
+        get_lagrange_points(planet.get_semi_major_axis(), planet.get_mass_in_kg(), stellar_mass)
+      })
+      .collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Retrieve or calculate the total number of stars in the system.
   #[named]
   pub fn get_stellar_count(&self) -> u8 {
@@ -65,4 +170,123 @@ impl PlanetarySystem {
     trace_exit!();
     result
   }
+
+  /// Find the resonances between each adjacent pair of planets.
+  #[named]
+  pub fn get_resonances(&self) -> Vec<Option<(u8, u8)>> {
+    trace_enter!();
+    let semi_major_axes = self.satellite_systems.get_semi_major_axes();
+    trace_var!(semi_major_axes);
+    let result = get_resonances(&semi_major_axes);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the brightness of the zodiacal light, relative to Earth's, as seen
+  /// from each planet in the habitable zone.
+  #[named]
+  pub fn get_zodiacal_light(&self) -> Vec<(usize, f64)> {
+    trace_enter!();
+    let (inner, outer) = self.host_star.get_habitable_zone();
+    trace_var!(inner);
+    trace_var!(outer);
+    let luminosity = self.get_luminosity();
+    trace_var!(luminosity);
+    let result = match &self.zodiacal_dust {
+      Some(zodiacal_dust) => self
+        .satellite_systems
+        .get_semi_major_axes()
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, semi_major_axis)| semi_major_axis >= inner && semi_major_axis <= outer)
+        .map(|(index, semi_major_axis)| (index, zodiacal_dust.get_brightness_at(semi_major_axis, luminosity)))
+        .collect(),
+      None => vec![],
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Work out how each planet in the habitable zone appears from each of the
+  /// others.
+  #[named]
+  pub fn get_sister_worlds(&self) -> Vec<SisterWorldView> {
+    trace_enter!();
+    let (inner, outer) = self.host_star.get_habitable_zone();
+    trace_var!(inner);
+    trace_var!(outer);
+    let luminosity = self.get_luminosity();
+    trace_var!(luminosity);
+    let planets = self
+      .satellite_systems
+      .satellite_systems
+      .iter()
+      .map(|satellite_system| &satellite_system.planet)
+      .enumerate()
+      .filter(|(_, planet)| {
+        let semi_major_axis = planet.get_semi_major_axis();
+        semi_major_axis >= inner && semi_major_axis <= outer
+      })
+      .collect::<Vec<_>>();
+    trace_var!(planets);
+    let mut result = vec![];
+    for &(observer_index, observer) in &planets {
+      for &(target_index, target) in &planets {
+        if observer_index != target_index {
+          result.push(SisterWorldView::from_planets(
+            luminosity,
+            observer_index,
+            observer,
+            target_index,
+            target,
+          ));
+        }
+      }
+    }
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the giants that can offer a gravity assist to a spacecraft leaving
+  /// the planet with the specified index, most useful first.
+  ///
+  /// An index past the last planet gets no assists.
+  #[named]
+  pub fn get_gravity_assists(&self, origin: usize) -> Vec<GravityAssist> {
+    trace_enter!();
+    trace_var!(origin);
+    let planets = self.get_planets();
+    let result = get_gravity_assists(self.get_stellar_mass(), &planets, origin);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the routes by way of giant flybys that save fuel on the way out from
+  /// the planet with the specified index, best savings first.
+  ///
+  /// An index past the last planet gets no routes.
+  #[named]
+  pub fn get_gravity_assist_chains(&self, origin: usize) -> Vec<GravityAssistChain> {
+    trace_enter!();
+    trace_var!(origin);
+    let planets = self.get_planets();
+    let result = get_gravity_assist_chains(self.get_stellar_mass(), &planets, origin);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the planets, innermost first.
+  fn get_planets(&self) -> Vec<&Planet> {
+    self
+      .satellite_systems
+      .satellite_systems
+      .iter()
+      .map(|satellite_system| &satellite_system.planet)
+      .collect()
+  }
 }
@@ -0,0 +1,175 @@
+use serde::Serialize;
+
+use crate::astronomy::planet::math::albedo::bond_to_geometric_albedo;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::planetary_system::math::mutual_sky::*;
+
+/// The number of steps to take around the synodic cycle when looking for the
+/// extremes.
+pub const SYNODIC_STEPS: usize = 720;
+
+/// How one planet appears in the sky of another.
+///
+/// Orbits are treated as circular and coplanar, which is close enough for a
+/// pair of planets that are both habitable.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SisterWorldView {
+  /// The index of the planet doing the looking.
+  pub observer: usize,
+  /// The index of the planet being looked at.
+  pub target: usize,
+  /// The time between successive closest approaches, in Earth years.
+  pub synodic_period: f64,
+  /// The closest approach, in AU.
+  pub minimum_distance: f64,
+  /// The greatest separation, in AU.
+  pub maximum_distance: f64,
+  /// The angular diameter at closest approach, in arcseconds.
+  pub maximum_angular_diameter: f64,
+  /// The apparent bolometric magnitude at its brightest.
+  pub brightest_magnitude: f64,
+  /// The fraction of the disk that's lit when it's brightest.
+  pub illuminated_fraction_at_brightest: f64,
+  /// The thinnest phase it's ever seen in, as a fraction of the disk lit.
+  ///
+  /// Planets further out are never less than gibbous; planets further in
+  /// show the full range of phases, like the Moon.
+  pub minimum_illuminated_fraction: f64,
+  /// The furthest it strays from the star, in degrees.
+  ///
+  /// Planets further out reach opposition, at 180°.
+  pub maximum_elongation: f64,
+}
+
+impl SisterWorldView {
+  /// Work out how the target appears from the observer, in a system of the
+  /// given luminosity (in Lsol).
+  #[named]
+  pub fn from_planets(
+    luminosity: f64,
+    observer_index: usize,
+    observer: &Planet,
+    target_index: usize,
+    target: &Planet,
+  ) -> Self {
+    trace_enter!();
+    trace_var!(luminosity);
+    trace_var!(observer_index);
+    trace_var!(target_index);
+    let observer_distance = observer.get_semi_major_axis();
+    trace_var!(observer_distance);
+    let target_distance = target.get_semi_major_axis();
+    trace_var!(target_distance);
+    let radius = target.get_equatorial_radius_in_km();
+    trace_var!(radius);
+    let geometric_albedo = bond_to_geometric_albedo(target.get_bond_albedo());
+    trace_var!(geometric_albedo);
+    let synodic_period = 1.0 / (1.0 / observer.get_orbital_period() - 1.0 / target.get_orbital_period()).abs();
+    trace_var!(synodic_period);
+    let minimum_distance = (observer_distance - target_distance).abs();
+    trace_var!(minimum_distance);
+    let maximum_distance = observer_distance + target_distance;
+    trace_var!(maximum_distance);
+    let maximum_angular_diameter = get_angular_diameter(radius, minimum_distance);
+    trace_var!(maximum_angular_diameter);
+    let mut brightest_magnitude = f64::INFINITY;
+    let mut illuminated_fraction_at_brightest = 0.0;
+    let mut minimum_illuminated_fraction = 1.0f64;
+    let mut maximum_elongation = 0.0f64;
+    // The cycle is symmetric, so half of it will do.
+    for step in 0..=SYNODIC_STEPS {
+      let angle = 180.0 * step as f64 / SYNODIC_STEPS as f64;
+      let distance = get_distance(observer_distance, target_distance, angle);
+      let phase_angle = get_phase_angle(observer_distance, target_distance, distance);
+      let illuminated_fraction = get_illuminated_fraction(phase_angle);
+      let magnitude = get_apparent_magnitude(
+        luminosity,
+        geometric_albedo,
+        radius,
+        target_distance,
+        distance,
+        phase_angle,
+      );
+      if magnitude < brightest_magnitude {
+        brightest_magnitude = magnitude;
+        illuminated_fraction_at_brightest = illuminated_fraction;
+      }
+      minimum_illuminated_fraction = minimum_illuminated_fraction.min(illuminated_fraction);
+      maximum_elongation = maximum_elongation.max(get_elongation(observer_distance, target_distance, distance));
+    }
+    trace_var!(brightest_magnitude);
+    trace_var!(illuminated_fraction_at_brightest);
+    trace_var!(minimum_illuminated_fraction);
+    trace_var!(maximum_elongation);
+    let result = Self {
+      observer: observer_index,
+      target: target_index,
+      synodic_period,
+      minimum_distance,
+      maximum_distance,
+      maximum_angular_diameter,
+      brightest_magnitude,
+      illuminated_fraction_at_brightest,
+      minimum_illuminated_fraction,
+      maximum_elongation,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::planetary_system::constraints::Constraints;
+  use crate::astronomy::planetary_system::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_from_planets() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let planetary_system = Constraints::habitable().generate(&mut rng)?;
+    let luminosity = planetary_system.get_luminosity();
+    let planets = &planetary_system.satellite_systems.satellite_systems;
+    for (observer_index, observer) in planets.iter().enumerate() {
+      for (target_index, target) in planets.iter().enumerate() {
+        if observer_index == target_index {
+          continue;
+        }
+        let view = SisterWorldView::from_planets(
+          luminosity,
+          observer_index,
+          &observer.planet,
+          target_index,
+          &target.planet,
+        );
+        print_var!(view);
+        assert!(view.minimum_distance < view.maximum_distance);
+        assert!(view.brightest_magnitude.is_finite());
+        assert!(view.synodic_period > 0.0);
+        match target.planet.get_semi_major_axis() > observer.planet.get_semi_major_axis() {
+          true => {
+            assert_approx_eq!(view.maximum_elongation, 180.0, 0.5);
+            assert_approx_eq!(view.illuminated_fraction_at_brightest, 1.0, 0.01);
+            assert!(view.minimum_illuminated_fraction > 0.5);
+          },
+          false => {
+            assert!(view.maximum_elongation < 90.0);
+            assert_approx_eq!(view.minimum_illuminated_fraction, 0.0, 0.01);
+          },
+        }
+      }
+    }
+    print_var!(planetary_system.get_sister_worlds());
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,57 @@
+use crate::math::probability::Probability;
+
+/// The probability that a gas giant has a ring system worth mentioning.
+///
+/// All four of ours have rings, but only Saturn's are much to look at.
+pub const GAS_GIANT_PROBABILITY: Probability = Probability::from_const(0.5);
+
+/// The probability that an ice giant has a ring system worth mentioning.
+///
+/// Uranus and Neptune both have rings, but they're dark and narrow.
+pub const ICE_GIANT_PROBABILITY: Probability = Probability::from_const(0.3);
+
+/// The probability that a terrestrial planet has a ring system.
+///
+/// Rings this close to a star are short-lived, typically the debris of a
+/// moon that strayed inside the Roche limit.
+pub const TERRESTRIAL_PROBABILITY: Probability = Probability::from_const(0.02);
+
+/// The innermost inner edge of a ring system, in planetary radii.
+///
+/// Saturn's D ring reaches down to about 1.11 Saturn radii.
+pub const MINIMUM_INNER_RADIUS: f64 = 1.1;
+
+/// The outermost inner edge of a ring system, in planetary radii.
+pub const MAXIMUM_INNER_RADIUS: f64 = 1.5;
+
+/// The innermost outer edge of a ring system, as a fraction of the Roche
+/// limit for its particles.
+pub const MINIMUM_OUTER_RADIUS_RATIO: f64 = 0.6;
+
+/// The outermost outer edge of a ring system, as a fraction of the Roche
+/// limit for its particles.
+///
+/// Saturn's A ring ends just about at its Roche limit for ice.
+pub const MAXIMUM_OUTER_RADIUS_RATIO: f64 = 1.0;
+
+/// The density of ring particles made of water ice, in g/cm³.
+pub const ICE_DENSITY: f64 = 0.92;
+
+/// The density of ring particles made of rock, in g/cm³.
+pub const ROCK_DENSITY: f64 = 3.0;
+
+/// The optical depth range of icy rings.
+///
+/// Saturn's B ring is optically thick, at 2 or so; its C ring about 0.1.
+pub const ICE_OPTICAL_DEPTH: (f64, f64) = (0.01, 2.0);
+
+/// The optical depth range of rocky and dusty rings.
+///
+/// Jupiter's main ring is about 1E-6; Uranus's dark rings about 0.5.
+pub const ROCK_OPTICAL_DEPTH: (f64, f64) = (1.0E-6, 0.5);
+
+/// The Bond albedo of icy ring particles.
+pub const ICE_ALBEDO: f64 = 0.5;
+
+/// The Bond albedo of rocky ring particles.
+pub const ROCK_ALBEDO: f64 = 0.05;
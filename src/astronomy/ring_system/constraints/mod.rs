@@ -0,0 +1,154 @@
+use rand::prelude::*;
+
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::ring_system::constants::*;
+use crate::astronomy::ring_system::{RingComposition, RingSystem};
+use crate::math::gravity::get_fluid_roche_limit;
+use crate::math::probability::Probability;
+use crate::merge::Merge;
+
+/// Constraints for creating a ring system.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+  /// The probability that a gas giant has rings.
+  pub gas_giant_probability: Option<Probability>,
+  /// The probability that an ice giant has rings.
+  pub ice_giant_probability: Option<Probability>,
+  /// The probability that a terrestrial planet has rings.
+  pub terrestrial_probability: Option<Probability>,
+}
+
+impl Constraints {
+  /// Generate.
+  ///
+  /// Rings are icy beyond the frost line and rocky within it; either way,
+  /// they end somewhere short of the Roche limit for their particles.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: &HostStar,
+    star_distance: f64,
+    planet: &Planet,
+  ) -> Option<RingSystem> {
+    trace_enter!();
+    trace_var!(host_star);
+    trace_var!(star_distance);
+    trace_var!(planet);
+    let probability = match planet {
+      Planet::GasGiantPlanet(_) => self.gas_giant_probability.unwrap_or(GAS_GIANT_PROBABILITY),
+      Planet::IceGiantPlanet(_) => self.ice_giant_probability.unwrap_or(ICE_GIANT_PROBABILITY),
+      Planet::TerrestrialPlanet(_) => self.terrestrial_probability.unwrap_or(TERRESTRIAL_PROBABILITY),
+    };
+    trace_var!(probability);
+    if !probability.sample(rng) {
+      trace_exit!();
+      return None;
+    }
+    let composition = match star_distance > host_star.get_frost_line() {
+      true => RingComposition::Ice,
+      false => RingComposition::Rock,
+    };
+    trace_var!(composition);
+    let planet_radius = planet.get_equatorial_radius_in_km();
+    trace_var!(planet_radius);
+    let roche_limit = get_fluid_roche_limit(planet_radius, planet.get_density(), composition.get_density());
+    trace_var!(roche_limit);
+    let inner_radius = planet_radius * rng.gen_range(MINIMUM_INNER_RADIUS..MAXIMUM_INNER_RADIUS);
+    trace_var!(inner_radius);
+    let outer_radius = roche_limit * rng.gen_range(MINIMUM_OUTER_RADIUS_RATIO..=MAXIMUM_OUTER_RADIUS_RATIO);
+    trace_var!(outer_radius);
+    // A dense planet and light particles may leave no room at all.
+    if outer_radius <= inner_radius {
+      trace_exit!();
+      return None;
+    }
+    let (minimum_optical_depth, maximum_optical_depth) = match composition {
+      RingComposition::Ice => ICE_OPTICAL_DEPTH,
+      RingComposition::Rock => ROCK_OPTICAL_DEPTH,
+    };
+    let optical_depth = rng
+      .gen_range(minimum_optical_depth.ln()..maximum_optical_depth.ln())
+      .exp();
+    trace_var!(optical_depth);
+    let result = RingSystem {
+      inner_radius,
+      outer_radius,
+      optical_depth,
+      composition,
+    };
+    trace_var!(result);
+    trace_exit!();
+    Some(result)
+  }
+}
+
+impl Default for Constraints {
+  /// No constraints, just let it all hang out.
+  fn default() -> Self {
+    let gas_giant_probability = None;
+    let ice_giant_probability = None;
+    let terrestrial_probability = None;
+    Self {
+      gas_giant_probability,
+      ice_giant_probability,
+      terrestrial_probability,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      gas_giant_probability: overrides.gas_giant_probability.or(self.gas_giant_probability),
+      ice_giant_probability: overrides.ice_giant_probability.or(self.ice_giant_probability),
+      terrestrial_probability: overrides.terrestrial_probability.or(self.terrestrial_probability),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use crate::astronomy::gas_giant_planet::constraints::Constraints as GasGiantPlanetConstraints;
+  use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+  use crate::astronomy::planet::error::Error;
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = HostStarConstraints::default().generate(&mut rng)?;
+    let star_distance = 2.0 * host_star.get_frost_line();
+    let planet =
+      Planet::GasGiantPlanet(GasGiantPlanetConstraints::default().generate(&mut rng, &host_star, star_distance)?);
+    let constraints = Constraints {
+      gas_giant_probability: Some(Probability::ALWAYS),
+      ..Constraints::default()
+    };
+    let ring_system = constraints.generate(&mut rng, &host_star, star_distance, &planet);
+    print_var!(ring_system);
+    let ring_system = ring_system.unwrap();
+    assert_eq!(ring_system.composition, RingComposition::Ice);
+    assert!(ring_system.inner_radius > planet.get_equatorial_radius_in_km());
+    assert!(ring_system.get_width() > 0.0);
+    assert!(ring_system.get_reflectance() < ICE_ALBEDO);
+    let constraints = Constraints {
+      gas_giant_probability: Some(Probability::NEVER),
+      ..Constraints::default()
+    };
+    assert!(constraints
+      .generate(&mut rng, &host_star, star_distance, &planet)
+      .is_none());
+    trace_exit!();
+    Ok(())
+  }
+}
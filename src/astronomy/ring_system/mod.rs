@@ -0,0 +1,71 @@
+use serde::Serialize;
+
+pub mod constants;
+use constants::*;
+pub mod constraints;
+
+/// What a ring system is made of.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum RingComposition {
+  /// Bright water ice, like Saturn's; only stable beyond the frost line.
+  Ice,
+  /// Dark rock and dust, like Jupiter's and Uranus's.
+  Rock,
+}
+
+impl RingComposition {
+  /// Get the density of the particles, in g/cm³.
+  pub fn get_density(&self) -> f64 {
+    match self {
+      RingComposition::Ice => ICE_DENSITY,
+      RingComposition::Rock => ROCK_DENSITY,
+    }
+  }
+
+  /// Get the Bond albedo of the particles.
+  pub fn get_albedo(&self) -> f64 {
+    match self {
+      RingComposition::Ice => ICE_ALBEDO,
+      RingComposition::Rock => ROCK_ALBEDO,
+    }
+  }
+}
+
+/// A `RingSystem` is a disk of particles orbiting within a planet's Roche
+/// limit.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RingSystem {
+  /// The inner edge, in KM from the center of the planet.
+  pub inner_radius: f64,
+  /// The outer edge, in KM from the center of the planet.
+  pub outer_radius: f64,
+  /// The optical depth; above 1 or so, the rings are opaque.
+  pub optical_depth: f64,
+  /// What the rings are made of.
+  pub composition: RingComposition,
+}
+
+impl RingSystem {
+  /// Get the width of the rings, in KM.
+  #[named]
+  pub fn get_width(&self) -> f64 {
+    trace_enter!();
+    let result = self.outer_radius - self.inner_radius;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the fraction of light falling on the rings that they reflect.
+  ///
+  /// Thin rings let most light through; thick ones are as bright as their
+  /// particles.
+  #[named]
+  pub fn get_reflectance(&self) -> f64 {
+    trace_enter!();
+    let result = self.composition.get_albedo() * (1.0 - (-self.optical_depth).exp());
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
@@ -0,0 +1,35 @@
+/// Minimum mass of a rogue planet, in Mearth.
+///
+/// Smaller bodies surely wander too, but they're too faint for anything to
+/// come of them.
+pub const MINIMUM_MASS: f64 = 0.1;
+
+/// Maximum mass of a rogue planet, in Mearth.
+///
+/// About 13 Mjupiter; anything heavier would burn deuterium, which makes it a
+/// brown dwarf.
+pub const MAXIMUM_MASS: f64 = 4_000.0;
+
+/// The least massive rogue planet to have held onto an ice giant's envelope,
+/// in Mearth.
+pub const MINIMUM_ICE_GIANT_MASS: f64 = 10.0;
+
+/// The least massive rogue planet to have held onto a gas giant's envelope,
+/// in Mearth.
+pub const MINIMUM_GAS_GIANT_MASS: f64 = 50.0;
+
+/// The density of a terrestrial rogue planet, in g/cm³.
+///
+/// This is Earth's.
+pub const TERRESTRIAL_DENSITY: f64 = 5.51;
+
+/// The density of an ice giant rogue planet, in g/cm³.
+///
+/// This is Neptune's.
+pub const ICE_GIANT_DENSITY: f64 = 1.64;
+
+/// The radius of a gas giant rogue planet, in Rearth.
+///
+/// Degeneracy pressure holds gas giants to about the radius of Jupiter
+/// across their whole range of masses.
+pub const GAS_GIANT_RADIUS: f64 = 11.2;
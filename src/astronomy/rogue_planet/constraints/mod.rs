@@ -0,0 +1,160 @@
+use rand::prelude::*;
+
+use crate::astronomy::rogue_planet::constants::*;
+use crate::astronomy::rogue_planet::error::Error;
+use crate::astronomy::rogue_planet::{RoguePlanet, RoguePlanetKind};
+use crate::astronomy::star::name::generate_star_name;
+use crate::astronomy::stellar_neighbor::math::point::get_random_point_in_sphere;
+use crate::astronomy::stellar_neighborhood::constants::STELLAR_NEIGHBORHOOD_RADIUS;
+use crate::astronomy::terrestrial_planet::math::radius::get_radius;
+use crate::generator::Generator;
+use crate::merge::Merge;
+
+/// Constraints for creating a rogue planet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+  /// The radius of the neighborhood, in light years.
+  pub radius: Option<f64>,
+  /// The minimum mass, in Mearth.
+  pub minimum_mass: Option<f64>,
+  /// The maximum mass, in Mearth.
+  pub maximum_mass: Option<f64>,
+}
+
+impl Constraints {
+  /// Generate a rogue planet somewhere in the neighborhood.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<RoguePlanet, Error> {
+    trace_enter!();
+    let radius = self.radius.unwrap_or(STELLAR_NEIGHBORHOOD_RADIUS);
+    trace_var!(radius);
+    let minimum_mass = self.minimum_mass.unwrap_or(MINIMUM_MASS);
+    trace_var!(minimum_mass);
+    let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
+    trace_var!(maximum_mass);
+    if minimum_mass > maximum_mass {
+      return Err(Error::MinimumMassExceedsMaximumMass);
+    }
+    // Smaller bodies are more common, so pick log-uniformly.
+    let mass = rng.gen_range(minimum_mass.ln()..=maximum_mass.ln()).exp();
+    trace_var!(mass);
+    let kind = match mass {
+      mass if mass >= MINIMUM_GAS_GIANT_MASS => RoguePlanetKind::GasGiant,
+      mass if mass >= MINIMUM_ICE_GIANT_MASS => RoguePlanetKind::IceGiant,
+      _ => RoguePlanetKind::Terrestrial,
+    };
+    trace_var!(kind);
+    let planet_radius = match kind {
+      RoguePlanetKind::Terrestrial => get_radius(mass, TERRESTRIAL_DENSITY),
+      RoguePlanetKind::IceGiant => get_radius(mass, ICE_GIANT_DENSITY),
+      RoguePlanetKind::GasGiant => GAS_GIANT_RADIUS,
+    };
+    trace_var!(planet_radius);
+    let (x, y, z) = get_random_point_in_sphere(rng);
+    let coordinates = (x * radius, y * radius, z * radius);
+    trace_var!(coordinates);
+    let distance = (x.powf(2.0) + y.powf(2.0) + z.powf(2.0)).sqrt() * radius;
+    trace_var!(distance);
+    let name = loop {
+      let name = generate_star_name(rng);
+      if !name.is_empty() {
+        break name;
+      }
+    };
+    trace_var!(name);
+    let result = RoguePlanet {
+      name,
+      kind,
+      coordinates,
+      distance,
+      mass,
+      radius: planet_radius,
+    };
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+}
+
+impl Generator for Constraints {
+  type Output = RoguePlanet;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<RoguePlanet, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
+impl Default for Constraints {
+  /// No constraints, just let it all hang out.
+  fn default() -> Self {
+    let radius = None;
+    let minimum_mass = None;
+    let maximum_mass = None;
+    Self {
+      radius,
+      minimum_mass,
+      maximum_mass,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      radius: overrides.radius.or(self.radius),
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints {
+      radius: Some(5.0),
+      ..Constraints::default()
+    };
+    for _ in 0..20 {
+      let rogue_planet = constraints.generate(&mut rng)?;
+      print_var!(rogue_planet);
+      assert!(rogue_planet.distance <= 5.0);
+      assert!((MINIMUM_MASS..=MAXIMUM_MASS).contains(&rogue_planet.mass));
+      assert!(rogue_planet.radius > 0.0 && rogue_planet.radius <= GAS_GIANT_RADIUS);
+      assert!(!rogue_planet.is_habitable());
+    }
+    let earth = Constraints {
+      minimum_mass: Some(1.0),
+      maximum_mass: Some(1.0),
+      ..Constraints::default()
+    }
+    .generate(&mut rng)?;
+    assert_eq!(earth.kind, RoguePlanetKind::Terrestrial);
+    assert_approx_eq!(earth.radius, 1.0);
+    let inverted = Constraints {
+      minimum_mass: Some(2.0),
+      maximum_mass: Some(1.0),
+      ..Constraints::default()
+    };
+    assert_eq!(inverted.generate(&mut rng), Err(Error::MinimumMassExceedsMaximumMass));
+    trace_exit!();
+    Ok(())
+  }
+}
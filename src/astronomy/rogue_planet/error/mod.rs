@@ -0,0 +1,16 @@
+/// RoguePlanet errors.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+  /// The minimum mass is greater than the maximum mass.
+  MinimumMassExceedsMaximumMass,
+  /// There's no star to keep it warm.
+  NoHostStar,
+}
+
+honeyholt_define_brief!(Error, |error: &Error| {
+  use Error::*;
+  match error {
+    MinimumMassExceedsMaximumMass => "the minimum mass is greater than the maximum mass".to_string(),
+    NoHostStar => "the rogue planet has no host star to keep it warm".to_string(),
+  }
+});
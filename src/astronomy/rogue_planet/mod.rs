@@ -0,0 +1,68 @@
+use serde::Serialize;
+
+pub mod constants;
+pub mod constraints;
+pub mod error;
+use error::Error;
+
+/// What sort of planet a rogue planet was before it was cast out.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum RoguePlanetKind {
+  /// Rock and metal, like the inner planets.
+  Terrestrial,
+  /// Mostly ices under a thin envelope, like Neptune.
+  IceGiant,
+  /// Mostly hydrogen and helium, like Jupiter.
+  GasGiant,
+}
+
+/// A `RoguePlanet` is a planetary-mass object that belongs to no star.
+///
+/// Most were flung out of their planetary systems young; a few of the
+/// heaviest may have collapsed on their own, like tiny brown dwarfs.  They
+/// drift through the neighborhood in the dark, which doesn't stop them from
+/// being worth a visit.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RoguePlanet {
+  /// The name.
+  pub name: String,
+  /// What sort of planet this is.
+  pub kind: RoguePlanetKind,
+  /// Three-dimensional coordinates, in light years from the origin.
+  pub coordinates: (f64, f64, f64),
+  /// Distance from the origin, in light years.
+  pub distance: f64,
+  /// Mass, in Mearth.
+  pub mass: f64,
+  /// Radius, in Rearth.
+  pub radius: f64,
+}
+
+impl RoguePlanet {
+  /// Indicate whether this rogue planet is capable of supporting
+  /// conventional life.
+  ///
+  /// It never is; whatever life it might once have had has long since frozen.
+  #[named]
+  pub fn check_habitable(&self) -> Result<(), Error> {
+    trace_enter!();
+    let result = Err(Error::NoHostStar);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this rogue planet is capable of supporting
+  /// conventional life.
+  #[named]
+  pub fn is_habitable(&self) -> bool {
+    trace_enter!();
+    let result = match self.check_habitable() {
+      Ok(()) => true,
+      Err(_) => false,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
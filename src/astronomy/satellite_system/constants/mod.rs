@@ -1 +1,5 @@
+/// Seconds per century, for reporting changes in day length.
+pub const SECONDS_PER_CENTURY: f64 = 36_525.0 * 86_400.0;
 
+/// Milliseconds per second.
+pub const MILLISECONDS_PER_SECOND: f64 = 1_000.0;
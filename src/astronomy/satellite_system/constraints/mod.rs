@@ -1,10 +1,15 @@
 use rand::prelude::*;
 
 use crate::astronomy::host_star::HostStar;
+use crate::astronomy::moon::constants::KG_PER_MOON_MASS;
 use crate::astronomy::moons::constraints::Constraints as MoonsConstraints;
 use crate::astronomy::planet::constraints::Constraints as PlanetConstraints;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::ring_system::constraints::Constraints as RingSystemConstraints;
 use crate::astronomy::satellite_system::error::Error;
 use crate::astronomy::satellite_system::SatelliteSystem;
+use crate::astronomy::trojan_swarm::constraints::Constraints as TrojanSwarmConstraints;
+use crate::merge::Merge;
 
 /// Constraints for creating a planet and its moons.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -13,6 +18,10 @@ pub struct Constraints {
   pub planet_constraints: Option<PlanetConstraints>,
   /// Moons constraints.
   pub moons_constraints: Option<MoonsConstraints>,
+  /// Ring system constraints.
+  pub ring_system_constraints: Option<RingSystemConstraints>,
+  /// Trojan swarm constraints.
+  pub trojan_swarm_constraints: Option<TrojanSwarmConstraints>,
 }
 
 impl Constraints {
@@ -38,13 +47,58 @@ impl Constraints {
     trace_var!(star_distance);
     let planet_constraints = self.planet_constraints.unwrap_or(PlanetConstraints::default());
     trace_var!(planet_constraints);
-    let moons_constraints = self.moons_constraints.unwrap_or(MoonsConstraints::default());
-    trace_var!(moons_constraints);
     let planet = planet_constraints.generate(rng, host_star, star_distance)?;
     trace_var!(planet);
+    let result = self.generate_around_planet(rng, host_star, planet);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Generate the moons, rings, and so forth around a planet that's already
+  /// been generated.
+  ///
+  /// The planet constraints are ignored.
+  #[named]
+  pub fn generate_around_planet<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: &HostStar,
+    mut planet: Planet,
+  ) -> Result<SatelliteSystem, Error> {
+    trace_enter!();
+    trace_var!(planet);
+    let star_distance = planet.get_semi_major_axis();
+    trace_var!(star_distance);
+    let moons_constraints = self.moons_constraints.unwrap_or(MoonsConstraints::default());
+    trace_var!(moons_constraints);
+    let ring_system_constraints = self.ring_system_constraints.unwrap_or_default();
+    trace_var!(ring_system_constraints);
+    let trojan_swarm_constraints = self.trojan_swarm_constraints.unwrap_or_default();
+    trace_var!(trojan_swarm_constraints);
     let moons = moons_constraints.generate(rng, host_star, star_distance, &planet)?;
     trace_var!(moons);
-    let result = SatelliteSystem { planet, moons };
+    let planet_mass = planet.get_mass_in_kg();
+    trace_var!(planet_mass);
+    if let Planet::TerrestrialPlanet(terrestrial_planet) = &mut planet {
+      let largest_moon_mass = moons
+        .moons
+        .iter()
+        .map(|moon| moon.mass * KG_PER_MOON_MASS)
+        .fold(0.0, f64::max);
+      trace_var!(largest_moon_mass);
+      terrestrial_planet.set_largest_moon_mass(largest_moon_mass, planet_mass);
+    }
+    let ring_system = ring_system_constraints.generate(rng, host_star, star_distance, &planet);
+    trace_var!(ring_system);
+    let trojan_swarms = trojan_swarm_constraints.generate(rng, host_star, &planet);
+    trace_var!(trojan_swarms);
+    let result = SatelliteSystem {
+      planet,
+      moons,
+      ring_system,
+      trojan_swarms,
+    };
     trace_var!(result);
     trace_exit!();
     Ok(result)
@@ -56,9 +110,28 @@ impl Default for Constraints {
   fn default() -> Self {
     let planet_constraints = None;
     let moons_constraints = None;
+    let ring_system_constraints = None;
+    let trojan_swarm_constraints = None;
     Self {
       planet_constraints,
       moons_constraints,
+      ring_system_constraints,
+      trojan_swarm_constraints,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      planet_constraints: self.planet_constraints.merged_with(&overrides.planet_constraints),
+      moons_constraints: self.moons_constraints.merged_with(&overrides.moons_constraints),
+      ring_system_constraints: self
+        .ring_system_constraints
+        .merged_with(&overrides.ring_system_constraints),
+      trojan_swarm_constraints: self
+        .trojan_swarm_constraints
+        .merged_with(&overrides.trojan_swarm_constraints),
     }
   }
 }
@@ -70,6 +143,7 @@ pub mod test {
   use rand::prelude::*;
 
   use super::*;
+  use crate::astronomy::time::Time;
   use crate::test::*;
 
   #[named]
@@ -88,4 +162,31 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_day_length() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = HostStarConstraints::habitable().generate(&mut rng)?;
+    let habitable_zone = host_star.get_habitable_zone();
+    let distance = rng.gen_range(habitable_zone.0..habitable_zone.1);
+    let satellite_system = Constraints::default().generate(&mut rng, &host_star, distance)?;
+    let rate = satellite_system.get_day_length_rate();
+    print_var!(rate);
+    let now = satellite_system.get_day_length_at(Time::EPOCH);
+    assert_approx_eq!(now, satellite_system.planet.get_rotation_period());
+    let past = satellite_system.get_day_length_at(Time::from_years(-1.0E8));
+    let future = satellite_system.get_day_length_at(Time::from_years(1.0E8));
+    print_var!(past);
+    print_var!(future);
+    match rate > 0.0 {
+      true => assert!(past <= now && now <= future),
+      false => assert!(past >= now && now >= future),
+    }
+    trace_exit!();
+    Ok(())
+  }
 }
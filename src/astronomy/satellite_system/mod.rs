@@ -1,18 +1,40 @@
+use serde::Serialize;
+
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::moon::constants::KG_PER_MOON_MASS;
+use crate::astronomy::moon::math::tides::{get_lunar_tide, get_solar_tide};
 use crate::astronomy::moons::Moons;
+use crate::astronomy::planet::math::oblateness::SECONDS_PER_DAY;
+use crate::astronomy::planet::math::radius::METERS_PER_EARTH_RADIUS;
+use crate::astronomy::planet::math::tidal_braking::{
+  get_rotation_period_at, get_rotation_period_rate, get_tidal_torque,
+};
 use crate::astronomy::planet::Planet;
+use crate::astronomy::ring_system::RingSystem;
+use crate::astronomy::time::Time;
+use crate::astronomy::trojan_swarm::TrojanSwarm;
+use crate::math::gravity::get_lagrange_points;
 
 pub mod constants;
+use constants::*;
 pub mod constraints;
 pub mod error;
 use error::Error;
+pub mod tides;
+use tides::Tides;
 
-/// A `SatelliteSystem` is a collection of a `Planet` and `Moons`.
-#[derive(Clone, Debug, PartialEq)]
+/// A `SatelliteSystem` is a collection of a `Planet`, `Moons`, perhaps a
+/// `RingSystem`, and any `TrojanSwarm` objects sharing the planet's orbit.
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct SatelliteSystem {
   /// The planet.
   pub planet: Planet,
   /// The moons.
   pub moons: Moons,
+  /// The rings, if any.
+  pub ring_system: Option<RingSystem>,
+  /// The trojan swarms at the planet's L4 and L5 points, if any.
+  pub trojan_swarms: Vec<TrojanSwarm>,
 }
 
 impl SatelliteSystem {
@@ -43,4 +65,136 @@ impl SatelliteSystem {
     trace_exit!();
     result
   }
+
+  /// Get the Lagrange points of each planet-moon pair, in KM from the
+  /// planet.
+  ///
+  /// These are in the same order as the moons, each L1 through L5.
+  #[named]
+  pub fn get_lagrange_points(&self) -> Vec<[f64; 5]> {
+    trace_enter!();
+    let planet_mass = self.planet.get_mass_in_kg();
+    trace_var!(planet_mass);
+    let result = self
+      .moons
+      .moons
+      .iter()
+      .map(|moon| get_lagrange_points(moon.orbit.semi_major_axis, moon.mass * KG_PER_MOON_MASS, planet_mass))
+      .collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Summarize the tides raised on the planet by the host star(s) and each
+  /// moon.
+  #[named]
+  pub fn get_tides(&self, host_star: &HostStar) -> Tides {
+    trace_enter!();
+    // In Rearth, whatever the kind of planet.
+    let planet_radius = self.planet.get_equatorial_radius_in_km() * 1_000.0 / METERS_PER_EARTH_RADIUS;
+    trace_var!(planet_radius);
+    let solar_tide = get_solar_tide(
+      host_star.get_stellar_mass(),
+      planet_radius,
+      self.planet.get_semi_major_axis(),
+    );
+    trace_var!(solar_tide);
+    let lunar_tides = self
+      .moons
+      .moons
+      .iter()
+      .map(|moon| get_lunar_tide(moon.mass, planet_radius, moon.orbit.semi_major_axis))
+      .collect();
+    trace_var!(lunar_tides);
+    let result = Tides::new(solar_tide, lunar_tides);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the rate at which the planet's day is lengthening, in ms/century.
+  ///
+  /// Each moon raises a tide on the planet; a moon orbiting more slowly than
+  /// the planet spins drags the tidal bulge back and brakes the planet, while
+  /// one orbiting faster (like Phobos) spins it up.  A planet locked to its
+  /// moon feels no torque from it.
+  #[named]
+  pub fn get_day_length_rate(&self) -> f64 {
+    trace_enter!();
+    let rotation_period = self.planet.get_rotation_period();
+    trace_var!(rotation_period);
+    let planet_radius = self.planet.get_equatorial_radius_in_km() * 1_000.0;
+    trace_var!(planet_radius);
+    let tidal_lag = self.planet.get_tidal_lag();
+    trace_var!(tidal_lag);
+    let torque: f64 = self
+      .moons
+      .moons
+      .iter()
+      .filter(|moon| !moon.is_planet_tidally_locked)
+      .map(|moon| {
+        let torque = get_tidal_torque(
+          tidal_lag,
+          moon.mass * KG_PER_MOON_MASS,
+          planet_radius,
+          moon.orbit.semi_major_axis * 1_000.0,
+        );
+        match moon.sidereal_orbital_period < rotation_period {
+          true => -torque,
+          false => torque,
+        }
+      })
+      .sum();
+    trace_var!(torque);
+    let rate = get_rotation_period_rate(
+      torque,
+      self.planet.get_moment_of_inertia(),
+      rotation_period * SECONDS_PER_DAY,
+    );
+    trace_var!(rate);
+    let result = rate * SECONDS_PER_CENTURY * MILLISECONDS_PER_SECOND;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the length of the planet's day at a time after epoch (negative for
+  /// the past), in Dearth.
+  ///
+  /// This holds the current tidal torque steady, ignoring the drift of the
+  /// moons' orbits, so it's best over a few hundred million years.  Going
+  /// forward, the day stops changing once it matches the orbit of a moon.
+  #[named]
+  pub fn get_day_length_at(&self, time: Time) -> f64 {
+    trace_enter!();
+    trace_var!(time);
+    let rotation_period = self.planet.get_rotation_period();
+    trace_var!(rotation_period);
+    let rate = self.get_day_length_rate() / (SECONDS_PER_CENTURY * MILLISECONDS_PER_SECOND);
+    trace_var!(rate);
+    let day_length = get_rotation_period_at(rotation_period, rate, time.get_days());
+    trace_var!(day_length);
+    let (minimum, maximum) = match time > Time::EPOCH {
+      true => self
+        .moons
+        .moons
+        .iter()
+        .filter(|moon| !moon.is_planet_tidally_locked)
+        .map(|moon| moon.sidereal_orbital_period)
+        .fold((0.0f64, f64::INFINITY), |(minimum, maximum), period| {
+          match period < rotation_period {
+            true => (minimum.max(period), maximum),
+            false => (minimum, maximum.min(period)),
+          }
+        }),
+      false => (0.0, f64::INFINITY),
+    };
+    trace_var!(minimum);
+    trace_var!(maximum);
+    let result = day_length.clamp(minimum, maximum);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
 }
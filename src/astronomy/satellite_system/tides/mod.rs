@@ -0,0 +1,110 @@
+use serde::Serialize;
+
+use crate::astronomy::moon::math::tides::{get_neap_tide, get_spring_tide};
+
+/// Earth's spring tide, in meters, for comparison.
+pub const EARTH_SPRING_TIDE: f64 = 0.786;
+
+/// Spring tides this many times Earth's, or more, are extreme.
+///
+/// Think of the Bay of Fundy, but everywhere, twice a day.
+pub const EXTREME_TIDE_RATIO: f64 = 10.0;
+
+/// A summary of the tides raised on a planet by its host star(s) and moons.
+///
+/// All magnitudes are in meters; Earth's lunar tide is about 1.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Tides {
+  /// The tide raised by the host star(s).
+  pub solar_tide: f64,
+  /// The tide raised by each moon, in the same order as the moons.
+  pub lunar_tides: Vec<f64>,
+  /// The tide when everything lines up.
+  pub spring_tide: f64,
+  /// The tide when the strongest pull is opposed by all the others.
+  pub neap_tide: f64,
+}
+
+impl Tides {
+  /// Summarize the tides from the solar tide and each lunar tide.
+  #[named]
+  pub fn new(solar_tide: f64, lunar_tides: Vec<f64>) -> Self {
+    trace_enter!();
+    trace_var!(solar_tide);
+    trace_var!(lunar_tides);
+    let total_lunar_tide: f64 = lunar_tides.iter().sum();
+    trace_var!(total_lunar_tide);
+    let spring_tide = get_spring_tide(total_lunar_tide, solar_tide);
+    trace_var!(spring_tide);
+    let strongest_tide = lunar_tides.iter().copied().fold(solar_tide, f64::max);
+    trace_var!(strongest_tide);
+    let neap_tide = get_neap_tide(strongest_tide, solar_tide + total_lunar_tide - strongest_tide);
+    trace_var!(neap_tide);
+    let result = Self {
+      solar_tide,
+      lunar_tides,
+      spring_tide,
+      neap_tide,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the spring tide relative to Earth's.
+  #[named]
+  pub fn get_relative_spring_tide(&self) -> f64 {
+    trace_enter!();
+    let result = self.spring_tide / EARTH_SPRING_TIDE;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the ratio of the spring tide to the neap tide.
+  ///
+  /// Earth's is about 2.7; a single dominant moon or star pushes it toward 1.
+  #[named]
+  pub fn get_spring_neap_ratio(&self) -> f64 {
+    trace_enter!();
+    let result = self.spring_tide / self.neap_tide;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether the tides are extreme.
+  #[named]
+  pub fn is_extreme(&self) -> bool {
+    trace_enter!();
+    let result = self.get_relative_spring_tide() >= EXTREME_TIDE_RATIO;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_new() {
+    init();
+    trace_enter!();
+    let earth = Tides::new(0.460, vec![0.996]);
+    print_var!(earth);
+    assert_approx_eq!(earth.get_relative_spring_tide(), 1.0, 0.01);
+    assert_approx_eq!(earth.get_spring_neap_ratio(), 2.72, 0.01);
+    assert!(!earth.is_extreme());
+    // A planet in the habitable zone of a red dwarf, with no moons.
+    let red_dwarf = Tides::new(0.46 * 0.12 / 0.05_f64.powf(3.0), vec![]);
+    print_var!(red_dwarf);
+    assert!(red_dwarf.is_extreme());
+    assert_approx_eq!(red_dwarf.get_spring_neap_ratio(), 1.0);
+    trace_exit!();
+  }
+}
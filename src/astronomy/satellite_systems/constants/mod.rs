@@ -3,3 +3,8 @@ pub const MINIMUM_SATELLITE_SYSTEMS: usize = 0;
 
 /// Maximum number of satellite systems to generate.
 pub const MAXIMUM_SATELLITE_SYSTEMS: usize = 12;
+
+/// The most planets to place in a resonant chain.
+///
+/// TRAPPIST-1 has seven, each in resonance with its neighbors.
+pub const MAXIMUM_RESONANT_CHAIN_LENGTH: usize = 7;
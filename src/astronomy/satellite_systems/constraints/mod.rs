@@ -2,10 +2,19 @@ use rand::prelude::*;
 use std::default::Default;
 
 use crate::astronomy::host_star::HostStar;
+use crate::astronomy::planet::constraints::Constraints as PlanetConstraints;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::planetary_system::accrete::Accretion;
+use crate::astronomy::planetary_system::math::resonance::get_resonant_chain;
 use crate::astronomy::satellite_system::constraints::Constraints as SatelliteSystemConstraints;
+use crate::astronomy::satellite_system::error::Error as SatelliteSystemError;
 use crate::astronomy::satellite_systems::constants::*;
 use crate::astronomy::satellite_systems::error::Error;
+use crate::astronomy::satellite_systems::math::spacing::SpacingLaw;
 use crate::astronomy::satellite_systems::SatelliteSystems;
+use crate::astronomy::star::math::satellite_zone::get_approximate_innermost_orbit;
+use crate::math::probability::Probability;
+use crate::merge::Merge;
 
 /// Constraints for creating satellite systems.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -17,9 +26,25 @@ pub struct Constraints {
   /// Satellite System constraints.
   pub satellite_system_constraints: Option<SatelliteSystemConstraints>,
   /// Generate a primary gas giant.
-  pub generate_primary_gas_giant: bool,
+  pub generate_primary_gas_giant: Option<bool>,
   /// Generate a habitable planet.
-  pub generate_habitable: bool,
+  pub generate_habitable: Option<bool>,
+  /// The widest orbit to place a planet on, in AU.
+  ///
+  /// This is for when something else (say, a companion star) would disrupt
+  /// orbits further out than the host star alone would.
+  pub maximum_orbit: Option<f64>,
+  /// Generate the planets in a resonant chain, like TRAPPIST-1's.
+  pub generate_resonant_chain: Option<bool>,
+  /// How the planets' orbits are spaced out, unless they're in a resonant
+  /// chain.
+  pub spacing_law: Option<SpacingLaw>,
+  /// Grow the planets out of a simulated protoplanetary disk, rather than
+  /// placing them statistically.
+  ///
+  /// The planets' masses and orbits come out of the simulation, so a
+  /// habitable planet or primary gas giant can't be asked for.
+  pub generate_by_accretion: Option<bool>,
 }
 
 impl Constraints {
@@ -27,8 +52,8 @@ impl Constraints {
   #[named]
   pub fn habitable() -> Self {
     trace_enter!();
-    let generate_primary_gas_giant = true;
-    let generate_habitable = true;
+    let generate_primary_gas_giant = Some(true);
+    let generate_habitable = Some(true);
     let satellite_system_constraints = Some(SatelliteSystemConstraints::habitable());
     let result = Self {
       generate_primary_gas_giant,
@@ -53,13 +78,58 @@ impl Constraints {
       .satellite_system_constraints
       .unwrap_or(SatelliteSystemConstraints::default());
     trace_var!(satellite_system_constraints);
+    // However poor the star is in metals, the primary gas giant -- the
+    // first planet beyond the frost line -- is a giant if we asked for one.
+    let primary_gas_giant_constraints = satellite_system_constraints.merged_with(&SatelliteSystemConstraints {
+      planet_constraints: Some(PlanetConstraints {
+        giant_planet_probability: Some(Probability::ALWAYS),
+        ..PlanetConstraints::default()
+      }),
+      ..SatelliteSystemConstraints::default()
+    });
+    trace_var!(primary_gas_giant_constraints);
+    let frost_line = host_star.get_frost_line();
+    trace_var!(frost_line);
+    if self.generate_by_accretion.unwrap_or(false) {
+      let result = self.generate_by_accretion(rng, host_star, &satellite_system_constraints);
+      trace_var!(result);
+      trace_exit!();
+      return result;
+    }
+    let mut needs_primary_gas_giant = self.generate_primary_gas_giant.unwrap_or(false);
     let mut satellite_systems = Vec::new();
     let orbits = self.generate_orbits(rng, host_star)?;
     for orbit in orbits.into_iter() {
-      let satellite_system = satellite_system_constraints.generate(rng, host_star, orbit)?;
+      let constraints = match needs_primary_gas_giant && orbit >= frost_line {
+        true => {
+          needs_primary_gas_giant = false;
+          &primary_gas_giant_constraints
+        },
+        false => &satellite_system_constraints,
+      };
+      let satellite_system = constraints.generate(rng, host_star, orbit)?;
       trace_var!(satellite_system);
       satellite_systems.push(satellite_system);
     }
+    // A gas giant inside the frost line migrated there, and anything rocky
+    // that formed along the way was swallowed or flung out of the system.
+    // Only what was already inside the giant's final orbit can survive.
+    let migrated_gas_giant_orbit = satellite_systems
+      .iter()
+      .map(|satellite_system| &satellite_system.planet)
+      .filter(|planet| matches!(planet, Planet::GasGiantPlanet(_)))
+      .map(|planet| planet.get_semi_major_axis())
+      .filter(|&semi_major_axis| semi_major_axis < frost_line)
+      .reduce(f64::min);
+    trace_var!(migrated_gas_giant_orbit);
+    if let Some(migrated_gas_giant_orbit) = migrated_gas_giant_orbit {
+      satellite_systems.retain(|satellite_system| {
+        let semi_major_axis = satellite_system.planet.get_semi_major_axis();
+        !matches!(satellite_system.planet, Planet::TerrestrialPlanet(_))
+          || semi_major_axis < migrated_gas_giant_orbit
+          || semi_major_axis >= frost_line
+      });
+    }
     trace_var!(satellite_systems);
     let result = SatelliteSystems { satellite_systems };
     trace_var!(result);
@@ -67,50 +137,128 @@ impl Constraints {
     Ok(result)
   }
 
+  /// Generate planets by accretion in a simulated protoplanetary disk.
+  ///
+  /// Around a close binary, the disk is truncated where the binary keeps it
+  /// clear.
+  #[named]
+  pub fn generate_by_accretion<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    host_star: &HostStar,
+    satellite_system_constraints: &SatelliteSystemConstraints,
+  ) -> Result<SatelliteSystems, Error> {
+    trace_enter!();
+    let stability_limit = host_star.get_stability_limit();
+    trace_var!(stability_limit);
+    let maximum_orbit = self.maximum_orbit.unwrap_or(f64::INFINITY);
+    trace_var!(maximum_orbit);
+    let protoplanets = Accretion::new(
+      host_star.get_stellar_mass(),
+      host_star.get_luminosity(),
+      stability_limit,
+      maximum_orbit,
+    )
+    .run(rng);
+    trace_var!(protoplanets);
+    let planet_constraints = satellite_system_constraints.planet_constraints.unwrap_or_default();
+    trace_var!(planet_constraints);
+    let mut satellite_systems = Vec::new();
+    for protoplanet in protoplanets.iter() {
+      let planet = protoplanet
+        .generate_planet(rng, host_star, &planet_constraints)
+        .map_err(SatelliteSystemError::from)?;
+      if let Some(planet) = planet {
+        let satellite_system = satellite_system_constraints.generate_around_planet(rng, host_star, planet)?;
+        trace_var!(satellite_system);
+        satellite_systems.push(satellite_system);
+      }
+    }
+    let result = SatelliteSystems { satellite_systems };
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+
   /// Generate orbits.
+  ///
+  /// Around a close binary, these are all circumbinary orbits, so nothing is
+  /// placed inside the region the binary keeps clear.
   #[named]
   pub fn generate_orbits<R: Rng + ?Sized>(&self, rng: &mut R, host_star: &HostStar) -> Result<Vec<f64>, Error> {
     trace_enter!();
     trace_var!(host_star);
+    let stability_limit = host_star.get_stability_limit();
+    trace_var!(stability_limit);
+    let maximum_orbit = self.maximum_orbit.unwrap_or(f64::INFINITY);
+    trace_var!(maximum_orbit);
     let mut result = Vec::new();
-    if self.generate_primary_gas_giant {
-      let orbit = rng.gen_range(1.0..1.25) + host_star.get_frost_line();
-      result.push(orbit);
+    if self.generate_primary_gas_giant.unwrap_or(false) {
+      let orbit = rng.gen_range(1.0..1.25) + host_star.get_frost_line().max(stability_limit);
+      if orbit < maximum_orbit {
+        result.push(orbit);
+      }
     }
-    if self.generate_habitable {
+    if self.generate_habitable.unwrap_or(false) {
       let habitable_zone = host_star.get_habitable_zone();
-      let orbit = rng.gen_range(habitable_zone.0..habitable_zone.1);
-      result.push(orbit);
+      let inner_bound = habitable_zone.0.max(stability_limit);
+      let outer_bound = habitable_zone.1.min(maximum_orbit);
+      // If the habitable zone has been cleared out altogether, there's no
+      // stable place to put a habitable planet.
+      if inner_bound < outer_bound {
+        let orbit = rng.gen_range(inner_bound..outer_bound);
+        result.push(orbit);
+      }
     }
-    let satellite_zone = host_star.get_satellite_zone();
+    let satellite_zone = host_star.get_satellite_zones().system;
     trace_var!(satellite_zone);
-    let innermost_orbit = satellite_zone.0;
+    let innermost_orbit = get_approximate_innermost_orbit(host_star.get_stellar_mass());
     trace_var!(innermost_orbit);
-    let outermost_orbit = satellite_zone.1;
+    // With no room for circumbinary orbits, there's nowhere to put planets
+    // beyond the ones we insisted on.
+    let outermost_orbit = satellite_zone.map_or(0.0, |(_, outer)| outer);
     trace_var!(outermost_orbit);
-    let minimum = 40.0 * innermost_orbit;
+    let minimum = (40.0 * innermost_orbit).max(stability_limit);
     trace_var!(minimum);
-    let distance_limit = outermost_orbit;
+    let distance_limit = outermost_orbit.min(maximum_orbit);
     trace_var!(distance_limit);
-    let growth_factor = 0.3;
-    trace_var!(growth_factor);
-    let mut orbital_distance = minimum;
-    let mut index = 0;
-    loop {
-      let min_unwrapped = 0.80 * orbital_distance;
-      let max_unwrapped = 1.25 * orbital_distance;
-      if !result
-        .iter()
-        .any(|&orbit| orbit > min_unwrapped && orbit < max_unwrapped)
+    if self.generate_resonant_chain.unwrap_or(false) {
+      // The chain is built around the habitable planet, if we want one, or
+      // else the primary gas giant, so that it still gets its orbit.
+      let anchor = result.last().copied().unwrap_or(minimum);
+      trace_var!(anchor);
+      let result = match anchor <= distance_limit {
+        true => get_resonant_chain(rng, anchor, minimum, distance_limit, MAXIMUM_RESONANT_CHAIN_LENGTH),
+        false => vec![],
+      };
+      trace_var!(result);
+      trace_exit!();
+      return Ok(result);
+    }
+    let spacing_law = self.spacing_law.unwrap_or_default();
+    trace_var!(spacing_law);
+    let minimum_count = self.minimum_count.unwrap_or(MINIMUM_SATELLITE_SYSTEMS);
+    trace_var!(minimum_count);
+    let maximum_count = self
+      .maximum_count
+      .unwrap_or(MAXIMUM_SATELLITE_SYSTEMS)
+      .max(minimum_count);
+    trace_var!(maximum_count);
+    let count = rng.gen_range(minimum_count..=maximum_count);
+    trace_var!(count);
+    let orbital_distances = spacing_law.get_orbits(rng, minimum, distance_limit, host_star.get_stellar_mass(), count);
+    trace_var!(orbital_distances);
+    for orbital_distance in orbital_distances.into_iter() {
+      let min_unwrapped = (0.80 * orbital_distance).max(stability_limit);
+      let max_unwrapped = (1.25 * orbital_distance).min(maximum_orbit);
+      if min_unwrapped < max_unwrapped
+        && !result
+          .iter()
+          .any(|&orbit| orbit > min_unwrapped && orbit < max_unwrapped)
       {
         let orbit = rng.gen_range(min_unwrapped..max_unwrapped);
         result.push(orbit);
       }
-      orbital_distance = minimum + growth_factor * (2.0_f64).powf(index as f64);
-      index += 1;
-      if orbital_distance > distance_limit {
-        break;
-      }
     }
     result.sort_by(|a, b| a.partial_cmp(&b).unwrap());
     trace_var!(result);
@@ -125,14 +273,40 @@ impl Default for Constraints {
     let minimum_count = None;
     let maximum_count = None;
     let satellite_system_constraints = None;
-    let generate_primary_gas_giant = false;
-    let generate_habitable = false;
+    let generate_primary_gas_giant = None;
+    let generate_habitable = None;
+    let maximum_orbit = None;
+    let generate_resonant_chain = None;
+    let spacing_law = None;
+    let generate_by_accretion = None;
     Self {
       minimum_count,
       maximum_count,
       satellite_system_constraints,
       generate_primary_gas_giant,
       generate_habitable,
+      maximum_orbit,
+      generate_resonant_chain,
+      spacing_law,
+      generate_by_accretion,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_count: overrides.minimum_count.or(self.minimum_count),
+      maximum_count: overrides.maximum_count.or(self.maximum_count),
+      satellite_system_constraints: self
+        .satellite_system_constraints
+        .merged_with(&overrides.satellite_system_constraints),
+      generate_primary_gas_giant: overrides.generate_primary_gas_giant.or(self.generate_primary_gas_giant),
+      generate_habitable: overrides.generate_habitable.or(self.generate_habitable),
+      maximum_orbit: overrides.maximum_orbit.or(self.maximum_orbit),
+      generate_resonant_chain: overrides.generate_resonant_chain.or(self.generate_resonant_chain),
+      spacing_law: overrides.spacing_law.or(self.spacing_law),
+      generate_by_accretion: overrides.generate_by_accretion.or(self.generate_by_accretion),
     }
   }
 }
@@ -142,7 +316,11 @@ pub mod test {
 
   use rand::prelude::*;
 
+  use crate::astronomy::close_binary_star::CloseBinaryStar;
+  use crate::astronomy::gas_giant_planet::constraints::Constraints as GasGiantPlanetConstraints;
   use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+  use crate::astronomy::host_star::error::Error as HostStarError;
+  use crate::astronomy::star::Star;
 
   use super::*;
   use crate::test::*;
@@ -178,4 +356,100 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_circumbinary() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let primary = Star::from_mass(&mut rng, 1.0).map_err(HostStarError::from)?;
+    let secondary = Star::from_mass(&mut rng, 0.5).map_err(HostStarError::from)?;
+    let close_binary_star =
+      CloseBinaryStar::from_stars(&mut rng, primary, secondary, 1.0, 0.3).map_err(HostStarError::from)?;
+    let host_star = HostStar::CloseBinaryStar(close_binary_star);
+    let stability_limit = host_star.get_stability_limit();
+    print_var!(stability_limit);
+    assert!(stability_limit > 2.0 && stability_limit < 4.0);
+    for _ in 0..10 {
+      let orbits = Constraints::habitable().generate_orbits(&mut rng, &host_star)?;
+      print_var!(orbits);
+      assert!(!orbits.is_empty());
+      assert!(orbits.iter().all(|&orbit| orbit >= stability_limit));
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_migration() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = &HostStarConstraints::default().generate(&mut rng)?;
+    trace_var!(host_star);
+    let frost_line = host_star.get_frost_line();
+    let constraints = Constraints {
+      minimum_count: Some(MAXIMUM_SATELLITE_SYSTEMS),
+      satellite_system_constraints: Some(SatelliteSystemConstraints {
+        planet_constraints: Some(PlanetConstraints {
+          gas_giant_planet_constraints: Some(GasGiantPlanetConstraints {
+            migration_probability: Some(Probability::new(0.3).unwrap()),
+            ..GasGiantPlanetConstraints::default()
+          }),
+          ..PlanetConstraints::default()
+        }),
+        ..SatelliteSystemConstraints::default()
+      }),
+      ..Constraints::default()
+    };
+    for _ in 0..10 {
+      let planets = constraints
+        .generate(&mut rng, host_star)?
+        .satellite_systems
+        .into_iter()
+        .map(|satellite_system| satellite_system.planet)
+        .collect::<Vec<_>>();
+      print_var!(planets);
+      let migrated_gas_giant_orbit = planets
+        .iter()
+        .filter(|planet| matches!(planet, Planet::GasGiantPlanet(_)))
+        .map(Planet::get_semi_major_axis)
+        .find(|&semi_major_axis| semi_major_axis < frost_line);
+      if let Some(migrated_gas_giant_orbit) = migrated_gas_giant_orbit {
+        assert!(planets
+          .iter()
+          .filter(|planet| matches!(planet, Planet::TerrestrialPlanet(_)))
+          .map(Planet::get_semi_major_axis)
+          .all(|semi_major_axis| semi_major_axis < migrated_gas_giant_orbit || semi_major_axis >= frost_line));
+      }
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_accretion() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = &HostStarConstraints::habitable().generate(&mut rng)?;
+    trace_var!(host_star);
+    let constraints = Constraints {
+      generate_by_accretion: Some(true),
+      ..Constraints::default()
+    };
+    let satellite_systems = constraints.generate(&mut rng, host_star)?;
+    print_var!(satellite_systems);
+    let semi_major_axes = satellite_systems.get_semi_major_axes();
+    assert!(!semi_major_axes.is_empty());
+    assert!(semi_major_axes.windows(2).all(|pair| pair[0] < pair[1]));
+    trace_exit!();
+    Ok(())
+  }
 }
@@ -0,0 +1,134 @@
+use rand::prelude::*;
+use serde::Serialize;
+
+/// The growth factor of Titius-Bode spacing, in AU.
+///
+/// The classic rule puts the planets at 0.4 + 0.3 × 2ⁿ AU.
+pub const TITIUS_BODE_GROWTH_FACTOR: f64 = 0.3;
+
+/// The mass assumed for each planet when spacing by mutual Hill radii, in
+/// Msol.
+///
+/// We don't know what the planets will be yet; this is about 10 Mearth, a
+/// typical planet in the compact systems Kepler found.
+pub const MUTUAL_HILL_PLANET_MASS: f64 = 3.0E-5;
+
+/// The narrowest spacing of neighboring planets, in mutual Hill radii.
+///
+/// Much closer than this and the system goes unstable within a few billion
+/// years.
+pub const MINIMUM_MUTUAL_HILL_SPACING: f64 = 10.0;
+
+/// The widest spacing of neighboring planets, in mutual Hill radii.
+pub const MAXIMUM_MUTUAL_HILL_SPACING: f64 = 30.0;
+
+/// How the orbits of the planets are spaced out.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
+pub enum SpacingLaw {
+  /// Gaps that double with each planet, as in the Titius-Bode law.
+  #[default]
+  TitiusBode,
+  /// Neighbors a random number of mutual Hill radii apart, as in the
+  /// compact systems Kepler found.
+  MutualHill,
+  /// Scattered evenly in the log of the distance, with no particular order.
+  LogUniform,
+}
+
+impl SpacingLaw {
+  /// Get the nominal orbits of the planets, in AU, sorted from innermost to
+  /// outermost.
+  ///
+  /// These run from the minimum out to the maximum distance; the stellar
+  /// mass is in Msol, and the count is only used where the law doesn't fix
+  /// the number of planets itself.
+  #[named]
+  pub fn get_orbits<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    minimum: f64,
+    maximum: f64,
+    stellar_mass: f64,
+    count: usize,
+  ) -> Vec<f64> {
+    trace_enter!();
+    trace_var!(minimum);
+    trace_var!(maximum);
+    trace_var!(stellar_mass);
+    trace_var!(count);
+    use SpacingLaw::*;
+    let mut result = Vec::new();
+    match self {
+      TitiusBode => {
+        let mut orbit = minimum;
+        let mut index = 0;
+        while orbit <= maximum {
+          result.push(orbit);
+          orbit = minimum + TITIUS_BODE_GROWTH_FACTOR * (2.0_f64).powf(index as f64);
+          index += 1;
+        }
+      },
+      MutualHill => {
+        let hill_fraction = (2.0 * MUTUAL_HILL_PLANET_MASS / (3.0 * stellar_mass)).powf(1.0 / 3.0);
+        trace_var!(hill_fraction);
+        let mut orbit = minimum;
+        while orbit <= maximum {
+          result.push(orbit);
+          let half_spacing =
+            0.5 * hill_fraction * rng.gen_range(MINIMUM_MUTUAL_HILL_SPACING..=MAXIMUM_MUTUAL_HILL_SPACING);
+          // A lone giant star might not leave any room at all.
+          if half_spacing >= 1.0 {
+            break;
+          }
+          orbit *= (1.0 + half_spacing) / (1.0 - half_spacing);
+        }
+      },
+      LogUniform => {
+        if minimum < maximum {
+          for _ in 0..count {
+            result.push(rng.gen_range(minimum.ln()..maximum.ln()).exp());
+          }
+        }
+        result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+      },
+    }
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_orbits() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    // The planets of the Solar System, give or take Neptune.
+    let orbits = SpacingLaw::TitiusBode.get_orbits(&mut rng, 0.4, 40.0, 1.0, 0);
+    print_var!(orbits);
+    assert_eq!(orbits.len(), 9);
+    assert_approx_eq!(orbits[2], 1.0);
+    assert_approx_eq!(orbits[8], 38.8);
+    let orbits = SpacingLaw::MutualHill.get_orbits(&mut rng, 0.05, 1.0, 1.0, 0);
+    print_var!(orbits);
+    for pair in orbits.windows(2) {
+      let ratio = pair[1] / pair[0];
+      assert!(ratio > 1.3 && ratio < 2.5);
+    }
+    let orbits = SpacingLaw::LogUniform.get_orbits(&mut rng, 0.1, 10.0, 1.0, 6);
+    print_var!(orbits);
+    assert_eq!(orbits.len(), 6);
+    assert!(orbits.iter().all(|&orbit| (0.1..10.0).contains(&orbit)));
+    assert!(orbits.windows(2).all(|pair| pair[0] <= pair[1]));
+    trace_exit!();
+  }
+}
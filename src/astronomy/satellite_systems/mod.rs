@@ -1,12 +1,20 @@
+use rand::prelude::*;
+use serde::Serialize;
+
+use crate::astronomy::planet::name::{
+  generate_invented_planet_names, generate_mythic_planet_names, get_systematic_planet_name, PlanetNaming,
+};
 use crate::astronomy::satellite_system::SatelliteSystem;
 
 pub mod constants;
 pub mod constraints;
 pub mod error;
 use error::Error;
+pub(crate) mod math;
+pub use math::spacing::SpacingLaw;
 
 /// The `SatelliteSystems` object wraps a vector of `SatelliteSystem` objects.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct SatelliteSystems {
   /// SatelliteSystem objects.
   pub satellite_systems: Vec<SatelliteSystem>,
@@ -44,4 +52,42 @@ impl SatelliteSystems {
     trace_exit!();
     result
   }
+
+  /// Name the planets, innermost first.
+  #[named]
+  pub fn name_planets<R: Rng + ?Sized>(&mut self, rng: &mut R, star_name: &str, naming: PlanetNaming) {
+    trace_enter!();
+    trace_var!(star_name);
+    trace_var!(naming);
+    let mut order = (0..self.satellite_systems.len()).collect::<Vec<_>>();
+    let semi_major_axes = self.get_semi_major_axes();
+    order.sort_by(|&a, &b| semi_major_axes[a].partial_cmp(&semi_major_axes[b]).unwrap());
+    trace_var!(order);
+    let names = match naming {
+      PlanetNaming::Systematic => (0..order.len())
+        .map(|index| get_systematic_planet_name(star_name, index))
+        .collect(),
+      PlanetNaming::Mythic => generate_mythic_planet_names(rng, star_name, order.len()),
+      PlanetNaming::Invented => generate_invented_planet_names(rng, order.len()),
+    };
+    trace_var!(names);
+    for (index, name) in order.into_iter().zip(names) {
+      self.satellite_systems[index].planet.set_name(name);
+    }
+    trace_exit!();
+  }
+
+  /// Get the semi-major axis of each planet, in AU.
+  #[named]
+  pub fn get_semi_major_axes(&self) -> Vec<f64> {
+    trace_enter!();
+    let result = self
+      .satellite_systems
+      .iter()
+      .map(|satellite_system| satellite_system.planet.get_semi_major_axis())
+      .collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
 }
@@ -23,15 +23,6 @@ pub const MAXIMUM_HABITABLE_MASS: f64 = 1.25;
 /// Measured in Gyr, or billions of years.
 pub const MINIMUM_HABITABLE_AGE: f64 = 4.0;
 
-/// The probability of generating an O-class star.
-pub const CLASS_O_WEIGHT: f64 = 0.00003;
-
-/// The probability of generating a B-class star.
-pub const CLASS_B_WEIGHT: f64 = 0.13;
-
-/// The probability of generating an A-class star.
-pub const CLASS_A_WEIGHT: f64 = 0.6;
-
 /// The probability of generating an F-class star.
 pub const CLASS_F_WEIGHT: f64 = 3.0;
 
@@ -43,3 +34,63 @@ pub const CLASS_K_WEIGHT: f64 = 12.1;
 
 /// The probability of generating an M-class star.
 pub const CLASS_M_WEIGHT: f64 = 76.45;
+
+/// The lightest star that crosses the instability strip as a Cepheid, in
+/// Msol.
+pub const MINIMUM_CEPHEID_MASS: f64 = 4.0;
+
+/// The heaviest star that crosses the instability strip as a Cepheid, in
+/// Msol.
+pub const MAXIMUM_CEPHEID_MASS: f64 = 20.0;
+
+/// The cool edge of the instability strip, in Kelvin.
+pub const INSTABILITY_STRIP_MINIMUM_TEMPERATURE: f64 = 5_000.0;
+
+/// The hot edge of the instability strip, in Kelvin.
+pub const INSTABILITY_STRIP_MAXIMUM_TEMPERATURE: f64 = 7_500.0;
+
+/// The heaviest red giant that can settle onto the horizontal branch as an
+/// RR Lyrae variable, in Msol.
+pub const MAXIMUM_RR_LYRAE_MASS: f64 = 2.0;
+
+/// The probability that a light red giant is caught as an RR Lyrae variable.
+///
+/// Only the part of the horizontal branch that falls within the instability
+/// strip pulsates, and it's a short part of a red giant's life.
+pub const RR_LYRAE_PROBABILITY: f64 = 0.1;
+
+/// The lightest main-sequence star that pulsates as a Delta Scuti variable,
+/// in Msol.
+pub const MINIMUM_DELTA_SCUTI_MASS: f64 = 1.5;
+
+/// The heaviest main-sequence star that pulsates as a Delta Scuti variable,
+/// in Msol.
+pub const MAXIMUM_DELTA_SCUTI_MASS: f64 = 2.5;
+
+/// The probability that a star in the Delta Scuti mass range pulsates.
+pub const DELTA_SCUTI_PROBABILITY: f64 = 0.3;
+
+/// The heaviest star that can be a flare star, in Msol; this is about M0.
+pub const MAXIMUM_FLARE_STAR_MASS: f64 = 0.6;
+
+/// The probability that an M dwarf still in its active phase flares.
+pub const FLARE_STAR_PROBABILITY: f64 = 0.75;
+
+/// The mean metallicity, [Fe/H], of stars in the solar neighborhood, in dex.
+///
+/// The Sun is a little richer in metals than most of its neighbors.
+pub const METALLICITY_MEAN: f64 = -0.1;
+
+/// The spread of metallicity, [Fe/H], in the solar neighborhood, in dex.
+pub const METALLICITY_STANDARD_DEVIATION: f64 = 0.2;
+
+/// The most metal-poor star we'll generate, in dex.
+///
+/// Anything poorer belongs to the halo, not the disk.
+pub const MINIMUM_METALLICITY: f64 = -1.0;
+
+/// The most metal-rich star we'll generate, in dex.
+pub const MAXIMUM_METALLICITY: f64 = 0.5;
+
+/// How many stars to draw, looking for a habitable one, before giving up.
+pub const HABITABLE_STAR_ATTEMPTS: usize = 20;
@@ -0,0 +1,133 @@
+use std::ops::Range;
+
+use crate::astronomy::star::constants::*;
+use crate::astronomy::star::constraints::Constraints;
+use crate::astronomy::star::habitability::HabitabilityTable;
+use crate::astronomy::star::math::habitable_zone::HabitableZoneModel;
+use crate::astronomy::star::math::mass_function::MassFunction;
+use crate::math::range::check_range;
+use crate::math::range::error::Error;
+
+/// A fluent builder for star constraints.
+///
+/// Nothing is checked until `build()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Builder {
+  constraints: Constraints,
+}
+
+impl Builder {
+  /// Restrict the mass, in Msol.
+  pub fn mass_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_mass = Some(range.start);
+    self.constraints.maximum_mass = Some(range.end);
+    self
+  }
+
+  /// Ensure the star is habitable.
+  ///
+  /// The mass range is narrowed to habitable masses, under whichever table
+  /// is in force, when the star is drawn.
+  pub fn habitable(mut self) -> Self {
+    self.constraints.make_habitable = Some(true);
+    self
+  }
+
+  /// Allow the star to have left the main sequence.
+  pub fn allow_evolved(mut self) -> Self {
+    self.constraints.allow_evolved = Some(true);
+    self
+  }
+
+  /// Draw the mass from this initial mass function.
+  pub fn mass_function(mut self, mass_function: MassFunction) -> Self {
+    self.constraints.mass_function = Some(mass_function);
+    self
+  }
+
+  /// Draw metallicity, [Fe/H], from a normal distribution, in dex.
+  pub fn metallicity(mut self, mean: f64, standard_deviation: f64) -> Self {
+    self.constraints.metallicity_mean = Some(mean);
+    self.constraints.metallicity_standard_deviation = Some(standard_deviation);
+    self
+  }
+
+  /// Decide what's habitable by this table.
+  pub fn habitability_table(mut self, habitability_table: &'static HabitabilityTable) -> Self {
+    self.constraints.habitability_table = Some(habitability_table);
+    self
+  }
+
+  /// Draw the habitable zone with this model.
+  pub fn habitable_zone_model(mut self, habitable_zone_model: HabitableZoneModel) -> Self {
+    self.constraints.habitable_zone_model = Some(habitable_zone_model);
+    self
+  }
+
+  /// Check the constraints and return them.
+  #[named]
+  pub fn build(self) -> Result<Constraints, Error> {
+    trace_enter!();
+    let constraints = self.constraints;
+    trace_var!(constraints);
+    check_range(
+      "mass",
+      constraints.minimum_mass,
+      constraints.maximum_mass,
+      (MINIMUM_MASS, MAXIMUM_MASS),
+    )?;
+    if constraints.make_habitable.unwrap_or(false) {
+      // Only the habitable part of the range will be used.
+      let (minimum_mass, maximum_mass) = constraints.get_habitable_mass_span();
+      check_range(
+        "habitable mass",
+        constraints.minimum_mass.map(|mass| mass.max(minimum_mass)),
+        constraints.maximum_mass.map(|mass| mass.min(maximum_mass)),
+        (minimum_mass, maximum_mass),
+      )?;
+    }
+    trace_exit!();
+    Ok(constraints)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::star::error::Error as StarError;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_build() -> Result<(), StarError> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints::builder().mass_range(0.5..1.2).habitable().build().unwrap();
+    print_var!(constraints);
+    assert_eq!(constraints.minimum_mass, Some(0.5));
+    assert_eq!(constraints.maximum_mass, Some(1.2));
+    assert_eq!(constraints.make_habitable, Some(true));
+    let star = constraints.generate(&mut rng)?;
+    print_var!(star);
+    assert!(star.mass >= MINIMUM_HABITABLE_MASS && star.mass <= 1.2);
+    assert_eq!(
+      Constraints::builder().mass_range(1.2..0.5).build(),
+      Err(Error::RangeEmpty("mass"))
+    );
+    assert_eq!(
+      Constraints::builder().mass_range(0.01..1.0).build(),
+      Err(Error::RangeBelowBound("mass"))
+    );
+    assert_eq!(
+      Constraints::builder().mass_range(2.0..3.0).habitable().build(),
+      Err(Error::RangeEmpty("habitable mass"))
+    );
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -3,8 +3,16 @@ use std::default::Default;
 
 use crate::astronomy::star::constants::*;
 use crate::astronomy::star::error::Error;
-use crate::astronomy::star::math::spectral_class::*;
+use crate::astronomy::star::habitability::HabitabilityTable;
+use crate::astronomy::star::math::evolution::MAXIMUM_AGE;
+use crate::astronomy::star::math::habitable_zone::HabitableZoneModel;
+use crate::astronomy::star::math::mass_function::MassFunction;
+use crate::astronomy::star::math::metallicity::get_random_metallicity;
 use crate::astronomy::star::Star;
+use crate::generator::Generator;
+use crate::merge::Merge;
+pub mod builder;
+use builder::Builder;
 
 /// Constraints for creating a main-sequence star.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -14,15 +22,40 @@ pub struct Constraints {
   /// Maximum amount of mass.
   pub maximum_mass: Option<f64>,
   /// Ensure this star is habitable.
-  pub make_habitable: bool,
+  pub make_habitable: Option<bool>,
+  /// Allow the star to have left the main sequence.
+  ///
+  /// Its age may then run past its life expectancy, up to the age of the
+  /// oldest stars.  This is ignored for habitable stars.
+  pub allow_evolved: Option<bool>,
+  /// The initial mass function to draw the mass from.
+  pub mass_function: Option<MassFunction>,
+  /// The mean metallicity, [Fe/H], in dex.
+  pub metallicity_mean: Option<f64>,
+  /// The standard deviation of metallicity, [Fe/H], in dex.
+  pub metallicity_standard_deviation: Option<f64>,
+  /// How the habitable zone is drawn.
+  pub habitable_zone_model: Option<HabitableZoneModel>,
+  /// The habitability policy a habitable star is drawn under.
+  ///
+  /// Without one, habitable stars are drawn from the usual F, G, and K masses
+  /// and checked against the default table.  This is borrowed for good so
+  /// that constraints stay `Copy`; leak a table loaded at startup to use it
+  /// here.
+  pub habitability_table: Option<&'static HabitabilityTable>,
 }
 
 impl Constraints {
+  /// Start building constraints fluently.
+  pub fn builder() -> Builder {
+    Builder::default()
+  }
+
   /// Generate a habitable star.
   pub fn habitable() -> Self {
     let minimum_mass = Some(MINIMUM_HABITABLE_MASS);
     let maximum_mass = Some(MAXIMUM_HABITABLE_MASS);
-    let make_habitable = true;
+    let make_habitable = Some(true);
     Self {
       minimum_mass,
       maximum_mass,
@@ -31,55 +64,170 @@ impl Constraints {
     }
   }
 
+  /// Get the span of masses, in Msol, a habitable star is drawn from.
+  #[named]
+  pub fn get_habitable_mass_span(&self) -> (f64, f64) {
+    trace_enter!();
+    let result = self
+      .habitability_table
+      .map_or((MINIMUM_HABITABLE_MASS, MAXIMUM_HABITABLE_MASS), |habitability_table| {
+        habitability_table.get_habitable_mass_span()
+      });
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Generate.
   #[named]
   pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Star, Error> {
     trace_enter!();
-    let mass = {
-      let random_spectral_class = match self.make_habitable {
-        false => get_random_spectral_class(rng),
-        true => get_random_habitable_spectral_class(rng),
-      };
-      trace_var!(random_spectral_class);
-      let random_range = match self.make_habitable {
-        false => spectral_class_to_mass_range(random_spectral_class),
-        true => spectral_class_to_habitable_mass_range(random_spectral_class),
-      };
-      trace_var!(random_range);
-      let lower_bound_mass = random_range.start;
-      trace_var!(lower_bound_mass);
-      let upper_bound_mass = random_range.end;
-      trace_var!(upper_bound_mass);
-      let mass = rng.gen_range(lower_bound_mass..upper_bound_mass);
-      mass
+    let make_habitable = self.make_habitable.unwrap_or(false);
+    trace_var!(make_habitable);
+    let habitability_table = self.habitability_table.cloned().unwrap_or_default();
+    let habitable_mass_span = self.get_habitable_mass_span();
+    let (minimum_mass, maximum_mass) = match make_habitable {
+      false => (
+        self.minimum_mass.unwrap_or(MINIMUM_MASS),
+        self.maximum_mass.unwrap_or(MAXIMUM_MASS),
+      ),
+      true => (
+        self.minimum_mass.unwrap_or(MINIMUM_MASS).max(habitable_mass_span.0),
+        self.maximum_mass.unwrap_or(MAXIMUM_MASS).min(habitable_mass_span.1),
+      ),
     };
-    trace_var!(mass);
-    let mut result = Star::from_mass(rng, mass)?;
+    trace_var!(minimum_mass);
+    trace_var!(maximum_mass);
+    // If the range we were given doesn't overlap the habitable range, fall
+    // back on the habitable range alone.
+    let (minimum_mass, maximum_mass) = match minimum_mass <= maximum_mass {
+      true => (minimum_mass, maximum_mass),
+      false => habitable_mass_span,
+    };
+    let mut attempts = 0;
+    let result = loop {
+      attempts += 1;
+      let star = self.generate_once(rng, &habitability_table, minimum_mass, maximum_mass)?;
+      // A star of a habitable mass and age may still be flaring, so draw
+      // another until we find one that isn't.
+      let is_acceptable = !make_habitable || star.check_habitable_with(&habitability_table).is_ok();
+      trace_var!(is_acceptable);
+      if is_acceptable || attempts >= HABITABLE_STAR_ATTEMPTS {
+        break star;
+      }
+    };
+    if make_habitable {
+      result.check_habitable_with(&habitability_table)?;
+    }
     trace_var!(result);
-    let minimum_age = match self.make_habitable {
-      true => MINIMUM_HABITABLE_AGE,
-      false => 0.1 * result.life_expectancy,
+    trace_exit!();
+    Ok(result)
+  }
+
+  /// Draw a single star from a mass range, in Msol.
+  #[named]
+  fn generate_once<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    habitability_table: &HabitabilityTable,
+    minimum_mass: f64,
+    maximum_mass: f64,
+  ) -> Result<Star, Error> {
+    trace_enter!();
+    let make_habitable = self.make_habitable.unwrap_or(false);
+    let mass_function = self.mass_function.unwrap_or_default();
+    trace_var!(mass_function);
+    let mass = mass_function.get_random_mass(rng, minimum_mass, maximum_mass);
+    trace_var!(mass);
+    let metallicity_mean = self.metallicity_mean.unwrap_or(METALLICITY_MEAN);
+    trace_var!(metallicity_mean);
+    let metallicity_standard_deviation = self
+      .metallicity_standard_deviation
+      .unwrap_or(METALLICITY_STANDARD_DEVIATION);
+    trace_var!(metallicity_standard_deviation);
+    let metallicity = get_random_metallicity(rng, metallicity_mean, metallicity_standard_deviation);
+    trace_var!(metallicity);
+    let habitable_zone_model = self.habitable_zone_model.unwrap_or_default();
+    trace_var!(habitable_zone_model);
+    let star = Star {
+      metallicity,
+      ..Star::from_mass(rng, mass)?.with_habitable_zone_model(habitable_zone_model)
+    };
+    trace_var!(star);
+    let minimum_age = match make_habitable {
+      true => habitability_table
+        .get_entry_for_mass(mass)
+        .map_or(MINIMUM_HABITABLE_AGE, |entry| entry.minimum_age),
+      false => 0.1 * star.life_expectancy,
     };
     trace_var!(minimum_age);
-    let maximum_age = 0.9 * result.life_expectancy;
+    let maximum_age = match self.allow_evolved.unwrap_or(false) && !make_habitable {
+      true => (0.9 * star.life_expectancy).max(MAXIMUM_AGE),
+      false => 0.9 * star.life_expectancy,
+    };
     trace_var!(maximum_age);
-    result.current_age = rng.gen_range(minimum_age..maximum_age);
+    let current_age = rng.gen_range(minimum_age..maximum_age);
+    trace_var!(current_age);
+    let result = star.at_age(current_age);
     trace_var!(result);
     trace_exit!();
     Ok(result)
   }
 }
 
+impl Generator for Constraints {
+  type Output = Star;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Star, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
 impl Default for Constraints {
   /// No constraints, just let it all hang out.
   fn default() -> Self {
     let minimum_mass = None;
     let maximum_mass = None;
-    let make_habitable = false;
+    let make_habitable = None;
+    let allow_evolved = None;
+    let mass_function = None;
+    let metallicity_mean = None;
+    let metallicity_standard_deviation = None;
+    let habitable_zone_model = None;
+    let habitability_table = None;
     Self {
       minimum_mass,
       maximum_mass,
       make_habitable,
+      allow_evolved,
+      mass_function,
+      metallicity_mean,
+      metallicity_standard_deviation,
+      habitable_zone_model,
+      habitability_table,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+      make_habitable: overrides.make_habitable.or(self.make_habitable),
+      allow_evolved: overrides.allow_evolved.or(self.allow_evolved),
+      mass_function: overrides.mass_function.or(self.mass_function),
+      metallicity_mean: overrides.metallicity_mean.or(self.metallicity_mean),
+      metallicity_standard_deviation: overrides
+        .metallicity_standard_deviation
+        .or(self.metallicity_standard_deviation),
+      habitable_zone_model: overrides.habitable_zone_model.or(self.habitable_zone_model),
+      habitability_table: overrides.habitability_table.or(self.habitability_table),
     }
   }
 }
@@ -90,6 +238,7 @@ pub mod test {
   use rand::prelude::*;
 
   use super::*;
+  use crate::astronomy::star::math::evolution::EvolutionaryStage;
   use crate::test::*;
 
   #[named]
@@ -105,4 +254,83 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_allow_evolved() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints {
+      minimum_mass: Some(2.0),
+      maximum_mass: Some(4.0),
+      allow_evolved: Some(true),
+      ..Constraints::default()
+    };
+    // These burn out in a Gyr or so, so nearly all of them should be dead.
+    let stars = (0..20)
+      .map(|_| constraints.generate(&mut rng))
+      .collect::<Result<Vec<_>, _>>()?;
+    let evolved = stars
+      .iter()
+      .filter(|star| star.stage != EvolutionaryStage::MainSequence)
+      .count();
+    print_var!(evolved);
+    assert!(evolved > 10);
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_metallicity() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints {
+      metallicity_mean: Some(0.3),
+      metallicity_standard_deviation: Some(0.0),
+      ..Constraints::default()
+    };
+    let star = constraints.generate(&mut rng)?;
+    print_var!(star);
+    assert_eq!(star.metallicity, 0.3);
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_habitability_table() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let yaml = "
+entries:
+  - spectral_class: M
+    weight: 1.0
+    minimum_mass: 0.2
+    maximum_mass: 0.45
+    minimum_age: 1.0
+    flare_penalty: 0.5
+";
+    let table: &'static HabitabilityTable = Box::leak(Box::new(serde_yaml::from_str(yaml).unwrap()));
+    let constraints = Constraints::builder()
+      .habitable()
+      .habitability_table(table)
+      .build()
+      .unwrap();
+    for _ in 0..20 {
+      let star = constraints.generate(&mut rng)?;
+      print_var!(star);
+      assert!((0.2..=0.45).contains(&star.mass));
+      assert!(star.current_age >= 1.0);
+      assert_eq!(star.check_habitable_with(table), Ok(()));
+    }
+    trace_exit!();
+    Ok(())
+  }
 }
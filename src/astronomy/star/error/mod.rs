@@ -11,6 +11,10 @@ pub enum Error {
   MassTooLowToSupportLife,
   /// Higher than MAXIMUM_STAR_MASS_TO_SUPPORT_LIFE.
   MassTooHighToSupportLife,
+  /// Of a spectral class whose flares rule out life.
+  TooActiveToSupportLife,
+  /// No longer on the main sequence.
+  EvolvedPastMainSequence,
 }
 
 honeyholt_define_brief!(Error, |error: &Error| {
@@ -21,5 +25,7 @@ honeyholt_define_brief!(Error, |error: &Error| {
     TooYoungToSupportLife => "it is too young to support life".to_string(),
     MassTooLowToSupportLife => "its mass is too low to support life".to_string(),
     MassTooHighToSupportLife => "its mass is too high to support life".to_string(),
+    TooActiveToSupportLife => "it is too active to support life".to_string(),
+    EvolvedPastMainSequence => "it has left the main sequence".to_string(),
   }
 });
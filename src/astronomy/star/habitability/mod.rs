@@ -0,0 +1,341 @@
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::astronomy::star::constants::*;
+use crate::astronomy::star::error::Error;
+use crate::astronomy::star::math::evolution::EvolutionaryStage;
+use crate::astronomy::star::Star;
+
+/// A flare penalty at or above this rules a spectral class out altogether.
+pub const MAXIMUM_FLARE_PENALTY: f64 = 1.0;
+
+/// How much worse an active flare star is than the rest of its spectral
+/// class, added to the class's flare penalty.
+pub const FLARE_STAR_PENALTY: f64 = 0.5;
+
+/// What it takes for a star of one spectral class to support life.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct HabitabilityEntry {
+  /// The spectral class, e.g. 'G'.
+  pub spectral_class: char,
+  /// The relative frequency of the class, used to pick one at random.
+  pub weight: f64,
+  /// The minimum mass, in Msol.
+  pub minimum_mass: f64,
+  /// The maximum mass, in Msol.
+  pub maximum_mass: f64,
+  /// The minimum age, in Gyr.
+  pub minimum_age: f64,
+  /// How much flares and the like detract from habitability, from 0 (not at
+  /// all) to 1 (they strip the atmosphere from anything in the habitable
+  /// zone).
+  pub flare_penalty: f64,
+}
+
+impl Default for HabitabilityEntry {
+  /// What's asked of a star that no entry in a table takes in: it only has
+  /// to be old enough.
+  fn default() -> Self {
+    let spectral_class = '?';
+    let weight = 0.0;
+    let minimum_mass = MINIMUM_MASS;
+    let maximum_mass = MAXIMUM_MASS;
+    let minimum_age = MINIMUM_HABITABLE_AGE;
+    let flare_penalty = 0.0;
+    Self {
+      spectral_class,
+      weight,
+      minimum_mass,
+      maximum_mass,
+      minimum_age,
+      flare_penalty,
+    }
+  }
+}
+
+/// The habitability policy, as a table of entries by spectral class.
+///
+/// The default reproduces the usual assumptions: F, G, and K stars qualify,
+/// and so do M dwarfs, unless they're flaring.  Replace it wholesale
+/// (it can be loaded from YAML or JSON) and hand it to the star constraints
+/// to try something else.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct HabitabilityTable {
+  /// The entries.
+  pub entries: Vec<HabitabilityEntry>,
+}
+
+impl HabitabilityTable {
+  /// Get the entry for a spectral class, if there is one.
+  #[named]
+  pub fn get_entry(&self, spectral_class: char) -> Option<&HabitabilityEntry> {
+    trace_enter!();
+    trace_var!(spectral_class);
+    let result = self
+      .entries
+      .iter()
+      .find(|entry| entry.spectral_class.eq_ignore_ascii_case(&spectral_class));
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the entry whose mass band takes in a mass, in Msol, if there is one.
+  ///
+  /// Spectral classes are assigned by temperature, so a star near the edge of
+  /// a band may carry the letter of its neighbor; the band is what counts.
+  #[named]
+  pub fn get_entry_for_mass(&self, mass: f64) -> Option<&HabitabilityEntry> {
+    trace_enter!();
+    trace_var!(mass);
+    let result = self
+      .entries
+      .iter()
+      .find(|entry| (entry.minimum_mass..=entry.maximum_mass).contains(&mass));
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the entries for spectral classes that can support life at all.
+  #[named]
+  pub fn get_habitable_entries(&self) -> Vec<&HabitabilityEntry> {
+    trace_enter!();
+    let result = self
+      .entries
+      .iter()
+      .filter(|entry| entry.flare_penalty < MAXIMUM_FLARE_PENALTY)
+      .collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the habitable mass range of a spectral class, in Msol.
+  #[named]
+  pub fn get_habitable_mass_range(&self, spectral_class: char) -> Option<Range<f64>> {
+    trace_enter!();
+    trace_var!(spectral_class);
+    let result = self
+      .get_entry(spectral_class)
+      .map(|entry| entry.minimum_mass..entry.maximum_mass);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get a (weighted) random habitable spectral class.
+  #[named]
+  pub fn get_random_spectral_class<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+    trace_enter!();
+    let entries = self.get_habitable_entries();
+    let dist = WeightedIndex::new(entries.iter().map(|entry| entry.weight)).unwrap();
+    let result = entries[dist.sample(rng)].spectral_class;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the lightest and heaviest masses, in Msol, of any class that can
+  /// support life at all.
+  #[named]
+  pub fn get_habitable_mass_span(&self) -> (f64, f64) {
+    trace_enter!();
+    let entries = self.get_habitable_entries();
+    let minimum_mass = entries
+      .iter()
+      .map(|entry| entry.minimum_mass)
+      .fold(f64::INFINITY, f64::min);
+    trace_var!(minimum_mass);
+    let maximum_mass = entries.iter().map(|entry| entry.maximum_mass).fold(0.0, f64::max);
+    trace_var!(maximum_mass);
+    let result = (minimum_mass, maximum_mass);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether a star is capable of supporting conventional life
+  /// under this policy.
+  ///
+  /// A star lighter or heavier than any habitable class fails on its mass,
+  /// as it always has; within that span, the entry for its mass has the
+  /// final word.
+  #[named]
+  pub fn check_star(&self, star: &Star) -> Result<(), Error> {
+    trace_enter!();
+    if star.stage != EvolutionaryStage::MainSequence {
+      return Err(Error::EvolvedPastMainSequence);
+    }
+    let (minimum_mass, maximum_mass) = self.get_habitable_mass_span();
+    if star.mass < minimum_mass {
+      return Err(Error::MassTooLowToSupportLife);
+    }
+    if star.mass > maximum_mass {
+      return Err(Error::MassTooHighToSupportLife);
+    }
+    // A table with a gap between its bands has nothing to say about a star
+    // that falls into it.
+    let entry = self.get_entry_for_mass(star.mass).copied().unwrap_or_default();
+    trace_var!(entry);
+    if entry.flare_penalty >= MAXIMUM_FLARE_PENALTY {
+      return Err(Error::TooActiveToSupportLife);
+    }
+    // A class that's merely marginal can still be ruled out by a star that's
+    // actually flaring.
+    let is_flare_star = star.variability.is_some_and(|variability| variability.is_flare_star());
+    trace_var!(is_flare_star);
+    if is_flare_star && entry.flare_penalty + FLARE_STAR_PENALTY >= MAXIMUM_FLARE_PENALTY {
+      return Err(Error::TooActiveToSupportLife);
+    }
+    if star.current_age < entry.minimum_age {
+      return Err(Error::TooYoungToSupportLife);
+    }
+    trace_exit!();
+    Ok(())
+  }
+}
+
+impl Default for HabitabilityTable {
+  /// The usual assumptions.
+  fn default() -> Self {
+    let entry = |spectral_class, weight, minimum_mass, maximum_mass, flare_penalty| HabitabilityEntry {
+      spectral_class,
+      weight,
+      minimum_mass,
+      maximum_mass,
+      minimum_age: MINIMUM_HABITABLE_AGE,
+      flare_penalty,
+    };
+    let entries = vec![
+      entry('F', CLASS_F_WEIGHT, 1.04, MAXIMUM_HABITABLE_MASS, 0.0),
+      entry('G', CLASS_G_WEIGHT, 0.8, 1.04, 0.0),
+      entry('K', CLASS_K_WEIGHT, 0.45, 0.8, 0.1),
+      entry('M', CLASS_M_WEIGHT, MINIMUM_MASS, 0.45, 0.6),
+    ];
+    Self { entries }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::star::variability::{Variability, VariabilityKind};
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_default() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let table = HabitabilityTable::default();
+    assert_eq!(table.get_habitable_mass_range('g'), Some(0.8..1.04));
+    assert_eq!(table.get_habitable_mass_range('O'), None);
+    assert_eq!(
+      table.get_entry_for_mass(0.82).map(|entry| entry.spectral_class),
+      Some('G')
+    );
+    assert_eq!(table.get_entry_for_mass(50.0), None);
+    assert_eq!(table.get_habitable_mass_span(), (MINIMUM_MASS, MAXIMUM_HABITABLE_MASS));
+    for _ in 0..20 {
+      assert!(['F', 'G', 'K', 'M'].contains(&table.get_random_spectral_class(&mut rng)));
+    }
+    let mut star = Star::from_mass(&mut rng, 1.0)?;
+    star.current_age = 5.0;
+    assert_eq!(table.check_star(&star), Ok(()));
+    star.current_age = 1.0;
+    assert_eq!(table.check_star(&star), Err(Error::TooYoungToSupportLife));
+    let mut dwarf = Star::from_mass(&mut rng, 0.3)?;
+    dwarf.current_age = 5.0;
+    dwarf.variability = None;
+    assert_eq!(table.check_star(&dwarf), Ok(()));
+    dwarf.variability = Some(Variability {
+      kind: VariabilityKind::FlareStar,
+      period: 2.0,
+      amplitude: 0.5,
+    });
+    assert_eq!(table.check_star(&dwarf), Err(Error::TooActiveToSupportLife));
+    // A K dwarf is quiet enough to get away with the odd flare.
+    let mut k_dwarf = Star::from_mass(&mut rng, 0.6)?;
+    k_dwarf.current_age = 5.0;
+    k_dwarf.variability = dwarf.variability;
+    assert_eq!(table.check_star(&k_dwarf), Ok(()));
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_habitable_masses() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let table = HabitabilityTable::default();
+    let mut mass = MINIMUM_HABITABLE_MASS;
+    while mass <= MAXIMUM_HABITABLE_MASS {
+      let mut star = Star::from_mass(&mut rng, mass)?;
+      star.current_age = MINIMUM_HABITABLE_AGE;
+      assert_eq!(table.check_star(&star), Ok(()), "{} Msol ({})", mass, star.class);
+      mass += 0.005;
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_replaced() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let yaml = "
+entries:
+  - spectral_class: M
+    weight: 1.0
+    minimum_mass: 0.2
+    maximum_mass: 0.45
+    minimum_age: 1.0
+    flare_penalty: 0.5
+";
+    let table: HabitabilityTable = serde_yaml::from_str(yaml).unwrap();
+    print_var!(table);
+    let mut dwarf = Star::from_mass(&mut rng, 0.3)?;
+    dwarf.current_age = 2.0;
+    dwarf.variability = None;
+    assert_eq!(dwarf.check_habitable_with(&table), Ok(()));
+    assert!(!dwarf.is_habitable());
+    dwarf.variability = Some(Variability {
+      kind: VariabilityKind::FlareStar,
+      period: 2.0,
+      amplitude: 0.5,
+    });
+    assert_eq!(dwarf.check_habitable_with(&table), Err(Error::TooActiveToSupportLife));
+    let mut sun = Star::from_mass(&mut rng, 1.0)?;
+    sun.current_age = 5.0;
+    assert_eq!(sun.check_habitable_with(&table), Err(Error::MassTooHighToSupportLife));
+    // Leave a gap between two bands, and a star in it is held only to the
+    // usual age.
+    let mut table = table;
+    table.entries.push(HabitabilityEntry {
+      spectral_class: 'G',
+      minimum_mass: 0.9,
+      maximum_mass: 1.1,
+      ..HabitabilityEntry::default()
+    });
+    let mut k_dwarf = Star::from_mass(&mut rng, 0.7)?;
+    k_dwarf.current_age = 5.0;
+    assert_eq!(k_dwarf.check_habitable_with(&table), Ok(()));
+    k_dwarf.current_age = 1.0;
+    assert_eq!(k_dwarf.check_habitable_with(&table), Err(Error::TooYoungToSupportLife));
+    trace_exit!();
+    Ok(())
+  }
+}
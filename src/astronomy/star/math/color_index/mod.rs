@@ -0,0 +1,122 @@
+/// The bluest B-V color index a main-sequence star can manage.
+///
+/// The approximation below keeps getting bluer as temperature climbs, but
+/// real O stars top out at around this value.
+pub const MINIMUM_B_V_COLOR_INDEX: f64 = -0.33;
+
+/// B-V and U-B color indices for main-sequence stars, after Schmidt-Kaler.
+pub const MAIN_SEQUENCE_COLOR_INDICES: &[(f64, f64)] = &[
+  (-0.33, -1.19),
+  (-0.30, -1.08),
+  (-0.24, -0.84),
+  (-0.17, -0.58),
+  (-0.11, -0.34),
+  (-0.02, -0.02),
+  (0.05, 0.05),
+  (0.15, 0.10),
+  (0.30, 0.03),
+  (0.44, -0.02),
+  (0.58, 0.06),
+  (0.68, 0.20),
+  (0.81, 0.45),
+  (0.91, 0.64),
+  (1.15, 1.08),
+  (1.40, 1.22),
+  (1.49, 1.18),
+  (1.64, 1.24),
+];
+
+/// Get the B-V color index of a star based on its temperature (in Kelvin).
+///
+/// This inverts Ballesteros' formula, which is good for most of the main
+/// sequence but overshoots for the very hottest stars.
+#[named]
+pub fn star_temperature_to_b_v_color_index(temperature: f64) -> f64 {
+  trace_enter!();
+  trace_var!(temperature);
+  let k = temperature / 4600.0;
+  trace_var!(k);
+  let b = 2.32 * k - 2.0;
+  trace_var!(b);
+  let c = 1.054 * k - 2.32;
+  trace_var!(c);
+  let y = (-b + (b.powf(2.0) - 4.0 * k * c).sqrt()) / (2.0 * k);
+  trace_var!(y);
+  let result = (y / 0.92).max(MINIMUM_B_V_COLOR_INDEX);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the U-B color index of a main-sequence star based on its B-V index.
+#[named]
+pub fn b_v_color_index_to_u_b_color_index(b_v: f64) -> f64 {
+  trace_enter!();
+  trace_var!(b_v);
+  let first = MAIN_SEQUENCE_COLOR_INDICES[0];
+  let last = MAIN_SEQUENCE_COLOR_INDICES[MAIN_SEQUENCE_COLOR_INDICES.len() - 1];
+  let result = if b_v <= first.0 {
+    first.1
+  } else if b_v >= last.0 {
+    last.1
+  } else {
+    let upper = MAIN_SEQUENCE_COLOR_INDICES
+      .iter()
+      .position(|&(index, _)| index >= b_v)
+      .unwrap();
+    let (x0, y0) = MAIN_SEQUENCE_COLOR_INDICES[upper - 1];
+    let (x1, y1) = MAIN_SEQUENCE_COLOR_INDICES[upper];
+    y0 + (y1 - y0) * (b_v - x0) / (x1 - x0)
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_star_temperature_to_b_v_color_index() {
+    init();
+    trace_enter!();
+    // Jolly ol' Sol
+    let mut temperature = 5776.0;
+    let mut expected = 0.651;
+    let mut actual = star_temperature_to_b_v_color_index(temperature);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    // M1V
+    temperature = 3407.0;
+    expected = 1.787;
+    actual = star_temperature_to_b_v_color_index(temperature);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    // O8V
+    temperature = 41970.0;
+    expected = MINIMUM_B_V_COLOR_INDEX;
+    actual = star_temperature_to_b_v_color_index(temperature);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_b_v_color_index_to_u_b_color_index() {
+    init();
+    trace_enter!();
+    // Jolly ol' Sol
+    let mut b_v = 0.65;
+    let mut expected = 0.158;
+    let mut actual = b_v_color_index_to_u_b_color_index(b_v);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    // Off the red end of the table.
+    b_v = 1.80;
+    expected = 1.24;
+    actual = b_v_color_index_to_u_b_color_index(b_v);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    trace_exit!();
+  }
+}
@@ -1,4 +1,4 @@
-pub const METERS_PER_SOLAR_RADIUS: f64 = 149_597_870_700.0;
+pub const METERS_PER_SOLAR_RADIUS: f64 = 695_700_000.0;
 pub const METERS_PER_AU: f64 = 1.496E11;
 
 /// Rsol -> M
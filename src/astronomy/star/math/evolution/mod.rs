@@ -0,0 +1,306 @@
+use serde::Serialize;
+
+/// The effective temperature of the Sun, in Kelvin.
+pub const SOLAR_TEMPERATURE: f64 = 5_772.0;
+
+/// How much fainter a star is at the start of its main-sequence life than it
+/// is partway through, as a fraction of the latter.
+///
+/// The young Sun was about 30% fainter than it is today (Gough 1981).
+pub const MAIN_SEQUENCE_BRIGHTENING: f64 = 0.4;
+
+/// How far through its main-sequence life the Sun is.
+///
+/// The mass-luminosity relation is calibrated on stars like the Sun, so we
+/// take it to give the luminosity at this point.
+pub const SOLAR_MAIN_SEQUENCE_PROGRESS: f64 = 0.46;
+
+/// How long a star spends as a subgiant, as a fraction of its main-sequence
+/// lifetime.
+pub const SUBGIANT_DURATION: f64 = 0.1;
+
+/// How long a star spends as a red giant, as a fraction of its main-sequence
+/// lifetime.
+///
+/// The Sun will spend about a billion years climbing the red giant branch.
+pub const RED_GIANT_DURATION: f64 = 0.1;
+
+/// How much brighter a star gets over the course of its subgiant phase.
+pub const SUBGIANT_LUMINOSITY_GAIN: f64 = 2.0;
+
+/// How much brighter a star gets from the base to the tip of the red giant
+/// branch.
+///
+/// The Sun will reach a couple of thousand Lsol.
+pub const RED_GIANT_LUMINOSITY_GAIN: f64 = 1_000.0;
+
+/// The temperature at the base of the red giant branch, in Kelvin.
+pub const RED_GIANT_BASE_TEMPERATURE: f64 = 5_000.0;
+
+/// The temperature at the tip of the red giant branch, in Kelvin.
+pub const RED_GIANT_TIP_TEMPERATURE: f64 = 3_200.0;
+
+/// The most massive star that ends its life as a white dwarf, in Msol.
+///
+/// Anything heavier goes out with a bang; we leave those as giants.
+pub const MAXIMUM_WHITE_DWARF_PROGENITOR_MASS: f64 = 8.0;
+
+/// The most massive a white dwarf can be, in Msol.
+pub const CHANDRASEKHAR_LIMIT: f64 = 1.4;
+
+/// The radius of a white dwarf of one Msol, in Rsol.
+pub const WHITE_DWARF_RADIUS: f64 = 0.0126;
+
+/// The luminosity of a white dwarf of one Msol that's been cooling for one
+/// Gyr, in Lsol.
+pub const WHITE_DWARF_LUMINOSITY: f64 = 2.0E-3;
+
+/// The youngest cooling age we'll calculate a white dwarf's luminosity for,
+/// in Gyr; the cooling law blows up at zero.
+pub const MINIMUM_COOLING_AGE: f64 = 0.001;
+
+/// The oldest a star can be, in Gyr.
+///
+/// The oldest stars in the galaxy are about thirteen billion years old.
+pub const MAXIMUM_AGE: f64 = 13.0;
+
+/// Where a star is in its life.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
+pub enum EvolutionaryStage {
+  /// Fusing hydrogen in its core; the vast majority of its life.
+  #[default]
+  MainSequence,
+  /// Its core is spent, and it's fusing hydrogen in a shell around it.
+  Subgiant,
+  /// Swollen and cool, with a shell burning ever more fiercely.
+  RedGiant,
+  /// The cooling, Earth-sized core left behind once the envelope is lost.
+  WhiteDwarf,
+}
+
+impl EvolutionaryStage {
+  /// Get the luminosity class used in the spectral classification.
+  #[named]
+  pub fn get_luminosity_class(&self, mass: f64) -> &'static str {
+    trace_enter!();
+    trace_var!(mass);
+    use EvolutionaryStage::*;
+    let result = match self {
+      MainSequence => "V",
+      Subgiant => "IV",
+      RedGiant if mass > MAXIMUM_WHITE_DWARF_PROGENITOR_MASS => "I",
+      RedGiant => "III",
+      WhiteDwarf => "D",
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+/// Work out the evolutionary stage of a star, and how far through that stage
+/// it is (from 0.0 to 1.0).
+///
+/// Mass is in Msol; life expectancy and age are in Gyr.  Stars too massive
+/// to leave a white dwarf stay at the end of their giant phase.  White
+/// dwarfs have no end, so their progress is always 0.0.
+#[named]
+pub fn get_evolutionary_stage(mass: f64, life_expectancy: f64, age: f64) -> (EvolutionaryStage, f64) {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(life_expectancy);
+  trace_var!(age);
+  use EvolutionaryStage::*;
+  let fraction = age / life_expectancy;
+  trace_var!(fraction);
+  let giant_end = 1.0 + SUBGIANT_DURATION + RED_GIANT_DURATION;
+  trace_var!(giant_end);
+  let result = match fraction {
+    fraction if fraction < 1.0 => (MainSequence, fraction),
+    fraction if fraction < 1.0 + SUBGIANT_DURATION => (Subgiant, (fraction - 1.0) / SUBGIANT_DURATION),
+    fraction if fraction < giant_end => (RedGiant, (fraction - 1.0 - SUBGIANT_DURATION) / RED_GIANT_DURATION),
+    _ if mass > MAXIMUM_WHITE_DWARF_PROGENITOR_MASS => (RedGiant, 1.0),
+    _ => (WhiteDwarf, 0.0),
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the luminosity of a main-sequence star, in Lsol, partway through
+/// its main-sequence life, from the luminosity the mass-luminosity relation
+/// gives it, also in Lsol.
+///
+/// Helium piles up in the core as the star burns, so the core contracts and
+/// heats up, and the star slowly brightens.
+#[named]
+pub fn get_main_sequence_luminosity(luminosity: f64, progress: f64) -> f64 {
+  trace_enter!();
+  trace_var!(luminosity);
+  trace_var!(progress);
+  let result = luminosity / (1.0 + MAIN_SEQUENCE_BRIGHTENING * (1.0 - progress / SOLAR_MAIN_SEQUENCE_PROGRESS));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the luminosity (in Lsol) and temperature (in Kelvin) of a star
+/// partway through its subgiant phase, from its main-sequence values.
+#[named]
+pub fn get_subgiant_luminosity_and_temperature(luminosity: f64, temperature: f64, progress: f64) -> (f64, f64) {
+  trace_enter!();
+  trace_var!(luminosity);
+  trace_var!(temperature);
+  trace_var!(progress);
+  let base_temperature = temperature.min(RED_GIANT_BASE_TEMPERATURE);
+  trace_var!(base_temperature);
+  let result = (
+    luminosity * (1.0 + (SUBGIANT_LUMINOSITY_GAIN - 1.0) * progress),
+    temperature + (base_temperature - temperature) * progress,
+  );
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the luminosity (in Lsol) and temperature (in Kelvin) of a star
+/// partway up the red giant branch, from its main-sequence values.
+#[named]
+pub fn get_red_giant_luminosity_and_temperature(luminosity: f64, temperature: f64, progress: f64) -> (f64, f64) {
+  trace_enter!();
+  trace_var!(luminosity);
+  trace_var!(temperature);
+  trace_var!(progress);
+  let base_temperature = temperature.min(RED_GIANT_BASE_TEMPERATURE);
+  trace_var!(base_temperature);
+  let tip_temperature = base_temperature.min(RED_GIANT_TIP_TEMPERATURE);
+  trace_var!(tip_temperature);
+  let result = (
+    luminosity * SUBGIANT_LUMINOSITY_GAIN * RED_GIANT_LUMINOSITY_GAIN.powf(progress),
+    base_temperature + (tip_temperature - base_temperature) * progress,
+  );
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the mass of the white dwarf a star leaves behind, in Msol.
+///
+/// This is Kalirai's linear initial-final mass relation.
+#[named]
+pub fn get_white_dwarf_mass(initial_mass: f64) -> f64 {
+  trace_enter!();
+  trace_var!(initial_mass);
+  let result = (0.109 * initial_mass + 0.394).min(CHANDRASEKHAR_LIMIT);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the radius of a white dwarf, in Rsol, from its mass in Msol.
+///
+/// Degenerate matter shrinks as more is piled on.
+#[named]
+pub fn get_white_dwarf_radius(mass: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  let result = WHITE_DWARF_RADIUS * mass.powf(-1.0 / 3.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the luminosity of a white dwarf, in Lsol, from its mass in Msol
+/// and how long it's been cooling, in Gyr.
+///
+/// This is Mestel's cooling law.
+#[named]
+pub fn get_white_dwarf_luminosity(mass: f64, cooling_age: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(cooling_age);
+  let result = WHITE_DWARF_LUMINOSITY * mass * cooling_age.max(MINIMUM_COOLING_AGE).powf(-1.4);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the radius of a star, in Rsol, from its luminosity in Lsol and
+/// its effective temperature in Kelvin.
+#[named]
+pub fn get_radius_from_luminosity_and_temperature(luminosity: f64, temperature: f64) -> f64 {
+  trace_enter!();
+  trace_var!(luminosity);
+  trace_var!(temperature);
+  let result = luminosity.sqrt() * (SOLAR_TEMPERATURE / temperature).powf(2.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the effective temperature of a star, in Kelvin, from its
+/// luminosity in Lsol and its radius in Rsol.
+#[named]
+pub fn get_temperature_from_luminosity_and_radius(luminosity: f64, radius: f64) -> f64 {
+  trace_enter!();
+  trace_var!(luminosity);
+  trace_var!(radius);
+  let result = SOLAR_TEMPERATURE * (luminosity / radius.powf(2.0)).powf(0.25);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_sun() {
+    init();
+    trace_enter!();
+    use EvolutionaryStage::*;
+    assert_eq!(get_evolutionary_stage(1.0, 10.0, 4.6).0, MainSequence);
+    assert_eq!(get_evolutionary_stage(1.0, 10.0, 10.5).0, Subgiant);
+    assert_eq!(get_evolutionary_stage(1.0, 10.0, 11.5).0, RedGiant);
+    assert_eq!(get_evolutionary_stage(1.0, 10.0, 13.0).0, WhiteDwarf);
+    assert_eq!(get_evolutionary_stage(10.0, 0.03, 13.0), (RedGiant, 1.0));
+    let (luminosity, temperature) = get_red_giant_luminosity_and_temperature(1.0, 5_772.0, 1.0);
+    let radius = get_radius_from_luminosity_and_temperature(luminosity, temperature);
+    print_var!(luminosity);
+    print_var!(radius);
+    assert!(radius > 100.0 && radius < 300.0);
+    assert_approx_eq!(get_radius_from_luminosity_and_temperature(1.0, SOLAR_TEMPERATURE), 1.0);
+    assert_approx_eq!(get_temperature_from_luminosity_and_radius(1.0, 1.0), SOLAR_TEMPERATURE);
+    let mass = get_white_dwarf_mass(1.0);
+    print_var!(mass);
+    assert_approx_eq!(mass, 0.5, 0.05);
+    // The Sun was about 70% as bright at birth, and will be about 1.9 times as
+    // bright as it leaves the main sequence.
+    assert_approx_eq!(get_main_sequence_luminosity(1.0, 0.0), 0.714, 0.01);
+    assert_approx_eq!(get_main_sequence_luminosity(1.0, SOLAR_MAIN_SEQUENCE_PROGRESS), 1.0);
+    assert_approx_eq!(get_main_sequence_luminosity(1.0, 1.0), 1.88, 0.01);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_sirius_b() {
+    init();
+    trace_enter!();
+    // Sirius B is about 1.0 Msol, and has been cooling for about 120 Myr.
+    let radius = get_white_dwarf_radius(1.02);
+    print_var!(radius);
+    assert_approx_eq!(radius, 0.0084, 0.005);
+    let luminosity = get_white_dwarf_luminosity(1.02, 0.12);
+    print_var!(luminosity);
+    assert_approx_eq!(luminosity, 0.056, 0.03);
+    let temperature = get_temperature_from_luminosity_and_radius(luminosity, radius);
+    print_var!(temperature);
+    assert_approx_eq!(temperature, 25_000.0, 5_000.0);
+    trace_exit!();
+  }
+}
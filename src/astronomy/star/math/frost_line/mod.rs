@@ -1,4 +1,7 @@
 /// Get the frost line of a star (in AU) based on its luminosity (in Lsol).
+///
+/// This doesn't need a `Star`, so it works just as well with stellar data
+/// from elsewhere.
 #[named]
 pub fn star_luminosity_to_frost_line(luminosity: f64) -> f64 {
   trace_enter!();
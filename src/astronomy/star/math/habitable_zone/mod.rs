@@ -1,10 +1,152 @@
-/// Get the habitable zone of a star (in AU) based on its luminosity (in Lsol).
+use serde::Serialize;
+
+/// How the boundaries of a habitable zone are drawn.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
+pub enum HabitableZoneModel {
+  /// Fixed limits on the light a planet receives: 1.1 times what Earth gets
+  /// at the inner edge, and 0.53 times at the outer.
+  ///
+  /// This doesn't care about the temperature of the star.
+  #[default]
+  Classic,
+  /// Kopparapu et al.'s (2014) conservative limits, from the runaway
+  /// greenhouse to the maximum greenhouse.
+  ///
+  /// Cooler stars put out more of their light in the infrared, which warms
+  /// a planet more efficiently, so their habitable zones sit at lower fluxes.
+  ConservativeKopparapu,
+  /// Kopparapu et al.'s (2014) optimistic limits, from "recent Venus" to
+  /// "early Mars", which were arguably habitable at one time.
+  OptimisticKopparapu,
+}
+
+/// Coefficients of Kopparapu et al.'s (2014) fits for an Earth-mass planet:
+/// the flux around the Sun, then the coefficients of the polynomial in the
+/// difference from the Sun's effective temperature.
+pub const RECENT_VENUS: [f64; 5] = [1.776, 2.136E-4, 2.533E-8, -1.332E-11, -3.097E-15];
+
+/// See `RECENT_VENUS`.
+pub const RUNAWAY_GREENHOUSE: [f64; 5] = [1.107, 1.332E-4, 1.580E-8, -8.308E-12, -1.931E-15];
+
+/// See `RECENT_VENUS`.
+pub const MAXIMUM_GREENHOUSE: [f64; 5] = [0.356, 6.171E-5, 1.698E-9, -3.198E-12, -5.575E-16];
+
+/// See `RECENT_VENUS`.
+pub const EARLY_MARS: [f64; 5] = [0.320, 5.547E-5, 1.526E-9, -2.874E-12, -5.011E-16];
+
+/// The range of effective temperatures, in Kelvin, that the Kopparapu fits
+/// cover; we hold temperatures outside it to the nearest end.
+pub const KOPPARAPU_TEMPERATURE_RANGE: (f64, f64) = (2_600.0, 7_200.0);
+
+/// Evaluate one of the Kopparapu flux fits at an effective temperature, in
+/// Kelvin.
+#[named]
+pub fn get_kopparapu_flux(coefficients: [f64; 5], effective_temperature: f64) -> f64 {
+  trace_enter!();
+  trace_var!(effective_temperature);
+  let temperature = effective_temperature.clamp(KOPPARAPU_TEMPERATURE_RANGE.0, KOPPARAPU_TEMPERATURE_RANGE.1) - 5_780.0;
+  trace_var!(temperature);
+  let result = coefficients
+    .iter()
+    .enumerate()
+    .map(|(power, coefficient)| coefficient * temperature.powi(power as i32))
+    .sum();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the stellar flux at the inner and outer edges of the habitable zone,
+/// relative to what Earth receives from the Sun.
+///
+/// Effective temperature is in Kelvin.
 #[named]
-pub fn star_luminosity_to_habitable_zone(luminosity: f64) -> (f64, f64) {
+pub fn get_habitable_zone_fluxes(effective_temperature: f64, model: HabitableZoneModel) -> (f64, f64) {
+  trace_enter!();
+  trace_var!(effective_temperature);
+  trace_var!(model);
+  use HabitableZoneModel::*;
+  let result = match model {
+    Classic => (1.1, 0.53),
+    ConservativeKopparapu => (
+      get_kopparapu_flux(RUNAWAY_GREENHOUSE, effective_temperature),
+      get_kopparapu_flux(MAXIMUM_GREENHOUSE, effective_temperature),
+    ),
+    OptimisticKopparapu => (
+      get_kopparapu_flux(RECENT_VENUS, effective_temperature),
+      get_kopparapu_flux(EARLY_MARS, effective_temperature),
+    ),
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the habitable zone (in AU) of anything shining with a luminosity (in
+/// Lsol) and an effective temperature (in Kelvin).
+///
+/// This doesn't need a `Star`, so it works just as well with stellar data
+/// from elsewhere.
+#[named]
+pub fn get_habitable_zone(luminosity: f64, effective_temperature: f64, model: HabitableZoneModel) -> (f64, f64) {
   trace_enter!();
   trace_var!(luminosity);
-  let result = ((luminosity / 1.1).sqrt(), (luminosity / 0.53).sqrt());
+  trace_var!(effective_temperature);
+  trace_var!(model);
+  let (inner_flux, outer_flux) = get_habitable_zone_fluxes(effective_temperature, model);
+  trace_var!(inner_flux);
+  trace_var!(outer_flux);
+  let result = ((luminosity / inner_flux).sqrt(), (luminosity / outer_flux).sqrt());
   trace_var!(result);
   trace_exit!();
   result
 }
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_habitable_zone() {
+    init();
+    trace_enter!();
+    let (inner, outer) = get_habitable_zone(1.0, 5_772.0, HabitableZoneModel::Classic);
+    print_var!((inner, outer));
+    assert!(inner < 1.0 && 1.0 < outer);
+    assert_approx_eq!(inner, 0.953, 0.001);
+    assert_approx_eq!(outer, 1.374, 0.001);
+    // Four times as bright pushes everything out twice as far.
+    let (bright_inner, bright_outer) = get_habitable_zone(4.0, 5_772.0, HabitableZoneModel::Classic);
+    assert_approx_eq!(bright_inner, 2.0 * inner);
+    assert_approx_eq!(bright_outer, 2.0 * outer);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_kopparapu() {
+    init();
+    trace_enter!();
+    use HabitableZoneModel::*;
+    let conservative = get_habitable_zone(1.0, 5_780.0, ConservativeKopparapu);
+    print_var!(conservative);
+    assert_approx_eq!(conservative.0, 0.950, 0.001);
+    assert_approx_eq!(conservative.1, 1.676, 0.001);
+    let optimistic = get_habitable_zone(1.0, 5_780.0, OptimisticKopparapu);
+    print_var!(optimistic);
+    assert!(optimistic.0 < conservative.0 && conservative.1 < optimistic.1);
+    // A cool star's habitable zone sits further out than a hotter star's of
+    // the same luminosity.
+    let cool = get_habitable_zone(1.0, 3_000.0, ConservativeKopparapu);
+    print_var!(cool);
+    assert!(cool.0 > conservative.0 && cool.1 > conservative.1);
+    assert_eq!(
+      get_habitable_zone(1.0, 2_000.0, ConservativeKopparapu),
+      get_habitable_zone(1.0, 2_600.0, ConservativeKopparapu)
+    );
+    trace_exit!();
+  }
+}
@@ -0,0 +1,162 @@
+/// The absolute bolometric magnitude of the Sun.
+pub const SOLAR_ABSOLUTE_BOLOMETRIC_MAGNITUDE: f64 = 4.74;
+
+/// The number of light years in a parsec.
+pub const LIGHT_YEARS_PER_PARSEC: f64 = 3.261_563_8;
+
+/// Ly -> Pc
+pub fn light_years_to_parsecs(light_years: f64) -> f64 {
+  light_years / LIGHT_YEARS_PER_PARSEC
+}
+
+/// Get the absolute bolometric magnitude of a star based on its luminosity (in Lsol).
+#[named]
+pub fn star_luminosity_to_absolute_magnitude(luminosity: f64) -> f64 {
+  trace_enter!();
+  trace_var!(luminosity);
+  let result = SOLAR_ABSOLUTE_BOLOMETRIC_MAGNITUDE - 2.5 * luminosity.log10();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the bolometric correction of a star based on its temperature (in Kelvin).
+///
+/// This is Flower's polynomial fit, with the coefficients as corrected by
+/// Torres.  Subtract it from a bolometric magnitude to get a visual
+/// magnitude.
+#[named]
+pub fn star_temperature_to_bolometric_correction(temperature: f64) -> f64 {
+  trace_enter!();
+  trace_var!(temperature);
+  let log_temperature = temperature.log10();
+  trace_var!(log_temperature);
+  let coefficients: &[f64] = match log_temperature {
+    log_temperature if log_temperature < 3.70 => &[
+      -0.190537291496456E5,
+      0.155144866764412E5,
+      -0.421278819301717E4,
+      0.381476328422343E3,
+    ],
+    log_temperature if log_temperature < 3.90 => &[
+      -0.370510203809015E5,
+      0.385672629965804E5,
+      -0.150651486316025E5,
+      0.261724637119416E4,
+      -0.170623810323864E3,
+    ],
+    _ => &[
+      -0.118115450538963E6,
+      0.137145973583929E6,
+      -0.636233812100225E5,
+      0.147412923562646E5,
+      -0.170587278406872E4,
+      0.788731721804990E2,
+    ],
+  };
+  let result = coefficients
+    .iter()
+    .rev()
+    .fold(0.0, |sum, coefficient| sum * log_temperature + coefficient);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the absolute visual magnitude of a star based on its luminosity (in
+/// Lsol) and temperature (in Kelvin).
+#[named]
+pub fn star_luminosity_to_absolute_visual_magnitude(luminosity: f64, temperature: f64) -> f64 {
+  trace_enter!();
+  trace_var!(luminosity);
+  trace_var!(temperature);
+  let absolute_magnitude = star_luminosity_to_absolute_magnitude(luminosity);
+  trace_var!(absolute_magnitude);
+  let bolometric_correction = star_temperature_to_bolometric_correction(temperature);
+  trace_var!(bolometric_correction);
+  let result = absolute_magnitude - bolometric_correction;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the apparent magnitude of an object from its absolute magnitude and
+/// its distance (in Ly).
+#[named]
+pub fn absolute_magnitude_to_apparent_magnitude(absolute_magnitude: f64, distance: f64) -> f64 {
+  trace_enter!();
+  trace_var!(absolute_magnitude);
+  trace_var!(distance);
+  let parsecs = light_years_to_parsecs(distance);
+  trace_var!(parsecs);
+  let result = absolute_magnitude + 5.0 * parsecs.log10() - 5.0;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_star_luminosity_to_absolute_magnitude() {
+    init();
+    trace_enter!();
+    // Jolly ol' Sol
+    let mut luminosity = 1.0;
+    let mut expected = 4.74;
+    let mut actual = star_luminosity_to_absolute_magnitude(luminosity);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    // Sirius A, more or less.
+    luminosity = 25.4;
+    expected = 1.228;
+    actual = star_luminosity_to_absolute_magnitude(luminosity);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_star_temperature_to_bolometric_correction() {
+    init();
+    trace_enter!();
+    // Jolly ol' Sol
+    let mut temperature = 5776.0;
+    let mut expected = -0.080;
+    let mut actual = star_temperature_to_bolometric_correction(temperature);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    // M1V
+    temperature = 3407.0;
+    expected = -2.656;
+    actual = star_temperature_to_bolometric_correction(temperature);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    // B5V
+    temperature = 21428.0;
+    expected = -2.046;
+    actual = star_temperature_to_bolometric_correction(temperature);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_absolute_magnitude_to_apparent_magnitude() {
+    init();
+    trace_enter!();
+    // At ten parsecs, apparent and absolute magnitude coincide.
+    let mut distance = 10.0 * LIGHT_YEARS_PER_PARSEC;
+    let mut expected = 4.74;
+    let mut actual = absolute_magnitude_to_apparent_magnitude(4.74, distance);
+    assert_approx_eq!(expected, actual, 1e-3f64);
+    // Alpha Centauri A, roughly.
+    distance = 4.37;
+    expected = 0.015;
+    actual = absolute_magnitude_to_apparent_magnitude(4.38, distance);
+    assert_approx_eq!(expected, actual, 1e-2f64);
+    trace_exit!();
+  }
+}
@@ -0,0 +1,149 @@
+use rand::prelude::*;
+use serde::Serialize;
+
+/// How many steps to take across the mass range when sampling.
+const SAMPLE_STEPS: usize = 200;
+
+/// An initial mass function, giving how many stars form at each mass.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
+pub enum MassFunction {
+  /// A single power law, dN/dm ∝ m^-2.35 (Salpeter 1955).
+  ///
+  /// This was fitted to stars above about a solar mass, and overproduces
+  /// the lightest M dwarfs.
+  Salpeter,
+  /// A broken power law, dN/dm ∝ m^-1.3 below 0.5 Msol and m^-2.3 above
+  /// (Kroupa 2001).
+  #[default]
+  Kroupa,
+  /// A lognormal below a solar mass, peaking around 0.08 Msol, with a
+  /// Salpeter tail above (Chabrier 2003).
+  Chabrier,
+}
+
+impl MassFunction {
+  /// Get the relative number of stars formed per unit of log mass, dN/dlog m,
+  /// at a given mass, in Msol.
+  #[named]
+  pub fn get_relative_frequency(&self, mass: f64) -> f64 {
+    trace_enter!();
+    trace_var!(mass);
+    use MassFunction::*;
+    let result = match self {
+      Salpeter => mass.powf(-1.35),
+      Kroupa => match mass {
+        mass if mass < 0.5 => mass.powf(-0.3),
+        _ => 0.5 * mass.powf(-1.3),
+      },
+      Chabrier => match mass {
+        mass if mass <= 1.0 => {
+          0.158 * (-(mass.log10() - 0.079_f64.log10()).powf(2.0) / (2.0 * 0.69_f64.powf(2.0))).exp()
+        },
+        _ => 4.43E-2 * mass.powf(-1.3),
+      },
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get a random mass, in Msol, between the given bounds, also in Msol.
+  ///
+  /// The cumulative distribution is worked out numerically across the range
+  /// in log mass, then inverted.
+  #[named]
+  pub fn get_random_mass<R: Rng + ?Sized>(&self, rng: &mut R, minimum_mass: f64, maximum_mass: f64) -> f64 {
+    trace_enter!();
+    trace_var!(minimum_mass);
+    trace_var!(maximum_mass);
+    if minimum_mass >= maximum_mass {
+      trace_exit!();
+      return minimum_mass;
+    }
+    let minimum_log_mass = minimum_mass.log10();
+    trace_var!(minimum_log_mass);
+    let step = (maximum_mass.log10() - minimum_log_mass) / SAMPLE_STEPS as f64;
+    trace_var!(step);
+    let log_masses = (0..=SAMPLE_STEPS)
+      .map(|index| minimum_log_mass + step * index as f64)
+      .collect::<Vec<_>>();
+    let frequencies = log_masses
+      .iter()
+      .map(|log_mass| self.get_relative_frequency(10.0_f64.powf(*log_mass)))
+      .collect::<Vec<_>>();
+    let mut cumulative = vec![0.0; SAMPLE_STEPS + 1];
+    for index in 1..=SAMPLE_STEPS {
+      cumulative[index] = cumulative[index - 1] + 0.5 * (frequencies[index - 1] + frequencies[index]) * step;
+    }
+    let target = rng.gen_range(0.0..cumulative[SAMPLE_STEPS]);
+    trace_var!(target);
+    let index = cumulative
+      .partition_point(|&value| value < target)
+      .clamp(1, SAMPLE_STEPS);
+    trace_var!(index);
+    let fraction = (target - cumulative[index - 1]) / (cumulative[index] - cumulative[index - 1]);
+    trace_var!(fraction);
+    let log_mass = log_masses[index - 1] + fraction * step;
+    let result = 10.0_f64.powf(log_mass).clamp(minimum_mass, maximum_mass);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_relative_frequency() {
+    init();
+    trace_enter!();
+    use MassFunction::*;
+    // The pieces of each function should meet where they're joined.
+    assert_approx_eq!(
+      Kroupa.get_relative_frequency(0.5 - 1.0E-9),
+      Kroupa.get_relative_frequency(0.5),
+      1.0E-6
+    );
+    assert_approx_eq!(
+      Chabrier.get_relative_frequency(1.0),
+      Chabrier.get_relative_frequency(1.0 + 1.0E-9),
+      1.0E-3
+    );
+    // Salpeter makes far more of the lightest stars than the others do.
+    let salpeter = Salpeter.get_relative_frequency(0.1) / Salpeter.get_relative_frequency(1.0);
+    let kroupa = Kroupa.get_relative_frequency(0.1) / Kroupa.get_relative_frequency(1.0);
+    print_var!(salpeter);
+    print_var!(kroupa);
+    assert!(salpeter > kroupa);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_random_mass() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    for mass_function in [MassFunction::Salpeter, MassFunction::Kroupa, MassFunction::Chabrier] {
+      let masses = (0..1_000)
+        .map(|_| mass_function.get_random_mass(&mut rng, 0.075, 120.0))
+        .collect::<Vec<_>>();
+      assert!(masses.iter().all(|mass| (0.075..=120.0).contains(mass)));
+      // Whatever the details, most stars are M dwarfs.
+      let m_dwarfs = masses.iter().filter(|&&mass| mass < 0.45).count();
+      print_var!(mass_function);
+      print_var!(m_dwarfs);
+      assert!(m_dwarfs > 600);
+    }
+    assert_eq!(MassFunction::Kroupa.get_random_mass(&mut rng, 1.0, 1.0), 1.0);
+    trace_exit!();
+  }
+}
@@ -0,0 +1,78 @@
+use rand::prelude::*;
+use rand_distr::Normal;
+
+use crate::astronomy::star::constants::{MAXIMUM_METALLICITY, MINIMUM_METALLICITY};
+
+/// Draw a random metallicity, [Fe/H], in dex, from a normal distribution.
+///
+/// The result is clamped to the range we know how to deal with.
+#[named]
+pub fn get_random_metallicity<R: Rng + ?Sized>(rng: &mut R, mean: f64, standard_deviation: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mean);
+  trace_var!(standard_deviation);
+  let normal = Normal::new(mean, standard_deviation.max(0.0)).unwrap();
+  let result = normal.sample(rng).clamp(MINIMUM_METALLICITY, MAXIMUM_METALLICITY);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate how much more likely a star of a given metallicity, [Fe/H], in
+/// dex, is to host giant planets than the Sun.
+///
+/// Giant planet occurrence goes as the square of the iron abundance
+/// (Fischer & Valenti 2005); a star with twice the Sun's iron is about four
+/// times as likely to have a Jupiter.
+#[named]
+pub fn get_giant_planet_occurrence_factor(metallicity: f64) -> f64 {
+  trace_enter!();
+  trace_var!(metallicity);
+  let result = 10.0_f64.powf(2.0 * metallicity);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_random_metallicity() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let metallicities = (0..1_000)
+      .map(|_| get_random_metallicity(&mut rng, -0.1, 0.2))
+      .collect::<Vec<_>>();
+    let mean = metallicities.iter().sum::<f64>() / metallicities.len() as f64;
+    print_var!(mean);
+    assert_approx_eq!(mean, -0.1, 0.05);
+    assert!(metallicities
+      .iter()
+      .all(|&metallicity| (MINIMUM_METALLICITY..=MAXIMUM_METALLICITY).contains(&metallicity)));
+    assert_eq!(get_random_metallicity(&mut rng, 0.2, 0.0), 0.2);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_giant_planet_occurrence_factor() {
+    init();
+    trace_enter!();
+    assert_approx_eq!(get_giant_planet_occurrence_factor(0.0), 1.0);
+    // Twice the Sun's iron.
+    let factor = get_giant_planet_occurrence_factor(2.0_f64.log10());
+    print_var!(factor);
+    assert_approx_eq!(factor, 4.0, 1.0E-6);
+    assert_approx_eq!(get_giant_planet_occurrence_factor(-0.5), 0.1);
+    trace_exit!();
+  }
+}
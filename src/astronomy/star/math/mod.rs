@@ -1,10 +1,16 @@
 pub mod color;
+pub mod color_index;
 pub mod distance;
+pub mod evolution;
 pub mod frost_line;
 pub mod habitable_zone;
 pub mod luminosity;
+pub mod magnitude;
 pub mod mass;
+pub mod mass_function;
+pub mod metallicity;
 pub mod radius;
 pub mod satellite_zone;
 pub mod spectral_class;
 pub mod temperature;
+pub mod variability;
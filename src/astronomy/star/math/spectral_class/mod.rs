@@ -1,45 +1,9 @@
-use rand::distributions::WeightedIndex;
-use rand::prelude::*;
 use std::ops::Range;
 
 use crate::astronomy::star::constants::*;
 use crate::astronomy::star::error::Error;
 use crate::astronomy::star::math::temperature::star_mass_to_temperature;
 
-/// Get a (weighted) random spectral class.
-#[named]
-pub fn get_random_spectral_class<R: Rng + ?Sized>(rng: &mut R) -> char {
-  trace_enter!();
-  let choices = ['O', 'B', 'A', 'F', 'G', 'K', 'M'];
-  let weights = [
-    CLASS_O_WEIGHT,
-    CLASS_B_WEIGHT,
-    CLASS_A_WEIGHT,
-    CLASS_F_WEIGHT,
-    CLASS_G_WEIGHT,
-    CLASS_K_WEIGHT,
-    CLASS_M_WEIGHT,
-  ];
-  let dist = WeightedIndex::new(&weights).unwrap();
-  let result = choices[dist.sample(rng)];
-  trace_var!(result);
-  trace_exit!();
-  result
-}
-
-/// Get a (weighted) random habitable spectral class.
-#[named]
-pub fn get_random_habitable_spectral_class<R: Rng + ?Sized>(rng: &mut R) -> char {
-  trace_enter!();
-  let choices = ['F', 'G', 'K'];
-  let weights = [CLASS_F_WEIGHT, CLASS_G_WEIGHT, CLASS_K_WEIGHT];
-  let dist = WeightedIndex::new(&weights).unwrap();
-  let result = choices[dist.sample(rng)];
-  trace_var!(result);
-  trace_exit!();
-  result
-}
-
 /// Get a mass range from a specified spectral class.
 #[named]
 pub fn spectral_class_to_mass_range(char: char) -> Range<f64> {
@@ -60,22 +24,6 @@ pub fn spectral_class_to_mass_range(char: char) -> Range<f64> {
   result
 }
 
-/// Get a mass range from a specified spectral class.
-#[named]
-pub fn spectral_class_to_habitable_mass_range(char: char) -> Range<f64> {
-  trace_enter!();
-  trace_var!(char);
-  let result = match char {
-    'f' | 'F' => 1.04..MAXIMUM_HABITABLE_MASS,
-    'g' | 'G' => 0.8..1.04,
-    'k' | 'K' => MINIMUM_HABITABLE_MASS..0.8,
-    _ => unreachable!(),
-  };
-  trace_var!(result);
-  trace_exit!();
-  result
-}
-
 /// Get the spectral class of a main-sequence star in Kelvin based on its Msol.
 #[named]
 pub fn star_mass_to_spectral_class(mass: f64) -> Result<String, Error> {
@@ -88,6 +36,23 @@ pub fn star_mass_to_spectral_class(mass: f64) -> Result<String, Error> {
     return Err(Error::MassTooHighForMainSequence);
   }
   let temperature = star_mass_to_temperature(mass)?;
+  trace_var!(temperature);
+  let result = star_temperature_to_spectral_class(temperature, "V");
+  trace_var!(result);
+  trace_exit!();
+  Ok(result)
+}
+
+/// Get the spectral class of a star based on its effective temperature (in
+/// Kelvin) and its luminosity class, e.g. "III" for a giant.
+///
+/// Temperatures beyond the hottest O-class stars are treated as O0.
+#[named]
+pub fn star_temperature_to_spectral_class(temperature: f64, luminosity_class: &str) -> String {
+  trace_enter!();
+  trace_var!(temperature);
+  trace_var!(luminosity_class);
+  let temperature = temperature.clamp(2_000.0, 95_000.0 - 1.0);
   let spectral_type = match temperature {
     temperature if temperature < 3_700.0 => 'M',
     temperature if temperature < 5_200.0 => 'K',
@@ -108,8 +73,8 @@ pub fn star_mass_to_spectral_class(mass: f64) -> Result<String, Error> {
     temperature if temperature < 95_000.0 => (10.0 * (1.0 - ((temperature - 33_000.0) / 62_000.0))),
     _ => unreachable!(),
   };
-  let result = format!("{}{}V", spectral_type, format!("{:.0}", decile));
+  let result = format!("{}{}{}", spectral_type, format!("{:.0}", decile), luminosity_class);
   trace_var!(result);
   trace_exit!();
-  Ok(result)
+  result
 }
@@ -0,0 +1,55 @@
+/// Calculate the pulsation period of a classical Cepheid, in days, from its
+/// luminosity, in Lsol.
+///
+/// This inverts the Leavitt law, M_V = -2.43 (log P - 1) - 4.05, with the
+/// bolometric correction taken to be zero, as it nearly is for yellow
+/// supergiants.
+#[named]
+pub fn get_cepheid_period(luminosity: f64) -> f64 {
+  trace_enter!();
+  trace_var!(luminosity);
+  let result = 10.0_f64.powf((luminosity.log10() - 2.544) / 0.972);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate how long an M dwarf stays magnetically active, in Gyr, from its
+/// mass, in Msol.
+///
+/// Early M dwarfs settle down after a billion years or two; fully
+/// convective late ones stay active for most of the age of the galaxy (West
+/// et al. 2008).
+#[named]
+pub fn get_activity_lifetime(mass: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  let result = match mass {
+    mass if mass >= 0.35 => 2.0,
+    _ => 8.0,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_delta_cephei() {
+    init();
+    trace_enter!();
+    // Delta Cephei itself shines at about 2,000 Lsol with a period of 5.37
+    // days.
+    let period = get_cepheid_period(2_000.0);
+    print_var!(period);
+    assert_approx_eq!(period, 5.37, 1.5);
+    assert!(get_activity_lifetime(0.1) > get_activity_lifetime(0.5));
+    trace_exit!();
+  }
+}
@@ -1,32 +1,54 @@
 use rand::prelude::*;
+use serde::Serialize;
 
 pub mod constants;
-use constants::*;
+use constants::{METALLICITY_MEAN, METALLICITY_STANDARD_DEVIATION};
 pub mod constraints;
 pub mod error;
 use error::*;
-pub mod math;
-use math::color::star_mass_to_rgb;
+pub mod habitability;
+use habitability::HabitabilityTable;
+pub(crate) mod math;
+pub use math::color::temperature_to_rgb;
+use math::color_index::{b_v_color_index_to_u_b_color_index, star_temperature_to_b_v_color_index};
+use math::distance::{meters_to_au, rsol_to_meters};
+pub use math::evolution::EvolutionaryStage;
+use math::evolution::*;
+pub use math::frost_line::star_luminosity_to_frost_line;
+pub use math::habitable_zone::{get_habitable_zone, get_habitable_zone_fluxes, HabitableZoneModel};
 use math::luminosity::star_mass_to_luminosity;
+use math::magnitude::{
+  absolute_magnitude_to_apparent_magnitude, star_luminosity_to_absolute_magnitude,
+  star_luminosity_to_absolute_visual_magnitude, star_temperature_to_bolometric_correction,
+};
+pub use math::mass_function::MassFunction;
+use math::metallicity::get_random_metallicity;
 use math::radius::star_mass_to_radius;
 use math::satellite_zone::{get_approximate_innermost_orbit, get_approximate_outermost_orbit};
-use math::spectral_class::star_mass_to_spectral_class;
+use math::spectral_class::{star_mass_to_spectral_class, star_temperature_to_spectral_class};
 use math::temperature::star_mass_to_temperature;
 pub mod name;
 use name::generate_star_name;
+pub mod variability;
+use variability::Variability;
 
 /// The `Star` type.
 ///
 /// This is intended to encompass the most useful information we can generate
 /// about main-sequence stars.  Other types will use different structs; it's
 /// useful to view and treat these as the default sense of "star", given their
-/// centrality to our purpose.
-#[derive(Clone, Debug, PartialEq)]
+/// centrality to our purpose.  Stars that have outlived the main sequence are
+/// still stars, though, so giants and white dwarfs are covered too.
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Star {
   /// Type, Decile, Luminosity class.
   pub class: String,
+  /// Where the star is in its life.
+  pub stage: EvolutionaryStage,
   /// Measured in Msol.
   pub mass: f64,
+  /// The mass the star was born with, before it shed any, in Msol.
+  pub initial_mass: f64,
   /// Measured in Kelvin.
   pub temperature: f64,
   /// Measured in Rsol.
@@ -39,8 +61,12 @@ pub struct Star {
   pub current_age: f64,
   /// Measured in Dsol.
   pub density: f64,
+  /// Metallicity, [Fe/H], measured in dex.
+  pub metallicity: f64,
   /// Habitable zone, measured in AU.
   pub habitable_zone: (f64, f64),
+  /// How the habitable zone is drawn.
+  pub habitable_zone_model: HabitableZoneModel,
   /// Minimum and maximum sustainable distance for satellites, measured in AU.
   /// This is inferior to computing the Roche limit and Hill sphere, but we
   /// don't have enough information for that yet.
@@ -49,8 +75,16 @@ pub struct Star {
   pub frost_line: f64,
   /// The absolute color of this star in SRGB.
   pub absolute_rgb: (u8, u8, u8),
+  /// The absolute visual magnitude (M_V) of this star.
+  pub absolute_visual_magnitude: f64,
+  /// The B-V color index of this star.
+  pub b_v_color_index: f64,
+  /// The U-B color index of this star.
+  pub u_b_color_index: f64,
   /// A generated name for this star.
   pub name: String,
+  /// How, if at all, this star varies in brightness.
+  pub variability: Option<Variability>,
 }
 
 /// Implementation of Star.
@@ -78,54 +112,210 @@ impl Star {
     trace_var!(current_age);
     let density = mass / radius.powf(3.0);
     trace_var!(density);
-    let habitable_zone = ((luminosity / 1.1).sqrt(), (luminosity / 0.53).sqrt());
+    let metallicity = get_random_metallicity(rng, METALLICITY_MEAN, METALLICITY_STANDARD_DEVIATION);
+    trace_var!(metallicity);
+    let habitable_zone = get_habitable_zone(luminosity, temperature, HabitableZoneModel::Classic);
     trace_var!(habitable_zone);
     let satellite_inner_bound = get_approximate_innermost_orbit(mass);
     trace_var!(satellite_inner_bound);
     let satellite_outer_bound = get_approximate_outermost_orbit(mass);
     trace_var!(satellite_outer_bound);
     let satellite_zone = (satellite_inner_bound, satellite_outer_bound);
-    let frost_line = 4.85 * luminosity.sqrt();
+    let frost_line = star_luminosity_to_frost_line(luminosity);
     trace_var!(frost_line);
-    let absolute_rgb = star_mass_to_rgb(mass)?;
+    let absolute_rgb = temperature_to_rgb(temperature);
     trace_3u8!(absolute_rgb);
+    let absolute_visual_magnitude = star_luminosity_to_absolute_visual_magnitude(luminosity, temperature);
+    trace_var!(absolute_visual_magnitude);
+    let b_v_color_index = star_temperature_to_b_v_color_index(temperature);
+    trace_var!(b_v_color_index);
+    let u_b_color_index = b_v_color_index_to_u_b_color_index(b_v_color_index);
+    trace_var!(u_b_color_index);
     let name = generate_star_name(rng);
     trace_var!(name);
-    let result = Star {
+    let stage = EvolutionaryStage::MainSequence;
+    trace_var!(stage);
+    let mut result = Star {
       class,
+      stage,
       mass,
+      initial_mass: mass,
       luminosity,
       radius,
       temperature,
       life_expectancy,
       current_age,
       density,
+      metallicity,
       habitable_zone,
+      habitable_zone_model: HabitableZoneModel::Classic,
       satellite_zone,
       frost_line,
       absolute_rgb,
+      absolute_visual_magnitude,
+      b_v_color_index,
+      u_b_color_index,
       name,
+      variability: None,
     };
+    result.variability = Variability::from_star(&result);
+    let result = result.at_age(current_age);
     trace_var!(result);
     trace_exit!();
     Ok(result)
   }
 
+  /// Evolve this star forward by the given time, in Gyr.
+  ///
+  /// A negative time takes it back instead, though not past its birth.
+  #[named]
+  pub fn evolve(&self, delta: f64) -> Self {
+    trace_enter!();
+    trace_var!(delta);
+    let result = self.clone().at_age((self.current_age + delta).max(0.0));
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Redraw this star's habitable zone with another model.
+  #[named]
+  pub fn with_habitable_zone_model(self, habitable_zone_model: HabitableZoneModel) -> Self {
+    trace_enter!();
+    trace_var!(habitable_zone_model);
+    let habitable_zone = get_habitable_zone(self.luminosity, self.temperature, habitable_zone_model);
+    trace_var!(habitable_zone);
+    let result = Self {
+      habitable_zone,
+      habitable_zone_model,
+      ..self
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Age this star to the given age, in Gyr.
+  ///
+  /// Everything is worked out afresh from the star's initial mass, so this
+  /// can go backward as well as forward.  On the main sequence the star
+  /// slowly brightens; once it's outlived its main-sequence lifetime, it
+  /// becomes a subgiant, a red giant, or a white dwarf, and everything that
+  /// depends upon its size and brightness is recalculated.
+  #[named]
+  pub fn at_age(self, age: f64) -> Self {
+    trace_enter!();
+    trace_var!(age);
+    let (stage, progress) = get_evolutionary_stage(self.initial_mass, self.life_expectancy, age);
+    trace_var!(stage);
+    trace_var!(progress);
+    // The initial mass was checked when the star was made, so these are safe.
+    let main_sequence_luminosity = star_mass_to_luminosity(self.initial_mass).unwrap();
+    trace_var!(main_sequence_luminosity);
+    let main_sequence_radius = star_mass_to_radius(self.initial_mass).unwrap();
+    trace_var!(main_sequence_radius);
+    let main_sequence_temperature = star_mass_to_temperature(self.initial_mass).unwrap();
+    trace_var!(main_sequence_temperature);
+    use EvolutionaryStage::*;
+    let (mass, luminosity, radius, temperature) = match stage {
+      MainSequence => {
+        let luminosity = get_main_sequence_luminosity(main_sequence_luminosity, progress);
+        // It swells as it brightens, staying about the same temperature.
+        let radius = main_sequence_radius * (luminosity / main_sequence_luminosity).sqrt();
+        (self.initial_mass, luminosity, radius, main_sequence_temperature)
+      },
+      Subgiant | RedGiant => {
+        // These pick up where the main sequence left off.
+        let luminosity = get_main_sequence_luminosity(main_sequence_luminosity, 1.0);
+        let (luminosity, temperature) = match stage {
+          Subgiant => get_subgiant_luminosity_and_temperature(luminosity, main_sequence_temperature, progress),
+          _ => get_red_giant_luminosity_and_temperature(luminosity, main_sequence_temperature, progress),
+        };
+        let radius = get_radius_from_luminosity_and_temperature(luminosity, temperature);
+        (self.initial_mass, luminosity, radius, temperature)
+      },
+      WhiteDwarf => {
+        let mass = get_white_dwarf_mass(self.initial_mass);
+        let cooling_age = age - self.life_expectancy * (1.0 + SUBGIANT_DURATION + RED_GIANT_DURATION);
+        let luminosity = get_white_dwarf_luminosity(mass, cooling_age);
+        let radius = get_white_dwarf_radius(mass);
+        let temperature = get_temperature_from_luminosity_and_radius(luminosity, radius);
+        (mass, luminosity, radius, temperature)
+      },
+    };
+    trace_var!(mass);
+    trace_var!(luminosity);
+    trace_var!(radius);
+    trace_var!(temperature);
+    let class = match stage {
+      // White dwarfs are classified by temperature alone.
+      WhiteDwarf => format!("DA{:.0}", (50_400.0 / temperature).min(9.0)),
+      _ => star_temperature_to_spectral_class(temperature, stage.get_luminosity_class(mass)),
+    };
+    trace_var!(class);
+    let density = mass / radius.powf(3.0);
+    trace_var!(density);
+    let habitable_zone = get_habitable_zone(luminosity, temperature, self.habitable_zone_model);
+    trace_var!(habitable_zone);
+    // Nothing can orbit inside the star itself.
+    let satellite_zone = (
+      get_approximate_innermost_orbit(mass).max(meters_to_au(rsol_to_meters(radius))),
+      get_approximate_outermost_orbit(mass),
+    );
+    trace_var!(satellite_zone);
+    let frost_line = star_luminosity_to_frost_line(luminosity);
+    trace_var!(frost_line);
+    let absolute_rgb = temperature_to_rgb(temperature);
+    trace_3u8!(absolute_rgb);
+    let absolute_visual_magnitude = star_luminosity_to_absolute_visual_magnitude(luminosity, temperature);
+    trace_var!(absolute_visual_magnitude);
+    let b_v_color_index = star_temperature_to_b_v_color_index(temperature);
+    trace_var!(b_v_color_index);
+    let u_b_color_index = b_v_color_index_to_u_b_color_index(b_v_color_index);
+    trace_var!(u_b_color_index);
+    let mut result = Self {
+      class,
+      stage,
+      mass,
+      temperature,
+      radius,
+      luminosity,
+      current_age: age,
+      density,
+      habitable_zone,
+      satellite_zone,
+      frost_line,
+      absolute_rgb,
+      absolute_visual_magnitude,
+      b_v_color_index,
+      u_b_color_index,
+      ..self
+    };
+    result.variability = Variability::from_star(&result);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Indicate whether this star is capable of supporting conventional life.
   #[named]
   pub fn check_habitable(&self) -> Result<(), Error> {
     trace_enter!();
-    if self.mass < MINIMUM_HABITABLE_MASS {
-      return Err(Error::MassTooLowToSupportLife);
-    }
-    if self.mass > MAXIMUM_HABITABLE_MASS {
-      return Err(Error::MassTooHighToSupportLife);
-    }
-    if self.current_age < MINIMUM_HABITABLE_AGE {
-      return Err(Error::TooYoungToSupportLife);
-    }
+    let result = self.check_habitable_with(&HabitabilityTable::default());
+    trace_var!(result);
     trace_exit!();
-    Ok(())
+    result
+  }
+
+  /// Indicate whether this star is capable of supporting conventional life,
+  /// under a particular habitability policy.
+  #[named]
+  pub fn check_habitable_with(&self, habitability_table: &HabitabilityTable) -> Result<(), Error> {
+    trace_enter!();
+    let result = habitability_table.check_star(self);
+    trace_var!(result);
+    trace_exit!();
+    result
   }
 
   /// Indicate whether this star is capable of supporting conventional life.
@@ -140,6 +330,41 @@ impl Star {
     trace_exit!();
     result
   }
+
+  /// Calculate the absolute bolometric magnitude, from the luminosity.
+  #[named]
+  pub fn get_absolute_magnitude(&self) -> f64 {
+    trace_enter!();
+    let result = star_luminosity_to_absolute_magnitude(self.luminosity);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Calculate the bolometric correction, from the temperature.
+  ///
+  /// This is the gap between the bolometric and the visual magnitude; it's
+  /// largest for hot stars and cool stars, which put most of their light
+  /// outside the visible band.
+  #[named]
+  pub fn get_bolometric_correction(&self) -> f64 {
+    trace_enter!();
+    let result = star_temperature_to_bolometric_correction(self.temperature);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Calculate the apparent visual magnitude at a distance (in Ly).
+  #[named]
+  pub fn get_apparent_magnitude_at(&self, distance: f64) -> f64 {
+    trace_enter!();
+    trace_var!(distance);
+    let result = absolute_magnitude_to_apparent_magnitude(self.absolute_visual_magnitude, distance);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
 }
 
 #[cfg(test)]
@@ -149,6 +374,7 @@ pub mod test {
 
   use super::constraints::Constraints;
   use super::*;
+  use crate::astronomy::star::math::magnitude::LIGHT_YEARS_PER_PARSEC;
   use crate::test::*;
 
   #[named]
@@ -164,4 +390,92 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_at_age() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let star = Star::from_mass(&mut rng, 1.0)?;
+    let life_expectancy = star.life_expectancy;
+    let young = star.clone().at_age(0.1 * life_expectancy);
+    let main_sequence = star.clone().at_age(0.5 * life_expectancy);
+    assert_eq!(main_sequence.stage, EvolutionaryStage::MainSequence);
+    assert!(main_sequence.luminosity > young.luminosity);
+    assert!(main_sequence.habitable_zone.1 > young.habitable_zone.1);
+    assert_eq!(main_sequence.mass, young.mass);
+    let red_giant = star.clone().at_age(1.15 * life_expectancy);
+    print_var!(red_giant);
+    assert_eq!(red_giant.stage, EvolutionaryStage::RedGiant);
+    assert!(red_giant.class.ends_with("III"));
+    assert!(red_giant.radius > 10.0 * star.radius);
+    assert!(red_giant.habitable_zone.0 > star.habitable_zone.1);
+    let white_dwarf = star.clone().at_age(1.5 * life_expectancy);
+    print_var!(white_dwarf);
+    assert_eq!(white_dwarf.stage, EvolutionaryStage::WhiteDwarf);
+    assert!(white_dwarf.class.starts_with("DA"));
+    assert!(white_dwarf.mass < star.mass);
+    assert!(white_dwarf.radius < 0.02);
+    assert!(white_dwarf.check_habitable().is_err());
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_evolve() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let star = Star::from_mass(&mut rng, 1.0)?;
+    let older = star.evolve(1.0);
+    print_var!(older);
+    assert_approx_eq!(older.current_age, star.current_age + 1.0);
+    assert!(older.luminosity > star.luminosity);
+    let restored = older.evolve(-1.0);
+    assert_approx_eq!(restored.luminosity, star.luminosity);
+    assert_eq!(star.evolve(-100.0).current_age, 0.0);
+    let remnant = star.evolve(100.0);
+    assert_eq!(remnant.stage, EvolutionaryStage::WhiteDwarf);
+    assert_eq!(remnant.initial_mass, star.mass);
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_apparent_magnitude_at() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let star = Star::from_mass(&mut rng, 1.0)?;
+    let absolute_magnitude = star.get_absolute_magnitude();
+    let bolometric_correction = star.get_bolometric_correction();
+    print_var!((absolute_magnitude, bolometric_correction));
+    assert!(bolometric_correction < 0.0);
+    assert_approx_eq!(
+      star.absolute_visual_magnitude,
+      absolute_magnitude - bolometric_correction,
+      1e-9
+    );
+    // At ten parsecs, apparent and absolute magnitude coincide.
+    let distance = 10.0 * LIGHT_YEARS_PER_PARSEC;
+    assert_approx_eq!(
+      star.get_apparent_magnitude_at(distance),
+      star.absolute_visual_magnitude,
+      1e-9
+    );
+    // Ten times as far is a hundred times as faint, or five magnitudes.
+    assert_approx_eq!(
+      star.get_apparent_magnitude_at(10.0 * distance),
+      star.absolute_visual_magnitude + 5.0,
+      1e-9
+    );
+    trace_exit!();
+    Ok(())
+  }
 }
@@ -0,0 +1,152 @@
+use inflector::cases::sentencecase::to_sentence_case;
+use rand::prelude::*;
+use std::collections::BTreeMap;
+
+/// Marks the start of a name in the chain.
+pub const START: char = '^';
+
+/// Marks the end of a name in the chain.
+pub const END: char = '$';
+
+/// How many times to try for a name before giving up and borrowing one from
+/// the corpus.
+pub const MAXIMUM_ATTEMPTS: usize = 100;
+
+/// A name generator trained on a corpus of names.
+///
+/// Each letter is picked based on the few letters before it, so the names
+/// come out sounding like the corpus without (usually) being copied from it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkovNameGenerator {
+  /// How many letters of context each choice is based on.
+  pub order: usize,
+  /// The shortest name we'll produce.
+  pub minimum_length: usize,
+  /// The longest name we'll produce.
+  pub maximum_length: usize,
+  /// The letters that follow each context in the corpus, repeats and all.
+  pub transitions: BTreeMap<String, Vec<char>>,
+  /// The corpus, lowercased, so we can avoid reproducing it.
+  pub corpus: Vec<String>,
+}
+
+impl MarkovNameGenerator {
+  /// Train a generator on a corpus of names.
+  ///
+  /// An order of 2 or 3 works best; lower is more inventive, higher more
+  /// faithful to the corpus.
+  #[named]
+  pub fn train(order: usize, minimum_length: usize, maximum_length: usize, corpus: &[&str]) -> Self {
+    trace_enter!();
+    trace_var!(order);
+    trace_var!(minimum_length);
+    trace_var!(maximum_length);
+    let corpus: Vec<String> = corpus.iter().map(|name| name.to_lowercase()).collect();
+    trace_var!(corpus);
+    let mut transitions: BTreeMap<String, Vec<char>> = BTreeMap::new();
+    for name in corpus.iter() {
+      let padded: Vec<char> = std::iter::repeat_n(START, order)
+        .chain(name.chars())
+        .chain(std::iter::once(END))
+        .collect();
+      for window in padded.windows(order + 1) {
+        let context: String = window[..order].iter().collect();
+        transitions.entry(context).or_default().push(window[order]);
+      }
+    }
+    trace_var!(transitions);
+    let result = Self {
+      order,
+      minimum_length,
+      maximum_length,
+      transitions,
+      corpus,
+    };
+    trace_exit!();
+    result
+  }
+
+  /// Generate a name.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+    trace_enter!();
+    for _ in 0..MAXIMUM_ATTEMPTS {
+      if let Some(name) = self.generate_attempt(rng) {
+        let result = to_sentence_case(&name);
+        trace_var!(result);
+        trace_exit!();
+        return result;
+      }
+    }
+    let result = to_sentence_case(self.corpus.choose(rng).map(String::as_str).unwrap_or_default());
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Walk the chain once, returning a name if it's the right length and not
+  /// lifted straight from the corpus.
+  #[named]
+  fn generate_attempt<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<String> {
+    trace_enter!();
+    let mut context: Vec<char> = std::iter::repeat_n(START, self.order).collect();
+    let mut name = String::new();
+    let result = loop {
+      let key: String = context.iter().collect();
+      let next = match self.transitions.get(&key).and_then(|choices| choices.choose(rng)) {
+        Some(&next) => next,
+        None => break None,
+      };
+      if next == END {
+        let length = name.chars().count();
+        let is_acceptable =
+          length >= self.minimum_length && length <= self.maximum_length && !self.corpus.contains(&name);
+        break is_acceptable.then_some(name);
+      }
+      if name.chars().count() >= self.maximum_length {
+        break None;
+      }
+      name.push(next);
+      context.remove(0);
+      context.push(next);
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let corpus = [
+      "Aldebaran",
+      "Alcyone",
+      "Algol",
+      "Alnilam",
+      "Altair",
+      "Antares",
+      "Arcturus",
+    ];
+    let generator = MarkovNameGenerator::train(2, 4, 10, &corpus);
+    for _ in 0..20 {
+      let name = generator.generate(&mut rng);
+      print_var!(name);
+      let length = name.chars().count();
+      assert!((4..=10).contains(&length));
+      assert!(name.starts_with('A'));
+    }
+    trace_exit!();
+  }
+}
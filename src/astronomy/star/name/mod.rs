@@ -1,5 +1,11 @@
 use inflector::cases::sentencecase::to_sentence_case;
 use rand::prelude::*;
+use std::sync::OnceLock;
+
+pub mod markov;
+pub mod registry;
+pub mod theme;
+use markov::MarkovNameGenerator;
 
 pub const PREFIX: &'static [&'static str] = &[
   "", "aga", "allo", "an", "andro", "anti", "ap", "aqui", "ar", "ara", "ba", "bacty", "bal", "baltara", "bali", "bara",
@@ -22,8 +28,156 @@ pub const SUFFIX: &'static [&'static str] = &[
   "yria",
 ];
 
+/// The kind of body being named.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NameKind {
+  /// A star, stellar remnant, or brown dwarf.
+  Star,
+  /// A planet with a name of its own.
+  Planet,
+  /// A planet named after its star, e.g. "Rigel b".
+  SystematicPlanet,
+}
+
+/// Real star names, for training the name generator.
+pub const STAR_NAME_CORPUS: &[&str] = &[
+  "Achernar",
+  "Acrux",
+  "Adhara",
+  "Albireo",
+  "Alcor",
+  "Alcyone",
+  "Aldebaran",
+  "Alderamin",
+  "Algenib",
+  "Algieba",
+  "Algol",
+  "Alhena",
+  "Alioth",
+  "Alkaid",
+  "Almach",
+  "Alnair",
+  "Alnilam",
+  "Alnitak",
+  "Alphard",
+  "Alpheratz",
+  "Altair",
+  "Aludra",
+  "Ankaa",
+  "Antares",
+  "Arcturus",
+  "Arneb",
+  "Atria",
+  "Avior",
+  "Bellatrix",
+  "Betelgeuse",
+  "Canopus",
+  "Capella",
+  "Caph",
+  "Castor",
+  "Deneb",
+  "Denebola",
+  "Diphda",
+  "Dubhe",
+  "Elnath",
+  "Eltanin",
+  "Enif",
+  "Fomalhaut",
+  "Gacrux",
+  "Gienah",
+  "Hadar",
+  "Hamal",
+  "Izar",
+  "Kochab",
+  "Markab",
+  "Menkar",
+  "Menkent",
+  "Merak",
+  "Miaplacidus",
+  "Mimosa",
+  "Mintaka",
+  "Mirach",
+  "Mirfak",
+  "Mirzam",
+  "Mizar",
+  "Naos",
+  "Nunki",
+  "Peacock",
+  "Phecda",
+  "Polaris",
+  "Pollux",
+  "Procyon",
+  "Rasalhague",
+  "Regulus",
+  "Rigel",
+  "Sabik",
+  "Sadr",
+  "Saiph",
+  "Scheat",
+  "Schedar",
+  "Shaula",
+  "Sirius",
+  "Spica",
+  "Suhail",
+  "Thuban",
+  "Unukalhai",
+  "Vega",
+  "Wezen",
+  "Zaurak",
+  "Zubenelgenubi",
+];
+
+/// The order of the chain used for star names.
+pub const STAR_NAME_ORDER: usize = 2;
+
+/// The shortest star name we'll generate.
+pub const MINIMUM_STAR_NAME_LENGTH: usize = 4;
+
+/// The longest star name we'll generate.
+pub const MAXIMUM_STAR_NAME_LENGTH: usize = 10;
+
+/// Get the name generator trained on real star names.
+///
+/// It's trained once, the first time it's needed.
+#[named]
+pub fn get_star_name_generator() -> &'static MarkovNameGenerator {
+  trace_enter!();
+  static GENERATOR: OnceLock<MarkovNameGenerator> = OnceLock::new();
+  let result = GENERATOR.get_or_init(|| {
+    MarkovNameGenerator::train(
+      STAR_NAME_ORDER,
+      MINIMUM_STAR_NAME_LENGTH,
+      MAXIMUM_STAR_NAME_LENGTH,
+      STAR_NAME_CORPUS,
+    )
+  });
+  trace_exit!();
+  result
+}
+
+/// Generate a name for a star.
+///
+/// Half the time this is stitched together from prefixes, infixes, and
+/// suffixes; the other half, it's drawn from a chain trained on real star
+/// names.
 #[named]
 pub fn generate_star_name<R: Rng + ?Sized>(rng: &mut R) -> String {
+  trace_enter!();
+  if rng.gen_bool(0.5) {
+    let result = get_star_name_generator().generate(rng);
+    trace_var!(result);
+    trace_exit!();
+    return result;
+  }
+  let result = generate_fantasy_star_name(rng);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Generate a name for a star from prefixes, infixes, and suffixes.
+#[named]
+pub fn generate_fantasy_star_name<R: Rng + ?Sized>(rng: &mut R) -> String {
   trace_enter!();
   let first = PREFIX[rng.gen_range(0..PREFIX.len())];
   let second = INFIX[rng.gen_range(0..INFIX.len())];
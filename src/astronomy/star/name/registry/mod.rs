@@ -0,0 +1,67 @@
+use std::collections::BTreeSet;
+
+use crate::math::roman::get_roman_numeral;
+
+/// A record of the names already given out, so that no two bodies share one.
+///
+/// A name that's already taken gets a numeral tacked on: the second "Rigel"
+/// becomes "Rigel II", the third "Rigel III", and so on.  Since names are
+/// registered in the order they're generated, the same seed always yields
+/// the same names.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NameRegistry {
+  /// The names given out so far.
+  pub names: BTreeSet<String>,
+}
+
+impl NameRegistry {
+  /// Indicate whether a name has already been given out.
+  #[named]
+  pub fn contains(&self, name: &str) -> bool {
+    trace_enter!();
+    trace_var!(name);
+    let result = self.names.contains(name);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Claim a name, or the first free variant of it.
+  #[named]
+  pub fn register(&mut self, name: &str) -> String {
+    trace_enter!();
+    trace_var!(name);
+    let result = (1..)
+      .map(|number| match number {
+        1 => name.to_string(),
+        _ => format!("{} {}", name, get_roman_numeral(number)),
+      })
+      .find(|candidate| !self.names.contains(candidate))
+      .unwrap();
+    self.names.insert(result.clone());
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_register() {
+    init();
+    trace_enter!();
+    let mut registry = NameRegistry::default();
+    assert_eq!(registry.register("Rigel"), "Rigel");
+    assert_eq!(registry.register("Rigel"), "Rigel II");
+    assert_eq!(registry.register("Vega"), "Vega");
+    assert_eq!(registry.register("Rigel"), "Rigel III");
+    assert!(registry.contains("Rigel II"));
+    trace_exit!();
+  }
+}
@@ -0,0 +1,244 @@
+use rand::prelude::*;
+
+use crate::astronomy::star::name::markov::MarkovNameGenerator;
+use crate::astronomy::star::name::*;
+
+/// Greco-Roman names, for training the name generator.
+pub const GRECO_ROMAN_CORPUS: &[&str] = &[
+  "Achilles",
+  "Aegeus",
+  "Aeneas",
+  "Andromeda",
+  "Antigone",
+  "Apollo",
+  "Aquila",
+  "Ariadne",
+  "Athena",
+  "Aurelius",
+  "Aurora",
+  "Bacchus",
+  "Callisto",
+  "Cassandra",
+  "Cassius",
+  "Ceres",
+  "Cornelia",
+  "Corvus",
+  "Cygnus",
+  "Daedalus",
+  "Diana",
+  "Electra",
+  "Europa",
+  "Hector",
+  "Helena",
+  "Hercules",
+  "Hermes",
+  "Hyperion",
+  "Icarus",
+  "Janus",
+  "Julia",
+  "Juno",
+  "Jupiter",
+  "Leda",
+  "Livia",
+  "Lucretia",
+  "Lyra",
+  "Marcellus",
+  "Medusa",
+  "Minerva",
+  "Nereus",
+  "Octavia",
+  "Orion",
+  "Pegasus",
+  "Penelope",
+  "Perseus",
+  "Phoebe",
+  "Proserpina",
+  "Remus",
+  "Romulus",
+  "Saturnus",
+  "Selene",
+  "Severus",
+  "Theseus",
+  "Tiberius",
+  "Titania",
+  "Ulysses",
+  "Valeria",
+  "Vesta",
+  "Vulcan",
+];
+
+/// Star catalogs, and the highest number in each.
+pub const STAR_CATALOGS: &[(&str, usize)] = &[("HD", 359_083), ("HIP", 118_218), ("GJ", 1_294), ("LHS", 5_071)];
+
+/// The catalog planet candidates are drawn from, and the highest number in it.
+pub const PLANET_CATALOG: (&str, usize) = ("KOI", 8_000);
+
+/// A family of names, used for every star and planet generated in a run.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NameTheme {
+  /// Invented, but in the style of Greek and Roman myth, e.g. "Cassiope".
+  GrecoRoman,
+  /// Invented, but in the style of the traditional (mostly Arabic) star
+  /// names, e.g. "Alnirak".
+  Arabic,
+  /// Catalog numbers, as a survey would assign them, e.g. "HD 48915".
+  Catalog,
+  /// Stitched together from fanciful syllables, e.g. "Cepharrion".
+  Fantasy,
+  /// Invented, but in the style of the names supplied.
+  Custom(&'static [&'static str]),
+}
+
+impl NameTheme {
+  /// Get a generator for names in this theme.
+  #[named]
+  pub fn get_generator(&self) -> ThemedNameGenerator {
+    trace_enter!();
+    use NameTheme::*;
+    let corpus = match self {
+      GrecoRoman => Some(GRECO_ROMAN_CORPUS),
+      Arabic => Some(STAR_NAME_CORPUS),
+      Catalog | Fantasy => None,
+      Custom(corpus) => Some(*corpus),
+    };
+    trace_var!(corpus);
+    let generator = corpus.map(|corpus| {
+      MarkovNameGenerator::train(
+        STAR_NAME_ORDER,
+        MINIMUM_STAR_NAME_LENGTH,
+        MAXIMUM_STAR_NAME_LENGTH,
+        corpus,
+      )
+    });
+    let result = ThemedNameGenerator {
+      theme: *self,
+      generator,
+    };
+    trace_exit!();
+    result
+  }
+}
+
+/// Generates names in a single theme.
+///
+/// The chain, if the theme needs one, is trained once, so hold on to this
+/// for as long as names are needed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemedNameGenerator {
+  /// The theme.
+  pub theme: NameTheme,
+  /// The chain trained on the theme's names, if it has them.
+  pub generator: Option<MarkovNameGenerator>,
+}
+
+impl ThemedNameGenerator {
+  /// Generate a name for a body of this kind.
+  ///
+  /// Planets named after their star keep their name, since it already
+  /// follows the star's.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R, name: &str, kind: NameKind) -> String {
+    trace_enter!();
+    trace_var!(kind);
+    let result = match (kind, &self.generator) {
+      (NameKind::SystematicPlanet, _) => name.to_string(),
+      (_, Some(generator)) => generator.generate(rng),
+      (NameKind::Star, None) if self.theme == NameTheme::Catalog => generate_catalog_star_name(rng),
+      (NameKind::Planet, None) if self.theme == NameTheme::Catalog => generate_catalog_planet_name(rng),
+      (_, None) => loop {
+        let name = generate_fantasy_star_name(rng);
+        if !name.is_empty() {
+          break name;
+        }
+      },
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Replace the first word of a name like "Tarsus Reach" with one in this
+  /// theme.
+  #[named]
+  pub fn replace_root<R: Rng + ?Sized>(&self, rng: &mut R, name: &str) -> String {
+    trace_enter!();
+    let root = self.generate(rng, name, NameKind::Star);
+    trace_var!(root);
+    let result = match name.split_once(' ') {
+      Some((_, rest)) => format!("{} {}", root, rest),
+      None => root,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+/// Generate a catalog name for a star, e.g. "HD 48915".
+#[named]
+pub fn generate_catalog_star_name<R: Rng + ?Sized>(rng: &mut R) -> String {
+  trace_enter!();
+  let (catalog, maximum) = STAR_CATALOGS[rng.gen_range(0..STAR_CATALOGS.len())];
+  trace_var!(catalog);
+  let result = format!("{} {}", catalog, rng.gen_range(1..=maximum));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Generate a catalog name for a planet, e.g. "KOI-2194".
+#[named]
+pub fn generate_catalog_planet_name<R: Rng + ?Sized>(rng: &mut R) -> String {
+  trace_enter!();
+  let (catalog, maximum) = PLANET_CATALOG;
+  let result = format!("{}-{}", catalog, rng.gen_range(1..=maximum));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let themes = [
+      NameTheme::GrecoRoman,
+      NameTheme::Arabic,
+      NameTheme::Catalog,
+      NameTheme::Fantasy,
+      NameTheme::Custom(&["Brightwater", "Stillwater", "Deepwater", "Clearwater"]),
+    ];
+    for theme in themes {
+      let generator = theme.get_generator();
+      let star_name = generator.generate(&mut rng, "Sol", NameKind::Star);
+      let planet_name = generator.generate(&mut rng, "Ares", NameKind::Planet);
+      print_var!((theme, &star_name, &planet_name));
+      assert!(!star_name.is_empty());
+      assert!(!planet_name.is_empty());
+      assert_eq!(
+        generator.generate(&mut rng, "Sol b", NameKind::SystematicPlanet),
+        "Sol b"
+      );
+      assert!(generator.replace_root(&mut rng, "Tarsus Reach").ends_with(" Reach"));
+    }
+    let generator = NameTheme::Catalog.get_generator();
+    let star_name = generator.generate(&mut rng, "Sol", NameKind::Star);
+    assert!(STAR_CATALOGS
+      .iter()
+      .any(|(catalog, _)| star_name.starts_with(&format!("{} ", catalog))));
+    assert!(generator
+      .generate(&mut rng, "Ares", NameKind::Planet)
+      .starts_with("KOI-"));
+    trace_exit!();
+  }
+}
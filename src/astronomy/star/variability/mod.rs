@@ -0,0 +1,138 @@
+use rand::prelude::*;
+use serde::Serialize;
+
+use crate::astronomy::star::constants::*;
+use crate::astronomy::star::math::evolution::EvolutionaryStage;
+use crate::astronomy::star::math::variability::{get_activity_lifetime, get_cepheid_period};
+use crate::astronomy::star::Star;
+use crate::generator::seeded_rng;
+use crate::math::noise::get_seed;
+
+/// Why a star varies in brightness.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum VariabilityKind {
+  /// A yellow supergiant pulsating as it crosses the instability strip.
+  Cepheid,
+  /// An old, light giant pulsating on the horizontal branch.
+  RrLyrae,
+  /// A main-sequence A or F star pulsating rapidly and faintly.
+  DeltaScuti,
+  /// A young M dwarf given to sudden, violent flares.
+  FlareStar,
+}
+
+/// How a star varies in brightness.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Variability {
+  /// Why it varies.
+  pub kind: VariabilityKind,
+  /// The pulsation period, in days; for a flare star, the typical interval
+  /// between large flares.
+  pub period: f64,
+  /// The peak-to-peak amplitude, in visual magnitudes.
+  pub amplitude: f64,
+}
+
+impl Variability {
+  /// Work out whether and how a star varies.
+  ///
+  /// This is seeded by the star's name, so the same star always varies the
+  /// same way, however many times it's aged.
+  #[named]
+  pub fn from_star(star: &Star) -> Option<Self> {
+    trace_enter!();
+    let mut rng = seeded_rng(get_seed(&star.name));
+    use EvolutionaryStage::*;
+    use VariabilityKind::*;
+    let is_in_instability_strip =
+      (INSTABILITY_STRIP_MINIMUM_TEMPERATURE..=INSTABILITY_STRIP_MAXIMUM_TEMPERATURE).contains(&star.temperature);
+    trace_var!(is_in_instability_strip);
+    // Drawn up front, so that every star consumes the same numbers.
+    let roll: f64 = rng.gen();
+    trace_var!(roll);
+    let kind = match star.stage {
+      Subgiant | RedGiant
+        if is_in_instability_strip && (MINIMUM_CEPHEID_MASS..=MAXIMUM_CEPHEID_MASS).contains(&star.mass) =>
+      {
+        Some(Cepheid)
+      },
+      RedGiant if star.mass <= MAXIMUM_RR_LYRAE_MASS && roll < RR_LYRAE_PROBABILITY => Some(RrLyrae),
+      MainSequence
+        if (MINIMUM_DELTA_SCUTI_MASS..=MAXIMUM_DELTA_SCUTI_MASS).contains(&star.mass)
+          && roll < DELTA_SCUTI_PROBABILITY =>
+      {
+        Some(DeltaScuti)
+      },
+      MainSequence
+        if star.mass <= MAXIMUM_FLARE_STAR_MASS
+          && star.current_age < get_activity_lifetime(star.mass)
+          && roll < FLARE_STAR_PROBABILITY =>
+      {
+        Some(FlareStar)
+      },
+      _ => None,
+    };
+    trace_var!(kind);
+    let result = kind.map(|kind| {
+      let (period, amplitude) = match kind {
+        Cepheid => (get_cepheid_period(star.luminosity), rng.gen_range(0.3..1.2)),
+        RrLyrae => (rng.gen_range(0.2..1.0), rng.gen_range(0.3..1.2)),
+        DeltaScuti => (rng.gen_range(0.02..0.25), rng.gen_range(0.01..0.3)),
+        FlareStar => (rng.gen_range(0.5..10.0), rng.gen_range(0.1..1.0)),
+      };
+      Self {
+        kind,
+        period,
+        amplitude,
+      }
+    });
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this is a flare star.
+  #[named]
+  pub fn is_flare_star(&self) -> bool {
+    trace_enter!();
+    let result = self.kind == VariabilityKind::FlareStar;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::star::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_from_star() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let sun = Star::from_mass(&mut rng, 1.0)?;
+    assert_eq!(Variability::from_star(&sun), None);
+    let mut cepheid = Star::from_mass(&mut rng, 6.0)?;
+    cepheid.stage = EvolutionaryStage::Subgiant;
+    cepheid.temperature = 6_000.0;
+    cepheid.luminosity = 2_000.0;
+    let variability = Variability::from_star(&cepheid).unwrap();
+    print_var!(variability);
+    assert_eq!(variability.kind, VariabilityKind::Cepheid);
+    assert!(variability.period > 1.0 && variability.period < 20.0);
+    let mut dwarf = Star::from_mass(&mut rng, 0.2)?;
+    dwarf.current_age = 10.0;
+    assert_eq!(Variability::from_star(&dwarf), None);
+    assert_eq!(Variability::from_star(&dwarf), Variability::from_star(&dwarf.clone()));
+    trace_exit!();
+    Ok(())
+  }
+}
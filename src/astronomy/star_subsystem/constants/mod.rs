@@ -1,2 +1,4 @@
+use crate::math::probability::Probability;
+
 /// The probability that we generate a distant binary star system.
-pub const DISTANT_BINARY_PROBABILITY: f64 = 0.25;
+pub const DISTANT_BINARY_PROBABILITY: Probability = Probability::from_const(0.25);
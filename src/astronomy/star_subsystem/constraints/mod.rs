@@ -3,15 +3,19 @@ use std::default::Default;
 
 use crate::astronomy::distant_binary_star::constraints::Constraints as DistantBinaryStarConstraints;
 use crate::astronomy::planetary_system::constraints::Constraints as PlanetarySystemConstraints;
+use crate::astronomy::planetary_system::PlanetarySystem;
 use crate::astronomy::star_subsystem::constants::*;
 use crate::astronomy::star_subsystem::error::Error;
 use crate::astronomy::star_subsystem::StarSubsystem;
+use crate::generator::{GenerationStrategy, Generator};
+use crate::math::probability::Probability;
+use crate::merge::Merge;
 
 /// Constraints for creating a main-sequence star subsystem.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Constraints {
   /// The probability that we generate a distant binary star.
-  pub distant_binary_probability: Option<f64>,
+  pub distant_binary_probability: Option<Probability>,
   /// Distant Binary Star constraints.
   pub distant_binary_star_constraints: Option<DistantBinaryStarConstraints>,
   /// Planetary System constraints.
@@ -35,24 +39,40 @@ impl Constraints {
   #[named]
   pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<StarSubsystem, Error> {
     trace_enter!();
-    use StarSubsystem::*;
+    let planetary_system_constraints = self
+      .planetary_system_constraints
+      .unwrap_or(PlanetarySystemConstraints::default());
+    trace_var!(planetary_system_constraints);
+    let result = self.generate_with_planetary_system_strategy(rng, &planetary_system_constraints);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Generate, using another strategy for any solitary planetary system.
+  ///
+  /// The planetary system constraints are ignored.
+  #[named]
+  pub fn generate_with_planetary_system_strategy<R, S>(&self, rng: &mut R, strategy: &S) -> Result<StarSubsystem, Error>
+  where
+    R: Rng + ?Sized,
+    S: GenerationStrategy<PlanetarySystem>,
+    Error: From<S::Error>,
+  {
+    trace_enter!();
     let distant_binary_probability = self.distant_binary_probability.unwrap_or(DISTANT_BINARY_PROBABILITY);
     trace_var!(distant_binary_probability);
-    let generate_planetary_system: bool = rng.gen_range(0.0..1.0) > distant_binary_probability;
+    let generate_planetary_system: bool = !distant_binary_probability.sample(rng);
     trace_var!(generate_planetary_system);
     let result;
     if generate_planetary_system {
-      let planetary_system_constraints = self
-        .planetary_system_constraints
-        .unwrap_or(PlanetarySystemConstraints::default());
-      trace_var!(planetary_system_constraints);
-      result = PlanetarySystem(planetary_system_constraints.generate(rng)?);
+      result = StarSubsystem::PlanetarySystem(strategy.generate(rng)?);
     } else {
       let distant_binary_star_constraints = self
         .distant_binary_star_constraints
         .unwrap_or(DistantBinaryStarConstraints::default());
       trace_var!(distant_binary_star_constraints);
-      result = DistantBinaryStar(distant_binary_star_constraints.generate(rng)?);
+      result = StarSubsystem::DistantBinaryStar(distant_binary_star_constraints.generate(rng)?);
     }
     trace_var!(result);
     trace_exit!();
@@ -60,6 +80,19 @@ impl Constraints {
   }
 }
 
+impl Generator for Constraints {
+  type Output = StarSubsystem;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<StarSubsystem, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
 impl Default for Constraints {
   /// No constraints, just let it all hang out.
   fn default() -> Self {
@@ -74,6 +107,20 @@ impl Default for Constraints {
   }
 }
 
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      distant_binary_probability: overrides.distant_binary_probability.or(self.distant_binary_probability),
+      distant_binary_star_constraints: self
+        .distant_binary_star_constraints
+        .merged_with(&overrides.distant_binary_star_constraints),
+      planetary_system_constraints: self
+        .planetary_system_constraints
+        .merged_with(&overrides.planetary_system_constraints),
+    }
+  }
+}
+
 #[cfg(test)]
 pub mod test {
 
@@ -0,0 +1,200 @@
+use serde::Serialize;
+
+use crate::astronomy::distant_binary_star::math::stability::is_hierarchy_stable;
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::orbit::constants::DAYS_PER_YEAR;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::star::Star;
+use crate::astronomy::star_subsystem::StarSubsystem;
+
+/// The orbital hierarchy of a star system, as a tree.
+///
+/// Multiple stars are stable only when they're built up out of pairs: a
+/// triple is a close pair with a distant companion, and a quadruple is
+/// usually two close pairs orbiting one another at a distance.  Each node is
+/// either a single star or a pair of nodes.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum Hierarchy {
+  /// A single star.
+  Star {
+    /// The name of the star.
+    name: String,
+    /// The mass, in Msol.
+    mass: f64,
+  },
+  /// Two nodes orbiting their common barycenter.
+  Binary {
+    /// The orbit of the secondary around the primary, in AU.
+    orbit: OrbitalElements,
+    /// The orbital period, in Earth years.
+    orbital_period: f64,
+    /// The heavier node.
+    primary: Box<Hierarchy>,
+    /// The lighter node.
+    secondary: Box<Hierarchy>,
+  },
+}
+
+impl Hierarchy {
+  /// Create a node for a single star.
+  #[named]
+  pub fn from_star(star: &Star) -> Self {
+    trace_enter!();
+    let result = Hierarchy::Star {
+      name: star.name.clone(),
+      mass: star.mass,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Create the hierarchy of a host star.
+  #[named]
+  pub fn from_host_star(host_star: &HostStar) -> Self {
+    trace_enter!();
+    let result = match host_star {
+      HostStar::Star(star) => Hierarchy::from_star(star),
+      HostStar::StellarRemnant(stellar_remnant) => Hierarchy::Star {
+        name: stellar_remnant.name.clone(),
+        mass: stellar_remnant.mass,
+      },
+      HostStar::BrownDwarf(brown_dwarf) => Hierarchy::Star {
+        name: brown_dwarf.name.clone(),
+        mass: brown_dwarf.mass,
+      },
+      HostStar::CloseBinaryStar(close_binary_star) => Hierarchy::Binary {
+        orbit: close_binary_star.orbit,
+        orbital_period: close_binary_star.orbital_period / DAYS_PER_YEAR,
+        primary: Box::new(Hierarchy::from_star(&close_binary_star.primary)),
+        secondary: Box::new(Hierarchy::from_star(&close_binary_star.secondary)),
+      },
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Create the hierarchy of a star subsystem.
+  #[named]
+  pub fn from_star_subsystem(star_subsystem: &StarSubsystem) -> Self {
+    trace_enter!();
+    let result = match star_subsystem {
+      StarSubsystem::PlanetarySystem(planetary_system) => Hierarchy::from_host_star(&planetary_system.host_star),
+      StarSubsystem::DistantBinaryStar(distant_binary_star) => Hierarchy::Binary {
+        orbit: distant_binary_star.orbit,
+        orbital_period: distant_binary_star.orbital_period,
+        primary: Box::new(Hierarchy::from_host_star(&distant_binary_star.primary.host_star)),
+        secondary: Box::new(Hierarchy::from_host_star(&distant_binary_star.secondary.host_star)),
+      },
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve the nodes directly beneath this one, primary first.
+  #[named]
+  pub fn get_children(&self) -> Vec<&Hierarchy> {
+    trace_enter!();
+    let result = match self {
+      Hierarchy::Star { .. } => vec![],
+      Hierarchy::Binary { primary, secondary, .. } => vec![primary.as_ref(), secondary.as_ref()],
+    };
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve the orbital period of this pair, in years, or None for a
+  /// single star.
+  #[named]
+  pub fn get_orbital_period(&self) -> Option<f64> {
+    trace_enter!();
+    let result = match self {
+      Hierarchy::Star { .. } => None,
+      Hierarchy::Binary { orbital_period, .. } => Some(*orbital_period),
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve or calculate the total mass of the stars.
+  ///
+  /// Calculated in Msol.
+  #[named]
+  pub fn get_stellar_mass(&self) -> f64 {
+    trace_enter!();
+    let result = match self {
+      Hierarchy::Star { mass, .. } => *mass,
+      Hierarchy::Binary { primary, secondary, .. } => primary.get_stellar_mass() + secondary.get_stellar_mass(),
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve the names of the stars, in order from the primary down.
+  #[named]
+  pub fn get_star_names(&self) -> Vec<&str> {
+    trace_enter!();
+    let result = match self {
+      Hierarchy::Star { name, .. } => vec![name.as_str()],
+      Hierarchy::Binary { primary, secondary, .. } => {
+        let mut names = primary.get_star_names();
+        names.extend(secondary.get_star_names());
+        names
+      },
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve the total number of stars.
+  #[named]
+  pub fn get_stellar_count(&self) -> usize {
+    trace_enter!();
+    let result = self.get_star_names().len();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve the number of levels of orbits; zero for a single star, one
+  /// for a binary, and two for a hierarchical triple or quadruple.
+  #[named]
+  pub fn get_depth(&self) -> usize {
+    trace_enter!();
+    let result = self
+      .get_children()
+      .iter()
+      .map(|child| child.get_depth() + 1)
+      .max()
+      .unwrap_or(0);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether every orbit in the hierarchy leaves the pairs inside it
+  /// alone.
+  #[named]
+  pub fn is_stable(&self) -> bool {
+    trace_enter!();
+    let result = match self {
+      Hierarchy::Star { .. } => true,
+      Hierarchy::Binary {
+        orbit, orbital_period, ..
+      } => self.get_children().iter().all(|child| {
+        child.is_stable()
+          && child
+            .get_orbital_period()
+            .is_none_or(|inner_period| is_hierarchy_stable(inner_period, *orbital_period, orbit.eccentricity))
+      }),
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
@@ -1,15 +1,20 @@
+use serde::Serialize;
+
 use crate::astronomy::distant_binary_star::DistantBinaryStar;
 use crate::astronomy::planetary_system::PlanetarySystem;
+use crate::astronomy::star::name::NameKind;
 
 pub mod constants;
 pub mod constraints;
 pub mod error;
 use error::*;
+pub mod hierarchy;
+use hierarchy::Hierarchy;
 
 /// The `StarSubsystem` type.
 ///
 /// A StarSubsystem is either one star with a planetary system or a distant binary.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum StarSubsystem {
   /// A distant binary system.
   DistantBinaryStar(DistantBinaryStar),
@@ -61,6 +66,22 @@ impl StarSubsystem {
     result
   }
 
+  /// Retrieve or calculate the total luminosity of the stars.
+  ///
+  /// Calculated in Lsol.
+  #[named]
+  pub fn get_luminosity(&self) -> f64 {
+    trace_enter!();
+    use StarSubsystem::*;
+    let result = match &self {
+      DistantBinaryStar(distant_binary_star) => distant_binary_star.get_luminosity(),
+      PlanetarySystem(planetary_system) => planetary_system.get_luminosity(),
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Retrieve or calculate the total number of stars in the system.
   #[named]
   pub fn get_stellar_count(&self) -> u8 {
@@ -74,6 +95,55 @@ impl StarSubsystem {
     trace_exit!();
     result
   }
+
+  /// Retrieve the orbital hierarchy of the stars in this subsystem.
+  #[named]
+  pub fn get_hierarchy(&self) -> Hierarchy {
+    trace_enter!();
+    let result = Hierarchy::from_star_subsystem(self);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Rename the stars and planets with the given function.
+  #[named]
+  pub fn rename<F: FnMut(&str, NameKind) -> String>(&mut self, rename: &mut F) {
+    trace_enter!();
+    use StarSubsystem::*;
+    match self {
+      DistantBinaryStar(distant_binary_star) => distant_binary_star.rename(rename),
+      PlanetarySystem(planetary_system) => planetary_system.rename(rename),
+    }
+    trace_exit!();
+  }
+
+  /// Retrieve all of the planetary systems in this subsystem.
+  #[named]
+  pub fn get_planetary_systems(&self) -> Vec<&PlanetarySystem> {
+    trace_enter!();
+    use StarSubsystem::*;
+    let result = match &self {
+      DistantBinaryStar(distant_binary_star) => distant_binary_star.get_planetary_systems(),
+      PlanetarySystem(planetary_system) => vec![planetary_system],
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve all of the planetary systems in this subsystem mutably.
+  #[named]
+  pub fn get_planetary_systems_mut(&mut self) -> Vec<&mut PlanetarySystem> {
+    trace_enter!();
+    use StarSubsystem::*;
+    let result = match self {
+      DistantBinaryStar(distant_binary_star) => distant_binary_star.get_planetary_systems_mut(),
+      PlanetarySystem(planetary_system) => vec![planetary_system],
+    };
+    trace_exit!();
+    result
+  }
 }
 
 #[cfg(test)]
@@ -0,0 +1,147 @@
+use crate::astronomy::close_binary_star::constraints::builder::Builder as CloseBinaryStarBuilder;
+use crate::astronomy::star::constraints::builder::Builder as StarBuilder;
+use crate::astronomy::star_system::constraints::Constraints;
+use crate::math::probability::Probability;
+use crate::math::range::error::Error;
+
+/// A fluent builder for star system constraints.
+///
+/// This reaches down through the star subsystem and planetary system to the
+/// host star, which is where most of the interesting choices are made.  The
+/// star and binary builders are checked along with this one, at `build()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Builder {
+  star: Option<StarBuilder>,
+  close_binary_star: Option<CloseBinaryStarBuilder>,
+  binary_probability: Option<Probability>,
+  distant_binary_probability: Option<Probability>,
+  retries: Option<u8>,
+  habitable: bool,
+}
+
+impl Builder {
+  /// Use these constraints for a single host star.
+  pub fn star(mut self, builder: StarBuilder) -> Self {
+    self.star = Some(builder);
+    self
+  }
+
+  /// Use these constraints for a close binary host star.
+  pub fn close_binary_star(mut self, builder: CloseBinaryStarBuilder) -> Self {
+    self.close_binary_star = Some(builder);
+    self
+  }
+
+  /// Set the probability that the host star is a close binary.
+  pub fn binary_probability(mut self, probability: Probability) -> Self {
+    self.binary_probability = Some(probability);
+    self
+  }
+
+  /// Set the probability that the system is a distant binary.
+  pub fn distant_binary_probability(mut self, probability: Probability) -> Self {
+    self.distant_binary_probability = Some(probability);
+    self
+  }
+
+  /// Set the number of times to regenerate if requirements aren't met.
+  pub fn retries(mut self, retries: u8) -> Self {
+    self.retries = Some(retries);
+    self
+  }
+
+  /// Ensure the system is habitable.
+  pub fn habitable(mut self) -> Self {
+    self.habitable = true;
+    self
+  }
+
+  /// Check the constraints and return them.
+  #[named]
+  pub fn build(self) -> Result<Constraints, Error> {
+    trace_enter!();
+    let mut result = match self.habitable {
+      true => Constraints::habitable(),
+      false => Constraints::default(),
+    };
+    let mut star_subsystem_constraints = result.star_subsystem_constraints.unwrap_or_default();
+    let mut planetary_system_constraints = star_subsystem_constraints
+      .planetary_system_constraints
+      .unwrap_or_default();
+    let mut host_star_constraints = planetary_system_constraints.host_star_constraints.unwrap_or_default();
+    let (star, close_binary_star) = match self.habitable {
+      true => (
+        Some(self.star.unwrap_or_default().habitable()),
+        Some(self.close_binary_star.unwrap_or_default().habitable()),
+      ),
+      false => (self.star, self.close_binary_star),
+    };
+    if let Some(star) = star {
+      host_star_constraints.star_constraints = Some(star.build()?);
+    }
+    if let Some(close_binary_star) = close_binary_star {
+      host_star_constraints.close_binary_star_constraints = Some(close_binary_star.build()?);
+    }
+    if let Some(binary_probability) = self.binary_probability {
+      host_star_constraints.binary_probability = Some(binary_probability);
+    }
+    trace_var!(host_star_constraints);
+    planetary_system_constraints.host_star_constraints = Some(host_star_constraints);
+    star_subsystem_constraints.planetary_system_constraints = Some(planetary_system_constraints);
+    if let Some(distant_binary_probability) = self.distant_binary_probability {
+      star_subsystem_constraints.distant_binary_probability = Some(distant_binary_probability);
+    }
+    trace_var!(star_subsystem_constraints);
+    result.star_subsystem_constraints = Some(star_subsystem_constraints);
+    result.retries = self.retries.or(result.retries);
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::star::constraints::Constraints as StarConstraints;
+  use crate::astronomy::star_subsystem::StarSubsystem;
+  use crate::astronomy::star_system::error::Error as StarSystemError;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_build() -> Result<(), StarSystemError> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints::builder()
+      .star(StarConstraints::builder().mass_range(0.8..1.0))
+      .binary_probability(Probability::NEVER)
+      .distant_binary_probability(Probability::NEVER)
+      .retries(20)
+      .build()
+      .unwrap();
+    print_var!(constraints);
+    let star_system = constraints.generate(&mut rng)?;
+    print_var!(star_system);
+    match &star_system.star_subsystem {
+      StarSubsystem::PlanetarySystem(planetary_system) => {
+        let mass = planetary_system.host_star.get_stellar_mass();
+        assert!((0.8..=1.0).contains(&mass));
+      },
+      StarSubsystem::DistantBinaryStar(_) => panic!("expected a single planetary system"),
+    }
+    assert_eq!(
+      Constraints::builder()
+        .star(StarConstraints::builder().mass_range(1.0..0.8))
+        .build(),
+      Err(Error::RangeEmpty("mass"))
+    );
+    trace_exit!();
+    Ok(())
+  }
+}
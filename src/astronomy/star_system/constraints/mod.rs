@@ -1,8 +1,14 @@
 use rand::prelude::*;
 
+use crate::astronomy::planetary_system::PlanetarySystem;
 use crate::astronomy::star_subsystem::constraints::Constraints as StarSubsystemConstraints;
+use crate::astronomy::star_subsystem::error::Error as StarSubsystemError;
 use crate::astronomy::star_system::error::Error;
 use crate::astronomy::star_system::StarSystem;
+use crate::generator::{GenerationStrategy, Generator};
+use crate::merge::Merge;
+pub mod builder;
+use builder::Builder;
 
 /// Constraints for creating a star system.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -14,6 +20,11 @@ pub struct Constraints {
 }
 
 impl Constraints {
+  /// Start building constraints fluently.
+  pub fn builder() -> Builder {
+    Builder::default()
+  }
+
   /// Generate a main-sequence star system.
   pub fn main_sequence() -> Self {
     let star_subsystem_constraints = Some(StarSubsystemConstraints::default());
@@ -59,6 +70,28 @@ impl Constraints {
   /// This may or may not be habitable.
   #[named]
   pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<StarSystem, Error> {
+    trace_enter!();
+    let planetary_system_constraints = self
+      .star_subsystem_constraints
+      .and_then(|star_subsystem_constraints| star_subsystem_constraints.planetary_system_constraints)
+      .unwrap_or_default();
+    trace_var!(planetary_system_constraints);
+    let result = self.generate_with_planetary_system_strategy(rng, &planetary_system_constraints);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Generate, using another strategy for any solitary planetary system.
+  ///
+  /// The planetary system constraints are ignored.
+  #[named]
+  pub fn generate_with_planetary_system_strategy<R, S>(&self, rng: &mut R, strategy: &S) -> Result<StarSystem, Error>
+  where
+    R: Rng + ?Sized,
+    S: GenerationStrategy<PlanetarySystem>,
+    StarSubsystemError: From<S::Error>,
+  {
     trace_enter!();
     let star_subsystem_constraints = self
       .star_subsystem_constraints
@@ -67,7 +100,7 @@ impl Constraints {
       let mut retries = self.retries.unwrap_or(10);
       let star_subsystem;
       loop {
-        let candidate_result = star_subsystem_constraints.generate(rng);
+        let candidate_result = star_subsystem_constraints.generate_with_planetary_system_strategy(rng, strategy);
         if let Ok(candidate) = candidate_result {
           star_subsystem = candidate;
           break;
@@ -89,6 +122,19 @@ impl Constraints {
   }
 }
 
+impl Generator for Constraints {
+  type Output = StarSystem;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<StarSystem, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
 impl Default for Constraints {
   /// No constraints, just let it all hang out.
   fn default() -> Self {
@@ -101,6 +147,17 @@ impl Default for Constraints {
   }
 }
 
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      star_subsystem_constraints: self
+        .star_subsystem_constraints
+        .merged_with(&overrides.star_subsystem_constraints),
+      retries: overrides.retries.or(self.retries),
+    }
+  }
+}
+
 #[cfg(test)]
 pub mod test {
 
@@ -0,0 +1,240 @@
+use serde::Serialize;
+
+use crate::astronomy::brown_dwarf::BrownDwarf;
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::planet::math::mass::earth_mass_to_kg;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::star::math::distance::meters_to_rsol;
+use crate::astronomy::star::Star;
+use crate::astronomy::star_system::interest::InterestWeights;
+use crate::astronomy::star_system::StarSystem;
+use crate::astronomy::stellar_remnant::StellarRemnant;
+
+/// One row of the stars table.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StarRow {
+  /// The name.
+  pub name: String,
+  /// The spectral class, e.g. "G2V".
+  pub class: String,
+  /// Mass, in Msol.
+  pub mass: f64,
+  /// Radius, in Rsol.
+  pub radius: f64,
+  /// Luminosity, in Lsol.
+  pub luminosity: f64,
+  /// Surface temperature, in Kelvin.
+  pub temperature: f64,
+  /// Current age, in Gyr.
+  pub age: f64,
+}
+
+impl From<&Star> for StarRow {
+  fn from(star: &Star) -> Self {
+    Self {
+      name: star.name.clone(),
+      class: star.class.clone(),
+      mass: star.mass,
+      radius: star.radius,
+      luminosity: star.luminosity,
+      temperature: star.temperature,
+      age: star.current_age,
+    }
+  }
+}
+
+impl From<&StellarRemnant> for StarRow {
+  fn from(stellar_remnant: &StellarRemnant) -> Self {
+    Self {
+      name: stellar_remnant.name.clone(),
+      class: stellar_remnant.get_class(),
+      mass: stellar_remnant.mass,
+      radius: meters_to_rsol(stellar_remnant.radius * 1_000.0),
+      luminosity: stellar_remnant.luminosity,
+      temperature: stellar_remnant.temperature,
+      age: stellar_remnant.current_age,
+    }
+  }
+}
+
+impl From<&BrownDwarf> for StarRow {
+  fn from(brown_dwarf: &BrownDwarf) -> Self {
+    Self {
+      name: brown_dwarf.name.clone(),
+      class: brown_dwarf.spectral_class.clone(),
+      mass: brown_dwarf.mass,
+      radius: brown_dwarf.radius,
+      luminosity: brown_dwarf.luminosity,
+      temperature: brown_dwarf.temperature,
+      age: brown_dwarf.current_age,
+    }
+  }
+}
+
+/// One row of the planets table.
+///
+/// Everything is in the same units regardless of the kind of planet.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PlanetRow {
+  /// The name, e.g. "Rigel b".
+  pub name: String,
+  /// The index of the planetary system the planet belongs to.
+  pub planetary_system: usize,
+  /// "Terrestrial", "Gas giant", or "Ice giant".
+  pub kind: String,
+  /// Semi-major axis, in AU.
+  pub semi_major_axis: f64,
+  /// Orbital eccentricity.
+  pub eccentricity: f64,
+  /// Orbital period, in years.
+  pub orbital_period: f64,
+  /// Mass, in Mearth.
+  pub mass: f64,
+  /// Equatorial radius, in KM.
+  pub radius: f64,
+  /// Surface temperature, in Kelvin, where known.
+  pub temperature: Option<f64>,
+  /// The number of moons.
+  pub moons: usize,
+  /// Whether it has rings.
+  pub has_rings: bool,
+  /// Whether it can support conventional life.
+  pub is_habitable: bool,
+}
+
+/// A flattened summary of a star system, for display.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FactSheet {
+  /// The name of the system.
+  pub name: String,
+  /// The number of stars.
+  pub stellar_count: u8,
+  /// The total mass of the stars, in Msol.
+  pub stellar_mass: f64,
+  /// The total luminosity of the stars, in Lsol.
+  pub luminosity: f64,
+  /// Whether the system can support conventional life.
+  pub is_habitable: bool,
+  /// The interest score, under the default weights.
+  pub interest_score: f64,
+  /// The stars.
+  pub stars: Vec<StarRow>,
+  /// The planets, by planetary system and then outward.
+  pub planets: Vec<PlanetRow>,
+  /// Anything else worth mentioning, in plain English.
+  pub features: Vec<String>,
+}
+
+/// Summarize a star system.
+#[named]
+pub fn get_fact_sheet(star_system: &StarSystem) -> FactSheet {
+  trace_enter!();
+  let mut stars = vec![];
+  let mut planets = vec![];
+  let mut features = vec![];
+  for (index, planetary_system) in star_system.get_planetary_systems().into_iter().enumerate() {
+    let star_name = planetary_system.host_star.get_name();
+    trace_var!(star_name);
+    match &planetary_system.host_star {
+      HostStar::Star(star) => stars.push(StarRow::from(star)),
+      HostStar::StellarRemnant(stellar_remnant) => {
+        stars.push(StarRow::from(stellar_remnant));
+        if stellar_remnant.is_pulsar {
+          features.push(format!("{} is a pulsar", star_name));
+        }
+      },
+      HostStar::BrownDwarf(brown_dwarf) => stars.push(StarRow::from(brown_dwarf)),
+      HostStar::CloseBinaryStar(close_binary_star) => {
+        stars.push(StarRow::from(&close_binary_star.primary));
+        stars.push(StarRow::from(&close_binary_star.secondary));
+        features.push(format!(
+          "{} is a close binary, {:.2} AU apart",
+          star_name, close_binary_star.orbit.semi_major_axis
+        ));
+      },
+    }
+    let satellite_systems = &planetary_system.satellite_systems.satellite_systems;
+    for satellite_system in satellite_systems.iter() {
+      let planet = &satellite_system.planet;
+      let name = planet.get_name();
+      let (kind, temperature) = match planet {
+        Planet::TerrestrialPlanet(terrestrial_planet) => ("Terrestrial", Some(terrestrial_planet.surface_temperature)),
+        Planet::GasGiantPlanet(_) => ("Gas giant", None),
+        Planet::IceGiantPlanet(_) => ("Ice giant", None),
+      };
+      let is_habitable = satellite_system.is_habitable();
+      if is_habitable {
+        features.push(format!("{} can support life", name));
+      }
+      if let Some(ring_system) = &satellite_system.ring_system {
+        if ring_system.optical_depth >= 1.0 {
+          features.push(format!("{} has bright, opaque rings", name));
+        }
+      }
+      planets.push(PlanetRow {
+        name,
+        planetary_system: index,
+        kind: kind.to_string(),
+        semi_major_axis: planet.get_semi_major_axis(),
+        eccentricity: planet.get_orbital_eccentricity(),
+        orbital_period: planet.get_orbital_period(),
+        mass: planet.get_mass_in_kg() / earth_mass_to_kg(1.0),
+        radius: planet.get_equatorial_radius_in_km(),
+        temperature,
+        moons: satellite_system.moons.moons.len(),
+        has_rings: satellite_system.ring_system.is_some(),
+        is_habitable,
+      });
+    }
+    for (planet_index, resonance) in planetary_system.get_resonances().into_iter().enumerate() {
+      if let Some((inner, outer)) = resonance {
+        features.push(format!(
+          "{} and {} are in a {}:{} resonance",
+          satellite_systems[planet_index].planet.get_name(),
+          satellite_systems[planet_index + 1].planet.get_name(),
+          inner,
+          outer
+        ));
+      }
+    }
+    if let Some(asteroid_belt) = &planetary_system.asteroid_belt {
+      features.push(format!(
+        "{} has an asteroid belt from {:.1} to {:.1} AU",
+        star_name, asteroid_belt.inner_edge, asteroid_belt.outer_edge
+      ));
+      for dwarf_planet in &asteroid_belt.dwarf_planets {
+        features.push(format!(
+          "{} is a dwarf planet in the asteroid belt of {}",
+          dwarf_planet.name, star_name
+        ));
+      }
+    }
+    if let Some(kuiper_belt) = &planetary_system.kuiper_belt {
+      features.push(format!(
+        "{} has a Kuiper belt with {} dwarf planets",
+        star_name,
+        kuiper_belt.dwarf_planets.len()
+      ));
+    }
+    if let Some(oort_cloud) = &planetary_system.oort_cloud {
+      features.push(format!(
+        "{} has an Oort cloud reaching out to {:.0} AU",
+        star_name, oort_cloud.outer_radius
+      ));
+    }
+  }
+  let result = FactSheet {
+    name: star_system.name.clone(),
+    stellar_count: star_system.get_stellar_count(),
+    stellar_mass: star_system.get_stellar_mass(),
+    luminosity: star_system.get_luminosity(),
+    is_habitable: star_system.is_habitable(),
+    interest_score: star_system.get_interest_score(&InterestWeights::default()),
+    stars,
+    planets,
+    features,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
@@ -0,0 +1,279 @@
+use serde::Serialize;
+use std::fmt::Write;
+
+use crate::astronomy::gas_giant_planet::math::radiation_belt::get_lethal_radiation_distance;
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::moons::constants::ROCKY_MOON_DENSITY;
+use crate::astronomy::planet::math::radius::METERS_PER_JUPITER_RADIUS;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::planetary_system::PlanetarySystem;
+use crate::astronomy::star_system::snapshot::Body;
+use crate::math::gravity::get_fluid_roche_limit;
+
+/// What makes a region hazardous.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum HazardKind {
+  /// Where a close binary flings out or swallows anything that tries to
+  /// orbit.
+  ForbiddenZone,
+  /// Where a close binary keeps any orbit too unsettled for anything
+  /// habitable.
+  DangerZone,
+  /// Where a planet's tides would tear a rocky moon apart.
+  RocheLimit,
+  /// Where a planet's radiation belts are lethal to an unshielded human.
+  RadiationBelt,
+  /// The plane of a planet's rings.
+  RingPlane,
+}
+
+impl HazardKind {
+  /// Get a short name, e.g. for an SVG class.
+  pub fn get_name(&self) -> &'static str {
+    use HazardKind::*;
+    match self {
+      ForbiddenZone => "forbidden-zone",
+      DangerZone => "danger-zone",
+      RocheLimit => "roche-limit",
+      RadiationBelt => "radiation-belt",
+      RingPlane => "ring-plane",
+    }
+  }
+}
+
+/// The shape of a hazardous region.
+///
+/// Annuli lie in the reference plane of whatever they're centered on: the
+/// orbital plane of a close binary, or the equator of a planet.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum HazardShape {
+  /// Everything within a radius.
+  Sphere {
+    /// The radius.
+    radius: f64,
+  },
+  /// A flat ring between two radii.
+  Annulus {
+    /// The inner radius.
+    inner_radius: f64,
+    /// The outer radius.
+    outer_radius: f64,
+  },
+}
+
+/// A labeled region of a planetary system that navigation should steer
+/// clear of.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HazardZone {
+  /// The index of the planetary system the region belongs to.
+  pub planetary_system: usize,
+  /// The body the region is centered on, or `None` for the barycenter of
+  /// the host star.
+  pub center: Option<Body>,
+  /// What makes the region hazardous.
+  pub kind: HazardKind,
+  /// A human-readable label, e.g. "Rigel b radiation belt".
+  pub label: String,
+  /// The shape of the region; in AU about the host star, and KM about a
+  /// planet.
+  pub shape: HazardShape,
+}
+
+/// Get the hazardous regions of a planetary system.
+#[named]
+pub fn get_hazard_zones(index: usize, planetary_system: &PlanetarySystem) -> Vec<HazardZone> {
+  trace_enter!();
+  trace_var!(index);
+  let star_name = planetary_system.host_star.get_name();
+  trace_var!(star_name);
+  let zone = |center: Option<Body>, kind: HazardKind, label: String, shape: HazardShape| HazardZone {
+    planetary_system: index,
+    center,
+    kind,
+    label,
+    shape,
+  };
+  let mut result = vec![];
+  if let HostStar::CloseBinaryStar(close_binary_star) = &planetary_system.host_star {
+    let (inner_radius, outer_radius) = close_binary_star.forbidden_zone;
+    result.push(zone(
+      None,
+      HazardKind::ForbiddenZone,
+      format!("{} forbidden zone", star_name),
+      HazardShape::Annulus {
+        inner_radius,
+        outer_radius,
+      },
+    ));
+    result.push(zone(
+      None,
+      HazardKind::DangerZone,
+      format!("{} danger zone", star_name),
+      HazardShape::Sphere {
+        radius: close_binary_star.danger_zone.1,
+      },
+    ));
+  }
+  for (planet_index, satellite_system) in planetary_system.satellite_systems.satellite_systems.iter().enumerate() {
+    let planet = &satellite_system.planet;
+    let planet_name = planet.get_name();
+    trace_var!(planet_name);
+    let center = Some(Body::Planet(planet_index));
+    let planet_radius = planet.get_equatorial_radius_in_km();
+    trace_var!(planet_radius);
+    let roche_limit = get_fluid_roche_limit(planet_radius, planet.get_density(), ROCKY_MOON_DENSITY);
+    trace_var!(roche_limit);
+    result.push(zone(
+      center,
+      HazardKind::RocheLimit,
+      format!("{} Roche limit", planet_name),
+      HazardShape::Sphere { radius: roche_limit },
+    ));
+    if let Planet::GasGiantPlanet(gas_giant_planet) = planet {
+      let lethal_distance =
+        get_lethal_radiation_distance(gas_giant_planet.magnetic_moment, gas_giant_planet.magnetopause_distance)
+          * METERS_PER_JUPITER_RADIUS
+          / 1_000.0;
+      trace_var!(lethal_distance);
+      if lethal_distance > planet_radius {
+        result.push(zone(
+          center,
+          HazardKind::RadiationBelt,
+          format!("{} radiation belt", planet_name),
+          HazardShape::Annulus {
+            inner_radius: planet_radius,
+            outer_radius: lethal_distance,
+          },
+        ));
+      }
+    }
+    if let Some(ring_system) = &satellite_system.ring_system {
+      result.push(zone(
+        center,
+        HazardKind::RingPlane,
+        format!("{} rings", planet_name),
+        HazardShape::Annulus {
+          inner_radius: ring_system.inner_radius,
+          outer_radius: ring_system.outer_radius,
+        },
+      ));
+    }
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Escape a string for use as SVG text.
+fn escape(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Render hazardous regions as SVG.
+///
+/// This is geometry for an engine to pick apart rather than a picture: the
+/// regions are grouped by what they're centered on, each group in its own
+/// units (noted in `data-unit`) with its center at the origin.  Every shape
+/// carries its label as a `<title>`.
+#[named]
+pub fn render_hazard_zones(hazard_zones: &[HazardZone]) -> String {
+  trace_enter!();
+  let mut output = String::new();
+  writeln!(output, "<svg xmlns=\"http://www.w3.org/2000/svg\">").unwrap();
+  let mut frames: Vec<(usize, Option<Body>)> = vec![];
+  for hazard_zone in hazard_zones {
+    let frame = (hazard_zone.planetary_system, hazard_zone.center);
+    if !frames.contains(&frame) {
+      frames.push(frame);
+    }
+  }
+  trace_var!(frames);
+  for (planetary_system, center) in frames {
+    let (center_name, unit) = match center {
+      None => ("barycenter".to_string(), "AU"),
+      Some(Body::Star(index)) => (format!("star-{}", index), "AU"),
+      Some(Body::Planet(index)) => (format!("planet-{}", index), "km"),
+      Some(Body::Moon(planet, index)) => (format!("moon-{}-{}", planet, index), "km"),
+    };
+    writeln!(
+      output,
+      "  <g data-planetary-system=\"{}\" data-center=\"{}\" data-unit=\"{}\">",
+      planetary_system, center_name, unit
+    )
+    .unwrap();
+    let zones = hazard_zones
+      .iter()
+      .filter(|zone| zone.planetary_system == planetary_system && zone.center == center);
+    for zone in zones {
+      let class = zone.kind.get_name();
+      let label = escape(&zone.label);
+      match zone.shape {
+        HazardShape::Sphere { radius } => writeln!(
+          output,
+          "    <circle class=\"{}\" cx=\"0\" cy=\"0\" r=\"{}\"><title>{}</title></circle>",
+          class, radius, label
+        ),
+        // Two full circles, with the inner one cut out of the outer.
+        HazardShape::Annulus {
+          inner_radius,
+          outer_radius,
+        } => writeln!(
+          output,
+          "    <path class=\"{}\" fill-rule=\"evenodd\" d=\"M {1} 0 A {1} {1} 0 1 0 -{1} 0 A {1} {1} 0 1 0 {1} 0 Z M {2} 0 A {2} {2} 0 1 0 -{2} 0 A {2} {2} 0 1 0 {2} 0 Z\"><title>{3}</title></path>",
+          class, outer_radius, inner_radius, label
+        ),
+      }
+      .unwrap();
+    }
+    writeln!(output, "  </g>").unwrap();
+  }
+  writeln!(output, "</svg>").unwrap();
+  trace_exit!();
+  output
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::planetary_system::constraints::Constraints;
+  use crate::astronomy::planetary_system::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_hazard_zones() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let planetary_system = Constraints::default().generate(&mut rng)?;
+    let hazard_zones = get_hazard_zones(0, &planetary_system);
+    print_var!(hazard_zones);
+    let planet_count = planetary_system.satellite_systems.satellite_systems.len();
+    let roche_limits = hazard_zones
+      .iter()
+      .filter(|zone| zone.kind == HazardKind::RocheLimit)
+      .count();
+    assert_eq!(roche_limits, planet_count);
+    let is_binary = matches!(planetary_system.host_star, HostStar::CloseBinaryStar(_));
+    assert_eq!(hazard_zones.iter().any(|zone| zone.center.is_none()), is_binary);
+    for zone in &hazard_zones {
+      if let HazardShape::Annulus {
+        inner_radius,
+        outer_radius,
+      } = zone.shape
+      {
+        assert!(inner_radius < outer_radius);
+      }
+    }
+    let svg = render_hazard_zones(&hazard_zones);
+    print_var!(svg);
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches("<title>").count(), hazard_zones.len());
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,245 @@
+use crate::astronomy::orbit::constants::KM_PER_AU;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::planetary_system::PlanetarySystem;
+use crate::astronomy::star_system::StarSystem;
+
+/// The smallest angle the naked eye can resolve, in radians (one arcminute).
+pub const NAKED_EYE_RESOLUTION: f64 = 2.908_882e-4;
+
+/// The span over which great comets are counted, in years.
+pub const GREAT_COMET_SPAN: f64 = 100.0;
+
+/// How much each feature of a star system contributes to its interest score.
+///
+/// These are just multipliers; set one to zero to ignore a feature entirely.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InterestWeights {
+  /// Each star beyond the first.
+  pub additional_star: f64,
+  /// Each planet capable of supporting conventional life.
+  pub habitable_planet: f64,
+  /// Each moon of a habitable planet, hanging in its sky.
+  pub habitable_planet_moon: f64,
+  /// Each adjacent pair of planets in a mean-motion resonance.
+  pub resonant_pair: f64,
+  /// Each giant planet, gas or ice.
+  pub gas_giant: f64,
+  /// Each dwarf planet, in an asteroid belt or beyond the planets.
+  pub dwarf_planet: f64,
+  /// Each ring system that can be made out with the naked eye from a planet
+  /// in the habitable zone, including that planet's own.
+  pub visible_rings: f64,
+  /// Each great comet expected in a century.
+  pub great_comet: f64,
+}
+
+impl Default for InterestWeights {
+  /// Habitable worlds matter most; everything else is scenery.
+  fn default() -> Self {
+    let additional_star = 2.0;
+    let habitable_planet = 5.0;
+    let habitable_planet_moon = 1.0;
+    let resonant_pair = 1.5;
+    let gas_giant = 0.5;
+    let dwarf_planet = 0.25;
+    let visible_rings = 2.0;
+    let great_comet = 0.1;
+    Self {
+      additional_star,
+      habitable_planet,
+      habitable_planet_moon,
+      resonant_pair,
+      gas_giant,
+      dwarf_planet,
+      visible_rings,
+      great_comet,
+    }
+  }
+}
+
+/// Count the ring systems that can be made out with the naked eye from some
+/// planet in the habitable zone.
+///
+/// A planet's own rings are always visible; another planet's count if they
+/// span at least the naked eye's resolution when the planets are closest.
+#[named]
+pub fn get_visible_ring_systems(planetary_system: &PlanetarySystem) -> usize {
+  trace_enter!();
+  let (inner, outer) = planetary_system.host_star.get_habitable_zone();
+  trace_var!(inner);
+  trace_var!(outer);
+  let satellite_systems = &planetary_system.satellite_systems.satellite_systems;
+  let observers = satellite_systems
+    .iter()
+    .map(|satellite_system| satellite_system.planet.get_semi_major_axis())
+    .filter(|semi_major_axis| (inner..=outer).contains(semi_major_axis))
+    .collect::<Vec<f64>>();
+  trace_var!(observers);
+  let result = satellite_systems
+    .iter()
+    .filter_map(|satellite_system| {
+      let ring_system = satellite_system.ring_system.as_ref()?;
+      let semi_major_axis = satellite_system.planet.get_semi_major_axis();
+      Some((ring_system.outer_radius, semi_major_axis))
+    })
+    .filter(|&(outer_radius, semi_major_axis)| {
+      observers.iter().any(|observer| {
+        let distance = (semi_major_axis - observer).abs() * KM_PER_AU;
+        distance == 0.0 || 2.0 * outer_radius / distance >= NAKED_EYE_RESOLUTION
+      })
+    })
+    .count();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Score how interesting a star system is likely to be.
+///
+/// This is a heuristic meant for sorting a large batch of generated systems,
+/// so the absolute value means nothing; only comparisons under the same
+/// weights do.
+#[named]
+pub fn get_interest_score(star_system: &StarSystem, weights: &InterestWeights) -> f64 {
+  trace_enter!();
+  trace_var!(weights);
+  let mut result = weights.additional_star * (star_system.get_stellar_count() as f64 - 1.0);
+  for planetary_system in star_system.get_planetary_systems() {
+    for satellite_system in planetary_system.satellite_systems.satellite_systems.iter() {
+      use Planet::*;
+      match &satellite_system.planet {
+        TerrestrialPlanet(terrestrial_planet) if terrestrial_planet.is_habitable() => {
+          result += weights.habitable_planet;
+          result += weights.habitable_planet_moon * satellite_system.moons.moons.len() as f64;
+        },
+        TerrestrialPlanet(_) => {},
+        GasGiantPlanet(_) | IceGiantPlanet(_) => result += weights.gas_giant,
+      }
+    }
+    let resonant_pairs = planetary_system.get_resonances().iter().flatten().count();
+    trace_var!(resonant_pairs);
+    result += weights.resonant_pair * resonant_pairs as f64;
+    let dwarf_planets = planetary_system
+      .asteroid_belt
+      .iter()
+      .map(|asteroid_belt| asteroid_belt.dwarf_planets.len())
+      .chain(
+        planetary_system
+          .kuiper_belt
+          .iter()
+          .map(|kuiper_belt| kuiper_belt.dwarf_planets.len()),
+      )
+      .sum::<usize>();
+    trace_var!(dwarf_planets);
+    result += weights.dwarf_planet * dwarf_planets as f64;
+    let visible_rings = get_visible_ring_systems(planetary_system);
+    trace_var!(visible_rings);
+    result += weights.visible_rings * visible_rings as f64;
+    let great_comets = planetary_system
+      .oort_cloud
+      .as_ref()
+      .map_or(0.0, |oort_cloud| oort_cloud.get_expected_great_comets(GREAT_COMET_SPAN));
+    trace_var!(great_comets);
+    result += weights.great_comet * great_comets;
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Pick out the most interesting star systems from a batch, best first.
+#[named]
+pub fn get_most_interesting<'a>(
+  star_systems: &'a [StarSystem],
+  weights: &InterestWeights,
+  count: usize,
+) -> Vec<&'a StarSystem> {
+  trace_enter!();
+  trace_var!(count);
+  let mut scored: Vec<(f64, &StarSystem)> = star_systems
+    .iter()
+    .map(|star_system| (get_interest_score(star_system, weights), star_system))
+    .collect();
+  scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+  let result: Vec<&StarSystem> = scored
+    .into_iter()
+    .take(count)
+    .map(|(_, star_system)| star_system)
+    .collect();
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::star_system::constraints::Constraints;
+  use crate::astronomy::star_system::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_most_interesting() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let mut star_systems = Vec::new();
+    for _ in 0..10 {
+      star_systems.push(Constraints::default().generate(&mut rng)?);
+    }
+    let weights = InterestWeights::default();
+    let most_interesting = get_most_interesting(&star_systems, &weights, 3);
+    assert_eq!(most_interesting.len(), 3);
+    let scores: Vec<f64> = most_interesting
+      .iter()
+      .map(|star_system| get_interest_score(star_system, &weights))
+      .collect();
+    print_var!(scores);
+    assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+    let best = star_systems
+      .iter()
+      .map(|star_system| get_interest_score(star_system, &weights))
+      .fold(f64::MIN, f64::max);
+    assert_approx_eq!(scores[0], best);
+    let indifferent = InterestWeights {
+      additional_star: 0.0,
+      habitable_planet: 0.0,
+      habitable_planet_moon: 0.0,
+      resonant_pair: 0.0,
+      gas_giant: 0.0,
+      dwarf_planet: 0.0,
+      visible_rings: 0.0,
+      great_comet: 0.0,
+    };
+    assert_approx_eq!(get_interest_score(&star_systems[0], &indifferent), 0.0);
+    for star_system in star_systems.iter() {
+      let rings = InterestWeights {
+        visible_rings: 1.0,
+        ..indifferent
+      };
+      let visible_rings = star_system
+        .get_planetary_systems()
+        .iter()
+        .map(|planetary_system| get_visible_ring_systems(planetary_system))
+        .sum::<usize>();
+      assert_approx_eq!(get_interest_score(star_system, &rings), visible_rings as f64);
+      let comets = InterestWeights {
+        great_comet: 1.0,
+        ..indifferent
+      };
+      let great_comets = star_system
+        .get_planetary_systems()
+        .iter()
+        .filter_map(|planetary_system| planetary_system.oort_cloud.as_ref())
+        .map(|oort_cloud| oort_cloud.get_expected_great_comets(GREAT_COMET_SPAN))
+        .sum::<f64>();
+      assert_approx_eq!(get_interest_score(star_system, &comets), great_comets);
+    }
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -1,8 +1,24 @@
+use serde::Serialize;
+
+use crate::astronomy::planetary_system::PlanetarySystem;
+use crate::astronomy::star::name::NameKind;
+use crate::astronomy::star_subsystem::hierarchy::Hierarchy;
 use crate::astronomy::star_subsystem::StarSubsystem;
+use crate::astronomy::time::Time;
 
 pub mod constraints;
 pub mod error;
 use error::*;
+pub mod fact_sheet;
+use fact_sheet::{get_fact_sheet, FactSheet};
+pub mod hazard_zones;
+use hazard_zones::{get_hazard_zones, render_hazard_zones, HazardZone};
+pub mod interest;
+use interest::{get_interest_score, InterestWeights};
+pub mod orrery;
+use orrery::{get_orrery_positions, OrreryPosition};
+pub mod snapshot;
+use snapshot::{get_positions, Snapshot};
 
 /// The `StarSystem` type.
 ///
@@ -16,7 +32,7 @@ use error::*;
 /// bound to those stars in some interesting way.
 ///
 /// And I use "solar system" only to refer to our (your and my) star system.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct StarSystem {
   /// The basic configuration of the host star(s).
   pub star_subsystem: StarSubsystem,
@@ -37,6 +53,18 @@ impl StarSystem {
     result
   }
 
+  /// Retrieve or calculate the total luminosity of the stars.
+  ///
+  /// Calculated in Lsol.
+  #[named]
+  pub fn get_luminosity(&self) -> f64 {
+    trace_enter!();
+    let result = self.star_subsystem.get_luminosity();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Retrieve or calculate the total number of stars in the system.
   #[named]
   pub fn get_stellar_count(&self) -> u8 {
@@ -47,6 +75,121 @@ impl StarSystem {
     result
   }
 
+  /// Rename the stars and planets with the given function.
+  #[named]
+  pub fn rename<F: FnMut(&str, NameKind) -> String>(&mut self, rename: &mut F) {
+    trace_enter!();
+    self.star_subsystem.rename(rename);
+    trace_exit!();
+  }
+
+  /// Retrieve the orbital hierarchy of the stars in this star system.
+  #[named]
+  pub fn get_hierarchy(&self) -> Hierarchy {
+    trace_enter!();
+    let result = self.star_subsystem.get_hierarchy();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve all of the planetary systems in this star system.
+  #[named]
+  pub fn get_planetary_systems(&self) -> Vec<&PlanetarySystem> {
+    trace_enter!();
+    let result = self.star_subsystem.get_planetary_systems();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve all of the planetary systems in this star system mutably.
+  #[named]
+  pub fn get_planetary_systems_mut(&mut self) -> Vec<&mut PlanetarySystem> {
+    trace_enter!();
+    let result = self.star_subsystem.get_planetary_systems_mut();
+    trace_exit!();
+    result
+  }
+
+  /// Find where everything in this star system is at a time after epoch.
+  #[named]
+  pub fn snapshot_at(&self, time: Time) -> Snapshot {
+    trace_enter!();
+    trace_var!(time);
+    let positions = self
+      .get_planetary_systems()
+      .into_iter()
+      .enumerate()
+      .flat_map(|(index, planetary_system)| get_positions(index, planetary_system, time))
+      .collect();
+    let result = Snapshot { time, positions };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the angular position of every body at epoch, for drawing the system
+  /// from above.
+  #[named]
+  pub fn get_orrery(&self) -> Vec<OrreryPosition> {
+    trace_enter!();
+    let result = self
+      .get_planetary_systems()
+      .into_iter()
+      .enumerate()
+      .flat_map(|(index, planetary_system)| get_orrery_positions(index, planetary_system))
+      .collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the regions of this star system that navigation should avoid.
+  #[named]
+  pub fn get_hazard_zones(&self) -> Vec<HazardZone> {
+    trace_enter!();
+    let result = self
+      .get_planetary_systems()
+      .into_iter()
+      .enumerate()
+      .flat_map(|(index, planetary_system)| get_hazard_zones(index, planetary_system))
+      .collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Render the regions of this star system that navigation should avoid as
+  /// SVG.
+  #[named]
+  pub fn render_hazard_zones(&self) -> String {
+    trace_enter!();
+    let result = render_hazard_zones(&self.get_hazard_zones());
+    trace_exit!();
+    result
+  }
+
+  /// Score how interesting this star system is likely to be.
+  #[named]
+  pub fn get_interest_score(&self, weights: &InterestWeights) -> f64 {
+    trace_enter!();
+    let result = get_interest_score(self, weights);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Summarize this star system in a flat form, for display.
+  #[named]
+  pub fn fact_sheet(&self) -> FactSheet {
+    trace_enter!();
+    let result = get_fact_sheet(self);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Indicate whether this star system is capable of supporting conventional life.
   #[named]
   pub fn check_habitable(&self) -> Result<(), Error> {
@@ -77,7 +220,11 @@ pub mod test {
   use rand::prelude::*;
 
   use super::constraints::Constraints;
+  use super::snapshot::Body;
   use super::*;
+  use crate::astronomy::host_star::HostStar;
+  use crate::generator::seeded_rng;
+  use crate::math::probability::Probability;
   use crate::test::*;
 
   #[named]
@@ -93,4 +240,131 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_snapshot_at() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let constraints = Constraints::builder()
+      .binary_probability(Probability::ALWAYS)
+      .distant_binary_probability(Probability::NEVER)
+      .retries(20)
+      .build()
+      .unwrap();
+    let star_system = constraints.generate(&mut rng)?;
+    let planetary_system = star_system.get_planetary_systems()[0];
+    let planet_count = planetary_system.satellite_systems.satellite_systems.len();
+    let start = star_system.snapshot_at(Time::EPOCH);
+    print_var!(start);
+    let stars = start
+      .positions
+      .iter()
+      .filter(|position| matches!(position.body, Body::Star(_)))
+      .collect::<Vec<_>>();
+    assert_eq!(stars.len(), 2);
+    // The barycenter stays put.
+    let (primary, secondary) = match &planetary_system.host_star {
+      HostStar::CloseBinaryStar(close_binary_star) => {
+        (close_binary_star.primary.mass, close_binary_star.secondary.mass)
+      },
+      HostStar::Star(_) | HostStar::StellarRemnant(_) | HostStar::BrownDwarf(_) => unreachable!(),
+    };
+    assert_approx_eq!(
+      primary * stars[0].coordinates.0 + secondary * stars[1].coordinates.0,
+      0.0
+    );
+    for planet_index in 0..planet_count {
+      let planet = &planetary_system.satellite_systems.satellite_systems[planet_index].planet;
+      let later = star_system.snapshot_at(Time::from_years(planet.get_orbital_period() / 4.0));
+      let get_coordinates = |snapshot: &Snapshot| {
+        snapshot
+          .positions
+          .iter()
+          .find(|position| position.body == Body::Planet(planet_index))
+          .unwrap()
+          .coordinates
+      };
+      let (x, y, z) = get_coordinates(&later);
+      let distance = (x * x + y * y + z * z).sqrt();
+      assert!(distance >= planet.get_orbit().get_periapsis() - 1e-9);
+      assert!(distance <= planet.get_orbit().get_apoapsis() + 1e-9);
+      assert_ne!(get_coordinates(&start), (x, y, z));
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_orrery() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    // A habitable star doesn't promise any planets, so keep looking.
+    let star_system = loop {
+      let star_system = Constraints::habitable().generate(&mut rng)?;
+      if star_system
+        .get_planetary_systems()
+        .iter()
+        .any(|planetary_system| !planetary_system.satellite_systems.satellite_systems.is_empty())
+      {
+        break star_system;
+      }
+    };
+    let orrery = star_system.get_orrery();
+    print_var!(orrery);
+    assert!(orrery.iter().all(|position| (0.0..360.0).contains(&position.longitude)));
+    let planets = orrery
+      .iter()
+      .filter(|position| matches!(position.body, Body::Planet(_)))
+      .collect::<Vec<_>>();
+    assert!(!planets.is_empty());
+    // The planets should be scattered, not lined up.
+    if planets.len() > 1 {
+      assert!(planets
+        .iter()
+        .any(|position| position.longitude != planets[0].longitude));
+    }
+    // The orrery agrees with a snapshot at epoch.
+    let snapshot = star_system.snapshot_at(Time::EPOCH);
+    for planet in planets.iter() {
+      let (x, y, _) = snapshot
+        .positions
+        .iter()
+        .find(|position| position.body == planet.body && position.planetary_system == planet.planetary_system)
+        .unwrap()
+        .coordinates;
+      assert_approx_eq!(x.hypot(y), planet.distance);
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_fact_sheet() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let star_system = Constraints::habitable().generate(&mut seeded_rng(2))?;
+    let fact_sheet = star_system.fact_sheet();
+    print_var!(fact_sheet);
+    assert_eq!(fact_sheet.name, star_system.name);
+    assert_eq!(fact_sheet.stars.len(), star_system.get_stellar_count() as usize);
+    let planet_count: usize = star_system
+      .get_planetary_systems()
+      .iter()
+      .map(|planetary_system| planetary_system.satellite_systems.satellite_systems.len())
+      .sum();
+    assert_eq!(fact_sheet.planets.len(), planet_count);
+    assert_eq!(
+      fact_sheet.is_habitable,
+      fact_sheet.planets.iter().any(|planet| planet.is_habitable)
+    );
+    trace_exit!();
+    Ok(())
+  }
 }
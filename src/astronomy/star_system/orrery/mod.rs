@@ -0,0 +1,84 @@
+use serde::Serialize;
+
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::planetary_system::PlanetarySystem;
+use crate::astronomy::star_system::snapshot::Body;
+use crate::astronomy::time::Time;
+
+/// Where a body sits around whatever it orbits at epoch, as seen from above.
+///
+/// This is all a static render needs: an angle and a distance.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct OrreryPosition {
+  /// The index of the planetary system the body belongs to.
+  pub planetary_system: usize,
+  /// The body.
+  pub body: Body,
+  /// The longitude, in degrees, in the reference plane of whatever the body
+  /// orbits.
+  pub longitude: f64,
+  /// The distance from whatever the body orbits, projected onto the same
+  /// plane; in AU for stars and planets, and KM for moons.
+  pub distance: f64,
+}
+
+/// Project coordinates onto the reference plane as a longitude (in degrees)
+/// and a distance.
+fn get_polar((x, y, _): (f64, f64, f64)) -> (f64, f64) {
+  (y.atan2(x).to_degrees().rem_euclid(360.0), x.hypot(y))
+}
+
+/// Get the angular positions at epoch of every body in a planetary system.
+///
+/// Stars are placed about their barycenter, planets about their host star,
+/// and moons about their planet.
+#[named]
+pub fn get_orrery_positions(index: usize, planetary_system: &PlanetarySystem) -> Vec<OrreryPosition> {
+  trace_enter!();
+  trace_var!(index);
+  let position = |body: Body, (longitude, distance): (f64, f64)| OrreryPosition {
+    planetary_system: index,
+    body,
+    longitude,
+    distance,
+  };
+  let mut result = vec![];
+  match &planetary_system.host_star {
+    HostStar::Star(_) | HostStar::StellarRemnant(_) | HostStar::BrownDwarf(_) => {
+      result.push(position(Body::Star(0), (0.0, 0.0)))
+    },
+    HostStar::CloseBinaryStar(close_binary_star) => {
+      let combined_mass = close_binary_star.get_stellar_mass();
+      let period = (close_binary_star.orbit.semi_major_axis.powf(3.0) / combined_mass).sqrt();
+      let (longitude, separation) = get_polar(
+        close_binary_star
+          .orbit
+          .position_at(Time::EPOCH, Time::from_years(period)),
+      );
+      // The stars sit on opposite sides of their barycenter.
+      let primary_distance = separation * close_binary_star.secondary.mass / combined_mass;
+      let secondary_distance = separation * close_binary_star.primary.mass / combined_mass;
+      result.push(position(Body::Star(0), ((longitude + 180.0) % 360.0, primary_distance)));
+      result.push(position(Body::Star(1), (longitude, secondary_distance)));
+    },
+  }
+  for (planet_index, satellite_system) in planetary_system.satellite_systems.satellite_systems.iter().enumerate() {
+    let planet = &satellite_system.planet;
+    let planet_coordinates = planet
+      .get_orbit()
+      .position_at(Time::EPOCH, Time::from_years(planet.get_orbital_period()));
+    result.push(position(Body::Planet(planet_index), get_polar(planet_coordinates)));
+    for (moon_index, moon) in satellite_system.moons.moons.iter().enumerate() {
+      let moon_coordinates = moon
+        .orbit
+        .position_at(Time::EPOCH, Time::from_days(moon.sidereal_orbital_period));
+      result.push(position(
+        Body::Moon(planet_index, moon_index),
+        get_polar(moon_coordinates),
+      ));
+    }
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
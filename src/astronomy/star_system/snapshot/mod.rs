@@ -0,0 +1,101 @@
+use serde::Serialize;
+
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::orbit::constants::KM_PER_AU;
+use crate::astronomy::planetary_system::PlanetarySystem;
+use crate::astronomy::time::Time;
+
+/// Which body in a planetary system a position belongs to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum Body {
+  /// A star, by index; 0 is the primary, and 1 the secondary of a close
+  /// binary.
+  Star(usize),
+  /// A planet, by index among the satellite systems.
+  Planet(usize),
+  /// A moon, by the index of its planet and its index among that planet's
+  /// moons.
+  Moon(usize, usize),
+}
+
+/// Where a body is at a particular time.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct BodyPosition {
+  /// The index of the planetary system the body belongs to.
+  pub planetary_system: usize,
+  /// The body.
+  pub body: Body,
+  /// The position of the body, in AU from the barycenter of its host star.
+  pub coordinates: (f64, f64, f64),
+}
+
+/// The positions of everything in a star system at a particular time.
+///
+/// Positions are grouped by planetary system; the two halves of a distant
+/// binary are each centered on their own host star, since they take
+/// millennia to swing around one another.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Snapshot {
+  /// The time after epoch.
+  pub time: Time,
+  /// The positions of all of the bodies.
+  pub positions: Vec<BodyPosition>,
+}
+
+/// Get the positions of every body in a planetary system at a time after
+/// epoch.
+#[named]
+pub fn get_positions(index: usize, planetary_system: &PlanetarySystem, time: Time) -> Vec<BodyPosition> {
+  trace_enter!();
+  trace_var!(index);
+  trace_var!(time);
+  let position = |body: Body, coordinates: (f64, f64, f64)| BodyPosition {
+    planetary_system: index,
+    body,
+    coordinates,
+  };
+  let mut result = vec![];
+  match &planetary_system.host_star {
+    HostStar::Star(_) | HostStar::StellarRemnant(_) | HostStar::BrownDwarf(_) => {
+      result.push(position(Body::Star(0), (0.0, 0.0, 0.0)))
+    },
+    HostStar::CloseBinaryStar(close_binary_star) => {
+      let combined_mass = close_binary_star.get_stellar_mass();
+      let period = (close_binary_star.orbit.semi_major_axis.powf(3.0) / combined_mass).sqrt();
+      let (x, y, z) = close_binary_star.orbit.position_at(time, Time::from_years(period));
+      // Each star sits opposite the other about the barycenter, at a distance
+      // in inverse proportion to its mass.
+      let primary_share = -close_binary_star.secondary.mass / combined_mass;
+      let secondary_share = close_binary_star.primary.mass / combined_mass;
+      result.push(position(
+        Body::Star(0),
+        (x * primary_share, y * primary_share, z * primary_share),
+      ));
+      result.push(position(
+        Body::Star(1),
+        (x * secondary_share, y * secondary_share, z * secondary_share),
+      ));
+    },
+  }
+  for (planet_index, satellite_system) in planetary_system.satellite_systems.satellite_systems.iter().enumerate() {
+    let planet = &satellite_system.planet;
+    let planet_coordinates = planet
+      .get_orbit()
+      .position_at(time, Time::from_years(planet.get_orbital_period()));
+    result.push(position(Body::Planet(planet_index), planet_coordinates));
+    for (moon_index, moon) in satellite_system.moons.moons.iter().enumerate() {
+      let (x, y, z) = moon
+        .orbit
+        .position_at(time, Time::from_days(moon.sidereal_orbital_period));
+      let coordinates = (
+        planet_coordinates.0 + x / KM_PER_AU,
+        planet_coordinates.1 + y / KM_PER_AU,
+        planet_coordinates.2 + z / KM_PER_AU,
+      );
+      result.push(position(Body::Moon(planet_index, moon_index), coordinates));
+    }
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
@@ -6,6 +6,8 @@ use crate::astronomy::stellar_neighbor::error::Error;
 use crate::astronomy::stellar_neighbor::math::point::get_random_point_in_sphere;
 use crate::astronomy::stellar_neighbor::StellarNeighbor;
 use crate::astronomy::stellar_neighborhood::constants::STELLAR_NEIGHBORHOOD_RADIUS;
+use crate::generator::Generator;
+use crate::merge::Merge;
 
 /// Constraints for creating a stellar neighborhood.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -48,6 +50,21 @@ impl Constraints {
     trace_var!(z);
     let coordinates = (x, y, z);
     trace_var!(coordinates);
+    let result = self.generate_at(rng, coordinates);
+    trace_exit!();
+    result
+  }
+
+  /// Generate a stellar neighbor at the given coordinates, in Ly.
+  #[named]
+  pub fn generate_at<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    coordinates: (f64, f64, f64),
+  ) -> Result<StellarNeighbor, Error> {
+    trace_enter!();
+    trace_var!(coordinates);
+    let (x, y, z) = coordinates;
     let distance = (x.powf(2.0) + y.powf(2.0) + z.powf(2.0)).sqrt();
     let system_constraints = self.system_constraints.unwrap_or(StarSystemConstraints::default());
     let star_system = system_constraints.generate(rng)?;
@@ -65,6 +82,19 @@ impl Constraints {
   }
 }
 
+impl Generator for Constraints {
+  type Output = StellarNeighbor;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<StellarNeighbor, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
 impl Default for Constraints {
   /// No constraints, just let it all hang out.
   fn default() -> Self {
@@ -77,6 +107,15 @@ impl Default for Constraints {
   }
 }
 
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      radius: overrides.radius.or(self.radius),
+      system_constraints: self.system_constraints.merged_with(&overrides.system_constraints),
+    }
+  }
+}
+
 #[cfg(test)]
 pub mod test {
 
@@ -95,6 +134,12 @@ pub mod test {
     let stellar_neighbor = &Constraints::default().generate(&mut rng)?;
     trace_var!(stellar_neighbor);
     print_var!(stellar_neighbor);
+    let galactic_coordinates = stellar_neighbor.get_galactic_coordinates();
+    print_var!(galactic_coordinates);
+    assert_approx_eq!(galactic_coordinates.distance, stellar_neighbor.distance);
+    let equatorial_coordinates = stellar_neighbor.get_equatorial_coordinates();
+    print_var!(equatorial_coordinates);
+    assert_approx_eq!(equatorial_coordinates.distance, stellar_neighbor.distance);
     trace_exit!();
     Ok(())
   }
@@ -0,0 +1,168 @@
+use rand::prelude::*;
+use rand_distr::StandardNormal;
+
+use crate::astronomy::stellar_neighbor::math::point::get_random_point_in_sphere;
+
+/// How stars are spread through a neighborhood.
+///
+/// The z-axis points out of the galactic plane.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SpatialDensityModel {
+  /// Evenly, wherever.
+  #[default]
+  Uniform,
+  /// Thinning out exponentially away from the galactic plane.
+  ExponentialDisk {
+    /// The scale height of the disk, in Ly.
+    scale_height: f64,
+    /// The height of the neighborhood's center above the plane, in Ly.
+    height: f64,
+  },
+  /// Partly in loose clumps, like moving groups or dissolving open clusters.
+  Clustered {
+    /// The number of clumps.
+    cluster_count: u8,
+    /// The spread of each clump, in Ly.
+    cluster_radius: f64,
+    /// The fraction of stars that belong to a clump.
+    clustered_fraction: f64,
+  },
+}
+
+impl SpatialDensityModel {
+  /// Get a sampler for points within a neighborhood of the given radius.
+  ///
+  /// Any clumps are placed now, so use the same sampler for every star in the
+  /// neighborhood.
+  #[named]
+  pub fn get_sampler<R: Rng + ?Sized>(&self, rng: &mut R, radius: f64) -> SpatialDensitySampler {
+    trace_enter!();
+    trace_var!(radius);
+    let cluster_centers = match self {
+      SpatialDensityModel::Clustered { cluster_count, .. } => (0..*cluster_count)
+        .map(|_| {
+          let (x, y, z) = get_random_point_in_sphere(rng);
+          (x * radius, y * radius, z * radius)
+        })
+        .collect(),
+      _ => vec![],
+    };
+    trace_var!(cluster_centers);
+    let result = SpatialDensitySampler {
+      model: *self,
+      radius,
+      cluster_centers,
+    };
+    trace_exit!();
+    result
+  }
+}
+
+/// Draws points within a neighborhood according to a density model.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpatialDensitySampler {
+  /// The density model.
+  pub model: SpatialDensityModel,
+  /// The radius of the neighborhood, in Ly.
+  pub radius: f64,
+  /// The centers of any clumps.
+  pub cluster_centers: Vec<(f64, f64, f64)>,
+}
+
+impl SpatialDensitySampler {
+  /// Draw a random point within the neighborhood.
+  #[named]
+  pub fn get_random_point<R: Rng + ?Sized>(&self, rng: &mut R) -> (f64, f64, f64) {
+    trace_enter!();
+    use SpatialDensityModel::*;
+    let result = match self.model {
+      Uniform => self.get_uniform_point(rng),
+      ExponentialDisk { scale_height, height } => {
+        // Accept in proportion to the density, relative to the densest point.
+        let nearest_height = (height.abs() - self.radius).max(0.0);
+        loop {
+          let point = self.get_uniform_point(rng);
+          let acceptance = (-((height + point.2).abs() - nearest_height) / scale_height).exp();
+          if rng.gen_bool(acceptance.clamp(0.0, 1.0)) {
+            break point;
+          }
+        }
+      },
+      Clustered {
+        cluster_radius,
+        clustered_fraction,
+        ..
+      } if !self.cluster_centers.is_empty() && rng.gen_bool(clustered_fraction.clamp(0.0, 1.0)) => {
+        let (cx, cy, cz) = *self.cluster_centers.choose(rng).unwrap();
+        loop {
+          let x = cx + cluster_radius * rng.sample::<f64, _>(StandardNormal);
+          let y = cy + cluster_radius * rng.sample::<f64, _>(StandardNormal);
+          let z = cz + cluster_radius * rng.sample::<f64, _>(StandardNormal);
+          if x.powf(2.0) + y.powf(2.0) + z.powf(2.0) <= self.radius.powf(2.0) {
+            break (x, y, z);
+          }
+        }
+      },
+      Clustered { .. } => self.get_uniform_point(rng),
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Draw a point anywhere within the neighborhood.
+  fn get_uniform_point<R: Rng + ?Sized>(&self, rng: &mut R) -> (f64, f64, f64) {
+    let (x, y, z) = get_random_point_in_sphere(rng);
+    (x * self.radius, y * self.radius, z * self.radius)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_exponential_disk() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let sampler = SpatialDensityModel::ExponentialDisk {
+      scale_height: 2.0,
+      height: 0.0,
+    }
+    .get_sampler(&mut rng, 10.0);
+    let points = (0..500).map(|_| sampler.get_random_point(&mut rng)).collect::<Vec<_>>();
+    assert!(points.iter().all(|point| point.0.hypot(point.1).hypot(point.2) <= 10.0));
+    let near_plane = points.iter().filter(|point| point.2.abs() < 2.0).count();
+    let far_from_plane = points.iter().filter(|point| point.2.abs() > 6.0).count();
+    print_var!((near_plane, far_from_plane));
+    assert!(near_plane > 4 * far_from_plane);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_clustered() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let sampler = SpatialDensityModel::Clustered {
+      cluster_count: 1,
+      cluster_radius: 0.5,
+      clustered_fraction: 1.0,
+    }
+    .get_sampler(&mut rng, 10.0);
+    let (cx, cy, cz) = sampler.cluster_centers[0];
+    for _ in 0..100 {
+      let (x, y, z) = sampler.get_random_point(&mut rng);
+      assert!(x.powf(2.0) + y.powf(2.0) + z.powf(2.0) <= 100.0);
+      assert!((x - cx).hypot(y - cy).hypot(z - cz) < 5.0);
+    }
+    trace_exit!();
+  }
+}
@@ -1 +1,2 @@
+pub mod density;
 pub mod point;
@@ -1,8 +1,13 @@
+use serde::Serialize;
+
+use crate::astronomy::coordinates::{CartesianCoordinates, EquatorialCoordinates, GalacticCoordinates};
 use crate::astronomy::star_system::StarSystem;
+use crate::astronomy::stellar_neighborhood::math::interaction::get_apparent_magnitude;
 
 pub mod constraints;
 pub mod error;
-pub mod math;
+pub(crate) mod math;
+pub use math::density::{SpatialDensityModel, SpatialDensitySampler};
 
 /// The `StellarNeighbor` class.
 ///
@@ -10,9 +15,11 @@ pub mod math;
 ///
 /// This is just a combination of a fully-fledged star system and a set of 3-D
 /// coordinates so that we can place it relative to our primary star system.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct StellarNeighbor {
   /// Each coordinate (x,y,z) is a distance (in light years) from the origin.
+  ///
+  /// The axes are galactic; see `CartesianCoordinates`.
   pub coordinates: (f64, f64, f64),
   /// The details of this particular star system.
   pub star_system: StarSystem,
@@ -23,6 +30,34 @@ pub struct StellarNeighbor {
 }
 
 impl StellarNeighbor {
+  /// Get the position, in Ly, along galactic axes.
+  #[named]
+  pub fn get_cartesian_coordinates(&self) -> CartesianCoordinates {
+    trace_enter!();
+    let result = CartesianCoordinates::from(self.coordinates);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the position in galactic coordinates, as seen from the origin.
+  #[named]
+  pub fn get_galactic_coordinates(&self) -> GalacticCoordinates {
+    trace_enter!();
+    let result = self.get_cartesian_coordinates().to_galactic();
+    trace_exit!();
+    result
+  }
+
+  /// Get the position in equatorial coordinates, as seen from the origin.
+  #[named]
+  pub fn get_equatorial_coordinates(&self) -> EquatorialCoordinates {
+    trace_enter!();
+    let result = self.get_cartesian_coordinates().to_equatorial();
+    trace_exit!();
+    result
+  }
+
   /// Retrieve or calculate the total mass of the stars.
   ///
   /// Calculated in Msol.
@@ -35,6 +70,28 @@ impl StellarNeighbor {
     result
   }
 
+  /// Retrieve or calculate the total luminosity of the stars.
+  ///
+  /// Calculated in Lsol.
+  #[named]
+  pub fn get_luminosity(&self) -> f64 {
+    trace_enter!();
+    let result = self.star_system.get_luminosity();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the apparent visual magnitude of the stars as seen from the origin.
+  #[named]
+  pub fn get_apparent_magnitude(&self) -> f64 {
+    trace_enter!();
+    let result = get_apparent_magnitude(self);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Retrieve or calculate the total number of stars in the system.
   #[named]
   pub fn get_stellar_count(&self) -> u8 {
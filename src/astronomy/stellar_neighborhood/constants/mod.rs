@@ -1,3 +1,5 @@
+use crate::math::probability::Probability;
+
 /// The radius of our stellar neighborhood.
 ///
 /// This may be flexible or changed at some point, but for the time being I'm
@@ -13,3 +15,174 @@ pub const STELLAR_NEIGHBORHOOD_RADIUS: f64 = 10.0;
 ///
 /// Measured in s/ly^3, or stars per cubic light year.
 pub const STELLAR_NEIGHBORHOOD_DENSITY: f64 = 0.004;
+
+/// The number density of rogue planets in our neighborhood.
+///
+/// Microlensing surveys suggest there are a couple of rogue planets for
+/// every star, most of them no heavier than Earth.
+///
+/// Measured in rogue planets per cubic light year.
+pub const ROGUE_PLANET_DENSITY: f64 = 0.008;
+
+/// The separation below which two systems disturb one another's Oort clouds.
+///
+/// This is roughly the outer edge of our own Oort cloud; anything passing
+/// within it is liable to send a shower of comets inward.
+///
+/// Measured in Ly, or light years.
+pub const OORT_CLOUD_DISTURBANCE_DISTANCE: f64 = 1.5;
+
+/// The widest angular separation at which two systems read as a double star
+/// in the night sky of the origin.
+///
+/// Measured in degrees.
+pub const BRIGHT_DOUBLE_MAXIMUM_SEPARATION: f64 = 3.0;
+
+/// The narrowest angular separation at which the naked eye can split a
+/// double star.
+///
+/// Measured in degrees.
+pub const BRIGHT_DOUBLE_MINIMUM_SEPARATION: f64 = 1.0 / 60.0;
+
+/// The faintest apparent visual magnitude at which both members of a double
+/// star still count as bright.
+pub const BRIGHT_DOUBLE_MAXIMUM_MAGNITUDE: f64 = 4.0;
+
+/// How far from the origin we look for past supernovae.
+///
+/// This is a good deal wider than the neighborhood itself, since a supernova
+/// shapes the interstellar medium for a hundred light years or more around.
+///
+/// Measured in Ly, or light years.
+pub const SUPERNOVA_HISTORY_RADIUS: f64 = 300.0;
+
+/// How far back we look for past supernovae.
+///
+/// Measured in Myr, or millions of years.
+pub const SUPERNOVA_HISTORY_SPAN: f64 = 20.0;
+
+/// The rate of supernovae per unit volume, at our stellar density.
+///
+/// This is roughly two per century spread across the disk of the Milky Way.
+///
+/// Measured in supernovae per cubic light year per Myr.
+pub const SUPERNOVA_RATE_DENSITY: f64 = 2.5e-9;
+
+/// The fraction of supernovae that are thermonuclear (Type Ia) explosions of
+/// white dwarfs, rather than the core collapse of massive stars.
+pub const TYPE_IA_PROBABILITY: Probability = Probability::from_const(0.25);
+
+/// The least massive star that will end in a core-collapse supernova.
+///
+/// Measured in Msol.
+pub const MINIMUM_CORE_COLLAPSE_MASS: f64 = 8.0;
+
+/// The most massive progenitor we'll consider.
+///
+/// Measured in Msol.
+pub const MAXIMUM_CORE_COLLAPSE_MASS: f64 = 40.0;
+
+/// The least massive progenitor that leaves a black hole rather than a
+/// neutron star.
+///
+/// Measured in Msol.
+pub const MINIMUM_BLACK_HOLE_PROGENITOR_MASS: f64 = 25.0;
+
+/// The slope of the (Salpeter) initial mass function for massive stars.
+pub const INITIAL_MASS_FUNCTION_SLOPE: f64 = 2.35;
+
+/// The mass of a white dwarf when it detonates.
+///
+/// Measured in Msol.
+pub const CHANDRASEKHAR_MASS: f64 = 1.4;
+
+/// The radius of a supernova shell ten thousand years after the explosion.
+///
+/// Measured in Ly, or light years.
+pub const SEDOV_TAYLOR_SHELL_RADIUS: f64 = 40.0;
+
+/// The age at which a shell stops expanding adiabatically and starts to
+/// sweep up the surrounding gas like a snowplow.
+///
+/// Measured in Myr, or millions of years.
+pub const SNOWPLOW_TRANSITION_AGE: f64 = 0.03;
+
+/// The age at which a shell slows to the speed of the surrounding gas and
+/// merges into it.
+///
+/// Measured in Myr, or millions of years.
+pub const SHELL_LIFETIME: f64 = 1.0;
+
+/// How much a supernova raises the metallicity, [Fe/H], of the stars that
+/// form from the gas it enriched.
+///
+/// Measured in dex.
+pub const SUPERNOVA_ENRICHMENT: f64 = 0.05;
+
+/// How long the shell of a supernova remnant glows visibly.
+///
+/// Measured in Myr, or millions of years.
+pub const SHELL_NEBULA_LIFETIME: f64 = 0.1;
+
+/// How long a young pulsar lights up a wind nebula around itself.
+///
+/// Measured in Myr, or millions of years.
+pub const PULSAR_WIND_NEBULA_LIFETIME: f64 = 0.02;
+
+/// How far the edge of a nebula strays from a perfect sphere, as a fraction
+/// of its radius.
+pub const NEBULA_ROUGHNESS: f64 = 0.25;
+
+/// How many lumps of filament there are across the face of a nebula.
+pub const NEBULA_FREQUENCY: f64 = 3.0;
+
+/// The typical speed at which a neutron star is kicked out of its supernova.
+///
+/// Measured in KM/sec.
+pub const NEUTRON_STAR_KICK_VELOCITY: f64 = 400.0;
+
+/// The typical speed at which a black hole is kicked out of its supernova.
+///
+/// Measured in KM/sec.
+pub const BLACK_HOLE_KICK_VELOCITY: f64 = 100.0;
+
+/// Light years traveled in a million years at one KM/sec.
+pub const LY_PER_MYR_PER_KM_PER_SEC: f64 = 3.336;
+
+/// The longest jump between two systems that the sector map draws a link
+/// for.
+///
+/// Measured in Ly, or light years.
+pub const SECTOR_MAP_JUMP_DISTANCE: f64 = 5.0;
+
+/// The spacing between the distance rings on the sector map.
+///
+/// Measured in Ly, or light years.
+pub const SECTOR_MAP_RING_SPACING: f64 = 2.0;
+
+/// The width (and height) of the sector map.
+///
+/// Measured in pixels.
+pub const SECTOR_MAP_SIZE: f64 = 800.0;
+
+/// The interest score, under the default weights, at or above which an
+/// explorer's log entry flags a system as worth a closer look.
+pub const EXPLORERS_LOG_REMARKABLE_INTEREST_SCORE: f64 = 8.0;
+
+/// The most planets an explorer's log entry describes individually.
+pub const EXPLORERS_LOG_PLANET_COUNT: usize = 2;
+
+/// The faintest apparent magnitude visible to the naked eye under a dark sky.
+pub const NAKED_EYE_LIMITING_MAGNITUDE: f64 = 6.5;
+
+/// The widest gap between two stars that a constellation's stick figure
+/// will bridge.
+///
+/// Measured in degrees.
+pub const CONSTELLATION_MAXIMUM_LINK_ANGLE: f64 = 40.0;
+
+/// The fewest stars that make up a constellation.
+pub const CONSTELLATION_MINIMUM_STARS: usize = 2;
+
+/// The most stars that make up a constellation.
+pub const CONSTELLATION_MAXIMUM_STARS: usize = 7;
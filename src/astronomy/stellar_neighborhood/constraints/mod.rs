@@ -1,11 +1,21 @@
 use rand::prelude::*;
-use std::f64::consts::PI;
 
+use crate::astronomy::rogue_planet::constraints::Constraints as RoguePlanetConstraints;
+use crate::astronomy::star::name::registry::NameRegistry;
+use crate::astronomy::star::name::theme::NameTheme;
 use crate::astronomy::star_system::constraints::Constraints as StarSystemConstraints;
 use crate::astronomy::stellar_neighbor::constraints::Constraints as StellarNeighborConstraints;
+use crate::astronomy::stellar_neighbor::math::density::SpatialDensityModel;
 use crate::astronomy::stellar_neighborhood::constants::*;
 use crate::astronomy::stellar_neighborhood::error::*;
+use crate::astronomy::stellar_neighborhood::math::count::*;
+use crate::astronomy::stellar_neighborhood::math::interaction::get_interactions;
+use crate::astronomy::stellar_neighborhood::math::supernova::{enrich_neighbors, get_supernovae};
+use crate::astronomy::stellar_neighborhood::name::generate_stellar_neighborhood_name;
+use crate::astronomy::stellar_neighborhood::spatial_index::SpatialIndex;
 use crate::astronomy::stellar_neighborhood::StellarNeighborhood;
+use crate::generator::Generator;
+use crate::merge::Merge;
 
 /// Constraints for creating a stellar neighborhood.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -16,6 +26,15 @@ pub struct Constraints {
   pub density: Option<f64>,
   /// Any constraints placed on the various neighbors.
   pub neighbor_constraints: Option<StellarNeighborConstraints>,
+  /// The density of rogue planets, in rogue planets per cubic light year.
+  pub rogue_planet_density: Option<f64>,
+  /// Any constraints placed on the rogue planets.
+  pub rogue_planet_constraints: Option<RoguePlanetConstraints>,
+  /// How the neighbors are spread through the neighborhood.
+  pub density_model: Option<SpatialDensityModel>,
+  /// The theme used for the names of the neighborhood, its stars, and its
+  /// planets.
+  pub name_theme: Option<NameTheme>,
 }
 
 impl Constraints {
@@ -28,8 +47,19 @@ impl Constraints {
     }
   }
 
+  /// Use a stellar density given in stars per cubic parsec, the unit most
+  /// surveys quote.
+  pub fn with_density_per_cubic_parsec(self, density: f64) -> Self {
+    let density = Some(get_density_per_cubic_light_year(density));
+    Self { density, ..self }
+  }
+
   /// Generate a random stellar neighborhood with the specified constraints.
   ///
+  /// The number of stars is drawn from a Poisson distribution, as though
+  /// they'd been scattered at random through the neighborhood at the given
+  /// density.  There's always at least one neighbor.
+  ///
   /// This may or may not be habitable.
   #[named]
   pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<StellarNeighborhood, Error> {
@@ -38,11 +68,9 @@ impl Constraints {
     trace_var!(radius);
     let density = self.density.unwrap_or(STELLAR_NEIGHBORHOOD_DENSITY);
     trace_var!(density);
-    let volume = (4.0 / 3.0) * PI * radius.powf(3.0);
-    trace_var!(volume);
-    let average_stars = density * volume;
+    let average_stars = get_expected_count(density, radius);
     trace_var!(average_stars);
-    let number_of_stars = rng.gen_range((0.875 * average_stars)..(1.125 * average_stars)) as usize;
+    let number_of_stars = get_random_count(rng, average_stars);
     trace_var!(number_of_stars);
     let mut neighbors = vec![];
     trace_var!(neighbors);
@@ -52,8 +80,12 @@ impl Constraints {
       system_constraints: Some(StarSystemConstraints::default()),
     });
     trace_var!(neighbor_constraints);
+    let density_model = self.density_model.unwrap_or_default();
+    trace_var!(density_model);
+    let sampler = density_model.get_sampler(rng, radius);
     loop {
-      let neighbor = neighbor_constraints.generate(rng)?;
+      let coordinates = sampler.get_random_point(rng);
+      let neighbor = neighbor_constraints.generate_at(rng, coordinates)?;
       star_count += neighbor.get_stellar_count() as usize;
       neighbors.push(neighbor);
       if star_count >= number_of_stars {
@@ -62,16 +94,80 @@ impl Constraints {
     }
     trace_var!(neighbors);
     trace_var!(star_count);
-    let result = StellarNeighborhood {
+    let rogue_planet_density = self.rogue_planet_density.unwrap_or(ROGUE_PLANET_DENSITY);
+    trace_var!(rogue_planet_density);
+    let average_rogue_planets = get_expected_count(rogue_planet_density, radius);
+    trace_var!(average_rogue_planets);
+    let number_of_rogue_planets = get_random_count(rng, average_rogue_planets);
+    trace_var!(number_of_rogue_planets);
+    let rogue_planet_constraints = RoguePlanetConstraints {
+      radius: Some(radius),
+      ..RoguePlanetConstraints::default()
+    }
+    .merged_with(&self.rogue_planet_constraints.unwrap_or_default());
+    trace_var!(rogue_planet_constraints);
+    let rogue_planets = (0..number_of_rogue_planets)
+      .map(|_| rogue_planet_constraints.generate(rng))
+      .collect::<Result<Vec<_>, _>>()?;
+    trace_var!(rogue_planets);
+    let interactions = get_interactions(&neighbors);
+    trace_var!(interactions);
+    let supernovae = get_supernovae(rng, density, &neighbors);
+    trace_var!(supernovae);
+    enrich_neighbors(&supernovae, &mut neighbors);
+    let name = generate_stellar_neighborhood_name(rng);
+    trace_var!(name);
+    let mut result = StellarNeighborhood {
+      name,
       radius,
       density,
       neighbors,
+      rogue_planets,
       star_count,
+      interactions,
+      supernovae,
+      spatial_index: SpatialIndex::default(),
     };
+    result.update_spatial_index();
+    if let Some(name_theme) = self.name_theme {
+      result.apply_name_theme(rng, name_theme);
+    }
     trace_var!(result);
     trace_exit!();
     Ok(result)
   }
+
+  /// Generate, giving every star and planet a name no other body in the
+  /// registry has.
+  ///
+  /// Pass the same registry to each neighborhood to keep names unique
+  /// across all of them.
+  #[named]
+  pub fn generate_with_name_registry<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+    registry: &mut NameRegistry,
+  ) -> Result<StellarNeighborhood, Error> {
+    trace_enter!();
+    let mut result = self.generate(rng)?;
+    result.register_names(registry);
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+}
+
+impl Generator for Constraints {
+  type Output = StellarNeighborhood;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<StellarNeighborhood, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
 }
 
 impl Default for Constraints {
@@ -80,10 +176,34 @@ impl Default for Constraints {
     let radius = Some(STELLAR_NEIGHBORHOOD_RADIUS);
     let density = Some(STELLAR_NEIGHBORHOOD_DENSITY);
     let neighbor_constraints = Some(StellarNeighborConstraints::default());
+    let rogue_planet_density = None;
+    let rogue_planet_constraints = None;
+    let density_model = None;
+    let name_theme = None;
     Self {
       radius,
       density,
       neighbor_constraints,
+      rogue_planet_density,
+      rogue_planet_constraints,
+      density_model,
+      name_theme,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      radius: overrides.radius.or(self.radius),
+      density: overrides.density.or(self.density),
+      neighbor_constraints: self.neighbor_constraints.merged_with(&overrides.neighbor_constraints),
+      rogue_planet_density: overrides.rogue_planet_density.or(self.rogue_planet_density),
+      rogue_planet_constraints: self
+        .rogue_planet_constraints
+        .merged_with(&overrides.rogue_planet_constraints),
+      density_model: overrides.density_model.or(self.density_model),
+      name_theme: overrides.name_theme.or(self.name_theme),
     }
   }
 }
@@ -94,6 +214,9 @@ pub mod test {
   use rand::prelude::*;
 
   use super::*;
+  use crate::astronomy::star::name::theme::STAR_CATALOGS;
+  use crate::astronomy::star::name::NameKind;
+  use crate::astronomy::star_subsystem::hierarchy::Hierarchy;
   use crate::test::*;
 
   #[named]
@@ -110,4 +233,140 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_generate_with_name_registry() -> Result<(), Error> {
+    init();
+    let mut rng = thread_rng();
+    let mut registry = NameRegistry::default();
+    let constraints = Constraints::default();
+    let mut names = vec![];
+    for _ in 0..2 {
+      let stellar_neighborhood = constraints.generate_with_name_registry(&mut rng, &mut registry)?;
+      for neighbor in stellar_neighborhood.neighbors.iter() {
+        for planetary_system in neighbor.star_system.get_planetary_systems() {
+          let hierarchy = Hierarchy::from_host_star(&planetary_system.host_star);
+          names.extend(hierarchy.get_star_names().into_iter().map(String::from));
+          names.extend(
+            planetary_system
+              .satellite_systems
+              .satellite_systems
+              .iter()
+              .map(|satellite_system| satellite_system.planet.get_name()),
+          );
+        }
+      }
+      names.extend(
+        stellar_neighborhood
+          .rogue_planets
+          .iter()
+          .map(|rogue_planet| rogue_planet.name.clone()),
+      );
+    }
+    let count = names.len();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), count);
+    assert_eq!(registry.names.len(), count);
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_name_theme() -> Result<(), Error> {
+    init();
+    let mut rng = thread_rng();
+    let constraints = Constraints {
+      rogue_planet_density: Some(0.05),
+      name_theme: Some(NameTheme::Catalog),
+      ..Constraints::default()
+    };
+    let mut stellar_neighborhood = constraints.generate(&mut rng)?;
+    print_var!(stellar_neighborhood.name);
+    let mut names = vec![];
+    stellar_neighborhood.rename(&mut |name, kind| {
+      names.push((name.to_string(), kind));
+      name.to_string()
+    });
+    print_var!(names);
+    assert!(names.iter().any(|(_, kind)| *kind == NameKind::Planet));
+    for (name, kind) in names {
+      match kind {
+        NameKind::Star => assert!(STAR_CATALOGS
+          .iter()
+          .any(|(catalog, _)| name.starts_with(&format!("{} ", catalog)))),
+        NameKind::Planet => assert!(name.starts_with("KOI-")),
+        NameKind::SystematicPlanet => {},
+      }
+    }
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_with_density_per_cubic_parsec() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let constraints = Constraints::default().with_density_per_cubic_parsec(0.14);
+    print_var!(constraints.density);
+    assert_approx_eq!(constraints.density.unwrap(), STELLAR_NEIGHBORHOOD_DENSITY, 0.0001);
+    let stellar_neighborhood = constraints.generate(&mut rng)?;
+    assert!(!stellar_neighborhood.neighbors.is_empty());
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_density_model() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let constraints = Constraints {
+      density_model: Some(SpatialDensityModel::Clustered {
+        cluster_count: 2,
+        cluster_radius: 1.0,
+        clustered_fraction: 0.5,
+      }),
+      ..Constraints::default()
+    };
+    let stellar_neighborhood = constraints.generate(&mut rng)?;
+    print_var!(stellar_neighborhood.neighbors.len());
+    assert!(stellar_neighborhood
+      .neighbors
+      .iter()
+      .all(|neighbor| neighbor.distance <= stellar_neighborhood.radius));
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_rogue_planets() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let crowded = Constraints {
+      rogue_planet_density: Some(0.05),
+      ..Constraints::default()
+    }
+    .generate(&mut rng)?;
+    print_var!(crowded.rogue_planets);
+    assert!(!crowded.rogue_planets.is_empty());
+    assert!(crowded
+      .rogue_planets
+      .iter()
+      .all(|rogue_planet| rogue_planet.distance <= crowded.radius));
+    let empty = Constraints {
+      rogue_planet_density: Some(0.0),
+      ..Constraints::default()
+    }
+    .generate(&mut rng)?;
+    assert!(empty.rogue_planets.is_empty());
+    trace_exit!();
+    Ok(())
+  }
 }
@@ -1,8 +1,15 @@
+use crate::astronomy::rogue_planet::error::Error as RoguePlanetError;
 use crate::astronomy::stellar_neighbor::error::Error as StellarNeighborError;
 
 /// Stellar Neighborhood errors.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Error {
+  /// No planet in the neighborhood has the given name.
+  PlanetNotFound,
+  /// The planet can't support conventional life.
+  PlanetNotHabitable,
+  /// Rogue Planet Error.
+  RoguePlanetError(RoguePlanetError),
   /// Stellar Neighbor Error.
   StellarNeighborError(StellarNeighborError),
 }
@@ -10,6 +17,12 @@ pub enum Error {
 honeyholt_define_brief!(Error, |error: &Error| {
   use Error::*;
   match error {
+    PlanetNotFound => "no planet in the neighborhood has that name".to_string(),
+    PlanetNotHabitable => "the planet is not habitable".to_string(),
+    RoguePlanetError(rogue_planet_error) => format!(
+      "an error occurred in a rogue planet ({})",
+      honeyholt_brief!(rogue_planet_error)
+    ),
     StellarNeighborError(stellar_neighbor_error) => format!(
       "an error occurred in the stellar neighbor ({})",
       honeyholt_brief!(stellar_neighbor_error)
@@ -23,3 +36,10 @@ impl From<StellarNeighborError> for Error {
     Error::StellarNeighborError(error)
   }
 }
+
+impl From<RoguePlanetError> for Error {
+  #[named]
+  fn from(error: RoguePlanetError) -> Self {
+    Error::RoguePlanetError(error)
+  }
+}
@@ -0,0 +1,329 @@
+use serde::Serialize;
+
+use crate::astronomy::planet::Planet;
+use crate::astronomy::star_system::fact_sheet::PlanetRow;
+use crate::astronomy::star_system::interest::InterestWeights;
+use crate::astronomy::stellar_neighbor::StellarNeighbor;
+use crate::astronomy::stellar_neighborhood::constants::*;
+use crate::astronomy::stellar_neighborhood::StellarNeighborhood;
+
+/// The phrases an explorer's log is written with.
+///
+/// Each is a template; anything in braces (e.g. `{name}`) is filled in when
+/// the entry is written.  Swap these out to change the voice of the log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhraseBank {
+  /// Opens each entry; takes `{name}`, `{distance}` (in Ly), and `{class}`.
+  pub heading: String,
+  /// Stands in for the planets of a system that has none.
+  pub no_planets: String,
+  /// A planet that can support life; takes `{size}` and `{semi_major_axis}`.
+  pub habitable_planet: String,
+  /// Any other terrestrial planet; takes `{temperature}`, `{size}`, and
+  /// `{semi_major_axis}`.
+  pub terrestrial_planet: String,
+  /// A gas or ice giant; takes `{kind}` and `{semi_major_axis}`.
+  pub giant_planet: String,
+  /// The planets not described individually; takes `{count}`.
+  pub other_planets: String,
+  /// A planet with bright rings; takes `{planet}`.
+  pub rings: String,
+  /// Closes the entry of a remarkable system.
+  pub remarkable: String,
+  /// Terrestrial planets under half, under twice, and over twice the mass of
+  /// Earth.
+  pub sizes: [String; 3],
+  /// Gas giants and ice giants.
+  pub kinds: [String; 2],
+  /// Terrestrial planets under 200 K, under 260 K, under 320 K, under 500 K,
+  /// and hotter still.
+  pub temperatures: [String; 5],
+}
+
+impl Default for PhraseBank {
+  /// Terse, like a survey officer with too many systems to get through.
+  fn default() -> Self {
+    let heading = "{name} ({distance} ly): {class}".to_string();
+    let no_planets = "no planets".to_string();
+    let habitable_planet = "a habitable {size} at {semi_major_axis} AU".to_string();
+    let terrestrial_planet = "a {temperature} {size} at {semi_major_axis} AU".to_string();
+    let giant_planet = "{kind} at {semi_major_axis} AU".to_string();
+    let other_planets = "{count} more planets".to_string();
+    let rings = "spectacular rings around {planet}".to_string();
+    let remarkable = "worth a closer look".to_string();
+    let sizes = ["sub-Earth", "Earth-sized world", "super-Earth"].map(String::from);
+    let kinds = ["a gas giant", "an ice giant"].map(String::from);
+    let temperatures = ["frozen", "cold", "temperate", "hot", "scorched"].map(String::from);
+    Self {
+      heading,
+      no_planets,
+      habitable_planet,
+      terrestrial_planet,
+      giant_planet,
+      other_planets,
+      rings,
+      remarkable,
+      sizes,
+      kinds,
+      temperatures,
+    }
+  }
+}
+
+/// One system's entry in an explorer's log.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LogEntry {
+  /// The index of the neighbor.
+  pub neighbor: usize,
+  /// The name of the system.
+  pub name: String,
+  /// The interest score, under the default weights.
+  pub interest_score: f64,
+  /// The entry itself.
+  pub text: String,
+}
+
+/// Get the name a neighbor goes by in the log: that of its primary host
+/// star.
+fn get_name(stellar_neighbor: &StellarNeighbor) -> String {
+  stellar_neighbor.star_system.get_planetary_systems()[0]
+    .host_star
+    .get_name()
+}
+
+/// Fill in the placeholders of a template.
+fn fill(template: &str, values: &[(&str, String)]) -> String {
+  values.iter().fold(template.to_string(), |text, (key, value)| {
+    text.replace(&format!("{{{}}}", key), value)
+  })
+}
+
+/// Describe a single planet, given its row in the fact sheet.
+fn describe_planet(planet: &Planet, row: &PlanetRow, phrase_bank: &PhraseBank) -> String {
+  // Planets around red dwarfs huddle close enough to need another digit.
+  let semi_major_axis = match row.semi_major_axis {
+    semi_major_axis if semi_major_axis < 1.0 => ("semi_major_axis", format!("{:.2}", semi_major_axis)),
+    semi_major_axis => ("semi_major_axis", format!("{:.1}", semi_major_axis)),
+  };
+  let size = match row.mass {
+    mass if mass < 0.5 => &phrase_bank.sizes[0],
+    mass if mass < 2.0 => &phrase_bank.sizes[1],
+    _ => &phrase_bank.sizes[2],
+  };
+  match planet {
+    _ if row.is_habitable => fill(
+      &phrase_bank.habitable_planet,
+      &[("size", size.clone()), semi_major_axis],
+    ),
+    Planet::TerrestrialPlanet(terrestrial_planet) => {
+      let temperature = match terrestrial_planet.surface_temperature {
+        temperature if temperature < 200.0 => &phrase_bank.temperatures[0],
+        temperature if temperature < 260.0 => &phrase_bank.temperatures[1],
+        temperature if temperature < 320.0 => &phrase_bank.temperatures[2],
+        temperature if temperature < 500.0 => &phrase_bank.temperatures[3],
+        _ => &phrase_bank.temperatures[4],
+      };
+      fill(
+        &phrase_bank.terrestrial_planet,
+        &[
+          ("temperature", temperature.clone()),
+          ("size", size.clone()),
+          semi_major_axis,
+        ],
+      )
+    },
+    Planet::GasGiantPlanet(_) => fill(
+      &phrase_bank.giant_planet,
+      &[("kind", phrase_bank.kinds[0].clone()), semi_major_axis],
+    ),
+    Planet::IceGiantPlanet(_) => fill(
+      &phrase_bank.giant_planet,
+      &[("kind", phrase_bank.kinds[1].clone()), semi_major_axis],
+    ),
+  }
+}
+
+/// Write a log entry for a neighbor.
+///
+/// Habitable planets are described first, then other terrestrial planets,
+/// then giants; anything past `EXPLORERS_LOG_PLANET_COUNT` is just counted.
+#[named]
+pub fn get_log_entry_text(stellar_neighbor: &StellarNeighbor, phrase_bank: &PhraseBank) -> String {
+  trace_enter!();
+  let star_system = &stellar_neighbor.star_system;
+  let fact_sheet = star_system.fact_sheet();
+  trace_var!(fact_sheet);
+  let class = fact_sheet
+    .stars
+    .iter()
+    .map(|star| star.class.as_str())
+    .collect::<Vec<&str>>()
+    .join("+");
+  trace_var!(class);
+  let mut text = fill(
+    &phrase_bank.heading,
+    &[
+      ("name", get_name(stellar_neighbor)),
+      ("distance", format!("{:.1}", stellar_neighbor.distance)),
+      ("class", class),
+    ],
+  );
+  // The fact sheet lists the planets in the same order we walk them here.
+  let mut planets: Vec<(&Planet, &PlanetRow)> = star_system
+    .get_planetary_systems()
+    .into_iter()
+    .flat_map(|planetary_system| planetary_system.satellite_systems.satellite_systems.iter())
+    .map(|satellite_system| &satellite_system.planet)
+    .zip(fact_sheet.planets.iter())
+    .collect();
+  planets.sort_by_key(|(planet, row)| (!row.is_habitable, !matches!(planet, Planet::TerrestrialPlanet(_))));
+  let mut descriptions: Vec<String> = planets
+    .iter()
+    .take(EXPLORERS_LOG_PLANET_COUNT)
+    .map(|(planet, row)| describe_planet(planet, row, phrase_bank))
+    .collect();
+  if planets.len() > EXPLORERS_LOG_PLANET_COUNT {
+    descriptions.push(fill(
+      &phrase_bank.other_planets,
+      &[("count", (planets.len() - EXPLORERS_LOG_PLANET_COUNT).to_string())],
+    ));
+  }
+  trace_var!(descriptions);
+  text.push_str(" with ");
+  match descriptions.split_last() {
+    None => text.push_str(&phrase_bank.no_planets),
+    Some((last, [])) => text.push_str(last),
+    Some((last, rest)) => text.push_str(&format!("{} and {}", rest.join(", "), last)),
+  }
+  // Only opaque rings make the log, and only the first of those.
+  let ringed_planet = star_system
+    .get_planetary_systems()
+    .into_iter()
+    .find_map(|planetary_system| {
+      planetary_system
+        .satellite_systems
+        .satellite_systems
+        .iter()
+        .find(|satellite_system| {
+          satellite_system
+            .ring_system
+            .as_ref()
+            .is_some_and(|ring_system| ring_system.optical_depth >= 1.0)
+        })
+        .map(|satellite_system| satellite_system.planet.get_name())
+    });
+  trace_var!(ringed_planet);
+  if let Some(planet) = ringed_planet {
+    text.push_str("; ");
+    text.push_str(&fill(&phrase_bank.rings, &[("planet", planet)]));
+  }
+  if fact_sheet.interest_score >= EXPLORERS_LOG_REMARKABLE_INTEREST_SCORE {
+    text.push_str("; ");
+    text.push_str(&phrase_bank.remarkable);
+  }
+  text.push('.');
+  let result = text;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Write an explorer's log of a neighborhood, one entry per system, nearest
+/// first.
+#[named]
+pub fn get_explorers_log(stellar_neighborhood: &StellarNeighborhood, phrase_bank: &PhraseBank) -> Vec<LogEntry> {
+  trace_enter!();
+  let mut result: Vec<LogEntry> = stellar_neighborhood
+    .neighbors
+    .iter()
+    .enumerate()
+    .map(|(neighbor, stellar_neighbor)| LogEntry {
+      neighbor,
+      name: get_name(stellar_neighbor),
+      interest_score: stellar_neighbor
+        .star_system
+        .get_interest_score(&InterestWeights::default()),
+      text: get_log_entry_text(stellar_neighbor, phrase_bank),
+    })
+    .collect();
+  result.sort_by(|a, b| {
+    let distance = |entry: &LogEntry| stellar_neighborhood.neighbors[entry.neighbor].distance;
+    distance(a).partial_cmp(&distance(b)).unwrap()
+  });
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::gas_giant_planet::GasGiantPlanet;
+  use crate::astronomy::ice_giant_planet::composition::IceGiantComposition;
+  use crate::astronomy::ice_giant_planet::IceGiantPlanet;
+  use crate::astronomy::stellar_neighborhood::constraints::Constraints;
+  use crate::astronomy::stellar_neighborhood::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_explorers_log() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let stellar_neighborhood = Constraints::default().generate(&mut rng)?;
+    let explorers_log = get_explorers_log(&stellar_neighborhood, &PhraseBank::default());
+    print_var!(explorers_log);
+    assert_eq!(explorers_log.len(), stellar_neighborhood.neighbors.len());
+    for entry in &explorers_log {
+      assert!(entry.text.starts_with(&entry.name));
+      assert!(entry.text.ends_with('.'));
+      assert!(!entry.text.contains('{'));
+    }
+    let terse = PhraseBank {
+      heading: "{name}".to_string(),
+      ..PhraseBank::default()
+    };
+    if let Some(entry) = get_explorers_log(&stellar_neighborhood, &terse).first() {
+      assert!(entry.text.starts_with(&format!("{} with ", entry.name)));
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_describe_giant() {
+    init();
+    trace_enter!();
+    // The wording of the fact sheet has no say in what kind of giant it is.
+    let row = PlanetRow {
+      name: "Steve VI".to_string(),
+      planetary_system: 0,
+      kind: "Jovian".to_string(),
+      semi_major_axis: 5.2,
+      eccentricity: 0.05,
+      orbital_period: 11.9,
+      mass: 318.0,
+      radius: 71_492.0,
+      temperature: None,
+      moons: 0,
+      has_rings: false,
+      is_habitable: false,
+    };
+    let phrase_bank = PhraseBank::default();
+    let gas_giant = Planet::GasGiantPlanet(GasGiantPlanet::from_mass(1.0).unwrap());
+    assert_eq!(describe_planet(&gas_giant, &row, &phrase_bank), "a gas giant at 5.2 AU");
+    let composition = IceGiantComposition::new(0.2, 0.1);
+    let ice_giant = Planet::IceGiantPlanet(IceGiantPlanet::from_mass(15.0, composition, 0.7).unwrap());
+    assert_eq!(
+      describe_planet(&ice_giant, &row, &phrase_bank),
+      "an ice giant at 5.2 AU"
+    );
+    trace_exit!();
+  }
+}
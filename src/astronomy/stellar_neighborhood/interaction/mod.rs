@@ -0,0 +1,26 @@
+use serde::Serialize;
+
+/// The kind of relationship between two stellar neighbors.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum InteractionKind {
+  /// The systems are close enough to perturb one another's Oort clouds,
+  /// which tends to mean comet showers.
+  OortCloudDisturbance,
+  /// The systems sit close together in the night sky of the origin, and are
+  /// both bright enough to be seen as a double star.
+  BrightDouble,
+}
+
+/// A notable relationship between two stellar neighbors.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Interaction {
+  /// What sort of relationship this is.
+  pub kind: InteractionKind,
+  /// The indices of the two neighbors involved.
+  pub neighbors: (usize, usize),
+  /// The physical distance between the two neighbors, measured in Ly.
+  pub separation: f64,
+  /// The angular distance between the two neighbors as seen from the origin,
+  /// measured in degrees.
+  pub angular_separation: f64,
+}
@@ -0,0 +1,83 @@
+use rand::prelude::*;
+use rand_distr::Poisson;
+use std::f64::consts::PI;
+
+use crate::astronomy::star::math::magnitude::LIGHT_YEARS_PER_PARSEC;
+
+/// Convert a density in objects per cubic parsec to objects per cubic light
+/// year.
+#[named]
+pub fn get_density_per_cubic_light_year(density_per_cubic_parsec: f64) -> f64 {
+  trace_enter!();
+  trace_var!(density_per_cubic_parsec);
+  let result = density_per_cubic_parsec / LIGHT_YEARS_PER_PARSEC.powf(3.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the number of objects we'd expect in a sphere.
+///
+/// The density is in objects per cubic light year, the radius in light years.
+#[named]
+pub fn get_expected_count(density: f64, radius: f64) -> f64 {
+  trace_enter!();
+  trace_var!(density);
+  trace_var!(radius);
+  let volume = (4.0 / 3.0) * PI * radius.powf(3.0);
+  trace_var!(volume);
+  let result = density * volume;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Draw the number of objects actually present, given the number expected.
+///
+/// Objects scattered independently through space follow a Poisson
+/// distribution.
+#[named]
+pub fn get_random_count<R: Rng + ?Sized>(rng: &mut R, expected_count: f64) -> usize {
+  trace_enter!();
+  trace_var!(expected_count);
+  let result = match expected_count > 0.0 {
+    true => Poisson::new(expected_count).unwrap().sample(rng) as usize,
+    false => 0,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::stellar_neighborhood::constants::STELLAR_NEIGHBORHOOD_DENSITY;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_random_count() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let density = get_density_per_cubic_light_year(0.14);
+    print_var!(density);
+    assert_approx_eq!(density, STELLAR_NEIGHBORHOOD_DENSITY, 0.0001);
+    let expected_count = get_expected_count(density, 10.0);
+    print_var!(expected_count);
+    assert_approx_eq!(expected_count, 16.8, 0.5);
+    let counts = (0..1000)
+      .map(|_| get_random_count(&mut rng, expected_count))
+      .collect::<Vec<_>>();
+    let mean = counts.iter().sum::<usize>() as f64 / counts.len() as f64;
+    print_var!(mean);
+    assert_approx_eq!(mean, expected_count, 1.0);
+    assert!(counts.iter().any(|&count| (count as f64) < 0.75 * expected_count));
+    assert_eq!(get_random_count(&mut rng, 0.0), 0);
+    trace_exit!();
+  }
+}
@@ -0,0 +1,152 @@
+use crate::astronomy::star::math::magnitude::absolute_magnitude_to_apparent_magnitude;
+use crate::astronomy::stellar_neighbor::StellarNeighbor;
+use crate::astronomy::stellar_neighborhood::constants::*;
+use crate::astronomy::stellar_neighborhood::interaction::*;
+use crate::astronomy::stellar_neighborhood::sky_catalog::get_neighbor_light;
+
+/// Get the distance between two points (in Ly).
+#[named]
+pub fn get_separation(first: (f64, f64, f64), second: (f64, f64, f64)) -> f64 {
+  trace_enter!();
+  trace_var!(first);
+  trace_var!(second);
+  let result =
+    ((first.0 - second.0).powf(2.0) + (first.1 - second.1).powf(2.0) + (first.2 - second.2).powf(2.0)).sqrt();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the angle between two points as seen from the origin (in degrees).
+#[named]
+pub fn get_angular_separation(first: (f64, f64, f64), second: (f64, f64, f64)) -> f64 {
+  trace_enter!();
+  trace_var!(first);
+  trace_var!(second);
+  let origin = (0.0, 0.0, 0.0);
+  let product = get_separation(origin, first) * get_separation(origin, second);
+  trace_var!(product);
+  let dot = first.0 * second.0 + first.1 * second.1 + first.2 * second.2;
+  trace_var!(dot);
+  let result = (dot / product).clamp(-1.0, 1.0).acos().to_degrees();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the apparent visual magnitude of a neighbor as seen from the origin.
+///
+/// All of its stars are counted, as the naked eye would see them.
+#[named]
+pub fn get_apparent_magnitude(neighbor: &StellarNeighbor) -> f64 {
+  trace_enter!();
+  let (absolute_magnitude, _) = get_neighbor_light(neighbor);
+  trace_var!(absolute_magnitude);
+  let result = absolute_magnitude_to_apparent_magnitude(absolute_magnitude, neighbor.distance);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Find every pair of neighbors that interacts in some notable way.
+#[named]
+pub fn get_interactions(neighbors: &[StellarNeighbor]) -> Vec<Interaction> {
+  trace_enter!();
+  let magnitudes = neighbors.iter().map(get_apparent_magnitude).collect::<Vec<f64>>();
+  trace_var!(magnitudes);
+  let mut result = vec![];
+  for (i, first) in neighbors.iter().enumerate() {
+    for (j, second) in neighbors.iter().enumerate().skip(i + 1) {
+      let separation = get_separation(first.coordinates, second.coordinates);
+      trace_var!(separation);
+      let angular_separation = get_angular_separation(first.coordinates, second.coordinates);
+      trace_var!(angular_separation);
+      let mut kinds = vec![];
+      if separation < OORT_CLOUD_DISTURBANCE_DISTANCE {
+        kinds.push(InteractionKind::OortCloudDisturbance);
+      }
+      if (BRIGHT_DOUBLE_MINIMUM_SEPARATION..BRIGHT_DOUBLE_MAXIMUM_SEPARATION).contains(&angular_separation)
+        && magnitudes[i] <= BRIGHT_DOUBLE_MAXIMUM_MAGNITUDE
+        && magnitudes[j] <= BRIGHT_DOUBLE_MAXIMUM_MAGNITUDE
+      {
+        kinds.push(InteractionKind::BrightDouble);
+      }
+      for kind in kinds {
+        result.push(Interaction {
+          kind,
+          neighbors: (i, j),
+          separation,
+          angular_separation,
+        });
+      }
+    }
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::host_star::HostStar;
+  use crate::astronomy::stellar_neighbor::constraints::Constraints as StellarNeighborConstraints;
+  use crate::astronomy::stellar_neighbor::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_separation() {
+    init();
+    trace_enter!();
+    let actual = get_separation((1.0, 2.0, 3.0), (4.0, 6.0, 3.0));
+    assert_approx_eq!(5.0, actual, 1e-3f64);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_angular_separation() {
+    init();
+    trace_enter!();
+    let mut actual = get_angular_separation((1.0, 0.0, 0.0), (0.0, 3.0, 0.0));
+    assert_approx_eq!(90.0, actual, 1e-3f64);
+    actual = get_angular_separation((1.0, 0.0, 0.0), (5.0, 0.0, 0.0));
+    assert_approx_eq!(0.0, actual, 1e-3f64);
+    actual = get_angular_separation((1.0, 1.0, 0.0), (-2.0, -2.0, 0.0));
+    assert_approx_eq!(180.0, actual, 1e-3f64);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_apparent_magnitude() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let neighbor = loop {
+      let neighbor = StellarNeighborConstraints::default().generate(&mut rng)?;
+      let planetary_systems = neighbor.star_system.get_planetary_systems();
+      if planetary_systems.len() == 1 && matches!(planetary_systems[0].host_star, HostStar::Star(_)) {
+        break neighbor;
+      }
+    };
+    let star = match &neighbor.star_system.get_planetary_systems()[0].host_star {
+      HostStar::Star(star) => star.clone(),
+      _ => unreachable!(),
+    };
+    let magnitude = get_apparent_magnitude(&neighbor);
+    print_var!(magnitude);
+    // What the eye sees, not the total output.
+    assert_approx_eq!(
+      magnitude,
+      absolute_magnitude_to_apparent_magnitude(star.absolute_visual_magnitude, neighbor.distance)
+    );
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,3 @@
+pub mod count;
+pub mod interaction;
+pub mod supernova;
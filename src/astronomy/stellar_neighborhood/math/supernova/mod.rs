@@ -0,0 +1,345 @@
+use rand::prelude::*;
+use std::f64::consts::PI;
+
+use crate::astronomy::star::constants::MAXIMUM_METALLICITY;
+use crate::astronomy::stellar_neighbor::math::point::get_random_point_in_sphere;
+use crate::astronomy::stellar_neighbor::StellarNeighbor;
+use crate::astronomy::stellar_neighborhood::constants::*;
+use crate::astronomy::stellar_neighborhood::math::count::get_random_count;
+use crate::astronomy::stellar_neighborhood::math::interaction::get_separation;
+use crate::astronomy::stellar_neighborhood::supernova::*;
+
+/// Get the number of supernovae we'd expect in the history of a region.
+///
+/// The rate scales with stellar density, since a denser region has more
+/// massive stars to lose.
+#[named]
+pub fn get_expected_supernova_count(density: f64) -> f64 {
+  trace_enter!();
+  trace_var!(density);
+  let volume = (4.0 / 3.0) * PI * SUPERNOVA_HISTORY_RADIUS.powf(3.0);
+  trace_var!(volume);
+  let rate = SUPERNOVA_RATE_DENSITY * density / STELLAR_NEIGHBORHOOD_DENSITY;
+  trace_var!(rate);
+  let result = rate * volume * SUPERNOVA_HISTORY_SPAN;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the radius of a supernova shell of a given age (in Myr).
+///
+/// Shells expand quickly at first (the Sedov-Taylor phase, R ∝ t^2/5), then
+/// slow as they sweep up gas (the snowplow phase, R ∝ t^2/7), and finally
+/// merge into the interstellar medium, at which point there's no shell.
+#[named]
+pub fn get_shell_radius(age: f64) -> Option<f64> {
+  trace_enter!();
+  trace_var!(age);
+  let sedov_taylor_radius = |age: f64| SEDOV_TAYLOR_SHELL_RADIUS * (age / 0.01).powf(2.0 / 5.0);
+  let result = match age {
+    age if age >= SHELL_LIFETIME => None,
+    age if age < SNOWPLOW_TRANSITION_AGE => Some(sedov_taylor_radius(age)),
+    age => Some(sedov_taylor_radius(SNOWPLOW_TRANSITION_AGE) * (age / SNOWPLOW_TRANSITION_AGE).powf(2.0 / 7.0)),
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the nebula visible around a supernova remnant, if any.
+///
+/// Every young remnant has a glowing shell; a young neutron star, spinning
+/// fast as a pulsar, fills it with a wind nebula for a while first.
+#[named]
+pub fn get_nebula(age: f64, remnant: Option<RemnantKind>) -> Option<NebulaKind> {
+  trace_enter!();
+  trace_var!(age);
+  trace_var!(remnant);
+  let result = match (age, remnant) {
+    (age, Some(RemnantKind::NeutronStar)) if age < PULSAR_WIND_NEBULA_LIFETIME => Some(NebulaKind::PulsarWind),
+    (age, _) if age < SHELL_NEBULA_LIFETIME => Some(NebulaKind::Shell),
+    _ => None,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Draw the mass of a core-collapse progenitor from the initial mass function.
+#[named]
+pub fn get_random_progenitor_mass<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+  trace_enter!();
+  let exponent = 1.0 - INITIAL_MASS_FUNCTION_SLOPE;
+  let minimum = MINIMUM_CORE_COLLAPSE_MASS.powf(exponent);
+  let maximum = MAXIMUM_CORE_COLLAPSE_MASS.powf(exponent);
+  let fraction: f64 = rng.gen_range(0.0..=1.0);
+  trace_var!(fraction);
+  let result = (minimum + fraction * (maximum - minimum)).powf(1.0 / exponent);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Place the remnant of a core-collapse supernova.
+///
+/// Remnants are born with a kick, and by now may be a long way from where
+/// their progenitor died.
+#[named]
+pub fn get_remnant<R: Rng + ?Sized>(
+  rng: &mut R,
+  progenitor_mass: f64,
+  age: f64,
+  coordinates: (f64, f64, f64),
+) -> Remnant {
+  trace_enter!();
+  trace_var!(progenitor_mass);
+  trace_var!(age);
+  let (kind, typical_kick_velocity) = match progenitor_mass >= MINIMUM_BLACK_HOLE_PROGENITOR_MASS {
+    true => (RemnantKind::BlackHole, BLACK_HOLE_KICK_VELOCITY),
+    false => (RemnantKind::NeutronStar, NEUTRON_STAR_KICK_VELOCITY),
+  };
+  trace_var!(kind);
+  let kick_velocity = rng.gen_range(0.0..=2.0 * typical_kick_velocity);
+  trace_var!(kick_velocity);
+  let direction = get_random_point_in_sphere(rng);
+  let length = get_separation((0.0, 0.0, 0.0), direction).max(f64::EPSILON);
+  let travel = kick_velocity * LY_PER_MYR_PER_KM_PER_SEC * age / length;
+  trace_var!(travel);
+  let coordinates = (
+    coordinates.0 + direction.0 * travel,
+    coordinates.1 + direction.1 * travel,
+    coordinates.2 + direction.2 * travel,
+  );
+  trace_var!(coordinates);
+  let distance = get_separation((0.0, 0.0, 0.0), coordinates);
+  let result = Remnant {
+    kind,
+    coordinates,
+    distance,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Find the neighbors that formed from gas a supernova enriched.
+///
+/// These are the ones younger than the supernova, and close enough that its
+/// shell reached them before it dissipated.
+#[named]
+pub fn get_enriched_neighbors(supernova: &Supernova, neighbors: &[StellarNeighbor]) -> Vec<usize> {
+  trace_enter!();
+  let reach = get_shell_radius(SHELL_LIFETIME - f64::EPSILON).unwrap_or_default();
+  trace_var!(reach);
+  let result = neighbors
+    .iter()
+    .enumerate()
+    .filter(|(_, neighbor)| {
+      // Ages of stars are in Gyr, those of supernovae in Myr.
+      let age = neighbor.star_system.get_planetary_systems()[0]
+        .host_star
+        .get_current_age()
+        * 1_000.0;
+      age < supernova.age && get_separation(neighbor.coordinates, supernova.coordinates) <= reach
+    })
+    .map(|(index, _)| index)
+    .collect::<Vec<usize>>();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Raise the metallicity of every star that formed from a supernova's
+/// debris, once for each supernova that enriched it.
+///
+/// Stellar remnants and brown dwarfs keep no metallicity, so they're left as
+/// they are.
+#[named]
+pub fn enrich_neighbors(supernovae: &[Supernova], neighbors: &mut [StellarNeighbor]) {
+  trace_enter!();
+  for index in supernovae.iter().flat_map(|supernova| &supernova.enriched_neighbors) {
+    trace_var!(index);
+    for planetary_system in neighbors[*index].star_system.get_planetary_systems_mut() {
+      let host_star = &mut planetary_system.host_star;
+      let metallicity = (host_star.get_metallicity() + SUPERNOVA_ENRICHMENT).min(MAXIMUM_METALLICITY);
+      trace_var!(metallicity);
+      host_star.set_metallicity(metallicity);
+    }
+  }
+  trace_exit!();
+}
+
+/// Generate the supernovae in the recent history of a neighborhood.
+///
+/// These are sorted from most to least recent.
+#[named]
+pub fn get_supernovae<R: Rng + ?Sized>(rng: &mut R, density: f64, neighbors: &[StellarNeighbor]) -> Vec<Supernova> {
+  trace_enter!();
+  let expected_count = get_expected_supernova_count(density);
+  trace_var!(expected_count);
+  let count = get_random_count(rng, expected_count);
+  trace_var!(count);
+  let mut result = (0..count)
+    .map(|_| {
+      let age = rng.gen_range(0.0..SUPERNOVA_HISTORY_SPAN);
+      let point = get_random_point_in_sphere(rng);
+      let coordinates = (
+        point.0 * SUPERNOVA_HISTORY_RADIUS,
+        point.1 * SUPERNOVA_HISTORY_RADIUS,
+        point.2 * SUPERNOVA_HISTORY_RADIUS,
+      );
+      let distance = get_separation((0.0, 0.0, 0.0), coordinates);
+      let (kind, progenitor_mass, remnant) = match TYPE_IA_PROBABILITY.sample(rng) {
+        true => (SupernovaKind::TypeIa, CHANDRASEKHAR_MASS, None),
+        false => {
+          let progenitor_mass = get_random_progenitor_mass(rng);
+          let remnant = get_remnant(rng, progenitor_mass, age, coordinates);
+          (SupernovaKind::CoreCollapse, progenitor_mass, Some(remnant))
+        },
+      };
+      let shell_radius = get_shell_radius(age);
+      let nebula = get_nebula(age, remnant.map(|remnant| remnant.kind));
+      let mut supernova = Supernova {
+        kind,
+        progenitor_mass,
+        age,
+        coordinates,
+        distance,
+        remnant,
+        shell_radius,
+        nebula,
+        enriched_neighbors: vec![],
+      };
+      supernova.enriched_neighbors = get_enriched_neighbors(&supernova, neighbors);
+      supernova
+    })
+    .collect::<Vec<Supernova>>();
+  result.sort_by(|a, b| a.age.partial_cmp(&b.age).unwrap());
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::host_star::HostStar;
+  use crate::astronomy::stellar_neighbor::constraints::Constraints as StellarNeighborConstraints;
+  use crate::astronomy::stellar_neighbor::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_shell_radius() {
+    init();
+    trace_enter!();
+    assert_approx_eq!(get_shell_radius(0.01).unwrap(), SEDOV_TAYLOR_SHELL_RADIUS);
+    // The two phases should meet.
+    let before = get_shell_radius(SNOWPLOW_TRANSITION_AGE - 1e-9).unwrap();
+    let after = get_shell_radius(SNOWPLOW_TRANSITION_AGE).unwrap();
+    assert_approx_eq!(before, after, 1e-3);
+    let oldest = get_shell_radius(SHELL_LIFETIME - 1e-9).unwrap();
+    print_var!(oldest);
+    assert!(oldest > after);
+    assert_eq!(get_shell_radius(SHELL_LIFETIME), None);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_nebula() {
+    init();
+    trace_enter!();
+    assert_eq!(
+      get_nebula(0.001, Some(RemnantKind::NeutronStar)),
+      Some(NebulaKind::PulsarWind)
+    );
+    assert_eq!(get_nebula(0.001, Some(RemnantKind::BlackHole)), Some(NebulaKind::Shell));
+    assert_eq!(get_nebula(0.001, None), Some(NebulaKind::Shell));
+    assert_eq!(
+      get_nebula(0.05, Some(RemnantKind::NeutronStar)),
+      Some(NebulaKind::Shell)
+    );
+    assert_eq!(get_nebula(1.0, Some(RemnantKind::NeutronStar)), None);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_supernovae() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let supernovae = get_supernovae(&mut rng, 10.0 * STELLAR_NEIGHBORHOOD_DENSITY, &[]);
+    print_var!(supernovae);
+    assert!(!supernovae.is_empty());
+    for supernova in supernovae.iter() {
+      assert!(supernova.distance <= SUPERNOVA_HISTORY_RADIUS);
+      match supernova.kind {
+        SupernovaKind::TypeIa => assert_eq!(supernova.remnant, None),
+        SupernovaKind::CoreCollapse => {
+          let remnant = supernova.remnant.unwrap();
+          assert!(supernova.progenitor_mass >= MINIMUM_CORE_COLLAPSE_MASS);
+          let is_black_hole = supernova.progenitor_mass >= MINIMUM_BLACK_HOLE_PROGENITOR_MASS;
+          assert_eq!(remnant.kind == RemnantKind::BlackHole, is_black_hole);
+        },
+      }
+    }
+    assert!(supernovae.windows(2).all(|pair| pair[0].age <= pair[1].age));
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_enriched_neighbors() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let neighbors = vec![StellarNeighborConstraints::default().generate(&mut rng)?];
+    let age = neighbors[0].star_system.get_planetary_systems()[0]
+      .host_star
+      .get_current_age()
+      * 1_000.0;
+    print_var!(age);
+    let mut supernova = Supernova {
+      kind: SupernovaKind::TypeIa,
+      progenitor_mass: CHANDRASEKHAR_MASS,
+      age: age + 1.0,
+      coordinates: neighbors[0].coordinates,
+      distance: neighbors[0].distance,
+      remnant: None,
+      shell_radius: None,
+      nebula: None,
+      enriched_neighbors: vec![],
+    };
+    assert_eq!(get_enriched_neighbors(&supernova, &neighbors), vec![0]);
+    let mut enriched = neighbors.clone();
+    supernova.enriched_neighbors = vec![0];
+    enrich_neighbors(std::slice::from_ref(&supernova), &mut enriched);
+    let metallicity = neighbors[0].star_system.get_planetary_systems()[0]
+      .host_star
+      .get_metallicity();
+    let enriched_metallicity = enriched[0].star_system.get_planetary_systems()[0]
+      .host_star
+      .get_metallicity();
+    print_var!(enriched_metallicity);
+    match &neighbors[0].star_system.get_planetary_systems()[0].host_star {
+      HostStar::StellarRemnant(_) | HostStar::BrownDwarf(_) => assert_eq!(enriched_metallicity, metallicity),
+      _ => assert_approx_eq!(
+        enriched_metallicity,
+        (metallicity + SUPERNOVA_ENRICHMENT).min(MAXIMUM_METALLICITY)
+      ),
+    }
+    // A star can't have formed from the debris of a younger supernova.
+    supernova.age = age / 2.0;
+    assert!(get_enriched_neighbors(&supernova, &neighbors).is_empty());
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -1,8 +1,33 @@
+use rand::prelude::*;
+use serde::Serialize;
+
+use crate::astronomy::rogue_planet::RoguePlanet;
+use crate::astronomy::star::name::registry::NameRegistry;
+use crate::astronomy::star::name::theme::NameTheme;
+use crate::astronomy::star::name::NameKind;
 use crate::astronomy::stellar_neighbor::*;
 
 pub mod constants;
 pub mod constraints;
 pub mod error;
+use error::Error;
+pub mod explorers_log;
+use explorers_log::{get_explorers_log, LogEntry, PhraseBank};
+pub mod interaction;
+use interaction::*;
+pub(crate) mod math;
+use math::interaction::get_interactions;
+pub mod name;
+pub mod sector_map;
+use sector_map::{render_sector_map, SectorMapOptions};
+pub mod sky_catalog;
+use sky_catalog::{get_sky_catalog, SkyCatalog};
+pub mod sky_culture;
+use sky_culture::{generate_sky_culture, SkyCulture};
+pub mod spatial_index;
+use spatial_index::{Point, SpatialIndex};
+pub mod supernova;
+use supernova::Supernova;
 
 /// The `StellarNeighborhood` type.
 ///
@@ -13,8 +38,10 @@ pub mod error;
 /// stars, possibly with planets of their own.
 ///
 /// Why?  Well, just to add a little color to the night sky.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct StellarNeighborhood {
+  /// A generated name for this neighborhood, e.g. "Tarsus Reach".
+  pub name: String,
   /// The radius of this neighborhood, measured in light years.
   pub radius: f64,
   /// The stellar density of this neighborhood, measured in stars per cubic
@@ -24,8 +51,307 @@ pub struct StellarNeighborhood {
   /// Stellar "neighbors", which is a glorified tuple of three-dimensional
   /// coordinates and a star system.
   pub neighbors: Vec<StellarNeighbor>,
+  /// Planets drifting through the neighborhood on their own, bound to no
+  /// star.
+  pub rogue_planets: Vec<RoguePlanet>,
   /// The number of stars in this stellar neighborhood.
   pub star_count: usize,
+  /// Notable relationships between pairs of neighbors, such as systems that
+  /// stir up one another's Oort clouds or that form a double in the sky.
+  pub interactions: Vec<Interaction>,
+  /// Supernovae in the recent past of this region, most recent first.
+  ///
+  /// These reach well beyond the neighborhood itself; a shell blown out by
+  /// a supernova a couple hundred light years away can still sweep through.
+  pub supernovae: Vec<Supernova>,
+  /// An index of the neighbors' coordinates, for finding those near a point.
+  #[serde(skip)]
+  pub spatial_index: SpatialIndex,
 }
 
-impl StellarNeighborhood {}
+impl StellarNeighborhood {
+  /// Recalculate the interactions between neighbors.
+  ///
+  /// This should be called after neighbors are added, removed, or moved.
+  #[named]
+  pub fn update_interactions(&mut self) {
+    trace_enter!();
+    self.interactions = get_interactions(&self.neighbors);
+    trace_var!(self.interactions);
+    trace_exit!();
+  }
+
+  /// Rebuild the index of the neighbors' coordinates.
+  ///
+  /// This should be called after neighbors are added, removed, or moved.
+  #[named]
+  pub fn update_spatial_index(&mut self) {
+    trace_enter!();
+    let points = self
+      .neighbors
+      .iter()
+      .map(|neighbor| neighbor.coordinates)
+      .collect::<Vec<_>>();
+    self.spatial_index = SpatialIndex::new(&points);
+    trace_exit!();
+  }
+
+  /// Find the neighbors within a radius (in Ly) of a point, nearest first.
+  #[named]
+  pub fn get_neighbors_within(&self, point: Point, radius: f64) -> Vec<&StellarNeighbor> {
+    trace_enter!();
+    let result = self
+      .spatial_index
+      .get_within(point, radius)
+      .into_iter()
+      .map(|index| &self.neighbors[index])
+      .collect();
+    trace_exit!();
+    result
+  }
+
+  /// Find the `count` neighbors nearest a point, nearest first.
+  #[named]
+  pub fn get_nearest_neighbors(&self, point: Point, count: usize) -> Vec<&StellarNeighbor> {
+    trace_enter!();
+    let result = self
+      .spatial_index
+      .get_nearest(point, count)
+      .into_iter()
+      .map(|index| &self.neighbors[index])
+      .collect();
+    trace_exit!();
+    result
+  }
+
+  /// The neighbors capable of supporting conventional life.
+  #[named]
+  pub fn get_habitable_systems(&self) -> impl Iterator<Item = &StellarNeighbor> {
+    trace_enter!();
+    let result = self
+      .neighbors
+      .iter()
+      .filter(|neighbor| neighbor.star_system.is_habitable());
+    trace_exit!();
+    result
+  }
+
+  /// Find the habitable neighbor nearest a point, if there are any.
+  ///
+  /// We look at ever-larger sets of the nearest neighbors, so a habitable
+  /// neighbor close by is found without checking all of the others.
+  #[named]
+  pub fn get_nearest_habitable(&self, from: Point) -> Option<&StellarNeighbor> {
+    trace_enter!();
+    trace_var!(from);
+    let mut checked = 0;
+    let mut count = 1;
+    let result = loop {
+      let nearest = self.spatial_index.get_nearest(from, count);
+      if let Some(index) = nearest[checked..]
+        .iter()
+        .find(|index| self.neighbors[**index].star_system.is_habitable())
+      {
+        break Some(&self.neighbors[*index]);
+      }
+      if nearest.len() < count {
+        break None;
+      }
+      checked = nearest.len();
+      count *= 2;
+    };
+    trace_exit!();
+    result
+  }
+
+  /// Rename the stars and planets with the given function.
+  ///
+  /// Neighbors are renamed nearest first, then the rogue planets.
+  #[named]
+  pub fn rename<F: FnMut(&str, NameKind) -> String>(&mut self, rename: &mut F) {
+    trace_enter!();
+    let mut neighbors = self.neighbors.iter_mut().collect::<Vec<_>>();
+    neighbors.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap());
+    for neighbor in neighbors {
+      neighbor.star_system.rename(rename);
+    }
+    for rogue_planet in self.rogue_planets.iter_mut() {
+      rogue_planet.name = rename(&rogue_planet.name, NameKind::Planet);
+    }
+    trace_exit!();
+  }
+
+  /// Give the stars and planets names no other body in the registry has.
+  #[named]
+  pub fn register_names(&mut self, registry: &mut NameRegistry) {
+    trace_enter!();
+    self.rename(&mut |name, _| registry.register(name));
+    trace_exit!();
+  }
+
+  /// Rename the neighborhood, its stars, and its planets in a theme.
+  #[named]
+  pub fn apply_name_theme<R: Rng + ?Sized>(&mut self, rng: &mut R, theme: NameTheme) {
+    trace_enter!();
+    trace_var!(theme);
+    let generator = theme.get_generator();
+    self.name = generator.replace_root(rng, &self.name);
+    trace_var!(self.name);
+    self.rename(&mut |name, kind| generator.generate(rng, name, kind));
+    trace_exit!();
+  }
+
+  /// Render a top-down map of this neighborhood as SVG.
+  #[named]
+  pub fn get_sector_map(&self, options: &SectorMapOptions) -> String {
+    trace_enter!();
+    let result = render_sector_map(self, options);
+    trace_exit!();
+    result
+  }
+
+  /// Catalog the night sky as seen from the habitable planet of the given
+  /// name.
+  #[named]
+  pub fn get_sky_catalog(&self, planet_name: &str) -> Result<SkyCatalog, Error> {
+    trace_enter!();
+    let result = get_sky_catalog(self, planet_name);
+    trace_exit!();
+    result
+  }
+
+  /// Draw and name the constellations seen from the habitable planet of
+  /// the given name.
+  #[named]
+  pub fn get_sky_culture<R: Rng + ?Sized>(&self, rng: &mut R, planet_name: &str) -> Result<SkyCulture, Error> {
+    trace_enter!();
+    let catalog = self.get_sky_catalog(planet_name)?;
+    let result = generate_sky_culture(rng, catalog);
+    trace_exit!();
+    Ok(result)
+  }
+
+  /// Write an explorer's log of this neighborhood, one entry per system,
+  /// nearest first.
+  #[named]
+  pub fn get_explorers_log(&self, phrase_bank: &PhraseBank) -> Vec<LogEntry> {
+    trace_enter!();
+    let result = get_explorers_log(self, phrase_bank);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve the interactions involving the neighbor at the specified index.
+  #[named]
+  pub fn get_interactions_for(&self, index: usize) -> Vec<&Interaction> {
+    trace_enter!();
+    trace_var!(index);
+    let result = self
+      .interactions
+      .iter()
+      .filter(|interaction| interaction.neighbors.0 == index || interaction.neighbors.1 == index)
+      .collect::<Vec<&Interaction>>();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Retrieve the supernovae whose debris the neighbor at the specified
+  /// index formed from.
+  #[named]
+  pub fn get_supernovae_enriching(&self, index: usize) -> Vec<&Supernova> {
+    trace_enter!();
+    trace_var!(index);
+    let result = self
+      .supernovae
+      .iter()
+      .filter(|supernova| supernova.enriched_neighbors.contains(&index))
+      .collect::<Vec<&Supernova>>();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::constraints::Constraints;
+  use super::error::Error;
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_oort_cloud_disturbance() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let mut stellar_neighborhood = Constraints::default().generate(&mut rng)?;
+    while stellar_neighborhood.neighbors.len() < 2 {
+      stellar_neighborhood = Constraints::default().generate(&mut rng)?;
+    }
+    let (x, y, z) = stellar_neighborhood.neighbors[0].coordinates;
+    stellar_neighborhood.neighbors[1].coordinates = (x + 0.5, y, z);
+    stellar_neighborhood.update_interactions();
+    let interactions = stellar_neighborhood.get_interactions_for(1);
+    print_var!(interactions);
+    assert!(interactions.iter().any(|interaction| {
+      interaction.kind == InteractionKind::OortCloudDisturbance && interaction.neighbors == (0, 1)
+    }));
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_nearest_habitable() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let stellar_neighborhood = Constraints::default().generate(&mut rng)?;
+    let from = (2.0, -3.0, 1.0);
+    let nearest_habitable = stellar_neighborhood.get_nearest_habitable(from);
+    print_var!(nearest_habitable.map(|neighbor| &neighbor.name));
+    let get_distance = |neighbor: &&StellarNeighbor| {
+      let (x, y, z) = neighbor.coordinates;
+      ((x - from.0).powf(2.0) + (y - from.1).powf(2.0) + (z - from.2).powf(2.0)).sqrt()
+    };
+    let expected = stellar_neighborhood
+      .get_habitable_systems()
+      .min_by(|a, b| get_distance(a).partial_cmp(&get_distance(b)).unwrap());
+    assert_eq!(nearest_habitable, expected);
+    assert!(stellar_neighborhood
+      .get_habitable_systems()
+      .all(|neighbor| neighbor.star_system.is_habitable()));
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_nearest_neighbors() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let stellar_neighborhood = Constraints::default().generate(&mut rng)?;
+    let origin = (0.0, 0.0, 0.0);
+    let nearest = stellar_neighborhood.get_nearest_neighbors(origin, 3);
+    let mut by_distance = stellar_neighborhood.neighbors.iter().collect::<Vec<_>>();
+    by_distance.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap());
+    assert_eq!(nearest, by_distance.iter().copied().take(3).collect::<Vec<_>>());
+    let within = stellar_neighborhood.get_neighbors_within(origin, 5.0);
+    assert_eq!(
+      within,
+      by_distance
+        .into_iter()
+        .filter(|neighbor| neighbor.distance <= 5.0)
+        .collect::<Vec<_>>()
+    );
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,43 @@
+use rand::prelude::*;
+
+use crate::astronomy::star::name::generate_star_name;
+
+pub const REGIONS: &[&str] = &[
+  "Reach", "Expanse", "Drift", "Marches", "Deep", "Verge", "Shoals", "Hollow", "Frontier", "Cluster",
+];
+
+/// Generate a name for a stellar neighborhood, e.g. "Tarsus Reach".
+#[named]
+pub fn generate_stellar_neighborhood_name<R: Rng + ?Sized>(rng: &mut R) -> String {
+  trace_enter!();
+  let root = generate_star_name(rng);
+  trace_var!(root);
+  let region = REGIONS[rng.gen_range(0..REGIONS.len())];
+  trace_var!(region);
+  let result = format!("{} {}", root, region);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_name_stellar_neighborhood() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let name = generate_stellar_neighborhood_name(&mut rng);
+    print_var!(name);
+    assert!(REGIONS.iter().any(|region| name.ends_with(region)));
+    trace_exit!();
+  }
+}
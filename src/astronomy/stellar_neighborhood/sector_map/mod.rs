@@ -0,0 +1,239 @@
+use std::fmt::Write;
+
+use crate::astronomy::stellar_neighbor::StellarNeighbor;
+use crate::astronomy::stellar_neighborhood::constants::*;
+use crate::astronomy::stellar_neighborhood::math::interaction::get_separation;
+use crate::astronomy::stellar_neighborhood::StellarNeighborhood;
+
+/// The blank space around the edge of the map, in pixels.
+const MARGIN: f64 = 40.0;
+
+/// Options for rendering a sector map.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SectorMapOptions {
+  /// The width (and height) of the map, in pixels.
+  pub size: f64,
+  /// The spacing between distance rings, in Ly; anything but a positive
+  /// spacing leaves the rings off.
+  pub ring_spacing: f64,
+  /// The longest jump that gets a link, in Ly.
+  pub jump_distance: f64,
+  /// Whether to label each system with its name.
+  pub show_labels: bool,
+}
+
+impl Default for SectorMapOptions {
+  /// A square map with everything turned on.
+  fn default() -> Self {
+    let size = SECTOR_MAP_SIZE;
+    let ring_spacing = SECTOR_MAP_RING_SPACING;
+    let jump_distance = SECTOR_MAP_JUMP_DISTANCE;
+    let show_labels = true;
+    Self {
+      size,
+      ring_spacing,
+      jump_distance,
+      show_labels,
+    }
+  }
+}
+
+/// A link between two neighbors close enough to travel between.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JumpLink {
+  /// The indices of the two neighbors.
+  pub neighbors: (usize, usize),
+  /// The true (three-dimensional) distance between them, in Ly.
+  pub distance: f64,
+}
+
+/// Find every pair of neighbors within the jump distance (in Ly).
+///
+/// This goes by the true distance, so two systems that overlap on the map
+/// may still be out of reach of one another.
+#[named]
+pub fn get_jump_links(neighbors: &[StellarNeighbor], jump_distance: f64) -> Vec<JumpLink> {
+  trace_enter!();
+  trace_var!(jump_distance);
+  let mut result = vec![];
+  for (i, first) in neighbors.iter().enumerate() {
+    for (j, second) in neighbors.iter().enumerate().skip(i + 1) {
+      let distance = get_separation(first.coordinates, second.coordinates);
+      if distance <= jump_distance {
+        result.push(JumpLink {
+          neighbors: (i, j),
+          distance,
+        });
+      }
+    }
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Escape a string for use as SVG text.
+fn escape(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Render a top-down map of the neighborhood as SVG.
+///
+/// Neighbors are projected onto the galactic (x, y) plane, with the origin
+/// at the center and galactic north at the top.  Each link is labeled with
+/// its length, and systems capable of supporting life are ringed in green.
+#[named]
+pub fn render_sector_map(stellar_neighborhood: &StellarNeighborhood, options: &SectorMapOptions) -> String {
+  trace_enter!();
+  trace_var!(options);
+  let center = options.size / 2.0;
+  trace_var!(center);
+  let scale = (center - MARGIN) / stellar_neighborhood.radius;
+  trace_var!(scale);
+  let project = |coordinates: (f64, f64, f64)| (center + coordinates.0 * scale, center - coordinates.1 * scale);
+  let mut output = String::new();
+  writeln!(
+    output,
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0:.0}\" height=\"{0:.0}\" viewBox=\"0 0 {0:.0} {0:.0}\">",
+    options.size
+  )
+  .unwrap();
+  writeln!(output, "  <title>{}</title>", escape(&stellar_neighborhood.name)).unwrap();
+  writeln!(output, "  <rect width=\"100%\" height=\"100%\" fill=\"#05070f\"/>").unwrap();
+  let mut ring = options.ring_spacing;
+  while options.ring_spacing > 0.0 && ring <= stellar_neighborhood.radius {
+    writeln!(
+      output,
+      "  <circle class=\"ring\" cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"none\" stroke=\"#1f3a5f\" stroke-dasharray=\"4 4\"/>",
+      center,
+      center,
+      ring * scale
+    )
+    .unwrap();
+    writeln!(
+      output,
+      "  <text x=\"{:.1}\" y=\"{:.1}\" fill=\"#1f3a5f\" font-size=\"10\">{} ly</text>",
+      center + 2.0,
+      center - ring * scale - 2.0,
+      ring
+    )
+    .unwrap();
+    ring += options.ring_spacing;
+  }
+  writeln!(
+    output,
+    "  <path class=\"origin\" d=\"M {0:.1} {1:.1} h 12 M {2:.1} {3:.1} v 12\" stroke=\"#ffffff\"/>",
+    center - 6.0,
+    center,
+    center,
+    center - 6.0
+  )
+  .unwrap();
+  let neighbors = &stellar_neighborhood.neighbors;
+  for link in get_jump_links(neighbors, options.jump_distance) {
+    let (x1, y1) = project(neighbors[link.neighbors.0].coordinates);
+    let (x2, y2) = project(neighbors[link.neighbors.1].coordinates);
+    writeln!(
+      output,
+      "  <line class=\"link\" x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#3f6f9f\"/>",
+      x1, y1, x2, y2
+    )
+    .unwrap();
+    writeln!(
+      output,
+      "  <text x=\"{:.1}\" y=\"{:.1}\" fill=\"#3f6f9f\" font-size=\"9\" text-anchor=\"middle\">{:.1}</text>",
+      (x1 + x2) / 2.0,
+      (y1 + y2) / 2.0,
+      link.distance
+    )
+    .unwrap();
+  }
+  for neighbor in neighbors.iter() {
+    let (x, y) = project(neighbor.coordinates);
+    // Brighter systems get bigger dots, within reason.
+    let radius = (3.0 + neighbor.get_luminosity().log10()).clamp(2.0, 8.0);
+    if neighbor.star_system.is_habitable() {
+      writeln!(
+        output,
+        "  <circle class=\"habitable\" cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"none\" stroke=\"#3fdf6f\" stroke-width=\"2\"/>",
+        x,
+        y,
+        radius + 4.0
+      )
+      .unwrap();
+    }
+    writeln!(
+      output,
+      "  <circle class=\"star\" cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"#fff4d6\"/>",
+      x, y, radius
+    )
+    .unwrap();
+    if options.show_labels {
+      writeln!(
+        output,
+        "  <text x=\"{:.1}\" y=\"{:.1}\" fill=\"#c0c8d8\" font-size=\"11\">{}</text>",
+        x + radius + 4.0,
+        y + 4.0,
+        escape(&neighbor.name)
+      )
+      .unwrap();
+    }
+  }
+  writeln!(output, "</svg>").unwrap();
+  let result = output;
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::stellar_neighborhood::constraints::Constraints;
+  use crate::astronomy::stellar_neighborhood::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_render_sector_map() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let mut stellar_neighborhood = Constraints::default().generate(&mut rng)?;
+    while stellar_neighborhood.neighbors.len() < 2 {
+      stellar_neighborhood = Constraints::default().generate(&mut rng)?;
+    }
+    let (x, y, z) = stellar_neighborhood.neighbors[0].coordinates;
+    stellar_neighborhood.neighbors[1].coordinates = (x + 1.0, y, z);
+    let links = get_jump_links(&stellar_neighborhood.neighbors, 1.5);
+    print_var!(links);
+    assert!(links.iter().any(|link| link.neighbors == (0, 1)));
+    assert!(links.iter().all(|link| link.distance <= 1.5));
+    let svg = stellar_neighborhood.get_sector_map(&SectorMapOptions::default());
+    print_var!(svg);
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(
+      svg.matches("class=\"star\"").count(),
+      stellar_neighborhood.neighbors.len()
+    );
+    let label = format!(">{}</text>", escape(&stellar_neighborhood.neighbors[0].name));
+    assert!(svg.contains(&label));
+    let unlabeled = stellar_neighborhood.get_sector_map(&SectorMapOptions {
+      show_labels: false,
+      ..SectorMapOptions::default()
+    });
+    assert!(!unlabeled.contains(&label));
+    assert!(svg.contains("class=\"ring\""));
+    let ringless = stellar_neighborhood.get_sector_map(&SectorMapOptions {
+      ring_spacing: 0.0,
+      ..SectorMapOptions::default()
+    });
+    assert!(!ringless.contains("class=\"ring\""));
+    trace_exit!();
+    Ok(())
+  }
+}
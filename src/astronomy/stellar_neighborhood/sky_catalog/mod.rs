@@ -0,0 +1,244 @@
+use serde::Serialize;
+
+use crate::astronomy::coordinates::{CartesianCoordinates, GalacticCoordinates};
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::star::math::color::temperature_to_rgb;
+use crate::astronomy::star::math::magnitude::absolute_magnitude_to_apparent_magnitude;
+use crate::astronomy::star::math::magnitude::star_luminosity_to_absolute_magnitude;
+use crate::astronomy::stellar_neighbor::StellarNeighbor;
+use crate::astronomy::stellar_neighborhood::constants::*;
+use crate::astronomy::stellar_neighborhood::error::Error;
+use crate::astronomy::stellar_neighborhood::StellarNeighborhood;
+
+/// A star system as it appears in another world's night sky.
+///
+/// At these distances the stars of a multiple system blur into one point of
+/// light, so each neighbor gets a single entry.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SkyStar {
+  /// The name of the neighbor.
+  pub name: String,
+  /// The combined apparent visual magnitude of its stars.
+  pub apparent_magnitude: f64,
+  /// The color of its brightest star.
+  pub rgb: (u8, u8, u8),
+  /// Where it sits on the sky, measured along galactic axes centered on the
+  /// observer, and how far away it is (in Ly).
+  pub position: GalacticCoordinates,
+}
+
+/// The night sky as seen from a particular planet.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SkyCatalog {
+  /// The name of the planet we're standing on.
+  pub planet: String,
+  /// The name of the neighbor the planet belongs to.
+  pub neighbor: String,
+  /// The other neighbors, brightest first.
+  pub stars: Vec<SkyStar>,
+}
+
+impl SkyCatalog {
+  /// The stars bright enough to see without a telescope, brightest first.
+  #[named]
+  pub fn get_naked_eye_stars(&self) -> impl Iterator<Item = &SkyStar> {
+    trace_enter!();
+    let result = self
+      .stars
+      .iter()
+      .take_while(|star| star.apparent_magnitude <= NAKED_EYE_LIMITING_MAGNITUDE);
+    trace_exit!();
+    result
+  }
+}
+
+/// Get the absolute visual magnitude and color of each star in a host star.
+///
+/// Bolometric corrections only make sense for stars proper, so remnants and
+/// brown dwarfs get their bolometric magnitudes, which flatters them a bit.
+#[named]
+fn get_host_star_lights(host_star: &HostStar) -> Vec<(f64, (u8, u8, u8))> {
+  trace_enter!();
+  use HostStar::*;
+  let result = match host_star {
+    Star(star) => vec![(star.absolute_visual_magnitude, star.absolute_rgb)],
+    CloseBinaryStar(close_binary_star) => vec![
+      (
+        close_binary_star.primary.absolute_visual_magnitude,
+        close_binary_star.primary.absolute_rgb,
+      ),
+      (
+        close_binary_star.secondary.absolute_visual_magnitude,
+        close_binary_star.secondary.absolute_rgb,
+      ),
+    ],
+    StellarRemnant(stellar_remnant) => vec![(
+      star_luminosity_to_absolute_magnitude(stellar_remnant.luminosity),
+      temperature_to_rgb(stellar_remnant.temperature),
+    )],
+    BrownDwarf(brown_dwarf) => vec![(
+      star_luminosity_to_absolute_magnitude(brown_dwarf.luminosity),
+      temperature_to_rgb(brown_dwarf.temperature),
+    )],
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the combined absolute visual magnitude and the color of the
+/// brightest star of a neighbor.
+#[named]
+pub(crate) fn get_neighbor_light(neighbor: &StellarNeighbor) -> (f64, (u8, u8, u8)) {
+  trace_enter!();
+  let lights = neighbor
+    .star_system
+    .get_planetary_systems()
+    .into_iter()
+    .flat_map(|planetary_system| get_host_star_lights(&planetary_system.host_star))
+    .collect::<Vec<_>>();
+  trace_var!(lights);
+  let flux = lights
+    .iter()
+    .map(|(magnitude, _)| 10.0_f64.powf(-0.4 * magnitude))
+    .sum::<f64>();
+  trace_var!(flux);
+  let magnitude = -2.5 * flux.log10();
+  trace_var!(magnitude);
+  let rgb = lights
+    .iter()
+    .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+    .map(|(_, rgb)| *rgb)
+    .unwrap_or((0, 0, 0));
+  trace_var!(rgb);
+  let result = (magnitude, rgb);
+  trace_exit!();
+  result
+}
+
+/// Find the neighbor with a planet of the given name, and the planet.
+#[named]
+fn find_planet<'a>(neighborhood: &'a StellarNeighborhood, name: &str) -> Option<(usize, &'a Planet)> {
+  trace_enter!();
+  trace_var!(name);
+  let result = neighborhood.neighbors.iter().enumerate().find_map(|(index, neighbor)| {
+    neighbor
+      .star_system
+      .get_planetary_systems()
+      .into_iter()
+      .flat_map(|planetary_system| planetary_system.satellite_systems.satellite_systems.iter())
+      .map(|satellite_system| &satellite_system.planet)
+      .find(|planet| planet.get_name() == name)
+      .map(|planet| (index, planet))
+  });
+  trace_exit!();
+  result
+}
+
+/// Catalog the night sky as seen from the habitable planet of the given name.
+///
+/// Every other neighbor is listed, brightest first; stars that give off no
+/// light to speak of, like black holes, are left out.
+#[named]
+pub fn get_sky_catalog(neighborhood: &StellarNeighborhood, planet_name: &str) -> Result<SkyCatalog, Error> {
+  trace_enter!();
+  trace_var!(planet_name);
+  let (observer, planet) = find_planet(neighborhood, planet_name).ok_or(Error::PlanetNotFound)?;
+  trace_var!(observer);
+  if !planet.is_habitable() {
+    return Err(Error::PlanetNotHabitable);
+  }
+  let origin = neighborhood.neighbors[observer].coordinates;
+  trace_var!(origin);
+  let mut stars = neighborhood
+    .neighbors
+    .iter()
+    .enumerate()
+    .filter(|(index, _)| *index != observer)
+    .filter_map(|(_, neighbor)| {
+      let (x, y, z) = neighbor.coordinates;
+      let position = CartesianCoordinates::from((x - origin.0, y - origin.1, z - origin.2)).to_galactic();
+      let (absolute_magnitude, rgb) = get_neighbor_light(neighbor);
+      let apparent_magnitude = absolute_magnitude_to_apparent_magnitude(absolute_magnitude, position.distance);
+      apparent_magnitude.is_finite().then(|| SkyStar {
+        name: neighbor.name.clone(),
+        apparent_magnitude,
+        rgb,
+        position,
+      })
+    })
+    .collect::<Vec<_>>();
+  stars.sort_by(|a, b| a.apparent_magnitude.partial_cmp(&b.apparent_magnitude).unwrap());
+  let result = SkyCatalog {
+    planet: planet_name.to_string(),
+    neighbor: neighborhood.neighbors[observer].name.clone(),
+    stars,
+  };
+  trace_var!(result);
+  trace_exit!();
+  Ok(result)
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::stellar_neighborhood::constraints::Constraints;
+  use crate::astronomy::stellar_neighborhood::math::interaction::get_separation;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_sky_catalog() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let (stellar_neighborhood, observer, planet_name) = loop {
+      let stellar_neighborhood = Constraints::default().generate(&mut rng)?;
+      if let Some((observer, planet)) = stellar_neighborhood
+        .neighbors
+        .iter()
+        .enumerate()
+        .flat_map(|(index, neighbor)| {
+          neighbor
+            .star_system
+            .get_planetary_systems()
+            .into_iter()
+            .flat_map(|planetary_system| planetary_system.satellite_systems.satellite_systems.iter())
+            .map(move |satellite_system| (index, &satellite_system.planet))
+        })
+        .find(|(_, planet)| planet.is_habitable())
+      {
+        let planet_name = planet.get_name();
+        break (stellar_neighborhood, observer, planet_name);
+      }
+    };
+    let sky_catalog = get_sky_catalog(&stellar_neighborhood, &planet_name)?;
+    print_var!(sky_catalog);
+    assert!(sky_catalog.stars.len() < stellar_neighborhood.neighbors.len());
+    assert!(sky_catalog
+      .stars
+      .windows(2)
+      .all(|pair| pair[0].apparent_magnitude <= pair[1].apparent_magnitude));
+    let origin = stellar_neighborhood.neighbors[observer].coordinates;
+    for star in sky_catalog.stars.iter() {
+      assert!(star.position.distance > 0.0);
+      assert!(stellar_neighborhood
+        .neighbors
+        .iter()
+        .any(|neighbor| (get_separation(origin, neighbor.coordinates) - star.position.distance).abs() < 1e-9));
+    }
+    assert!(sky_catalog
+      .get_naked_eye_stars()
+      .all(|star| star.apparent_magnitude <= NAKED_EYE_LIMITING_MAGNITUDE));
+    assert_eq!(
+      get_sky_catalog(&stellar_neighborhood, "Nowhere In Particular"),
+      Err(Error::PlanetNotFound)
+    );
+    trace_exit!();
+    Ok(())
+  }
+}
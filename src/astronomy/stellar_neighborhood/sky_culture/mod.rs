@@ -0,0 +1,200 @@
+use rand::prelude::*;
+use serde::Serialize;
+
+use crate::astronomy::star::name::generate_star_name;
+use crate::astronomy::stellar_neighborhood::constants::*;
+use crate::astronomy::stellar_neighborhood::math::interaction::get_angular_separation;
+use crate::astronomy::stellar_neighborhood::sky_catalog::SkyCatalog;
+
+pub const FIGURES: &[&str] = &[
+  "Hunter", "Serpent", "Crown", "Ship", "Lantern", "Hound", "Spear", "Weaver", "Twins", "Scales", "Harp", "Bull",
+  "Crane", "Anvil", "Wolf", "Chalice", "Archer", "Dragon", "Plough", "Key",
+];
+
+/// The stars of a figure, as indices into a catalog, and the lines between
+/// them.
+pub type StickFigure = (Vec<usize>, Vec<(usize, usize)>);
+
+/// A group of stars seen as a figure in the sky.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Constellation {
+  /// A generated name, e.g. "The Lantern of Tarsus".
+  pub name: String,
+  /// The stars in this constellation, as indices into the catalog, brightest
+  /// first.
+  pub stars: Vec<usize>,
+  /// The lines of the stick figure, as pairs of indices into the catalog.
+  pub lines: Vec<(usize, usize)>,
+}
+
+/// The way the people of a planet see their night sky.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SkyCulture {
+  /// The sky these constellations were drawn on.
+  pub catalog: SkyCatalog,
+  /// The constellations, in order of their brightest stars.
+  pub constellations: Vec<Constellation>,
+}
+
+/// Generate a name for a constellation, e.g. "The Lantern of Tarsus".
+#[named]
+pub fn generate_constellation_name<R: Rng + ?Sized>(rng: &mut R) -> String {
+  trace_enter!();
+  let figure = FIGURES[rng.gen_range(0..FIGURES.len())];
+  trace_var!(figure);
+  let root = generate_star_name(rng);
+  trace_var!(root);
+  let result = format!("The {} of {}", figure, root);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Group the naked-eye stars of a catalog into stick figures.
+///
+/// The closest pairs of stars on the sky are joined first, so long as the
+/// figure they'd join stays small enough, which grows each figure as a tree.
+/// The figures come out in order of their brightest stars.
+#[named]
+pub fn get_stick_figures(catalog: &SkyCatalog) -> Vec<StickFigure> {
+  trace_enter!();
+  let points = catalog
+    .get_naked_eye_stars()
+    .map(|star| {
+      let coordinates = star.position.to_cartesian();
+      (coordinates.x, coordinates.y, coordinates.z)
+    })
+    .collect::<Vec<_>>();
+  trace_var!(points);
+  let mut links = vec![];
+  for (i, first) in points.iter().enumerate() {
+    for (j, second) in points.iter().enumerate().skip(i + 1) {
+      let angle = get_angular_separation(*first, *second);
+      if angle <= CONSTELLATION_MAXIMUM_LINK_ANGLE {
+        links.push((angle, i, j));
+      }
+    }
+  }
+  links.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+  trace_var!(links);
+  let mut figure_of = (0..points.len()).collect::<Vec<usize>>();
+  let mut figures = (0..points.len())
+    .map(|index| (vec![index], vec![]))
+    .collect::<Vec<StickFigure>>();
+  for (_, i, j) in links {
+    let (keep, merge) = (figure_of[i].min(figure_of[j]), figure_of[i].max(figure_of[j]));
+    if keep == merge || figures[keep].0.len() + figures[merge].0.len() > CONSTELLATION_MAXIMUM_STARS {
+      continue;
+    }
+    let (stars, lines) = std::mem::take(&mut figures[merge]);
+    for star in stars.iter() {
+      figure_of[*star] = keep;
+    }
+    figures[keep].0.extend(stars);
+    figures[keep].1.extend(lines);
+    figures[keep].1.push((i, j));
+  }
+  let result = figures
+    .into_iter()
+    .filter(|(stars, _)| stars.len() >= CONSTELLATION_MINIMUM_STARS)
+    .map(|(mut stars, lines)| {
+      stars.sort_unstable();
+      (stars, lines)
+    })
+    .collect::<Vec<_>>();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Draw and name the constellations of a sky.
+#[named]
+pub fn generate_sky_culture<R: Rng + ?Sized>(rng: &mut R, catalog: SkyCatalog) -> SkyCulture {
+  trace_enter!();
+  let constellations = get_stick_figures(&catalog)
+    .into_iter()
+    .map(|(stars, lines)| Constellation {
+      name: generate_constellation_name(rng),
+      stars,
+      lines,
+    })
+    .collect();
+  let result = SkyCulture {
+    catalog,
+    constellations,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::coordinates::GalacticCoordinates;
+  use crate::astronomy::stellar_neighborhood::sky_catalog::SkyStar;
+  use crate::test::*;
+
+  /// A star on the sky, bright enough to see.
+  fn get_sky_star(longitude: f64, latitude: f64) -> SkyStar {
+    SkyStar {
+      name: "Steve".to_string(),
+      apparent_magnitude: 1.0,
+      rgb: (255, 255, 255),
+      position: GalacticCoordinates {
+        longitude,
+        latitude,
+        distance: 5.0,
+      },
+    }
+  }
+
+  #[named]
+  #[test]
+  pub fn test_generate_sky_culture() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let mut stars = vec![
+      get_sky_star(10.0, 0.0),
+      get_sky_star(15.0, 5.0),
+      get_sky_star(20.0, 0.0),
+      get_sky_star(200.0, 60.0),
+      get_sky_star(205.0, 65.0),
+      get_sky_star(100.0, -70.0),
+    ];
+    stars.extend((0..9).map(|index| get_sky_star(300.0 + index as f64, -10.0)));
+    stars.push(SkyStar {
+      apparent_magnitude: NAKED_EYE_LIMITING_MAGNITUDE + 1.0,
+      ..get_sky_star(101.0, -70.0)
+    });
+    let catalog = SkyCatalog {
+      planet: "Steve b".to_string(),
+      neighbor: "Steve".to_string(),
+      stars,
+    };
+    let sky_culture = generate_sky_culture(&mut rng, catalog);
+    print_var!(sky_culture);
+    let figures = sky_culture
+      .constellations
+      .iter()
+      .map(|constellation| constellation.stars.clone())
+      .collect::<Vec<_>>();
+    assert_eq!(figures[0..2], [vec![0, 1, 2], vec![3, 4]]);
+    assert_eq!(figures[2..].concat().len(), 9);
+    assert!(figures[2..].concat().iter().all(|star| (6..15).contains(star)));
+    for constellation in sky_culture.constellations.iter() {
+      assert!(constellation.stars.len() <= CONSTELLATION_MAXIMUM_STARS);
+      assert_eq!(constellation.lines.len(), constellation.stars.len() - 1);
+      assert!(constellation
+        .lines
+        .iter()
+        .all(|(i, j)| constellation.stars.contains(i) && constellation.stars.contains(j)));
+      assert!(constellation.name.starts_with("The "));
+    }
+    trace_exit!();
+  }
+}
@@ -0,0 +1,220 @@
+/// A point in space, in Ly.
+pub type Point = (f64, f64, f64);
+
+/// A node of the tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+  /// The point at this node.
+  pub point: Point,
+  /// The index of the point in the list the tree was built from.
+  pub index: usize,
+  /// The axis this node splits on: 0, 1, or 2 for x, y, or z.
+  pub axis: usize,
+  /// The node holding points below the split, if any.
+  pub left: Option<usize>,
+  /// The node holding points above the split, if any.
+  pub right: Option<usize>,
+}
+
+/// A k-d tree over a list of points, for finding the points near a place
+/// without looking at every one of them.
+///
+/// The tree refers to points by their index in the list it was built from,
+/// so it needs to be rebuilt whenever that list changes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpatialIndex {
+  /// The nodes; the root is the first.
+  pub nodes: Vec<Node>,
+}
+
+impl SpatialIndex {
+  /// Build a tree over the points.
+  #[named]
+  pub fn new(points: &[Point]) -> Self {
+    trace_enter!();
+    let mut entries = points.iter().copied().enumerate().collect::<Vec<_>>();
+    let mut result = Self {
+      nodes: Vec::with_capacity(points.len()),
+    };
+    result.build(&mut entries, 0);
+    trace_exit!();
+    result
+  }
+
+  /// Build a subtree, splitting at the median, and return its root.
+  fn build(&mut self, entries: &mut [(usize, Point)], depth: usize) -> Option<usize> {
+    if entries.is_empty() {
+      return None;
+    }
+    let axis = depth % 3;
+    entries.sort_by(|a, b| {
+      get_coordinate(&a.1, axis)
+        .partial_cmp(&get_coordinate(&b.1, axis))
+        .unwrap()
+    });
+    let median = entries.len() / 2;
+    let (index, point) = entries[median];
+    let node = self.nodes.len();
+    self.nodes.push(Node {
+      point,
+      index,
+      axis,
+      left: None,
+      right: None,
+    });
+    let (below, rest) = entries.split_at_mut(median);
+    self.nodes[node].left = self.build(below, depth + 1);
+    self.nodes[node].right = self.build(&mut rest[1..], depth + 1);
+    Some(node)
+  }
+
+  /// Find the indices of the points within a radius of a point, nearest
+  /// first.
+  #[named]
+  pub fn get_within(&self, point: Point, radius: f64) -> Vec<usize> {
+    trace_enter!();
+    trace_var!(point);
+    trace_var!(radius);
+    let mut found = vec![];
+    if !self.nodes.is_empty() {
+      self.search_within(0, point, radius, &mut found);
+    }
+    found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let result = found.into_iter().map(|(_, index)| index).collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Collect the points within a radius from a subtree.
+  fn search_within(&self, node: usize, point: Point, radius: f64, found: &mut Vec<(f64, usize)>) {
+    let Node {
+      point: node_point,
+      index,
+      axis,
+      left,
+      right,
+    } = self.nodes[node];
+    let distance = get_distance(&point, &node_point);
+    if distance <= radius {
+      found.push((distance, index));
+    }
+    let offset = get_coordinate(&point, axis) - get_coordinate(&node_point, axis);
+    let (near, far) = if offset < 0.0 { (left, right) } else { (right, left) };
+    if let Some(near) = near {
+      self.search_within(near, point, radius, found);
+    }
+    if let Some(far) = far {
+      if offset.abs() <= radius {
+        self.search_within(far, point, radius, found);
+      }
+    }
+  }
+
+  /// Find the indices of the `count` points nearest a point, nearest first.
+  #[named]
+  pub fn get_nearest(&self, point: Point, count: usize) -> Vec<usize> {
+    trace_enter!();
+    trace_var!(point);
+    trace_var!(count);
+    let mut found = Vec::with_capacity(count + 1);
+    if !self.nodes.is_empty() && count > 0 {
+      self.search_nearest(0, point, count, &mut found);
+    }
+    let result = found.into_iter().map(|(_, index)| index).collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Collect the nearest points from a subtree, keeping them sorted.
+  fn search_nearest(&self, node: usize, point: Point, count: usize, found: &mut Vec<(f64, usize)>) {
+    let Node {
+      point: node_point,
+      index,
+      axis,
+      left,
+      right,
+    } = self.nodes[node];
+    let distance = get_distance(&point, &node_point);
+    if found.len() < count || distance < found[found.len() - 1].0 {
+      let position = found.partition_point(|entry| entry.0 <= distance);
+      found.insert(position, (distance, index));
+      found.truncate(count);
+    }
+    let offset = get_coordinate(&point, axis) - get_coordinate(&node_point, axis);
+    let (near, far) = if offset < 0.0 { (left, right) } else { (right, left) };
+    if let Some(near) = near {
+      self.search_nearest(near, point, count, found);
+    }
+    if let Some(far) = far {
+      if found.len() < count || offset.abs() < found[found.len() - 1].0 {
+        self.search_nearest(far, point, count, found);
+      }
+    }
+  }
+}
+
+/// Get one coordinate of a point.
+fn get_coordinate(point: &Point, axis: usize) -> f64 {
+  match axis {
+    0 => point.0,
+    1 => point.1,
+    _ => point.2,
+  }
+}
+
+/// Get the distance between two points.
+fn get_distance(a: &Point, b: &Point) -> f64 {
+  ((a.0 - b.0).powf(2.0) + (a.1 - b.1).powf(2.0) + (a.2 - b.2).powf(2.0)).sqrt()
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_spatial_index() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let points = (0..200)
+      .map(|_| {
+        (
+          rng.gen_range(-10.0..10.0),
+          rng.gen_range(-10.0..10.0),
+          rng.gen_range(-10.0..10.0),
+        )
+      })
+      .collect::<Vec<Point>>();
+    let spatial_index = SpatialIndex::new(&points);
+    assert_eq!(spatial_index.nodes.len(), points.len());
+    for _ in 0..20 {
+      let point = (
+        rng.gen_range(-10.0..10.0),
+        rng.gen_range(-10.0..10.0),
+        rng.gen_range(-10.0..10.0),
+      );
+      let mut by_distance = (0..points.len()).collect::<Vec<_>>();
+      by_distance.sort_by(|a, b| {
+        get_distance(&point, &points[*a])
+          .partial_cmp(&get_distance(&point, &points[*b]))
+          .unwrap()
+      });
+      assert_eq!(spatial_index.get_nearest(point, 5), by_distance[..5].to_vec());
+      let within = by_distance
+        .iter()
+        .copied()
+        .filter(|index| get_distance(&point, &points[*index]) <= 4.0)
+        .collect::<Vec<_>>();
+      assert_eq!(spatial_index.get_within(point, 4.0), within);
+    }
+    assert!(SpatialIndex::new(&[]).get_nearest((0.0, 0.0, 0.0), 3).is_empty());
+    trace_exit!();
+  }
+}
@@ -0,0 +1,151 @@
+use serde::Serialize;
+
+use crate::astronomy::stellar_neighborhood::constants::*;
+use crate::math::noise::{FbmOptions, Noise};
+
+/// What blew up.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum SupernovaKind {
+  /// A massive star ran out of fuel and its core collapsed.
+  CoreCollapse,
+  /// A white dwarf took on too much mass and detonated; nothing is left.
+  TypeIa,
+}
+
+/// What a core-collapse supernova leaves behind.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum RemnantKind {
+  /// The remains of a star of less than 25 Msol or so.
+  NeutronStar,
+  /// The remains of anything heavier.
+  BlackHole,
+}
+
+/// The compact object left behind by a supernova.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Remnant {
+  /// What sort of object this is.
+  pub kind: RemnantKind,
+  /// Where it is now, in light years from the origin.
+  ///
+  /// Remnants are kicked out of the explosion, so this is generally not
+  /// where the supernova happened.
+  pub coordinates: (f64, f64, f64),
+  /// The distance from the origin.
+  pub distance: f64,
+}
+
+/// The glowing gas around a young supernova remnant.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum NebulaKind {
+  /// The expanding shell itself, lit up by the shock.
+  Shell,
+  /// The shell, filled in by the wind of a young pulsar (e.g. the Crab).
+  PulsarWind,
+}
+
+/// A supernova in the past of a stellar neighborhood.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Supernova {
+  /// What blew up.
+  pub kind: SupernovaKind,
+  /// The mass of the star that blew up, in Msol.
+  pub progenitor_mass: f64,
+  /// How long ago it happened, in Myr.
+  pub age: f64,
+  /// Where it happened, in light years from the origin.
+  pub coordinates: (f64, f64, f64),
+  /// The distance from the origin.
+  pub distance: f64,
+  /// The compact object left behind, if any.
+  pub remnant: Option<Remnant>,
+  /// The radius of the expanding shell, in light years, if it hasn't yet
+  /// merged into the surrounding gas.
+  pub shell_radius: Option<f64>,
+  /// The visible nebula, if the remnant is young enough to have one.
+  pub nebula: Option<NebulaKind>,
+  /// The indices of neighbors that formed from gas this supernova enriched;
+  /// each is a little more metal-rich for it.
+  pub enriched_neighbors: Vec<usize>,
+}
+
+impl Supernova {
+  /// Get the noise that shapes the nebula's lumps and filaments.
+  ///
+  /// Supernovae don't have names, so this is seeded from where it happened.
+  /// Build this once and reuse it for every direction sampled.
+  #[named]
+  pub fn get_nebula_noise(&self) -> Noise {
+    trace_enter!();
+    let (x, y, z) = self.coordinates;
+    let result = Noise::from_id(&format!("{:.3},{:.3},{:.3}:nebula", x, y, z));
+    trace_exit!();
+    result
+  }
+
+  /// Get the distance from the center to the edge of the nebula in a given
+  /// direction, in light years, if there's a nebula to speak of.
+  ///
+  /// The direction is given in degrees of galactic longitude and latitude;
+  /// the noise should be the supernova's nebula noise.
+  #[named]
+  pub fn get_nebula_radius(&self, noise: &Noise, longitude: f64, latitude: f64) -> Option<f64> {
+    trace_enter!();
+    trace_var!(longitude);
+    trace_var!(latitude);
+    let result = self.nebula.zip(self.shell_radius).map(|(_, shell_radius)| {
+      let (longitude, latitude) = (longitude.to_radians(), latitude.to_radians());
+      let turbulence = noise.get_fbm_3d(
+        latitude.cos() * longitude.cos() * NEBULA_FREQUENCY,
+        latitude.cos() * longitude.sin() * NEBULA_FREQUENCY,
+        latitude.sin() * NEBULA_FREQUENCY,
+        &FbmOptions::default(),
+      );
+      trace_var!(turbulence);
+      shell_radius * (1.0 + NEBULA_ROUGHNESS * turbulence)
+    });
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_nebula_radius() {
+    init();
+    trace_enter!();
+    let mut supernova = Supernova {
+      kind: SupernovaKind::CoreCollapse,
+      progenitor_mass: 15.0,
+      age: 0.01,
+      coordinates: (3.0, -4.0, 5.0),
+      distance: 50.0_f64.sqrt(),
+      remnant: None,
+      shell_radius: Some(20.0),
+      nebula: Some(NebulaKind::Shell),
+      enriched_neighbors: vec![],
+    };
+    let noise = supernova.get_nebula_noise();
+    let radii = (0..360)
+      .step_by(10)
+      .filter_map(|longitude| supernova.get_nebula_radius(&noise, longitude as f64, 30.0))
+      .collect::<Vec<f64>>();
+    print_var!(radii);
+    assert_eq!(radii.len(), 36);
+    assert!(radii
+      .iter()
+      .all(|radius| (20.0 * (1.0 - NEBULA_ROUGHNESS)..=20.0 * (1.0 + NEBULA_ROUGHNESS)).contains(radius)));
+    assert!(radii.iter().any(|radius| (radius - radii[0]).abs() > 0.1));
+    // Once the shell stops glowing, there's nothing to see.
+    supernova.nebula = None;
+    assert_eq!(supernova.get_nebula_radius(&noise, 0.0, 30.0), None);
+    trace_exit!();
+  }
+}
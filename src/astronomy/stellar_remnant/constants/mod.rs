@@ -0,0 +1,57 @@
+/// The least massive star to leave a neutron star behind, in Msol.
+///
+/// Anything lighter sheds its envelope and leaves a white dwarf instead.
+pub const MINIMUM_PROGENITOR_MASS: f64 = 8.0;
+
+/// The most massive progenitor we'll generate, in Msol.
+pub const MAXIMUM_PROGENITOR_MASS: f64 = 120.0;
+
+/// The least massive star to collapse all the way to a black hole, in Msol.
+pub const MINIMUM_BLACK_HOLE_PROGENITOR_MASS: f64 = 25.0;
+
+/// The lightest neutron star, in Msol.
+pub const MINIMUM_NEUTRON_STAR_MASS: f64 = 1.2;
+
+/// The heaviest neutron star, in Msol.
+///
+/// Heavier than about this, and not even neutron degeneracy can hold it up.
+pub const MAXIMUM_NEUTRON_STAR_MASS: f64 = 2.1;
+
+/// The smallest neutron star, in KM.
+pub const MINIMUM_NEUTRON_STAR_RADIUS: f64 = 11.0;
+
+/// The largest neutron star, in KM.
+pub const MAXIMUM_NEUTRON_STAR_RADIUS: f64 = 13.0;
+
+/// The lightest black hole, in Msol.
+pub const MINIMUM_BLACK_HOLE_MASS: f64 = 3.0;
+
+/// The most of its progenitor's mass a black hole keeps.
+///
+/// Cygnus X-1 is about 21 Msol, from a star of perhaps 40.
+pub const MAXIMUM_BLACK_HOLE_MASS_FRACTION: f64 = 0.5;
+
+/// The fastest a neutron star spins at birth, in seconds.
+pub const MINIMUM_INITIAL_SPIN_PERIOD: f64 = 0.01;
+
+/// The slowest a neutron star spins at birth, in seconds.
+pub const MAXIMUM_INITIAL_SPIN_PERIOD: f64 = 0.1;
+
+/// How quickly a neutron star spins down, in seconds squared per Gyr.
+///
+/// Magnetic braking takes a typical pulsar to a period of about a second in
+/// ten million years.
+pub const SPIN_DOWN_RATE: f64 = 100.0;
+
+/// The slowest a neutron star can spin and still shine as a pulsar, in
+/// seconds.
+///
+/// Beyond this "death line", it no longer makes the pairs it needs.
+pub const PULSAR_DEATH_PERIOD: f64 = 5.0;
+
+/// The surface temperature of a neutron star a million years after its
+/// birth, in Kelvin.
+pub const NEUTRON_STAR_TEMPERATURE: f64 = 1.0E6;
+
+/// The age at which a neutron star has the temperature above, in Gyr.
+pub const NEUTRON_STAR_COOLING_AGE: f64 = 0.001;
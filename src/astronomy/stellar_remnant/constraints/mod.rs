@@ -0,0 +1,135 @@
+use rand::prelude::*;
+
+use crate::astronomy::star::math::evolution::MAXIMUM_AGE;
+use crate::astronomy::star::name::generate_star_name;
+use crate::astronomy::stellar_remnant::constants::*;
+use crate::astronomy::stellar_remnant::error::Error;
+use crate::astronomy::stellar_remnant::StellarRemnant;
+use crate::generator::Generator;
+use crate::merge::Merge;
+
+/// Constraints for creating a stellar remnant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+  /// The minimum mass of the progenitor, in Msol.
+  pub minimum_progenitor_mass: Option<f64>,
+  /// The maximum mass of the progenitor, in Msol.
+  pub maximum_progenitor_mass: Option<f64>,
+}
+
+impl Constraints {
+  /// Generate a neutron star.
+  ///
+  /// The progenitor mass is drawn from a half-open range, so it always stops
+  /// just short of making a black hole.
+  pub fn neutron_star() -> Self {
+    let minimum_progenitor_mass = Some(MINIMUM_PROGENITOR_MASS);
+    let maximum_progenitor_mass = Some(MINIMUM_BLACK_HOLE_PROGENITOR_MASS);
+    Self {
+      minimum_progenitor_mass,
+      maximum_progenitor_mass,
+    }
+  }
+
+  /// Generate a black hole.
+  pub fn black_hole() -> Self {
+    let minimum_progenitor_mass = Some(MINIMUM_BLACK_HOLE_PROGENITOR_MASS);
+    let maximum_progenitor_mass = Some(MAXIMUM_PROGENITOR_MASS);
+    Self {
+      minimum_progenitor_mass,
+      maximum_progenitor_mass,
+    }
+  }
+
+  /// Generate.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<StellarRemnant, Error> {
+    trace_enter!();
+    let minimum_progenitor_mass = self.minimum_progenitor_mass.unwrap_or(MINIMUM_PROGENITOR_MASS);
+    trace_var!(minimum_progenitor_mass);
+    let maximum_progenitor_mass = self.maximum_progenitor_mass.unwrap_or(MAXIMUM_PROGENITOR_MASS);
+    trace_var!(maximum_progenitor_mass);
+    if minimum_progenitor_mass > maximum_progenitor_mass {
+      return Err(Error::MinimumProgenitorMassExceedsMaximumProgenitorMass);
+    }
+    // Heavy stars are rare, so pick log-uniformly.
+    let progenitor_mass = rng
+      .gen_range(minimum_progenitor_mass.ln()..maximum_progenitor_mass.ln())
+      .exp();
+    trace_var!(progenitor_mass);
+    // Massive stars die young, so nearly all of the galaxy's history is open.
+    let current_age = rng.gen_range(0.0..MAXIMUM_AGE);
+    trace_var!(current_age);
+    let name = generate_star_name(rng);
+    trace_var!(name);
+    let result = StellarRemnant::from_progenitor(rng, name, progenitor_mass, current_age);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl Generator for Constraints {
+  type Output = StellarRemnant;
+  type Error = Error;
+
+  #[named]
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<StellarRemnant, Error> {
+    trace_enter!();
+    let result = Constraints::generate(self, rng);
+    trace_exit!();
+    result
+  }
+}
+
+impl Default for Constraints {
+  /// No constraints, just let it all hang out.
+  fn default() -> Self {
+    let minimum_progenitor_mass = None;
+    let maximum_progenitor_mass = None;
+    Self {
+      minimum_progenitor_mass,
+      maximum_progenitor_mass,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_progenitor_mass: overrides.minimum_progenitor_mass.or(self.minimum_progenitor_mass),
+      maximum_progenitor_mass: overrides.maximum_progenitor_mass.or(self.maximum_progenitor_mass),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::stellar_remnant::StellarRemnantKind;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let neutron_star = Constraints::neutron_star().generate(&mut rng)?;
+    print_var!(neutron_star);
+    assert_eq!(neutron_star.kind, StellarRemnantKind::NeutronStar);
+    assert_eq!(
+      Constraints::neutron_star().maximum_progenitor_mass,
+      Constraints::black_hole().minimum_progenitor_mass
+    );
+    let black_hole = Constraints::black_hole().generate(&mut rng)?;
+    print_var!(black_hole);
+    assert_eq!(black_hole.kind, StellarRemnantKind::BlackHole);
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,33 @@
+use crate::astronomy::star::error::Error as StarError;
+
+/// StellarRemnant errors.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+  /// The minimum progenitor mass is greater than the maximum.
+  MinimumProgenitorMassExceedsMaximumProgenitorMass,
+  /// There's no fusion left to keep anything warm.
+  NoFusion,
+  /// Star Error
+  StarError(StarError),
+}
+
+honeyholt_define_brief!(Error, |error: &Error| {
+  use Error::*;
+  match error {
+    MinimumProgenitorMassExceedsMaximumProgenitorMass => {
+      "the minimum progenitor mass is greater than the maximum progenitor mass".to_string()
+    },
+    NoFusion => "the stellar remnant gives off too little light to support life".to_string(),
+    StarError(star_error) => format!(
+      "an error occurred in the progenitor star ({})",
+      honeyholt_brief!(star_error)
+    ),
+  }
+});
+
+impl From<StarError> for Error {
+  #[named]
+  fn from(error: StarError) -> Self {
+    Error::StarError(error)
+  }
+}
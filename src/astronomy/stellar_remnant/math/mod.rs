@@ -0,0 +1,83 @@
+use crate::astronomy::star::math::distance::METERS_PER_SOLAR_RADIUS;
+use crate::astronomy::star::math::evolution::SOLAR_TEMPERATURE;
+use crate::astronomy::stellar_remnant::constants::*;
+
+/// The Schwarzschild radius of one Msol, in KM.
+pub const SCHWARZSCHILD_RADIUS_PER_MSOL: f64 = 2.953;
+
+/// Calculate the radius of a black hole's event horizon, in KM, from its mass
+/// in Msol.
+#[named]
+pub fn get_schwarzschild_radius(mass: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  let result = SCHWARZSCHILD_RADIUS_PER_MSOL * mass;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the spin period of a neutron star, in seconds, from its spin
+/// period at birth (in seconds) and its age (in Gyr).
+///
+/// Magnetic dipole braking makes the square of the period grow steadily.
+#[named]
+pub fn get_spin_period(initial_spin_period: f64, age: f64) -> f64 {
+  trace_enter!();
+  trace_var!(initial_spin_period);
+  trace_var!(age);
+  let result = (initial_spin_period.powf(2.0) + SPIN_DOWN_RATE * age).sqrt();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the surface temperature of a neutron star, in Kelvin, from its
+/// age in Gyr.
+#[named]
+pub fn get_neutron_star_temperature(age: f64) -> f64 {
+  trace_enter!();
+  trace_var!(age);
+  let result = NEUTRON_STAR_TEMPERATURE * (age.max(NEUTRON_STAR_COOLING_AGE) / NEUTRON_STAR_COOLING_AGE).powf(-0.25);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the thermal luminosity of a neutron star, in Lsol, from its
+/// radius in KM and its temperature in Kelvin.
+#[named]
+pub fn get_neutron_star_luminosity(radius: f64, temperature: f64) -> f64 {
+  trace_enter!();
+  trace_var!(radius);
+  trace_var!(temperature);
+  let result = (radius * 1_000.0 / METERS_PER_SOLAR_RADIUS).powf(2.0) * (temperature / SOLAR_TEMPERATURE).powf(4.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_crab_pulsar() {
+    init();
+    trace_enter!();
+    // The Crab pulsar is about a thousand years old, and spins every 33 ms.
+    let spin_period = get_spin_period(0.02, 1.0E-6);
+    print_var!(spin_period);
+    assert!(spin_period < PULSAR_DEATH_PERIOD);
+    assert!(get_spin_period(0.02, 1.0) > PULSAR_DEATH_PERIOD);
+    let temperature = get_neutron_star_temperature(1.0E-6);
+    let luminosity = get_neutron_star_luminosity(12.0, temperature);
+    print_var!(luminosity);
+    assert!(luminosity > 0.01 && luminosity < 10.0);
+    assert_approx_eq!(get_schwarzschild_radius(10.0), 29.53);
+    trace_exit!();
+  }
+}
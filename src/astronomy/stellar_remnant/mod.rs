@@ -0,0 +1,276 @@
+use rand::prelude::*;
+use serde::Serialize;
+
+use crate::astronomy::star::math::frost_line::star_luminosity_to_frost_line;
+use crate::astronomy::star::math::habitable_zone::{get_habitable_zone, HabitableZoneModel};
+use crate::astronomy::star::math::luminosity::star_mass_to_luminosity;
+use crate::astronomy::star::math::satellite_zone::{get_approximate_innermost_orbit, get_approximate_outermost_orbit};
+
+pub mod constants;
+use constants::*;
+pub mod constraints;
+pub mod error;
+use error::Error;
+pub(crate) mod math;
+use math::*;
+
+/// What a massive star leaves behind when it dies.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum StellarRemnantKind {
+  /// A city-sized ball of neutrons, spinning furiously.
+  NeutronStar,
+  /// Nothing at all, or nothing that can be seen.
+  BlackHole,
+}
+
+/// A `StellarRemnant` is the collapsed core of a star too massive to leave a
+/// white dwarf.
+///
+/// These give off next to no light, so nothing orbiting one is habitable,
+/// but pulsar planets are real enough, and make for a grim sort of setting.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StellarRemnant {
+  /// The name.
+  pub name: String,
+  /// What sort of remnant this is.
+  pub kind: StellarRemnantKind,
+  /// The mass of the star it used to be, in Msol.
+  pub progenitor_mass: f64,
+  /// How long the star it used to be lived, in Gyr.
+  pub progenitor_life_expectancy: f64,
+  /// Mass, in Msol.
+  pub mass: f64,
+  /// Radius, in KM; for a black hole, that of its event horizon.
+  pub radius: f64,
+  /// Measured in Gyr, from the birth of the progenitor.
+  pub current_age: f64,
+  /// Surface temperature, in Kelvin; zero for a black hole.
+  pub temperature: f64,
+  /// Thermal luminosity, in Lsol; zero for a black hole.
+  pub luminosity: f64,
+  /// Rotation period, in seconds, for a neutron star.
+  pub spin_period: Option<f64>,
+  /// Whether this is a neutron star still spinning fast enough to shine as
+  /// a pulsar.
+  pub is_pulsar: bool,
+  /// Habitable zone, measured in AU.
+  pub habitable_zone: (f64, f64),
+  /// Minimum and maximum sustainable distance for satellites, measured in AU.
+  pub satellite_zone: (f64, f64),
+  /// The frost line, measured in AU.
+  pub frost_line: f64,
+}
+
+impl StellarRemnant {
+  /// Create the remnant of a star of the given mass (in Msol), as it is at
+  /// the given age (in Gyr) since the star's birth.
+  ///
+  /// If the star wouldn't have died yet, the remnant is taken to be freshly
+  /// made.
+  #[named]
+  pub fn from_progenitor<R: Rng + ?Sized>(
+    rng: &mut R,
+    name: String,
+    progenitor_mass: f64,
+    current_age: f64,
+  ) -> Result<Self, Error> {
+    trace_enter!();
+    trace_var!(name);
+    trace_var!(progenitor_mass);
+    trace_var!(current_age);
+    let progenitor_life_expectancy = progenitor_mass / star_mass_to_luminosity(progenitor_mass)? * 10.0;
+    trace_var!(progenitor_life_expectancy);
+    let remnant_age = (current_age - progenitor_life_expectancy).max(0.0);
+    trace_var!(remnant_age);
+    let kind = match progenitor_mass >= MINIMUM_BLACK_HOLE_PROGENITOR_MASS {
+      true => StellarRemnantKind::BlackHole,
+      false => StellarRemnantKind::NeutronStar,
+    };
+    trace_var!(kind);
+    let (mass, radius, temperature, luminosity, spin_period) = match kind {
+      StellarRemnantKind::NeutronStar => {
+        let mass = rng.gen_range(MINIMUM_NEUTRON_STAR_MASS..MAXIMUM_NEUTRON_STAR_MASS);
+        let radius = rng.gen_range(MINIMUM_NEUTRON_STAR_RADIUS..MAXIMUM_NEUTRON_STAR_RADIUS);
+        let temperature = get_neutron_star_temperature(remnant_age);
+        let luminosity = get_neutron_star_luminosity(radius, temperature);
+        let initial_spin_period = rng.gen_range(MINIMUM_INITIAL_SPIN_PERIOD..MAXIMUM_INITIAL_SPIN_PERIOD);
+        let spin_period = get_spin_period(initial_spin_period, remnant_age);
+        (mass, radius, temperature, luminosity, Some(spin_period))
+      },
+      StellarRemnantKind::BlackHole => {
+        let maximum_mass = (MAXIMUM_BLACK_HOLE_MASS_FRACTION * progenitor_mass).max(MINIMUM_BLACK_HOLE_MASS);
+        let mass = rng.gen_range(MINIMUM_BLACK_HOLE_MASS..=maximum_mass);
+        (mass, get_schwarzschild_radius(mass), 0.0, 0.0, None)
+      },
+    };
+    trace_var!(mass);
+    trace_var!(radius);
+    trace_var!(temperature);
+    trace_var!(luminosity);
+    trace_var!(spin_period);
+    let is_pulsar = spin_period.is_some_and(|spin_period| spin_period < PULSAR_DEATH_PERIOD);
+    trace_var!(is_pulsar);
+    let habitable_zone = get_habitable_zone(luminosity, temperature, HabitableZoneModel::Classic);
+    trace_var!(habitable_zone);
+    let satellite_zone = (
+      get_approximate_innermost_orbit(mass),
+      get_approximate_outermost_orbit(mass),
+    );
+    trace_var!(satellite_zone);
+    let frost_line = star_luminosity_to_frost_line(luminosity);
+    trace_var!(frost_line);
+    let result = Self {
+      name,
+      kind,
+      progenitor_mass,
+      progenitor_life_expectancy,
+      mass,
+      radius,
+      current_age: current_age.max(progenitor_life_expectancy),
+      temperature,
+      luminosity,
+      spin_period,
+      is_pulsar,
+      habitable_zone,
+      satellite_zone,
+      frost_line,
+    };
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+
+  /// Evolve this remnant forward by the given time, in Gyr.
+  ///
+  /// A neutron star cools and spins down; a black hole just gets older.  It
+  /// can be taken back in time, but not past the death of its progenitor.
+  #[named]
+  pub fn evolve(&self, delta: f64) -> Self {
+    trace_enter!();
+    trace_var!(delta);
+    let current_age = (self.current_age + delta).max(self.progenitor_life_expectancy);
+    trace_var!(current_age);
+    let result = match self.kind {
+      StellarRemnantKind::NeutronStar => {
+        let remnant_age = current_age - self.progenitor_life_expectancy;
+        trace_var!(remnant_age);
+        let temperature = get_neutron_star_temperature(remnant_age);
+        trace_var!(temperature);
+        let luminosity = get_neutron_star_luminosity(self.radius, temperature);
+        trace_var!(luminosity);
+        let spin_period = self
+          .spin_period
+          .map(|spin_period| get_spin_period(spin_period, current_age - self.current_age));
+        trace_var!(spin_period);
+        let is_pulsar = spin_period.is_some_and(|spin_period| spin_period < PULSAR_DEATH_PERIOD);
+        trace_var!(is_pulsar);
+        let habitable_zone = get_habitable_zone(luminosity, temperature, HabitableZoneModel::Classic);
+        trace_var!(habitable_zone);
+        let frost_line = star_luminosity_to_frost_line(luminosity);
+        trace_var!(frost_line);
+        Self {
+          current_age,
+          temperature,
+          luminosity,
+          spin_period,
+          is_pulsar,
+          habitable_zone,
+          frost_line,
+          ..self.clone()
+        }
+      },
+      StellarRemnantKind::BlackHole => Self {
+        current_age,
+        ..self.clone()
+      },
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the short class used in tables: "NS" or "BH".
+  #[named]
+  pub fn get_class(&self) -> String {
+    trace_enter!();
+    let result = match self.kind {
+      StellarRemnantKind::NeutronStar => "NS",
+      StellarRemnantKind::BlackHole => "BH",
+    }
+    .to_string();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this stellar remnant is capable of supporting
+  /// conventional life.
+  ///
+  /// It never is; even a young neutron star shines mostly in X-rays.
+  #[named]
+  pub fn check_habitable(&self) -> Result<(), Error> {
+    trace_enter!();
+    let result = Err(Error::NoFusion);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this stellar remnant is capable of supporting
+  /// conventional life.
+  #[named]
+  pub fn is_habitable(&self) -> bool {
+    trace_enter!();
+    let result = match self.check_habitable() {
+      Ok(()) => true,
+      Err(_) => false,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_from_progenitor() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let neutron_star = StellarRemnant::from_progenitor(&mut rng, "Vela".to_string(), 12.0, 0.03)?;
+    print_var!(neutron_star);
+    assert_eq!(neutron_star.kind, StellarRemnantKind::NeutronStar);
+    assert!(neutron_star.is_pulsar);
+    assert!(!neutron_star.is_habitable());
+    let old_neutron_star = StellarRemnant::from_progenitor(&mut rng, "Old".to_string(), 12.0, 5.0)?;
+    print_var!(old_neutron_star);
+    assert!(!old_neutron_star.is_pulsar);
+    assert!(old_neutron_star.luminosity < neutron_star.luminosity);
+    let evolved = neutron_star.evolve(5.0);
+    print_var!(evolved);
+    assert!(!evolved.is_pulsar);
+    assert!(evolved.spin_period > neutron_star.spin_period);
+    assert!(evolved.luminosity < neutron_star.luminosity);
+    assert_eq!(
+      evolved.evolve(-100.0).current_age,
+      neutron_star.progenitor_life_expectancy
+    );
+    let black_hole = StellarRemnant::from_progenitor(&mut rng, "Cygnus".to_string(), 40.0, 1.0)?;
+    print_var!(black_hole);
+    assert_eq!(black_hole.kind, StellarRemnantKind::BlackHole);
+    assert!(black_hole.mass >= MINIMUM_BLACK_HOLE_MASS && black_hole.mass <= 20.0);
+    assert_eq!(black_hole.spin_period, None);
+    assert_eq!(black_hole.get_class(), "BH");
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -12,6 +12,39 @@ pub const MINIMUM_HABITABLE_MASS: f64 = 0.75;
 /// Lowered because 3.5 just sounds extreme to me.
 pub const MAXIMUM_HABITABLE_MASS: f64 = 1.50;
 
+/// Minimum core mass fraction.
+///
+/// Not much iron at all, like the Moon.
+pub const MINIMUM_CORE_MASS_FRACTION: f64 = 0.05;
+
+/// Maximum core mass fraction.
+///
+/// Mostly iron, like Mercury.
+pub const MAXIMUM_CORE_MASS_FRACTION: f64 = 0.70;
+
+/// Minimum habitable core mass fraction.
+pub const MINIMUM_HABITABLE_CORE_MASS_FRACTION: f64 = 0.25;
+
+/// Maximum habitable core mass fraction.
+pub const MAXIMUM_HABITABLE_CORE_MASS_FRACTION: f64 = 0.45;
+
+/// Minimum habitable axial tilt, in degrees.
+pub const MINIMUM_HABITABLE_AXIAL_TILT: f64 = 0.0;
+
+/// Maximum habitable axial tilt, in degrees.
+///
+/// Much past this and, without a big moon to steady it, the tilt is liable
+/// to wander somewhere unpleasant.
+pub const MAXIMUM_HABITABLE_AXIAL_TILT: f64 = 30.0;
+
+/// Minimum rotational period, in Dearth.
+///
+/// Much faster than this and a rocky planet starts to fly apart.
+pub const MINIMUM_ROTATIONAL_PERIOD: f64 = 0.1;
+
+/// Maximum rotational period, in Dearth.
+pub const MAXIMUM_ROTATIONAL_PERIOD: f64 = 5.0;
+
 /// Minimum habitable rotational period, in Dearth.
 pub const MINIMUM_HABITABLE_ROTATIONAL_PERIOD: f64 = 0.25;
 
@@ -42,6 +75,9 @@ pub const MINIMUM_HABITABLE_BOND_ALBEDO: f64 = 0.11;
 /// Maximum Bond albedo.
 pub const MAXIMUM_HABITABLE_BOND_ALBEDO: f64 = 0.50;
 
+/// The escape velocity of Earth, in km/s.
+pub const EARTH_ESCAPE_VELOCITY: f64 = 11.186;
+
 /// Stefan-Boltzmann constant.
 pub const STEFAN_BOLTZMANN_CONSTANT: f64 = 0.00005670374419;
 
@@ -56,3 +92,12 @@ pub const MINIMUM_HABITABLE_GRAVITY: f64 = 0.5;
 
 /// Too damned hard to get out of bed.
 pub const MAXIMUM_HABITABLE_GRAVITY: f64 = 1.5;
+
+/// The most atmospheric loss a habitable planet can have suffered, relative
+/// to Earth's.
+pub const MAXIMUM_ATMOSPHERIC_LOSS: f64 = 5.0;
+
+/// How many times the terrain noise repeats around a planet's radius.
+///
+/// Higher values give more, smaller continents.
+pub const TERRAIN_FREQUENCY: f64 = 1.5;
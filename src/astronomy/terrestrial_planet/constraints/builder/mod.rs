@@ -0,0 +1,155 @@
+use std::ops::Range;
+
+use crate::astronomy::terrestrial_planet::constraints::Constraints;
+use crate::math::range::check_range;
+use crate::math::range::error::Error;
+
+/// A fluent builder for terrestrial planet constraints.
+///
+/// Nothing is checked until `build()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Builder {
+  constraints: Constraints,
+}
+
+impl Builder {
+  /// Restrict the mass, in Mearth.
+  pub fn mass_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_mass = Some(range.start);
+    self.constraints.maximum_mass = Some(range.end);
+    self
+  }
+
+  /// Restrict the core mass fraction.
+  pub fn core_mass_fraction_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_core_mass_fraction = Some(range.start);
+    self.constraints.maximum_core_mass_fraction = Some(range.end);
+    self
+  }
+
+  /// Restrict the axial tilt, in degrees.
+  pub fn axial_tilt_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_axial_tilt = Some(range.start);
+    self.constraints.maximum_axial_tilt = Some(range.end);
+    self
+  }
+
+  /// Restrict the rotational period, in Dearth.
+  pub fn rotational_period_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_rotational_period = Some(range.start);
+    self.constraints.maximum_rotational_period = Some(range.end);
+    self
+  }
+
+  /// Restrict the orbital eccentricity.
+  pub fn eccentricity_range(mut self, range: Range<f64>) -> Self {
+    self.constraints.minimum_orbital_eccentricity = Some(range.start);
+    self.constraints.maximum_orbital_eccentricity = Some(range.end);
+    self
+  }
+
+  /// Count tidally locked planets as habitable.
+  pub fn allow_eyeball_worlds(mut self) -> Self {
+    self.constraints.allow_eyeball_worlds = Some(true);
+    self
+  }
+
+  /// Aim for a habitable planet.
+  ///
+  /// Anything not already given is filled in from `Constraints::habitable()`.
+  pub fn habitable(mut self) -> Self {
+    let habitable = Constraints::habitable();
+    let constraints = &mut self.constraints;
+    constraints.minimum_mass = constraints.minimum_mass.or(habitable.minimum_mass);
+    constraints.maximum_mass = constraints.maximum_mass.or(habitable.maximum_mass);
+    constraints.minimum_core_mass_fraction = constraints
+      .minimum_core_mass_fraction
+      .or(habitable.minimum_core_mass_fraction);
+    constraints.maximum_core_mass_fraction = constraints
+      .maximum_core_mass_fraction
+      .or(habitable.maximum_core_mass_fraction);
+    constraints.minimum_axial_tilt = constraints.minimum_axial_tilt.or(habitable.minimum_axial_tilt);
+    constraints.maximum_axial_tilt = constraints.maximum_axial_tilt.or(habitable.maximum_axial_tilt);
+    constraints.minimum_rotational_period = constraints
+      .minimum_rotational_period
+      .or(habitable.minimum_rotational_period);
+    constraints.maximum_rotational_period = constraints
+      .maximum_rotational_period
+      .or(habitable.maximum_rotational_period);
+    constraints.minimum_orbital_eccentricity = constraints
+      .minimum_orbital_eccentricity
+      .or(habitable.minimum_orbital_eccentricity);
+    constraints.maximum_orbital_eccentricity = constraints
+      .maximum_orbital_eccentricity
+      .or(habitable.maximum_orbital_eccentricity);
+    self
+  }
+
+  /// Check the constraints and return them.
+  #[named]
+  pub fn build(self) -> Result<Constraints, Error> {
+    trace_enter!();
+    let constraints = self.constraints;
+    trace_var!(constraints);
+    check_range(
+      "mass",
+      constraints.minimum_mass,
+      constraints.maximum_mass,
+      (0.0, f64::INFINITY),
+    )?;
+    check_range(
+      "core mass fraction",
+      constraints.minimum_core_mass_fraction,
+      constraints.maximum_core_mass_fraction,
+      (0.0, 1.0),
+    )?;
+    check_range(
+      "axial tilt",
+      constraints.minimum_axial_tilt,
+      constraints.maximum_axial_tilt,
+      (0.0, 180.0),
+    )?;
+    check_range(
+      "rotational period",
+      constraints.minimum_rotational_period,
+      constraints.maximum_rotational_period,
+      (0.0, f64::INFINITY),
+    )?;
+    check_range(
+      "orbital eccentricity",
+      constraints.minimum_orbital_eccentricity,
+      constraints.maximum_orbital_eccentricity,
+      (0.0, 1.0),
+    )?;
+    trace_exit!();
+    Ok(constraints)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::astronomy::terrestrial_planet::constants::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_build() {
+    init();
+    trace_enter!();
+    let constraints = Constraints::builder()
+      .axial_tilt_range(20.0..25.0)
+      .habitable()
+      .build()
+      .unwrap();
+    print_var!(constraints);
+    assert_eq!(constraints.minimum_axial_tilt, Some(20.0));
+    assert_eq!(constraints.minimum_mass, Some(MINIMUM_HABITABLE_MASS));
+    assert_eq!(
+      Constraints::builder().axial_tilt_range(0.0..200.0).build(),
+      Err(Error::RangeAboveBound("axial tilt"))
+    );
+    trace_exit!();
+  }
+}
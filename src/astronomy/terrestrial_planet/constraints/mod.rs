@@ -1,11 +1,35 @@
 use rand::prelude::*;
 
 use crate::astronomy::host_star::HostStar;
+use crate::astronomy::orbit::constants::DAYS_PER_YEAR;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::planet::math::oblateness::{
+  get_equatorial_and_polar_radii, get_j2, get_oblateness, get_rotational_parameter,
+  TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR,
+};
+use crate::astronomy::planet::seasons::Seasons;
 use crate::astronomy::terrestrial_planet::constants::*;
 use crate::astronomy::terrestrial_planet::error::Error;
-use crate::astronomy::terrestrial_planet::math::temperature::get_equilibrium_temperature;
+use crate::astronomy::terrestrial_planet::math::atmospheric_stability::is_atmospherically_stable;
+use crate::astronomy::terrestrial_planet::math::dynamo::{get_magnetic_moment, has_dynamo};
+use crate::astronomy::terrestrial_planet::math::geology::{
+  get_radiogenic_heat, get_relative_heat_flow, get_tidal_heat, has_plate_tectonics,
+};
+use crate::astronomy::terrestrial_planet::math::habitability_window::get_habitability_window;
+use crate::astronomy::terrestrial_planet::math::hydrosphere::{
+  get_ocean_fraction, get_random_water_mass_fraction, get_water_inventory, is_water_liquid,
+};
+use crate::astronomy::terrestrial_planet::math::temperature::{
+  get_equilibrium_temperature, get_greenhouse_effect, get_surface_temperature,
+};
+use crate::astronomy::terrestrial_planet::math::thermal_escape::{can_retain_atmosphere, get_insolation};
+use crate::astronomy::terrestrial_planet::math::tidal_locking::is_tidally_locked;
 use crate::astronomy::terrestrial_planet::rotation_direction::RotationDirection;
+use crate::astronomy::terrestrial_planet::volcanic_activity::VolcanicActivity;
 use crate::astronomy::terrestrial_planet::TerrestrialPlanet;
+use crate::merge::Merge;
+pub mod builder;
+use builder::Builder;
 
 /// Constraints for creating a planet.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -14,6 +38,10 @@ pub struct Constraints {
   pub minimum_mass: Option<f64>,
   /// The maximum mass.
   pub maximum_mass: Option<f64>,
+  /// The minimum core mass fraction.
+  pub minimum_core_mass_fraction: Option<f64>,
+  /// The maximum core mass fraction.
+  pub maximum_core_mass_fraction: Option<f64>,
   /// The minimum axial tilt.
   pub minimum_axial_tilt: Option<f64>,
   /// The maximum axial tilt.
@@ -30,13 +58,24 @@ pub struct Constraints {
   pub host_star_distance: Option<f64>,
   /// The luminosity of the host star, in Lsol.
   pub host_star_luminosity: Option<f64>,
+  /// Whether a tidally locked planet may still be habitable.
+  pub allow_eyeball_worlds: Option<bool>,
 }
 
 impl Constraints {
+  /// Start building constraints fluently.
+  pub fn builder() -> Builder {
+    Builder::default()
+  }
+
   /// No constraints, just let it all hang out.
   pub fn habitable() -> Self {
     let minimum_mass = Some(MINIMUM_HABITABLE_MASS);
     let maximum_mass = Some(MAXIMUM_HABITABLE_MASS);
+    let minimum_core_mass_fraction = Some(MINIMUM_HABITABLE_CORE_MASS_FRACTION);
+    let maximum_core_mass_fraction = Some(MAXIMUM_HABITABLE_CORE_MASS_FRACTION);
+    let minimum_axial_tilt = Some(MINIMUM_HABITABLE_AXIAL_TILT);
+    let maximum_axial_tilt = Some(MAXIMUM_HABITABLE_AXIAL_TILT);
     let minimum_rotational_period = Some(MINIMUM_HABITABLE_ROTATIONAL_PERIOD);
     let maximum_rotational_period = Some(MAXIMUM_HABITABLE_ROTATIONAL_PERIOD);
     let minimum_orbital_eccentricity = Some(MINIMUM_HABITABLE_ORBITAL_ECCENTRICITY);
@@ -44,6 +83,10 @@ impl Constraints {
     Self {
       minimum_mass,
       maximum_mass,
+      minimum_core_mass_fraction,
+      maximum_core_mass_fraction,
+      minimum_axial_tilt,
+      maximum_axial_tilt,
       minimum_rotational_period,
       maximum_rotational_period,
       minimum_orbital_eccentricity,
@@ -65,17 +108,28 @@ impl Constraints {
     trace_var!(minimum_mass);
     let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
     trace_var!(maximum_mass);
-    let mass = rng.gen_range(minimum_mass..maximum_mass);
+    let mass = rng.gen_range(minimum_mass..=maximum_mass);
     trace_var!(mass);
-    let mut result = TerrestrialPlanet::from_mass(mass)?;
+    let minimum_core_mass_fraction = self.minimum_core_mass_fraction.unwrap_or(MINIMUM_CORE_MASS_FRACTION);
+    trace_var!(minimum_core_mass_fraction);
+    let maximum_core_mass_fraction = self.maximum_core_mass_fraction.unwrap_or(MAXIMUM_CORE_MASS_FRACTION);
+    trace_var!(maximum_core_mass_fraction);
+    let core_mass_fraction = rng.gen_range(minimum_core_mass_fraction..=maximum_core_mass_fraction);
+    trace_var!(core_mass_fraction);
+    let mut result = TerrestrialPlanet::from_mass_and_core_mass_fraction(mass, core_mass_fraction)?;
     trace_var!(result);
     let minimum_axial_tilt = self.minimum_axial_tilt.unwrap_or(0.0);
     trace_var!(minimum_axial_tilt);
     let maximum_axial_tilt = self.maximum_axial_tilt.unwrap_or(180.0);
     trace_var!(maximum_axial_tilt);
-    let axial_tilt = rng.gen_range(minimum_axial_tilt..maximum_axial_tilt);
+    let axial_tilt = rng.gen_range(minimum_axial_tilt..=maximum_axial_tilt);
     trace_var!(axial_tilt);
-    result.semi_major_axis = distance;
+    let minimum_rotational_period = self.minimum_rotational_period.unwrap_or(MINIMUM_ROTATIONAL_PERIOD);
+    trace_var!(minimum_rotational_period);
+    let maximum_rotational_period = self.maximum_rotational_period.unwrap_or(MAXIMUM_ROTATIONAL_PERIOD);
+    trace_var!(maximum_rotational_period);
+    let rotation_period = rng.gen_range(minimum_rotational_period..=maximum_rotational_period);
+    trace_var!(rotation_period);
     result.axial_tilt = axial_tilt;
     result.rotation_direction = match axial_tilt {
       axial_tilt if axial_tilt > 0.0 && axial_tilt < 90.0 => RotationDirection::Prograde,
@@ -100,25 +154,101 @@ impl Constraints {
       .maximum_orbital_eccentricity
       .unwrap_or(MAXIMUM_ORBITAL_ECCENTRICITY);
     trace_var!(maximum_orbital_eccentricity);
-    let orbital_eccentricity = rng.gen_range(minimum_orbital_eccentricity..maximum_orbital_eccentricity);
-    result.orbital_eccentricity = orbital_eccentricity;
+    let orbital_eccentricity = rng.gen_range(minimum_orbital_eccentricity..=maximum_orbital_eccentricity);
     trace_var!(orbital_eccentricity);
-    let perihelion = (1.0 - orbital_eccentricity) * distance;
-    result.perihelion = perihelion;
-    trace_var!(perihelion);
-    let aphelion = (1.0 + orbital_eccentricity) * distance;
-    result.aphelion = aphelion;
-    trace_var!(aphelion);
+    let orbit = OrbitalElements::new(distance, orbital_eccentricity).with_random_orientation(rng);
+    result.orbit = orbit;
+    trace_var!(orbit);
     let orbital_period = (distance.powf(3.0) / host_star.get_stellar_mass()).sqrt();
     result.orbital_period = orbital_period;
     trace_var!(orbital_period);
+    result.seasons = Seasons::new(axial_tilt, orbital_eccentricity, orbital_period);
+    let is_tidally_locked = is_tidally_locked(
+      host_star.get_stellar_mass(),
+      distance,
+      result.mass,
+      result.radius,
+      rotation_period,
+      host_star.get_current_age(),
+    );
+    trace_var!(is_tidally_locked);
+    result.is_tidally_locked = is_tidally_locked;
+    result.is_eyeball_world = is_tidally_locked && self.allow_eyeball_worlds.unwrap_or(false);
+    // A locked planet turns once per orbit.
+    let rotation_period = match is_tidally_locked {
+      true => orbital_period * DAYS_PER_YEAR,
+      false => rotation_period,
+    };
+    trace_var!(rotation_period);
+    let rotational_parameter = get_rotational_parameter(rotation_period, result.density);
+    trace_var!(rotational_parameter);
+    let oblateness = get_oblateness(rotational_parameter, TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR);
+    trace_var!(oblateness);
+    let (equatorial_radius, polar_radius) = get_equatorial_and_polar_radii(result.radius, oblateness);
+    result.rotation_period = rotation_period;
+    result.oblateness = oblateness;
+    result.equatorial_radius = equatorial_radius;
+    result.polar_radius = polar_radius;
+    result.j2 = get_j2(oblateness, rotational_parameter);
+    let has_magnetic_field = has_dynamo(result.mass, core_mass_fraction, host_star.get_current_age());
+    trace_var!(has_magnetic_field);
+    result.has_magnetic_field = has_magnetic_field;
+    result.magnetic_moment = match has_magnetic_field {
+      true => get_magnetic_moment(result.mass, core_mass_fraction, rotation_period),
+      false => 0.0,
+    };
     let bond_albedo = result.bond_albedo;
     trace_var!(bond_albedo);
-    let greenhouse_effect = result.greenhouse_effect;
-    result.greenhouse_effect = greenhouse_effect;
-    trace_var!(greenhouse_effect);
     let luminosity = host_star.get_luminosity();
-    result.equilibrium_temperature = get_equilibrium_temperature(bond_albedo, greenhouse_effect, luminosity, distance);
+    trace_var!(luminosity);
+    let equilibrium_temperature = get_equilibrium_temperature(bond_albedo, luminosity, distance);
+    trace_var!(equilibrium_temperature);
+    result.equilibrium_temperature = equilibrium_temperature;
+    let relative_escape_velocity = result.get_relative_escape_velocity();
+    trace_var!(relative_escape_velocity);
+    let insolation = get_insolation(luminosity, distance);
+    trace_var!(insolation);
+    let has_atmosphere = can_retain_atmosphere(insolation, relative_escape_velocity);
+    trace_var!(has_atmosphere);
+    result.has_atmosphere = has_atmosphere;
+    let greenhouse_effect = get_greenhouse_effect(has_atmosphere, equilibrium_temperature, relative_escape_velocity);
+    trace_var!(greenhouse_effect);
+    result.greenhouse_effect = greenhouse_effect;
+    let surface_temperature = get_surface_temperature(equilibrium_temperature, greenhouse_effect);
+    trace_var!(surface_temperature);
+    result.surface_temperature = surface_temperature;
+    result.is_atmospherically_stable =
+      has_atmosphere && is_atmospherically_stable(equilibrium_temperature, relative_escape_velocity);
+    let radiogenic_heat = get_radiogenic_heat(result.mass, core_mass_fraction, host_star.get_current_age());
+    trace_var!(radiogenic_heat);
+    result.radiogenic_heat = radiogenic_heat;
+    let tidal_heat = get_tidal_heat(
+      host_star.get_stellar_mass(),
+      result.radius,
+      distance,
+      orbital_eccentricity,
+      orbital_period,
+    );
+    trace_var!(tidal_heat);
+    result.tidal_heat = tidal_heat;
+    let relative_heat_flow = get_relative_heat_flow(radiogenic_heat + tidal_heat, result.radius);
+    trace_var!(relative_heat_flow);
+    result.volcanic_activity = VolcanicActivity::from_heat_flow(relative_heat_flow);
+    result.has_plate_tectonics = has_plate_tectonics(result.mass, relative_heat_flow, result.can_retain_water());
+    let water_mass_fraction = get_random_water_mass_fraction(rng, distance, host_star.get_frost_line());
+    trace_var!(water_mass_fraction);
+    let water_inventory = get_water_inventory(result.mass, water_mass_fraction);
+    trace_var!(water_inventory);
+    result.water_inventory = water_inventory;
+    result.ocean_fraction = match result.can_retain_water() && is_water_liquid(surface_temperature) {
+      true => get_ocean_fraction(water_inventory, result.radius, result.surface_gravity),
+      false => 0.0,
+    };
+    let habitability_window =
+      get_habitability_window(host_star, distance, result.mass, result.radius, surface_temperature);
+    trace_var!(habitability_window);
+    result.habitable_from_gyr = habitability_window.0;
+    result.habitable_until_gyr = habitability_window.1;
     trace_exit!();
     Ok(result)
   }
@@ -129,17 +259,22 @@ impl Default for Constraints {
   fn default() -> Self {
     let minimum_mass = None;
     let maximum_mass = None;
+    let minimum_core_mass_fraction = None;
+    let maximum_core_mass_fraction = None;
     let minimum_axial_tilt = None;
     let maximum_axial_tilt = None;
     let minimum_rotational_period = None;
     let maximum_rotational_period = None;
     let host_star_distance = None;
     let host_star_luminosity = None;
+    let allow_eyeball_worlds = None;
     let minimum_orbital_eccentricity = Some(MINIMUM_ORBITAL_ECCENTRICITY);
     let maximum_orbital_eccentricity = Some(MAXIMUM_ORBITAL_ECCENTRICITY);
     Self {
       minimum_mass,
       maximum_mass,
+      minimum_core_mass_fraction,
+      maximum_core_mass_fraction,
       minimum_axial_tilt,
       maximum_axial_tilt,
       minimum_rotational_period,
@@ -148,6 +283,31 @@ impl Default for Constraints {
       maximum_orbital_eccentricity,
       host_star_distance,
       host_star_luminosity,
+      allow_eyeball_worlds,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+      minimum_core_mass_fraction: overrides.minimum_core_mass_fraction.or(self.minimum_core_mass_fraction),
+      maximum_core_mass_fraction: overrides.maximum_core_mass_fraction.or(self.maximum_core_mass_fraction),
+      minimum_axial_tilt: overrides.minimum_axial_tilt.or(self.minimum_axial_tilt),
+      maximum_axial_tilt: overrides.maximum_axial_tilt.or(self.maximum_axial_tilt),
+      minimum_rotational_period: overrides.minimum_rotational_period.or(self.minimum_rotational_period),
+      maximum_rotational_period: overrides.maximum_rotational_period.or(self.maximum_rotational_period),
+      minimum_orbital_eccentricity: overrides
+        .minimum_orbital_eccentricity
+        .or(self.minimum_orbital_eccentricity),
+      maximum_orbital_eccentricity: overrides
+        .maximum_orbital_eccentricity
+        .or(self.maximum_orbital_eccentricity),
+      host_star_distance: overrides.host_star_distance.or(self.host_star_distance),
+      host_star_luminosity: overrides.host_star_luminosity.or(self.host_star_luminosity),
+      allow_eyeball_worlds: overrides.allow_eyeball_worlds.or(self.allow_eyeball_worlds),
     }
   }
 }
@@ -156,6 +316,8 @@ impl Default for Constraints {
 pub mod test {
 
   use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+  use crate::astronomy::host_star::error::Error as HostStarError;
+  use crate::astronomy::star::Star;
   use rand::prelude::*;
 
   use super::*;
@@ -188,4 +350,50 @@ pub mod test {
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_tidal_locking() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let mut star = Star::from_mass(&mut rng, 0.3).map_err(HostStarError::from)?;
+    star.current_age = 5.0;
+    let host_star = HostStar::Star(star);
+    let planet = Constraints::default().generate(&mut rng, &host_star, 0.1)?;
+    print_var!(planet);
+    assert!(planet.is_tidally_locked);
+    assert!(!planet.is_eyeball_world);
+    assert_approx_eq!(planet.rotation_period, planet.orbital_period * DAYS_PER_YEAR);
+    assert!(!planet.is_habitable());
+    let constraints = Constraints::builder().allow_eyeball_worlds().build().unwrap();
+    let planet = constraints.generate(&mut rng, &host_star, 0.1)?;
+    assert!(planet.is_eyeball_world);
+    assert_ne!(planet.check_habitable(), Err(Error::TidallyLocked));
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_thermal_escape() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let mut star = Star::from_mass(&mut rng, 1.0).map_err(HostStarError::from)?;
+    star.current_age = 4.6;
+    let host_star = HostStar::Star(star);
+    let constraints = Constraints::builder().mass_range(0.1..0.11).build().unwrap();
+    let planet = constraints.generate(&mut rng, &host_star, 0.1)?;
+    print_var!(planet);
+    assert!(!planet.has_magnetic_field);
+    assert!(!planet.has_atmosphere);
+    assert!(!planet.can_retain_nitrogen());
+    assert_approx_eq!(planet.greenhouse_effect, 0.0);
+    assert!(!planet.is_atmospherically_stable);
+    trace_exit!();
+    Ok(())
+  }
 }
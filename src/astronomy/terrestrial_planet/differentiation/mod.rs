@@ -0,0 +1,46 @@
+use serde::Serialize;
+
+/// Bodies at least this massive melt enough to differentiate fully, in Mearth.
+///
+/// The Moon made it; this is about where the heat of accretion and decay
+/// stops being the limiting factor.
+pub const FULL_DIFFERENTIATION_MASS: f64 = 0.01;
+
+/// Bodies at least this massive differentiate partially, in Mearth.
+///
+/// Ceres is just above this line, with a rocky core and an icy mantle that
+/// never fully separated.
+pub const PARTIAL_DIFFERENTIATION_MASS: f64 = 1.0E-4;
+
+/// How far a planet's interior has separated into core, mantle, and crust.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum Differentiation {
+  /// A well-mixed rubble pile.
+  Undifferentiated,
+  /// A core of sorts, but nothing clean.
+  PartiallyDifferentiated,
+  /// Iron core, silicate mantle, and crust.
+  Differentiated,
+}
+
+impl Differentiation {
+  /// Determine the differentiation state from mass, in Mearth, and CMF.
+  ///
+  /// A planet with no iron to speak of has nothing to sink into a core.
+  #[named]
+  pub fn from_mass(mass: f64, cmf: f64) -> Self {
+    trace_enter!();
+    trace_var!(mass);
+    trace_var!(cmf);
+    use Differentiation::*;
+    let result = match mass {
+      _ if cmf <= 0.0 => Undifferentiated,
+      mass if mass >= FULL_DIFFERENTIATION_MASS => Differentiated,
+      mass if mass >= PARTIAL_DIFFERENTIATION_MASS => PartiallyDifferentiated,
+      _ => Undifferentiated,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
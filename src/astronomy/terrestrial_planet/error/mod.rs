@@ -13,6 +13,8 @@ pub enum Error {
   GravityTooLowToSupportConventionalLife,
   /// Just sounds kinda lame.
   GravityTooHighToSupportConventionalLife,
+  /// Boiled away, if it ever had one.
+  Airless,
   /// Oxygen unstable in this atmosphere.
   AtmosphereUnstableForOxygen,
   /// Carbon Dioxide unstable in this atmosphere.
@@ -21,6 +23,12 @@ pub enum Error {
   AtmosphereUnstableForArgon,
   /// Nitrogen unstable in this atmosphere.
   AtmosphereUnstableForNitrogen,
+  /// Ice ages one epoch, equatorial glaciers the next.
+  ChaoticObliquity,
+  /// Nothing to keep the stellar wind off the surface.
+  NoMagneticField,
+  /// One side bakes while the other freezes.
+  TidallyLocked,
 }
 
 honeyholt_define_brief!(Error, |error: &Error| {
@@ -34,10 +42,14 @@ honeyholt_define_brief!(Error, |error: &Error| {
     TooHotToSupportConventionalLife => "not habitable because it is too hot".to_string(),
     GravityTooLowToSupportConventionalLife => "not habitable because its gravity is too low".to_string(),
     GravityTooHighToSupportConventionalLife => "not habitable because its gravity is too high".to_string(),
+    Airless => "not habitable because it has no atmosphere".to_string(),
     AtmosphereUnstableForOxygen => "not habitable because it cannot retain oxygen".to_string(),
     AtmosphereUnstableForCarbonDioxide => "not habitable because it cannot retain carbon dioxide".to_string(),
     AtmosphereUnstableForArgon => "not habitable because it cannot retain argon".to_string(),
     AtmosphereUnstableForNitrogen => "not habitable because it cannot retain nitrogen".to_string(),
+    ChaoticObliquity => "not habitable because its axial tilt wanders chaotically".to_string(),
+    NoMagneticField => "not habitable because it has no magnetic field to shield it".to_string(),
+    TidallyLocked => "not habitable because it is tidally locked".to_string(),
   }
 });
 
@@ -2,6 +2,7 @@ pub const OXYGEN_WEIGHT: f64 = 0.032;
 pub const CO2_WEIGHT: f64 = 0.044;
 pub const ARGON_WEIGHT: f64 = 0.04;
 pub const NITROGEN_WEIGHT: f64 = 0.028;
+pub const WATER_WEIGHT: f64 = 0.018;
 
 /// Calculates whether a molecule can be stable in this atmosphere, given:
 /// `equilibrium_temperature` - of the body, in Kelvin.
@@ -37,59 +38,23 @@ pub fn is_molecule_stable(mol_weight: f64, equilibrium_temperature: f64, escape_
 }
 
 #[named]
-pub fn get_oxygen_stability(equilibrium_temperature: f64, escape_velocity: f64) -> f64 {
+pub fn is_oxygen_stable(equilibrium_temperature: f64, escape_velocity: f64) -> bool {
   trace_enter!();
   trace_var!(equilibrium_temperature);
   trace_var!(escape_velocity);
   let mol_weight = OXYGEN_WEIGHT;
-  let result = get_molecule_stability(mol_weight, equilibrium_temperature, escape_velocity);
+  let result = is_molecule_stable(mol_weight, equilibrium_temperature, escape_velocity);
   trace_var!(result);
   trace_exit!();
   result
 }
 
 #[named]
-pub fn get_carbon_dioxide_stability(equilibrium_temperature: f64, escape_velocity: f64) -> f64 {
+pub fn is_carbon_dioxide_stable(equilibrium_temperature: f64, escape_velocity: f64) -> bool {
   trace_enter!();
   trace_var!(equilibrium_temperature);
   trace_var!(escape_velocity);
   let mol_weight = CO2_WEIGHT;
-  let result = get_molecule_stability(mol_weight, equilibrium_temperature, escape_velocity);
-  trace_var!(result);
-  trace_exit!();
-  result
-}
-
-#[named]
-pub fn get_argon_stability(equilibrium_temperature: f64, escape_velocity: f64) -> f64 {
-  trace_enter!();
-  trace_var!(equilibrium_temperature);
-  trace_var!(escape_velocity);
-  let mol_weight = ARGON_WEIGHT;
-  let result = get_molecule_stability(mol_weight, equilibrium_temperature, escape_velocity);
-  trace_var!(result);
-  trace_exit!();
-  result
-}
-
-#[named]
-pub fn get_nitrogen_stability(equilibrium_temperature: f64, escape_velocity: f64) -> f64 {
-  trace_enter!();
-  trace_var!(equilibrium_temperature);
-  trace_var!(escape_velocity);
-  let mol_weight = NITROGEN_WEIGHT;
-  let result = get_molecule_stability(mol_weight, equilibrium_temperature, escape_velocity);
-  trace_var!(result);
-  trace_exit!();
-  result
-}
-
-#[named]
-pub fn is_oxygen_stable(equilibrium_temperature: f64, escape_velocity: f64) -> bool {
-  trace_enter!();
-  trace_var!(equilibrium_temperature);
-  trace_var!(escape_velocity);
-  let mol_weight = OXYGEN_WEIGHT;
   let result = is_molecule_stable(mol_weight, equilibrium_temperature, escape_velocity);
   trace_var!(result);
   trace_exit!();
@@ -97,11 +62,11 @@ pub fn is_oxygen_stable(equilibrium_temperature: f64, escape_velocity: f64) -> b
 }
 
 #[named]
-pub fn is_carbon_dioxide_stable(equilibrium_temperature: f64, escape_velocity: f64) -> bool {
+pub fn is_argon_stable(equilibrium_temperature: f64, escape_velocity: f64) -> bool {
   trace_enter!();
   trace_var!(equilibrium_temperature);
   trace_var!(escape_velocity);
-  let mol_weight = CO2_WEIGHT;
+  let mol_weight = ARGON_WEIGHT;
   let result = is_molecule_stable(mol_weight, equilibrium_temperature, escape_velocity);
   trace_var!(result);
   trace_exit!();
@@ -109,11 +74,11 @@ pub fn is_carbon_dioxide_stable(equilibrium_temperature: f64, escape_velocity: f
 }
 
 #[named]
-pub fn is_argon_stable(equilibrium_temperature: f64, escape_velocity: f64) -> bool {
+pub fn is_nitrogen_stable(equilibrium_temperature: f64, escape_velocity: f64) -> bool {
   trace_enter!();
   trace_var!(equilibrium_temperature);
   trace_var!(escape_velocity);
-  let mol_weight = ARGON_WEIGHT;
+  let mol_weight = NITROGEN_WEIGHT;
   let result = is_molecule_stable(mol_weight, equilibrium_temperature, escape_velocity);
   trace_var!(result);
   trace_exit!();
@@ -121,11 +86,11 @@ pub fn is_argon_stable(equilibrium_temperature: f64, escape_velocity: f64) -> bo
 }
 
 #[named]
-pub fn is_nitrogen_stable(equilibrium_temperature: f64, escape_velocity: f64) -> bool {
+pub fn is_water_stable(equilibrium_temperature: f64, escape_velocity: f64) -> bool {
   trace_enter!();
   trace_var!(equilibrium_temperature);
   trace_var!(escape_velocity);
-  let mol_weight = NITROGEN_WEIGHT;
+  let mol_weight = WATER_WEIGHT;
   let result = is_molecule_stable(mol_weight, equilibrium_temperature, escape_velocity);
   trace_var!(result);
   trace_exit!();
@@ -159,7 +124,7 @@ pub mod test {
     trace_enter!();
     let equilibrium_temperature = 288.0;
     let escape_velocity = 1.0;
-    let oxygen_stability = get_oxygen_stability(equilibrium_temperature, escape_velocity);
+    let oxygen_stability = get_molecule_stability(OXYGEN_WEIGHT, equilibrium_temperature, escape_velocity);
     assert_approx_eq!(oxygen_stability, 0.579, 0.001);
     assert!(is_oxygen_stable(equilibrium_temperature, escape_velocity));
     trace_exit!();
@@ -172,7 +137,7 @@ pub mod test {
     trace_enter!();
     let equilibrium_temperature = 288.0;
     let escape_velocity = 1.0;
-    let carbon_dioxide_stability = get_carbon_dioxide_stability(equilibrium_temperature, escape_velocity);
+    let carbon_dioxide_stability = get_molecule_stability(CO2_WEIGHT, equilibrium_temperature, escape_velocity);
     assert_approx_eq!(carbon_dioxide_stability, 0.494, 0.001);
     assert!(is_carbon_dioxide_stable(equilibrium_temperature, escape_velocity));
     trace_exit!();
@@ -185,7 +150,7 @@ pub mod test {
     trace_enter!();
     let equilibrium_temperature = 288.0;
     let escape_velocity = 1.0;
-    let argon_stability = get_argon_stability(equilibrium_temperature, escape_velocity);
+    let argon_stability = get_molecule_stability(ARGON_WEIGHT, equilibrium_temperature, escape_velocity);
     assert_approx_eq!(argon_stability, 0.518, 0.001);
     assert!(is_argon_stable(equilibrium_temperature, escape_velocity));
     trace_exit!();
@@ -198,9 +163,22 @@ pub mod test {
     trace_enter!();
     let equilibrium_temperature = 288.0;
     let escape_velocity = 1.0;
-    let nitrogen_stability = get_nitrogen_stability(equilibrium_temperature, escape_velocity);
+    let nitrogen_stability = get_molecule_stability(NITROGEN_WEIGHT, equilibrium_temperature, escape_velocity);
     assert_approx_eq!(nitrogen_stability, 0.619, 0.001);
     assert!(is_nitrogen_stable(equilibrium_temperature, escape_velocity));
     trace_exit!();
   }
+
+  #[named]
+  #[test]
+  pub fn test_get_water_stability() {
+    init();
+    trace_enter!();
+    let equilibrium_temperature = 288.0;
+    let escape_velocity = 1.0;
+    let water_stability = get_molecule_stability(WATER_WEIGHT, equilibrium_temperature, escape_velocity);
+    assert_approx_eq!(water_stability, 0.772, 0.001);
+    assert!(is_water_stable(equilibrium_temperature, escape_velocity));
+    trace_exit!();
+  }
 }
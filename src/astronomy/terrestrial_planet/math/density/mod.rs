@@ -1,21 +1,20 @@
+use crate::astronomy::terrestrial_planet::math::mass_radius::get_radius_from_mass;
+
 /// Calculate the density of terrestrial planet, given its mass and CMF.
 ///
 /// The CMF, or Core Mass Fraction, indicates what percentage of the planet's
 /// mass is contained within its iron core.
 ///
-/// Given that, we can calculate the overall density of the planet in Dearth.
+/// Given that, we can find the radius from the mass-radius relation, and
+/// from there the overall density of the planet in g/cm³.
 #[named]
 pub fn get_density(mass: f64, cmf: f64) -> f64 {
   trace_enter!();
   trace_var!(mass);
   trace_var!(cmf);
-  let d1 = 5.51 * mass.powf(0.189) / (1.07 - 0.21 * (cmf)).powf(3.0);
-  let d2 = 3.5 + 4.37 * cmf;
-  let result = match mass {
-    mass if mass > 0.6 => d1,
-    _mass if d1 > d2 => d1,
-    _ => d2,
-  };
+  let radius = get_radius_from_mass(mass, cmf);
+  trace_var!(radius);
+  let result = 5.51 * mass / radius.powf(3.0);
   trace_var!(result);
   trace_exit!();
   result
@@ -0,0 +1,85 @@
+/// Earth's core mass fraction.
+pub const EARTH_CORE_MASS_FRACTION: f64 = 0.325;
+
+/// How long an Earth-mass planet keeps its core convecting, in Gyr.
+///
+/// This comfortably outlasts the Sun.
+pub const EARTH_DYNAMO_LIFETIME: f64 = 15.0;
+
+/// How steeply the dynamo lifetime falls off with mass.
+///
+/// Small planets cool quickly; this puts Mars's dynamo dying out after about
+/// half a billion years, which is roughly when its crust stops recording one.
+pub const DYNAMO_LIFETIME_EXPONENT: f64 = 1.5;
+
+/// Calculate how long a planet's core dynamo lasts, in Gyr.
+///
+/// Mass is in Mearth.
+#[named]
+pub fn get_dynamo_lifetime(mass: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  let result = EARTH_DYNAMO_LIFETIME * mass.powf(DYNAMO_LIFETIME_EXPONENT);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Indicate whether a planet's core dynamo is still running at a given age,
+/// in Gyr.
+///
+/// Mass is in Mearth; a planet without a core has no dynamo at all.
+#[named]
+pub fn has_dynamo(mass: f64, cmf: f64, age: f64) -> bool {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(cmf);
+  trace_var!(age);
+  let result = cmf > 0.0 && age < get_dynamo_lifetime(mass);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the magnetic moment of a planet with a running dynamo, relative
+/// to Earth's.
+///
+/// This is Blackett's rule, more or less: the moment goes with the mass of
+/// the core and how fast it spins.  Mass is in Mearth and the rotation period
+/// in Dearth; Mercury comes out within a factor of a few.
+#[named]
+pub fn get_magnetic_moment(mass: f64, cmf: f64, rotation_period: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(cmf);
+  trace_var!(rotation_period);
+  let core_mass = mass * cmf / EARTH_CORE_MASS_FRACTION;
+  trace_var!(core_mass);
+  let result = core_mass / rotation_period;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_dynamo() {
+    init();
+    trace_enter!();
+    // Earth.
+    assert!(has_dynamo(1.0, EARTH_CORE_MASS_FRACTION, 4.6));
+    assert_approx_eq!(get_magnetic_moment(1.0, EARTH_CORE_MASS_FRACTION, 1.0), 1.0);
+    // Mars.
+    assert!(!has_dynamo(0.107, 0.24, 4.6));
+    assert!(has_dynamo(0.107, 0.24, 0.4));
+    // Venus spins too slowly for much of a field.
+    assert!(get_magnetic_moment(0.815, 0.32, 243.0) < 0.01);
+    trace_exit!();
+  }
+}
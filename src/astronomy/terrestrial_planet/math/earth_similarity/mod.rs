@@ -0,0 +1,81 @@
+/// Earth's mean density, in g/cm³.
+pub const EARTH_DENSITY: f64 = 5.514;
+
+/// Earth's mean surface temperature, in Kelvin.
+pub const EARTH_SURFACE_TEMPERATURE: f64 = 288.0;
+
+/// Weight of radius in the Earth Similarity Index.
+pub const RADIUS_WEIGHT: f64 = 0.57;
+
+/// Weight of density in the Earth Similarity Index.
+pub const DENSITY_WEIGHT: f64 = 1.07;
+
+/// Weight of escape velocity in the Earth Similarity Index.
+pub const ESCAPE_VELOCITY_WEIGHT: f64 = 0.70;
+
+/// Weight of surface temperature in the Earth Similarity Index.
+pub const SURFACE_TEMPERATURE_WEIGHT: f64 = 5.58;
+
+/// Calculate how similar one property is to Earth's, from 0 to 1.
+///
+/// The weight sharpens or softens the falloff; it's divided by the number of
+/// properties going into the overall index.
+#[named]
+pub fn get_similarity(value: f64, earth_value: f64, weight: f64, count: usize) -> f64 {
+  trace_enter!();
+  trace_var!(value);
+  trace_var!(earth_value);
+  trace_var!(weight);
+  let result = (1.0 - ((value - earth_value) / (value + earth_value)).abs()).powf(weight / count as f64);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the Earth Similarity Index (Schulze-Makuch et al., 2011), from
+/// 0 (nothing like Earth) to 1 (just like it).
+///
+/// Radius is in Rearth, density in g/cm³, escape velocity in Vearth, and
+/// surface temperature in Kelvin.
+#[named]
+pub fn get_earth_similarity_index(radius: f64, density: f64, escape_velocity: f64, surface_temperature: f64) -> f64 {
+  trace_enter!();
+  let similarities = [
+    get_similarity(radius, 1.0, RADIUS_WEIGHT, 4),
+    get_similarity(density, EARTH_DENSITY, DENSITY_WEIGHT, 4),
+    get_similarity(escape_velocity, 1.0, ESCAPE_VELOCITY_WEIGHT, 4),
+    get_similarity(
+      surface_temperature,
+      EARTH_SURFACE_TEMPERATURE,
+      SURFACE_TEMPERATURE_WEIGHT,
+      4,
+    ),
+  ];
+  trace_var!(similarities);
+  let result = similarities.iter().product();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_earth_similarity_index() {
+    init();
+    trace_enter!();
+    assert_approx_eq!(get_earth_similarity_index(1.0, EARTH_DENSITY, 1.0, 288.0), 1.0);
+    let mars = get_earth_similarity_index(0.532, 3.933, 0.450, 227.0);
+    print_var!(mars);
+    assert_approx_eq!(mars, 0.70, 0.02);
+    let venus = get_earth_similarity_index(0.950, 5.243, 0.926, 737.0);
+    print_var!(venus);
+    assert!(venus < mars);
+    trace_exit!();
+  }
+}
@@ -0,0 +1,152 @@
+use std::f64::consts::PI;
+
+use crate::astronomy::orbit::constants::DAYS_PER_YEAR;
+use crate::astronomy::planet::math::oblateness::{GRAVITATIONAL_CONSTANT, SECONDS_PER_DAY};
+use crate::astronomy::planet::math::radius::METERS_PER_EARTH_RADIUS;
+use crate::astronomy::planet::math::tidal_braking::TERRESTRIAL_TIDAL_LAG;
+use crate::astronomy::star::math::distance::au_to_meters;
+use crate::astronomy::star::math::mass::msol_to_kg;
+use crate::astronomy::terrestrial_planet::math::dynamo::EARTH_CORE_MASS_FRACTION;
+use crate::astronomy::terrestrial_planet::math::habitability_window::EARTH_AGE;
+
+/// Earth's present radiogenic heat production, in TW.
+pub const EARTH_RADIOGENIC_HEAT: f64 = 20.0;
+
+/// How long it takes a planet's radiogenic heat production to halve, in Gyr.
+///
+/// This lumps together uranium, thorium, and potassium; the real curve is
+/// steeper early on, when potassium-40 was still doing its share.
+pub const RADIOGENIC_HALF_LIFE: f64 = 3.0;
+
+/// The least mass at which a planet is thought to sustain plate tectonics, in
+/// Mearth.
+///
+/// Smaller planets cool into a single thick lid, like Mars.
+pub const MINIMUM_PLATE_TECTONICS_MASS: f64 = 0.5;
+
+/// The greatest mass at which a planet is thought to sustain plate tectonics,
+/// in Mearth.
+///
+/// This one is hotly debated; past here, we assume the lithosphere is too
+/// strong to break up.
+pub const MAXIMUM_PLATE_TECTONICS_MASS: f64 = 5.0;
+
+/// The least heat flow, relative to Earth's, that keeps the plates moving.
+pub const MINIMUM_PLATE_TECTONICS_HEAT_FLOW: f64 = 0.5;
+
+/// Calculate the heat produced by radioactive decay in a planet's mantle, in
+/// TW.
+///
+/// Mass is in Mearth and age in Gyr; the heat comes from the silicate
+/// mantle, so an iron-rich planet makes less of it.
+#[named]
+pub fn get_radiogenic_heat(mass: f64, cmf: f64, age: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(cmf);
+  trace_var!(age);
+  let mantle_mass = mass * (1.0 - cmf) / (1.0 - EARTH_CORE_MASS_FRACTION);
+  trace_var!(mantle_mass);
+  let result = EARTH_RADIOGENIC_HEAT * mantle_mass * 0.5_f64.powf((age - EARTH_AGE) / RADIOGENIC_HALF_LIFE);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the heat raised in a planet by the tides of its host star, in
+/// TW.
+///
+/// This only happens on an eccentric orbit, as the tidal bulge rises and
+/// falls.  Stellar mass is in Msol, radius in Rearth, the semi-major axis in
+/// AU, and the orbital period in Earth years.
+#[named]
+pub fn get_tidal_heat(
+  stellar_mass: f64,
+  radius: f64,
+  semi_major_axis: f64,
+  orbital_eccentricity: f64,
+  orbital_period: f64,
+) -> f64 {
+  trace_enter!();
+  trace_var!(stellar_mass);
+  trace_var!(radius);
+  trace_var!(semi_major_axis);
+  trace_var!(orbital_eccentricity);
+  trace_var!(orbital_period);
+  let mean_motion = 2.0 * PI / (orbital_period * DAYS_PER_YEAR * SECONDS_PER_DAY);
+  trace_var!(mean_motion);
+  let watts = 10.5
+    * TERRESTRIAL_TIDAL_LAG
+    * GRAVITATIONAL_CONSTANT
+    * msol_to_kg(stellar_mass).powf(2.0)
+    * (radius * METERS_PER_EARTH_RADIUS).powf(5.0)
+    * mean_motion
+    * orbital_eccentricity.powf(2.0)
+    / au_to_meters(semi_major_axis).powf(6.0);
+  trace_var!(watts);
+  let result = watts / 1.0E12;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the heat flowing through a planet's surface, relative to
+/// Earth's radiogenic heat flow.
+///
+/// Heat is in TW and radius in Rearth.
+#[named]
+pub fn get_relative_heat_flow(heat: f64, radius: f64) -> f64 {
+  trace_enter!();
+  trace_var!(heat);
+  trace_var!(radius);
+  let result = heat / EARTH_RADIOGENIC_HEAT / radius.powf(2.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Indicate whether a planet has plate tectonics.
+///
+/// It needs to be in the right mass range, to have enough heat to drive
+/// mantle convection, and to hold on to the water that weakens its
+/// lithosphere enough to break into plates.  Mass is in Mearth and heat flow
+/// is relative to Earth's.
+#[named]
+pub fn has_plate_tectonics(mass: f64, relative_heat_flow: f64, has_water: bool) -> bool {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(relative_heat_flow);
+  trace_var!(has_water);
+  let result = (MINIMUM_PLATE_TECTONICS_MASS..=MAXIMUM_PLATE_TECTONICS_MASS).contains(&mass)
+    && relative_heat_flow >= MINIMUM_PLATE_TECTONICS_HEAT_FLOW
+    && has_water;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_geology() {
+    init();
+    trace_enter!();
+    // Earth.
+    let heat = get_radiogenic_heat(1.0, EARTH_CORE_MASS_FRACTION, EARTH_AGE);
+    assert_approx_eq!(heat, EARTH_RADIOGENIC_HEAT);
+    assert!(get_tidal_heat(1.0, 1.0, 1.0, 0.0167, 1.0) < 0.001);
+    assert!(has_plate_tectonics(1.0, get_relative_heat_flow(heat, 1.0), true));
+    // Mars.
+    let heat = get_radiogenic_heat(0.107, 0.24, EARTH_AGE);
+    assert!(!has_plate_tectonics(0.107, get_relative_heat_flow(heat, 0.532), true));
+    // A close, eccentric orbit around a red dwarf cooks the interior.
+    let orbital_period = (0.05_f64.powf(3.0) / 0.3).sqrt();
+    assert!(get_tidal_heat(0.3, 1.0, 0.05, 0.1, orbital_period) > 1000.0);
+    trace_exit!();
+  }
+}
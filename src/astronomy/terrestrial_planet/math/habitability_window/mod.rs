@@ -0,0 +1,218 @@
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::terrestrial_planet::constants::*;
+
+/// How long a star like the Sun keeps its XUV output saturated, in Gyr.
+pub const SOLAR_XUV_SATURATION_TIME: f64 = 0.1;
+
+/// How quickly XUV output falls off once it is no longer saturated.
+pub const XUV_DECAY_EXPONENT: f64 = 1.23;
+
+/// Earth's current age, in Gyr.
+pub const EARTH_AGE: f64 = 4.6;
+
+/// How many steps we take through a star's lifetime looking for the window.
+pub const HABITABILITY_WINDOW_RESOLUTION: usize = 1000;
+
+/// Get the time for which a star's XUV output remains saturated, in Gyr.
+///
+/// Lower-mass stars spin down more slowly and stay active for much longer.
+#[named]
+pub fn get_xuv_saturation_time(stellar_mass: f64) -> f64 {
+  trace_enter!();
+  trace_var!(stellar_mass);
+  let result = SOLAR_XUV_SATURATION_TIME * stellar_mass.powf(-2.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Integrate XUV output over time, in units of saturated luminosity times Gyr.
+#[named]
+pub fn get_cumulative_xuv_output(age: f64, saturation_time: f64) -> f64 {
+  trace_enter!();
+  trace_var!(age);
+  trace_var!(saturation_time);
+  let decay = XUV_DECAY_EXPONENT - 1.0;
+  let result = match age {
+    age if age <= saturation_time => age,
+    age => saturation_time + (saturation_time / decay) * (1.0 - (age / saturation_time).powf(-decay)),
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the XUV fluence a planet has received by a given age, relative to what
+/// Earth has received by now.
+///
+/// XUV output is taken to be a fixed fraction of bolometric luminosity (in
+/// Lsol) while saturated; distance is in AU.
+#[named]
+pub fn get_relative_xuv_fluence(stellar_mass: f64, luminosity: f64, distance: f64, age: f64) -> f64 {
+  trace_enter!();
+  trace_var!(stellar_mass);
+  trace_var!(luminosity);
+  trace_var!(distance);
+  trace_var!(age);
+  let saturation_time = get_xuv_saturation_time(stellar_mass);
+  trace_var!(saturation_time);
+  let earth = get_cumulative_xuv_output(EARTH_AGE, SOLAR_XUV_SATURATION_TIME);
+  trace_var!(earth);
+  let result = (luminosity / distance.powf(2.0)) * get_cumulative_xuv_output(age, saturation_time) / earth;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the atmospheric loss a planet has suffered, relative to Earth's.
+///
+/// Energy-limited escape scales with XUV fluence and with R³/M, so small,
+/// fluffy planets suffer most.  Units are Mearth and Rearth.
+#[named]
+pub fn get_atmospheric_loss(mass: f64, radius: f64, relative_xuv_fluence: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(radius);
+  trace_var!(relative_xuv_fluence);
+  let result = relative_xuv_fluence * radius.powf(3.0) / mass;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the luminosity of a main-sequence star at some age, relative to its
+/// luminosity at the start of the main sequence.
+///
+/// This is Gough's approximation for the Sun, stretched over the lifetime of
+/// the star; stars brighten by about 40% by middle age and nearly double by
+/// the time they leave the main sequence.
+#[named]
+pub fn get_relative_luminosity(age: f64, life_expectancy: f64) -> f64 {
+  trace_enter!();
+  trace_var!(age);
+  trace_var!(life_expectancy);
+  let fraction = age / life_expectancy;
+  trace_var!(fraction);
+  let result = 1.0 / (1.0 + 0.4 * (1.0 - fraction / 0.457));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate when a planet first becomes habitable and when it stops.
+///
+/// A planet is habitable once its star has calmed down enough to let it keep
+/// an atmosphere, for as long as the brightening star leaves it within the
+/// habitable temperature range, and until it has lost too much of its
+/// atmosphere to escape.  The current temperature is scaled by the star's
+/// brightening over time.
+///
+/// Returns the start and end of the window in Gyr.  If the planet is never
+/// habitable, these are both zero.
+#[named]
+pub fn get_habitability_window(
+  host_star: &HostStar,
+  distance: f64,
+  mass: f64,
+  radius: f64,
+  current_temperature: f64,
+) -> (f64, f64) {
+  trace_enter!();
+  trace_var!(host_star);
+  trace_var!(distance);
+  trace_var!(mass);
+  trace_var!(radius);
+  trace_var!(current_temperature);
+  let stellar_mass = host_star.get_stellar_mass();
+  trace_var!(stellar_mass);
+  let luminosity = host_star.get_luminosity();
+  trace_var!(luminosity);
+  let life_expectancy = host_star.get_life_expectancy();
+  trace_var!(life_expectancy);
+  let current_age = host_star.get_current_age();
+  trace_var!(current_age);
+  let saturation_time = get_xuv_saturation_time(stellar_mass);
+  trace_var!(saturation_time);
+  let current_luminosity = get_relative_luminosity(current_age, life_expectancy);
+  trace_var!(current_luminosity);
+  let is_habitable_at = |age: f64| {
+    let temperature =
+      current_temperature * (get_relative_luminosity(age, life_expectancy) / current_luminosity).powf(0.25);
+    let fluence = get_relative_xuv_fluence(stellar_mass, luminosity, distance, age);
+    let loss = get_atmospheric_loss(mass, radius, fluence);
+    age >= saturation_time
+      && temperature > MINIMUM_HABITABLE_TEMPERATURE
+      && temperature < MAXIMUM_HABITABLE_TEMPERATURE
+      && loss < MAXIMUM_ATMOSPHERIC_LOSS
+  };
+  let step = life_expectancy / HABITABILITY_WINDOW_RESOLUTION as f64;
+  trace_var!(step);
+  let ages = (0..=HABITABILITY_WINDOW_RESOLUTION).map(|i| i as f64 * step);
+  let result = match ages.clone().find(|&age| is_habitable_at(age)) {
+    Some(from) => {
+      let until = ages
+        .filter(|&age| age > from)
+        .find(|&age| !is_habitable_at(age))
+        .unwrap_or(life_expectancy);
+      (from, until)
+    },
+    None => (0.0, 0.0),
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::host_star::error::Error;
+  use crate::astronomy::star::Star;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_relative_xuv_fluence() {
+    init();
+    trace_enter!();
+    // Earth, today.
+    let mut actual = get_relative_xuv_fluence(1.0, 1.0, 1.0, EARTH_AGE);
+    assert_approx_eq!(1.0, actual, 1e-3f64);
+    // Mars gets less.
+    actual = get_relative_xuv_fluence(1.0, 1.0, 1.524, EARTH_AGE);
+    assert_approx_eq!(0.431, actual, 1e-3f64);
+    // A red dwarf stays angry for much longer.
+    actual = get_relative_xuv_fluence(0.3, 1.0, 1.0, EARTH_AGE);
+    assert!(actual > 5.0);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_habitability_window() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let mut star = Star::from_mass(&mut rng, 1.0)?;
+    star.current_age = EARTH_AGE;
+    let host_star = HostStar::Star(star);
+    let window = get_habitability_window(&host_star, 1.0, 1.0, 1.0, 288.0);
+    print_var!(window);
+    assert!(window.0 < EARTH_AGE && EARTH_AGE < window.1);
+    // A world that is too hot today may have been habitable under a younger,
+    // dimmer star, like Venus.
+    let window = get_habitability_window(&host_star, 0.7, 1.0, 1.0, 340.0);
+    print_var!(window);
+    assert!(window.1 < EARTH_AGE);
+    // A world that is far too hot never gets a look in.
+    let window = get_habitability_window(&host_star, 0.4, 1.0, 1.0, 450.0);
+    print_var!(window);
+    assert_eq!(window, (0.0, 0.0));
+    trace_exit!();
+    Ok(())
+  }
+}
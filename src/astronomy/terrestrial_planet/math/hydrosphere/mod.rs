@@ -0,0 +1,139 @@
+use rand::prelude::*;
+use rand_distr::{Distribution, LogNormal};
+
+/// The fraction of Earth's mass in its oceans.
+pub const EARTH_WATER_MASS_FRACTION: f64 = 2.3E-4;
+
+/// Earth's distance from the Sun, as a fraction of the Sun's frost line.
+pub const EARTH_FROST_LINE_RATIO: f64 = 0.37;
+
+/// The fraction of Earth's surface covered by ocean.
+pub const EARTH_OCEAN_FRACTION: f64 = 0.71;
+
+/// How widely volatile delivery varies between planets, in natural log units.
+///
+/// Whether a few big, wet impactors arrive from beyond the frost line is
+/// mostly luck; this lets two otherwise identical planets differ by an order
+/// of magnitude or so.
+pub const WATER_DELIVERY_SIGMA: f64 = 1.0;
+
+/// The least water mass fraction of a planet that formed beyond the frost
+/// line.
+pub const MINIMUM_ICY_WATER_MASS_FRACTION: f64 = 0.05;
+
+/// The greatest water mass fraction of a planet that formed beyond the frost
+/// line.
+pub const MAXIMUM_ICY_WATER_MASS_FRACTION: f64 = 0.5;
+
+/// The freezing point of water, in Kelvin.
+pub const WATER_FREEZING_POINT: f64 = 273.15;
+
+/// The boiling point of water at one bar, in Kelvin.
+pub const WATER_BOILING_POINT: f64 = 373.15;
+
+/// Pick the fraction of a planet's mass that is water.
+///
+/// A planet that formed beyond the frost line is built partly from ice.
+/// Inside it, planetesimals are dry, and water has to be delivered from
+/// further out; the closer to the frost line, the more of it arrives.
+/// Distances just need to share units.
+#[named]
+pub fn get_random_water_mass_fraction<R: Rng + ?Sized>(rng: &mut R, distance: f64, frost_line: f64) -> f64 {
+  trace_enter!();
+  trace_var!(distance);
+  trace_var!(frost_line);
+  let result = match distance {
+    distance if distance >= frost_line => {
+      rng.gen_range(MINIMUM_ICY_WATER_MASS_FRACTION..=MAXIMUM_ICY_WATER_MASS_FRACTION)
+    },
+    distance => {
+      let delivery = LogNormal::new(0.0, WATER_DELIVERY_SIGMA).unwrap().sample(rng);
+      trace_var!(delivery);
+      let ratio = distance / frost_line / EARTH_FROST_LINE_RATIO;
+      trace_var!(ratio);
+      (EARTH_WATER_MASS_FRACTION * ratio.powf(2.0) * delivery).min(MINIMUM_ICY_WATER_MASS_FRACTION)
+    },
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate a planet's water inventory, in Earth oceans.
+///
+/// Mass is in Mearth.
+#[named]
+pub fn get_water_inventory(mass: f64, water_mass_fraction: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(water_mass_fraction);
+  let result = mass * water_mass_fraction / EARTH_WATER_MASS_FRACTION;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the fraction of a planet's surface covered by ocean.
+///
+/// How much water the basins hold goes with surface area, and with how deep
+/// they can be; stronger gravity flattens the relief.  Earth's inventory in
+/// Earth's basins gives Earth's coverage, and the oceans fill in from there.
+/// Radius is in Rearth and gravity in Gearth.
+#[named]
+pub fn get_ocean_fraction(water_inventory: f64, radius: f64, surface_gravity: f64) -> f64 {
+  trace_enter!();
+  trace_var!(water_inventory);
+  trace_var!(radius);
+  trace_var!(surface_gravity);
+  let basin_capacity = radius.powf(2.0) / surface_gravity;
+  trace_var!(basin_capacity);
+  let coefficient = -(1.0 - EARTH_OCEAN_FRACTION).ln();
+  trace_var!(coefficient);
+  let result = 1.0 - (-coefficient * water_inventory / basin_capacity).exp();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Indicate whether water can be liquid at a given surface temperature, in
+/// Kelvin.
+#[named]
+pub fn is_water_liquid(surface_temperature: f64) -> bool {
+  trace_enter!();
+  trace_var!(surface_temperature);
+  let result = surface_temperature > WATER_FREEZING_POINT && surface_temperature < WATER_BOILING_POINT;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_hydrosphere() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    // Earth.
+    let inventory = get_water_inventory(1.0, EARTH_WATER_MASS_FRACTION);
+    assert_approx_eq!(inventory, 1.0);
+    assert_approx_eq!(get_ocean_fraction(inventory, 1.0, 1.0), EARTH_OCEAN_FRACTION);
+    // Mars's basins are deeper, but there's so much less surface that
+    // Earth's oceans would cover more of it.
+    assert!(get_ocean_fraction(inventory, 0.532, 0.38) > EARTH_OCEAN_FRACTION);
+    // A planet from beyond the frost line drowns.
+    let water_mass_fraction = get_random_water_mass_fraction(&mut rng, 3.0, 2.7);
+    assert!(water_mass_fraction >= MINIMUM_ICY_WATER_MASS_FRACTION);
+    let inventory = get_water_inventory(1.0, water_mass_fraction);
+    assert!(get_ocean_fraction(inventory, 1.0, 1.0) > 0.99);
+    // One close to its star is parched.
+    assert!(get_random_water_mass_fraction(&mut rng, 0.1, 2.7) < MINIMUM_ICY_WATER_MASS_FRACTION);
+    trace_exit!();
+  }
+}
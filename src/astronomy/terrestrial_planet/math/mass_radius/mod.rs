@@ -0,0 +1,76 @@
+/// The mass above which planets are usually volatile-rich, in Mearth.
+///
+/// Chen & Kipping (2017) put the break between "Terran" and "Neptunian"
+/// worlds here; above it, most planets have held on to thick envelopes of
+/// water or gas and are much puffier than rock alone would make them.
+pub const VOLATILE_TRANSITION_MASS: f64 = 2.04;
+
+/// How radius scales with mass for a rocky planet.
+///
+/// Zeng et al. (2016) found R ∝ M^(1/3.7) for rocky planets, which is a
+/// little flatter than the cube root because of self-compression.
+pub const ROCKY_EXPONENT: f64 = 1.0 / 3.7;
+
+/// How radius scales with mass for a volatile-rich planet (Chen & Kipping).
+pub const VOLATILE_RICH_EXPONENT: f64 = 0.589;
+
+/// Calculate the radius of a rocky planet, given its mass and CMF.
+///
+/// Units are Mearth and Rearth.
+#[named]
+pub fn get_rocky_radius(mass: f64, cmf: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(cmf);
+  let result = (1.07 - 0.21 * cmf) * mass.powf(ROCKY_EXPONENT);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the radius of a terrestrial planet, given its mass and CMF.
+///
+/// Below the transition mass this is a rocky planet; above it, the radius
+/// picks up the steeper volatile-rich slope, continuing on from where the
+/// rocky relation leaves off.
+///
+/// Units are Mearth and Rearth.
+#[named]
+pub fn get_radius_from_mass(mass: f64, cmf: f64) -> f64 {
+  trace_enter!();
+  trace_var!(mass);
+  trace_var!(cmf);
+  let result = match mass {
+    mass if mass <= VOLATILE_TRANSITION_MASS => get_rocky_radius(mass, cmf),
+    mass => {
+      get_rocky_radius(VOLATILE_TRANSITION_MASS, cmf) * (mass / VOLATILE_TRANSITION_MASS).powf(VOLATILE_RICH_EXPONENT)
+    },
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_radius_from_mass() {
+    init();
+    trace_enter!();
+    // Earth.
+    assert_approx_eq!(get_radius_from_mass(1.0, 0.33), 1.0, 0.01);
+    // Mars.
+    assert_approx_eq!(get_radius_from_mass(0.107, 0.24), 0.532, 0.03);
+    // A super-Earth comes out fluffier than it would if it were all rock.
+    let super_earth = get_radius_from_mass(5.0, 0.33);
+    print_var!(super_earth);
+    assert!(super_earth > get_rocky_radius(5.0, 0.33));
+    assert!(5.0 / super_earth.powf(3.0) < 1.0);
+    trace_exit!();
+  }
+}
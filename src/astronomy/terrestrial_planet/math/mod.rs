@@ -1,6 +1,16 @@
 pub mod atmospheric_stability;
 pub mod density;
+pub mod dynamo;
+pub mod earth_similarity;
 pub mod escape_velocity;
+pub mod geology;
 pub mod gravity;
+pub mod habitability_window;
+pub mod hydrosphere;
+pub mod mass_radius;
+pub mod obliquity;
 pub mod radius;
+pub mod sky_color;
 pub mod temperature;
+pub mod thermal_escape;
+pub mod tidal_locking;
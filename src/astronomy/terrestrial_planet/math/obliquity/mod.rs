@@ -0,0 +1,68 @@
+/// The least mass a moon needs, relative to its planet, to hold the planet's
+/// axial tilt steady.
+///
+/// Our Moon is about 1/81 of Earth's mass, and does the job comfortably.
+pub const STABILIZING_MOON_MASS_RATIO: f64 = 0.005;
+
+/// How far a stabilized axial tilt wanders either way, in degrees.
+///
+/// Earth's swings between about 22.1º and 24.5º.
+pub const STABILIZED_OBLIQUITY_VARIATION: f64 = 1.3;
+
+/// How far an unstabilized axial tilt wanders either way, in degrees.
+///
+/// Without a big moon, resonances with the other planets send the tilt
+/// wandering chaotically; Mars's has ranged from near zero to past 60º.
+pub const CHAOTIC_OBLIQUITY_VARIATION: f64 = 30.0;
+
+/// Past this axial tilt, in degrees, the poles get more sunlight than the
+/// equator, and the climate is upended.
+pub const MAXIMUM_HABITABLE_OBLIQUITY: f64 = 54.0;
+
+/// Indicate whether a moon is massive enough to stabilize its planet's axial
+/// tilt.
+///
+/// The masses just need to share units.
+#[named]
+pub fn is_obliquity_stabilized(moon_mass: f64, planet_mass: f64) -> bool {
+  trace_enter!();
+  trace_var!(moon_mass);
+  trace_var!(planet_mass);
+  let result = moon_mass / planet_mass >= STABILIZING_MOON_MASS_RATIO;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get how far the axial tilt wanders either way, in degrees.
+#[named]
+pub fn get_obliquity_variation(is_obliquity_stabilized: bool) -> f64 {
+  trace_enter!();
+  trace_var!(is_obliquity_stabilized);
+  let result = match is_obliquity_stabilized {
+    true => STABILIZED_OBLIQUITY_VARIATION,
+    false => CHAOTIC_OBLIQUITY_VARIATION,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_is_obliquity_stabilized() {
+    init();
+    trace_enter!();
+    // Earth and the Moon, in KG.
+    assert!(is_obliquity_stabilized(7.342E22, 5.972E24));
+    // Mars and Phobos.
+    assert!(!is_obliquity_stabilized(1.07E16, 6.42E23));
+    trace_exit!();
+  }
+}
@@ -0,0 +1,196 @@
+/// The Rayleigh optical depth of Earth's atmosphere at 550nm, looking straight up.
+pub const EARTH_RAYLEIGH_OPTICAL_DEPTH: f64 = 0.0973;
+
+/// The reference wavelength for the optical depth above, in nm.
+pub const REFERENCE_WAVELENGTH: f64 = 550.0;
+
+/// Representative wavelengths for the red, green, and blue channels, in nm.
+pub const RGB_WAVELENGTHS: (f64, f64, f64) = (680.0, 550.0, 440.0);
+
+/// The effective temperature of the Sun, in Kelvin.
+///
+/// Our eyes are adapted to sunlight, so we treat this as white.
+pub const SOLAR_TEMPERATURE: f64 = 5776.0;
+
+/// Planck's constant times the speed of light over Boltzmann's constant, in nm K.
+pub const SECOND_RADIATION_CONSTANT: f64 = 1.438_777E7;
+
+/// Spectral radiance of a blackbody at a given wavelength (in nm) and
+/// temperature (in Kelvin), up to a constant factor.
+#[named]
+pub fn get_relative_spectral_radiance(wavelength: f64, temperature: f64) -> f64 {
+  trace_enter!();
+  trace_var!(wavelength);
+  trace_var!(temperature);
+  let result = 1.0 / (wavelength.powf(5.0) * ((SECOND_RADIATION_CONSTANT / (wavelength * temperature)).exp() - 1.0));
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Rayleigh optical depth of an atmosphere at a given wavelength (in nm),
+/// looking straight up.
+///
+/// The column of gas scales with surface pressure (in atm) over surface
+/// gravity (in Gearth).  The scattering coefficient describes how strongly
+/// the atmosphere's molecules scatter relative to Earth air; carbon dioxide
+/// is about 2.5, hydrogen is about 0.2.
+#[named]
+pub fn get_rayleigh_optical_depth(
+  wavelength: f64,
+  surface_pressure: f64,
+  surface_gravity: f64,
+  scattering_coefficient: f64,
+) -> f64 {
+  trace_enter!();
+  trace_var!(wavelength);
+  trace_var!(surface_pressure);
+  trace_var!(surface_gravity);
+  trace_var!(scattering_coefficient);
+  let column = surface_pressure / surface_gravity;
+  trace_var!(column);
+  let result =
+    EARTH_RAYLEIGH_OPTICAL_DEPTH * column * scattering_coefficient * (REFERENCE_WAVELENGTH / wavelength).powf(4.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Scale a linear RGB triple so that its brightest channel is 1.0, then
+/// encode it as sRGB.
+#[named]
+pub fn normalize_to_srgb(linear: (f64, f64, f64)) -> (u8, u8, u8) {
+  trace_enter!();
+  trace_var!(linear);
+  let maximum = linear.0.max(linear.1).max(linear.2);
+  trace_var!(maximum);
+  let encode = |channel: f64| {
+    let channel = if maximum > 0.0 { channel / maximum } else { 0.0 };
+    let encoded = if channel <= 0.0031308 {
+      12.92 * channel
+    } else {
+      1.055 * channel.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded.clamp(0.0, 1.0) * 255.0).round() as u8
+  };
+  let result = (encode(linear.0), encode(linear.1), encode(linear.2));
+  trace_3u8!(result);
+  trace_exit!();
+  result
+}
+
+/// Estimate the color of the daytime sky as seen from a planet's surface.
+///
+/// This only accounts for Rayleigh scattering of the host star's light, and
+/// gives a hue rather than a brightness.  Thin atmospheres scatter blue light
+/// preferentially; very thick ones scatter nearly everything and take on the
+/// color of the star itself.
+#[named]
+pub fn get_sky_color(
+  star_temperature: f64,
+  surface_pressure: f64,
+  surface_gravity: f64,
+  scattering_coefficient: f64,
+) -> (u8, u8, u8) {
+  trace_enter!();
+  trace_var!(star_temperature);
+  trace_var!(surface_pressure);
+  trace_var!(surface_gravity);
+  trace_var!(scattering_coefficient);
+  let channel = |wavelength: f64| {
+    let depth = get_rayleigh_optical_depth(wavelength, surface_pressure, surface_gravity, scattering_coefficient);
+    let radiance = get_relative_spectral_radiance(wavelength, star_temperature);
+    let white = get_relative_spectral_radiance(wavelength, SOLAR_TEMPERATURE);
+    radiance / white * (1.0 - (-depth).exp())
+  };
+  let linear = (
+    channel(RGB_WAVELENGTHS.0),
+    channel(RGB_WAVELENGTHS.1),
+    channel(RGB_WAVELENGTHS.2),
+  );
+  trace_var!(linear);
+  let result = normalize_to_srgb(linear);
+  trace_3u8!(result);
+  trace_exit!();
+  result
+}
+
+/// Estimate the color of the host star's disc as seen from a planet's surface.
+///
+/// The air mass is the length of the path through the atmosphere relative to
+/// looking straight up; 1.0 is overhead, and something like 38.0 is sitting on
+/// the horizon.  As with the sky, this gives a hue rather than a brightness.
+#[named]
+pub fn get_star_disc_color(
+  star_temperature: f64,
+  surface_pressure: f64,
+  surface_gravity: f64,
+  scattering_coefficient: f64,
+  air_mass: f64,
+) -> (u8, u8, u8) {
+  trace_enter!();
+  trace_var!(star_temperature);
+  trace_var!(surface_pressure);
+  trace_var!(surface_gravity);
+  trace_var!(scattering_coefficient);
+  trace_var!(air_mass);
+  let channel = |wavelength: f64| {
+    let depth = get_rayleigh_optical_depth(wavelength, surface_pressure, surface_gravity, scattering_coefficient);
+    let radiance = get_relative_spectral_radiance(wavelength, star_temperature);
+    let white = get_relative_spectral_radiance(wavelength, SOLAR_TEMPERATURE);
+    radiance / white * (-depth * air_mass).exp()
+  };
+  let linear = (
+    channel(RGB_WAVELENGTHS.0),
+    channel(RGB_WAVELENGTHS.1),
+    channel(RGB_WAVELENGTHS.2),
+  );
+  trace_var!(linear);
+  let result = normalize_to_srgb(linear);
+  trace_3u8!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_sky_color() {
+    init();
+    trace_enter!();
+    // Earth's sky is blue.
+    let earth = get_sky_color(SOLAR_TEMPERATURE, 1.0, 1.0, 1.0);
+    print_var!(earth);
+    assert!(earth.2 > earth.1 && earth.1 > earth.0);
+    // A thick carbon dioxide atmosphere washes out to the color of the star.
+    let venus = get_sky_color(SOLAR_TEMPERATURE, 90.0, 0.9, 2.5);
+    print_var!(venus);
+    assert!(venus.0 >= 250 && venus.1 >= 250 && venus.2 >= 250);
+    // A red dwarf can't manage much of a blue sky.
+    let red_dwarf = get_sky_color(3000.0, 1.0, 1.0, 1.0);
+    print_var!(red_dwarf);
+    assert!(red_dwarf.2 < earth.2 || red_dwarf.0 > earth.0);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_get_star_disc_color() {
+    init();
+    trace_enter!();
+    // The Sun is a little yellow overhead...
+    let noon = get_star_disc_color(SOLAR_TEMPERATURE, 1.0, 1.0, 1.0, 1.0);
+    print_var!(noon);
+    assert!(noon.0 >= noon.1 && noon.1 >= noon.2);
+    // ...and quite red on the horizon.
+    let sunset = get_star_disc_color(SOLAR_TEMPERATURE, 1.0, 1.0, 1.0, 38.0);
+    print_var!(sunset);
+    assert!(sunset.0 == 255 && sunset.2 < noon.2);
+    trace_exit!();
+  }
+}
@@ -3,35 +3,66 @@ use std::f64::consts::PI;
 use crate::astronomy::star::math::distance::METERS_PER_AU;
 use crate::astronomy::star::math::luminosity::ERGS_PER_SEC_PER_LSOL;
 use crate::astronomy::terrestrial_planet::constants::*;
+use crate::astronomy::terrestrial_planet::math::atmospheric_stability::is_carbon_dioxide_stable;
 
 pub const GREENHOUSE_EFFECT: f64 = 0.5841;
 
 /// Calculate the equilibrium temperature for a planet based on the host star's
 /// luminosity, distance, etc.
 /// Answer in Kelvin.
+///
+/// This is what the planet would settle at with no atmosphere to speak of;
+/// Earth's is about 255K.
 #[named]
-pub fn get_equilibrium_temperature(
-  bond_albedo: f64,
-  greenhouse_effect: f64,
-  star_luminosity: f64,
-  star_distance: f64,
-) -> f64 {
+pub fn get_equilibrium_temperature(bond_albedo: f64, star_luminosity: f64, star_distance: f64) -> f64 {
   trace_enter!();
   trace_var!(bond_albedo);
-  trace_var!(greenhouse_effect);
   trace_var!(star_luminosity);
   trace_var!(star_distance);
   let luminosity = star_luminosity * ERGS_PER_SEC_PER_LSOL;
   trace_var!(luminosity);
   let distance = star_distance * METERS_PER_AU * 100.0;
   trace_var!(distance);
-  let t_greenhouse = greenhouse_effect * GREENHOUSE_EFFECT;
-  trace_var!(t_greenhouse);
   let absorption = ((1.0 - bond_albedo) * luminosity / (16.0 * PI * STEFAN_BOLTZMANN_CONSTANT)).sqrt();
   trace_var!(absorption);
-  let t_effective = absorption.sqrt() * (1.0 / distance.sqrt());
-  trace_var!(t_effective);
-  let t_equilibrium = t_effective.powf(4.0) * (1.0 + (3.0 * t_greenhouse / 4.0));
+  let result = absorption.sqrt() * (1.0 / distance.sqrt());
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the greenhouse effect of a planet's atmosphere, relative to
+/// Earth's.
+///
+/// An atmosphere that holds on to its carbon dioxide traps about as much heat
+/// as ours does; one that can't, barely traps any, and no atmosphere traps
+/// none at all.
+#[named]
+pub fn get_greenhouse_effect(has_atmosphere: bool, equilibrium_temperature: f64, escape_velocity: f64) -> f64 {
+  trace_enter!();
+  trace_var!(has_atmosphere);
+  trace_var!(equilibrium_temperature);
+  trace_var!(escape_velocity);
+  let result = match has_atmosphere && is_carbon_dioxide_stable(equilibrium_temperature, escape_velocity) {
+    true => 1.0,
+    false => 0.0,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the surface temperature of a planet from its equilibrium
+/// temperature and greenhouse effect.
+/// Answer in Kelvin.
+#[named]
+pub fn get_surface_temperature(equilibrium_temperature: f64, greenhouse_effect: f64) -> f64 {
+  trace_enter!();
+  trace_var!(equilibrium_temperature);
+  trace_var!(greenhouse_effect);
+  let t_greenhouse = greenhouse_effect * GREENHOUSE_EFFECT;
+  trace_var!(t_greenhouse);
+  let t_equilibrium = equilibrium_temperature.powf(4.0) * (1.0 + (3.0 * t_greenhouse / 4.0));
   trace_var!(t_equilibrium);
   let t_surface = t_equilibrium / 0.9;
   trace_var!(t_surface);
@@ -40,3 +71,29 @@ pub fn get_equilibrium_temperature(
   trace_exit!();
   result
 }
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_surface_temperature() {
+    init();
+    trace_enter!();
+    let equilibrium_temperature = get_equilibrium_temperature(0.29, 1.0, 1.0);
+    assert_approx_eq!(equilibrium_temperature, 255.0, 2.0);
+    let greenhouse_effect = get_greenhouse_effect(true, equilibrium_temperature, 1.0);
+    assert_approx_eq!(greenhouse_effect, 1.0);
+    assert_approx_eq!(
+      get_surface_temperature(equilibrium_temperature, greenhouse_effect),
+      288.0,
+      2.0
+    );
+    // The Moon keeps nothing.
+    assert_approx_eq!(get_greenhouse_effect(false, equilibrium_temperature, 0.21), 0.0);
+    trace_exit!();
+  }
+}
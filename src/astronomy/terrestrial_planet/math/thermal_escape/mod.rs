@@ -0,0 +1,71 @@
+/// The insolation, relative to Earth's, at which a planet with Earth's escape
+/// velocity would lose its atmosphere.
+///
+/// Zahnle & Catling's "cosmic shoreline" runs through roughly 5 km/s at
+/// Earth's insolation; Mars sits just on the wrong side of it.
+pub const COSMIC_SHORELINE_COEFFICIENT: f64 = 25.0;
+
+/// Calculate the insolation, relative to Earth's, given the luminosity of
+/// the host star in Lsol and the distance to it in AU.
+#[named]
+pub fn get_insolation(luminosity: f64, distance: f64) -> f64 {
+  trace_enter!();
+  trace_var!(luminosity);
+  trace_var!(distance);
+  let result = luminosity / distance.powf(2.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Calculate the most insolation, relative to Earth's, that a planet can
+/// bear and still hold on to an atmosphere.
+///
+/// Escape velocity is in Vearth; the shoreline goes as its fourth power.
+#[named]
+pub fn get_cosmic_shoreline_insolation(escape_velocity: f64) -> f64 {
+  trace_enter!();
+  trace_var!(escape_velocity);
+  let result = COSMIC_SHORELINE_COEFFICIENT * escape_velocity.powf(4.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Indicate whether a planet can keep an atmosphere at all, or whether its
+/// star boils it away.
+///
+/// Insolation is relative to Earth's, escape velocity in Vearth.
+#[named]
+pub fn can_retain_atmosphere(insolation: f64, escape_velocity: f64) -> bool {
+  trace_enter!();
+  trace_var!(insolation);
+  trace_var!(escape_velocity);
+  let result = insolation < get_cosmic_shoreline_insolation(escape_velocity);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_can_retain_atmosphere() {
+    init();
+    trace_enter!();
+    // Earth.
+    assert!(can_retain_atmosphere(get_insolation(1.0, 1.0), 1.0));
+    // Mercury.
+    assert!(!can_retain_atmosphere(get_insolation(1.0, 0.387), 0.38));
+    // The Moon.
+    assert!(!can_retain_atmosphere(get_insolation(1.0, 1.0), 0.21));
+    // Titan.
+    assert!(can_retain_atmosphere(get_insolation(1.0, 9.58), 0.24));
+    trace_exit!();
+  }
+}
@@ -0,0 +1,99 @@
+use crate::astronomy::orbit::constants::DAYS_PER_YEAR;
+use crate::astronomy::planet::math::mass::KG_PER_EARTH_MASS;
+use crate::astronomy::planet::math::oblateness::{
+  GRAVITATIONAL_CONSTANT, SECONDS_PER_DAY, TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR,
+};
+use crate::astronomy::planet::math::radius::METERS_PER_EARTH_RADIUS;
+use crate::astronomy::star::math::distance::au_to_meters;
+use crate::astronomy::star::math::mass::msol_to_kg;
+
+/// Love number (k2) assumed for tidal locking, after Gladman et al. (1996).
+pub const TIDAL_LOCKING_LOVE_NUMBER: f64 = 0.3;
+
+/// Tidal quality factor (Q) assumed for tidal locking.
+///
+/// This is a long-term average; Earth's present oceans are far lossier.
+pub const TIDAL_LOCKING_QUALITY_FACTOR: f64 = 100.0;
+
+/// Calculate how long the host star takes to despin a terrestrial planet
+/// into synchronous rotation, in Gyr.
+///
+/// Stellar mass is in Msol, distance in AU, mass in Mearth, radius in
+/// Rearth, and the initial rotation period in Dearth.  Earth comes out at
+/// about 80 Gyr; a planet in the habitable zone of a red dwarf, well under a
+/// million years.
+#[named]
+pub fn get_tidal_locking_time(stellar_mass: f64, distance: f64, mass: f64, radius: f64, rotation_period: f64) -> f64 {
+  trace_enter!();
+  trace_var!(stellar_mass);
+  trace_var!(distance);
+  trace_var!(mass);
+  trace_var!(radius);
+  trace_var!(rotation_period);
+  let angular_velocity = 2.0 * std::f64::consts::PI / (rotation_period * SECONDS_PER_DAY);
+  trace_var!(angular_velocity);
+  let semi_major_axis = au_to_meters(distance);
+  trace_var!(semi_major_axis);
+  let radius = radius * METERS_PER_EARTH_RADIUS;
+  trace_var!(radius);
+  let moment_of_inertia = TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR * mass * KG_PER_EARTH_MASS * radius.powf(2.0);
+  trace_var!(moment_of_inertia);
+  let seconds = angular_velocity * semi_major_axis.powf(6.0) * moment_of_inertia * TIDAL_LOCKING_QUALITY_FACTOR
+    / (3.0
+      * GRAVITATIONAL_CONSTANT
+      * msol_to_kg(stellar_mass).powf(2.0)
+      * TIDAL_LOCKING_LOVE_NUMBER
+      * radius.powf(5.0));
+  trace_var!(seconds);
+  let result = seconds / (SECONDS_PER_DAY * DAYS_PER_YEAR * 1.0E9);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Indicate whether a terrestrial planet has become tidally locked by a
+/// given age of its host star, in Gyr.
+///
+/// Units otherwise as in `get_tidal_locking_time()`.
+#[named]
+pub fn is_tidally_locked(
+  stellar_mass: f64,
+  distance: f64,
+  mass: f64,
+  radius: f64,
+  rotation_period: f64,
+  age: f64,
+) -> bool {
+  trace_enter!();
+  trace_var!(age);
+  let locking_time = get_tidal_locking_time(stellar_mass, distance, mass, radius, rotation_period);
+  trace_var!(locking_time);
+  let result = locking_time <= age;
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_tidal_locking_time() {
+    init();
+    trace_enter!();
+    let earth = get_tidal_locking_time(1.0, 1.0, 1.0, 1.0, 1.0);
+    print_var!(earth);
+    assert!(earth > 13.8);
+    assert!(!is_tidally_locked(1.0, 1.0, 1.0, 1.0, 1.0, 4.6));
+    // Proxima Centauri b.
+    let proxima = get_tidal_locking_time(0.12, 0.0485, 1.07, 1.03, 1.0);
+    print_var!(proxima);
+    assert!(proxima < 0.001);
+    assert!(is_tidally_locked(0.12, 0.0485, 1.07, 1.03, 1.0, 4.85));
+    trace_exit!();
+  }
+}
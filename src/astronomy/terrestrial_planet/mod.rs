@@ -1,61 +1,138 @@
+use serde::Serialize;
+
+use crate::astronomy::composition::Composition;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::planet::math::oblateness::{
+  get_equatorial_and_polar_radii, get_j2, get_oblateness, get_rotational_parameter,
+  TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR,
+};
+use crate::astronomy::planet::seasons::Seasons;
+use crate::math::noise::{FbmOptions, Noise};
+
 pub mod constants;
 use constants::*;
 pub mod constraints;
+pub mod differentiation;
+use differentiation::Differentiation;
 pub mod error;
 use error::Error;
-pub mod math;
+pub(crate) mod math;
 use math::atmospheric_stability::{
   is_argon_stable, is_atmospherically_stable, is_carbon_dioxide_stable, is_nitrogen_stable, is_oxygen_stable,
+  is_water_stable,
 };
 use math::density::get_density;
+use math::dynamo::{get_magnetic_moment, has_dynamo};
+use math::earth_similarity::get_earth_similarity_index;
 use math::escape_velocity::get_escape_velocity;
+use math::geology::{get_radiogenic_heat, get_relative_heat_flow, get_tidal_heat, has_plate_tectonics};
 use math::gravity::get_gravity;
+use math::habitability_window::EARTH_AGE;
+use math::hydrosphere::{get_ocean_fraction, get_water_inventory, is_water_liquid, EARTH_WATER_MASS_FRACTION};
+use math::obliquity::{get_obliquity_variation, is_obliquity_stabilized, MAXIMUM_HABITABLE_OBLIQUITY};
 use math::radius::get_radius;
-use math::temperature::get_equilibrium_temperature;
+pub use math::sky_color::{get_sky_color, get_star_disc_color};
+use math::temperature::{get_equilibrium_temperature, get_greenhouse_effect, get_surface_temperature};
+use math::thermal_escape::{can_retain_atmosphere, get_insolation};
 pub mod rotation_direction;
 use rotation_direction::RotationDirection;
+pub mod volcanic_activity;
+use volcanic_activity::VolcanicActivity;
 
 /// The `TerrestrialPlanet` type.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct TerrestrialPlanet {
+  /// The name, given once the planet's place in its system is known.
+  pub name: String,
   /// Mass in Mearth.
   pub mass: f64,
   /// Core Mass Fraction.
   pub core_mass_fraction: f64,
+  /// How far the interior has separated into core and mantle.
+  pub differentiation: Differentiation,
+  /// Whether the core still drives a magnetic dynamo.
+  pub has_magnetic_field: bool,
+  /// Magnetic moment, relative to Earth's.
+  pub magnetic_moment: f64,
+  /// Heat from radioactive decay in the mantle, in TW.
+  pub radiogenic_heat: f64,
+  /// Heat from tides raised by the host star, in TW.
+  pub tidal_heat: f64,
+  /// How volcanically active the surface is.
+  pub volcanic_activity: VolcanicActivity,
+  /// Whether the lithosphere is broken into moving plates.
+  pub has_plate_tectonics: bool,
   /// Density, in Dearth.
   pub density: f64,
-  /// Escape velocity, in Vearth.
+  /// The bulk composition, inferred from density.
+  pub composition: Composition,
+  /// Escape velocity, in km/s.
   pub escape_velocity: f64,
-  /// Gravity, in Gearth.
-  pub gravity: f64,
+  /// Surface gravity, in Gearth.
+  pub surface_gravity: f64,
   /// Radius, in Rearth.
   pub radius: f64,
+  /// Rotation period, in Dearth.
+  pub rotation_period: f64,
+  /// Flattening caused by rotation.
+  pub oblateness: f64,
+  /// Equatorial radius, in Rearth.
+  pub equatorial_radius: f64,
+  /// Polar radius, in Rearth.
+  pub polar_radius: f64,
+  /// The J2 gravitational harmonic.
+  pub j2: f64,
   /// Axial tilt (0-180º).
   pub axial_tilt: f64,
   /// Rotation.
   pub rotation_direction: RotationDirection,
-  /// Semi-Major Axis.
-  pub semi_major_axis: f64,
+  /// Whether a large moon holds the axial tilt steady.
+  pub is_obliquity_stabilized: bool,
+  /// How far the axial tilt wanders either way over millions of years, in
+  /// degrees.
+  pub obliquity_variation: f64,
   /// Tropic Zone.
   pub tropic_zones: (f64, f64),
   /// Polar Zones.
   pub polar_zones: (f64, f64),
-  /// Orbital eccentricity.
-  pub orbital_eccentricity: f64,
-  /// Perihelion.
-  pub perihelion: f64,
-  /// Aphelion.
-  pub aphelion: f64,
+  /// How much the seasons vary.
+  pub seasons: Seasons,
+  /// Orbit around the host star, in AU.
+  pub orbit: OrbitalElements,
   /// Orbital period, in Earth years.
   pub orbital_period: f64,
   /// Bond albedo.
   pub bond_albedo: f64,
-  /// Greenhouse effect.
+  /// Greenhouse effect, relative to Earth's.
   pub greenhouse_effect: f64,
   /// Equilibrium temperature, in Kelvin.
   pub equilibrium_temperature: f64,
+  /// Surface temperature, in Kelvin, after the greenhouse effect.
+  pub surface_temperature: f64,
+  /// Whether the planet holds on to an atmosphere at all.
+  pub has_atmosphere: bool,
   /// Whether we can retain the gases necessary for conventional life.
   pub is_atmospherically_stable: bool,
+  /// Water inventory, in Earth oceans.
+  pub water_inventory: f64,
+  /// The fraction of the surface covered by liquid water.
+  ///
+  /// A planet too cold or too hot for liquid water, or that can't hold on to
+  /// it, has none, however much it has locked up as ice or lost to space.
+  pub ocean_fraction: f64,
+  /// Whether the host star has despun this planet into synchronous rotation.
+  pub is_tidally_locked: bool,
+  /// Whether a tidally locked planet still counts as habitable, with life
+  /// clinging to a temperate ring around the substellar point.
+  pub is_eyeball_world: bool,
+  /// When this planet becomes habitable, measured in Gyr since the formation
+  /// of the host star.
+  ///
+  /// Until the planet is placed around a host star, the window is empty.
+  pub habitable_from_gyr: f64,
+  /// When this planet stops being habitable, measured in Gyr since the
+  /// formation of the host star.
+  pub habitable_until_gyr: f64,
 }
 
 impl TerrestrialPlanet {
@@ -65,65 +142,161 @@ impl TerrestrialPlanet {
     trace_var!(mass);
     let core_mass_fraction: f64 = 0.35;
     trace_var!(core_mass_fraction);
+    let result = Self::from_mass_and_core_mass_fraction(mass, core_mass_fraction);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Create from a mass, in Mearth, and a core mass fraction, in an
+  /// Earth-like orbit around a Sun-like star.
+  #[named]
+  pub fn from_mass_and_core_mass_fraction(mass: f64, core_mass_fraction: f64) -> Result<Self, Error> {
+    trace_enter!();
+    trace_var!(mass);
+    trace_var!(core_mass_fraction);
+    let differentiation = Differentiation::from_mass(mass, core_mass_fraction);
+    trace_var!(differentiation);
     let density = get_density(mass, core_mass_fraction);
     trace_var!(density);
+    let composition = Composition::from_compressed_density(density, mass);
+    trace_var!(composition);
     let radius = get_radius(mass, density);
     trace_var!(radius);
-    let escape_velocity = get_escape_velocity(mass, radius);
+    let relative_escape_velocity = get_escape_velocity(mass, radius);
+    trace_var!(relative_escape_velocity);
+    let escape_velocity = relative_escape_velocity * EARTH_ESCAPE_VELOCITY;
     trace_var!(escape_velocity);
-    let gravity = get_gravity(mass, radius);
-    trace_var!(gravity);
+    let surface_gravity = get_gravity(mass, radius);
+    trace_var!(surface_gravity);
+    let rotation_period = 1.0;
+    trace_var!(rotation_period);
+    let has_magnetic_field = has_dynamo(mass, core_mass_fraction, EARTH_AGE);
+    trace_var!(has_magnetic_field);
+    let magnetic_moment = match has_magnetic_field {
+      true => get_magnetic_moment(mass, core_mass_fraction, rotation_period),
+      false => 0.0,
+    };
+    trace_var!(magnetic_moment);
+    let rotational_parameter = get_rotational_parameter(rotation_period, density);
+    trace_var!(rotational_parameter);
+    let oblateness = get_oblateness(rotational_parameter, TERRESTRIAL_MOMENT_OF_INERTIA_FACTOR);
+    trace_var!(oblateness);
+    let (equatorial_radius, polar_radius) = get_equatorial_and_polar_radii(radius, oblateness);
+    trace_var!(equatorial_radius);
+    trace_var!(polar_radius);
+    let j2 = get_j2(oblateness, rotational_parameter);
+    trace_var!(j2);
     let axial_tilt = 23.5;
     trace_var!(axial_tilt);
     let rotation_direction = RotationDirection::Prograde;
     trace_var!(rotation_direction);
+    // No moons yet, so nothing to hold the tilt steady.
+    let is_obliquity_stabilized = false;
+    trace_var!(is_obliquity_stabilized);
+    let obliquity_variation = get_obliquity_variation(is_obliquity_stabilized);
+    trace_var!(obliquity_variation);
     let tropic_zones = (0.0, axial_tilt);
     trace_var!(tropic_zones);
     let polar_zones = (90.0 - axial_tilt, 90.0);
     trace_var!(polar_zones);
     let bond_albedo = 0.29;
     trace_var!(bond_albedo);
-    let greenhouse_effect = 1.0;
-    trace_var!(greenhouse_effect);
     let host_star_luminosity = 1.0;
     trace_var!(host_star_luminosity);
     let host_star_distance = 1.0;
     trace_var!(host_star_distance);
     let semi_major_axis: f64 = host_star_distance;
     trace_var!(semi_major_axis);
-    let orbital_eccentricity = 0.0167;
-    trace_var!(orbital_eccentricity);
-    let perihelion = (1.0 - orbital_eccentricity) * semi_major_axis;
-    trace_var!(perihelion);
-    let aphelion = (1.0 + orbital_eccentricity) * semi_major_axis;
-    trace_var!(aphelion);
+    let orbit = OrbitalElements::new(semi_major_axis, 0.0167);
+    trace_var!(orbit);
     let orbital_period = semi_major_axis.powf(3.0).sqrt();
     trace_var!(orbital_period);
-    let equilibrium_temperature =
-      get_equilibrium_temperature(bond_albedo, greenhouse_effect, host_star_luminosity, host_star_distance);
+    let seasons = Seasons::new(axial_tilt, orbit.eccentricity, orbital_period);
+    trace_var!(seasons);
+    let equilibrium_temperature = get_equilibrium_temperature(bond_albedo, host_star_luminosity, host_star_distance);
     trace_var!(equilibrium_temperature);
-    let is_atmospherically_stable = is_atmospherically_stable(equilibrium_temperature, escape_velocity);
+    let insolation = get_insolation(host_star_luminosity, host_star_distance);
+    trace_var!(insolation);
+    let has_atmosphere = can_retain_atmosphere(insolation, relative_escape_velocity);
+    trace_var!(has_atmosphere);
+    let greenhouse_effect = get_greenhouse_effect(has_atmosphere, equilibrium_temperature, relative_escape_velocity);
+    trace_var!(greenhouse_effect);
+    let surface_temperature = get_surface_temperature(equilibrium_temperature, greenhouse_effect);
+    trace_var!(surface_temperature);
+    let is_atmospherically_stable =
+      has_atmosphere && is_atmospherically_stable(equilibrium_temperature, relative_escape_velocity);
     trace_var!(is_atmospherically_stable);
+    let radiogenic_heat = get_radiogenic_heat(mass, core_mass_fraction, EARTH_AGE);
+    trace_var!(radiogenic_heat);
+    let tidal_heat = get_tidal_heat(1.0, radius, semi_major_axis, orbit.eccentricity, orbital_period);
+    trace_var!(tidal_heat);
+    let relative_heat_flow = get_relative_heat_flow(radiogenic_heat + tidal_heat, radius);
+    trace_var!(relative_heat_flow);
+    let volcanic_activity = VolcanicActivity::from_heat_flow(relative_heat_flow);
+    trace_var!(volcanic_activity);
+    let has_water = has_atmosphere && is_water_stable(equilibrium_temperature, relative_escape_velocity);
+    trace_var!(has_water);
+    let has_plate_tectonics = has_plate_tectonics(mass, relative_heat_flow, has_water);
+    trace_var!(has_plate_tectonics);
+    let water_inventory = get_water_inventory(mass, EARTH_WATER_MASS_FRACTION);
+    trace_var!(water_inventory);
+    let ocean_fraction = match has_water && is_water_liquid(surface_temperature) {
+      true => get_ocean_fraction(water_inventory, radius, surface_gravity),
+      false => 0.0,
+    };
+    trace_var!(ocean_fraction);
+    let is_tidally_locked = false;
+    trace_var!(is_tidally_locked);
+    let is_eyeball_world = false;
+    trace_var!(is_eyeball_world);
+    let habitable_from_gyr = 0.0;
+    trace_var!(habitable_from_gyr);
+    let habitable_until_gyr = 0.0;
+    trace_var!(habitable_until_gyr);
+    let name = String::new();
     let result = Self {
+      name,
       mass,
       core_mass_fraction,
+      differentiation,
+      has_magnetic_field,
+      magnetic_moment,
+      radiogenic_heat,
+      tidal_heat,
+      volcanic_activity,
+      has_plate_tectonics,
       density,
+      composition,
       escape_velocity,
-      gravity,
+      surface_gravity,
       radius,
+      rotation_period,
+      oblateness,
+      equatorial_radius,
+      polar_radius,
+      j2,
       axial_tilt,
       rotation_direction,
-      semi_major_axis,
+      is_obliquity_stabilized,
+      obliquity_variation,
       tropic_zones,
       polar_zones,
-      orbital_eccentricity,
-      perihelion,
-      aphelion,
+      seasons,
+      orbit,
       orbital_period,
       bond_albedo,
       greenhouse_effect,
       equilibrium_temperature,
+      surface_temperature,
+      has_atmosphere,
       is_atmospherically_stable,
+      water_inventory,
+      ocean_fraction,
+      is_tidally_locked,
+      is_eyeball_world,
+      habitable_from_gyr,
+      habitable_until_gyr,
     };
     trace_var!(result);
     trace_exit!();
@@ -135,32 +308,45 @@ impl TerrestrialPlanet {
   pub fn check_habitable(&self) -> Result<(), Error> {
     trace_enter!();
     let result = {
-      if self.equilibrium_temperature <= MINIMUM_HABITABLE_TEMPERATURE {
+      if self.surface_temperature <= MINIMUM_HABITABLE_TEMPERATURE {
         // About 0ºC is too damned cold.
         return Err(Error::TooColdToSupportConventionalLife);
       }
-      if self.equilibrium_temperature >= MAXIMUM_HABITABLE_TEMPERATURE {
+      if self.surface_temperature >= MAXIMUM_HABITABLE_TEMPERATURE {
         // About 50ºC is too damned hot.
         return Err(Error::TooHotToSupportConventionalLife);
       }
-      if self.gravity <= MINIMUM_HABITABLE_GRAVITY {
+      if self.surface_gravity <= MINIMUM_HABITABLE_GRAVITY {
         return Err(Error::GravityTooLowToSupportConventionalLife);
       }
-      if self.gravity >= MAXIMUM_HABITABLE_GRAVITY {
+      if self.surface_gravity >= MAXIMUM_HABITABLE_GRAVITY {
         return Err(Error::GravityTooHighToSupportConventionalLife);
       }
-      if !is_oxygen_stable(self.equilibrium_temperature, self.escape_velocity) {
+      if !self.has_atmosphere {
+        return Err(Error::Airless);
+      }
+      let escape_velocity = self.get_relative_escape_velocity();
+      if !is_oxygen_stable(self.equilibrium_temperature, escape_velocity) {
         return Err(Error::AtmosphereUnstableForOxygen);
       }
-      if !is_carbon_dioxide_stable(self.equilibrium_temperature, self.escape_velocity) {
+      if !is_carbon_dioxide_stable(self.equilibrium_temperature, escape_velocity) {
         return Err(Error::AtmosphereUnstableForCarbonDioxide);
       }
-      if !is_argon_stable(self.equilibrium_temperature, self.escape_velocity) {
+      if !is_argon_stable(self.equilibrium_temperature, escape_velocity) {
         return Err(Error::AtmosphereUnstableForArgon);
       }
-      if !is_nitrogen_stable(self.equilibrium_temperature, self.escape_velocity) {
+      if !is_nitrogen_stable(self.equilibrium_temperature, escape_velocity) {
         return Err(Error::AtmosphereUnstableForNitrogen);
       }
+      if !self.is_obliquity_stabilized && self.get_maximum_obliquity() > MAXIMUM_HABITABLE_OBLIQUITY {
+        return Err(Error::ChaoticObliquity);
+      }
+      if !self.has_magnetic_field {
+        return Err(Error::NoMagneticField);
+      }
+      if self.is_tidally_locked && !self.is_eyeball_world {
+        return Err(Error::TidallyLocked);
+      }
       Ok(())
     };
     trace_var!(result);
@@ -168,6 +354,106 @@ impl TerrestrialPlanet {
     result
   }
 
+  /// Get the greatest obliquity the axial tilt wanders to, in degrees.
+  ///
+  /// A retrograde spin counts the same as its mirror image.
+  #[named]
+  pub fn get_maximum_obliquity(&self) -> f64 {
+    trace_enter!();
+    let obliquity = self.axial_tilt.min(180.0 - self.axial_tilt);
+    trace_var!(obliquity);
+    let result = (obliquity + self.obliquity_variation).min(90.0);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Account for the moons around this planet.
+  ///
+  /// A big enough moon, like ours, keeps the axial tilt from wandering.  The
+  /// masses are in KG.
+  #[named]
+  pub fn set_largest_moon_mass(&mut self, moon_mass: f64, planet_mass: f64) {
+    trace_enter!();
+    trace_var!(moon_mass);
+    trace_var!(planet_mass);
+    self.is_obliquity_stabilized = is_obliquity_stabilized(moon_mass, planet_mass);
+    self.obliquity_variation = get_obliquity_variation(self.is_obliquity_stabilized);
+    trace_exit!();
+  }
+
+  /// Get the escape velocity relative to Earth's, which is what the
+  /// atmospheric math works in.
+  #[named]
+  pub fn get_relative_escape_velocity(&self) -> f64 {
+    trace_enter!();
+    let result = self.escape_velocity / EARTH_ESCAPE_VELOCITY;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this planet can hold on to nitrogen.
+  #[named]
+  pub fn can_retain_nitrogen(&self) -> bool {
+    trace_enter!();
+    let result =
+      self.has_atmosphere && is_nitrogen_stable(self.equilibrium_temperature, self.get_relative_escape_velocity());
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this planet can hold on to oxygen.
+  #[named]
+  pub fn can_retain_oxygen(&self) -> bool {
+    trace_enter!();
+    let result =
+      self.has_atmosphere && is_oxygen_stable(self.equilibrium_temperature, self.get_relative_escape_velocity());
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this planet can hold on to water vapor.
+  #[named]
+  pub fn can_retain_water(&self) -> bool {
+    trace_enter!();
+    let result =
+      self.has_atmosphere && is_water_stable(self.equilibrium_temperature, self.get_relative_escape_velocity());
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Calculate the Earth Similarity Index, from 0 (nothing like Earth) to 1
+  /// (just like it).
+  #[named]
+  pub fn esi(&self) -> f64 {
+    trace_enter!();
+    let result = get_earth_similarity_index(
+      self.radius,
+      self.density,
+      self.get_relative_escape_velocity(),
+      self.surface_temperature,
+    );
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether this planet falls within its habitability window at a
+  /// given age of its host star, in Gyr.
+  #[named]
+  pub fn is_habitable_at(&self, age: f64) -> bool {
+    trace_enter!();
+    trace_var!(age);
+    let result = self.habitable_from_gyr <= age && age < self.habitable_until_gyr;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
   /// Indicate whether this planet is capable of supporting conventional life.
   #[named]
   pub fn is_habitable(&self) -> bool {
@@ -180,6 +466,38 @@ impl TerrestrialPlanet {
     trace_exit!();
     result
   }
+
+  /// Get the noise the terrain is drawn from.
+  ///
+  /// It's seeded from the planet's name, so a planet always has the same
+  /// continents.  Build this once and reuse it for every point sampled.
+  #[named]
+  pub fn get_terrain_noise(&self) -> Noise {
+    trace_enter!();
+    let result = Noise::from_id(&format!("{}:terrain", self.name));
+    trace_exit!();
+    result
+  }
+
+  /// Get the relative elevation of the terrain at a point on the surface, in
+  /// -1.0..=1.0.
+  ///
+  /// The latitude and longitude are in degrees; the noise should be the
+  /// planet's terrain noise.
+  #[named]
+  pub fn get_elevation(&self, noise: &Noise, latitude: f64, longitude: f64) -> f64 {
+    trace_enter!();
+    trace_var!(latitude);
+    trace_var!(longitude);
+    let (latitude, longitude) = (latitude.to_radians(), longitude.to_radians());
+    let x = latitude.cos() * longitude.cos() * TERRAIN_FREQUENCY;
+    let y = latitude.cos() * longitude.sin() * TERRAIN_FREQUENCY;
+    let z = latitude.sin() * TERRAIN_FREQUENCY;
+    let result = noise.get_fbm_3d(x, y, z, &FbmOptions::default());
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
 }
 
 #[cfg(test)]
@@ -196,13 +514,66 @@ pub mod test {
     let planet = TerrestrialPlanet::from_mass(1.0)?;
     assert_approx_eq!(planet.mass, 1.0);
     assert_approx_eq!(planet.core_mass_fraction, 0.35);
+    assert_eq!(planet.differentiation, Differentiation::Differentiated);
+    assert!(planet.has_magnetic_field);
+    assert_approx_eq!(planet.magnetic_moment, 1.0, 0.1);
+    assert_eq!(planet.volcanic_activity, VolcanicActivity::Moderate);
+    assert!(planet.has_plate_tectonics);
+    assert!(!planet.is_obliquity_stabilized);
+    assert_approx_eq!(planet.get_maximum_obliquity(), 53.5);
+    assert_approx_eq!(planet.ocean_fraction, 0.71, 0.01);
     assert_approx_eq!(planet.density, 5.56, 0.01);
-    assert_approx_eq!(planet.escape_velocity, 1.00, 0.01);
-    assert_approx_eq!(planet.gravity, 1.00, 0.01);
+    assert_approx_eq!(planet.escape_velocity, 11.19, 0.1);
+    assert_approx_eq!(planet.surface_gravity, 1.00, 0.01);
     assert_approx_eq!(planet.radius, 1.00, 0.01);
+    assert_approx_eq!(planet.oblateness, 0.0034, 0.001);
+    assert!(planet.equatorial_radius > planet.polar_radius);
+    assert_approx_eq!(planet.equilibrium_temperature, 255.0, 2.0);
+    assert_approx_eq!(planet.surface_temperature, 288.0, 2.0);
+    assert!(planet.esi() > 0.95);
+    assert!(!planet.is_tidally_locked);
+    assert!(planet.has_atmosphere);
+    assert!(planet.can_retain_nitrogen());
+    assert!(planet.can_retain_oxygen());
+    assert!(planet.can_retain_water());
     trace_var!(planet);
     print_var!(planet);
     trace_exit!();
     Ok(())
   }
+
+  #[named]
+  #[test]
+  pub fn test_get_elevation() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut planet = TerrestrialPlanet::from_mass(1.0)?;
+    planet.name = "Steve III".to_string();
+    let noise = planet.get_terrain_noise();
+    let elevations = (-80..=80)
+      .step_by(20)
+      .flat_map(|latitude| {
+        (0..360)
+          .step_by(30)
+          .map(move |longitude| (latitude as f64, longitude as f64))
+      })
+      .map(|(latitude, longitude)| planet.get_elevation(&noise, latitude, longitude))
+      .collect::<Vec<f64>>();
+    print_var!(elevations);
+    assert!(elevations.iter().all(|elevation| (-1.0..=1.0).contains(elevation)));
+    assert!(elevations.iter().any(|elevation| *elevation > 0.0));
+    assert!(elevations.iter().any(|elevation| *elevation < 0.0));
+    // Longitude wraps around.
+    assert_approx_eq!(
+      planet.get_elevation(&noise, 10.0, -170.0),
+      planet.get_elevation(&noise, 10.0, 190.0)
+    );
+    // Each planet has its own terrain.
+    let elevation = planet.get_elevation(&noise, 10.0, 20.0);
+    planet.name = "Steve IV".to_string();
+    let noise = planet.get_terrain_noise();
+    assert_ne!(planet.get_elevation(&noise, 10.0, 20.0), elevation);
+    trace_exit!();
+    Ok(())
+  }
 }
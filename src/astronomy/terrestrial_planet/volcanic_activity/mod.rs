@@ -0,0 +1,36 @@
+use serde::Serialize;
+
+/// How volcanically active a planet is.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum VolcanicActivity {
+  /// Cold and dead, like the Moon.
+  Dormant,
+  /// The odd eruption, like Mars.
+  Low,
+  /// Like Earth.
+  Moderate,
+  /// Lava plains and shield volcanoes everywhere.
+  High,
+  /// Like Io; the surface is constantly being repaved.
+  Extreme,
+}
+
+impl VolcanicActivity {
+  /// Determine volcanic activity from heat flow, relative to Earth's.
+  #[named]
+  pub fn from_heat_flow(relative_heat_flow: f64) -> Self {
+    trace_enter!();
+    trace_var!(relative_heat_flow);
+    use VolcanicActivity::*;
+    let result = match relative_heat_flow {
+      heat_flow if heat_flow < 0.1 => Dormant,
+      heat_flow if heat_flow < 0.5 => Low,
+      heat_flow if heat_flow < 2.0 => Moderate,
+      heat_flow if heat_flow < 10.0 => High,
+      _ => Extreme,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
@@ -0,0 +1,158 @@
+use serde::Serialize;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::astronomy::orbit::constants::DAYS_PER_YEAR;
+
+/// A time relative to the epoch of a system, or the span between two times.
+///
+/// Orbits are worked out in years and moons in days, and it's easy to hand
+/// one to the other by mistake; this keeps track of which is which.  It's
+/// stored (and serialized) in Julian years, and may be negative for the
+/// past.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Serialize)]
+pub struct Time(f64);
+
+impl Time {
+  /// The epoch itself.
+  pub const EPOCH: Time = Time(0.0);
+
+  /// Create from years after epoch.
+  pub const fn from_years(years: f64) -> Self {
+    Time(years)
+  }
+
+  /// Create from days after epoch.
+  pub fn from_days(days: f64) -> Self {
+    Time(days / DAYS_PER_YEAR)
+  }
+
+  /// Get the number of years after epoch.
+  pub const fn get_years(&self) -> f64 {
+    self.0
+  }
+
+  /// Get the number of days after epoch.
+  pub fn get_days(&self) -> f64 {
+    self.0 * DAYS_PER_YEAR
+  }
+
+  /// Get the number of orbits (or rotations, or any other cycle) with the
+  /// specified period that have elapsed, including the fraction of the
+  /// current one.
+  #[named]
+  pub fn get_cycles(&self, period: Time) -> f64 {
+    trace_enter!();
+    trace_var!(period);
+    let result = self.0 / period.0;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get how far through a cycle with the specified period this is, as a
+  /// fraction in 0.0..1.0.
+  #[named]
+  pub fn get_phase(&self, period: Time) -> f64 {
+    trace_enter!();
+    trace_var!(period);
+    let result = self.get_cycles(period).rem_euclid(1.0);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl Add for Time {
+  type Output = Time;
+
+  fn add(self, other: Time) -> Time {
+    Time(self.0 + other.0)
+  }
+}
+
+impl AddAssign for Time {
+  fn add_assign(&mut self, other: Time) {
+    self.0 += other.0;
+  }
+}
+
+impl Sub for Time {
+  type Output = Time;
+
+  fn sub(self, other: Time) -> Time {
+    Time(self.0 - other.0)
+  }
+}
+
+impl SubAssign for Time {
+  fn sub_assign(&mut self, other: Time) {
+    self.0 -= other.0;
+  }
+}
+
+impl Neg for Time {
+  type Output = Time;
+
+  fn neg(self) -> Time {
+    Time(-self.0)
+  }
+}
+
+impl Mul<f64> for Time {
+  type Output = Time;
+
+  fn mul(self, factor: f64) -> Time {
+    Time(self.0 * factor)
+  }
+}
+
+impl Div<f64> for Time {
+  type Output = Time;
+
+  fn div(self, divisor: f64) -> Time {
+    Time(self.0 / divisor)
+  }
+}
+
+impl fmt::Display for Time {
+  /// Short spans read best in days, and long ones in years.
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    match self.0.abs() {
+      years if years >= 1.0E9 => write!(formatter, "{:.2} Gyr", self.0 / 1.0E9),
+      years if years >= 1.0E6 => write!(formatter, "{:.2} Myr", self.0 / 1.0E6),
+      years if years >= 1.0 => write!(formatter, "{:.2} years", self.0),
+      _ => write!(formatter, "{:.2} days", self.get_days()),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_time() {
+    init();
+    trace_enter!();
+    let year = Time::from_years(1.0);
+    assert_approx_eq!(year.get_days(), DAYS_PER_YEAR);
+    assert_eq!(Time::from_days(DAYS_PER_YEAR), year);
+    let mut time = Time::EPOCH + year * 2.5;
+    time -= Time::from_days(DAYS_PER_YEAR / 2.0);
+    assert_approx_eq!(time.get_years(), 2.0);
+    assert!(-time < Time::EPOCH);
+    assert_approx_eq!((time / 4.0).get_years(), 0.5);
+    assert_approx_eq!(time.get_cycles(Time::from_years(0.75)), 8.0 / 3.0);
+    assert_approx_eq!(time.get_phase(Time::from_years(0.75)), 2.0 / 3.0);
+    assert_approx_eq!((-time).get_phase(Time::from_years(0.75)), 1.0 / 3.0);
+    assert_eq!(format!("{}", Time::from_days(12.345)), "12.35 days");
+    assert_eq!(format!("{}", time), "2.00 years");
+    assert_eq!(format!("{}", Time::from_years(-4.5E9)), "-4.50 Gyr");
+    assert_eq!(format!("{}", Time::from_years(2.0E7)), "20.00 Myr");
+    trace_exit!();
+  }
+}
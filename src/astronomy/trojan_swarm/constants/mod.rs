@@ -0,0 +1,15 @@
+use crate::math::probability::Probability;
+
+/// The probability that a giant planet has trojan swarms.
+///
+/// Jupiter and Neptune have them, Saturn doesn't seem to, and Uranus has
+/// one lonely trojan.
+pub const GIANT_PROBABILITY: Probability = Probability::from_const(0.5);
+
+/// Minimum mass of a trojan swarm, in Mearth.
+pub const MINIMUM_MASS: f64 = 1.0E-6;
+
+/// Maximum mass of a trojan swarm, in Mearth.
+///
+/// Each of Jupiter's swarms is thought to be about 1E-5 Mearth.
+pub const MAXIMUM_MASS: f64 = 1.0E-4;
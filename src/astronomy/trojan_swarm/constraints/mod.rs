@@ -0,0 +1,135 @@
+use rand::prelude::*;
+
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::moons::math::co_orbital::get_tadpole_half_width;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::star::math::mass::msol_to_kg;
+use crate::astronomy::trojan_swarm::constants::*;
+use crate::astronomy::trojan_swarm::{LagrangePoint, TrojanSwarm};
+use crate::math::probability::Probability;
+use crate::merge::Merge;
+
+/// Constraints for creating trojan swarms.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+  /// The probability that a giant planet has trojan swarms.
+  pub giant_probability: Option<Probability>,
+  /// The minimum mass of each swarm, in Mearth.
+  pub minimum_mass: Option<f64>,
+  /// The maximum mass of each swarm, in Mearth.
+  pub maximum_mass: Option<f64>,
+}
+
+impl Constraints {
+  /// Generate.
+  ///
+  /// Only giant planets are massive enough to hold onto swarms worth the
+  /// name; when they do, there's one at each of L4 and L5.
+  #[named]
+  pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R, host_star: &HostStar, planet: &Planet) -> Vec<TrojanSwarm> {
+    trace_enter!();
+    trace_var!(host_star);
+    trace_var!(planet);
+    let probability = match planet {
+      Planet::GasGiantPlanet(_) | Planet::IceGiantPlanet(_) => self.giant_probability.unwrap_or(GIANT_PROBABILITY),
+      Planet::TerrestrialPlanet(_) => Probability::NEVER,
+    };
+    trace_var!(probability);
+    if !probability.sample(rng) {
+      trace_exit!();
+      return vec![];
+    }
+    let minimum_mass = self.minimum_mass.unwrap_or(MINIMUM_MASS);
+    trace_var!(minimum_mass);
+    let maximum_mass = self.maximum_mass.unwrap_or(MAXIMUM_MASS);
+    trace_var!(maximum_mass);
+    let semi_major_axis = planet.get_semi_major_axis();
+    trace_var!(semi_major_axis);
+    let mass_ratio = planet.get_mass_in_kg() / msol_to_kg(host_star.get_stellar_mass());
+    trace_var!(mass_ratio);
+    let half_width = get_tadpole_half_width(semi_major_axis, mass_ratio);
+    trace_var!(half_width);
+    let result = [LagrangePoint::L4, LagrangePoint::L5]
+      .into_iter()
+      .map(|lagrange_point| TrojanSwarm {
+        lagrange_point,
+        semi_major_axis,
+        half_width,
+        mass: rng.gen_range(minimum_mass..=maximum_mass),
+      })
+      .collect();
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl Default for Constraints {
+  /// No constraints, just let it all hang out.
+  fn default() -> Self {
+    let giant_probability = None;
+    let minimum_mass = None;
+    let maximum_mass = None;
+    Self {
+      giant_probability,
+      minimum_mass,
+      maximum_mass,
+    }
+  }
+}
+
+impl Merge for Constraints {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    Self {
+      giant_probability: overrides.giant_probability.or(self.giant_probability),
+      minimum_mass: overrides.minimum_mass.or(self.minimum_mass),
+      maximum_mass: overrides.maximum_mass.or(self.maximum_mass),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use crate::astronomy::gas_giant_planet::constraints::Constraints as GasGiantPlanetConstraints;
+  use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+  use crate::astronomy::planet::error::Error;
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let host_star = HostStarConstraints::default().generate(&mut rng)?;
+    let star_distance = 2.0 * host_star.get_frost_line();
+    let planet =
+      Planet::GasGiantPlanet(GasGiantPlanetConstraints::default().generate(&mut rng, &host_star, star_distance)?);
+    let constraints = Constraints {
+      giant_probability: Some(Probability::ALWAYS),
+      ..Constraints::default()
+    };
+    let trojan_swarms = constraints.generate(&mut rng, &host_star, &planet);
+    print_var!(trojan_swarms);
+    assert_eq!(trojan_swarms.len(), 2);
+    assert_eq!(trojan_swarms[0].lagrange_point, LagrangePoint::L4);
+    assert_eq!(trojan_swarms[1].lagrange_point, LagrangePoint::L5);
+    for trojan_swarm in trojan_swarms.iter() {
+      assert!(trojan_swarm.contains(planet.get_semi_major_axis()));
+      assert!(trojan_swarm.half_width < 0.5 * planet.get_semi_major_axis());
+      assert!(trojan_swarm.mass >= MINIMUM_MASS && trojan_swarm.mass <= MAXIMUM_MASS);
+    }
+    let constraints = Constraints {
+      giant_probability: Some(Probability::NEVER),
+      ..Constraints::default()
+    };
+    assert!(constraints.generate(&mut rng, &host_star, &planet).is_empty());
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,44 @@
+use serde::Serialize;
+
+pub mod constants;
+pub mod constraints;
+
+/// Which of a planet's Lagrange points a swarm gathers at.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum LagrangePoint {
+  /// 60° ahead of the planet.
+  L4,
+  /// 60° behind the planet.
+  L5,
+}
+
+/// A `TrojanSwarm` is a cloud of asteroids sharing a planet's orbit, held
+/// about one of its stable Lagrange points.
+///
+/// Jupiter has thousands of them on either side; Neptune has a few dozen
+/// that we know of.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TrojanSwarm {
+  /// The Lagrange point the swarm librates about.
+  pub lagrange_point: LagrangePoint,
+  /// The semi-major axis of the swarm, which is the planet's, in AU.
+  pub semi_major_axis: f64,
+  /// How far the swarm extends inward and outward of the planet's orbit,
+  /// in AU.
+  pub half_width: f64,
+  /// The total mass, in Mearth.
+  pub mass: f64,
+}
+
+impl TrojanSwarm {
+  /// Indicate whether a given distance from the star falls within the swarm.
+  #[named]
+  pub fn contains(&self, distance: f64) -> bool {
+    trace_enter!();
+    trace_var!(distance);
+    let result = (distance - self.semi_major_axis).abs() <= self.half_width;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
@@ -0,0 +1,22 @@
+/// Optical depth of the Sun's zodiacal cloud, at 1 AU.
+pub const SOLAR_OPTICAL_DEPTH: f64 = 1.0E-7;
+
+/// Mass of the Sun's main asteroid belt, in Mearth, which feeds that cloud.
+pub const SOLAR_BELT_MASS: f64 = 0.0004;
+
+/// How steeply the optical depth rises toward the star.
+///
+/// Poynting-Robertson drag drains grains inward, so the cloud thickens in
+/// about proportion to r^-0.34.
+pub const RADIAL_PROFILE_EXPONENT: f64 = -0.34;
+
+/// Distance at which dust sublimates around a star of one Lsol, in AU.
+///
+/// Grains last until they reach about 1,500 K.
+pub const SUBLIMATION_DISTANCE: f64 = 0.034;
+
+/// Brightness of the zodiacal light, relative to Earth's, above which it's
+/// plain to see from a dark site.
+///
+/// Earth's is visible, but only after twilight and away from cities.
+pub const MINIMUM_VISIBLE_BRIGHTNESS: f64 = 0.5;
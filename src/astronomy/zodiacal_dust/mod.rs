@@ -0,0 +1,139 @@
+use serde::Serialize;
+
+use crate::astronomy::asteroid_belt::AsteroidBelt;
+
+pub mod constants;
+use constants::*;
+
+/// A `ZodiacalDust` is the cloud of fine dust ground out of an asteroid belt
+/// and dragged inward toward the star, visible as a glow along the ecliptic.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ZodiacalDust {
+  /// The inner edge, where grains sublimate, in AU.
+  pub inner_edge: f64,
+  /// The outer edge, the outer edge of the parent belt, in AU.
+  pub outer_edge: f64,
+  /// The optical depth at the inner edge of the parent belt.
+  pub optical_depth: f64,
+  /// The inner edge of the parent belt, in AU.
+  pub belt_inner_edge: f64,
+  /// The indices of planets orbiting within the cloud, which trap dust in
+  /// resonant rings and in clouds at their L4 and L5 points.
+  pub dust_clouds: Vec<usize>,
+}
+
+impl ZodiacalDust {
+  /// Grind a dust cloud out of an asteroid belt.
+  ///
+  /// Dust comes from collisions, which go as the square of the belt's mass;
+  /// luminosity is in Lsol and the planets' semi-major axes are in AU.
+  #[named]
+  pub fn from_belt(asteroid_belt: &AsteroidBelt, luminosity: f64, semi_major_axes: &[f64]) -> Self {
+    trace_enter!();
+    trace_var!(asteroid_belt);
+    trace_var!(luminosity);
+    trace_var!(semi_major_axes);
+    let inner_edge = SUBLIMATION_DISTANCE * luminosity.sqrt();
+    trace_var!(inner_edge);
+    let outer_edge = asteroid_belt.outer_edge;
+    trace_var!(outer_edge);
+    let belt_inner_edge = asteroid_belt.inner_edge;
+    trace_var!(belt_inner_edge);
+    // Normalize to the Sun's cloud, where the belt's inner edge is 2.1 AU.
+    let optical_depth =
+      SOLAR_OPTICAL_DEPTH * (asteroid_belt.mass / SOLAR_BELT_MASS).powf(2.0) * 2.1f64.powf(RADIAL_PROFILE_EXPONENT);
+    trace_var!(optical_depth);
+    let dust_clouds = semi_major_axes
+      .iter()
+      .enumerate()
+      .filter(|(_, &semi_major_axis)| semi_major_axis > inner_edge && semi_major_axis < belt_inner_edge)
+      .map(|(index, _)| index)
+      .collect();
+    trace_var!(dust_clouds);
+    let result = Self {
+      inner_edge,
+      outer_edge,
+      optical_depth,
+      belt_inner_edge,
+      dust_clouds,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the optical depth of the cloud at the specified distance in AU.
+  #[named]
+  pub fn get_optical_depth_at(&self, distance: f64) -> f64 {
+    trace_enter!();
+    trace_var!(distance);
+    let result = match distance {
+      distance if distance < self.inner_edge || distance > self.outer_edge => 0.0,
+      distance if distance > self.belt_inner_edge => self.optical_depth,
+      distance => self.optical_depth * (distance / self.belt_inner_edge).powf(RADIAL_PROFILE_EXPONENT),
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Get the brightness of the zodiacal light seen from the specified
+  /// distance in AU, relative to that seen from Earth.
+  ///
+  /// The glow is sunlight scattered by the nearby dust, so it scales with
+  /// both the optical depth and the starlight falling on it.
+  #[named]
+  pub fn get_brightness_at(&self, distance: f64, luminosity: f64) -> f64 {
+    trace_enter!();
+    trace_var!(distance);
+    trace_var!(luminosity);
+    let result = self.get_optical_depth_at(distance) / SOLAR_OPTICAL_DEPTH * luminosity / distance.powf(2.0);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Indicate whether the zodiacal light is plain to see from the specified
+  /// distance in AU.
+  #[named]
+  pub fn is_visible_at(&self, distance: f64, luminosity: f64) -> bool {
+    trace_enter!();
+    trace_var!(distance);
+    trace_var!(luminosity);
+    let result = self.get_brightness_at(distance, luminosity) >= MINIMUM_VISIBLE_BRIGHTNESS;
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::astronomy::asteroid_belt::constants::MSOL_PER_JUPITER_MASS;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_solar_system() {
+    init();
+    trace_enter!();
+    let belt = AsteroidBelt::from_giant(2.1, 3.3, SOLAR_BELT_MASS, 5.2, MSOL_PER_JUPITER_MASS, 4.85);
+    let dust = ZodiacalDust::from_belt(&belt, 1.0, &[0.39, 0.72, 1.0, 1.52, 5.2]);
+    print_var!(dust);
+    assert_eq!(dust.dust_clouds, vec![0, 1, 2, 3]);
+    assert_approx_eq!(dust.get_optical_depth_at(1.0), SOLAR_OPTICAL_DEPTH);
+    assert_approx_eq!(dust.get_brightness_at(1.0, 1.0), 1.0);
+    assert!(dust.is_visible_at(1.0, 1.0));
+    assert!(!dust.is_visible_at(10.0, 1.0));
+    assert!(dust.get_optical_depth_at(0.5) > dust.get_optical_depth_at(2.0));
+    let dusty = ZodiacalDust::from_belt(
+      &AsteroidBelt::from_giant(2.1, 3.3, 10.0 * SOLAR_BELT_MASS, 5.2, MSOL_PER_JUPITER_MASS, 4.85),
+      1.0,
+      &[],
+    );
+    assert_approx_eq!(dusty.get_brightness_at(1.0, 1.0), 100.0);
+    trace_exit!();
+  }
+}
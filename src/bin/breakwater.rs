@@ -0,0 +1,330 @@
+//! Generate stars, systems, neighborhoods, and galaxies from the command line.
+//!
+//! ```text
+//! breakwater <star|system|neighborhood|galaxy> [options]
+//! ```
+//!
+//! Everything is written to stdout as JSON; the seed used is written to
+//! stderr, so that anything interesting can be generated again.
+use honeyholt::r#trait::brief::Brief;
+use rand::prelude::*;
+use serde::Serialize;
+use std::env::args;
+use std::process::exit;
+
+use breakwater::astronomy::galaxy::morphology::GalaxyType;
+use breakwater::astronomy::star::constants::{MAXIMUM_MASS, MINIMUM_MASS};
+use breakwater::astronomy::star::constraints::builder::Builder as StarBuilder;
+use breakwater::astronomy::star::name::theme::NameTheme;
+use breakwater::prelude::*;
+use breakwater::*;
+
+const USAGE: &str = "\
+Usage: breakwater <COMMAND> [OPTIONS]
+
+Commands:
+  star            Generate a main-sequence star.
+  system          Generate a star system.
+  neighborhood    Generate a stellar neighborhood.
+  galaxy          Generate a galaxy (well, one neighborhood of one).
+
+Options:
+  --seed <N>                 Seed the generator, for reproducible output.
+  --format <json|pretty>     Print compact or indented JSON (default: pretty).
+  --habitable                Insist on something habitable.
+  --min-mass <MSOL>          The minimum mass of a star (star, system).
+  --max-mass <MSOL>          The maximum mass of a star (star, system).
+  --binary-probability <P>   The probability of a close binary (system).
+  --radius <LY>              The radius of a neighborhood (neighborhood, galaxy).
+  --density <N>              Stars per cubic light year (neighborhood, galaxy).
+  --galactic-radius <LY>     The distance from the galactic center (galaxy).
+  --galaxy-type <TYPE>       spiral, barred-spiral, elliptical, irregular, or
+                             dwarf (galaxy).
+  --name-theme <THEME>       greco-roman, arabic, catalog, or fantasy names
+                             (neighborhood, galaxy).
+  --help                     Print this message.";
+
+/// What to generate.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Command {
+  Star,
+  System,
+  Neighborhood,
+  Galaxy,
+}
+
+/// How to print it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+  Json,
+  Pretty,
+}
+
+/// Everything parsed from the command line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+  pub command: Command,
+  pub seed: Option<u64>,
+  pub format: Format,
+  pub habitable: bool,
+  pub minimum_mass: Option<f64>,
+  pub maximum_mass: Option<f64>,
+  pub binary_probability: Option<f64>,
+  pub radius: Option<f64>,
+  pub density: Option<f64>,
+  pub galactic_radius: Option<f64>,
+  pub galaxy_type: Option<GalaxyType>,
+  pub name_theme: Option<NameTheme>,
+}
+
+/// Parse the value following a flag.
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+  let value = value.ok_or(format!("{} needs a value", flag))?;
+  value.parse().map_err(|_| format!("{} can't be {:?}", flag, value))
+}
+
+/// Parse the command line, less the program name.
+#[named]
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+  trace_enter!();
+  let mut args = args.into_iter();
+  let command = match args.next().as_deref() {
+    Some("star") => Command::Star,
+    Some("system") => Command::System,
+    Some("neighborhood") => Command::Neighborhood,
+    Some("galaxy") => Command::Galaxy,
+    Some(other) => return Err(format!("unknown command {:?}", other)),
+    None => return Err("no command given".to_string()),
+  };
+  trace_var!(command);
+  let mut result = Options {
+    command,
+    seed: None,
+    format: Format::Pretty,
+    habitable: false,
+    minimum_mass: None,
+    maximum_mass: None,
+    binary_probability: None,
+    radius: None,
+    density: None,
+    galactic_radius: None,
+    galaxy_type: None,
+    name_theme: None,
+  };
+  while let Some(flag) = args.next() {
+    match flag.as_str() {
+      "--seed" => result.seed = Some(parse_value(&flag, args.next())?),
+      "--format" => {
+        result.format = match args.next().as_deref() {
+          Some("json") => Format::Json,
+          Some("pretty") => Format::Pretty,
+          other => return Err(format!("--format must be json or pretty, not {:?}", other)),
+        }
+      },
+      "--habitable" => result.habitable = true,
+      "--min-mass" => result.minimum_mass = Some(parse_value(&flag, args.next())?),
+      "--max-mass" => result.maximum_mass = Some(parse_value(&flag, args.next())?),
+      "--binary-probability" => result.binary_probability = Some(parse_value(&flag, args.next())?),
+      "--radius" => result.radius = Some(parse_value(&flag, args.next())?),
+      "--density" => result.density = Some(parse_value(&flag, args.next())?),
+      "--galactic-radius" => result.galactic_radius = Some(parse_value(&flag, args.next())?),
+      "--galaxy-type" => {
+        result.galaxy_type = match args.next().as_deref() {
+          Some("spiral") => Some(GalaxyType::Spiral),
+          Some("barred-spiral") => Some(GalaxyType::BarredSpiral),
+          Some("elliptical") => Some(GalaxyType::Elliptical),
+          Some("irregular") => Some(GalaxyType::Irregular),
+          Some("dwarf") => Some(GalaxyType::Dwarf),
+          other => {
+            return Err(format!(
+              "--galaxy-type must be spiral, barred-spiral, elliptical, irregular, or dwarf, not {:?}",
+              other
+            ))
+          },
+        }
+      },
+      "--name-theme" => {
+        result.name_theme = match args.next().as_deref() {
+          Some("greco-roman") => Some(NameTheme::GrecoRoman),
+          Some("arabic") => Some(NameTheme::Arabic),
+          Some("catalog") => Some(NameTheme::Catalog),
+          Some("fantasy") => Some(NameTheme::Fantasy),
+          other => {
+            return Err(format!(
+              "--name-theme must be greco-roman, arabic, catalog, or fantasy, not {:?}",
+              other
+            ))
+          },
+        }
+      },
+      other => return Err(format!("unknown option {:?}", other)),
+    }
+  }
+  trace_var!(result);
+  trace_exit!();
+  Ok(result)
+}
+
+/// Build a star builder from the mass options.
+fn get_star_builder(options: &Options) -> StarBuilder {
+  match (options.minimum_mass, options.maximum_mass) {
+    (None, None) => StarBuilder::default(),
+    (minimum_mass, maximum_mass) => {
+      StarBuilder::default().mass_range(minimum_mass.unwrap_or(MINIMUM_MASS)..maximum_mass.unwrap_or(MAXIMUM_MASS))
+    },
+  }
+}
+
+/// Build star constraints from the options.
+fn get_star_constraints(options: &Options) -> Result<StarConstraints, String> {
+  let builder = match options.habitable {
+    true => get_star_builder(options).habitable(),
+    false => get_star_builder(options),
+  };
+  builder.build().map_err(|error| honeyholt_brief!(error))
+}
+
+/// Build star system constraints from the options.
+fn get_star_system_constraints(options: &Options) -> Result<StarSystemConstraints, String> {
+  let mut builder = StarSystemConstraints::builder();
+  if options.minimum_mass.is_some() || options.maximum_mass.is_some() {
+    builder = builder.star(get_star_builder(options));
+  }
+  if let Some(binary_probability) = options.binary_probability {
+    let probability = Probability::new(binary_probability).map_err(|error| honeyholt_brief!(error))?;
+    builder = builder.binary_probability(probability);
+  }
+  if options.habitable {
+    builder = builder.habitable();
+  }
+  builder.build().map_err(|error| honeyholt_brief!(error))
+}
+
+/// Build stellar neighborhood constraints from the options.
+fn get_stellar_neighborhood_constraints(options: &Options) -> StellarNeighborhoodConstraints {
+  let base = match options.habitable {
+    true => StellarNeighborhoodConstraints::habitable(),
+    false => StellarNeighborhoodConstraints::default(),
+  };
+  StellarNeighborhoodConstraints {
+    radius: options.radius.or(base.radius),
+    density: options.density.or(base.density),
+    name_theme: options.name_theme.or(base.name_theme),
+    ..base
+  }
+}
+
+/// Generate from the constraints and print the result.
+fn print<G>(constraints: &G, seed: u64, format: Format) -> Result<(), String>
+where
+  G: Generator,
+  G::Output: Serialize,
+  G::Error: Brief,
+{
+  let output = constraints
+    .generate_from_seed(seed)
+    .map_err(|error| honeyholt_brief!(error))?;
+  let json = match format {
+    Format::Json => serde_json::to_string(&output),
+    Format::Pretty => serde_json::to_string_pretty(&output),
+  }
+  .map_err(|error| error.to_string())?;
+  println!("{}", json);
+  Ok(())
+}
+
+/// Do what we were asked.
+#[named]
+fn run(options: &Options) -> Result<(), String> {
+  trace_enter!();
+  trace_var!(options);
+  let seed = options.seed.unwrap_or_else(|| thread_rng().gen());
+  eprintln!("seed: {}", seed);
+  let result = match options.command {
+    Command::Star => print(&get_star_constraints(options)?, seed, options.format),
+    Command::System => print(&get_star_system_constraints(options)?, seed, options.format),
+    Command::Neighborhood => print(&get_stellar_neighborhood_constraints(options), seed, options.format),
+    Command::Galaxy => {
+      let stellar_neighborhood_constraints = Some(get_stellar_neighborhood_constraints(options));
+      let constraints = GalaxyConstraints {
+        stellar_neighborhood_constraints,
+        galactic_radius: options.galactic_radius,
+        galaxy_type: options.galaxy_type,
+        ..GalaxyConstraints::default()
+      };
+      print(&constraints, seed, options.format)
+    },
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[named]
+fn main() {
+  init_pretty_env_logger();
+  trace_enter!();
+  let args: Vec<String> = args().skip(1).collect();
+  if args.is_empty() || args.iter().any(|arg| arg == "--help" || arg == "-h") {
+    println!("{}", USAGE);
+    return;
+  }
+  let result = parse_args(args).and_then(|options| run(&options));
+  trace_exit!();
+  if let Err(error) = result {
+    eprintln!("error: {}", error);
+    eprintln!("{}", USAGE);
+    exit(1);
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+
+  fn to_args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(String::from).collect()
+  }
+
+  #[named]
+  #[test]
+  pub fn test_parse_args() {
+    trace_enter!();
+    let options = parse_args(to_args("system --seed 42 --format json --habitable --max-mass 1.1")).unwrap();
+    print_var!(options);
+    assert_eq!(options.command, Command::System);
+    assert_eq!(options.seed, Some(42));
+    assert_eq!(options.format, Format::Json);
+    assert!(options.habitable);
+    assert_eq!(options.minimum_mass, None);
+    assert_eq!(options.maximum_mass, Some(1.1));
+    assert!(parse_args(to_args("planet")).is_err());
+    assert!(parse_args(to_args("star --seed")).is_err());
+    assert!(parse_args(to_args("star --seed many")).is_err());
+    assert!(parse_args(to_args("star --format yaml")).is_err());
+    let options = parse_args(to_args("galaxy --name-theme catalog")).unwrap();
+    assert_eq!(options.name_theme, Some(NameTheme::Catalog));
+    assert!(parse_args(to_args("galaxy --name-theme elvish")).is_err());
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_run() {
+    trace_enter!();
+    for line in [
+      "star --seed 7 --habitable",
+      "system --seed 7",
+      "galaxy --seed 7 --radius 5",
+      "galaxy --seed 7 --radius 3 --galactic-radius 20000 --galaxy-type elliptical",
+      "neighborhood --seed 7 --radius 5 --name-theme greco-roman",
+    ] {
+      let options = parse_args(to_args(line)).unwrap();
+      assert_eq!(run(&options), Ok(()));
+    }
+    let options = parse_args(to_args("star --min-mass 2 --max-mass 1")).unwrap();
+    assert!(run(&options).is_err());
+    trace_exit!();
+  }
+}
@@ -0,0 +1,206 @@
+use inflector::cases::snakecase::to_snake_case;
+use rand_chacha::ChaCha8Rng;
+use std::fmt::Debug;
+use std::thread;
+
+use crate::generator::seeded_rng;
+
+/// Type names that show up in debug output but say nothing about which part
+/// of the crate produced a value.
+pub const UNINFORMATIVE_TYPE_NAMES: &[&str] = &["Some", "None", "Ok", "Err"];
+
+/// The first point at which two supposedly identical generations disagree.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Divergence {
+  /// A path to the divergent field, e.g. `stellar_neighborhood.neighbors[3].distance`.
+  pub path: String,
+  /// The module most likely responsible, e.g. `astronomy::stellar_neighbor`.
+  pub module: String,
+  /// The line of the pretty-printed output on which the divergence appears.
+  pub line: usize,
+  /// That line in the first run.
+  pub first: String,
+  /// That line in the second run.
+  pub second: String,
+}
+
+/// A level of nesting within pretty-printed debug output.
+#[derive(Clone, Debug)]
+struct Frame {
+  segment: String,
+  type_name: Option<String>,
+  opener: Option<char>,
+  children: usize,
+}
+
+/// Split a line of pretty-printed debug output into a field name, if any, and
+/// the remainder.
+fn split_field(line: &str) -> (Option<&str>, &str) {
+  if let Some(index) = line.find(": ") {
+    let name = &line[..index];
+    if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+      return (Some(name), &line[index + 2..]);
+    }
+  }
+  (None, line)
+}
+
+/// Pull a type or variant name out of an opening line, e.g. `Star {` or `Star(`.
+fn get_type_name(rest: &str) -> Option<String> {
+  let name = rest.trim_end_matches(['{', '(', '[']).trim();
+  let informative = name.starts_with(char::is_uppercase)
+    && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+    && !UNINFORMATIVE_TYPE_NAMES.contains(&name);
+  match informative {
+    true => Some(name.to_string()),
+    false => None,
+  }
+}
+
+/// Compare two pretty-printed debug strings and report where they first differ.
+#[named]
+pub fn diff_debug_output(first: &str, second: &str) -> Option<Divergence> {
+  trace_enter!();
+  let mut stack: Vec<Frame> = vec![Frame {
+    segment: String::new(),
+    type_name: None,
+    opener: None,
+    children: 0,
+  }];
+  let mut first_lines = first.lines();
+  let mut second_lines = second.lines();
+  let mut line = 0;
+  let result = loop {
+    let (first_line, second_line) = match (first_lines.next(), second_lines.next()) {
+      (None, None) => break None,
+      (first_line, second_line) => (first_line.unwrap_or(""), second_line.unwrap_or("")),
+    };
+    line += 1;
+    let indent = first_line.len() - first_line.trim_start().len();
+    let depth = indent / 4 + 1;
+    let trimmed = first_line.trim().trim_end_matches(',');
+    stack.truncate(depth);
+    let is_closer = trimmed.starts_with('}') || trimmed.starts_with(']') || trimmed.starts_with(')');
+    let (field, rest) = split_field(trimmed);
+    let segment = match (field, stack.last_mut()) {
+      (Some(name), _) => format!(".{}", name),
+      (None, Some(parent)) if !is_closer => {
+        parent.children += 1;
+        match parent.opener {
+          Some('[') => format!("[{}]", parent.children - 1),
+          Some('(') => format!(".{}", parent.children - 1),
+          _ => String::new(),
+        }
+      },
+      _ => String::new(),
+    };
+    if first_line != second_line {
+      let path = stack
+        .iter()
+        .map(|frame| frame.segment.as_str())
+        .chain(std::iter::once(segment.as_str()))
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string();
+      let module = stack
+        .iter()
+        .rev()
+        .find_map(|frame| frame.type_name.as_ref())
+        .map(|type_name| format!("astronomy::{}", to_snake_case(type_name)))
+        .unwrap_or_default();
+      break Some(Divergence {
+        path,
+        module,
+        line,
+        first: first_line.to_string(),
+        second: second_line.to_string(),
+      });
+    }
+    if !is_closer && (rest.ends_with('{') || rest.ends_with('(') || rest.ends_with('[')) {
+      // Tuples are indexed by position, but `Some(...)` and other wrappers
+      // don't add anything to the path.
+      let opener = match rest {
+        "(" => Some('('),
+        rest if rest.ends_with('[') => Some('['),
+        _ => None,
+      };
+      stack.push(Frame {
+        segment,
+        type_name: get_type_name(rest),
+        opener,
+        children: 0,
+      });
+    }
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Generate the same seed twice and report the first divergence, if any.
+///
+/// If `across_threads` is set, the second generation happens on another
+/// thread, which shakes out anything leaning on thread-local state.
+#[named]
+pub fn audit_determinism<T, F>(seed: u64, across_threads: bool, generate: F) -> Option<Divergence>
+where
+  T: Debug + Send,
+  F: Fn(&mut ChaCha8Rng) -> T + Sync,
+{
+  trace_enter!();
+  trace_var!(seed);
+  trace_var!(across_threads);
+  let run = || format!("{:#?}", generate(&mut seeded_rng(seed)));
+  let first = run();
+  let second = match across_threads {
+    true => thread::scope(|scope| scope.spawn(run).join().unwrap()),
+    false => run(),
+  };
+  let result = diff_debug_output(&first, &second);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::star::constraints::Constraints as StarConstraints;
+  use crate::astronomy::star_system::constraints::Constraints as StarSystemConstraints;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_diff_debug_output() {
+    init();
+    trace_enter!();
+    let first = "Outer {\n    inner: Some(\n        StarSystem {\n            values: [\n                1.0,\n                2.0,\n            ],\n        },\n    ),\n}";
+    let second = first.replace("2.0", "2.5");
+    let divergence = diff_debug_output(first, &second).unwrap();
+    print_var!(divergence);
+    assert_eq!(divergence.path, "inner.values[1]");
+    assert_eq!(divergence.module, "astronomy::star_system");
+    assert_eq!(divergence.line, 6);
+    assert_eq!(diff_debug_output(first, first), None);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_audit_determinism() {
+    init();
+    trace_enter!();
+    let constraints = StarSystemConstraints::default();
+    let result = audit_determinism(42, true, |rng| constraints.generate(rng));
+    print_var!(result);
+    assert_eq!(result, None);
+    let constraints = StarConstraints::default();
+    let result = audit_determinism(42, false, |_| constraints.generate(&mut thread_rng()));
+    print_var!(result);
+    assert!(result.is_some());
+    trace_exit!();
+  }
+}
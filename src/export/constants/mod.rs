@@ -0,0 +1,5 @@
+/// The ratio of Kerbin's radius to Earth's.
+pub const KERBAL_RADIUS_SCALE: f64 = 600_000.0 / 6_371_000.0;
+
+/// The ratio of Kerbin's orbit to Earth's.
+pub const KERBAL_DISTANCE_SCALE: f64 = 13_599_840_256.0 / 149_597_870_700.0;
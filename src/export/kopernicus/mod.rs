@@ -0,0 +1,195 @@
+use std::fmt::Write;
+
+use crate::astronomy::composition::Composition;
+use crate::astronomy::moon::constants::METERS_PER_MOON_RADIUS;
+use crate::astronomy::moon::name::get_systematic_moon_name;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::planetary_system::PlanetarySystem;
+use crate::astronomy::star::math::distance::{au_to_meters, meters_to_rsol, rsol_to_meters};
+use crate::export::*;
+
+/// The parts of a `Body` node that we care about.
+///
+/// Kopernicus wants surface gravity rather than mass, which suits us; scaling
+/// the radius while holding gravity fixed is the usual way to shrink a system
+/// down to KSP proportions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Body<'a> {
+  /// The name of the body.
+  pub name: &'a str,
+  /// The stock body to use as a starting point.
+  pub template: &'a str,
+  /// Radius, in M.
+  pub radius: f64,
+  /// Surface gravity, in Gearth.
+  pub surface_gravity: f64,
+  /// The name of the body this one orbits.
+  pub reference_body: &'a str,
+  /// The orbit, with the semi-major axis in M.
+  pub orbit: OrbitalElements,
+}
+
+impl Body<'_> {
+  /// Write this body as a `Body` node.
+  #[named]
+  pub fn write(&self, output: &mut String) {
+    trace_enter!();
+    trace_var!(self);
+    writeln!(output, "  Body").unwrap();
+    writeln!(output, "  {{").unwrap();
+    writeln!(output, "    name = {}", self.name).unwrap();
+    writeln!(output, "    Template").unwrap();
+    writeln!(output, "    {{").unwrap();
+    writeln!(output, "      name = {}", self.template).unwrap();
+    writeln!(output, "    }}").unwrap();
+    writeln!(output, "    Properties").unwrap();
+    writeln!(output, "    {{").unwrap();
+    writeln!(output, "      radius = {:.0}", self.radius).unwrap();
+    writeln!(output, "      geeASL = {:.4}", self.surface_gravity).unwrap();
+    writeln!(output, "    }}").unwrap();
+    writeln!(output, "    Orbit").unwrap();
+    writeln!(output, "    {{").unwrap();
+    writeln!(output, "      referenceBody = {}", self.reference_body).unwrap();
+    writeln!(output, "      semiMajorAxis = {:.0}", self.orbit.semi_major_axis).unwrap();
+    writeln!(output, "      eccentricity = {:.4}", self.orbit.eccentricity).unwrap();
+    writeln!(output, "      inclination = {:.2}", self.orbit.inclination).unwrap();
+    writeln!(output, "      LAN = {:.2}", self.orbit.longitude_of_ascending_node).unwrap();
+    writeln!(
+      output,
+      "      argumentOfPeriapsis = {:.2}",
+      self.orbit.argument_of_periapsis
+    )
+    .unwrap();
+    writeln!(output, "      meanAnomalyAtEpochD = {:.2}", self.orbit.mean_anomaly).unwrap();
+    writeln!(output, "    }}").unwrap();
+    writeln!(output, "  }}").unwrap();
+    trace_exit!();
+  }
+}
+
+/// Export a planetary system as a Kopernicus config for Kerbal Space Program.
+///
+/// The host star replaces the stock Sun; a close binary is exported as a
+/// single star, since KSP has no notion of a circumbinary orbit.
+#[named]
+pub fn export(planetary_system: &PlanetarySystem, options: &ExportOptions) -> Vec<ExportedFile> {
+  trace_enter!();
+  trace_var!(options);
+  let star_name = planetary_system.host_star.get_name();
+  trace_var!(star_name);
+  let star_radius = {
+    use crate::astronomy::host_star::HostStar::*;
+    match &planetary_system.host_star {
+      Star(star) => star.radius,
+      CloseBinaryStar(close_binary_star) => close_binary_star.primary.radius,
+      StellarRemnant(stellar_remnant) => meters_to_rsol(stellar_remnant.radius * 1_000.0),
+      BrownDwarf(brown_dwarf) => brown_dwarf.radius,
+    }
+  };
+  trace_var!(star_radius);
+  let mut output = String::new();
+  writeln!(output, "// {} system, generated by breakwater.", star_name).unwrap();
+  if let Some(location) = &options.location {
+    writeln!(output, "// Located in {}.", location).unwrap();
+  }
+  writeln!(output, "@Kopernicus:AFTER[Kopernicus]").unwrap();
+  writeln!(output, "{{").unwrap();
+  writeln!(output, "  Body").unwrap();
+  writeln!(output, "  {{").unwrap();
+  writeln!(output, "    name = Sun").unwrap();
+  writeln!(output, "    Template").unwrap();
+  writeln!(output, "    {{").unwrap();
+  writeln!(output, "      name = Sun").unwrap();
+  writeln!(output, "    }}").unwrap();
+  writeln!(output, "    Properties").unwrap();
+  writeln!(output, "    {{").unwrap();
+  writeln!(output, "      displayName = {}", star_name).unwrap();
+  writeln!(
+    output,
+    "      radius = {:.0}",
+    rsol_to_meters(star_radius) * options.radius_scale
+  )
+  .unwrap();
+  writeln!(output, "    }}").unwrap();
+  writeln!(output, "  }}").unwrap();
+  for satellite_system in planetary_system.satellite_systems.satellite_systems.iter() {
+    let planet = &satellite_system.planet;
+    let planet_name = planet.get_name();
+    trace_var!(planet_name);
+    let template = match planet {
+      Planet::GasGiantPlanet(_) | Planet::IceGiantPlanet(_) => "Jool",
+      Planet::TerrestrialPlanet(_) => "Kerbin",
+    };
+    Body {
+      name: &planet_name,
+      template,
+      radius: get_planet_radius_in_meters(planet) * options.radius_scale,
+      surface_gravity: planet.get_surface_gravity(),
+      reference_body: "Sun",
+      orbit: OrbitalElements {
+        semi_major_axis: au_to_meters(planet.get_semi_major_axis()) * options.distance_scale,
+        ..planet.get_orbit()
+      },
+    }
+    .write(&mut output);
+    for (moon_index, moon) in satellite_system.moons.moons.iter().enumerate() {
+      let moon_name = get_systematic_moon_name(&planet_name, moon_index);
+      trace_var!(moon_name);
+      let template = match moon.composition {
+        Composition::Icy => "Vall",
+        _ => "Mun",
+      };
+      Body {
+        name: &moon_name,
+        template,
+        radius: moon.radius * METERS_PER_MOON_RADIUS * options.radius_scale,
+        surface_gravity: moon.surface_gravity,
+        reference_body: &planet_name,
+        orbit: OrbitalElements {
+          semi_major_axis: moon.orbit.semi_major_axis * 1000.0 * options.distance_scale,
+          ..moon.orbit
+        },
+      }
+      .write(&mut output);
+    }
+  }
+  writeln!(output, "}}").unwrap();
+  let path = format!("GameData/{}/{}.cfg", star_name, star_name);
+  trace_var!(path);
+  let result = vec![ExportedFile { path, contents: output }];
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::planetary_system::constraints::Constraints;
+  use crate::astronomy::planetary_system::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_export() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let planetary_system = Constraints::habitable().generate(&mut rng)?;
+    let files = export(&planetary_system, &ExportOptions::kerbal());
+    print_var!(files);
+    assert_eq!(files.len(), 1);
+    let contents = &files[0].contents;
+    assert!(contents.starts_with("// "));
+    assert!(contents.contains("@Kopernicus:AFTER[Kopernicus]"));
+    assert!(contents.contains("name = Kerbin"));
+    assert_eq!(contents.matches('{').count(), contents.matches('}').count());
+    trace_exit!();
+    Ok(())
+  }
+}
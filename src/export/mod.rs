@@ -0,0 +1,99 @@
+use crate::astronomy::planet::math::mass::{earth_mass_to_kg, jupiter_mass_to_kg};
+use crate::astronomy::planet::math::radius::{earth_radius_to_meters, jupiter_radius_to_meters};
+use crate::astronomy::planet::Planet;
+
+pub mod constants;
+use constants::*;
+pub mod kopernicus;
+pub mod orbiter;
+
+/// Options for exporting a system to a game or simulator.
+///
+/// Radii and distances are scaled independently.  Masses are scaled by the
+/// square of the radius scale, which keeps surface gravity where it was.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportOptions {
+  /// Multiplier applied to the radii of all bodies.
+  pub radius_scale: f64,
+  /// Multiplier applied to all orbital distances.
+  pub distance_scale: f64,
+  /// Where the system is, e.g. from `Galaxy::get_location()`; noted in the
+  /// header of the exported files if present.
+  pub location: Option<String>,
+}
+
+impl ExportOptions {
+  /// Scale everything down to roughly the size of the Kerbol system.
+  pub fn kerbal() -> Self {
+    let radius_scale = KERBAL_RADIUS_SCALE;
+    let distance_scale = KERBAL_DISTANCE_SCALE;
+    Self {
+      radius_scale,
+      distance_scale,
+      ..ExportOptions::default()
+    }
+  }
+
+  /// Get the scale applied to masses.
+  #[named]
+  pub fn get_mass_scale(&self) -> f64 {
+    trace_enter!();
+    let result = self.radius_scale.powf(2.0);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl Default for ExportOptions {
+  /// Real scale.
+  fn default() -> Self {
+    let radius_scale = 1.0;
+    let distance_scale = 1.0;
+    let location = None;
+    Self {
+      radius_scale,
+      distance_scale,
+      location,
+    }
+  }
+}
+
+/// A single file produced by an exporter.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ExportedFile {
+  /// A relative path, e.g. `Config/Rigel.cfg`.
+  pub path: String,
+  /// The contents of the file.
+  pub contents: String,
+}
+
+/// Get the mass of a planet, in KG.
+#[named]
+pub fn get_planet_mass_in_kg(planet: &Planet) -> f64 {
+  trace_enter!();
+  use Planet::*;
+  let result = match planet {
+    TerrestrialPlanet(terrestrial_planet) => earth_mass_to_kg(terrestrial_planet.mass),
+    GasGiantPlanet(gas_giant_planet) => jupiter_mass_to_kg(gas_giant_planet.mass),
+    IceGiantPlanet(ice_giant_planet) => earth_mass_to_kg(ice_giant_planet.mass),
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the radius of a planet, in M.
+#[named]
+pub fn get_planet_radius_in_meters(planet: &Planet) -> f64 {
+  trace_enter!();
+  use Planet::*;
+  let result = match planet {
+    TerrestrialPlanet(terrestrial_planet) => earth_radius_to_meters(terrestrial_planet.radius),
+    GasGiantPlanet(gas_giant_planet) => jupiter_radius_to_meters(gas_giant_planet.radius),
+    IceGiantPlanet(ice_giant_planet) => earth_radius_to_meters(ice_giant_planet.radius),
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
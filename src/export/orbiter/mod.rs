@@ -0,0 +1,196 @@
+use std::fmt::Write;
+
+use crate::astronomy::moon::constants::{KG_PER_MOON_MASS, METERS_PER_MOON_RADIUS};
+use crate::astronomy::moon::name::get_systematic_moon_name;
+use crate::astronomy::orbit::OrbitalElements;
+use crate::astronomy::planetary_system::PlanetarySystem;
+use crate::astronomy::star::math::distance::{au_to_meters, meters_to_rsol, rsol_to_meters};
+use crate::astronomy::star::math::mass::msol_to_kg;
+use crate::export::*;
+
+/// The parts of an Orbiter body config that we care about.
+///
+/// Orbiter works in SI units and wants mass rather than surface gravity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Body<'a> {
+  /// The name of the body.
+  pub name: &'a str,
+  /// Mass, in KG.
+  pub mass: f64,
+  /// Radius, in M.
+  pub size: f64,
+  /// The orbit, with the semi-major axis in M.
+  pub orbit: OrbitalElements,
+}
+
+impl Body<'_> {
+  /// Write this body as an Orbiter config file.
+  #[named]
+  pub fn to_file(&self) -> ExportedFile {
+    trace_enter!();
+    trace_var!(self);
+    let mut contents = String::new();
+    writeln!(contents, "; {}, generated by breakwater.", self.name).unwrap();
+    writeln!(contents, "Name = {}", self.name).unwrap();
+    writeln!(contents, "Mass = {:e}", self.mass).unwrap();
+    writeln!(contents, "Size = {:.0}", self.size).unwrap();
+    writeln!(contents).unwrap();
+    writeln!(contents, "; === Orbital elements ===").unwrap();
+    writeln!(contents, "Epoch = 2000").unwrap();
+    // Orbiter wants longitudes, which are measured partly along the
+    // reference plane and partly along the orbit.
+    let longitude_of_periapsis = self.orbit.get_longitude_of_periapsis();
+    trace_var!(longitude_of_periapsis);
+    let mean_longitude = self.orbit.get_mean_longitude();
+    trace_var!(mean_longitude);
+    writeln!(contents, "SemiMajorAxis = {:e}", self.orbit.semi_major_axis).unwrap();
+    writeln!(contents, "Eccentricity = {:.4}", self.orbit.eccentricity).unwrap();
+    writeln!(contents, "Inclination = {:.4}", self.orbit.inclination.to_radians()).unwrap();
+    writeln!(
+      contents,
+      "LongAscNode = {:.4}",
+      self.orbit.longitude_of_ascending_node.to_radians()
+    )
+    .unwrap();
+    writeln!(contents, "LongPerihelion = {:.4}", longitude_of_periapsis.to_radians()).unwrap();
+    writeln!(contents, "MeanLongitude = {:.4}", mean_longitude.to_radians()).unwrap();
+    let path = format!("Config/{}.cfg", self.name);
+    trace_var!(path);
+    let result = ExportedFile { path, contents };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+/// Export a planetary system as a set of Orbiter config files.
+///
+/// This produces a solar system file (`<star> System`) listing the bodies,
+/// plus one file per body.  As with Kopernicus, a close binary is exported as
+/// a single star.
+#[named]
+pub fn export(planetary_system: &PlanetarySystem, options: &ExportOptions) -> Vec<ExportedFile> {
+  trace_enter!();
+  trace_var!(options);
+  let star_name = planetary_system.host_star.get_name();
+  trace_var!(star_name);
+  let (star_mass, star_radius) = {
+    use crate::astronomy::host_star::HostStar::*;
+    match &planetary_system.host_star {
+      Star(star) => (star.mass, star.radius),
+      CloseBinaryStar(close_binary_star) => (close_binary_star.get_stellar_mass(), close_binary_star.primary.radius),
+      StellarRemnant(stellar_remnant) => (stellar_remnant.mass, meters_to_rsol(stellar_remnant.radius * 1_000.0)),
+      BrownDwarf(brown_dwarf) => (brown_dwarf.mass, brown_dwarf.radius),
+    }
+  };
+  trace_var!(star_mass);
+  trace_var!(star_radius);
+  let mass_scale = options.get_mass_scale();
+  trace_var!(mass_scale);
+  let system_name = format!("{} System", star_name);
+  trace_var!(system_name);
+  let mut system = String::new();
+  writeln!(system, "; {}, generated by breakwater.", system_name).unwrap();
+  if let Some(location) = &options.location {
+    writeln!(system, "; Located in {}.", location).unwrap();
+  }
+  writeln!(system, "Name = {}", system_name).unwrap();
+  writeln!(system).unwrap();
+  writeln!(system, "[Planets]").unwrap();
+  writeln!(system, "Star1 = {}", star_name).unwrap();
+  let mut result = Vec::new();
+  result.push(
+    Body {
+      name: &star_name,
+      mass: msol_to_kg(star_mass) * mass_scale,
+      size: rsol_to_meters(star_radius) * options.radius_scale,
+      orbit: OrbitalElements::new(0.0, 0.0),
+    }
+    .to_file(),
+  );
+  let mut moon_lines = String::new();
+  for (index, satellite_system) in planetary_system.satellite_systems.satellite_systems.iter().enumerate() {
+    let planet = &satellite_system.planet;
+    let planet_name = planet.get_name();
+    trace_var!(planet_name);
+    writeln!(system, "Planet{} = {}", index + 1, planet_name).unwrap();
+    result.push(
+      Body {
+        name: &planet_name,
+        mass: get_planet_mass_in_kg(planet) * mass_scale,
+        size: get_planet_radius_in_meters(planet) * options.radius_scale,
+        orbit: OrbitalElements {
+          semi_major_axis: au_to_meters(planet.get_semi_major_axis()) * options.distance_scale,
+          ..planet.get_orbit()
+        },
+      }
+      .to_file(),
+    );
+    for (moon_index, moon) in satellite_system.moons.moons.iter().enumerate() {
+      let moon_name = get_systematic_moon_name(&planet_name, moon_index);
+      trace_var!(moon_name);
+      writeln!(moon_lines, "{}:Moon{} = {}", planet_name, moon_index + 1, moon_name).unwrap();
+      result.push(
+        Body {
+          name: &moon_name,
+          mass: moon.mass * KG_PER_MOON_MASS * mass_scale,
+          size: moon.radius * METERS_PER_MOON_RADIUS * options.radius_scale,
+          orbit: OrbitalElements {
+            semi_major_axis: moon.orbit.semi_major_axis * 1000.0 * options.distance_scale,
+            ..moon.orbit
+          },
+        }
+        .to_file(),
+      );
+    }
+  }
+  system.push_str(&moon_lines);
+  let path = format!("Config/{}.cfg", system_name);
+  trace_var!(path);
+  result.insert(0, ExportedFile { path, contents: system });
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::planetary_system::constraints::Constraints;
+  use crate::astronomy::planetary_system::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_export() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let planetary_system = Constraints::habitable().generate(&mut rng)?;
+    let options = ExportOptions {
+      location: Some("Tarsus Reach, Cassoria Whirlpool (NGC 4414)".to_string()),
+      ..ExportOptions::default()
+    };
+    let files = export(&planetary_system, &options);
+    print_var!(files);
+    let planet_count = planetary_system.satellite_systems.satellite_systems.len();
+    let moon_count: usize = planetary_system
+      .satellite_systems
+      .satellite_systems
+      .iter()
+      .map(|satellite_system| satellite_system.moons.moons.len())
+      .sum();
+    assert_eq!(files.len(), 2 + planet_count + moon_count);
+    assert!(files[0].contents.contains("Star1 = "));
+    assert!(files[0]
+      .contents
+      .contains("; Located in Tarsus Reach, Cassoria Whirlpool (NGC 4414)."));
+    assert!(files.iter().all(|file| file.path.starts_with("Config/")));
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,229 @@
+use honeyholt::r#trait::brief::Brief;
+use rand::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, write};
+use std::io::Result as IoResult;
+use std::path::Path;
+
+use crate::export::ExportedFile;
+use crate::generator::{seeded_rng, Generator};
+
+/// The name of the manifest file written alongside a batch.
+pub const MANIFEST_PATH: &str = "manifest.json";
+
+/// A record of one item in a batch.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct ManifestEntry {
+  /// The position of the item in the batch.
+  pub index: usize,
+  /// The seed that generates this item on its own.
+  pub seed: u64,
+  /// What went wrong, if generation failed.
+  pub error: Option<String>,
+  /// Where the item was written, if it was.
+  pub path: Option<String>,
+}
+
+/// A record of a whole batch, enough to regenerate any item in it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Manifest {
+  /// The seed the batch was generated from.
+  pub seed: u64,
+  /// The number of items requested.
+  pub count: usize,
+  /// The number of items generated successfully.
+  pub succeeded: usize,
+  /// The number of items that failed.
+  pub failed: usize,
+  /// How many times each error occurred.
+  pub failures: BTreeMap<String, usize>,
+  /// The items themselves.
+  pub entries: Vec<ManifestEntry>,
+}
+
+/// The output of `generate_batch()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Batch<T> {
+  /// The objects, in the same order as the manifest's entries; `None` where
+  /// generation failed.
+  pub objects: Vec<Option<T>>,
+  /// The manifest.
+  pub manifest: Manifest,
+}
+
+/// Generate a batch of objects from a single seed.
+///
+/// Each item gets its own seed, drawn from the batch's, so any one of them
+/// can be regenerated with `generate_from_seed()` without the rest.  Failed
+/// items are recorded in the manifest rather than ending the batch.
+#[named]
+pub fn generate_batch<G>(count: usize, generator: &G, seed: u64) -> Batch<G::Output>
+where
+  G: Generator,
+  G::Error: Brief,
+{
+  trace_enter!();
+  trace_var!(count);
+  trace_var!(seed);
+  let mut rng = seeded_rng(seed);
+  let mut objects = Vec::with_capacity(count);
+  let mut entries = Vec::with_capacity(count);
+  let mut failures = BTreeMap::new();
+  for index in 0..count {
+    let item_seed: u64 = rng.gen();
+    trace_var!(item_seed);
+    let (object, error) = match generator.generate_from_seed(item_seed) {
+      Ok(object) => (Some(object), None),
+      Err(error) => {
+        let error = honeyholt_brief!(error);
+        *failures.entry(error.clone()).or_insert(0) += 1;
+        (None, Some(error))
+      },
+    };
+    trace_var!(error);
+    objects.push(object);
+    entries.push(ManifestEntry {
+      index,
+      seed: item_seed,
+      error,
+      path: None,
+    });
+  }
+  let failed = failures.values().sum();
+  trace_var!(failed);
+  let manifest = Manifest {
+    seed,
+    count,
+    succeeded: count - failed,
+    failed,
+    failures,
+    entries,
+  };
+  trace_var!(manifest);
+  let result = Batch { objects, manifest };
+  trace_exit!();
+  result
+}
+
+impl<T: Serialize> Batch<T> {
+  /// Render each object as a JSON file, plus the manifest.
+  ///
+  /// The naming scheme may include `{index}` and `{seed}`, e.g.
+  /// `systems/{index}-{seed}.json`; the manifest records each path.
+  #[named]
+  pub fn to_files(&self, naming: &str) -> IoResult<Vec<ExportedFile>> {
+    trace_enter!();
+    trace_var!(naming);
+    let mut manifest = self.manifest.clone();
+    let mut result = vec![];
+    for (entry, object) in manifest.entries.iter_mut().zip(self.objects.iter()) {
+      if let Some(object) = object {
+        let path = naming
+          .replace("{index}", &entry.index.to_string())
+          .replace("{seed}", &entry.seed.to_string());
+        let contents = serde_json::to_string_pretty(object)?;
+        entry.path = Some(path.clone());
+        result.push(ExportedFile { path, contents });
+      }
+    }
+    let contents = serde_json::to_string_pretty(&manifest)?;
+    result.push(ExportedFile {
+      path: MANIFEST_PATH.to_string(),
+      contents,
+    });
+    trace_exit!();
+    Ok(result)
+  }
+
+  /// Write each object and the manifest into a directory.
+  #[named]
+  pub fn write_to<P: AsRef<Path>>(&self, directory: P, naming: &str) -> IoResult<()> {
+    trace_enter!();
+    for file in self.to_files(naming)? {
+      let path = directory.as_ref().join(&file.path);
+      trace_var!(path);
+      if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+      }
+      write(path, file.contents)?;
+    }
+    trace_exit!();
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::astronomy::star::constraints::Constraints as StarConstraints;
+  use crate::astronomy::star::error::Error as StarError;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_generate_batch() -> IoResult<()> {
+    init();
+    trace_enter!();
+    let constraints = StarConstraints::default();
+    let batch = generate_batch(4, &constraints, 42);
+    print_var!(batch.manifest);
+    assert_eq!(batch.objects.len(), 4);
+    assert_eq!(batch.manifest.succeeded, 4);
+    assert_eq!(batch, generate_batch(4, &constraints, 42));
+    let entry = &batch.manifest.entries[2];
+    assert_eq!(
+      batch.objects[2].as_ref(),
+      constraints.generate_from_seed(entry.seed).ok().as_ref()
+    );
+    let files = batch.to_files("stars/{index}-{seed}.json")?;
+    assert_eq!(files.len(), 5);
+    assert_eq!(
+      files[0].path,
+      format!("stars/0-{}.json", batch.manifest.entries[0].seed)
+    );
+    assert_eq!(files[4].path, MANIFEST_PATH);
+    assert!(files[4].contents.contains(&files[3].path));
+    let directory = std::env::temp_dir().join(format!("breakwater-batch-{}", std::process::id()));
+    batch.write_to(&directory, "stars/{index}.json")?;
+    assert!(directory.join("stars/3.json").exists());
+    assert!(directory.join(MANIFEST_PATH).exists());
+    std::fs::remove_dir_all(&directory)?;
+    trace_exit!();
+    Ok(())
+  }
+
+  /// Fails on odd seeds.
+  pub struct Fussy;
+
+  impl Generator for Fussy {
+    type Output = u64;
+    type Error = StarError;
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<u64, StarError> {
+      match rng.gen::<u64>() {
+        value if value % 2 == 0 => Ok(value),
+        _ => Err(StarError::TooYoungToSupportLife),
+      }
+    }
+  }
+
+  #[named]
+  #[test]
+  pub fn test_failures() {
+    init();
+    trace_enter!();
+    let batch = generate_batch(20, &Fussy, 7);
+    print_var!(batch.manifest);
+    assert_eq!(batch.manifest.succeeded + batch.manifest.failed, 20);
+    assert!(batch.manifest.failed > 0);
+    assert_eq!(batch.manifest.failures.values().sum::<usize>(), batch.manifest.failed);
+    for (entry, object) in batch.manifest.entries.iter().zip(batch.objects.iter()) {
+      assert_eq!(entry.error.is_some(), object.is_none());
+    }
+    let files = batch.to_files("{index}.json").unwrap();
+    assert_eq!(files.len(), batch.manifest.succeeded + 1);
+    trace_exit!();
+  }
+}
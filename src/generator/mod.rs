@@ -0,0 +1,195 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+pub mod batch;
+
+/// Build a deterministic RNG from a seed.
+///
+/// ChaCha is specified independently of platform and of `rand`'s choice of
+/// `StdRng`, so the same seed produces the same stream everywhere.
+#[named]
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+  trace_enter!();
+  trace_var!(seed);
+  let result = ChaCha8Rng::seed_from_u64(seed);
+  trace_exit!();
+  result
+}
+
+/// Something that can generate an object from scratch, given only an RNG.
+///
+/// This is implemented by the constraints of every object that doesn't need
+/// to be told about its surroundings; planets and moons, for instance, need a
+/// host star, so their constraints aren't generators.
+pub trait Generator {
+  /// The type of object generated.
+  type Output;
+  /// The type of error that may occur while generating.
+  type Error;
+
+  /// Generate.
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Self::Output, Self::Error>;
+
+  /// Generate reproducibly; the same seed always yields the same object.
+  fn generate_from_seed(&self, seed: u64) -> Result<Self::Output, Self::Error> {
+    self.generate(&mut seeded_rng(seed))
+  }
+}
+
+/// An algorithm for generating objects of some type.
+///
+/// Every generator is a strategy for whatever it generates, so the built-in
+/// constraints work anywhere a strategy is expected.  Implement this to swap
+/// in a different algorithm -- table-driven, say, or hand-curated -- for the
+/// stars, planetary systems, or stellar neighborhoods that the rest of the
+/// crate builds around.
+pub trait GenerationStrategy<T> {
+  /// The type of error that may occur while generating.
+  type Error;
+
+  /// Generate.
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<T, Self::Error>;
+}
+
+impl<G: Generator> GenerationStrategy<G::Output> for G {
+  type Error = G::Error;
+
+  fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<G::Output, G::Error> {
+    Generator::generate(self, rng)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::host_star::error::Error as HostStarError;
+  use crate::astronomy::host_star::HostStar;
+  use crate::astronomy::planetary_system::constraints::Constraints as PlanetarySystemConstraints;
+  use crate::astronomy::planetary_system::error::Error as PlanetarySystemError;
+  use crate::astronomy::planetary_system::PlanetarySystem;
+  use crate::astronomy::satellite_systems::constraints::Constraints as SatelliteSystemsConstraints;
+  use crate::astronomy::satellite_systems::SatelliteSystems;
+  use crate::astronomy::star::constraints::Constraints as StarConstraints;
+  use crate::astronomy::star::error::Error as StarError;
+  use crate::astronomy::star::Star;
+  use crate::astronomy::star_subsystem::constraints::Constraints as StarSubsystemConstraints;
+  use crate::astronomy::star_subsystem::error::Error as StarSubsystemError;
+  use crate::astronomy::star_subsystem::StarSubsystem;
+  use crate::astronomy::star_system::constraints::Constraints as StarSystemConstraints;
+  use crate::astronomy::star_system::error::Error as StarSystemError;
+  use crate::math::probability::Probability;
+  use crate::test::*;
+
+  /// Always a twin of the Sun.
+  pub struct Sunlike;
+
+  impl GenerationStrategy<Star> for Sunlike {
+    type Error = StarError;
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Star, StarError> {
+      Star::from_mass(rng, 1.0)
+    }
+  }
+
+  /// Always a lone Sun-like star with no planets.
+  pub struct Barren;
+
+  impl GenerationStrategy<PlanetarySystem> for Barren {
+    type Error = PlanetarySystemError;
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<PlanetarySystem, PlanetarySystemError> {
+      let star = Sunlike.generate(rng).map_err(HostStarError::from)?;
+      let host_star = HostStar::Star(star);
+      let satellite_systems = SatelliteSystems {
+        satellite_systems: vec![],
+      };
+      Ok(PlanetarySystem {
+        host_star,
+        satellite_systems,
+        asteroid_belt: None,
+        zodiacal_dust: None,
+        kuiper_belt: None,
+        oort_cloud: None,
+      })
+    }
+  }
+
+  #[named]
+  pub fn generate_several<G: Generator, R: Rng + ?Sized>(
+    generator: &G,
+    rng: &mut R,
+    count: usize,
+  ) -> Result<Vec<G::Output>, G::Error> {
+    trace_enter!();
+    let result = (0..count).map(|_| generator.generate(rng)).collect();
+    trace_exit!();
+    result
+  }
+
+  #[named]
+  #[test]
+  pub fn test_generate_several() -> Result<(), StarSystemError> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let stars = generate_several(&StarConstraints::default(), &mut rng, 3)?;
+    assert_eq!(stars.len(), 3);
+    let star_systems = generate_several(&StarSystemConstraints::habitable(), &mut rng, 2)?;
+    print_var!(star_systems);
+    assert_eq!(star_systems.len(), 2);
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_generate_from_seed() -> Result<(), StarSystemError> {
+    init();
+    trace_enter!();
+    let constraints = StarSystemConstraints::default();
+    let first = constraints.generate_from_seed(42)?;
+    let second = constraints.generate_from_seed(42)?;
+    print_var!(first);
+    assert_eq!(first, second);
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_generation_strategy() -> Result<(), StarSystemError> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let planetary_system = PlanetarySystemConstraints::default()
+      .generate_with_star_strategy(&mut rng, &Sunlike)
+      .map_err(StarSubsystemError::from)?;
+    print_var!(planetary_system);
+    assert_eq!(planetary_system.get_stellar_mass(), 1.0);
+    let constraints = StarSystemConstraints {
+      star_subsystem_constraints: Some(StarSubsystemConstraints {
+        distant_binary_probability: Some(Probability::NEVER),
+        ..StarSubsystemConstraints::default()
+      }),
+      ..StarSystemConstraints::default()
+    };
+    let star_system = constraints.generate_with_planetary_system_strategy(&mut rng, &Barren)?;
+    print_var!(star_system);
+    match star_system.star_subsystem {
+      StarSubsystem::PlanetarySystem(planetary_system) => {
+        assert!(planetary_system.satellite_systems.satellite_systems.is_empty())
+      },
+      _ => panic!("expected a planetary system"),
+    }
+    // The built-in constraints are strategies too.
+    let star = GenerationStrategy::generate(&StarConstraints::default(), &mut rng)?;
+    print_var!(star);
+    trace_exit!();
+    Ok(())
+  }
+}
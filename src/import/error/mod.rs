@@ -0,0 +1,101 @@
+use crate::astronomy::gas_giant_planet::error::Error as GasGiantPlanetError;
+use crate::astronomy::ice_giant_planet::error::Error as IceGiantPlanetError;
+use crate::astronomy::moons::error::Error as MoonsError;
+use crate::astronomy::satellite_systems::error::Error as SatelliteSystemsError;
+use crate::astronomy::star::error::Error as StarError;
+use crate::astronomy::terrestrial_planet::error::Error as TerrestrialPlanetError;
+
+/// Import-related errors.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+  /// The sketch could not be parsed.
+  ParseError(String),
+  /// The spectral class was not one of OBAFGKM.
+  UnknownSpectralClass(String),
+  /// We ran out of orbits for planets without a specified distance.
+  NoOrbitsAvailable,
+  /// A planet's distance was not a positive, finite number.
+  InvalidDistance,
+  /// Gas Giant Planet Error.
+  GasGiantPlanetError(GasGiantPlanetError),
+  /// Ice Giant Planet Error.
+  IceGiantPlanetError(IceGiantPlanetError),
+  /// Moons Error.
+  MoonsError(MoonsError),
+  /// Satellite Systems Error.
+  SatelliteSystemsError(SatelliteSystemsError),
+  /// Star Error.
+  StarError(StarError),
+  /// Terrestrial Planet Error.
+  TerrestrialPlanetError(TerrestrialPlanetError),
+}
+
+honeyholt_define_brief!(Error, |error: &Error| {
+  use Error::*;
+  match error {
+    ParseError(message) => format!("the sketch could not be parsed ({})", message),
+    UnknownSpectralClass(class) => format!("the spectral class {:?} is not recognized", class),
+    NoOrbitsAvailable => "no orbits were left for planets without a distance".to_string(),
+    InvalidDistance => "a planet's distance is not a positive number".to_string(),
+    GasGiantPlanetError(gas_giant_planet_error) => format!(
+      "an error occurred in the gas giant planet ({})",
+      honeyholt_brief!(gas_giant_planet_error)
+    ),
+    IceGiantPlanetError(ice_giant_planet_error) => format!(
+      "an error occurred in the ice giant planet ({})",
+      honeyholt_brief!(ice_giant_planet_error)
+    ),
+    MoonsError(moons_error) => format!("an error occurred in the moons ({})", honeyholt_brief!(moons_error)),
+    SatelliteSystemsError(satellite_systems_error) => format!(
+      "an error occurred in the satellite systems ({})",
+      honeyholt_brief!(satellite_systems_error)
+    ),
+    StarError(star_error) => format!("an error occurred in the star ({})", honeyholt_brief!(star_error)),
+    TerrestrialPlanetError(terrestrial_planet_error) => format!(
+      "an error occurred in the terrestrial planet ({})",
+      honeyholt_brief!(terrestrial_planet_error)
+    ),
+  }
+});
+
+impl From<GasGiantPlanetError> for Error {
+  #[named]
+  fn from(error: GasGiantPlanetError) -> Self {
+    Error::GasGiantPlanetError(error)
+  }
+}
+
+impl From<IceGiantPlanetError> for Error {
+  #[named]
+  fn from(error: IceGiantPlanetError) -> Self {
+    Error::IceGiantPlanetError(error)
+  }
+}
+
+impl From<MoonsError> for Error {
+  #[named]
+  fn from(error: MoonsError) -> Self {
+    Error::MoonsError(error)
+  }
+}
+
+impl From<SatelliteSystemsError> for Error {
+  #[named]
+  fn from(error: SatelliteSystemsError) -> Self {
+    Error::SatelliteSystemsError(error)
+  }
+}
+
+impl From<StarError> for Error {
+  #[named]
+  fn from(error: StarError) -> Self {
+    Error::StarError(error)
+  }
+}
+
+impl From<TerrestrialPlanetError> for Error {
+  #[named]
+  fn from(error: TerrestrialPlanetError) -> Self {
+    Error::TerrestrialPlanetError(error)
+  }
+}
@@ -0,0 +1,79 @@
+use rand::prelude::*;
+
+use crate::astronomy::planetary_system::PlanetarySystem;
+
+pub mod error;
+use error::Error;
+pub mod sketch;
+use sketch::SystemSketch;
+
+/// Parse a hand-written sketch of a system.
+///
+/// JSON is tried first if the input looks like it; anything else is read as
+/// YAML.  Unknown fields are ignored, so sketches can carry notes.
+#[named]
+pub fn parse_sketch(input: &str) -> Result<SystemSketch, Error> {
+  trace_enter!();
+  let result = match input.trim_start().starts_with('{') {
+    true => serde_json::from_str(input).map_err(|error| Error::ParseError(error.to_string())),
+    false => serde_yaml::from_str(input).map_err(|error| Error::ParseError(error.to_string())),
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Parse a hand-written sketch and complete it into a full system.
+#[named]
+pub fn import<R: Rng + ?Sized>(rng: &mut R, input: &str) -> Result<PlanetarySystem, Error> {
+  trace_enter!();
+  let sketch = parse_sketch(input)?;
+  trace_var!(sketch);
+  let result = sketch.complete(rng)?;
+  trace_var!(result);
+  trace_exit!();
+  Ok(result)
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::sketch::PlanetKind;
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_parse_sketch() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let json = r#"{ "star": { "class": "K" }, "planets": [ { "kind": "gas_giant", "mass": 2.0 } ] }"#;
+    let yaml = "star:\n  class: K\nplanets:\n  - kind: gas_giant\n    mass: 2.0\n";
+    let from_json = parse_sketch(json)?;
+    print_var!(from_json);
+    assert_eq!(from_json, parse_sketch(yaml)?);
+    assert_eq!(from_json.planets[0].kind, Some(PlanetKind::GasGiant));
+    assert!(parse_sketch("star: [").is_err());
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_import() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let planetary_system = import(
+      &mut rng,
+      "star:\n  mass: 1.0\nplanets:\n  - distance: 1.0\n  - {}\n  - {}\n",
+    )?;
+    print_var!(planetary_system);
+    assert_eq!(planetary_system.satellite_systems.satellite_systems.len(), 3);
+    trace_exit!();
+    Ok(())
+  }
+}
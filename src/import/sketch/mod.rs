@@ -0,0 +1,359 @@
+use rand::prelude::*;
+use serde::Deserialize;
+
+use crate::astronomy::asteroid_belt::constraints::Constraints as AsteroidBeltConstraints;
+use crate::astronomy::gas_giant_planet::constraints::Constraints as GasGiantPlanetConstraints;
+use crate::astronomy::host_star::HostStar;
+use crate::astronomy::ice_giant_planet::constraints::Constraints as IceGiantPlanetConstraints;
+use crate::astronomy::kuiper_belt::constraints::Constraints as KuiperBeltConstraints;
+use crate::astronomy::moons::constraints::Constraints as MoonsConstraints;
+use crate::astronomy::oort_cloud::constraints::Constraints as OortCloudConstraints;
+use crate::astronomy::planet::name::PlanetNaming;
+use crate::astronomy::planet::Planet;
+use crate::astronomy::planetary_system::PlanetarySystem;
+use crate::astronomy::ring_system::constraints::Constraints as RingSystemConstraints;
+use crate::astronomy::satellite_system::SatelliteSystem;
+use crate::astronomy::satellite_systems::constraints::Constraints as SatelliteSystemsConstraints;
+use crate::astronomy::satellite_systems::SatelliteSystems;
+use crate::astronomy::star::constraints::Constraints as StarConstraints;
+use crate::astronomy::star::math::spectral_class::spectral_class_to_mass_range;
+use crate::astronomy::star::Star;
+use crate::astronomy::terrestrial_planet::constraints::Constraints as TerrestrialPlanetConstraints;
+use crate::astronomy::trojan_swarm::constraints::Constraints as TrojanSwarmConstraints;
+use crate::astronomy::zodiacal_dust::ZodiacalDust;
+use crate::import::error::Error;
+
+/// The kind of planet described by a sketch.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanetKind {
+  /// A terrestrial planet.
+  Terrestrial,
+  /// A gas giant.
+  GasGiant,
+  /// An ice giant.
+  IceGiant,
+}
+
+/// A hand-written description of a star.
+///
+/// Every field is optional; whatever is missing is generated.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct StarSketch {
+  /// A name for the star.
+  pub name: Option<String>,
+  /// A spectral class, e.g. "G2V"; only the letter is significant.
+  pub class: Option<String>,
+  /// Mass, in Msol.  Takes precedence over the class.
+  pub mass: Option<f64>,
+  /// Age, in Gyr.
+  pub age: Option<f64>,
+}
+
+/// A hand-written description of a planet.
+///
+/// Every field is optional; whatever is missing is generated.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PlanetSketch {
+  /// Terrestrial, gas giant, or ice giant; inferred from the frost line if
+  /// missing.
+  pub kind: Option<PlanetKind>,
+  /// Mass, in Mjupiter for gas giants and Mearth for everything else.
+  pub mass: Option<f64>,
+  /// Semi-major axis, in AU.
+  pub distance: Option<f64>,
+  /// Orbital eccentricity.
+  pub eccentricity: Option<f64>,
+  /// Axial tilt, in degrees.  Ignored for ice giants.
+  pub axial_tilt: Option<f64>,
+  /// Rotation period, in Dearth.
+  pub rotation_period: Option<f64>,
+}
+
+/// A hand-written description of a planetary system.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SystemSketch {
+  /// The host star.
+  pub star: StarSketch,
+  /// The planets, in any order.
+  pub planets: Vec<PlanetSketch>,
+}
+
+impl StarSketch {
+  /// Complete this sketch into a full star.
+  #[named]
+  pub fn complete<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Star, Error> {
+    trace_enter!();
+    trace_var!(self);
+    let mut result = match (self.mass, &self.class) {
+      (Some(mass), _) => Star::from_mass(rng, mass)?,
+      (None, Some(class)) => {
+        let letter = class
+          .chars()
+          .next()
+          .filter(|letter| "OBAFGKM".contains(letter.to_ascii_uppercase()))
+          .ok_or_else(|| Error::UnknownSpectralClass(class.clone()))?;
+        trace_var!(letter);
+        let mass_range = spectral_class_to_mass_range(letter);
+        trace_var!(mass_range);
+        let mass = rng.gen_range(mass_range);
+        trace_var!(mass);
+        Star::from_mass(rng, mass)?
+      },
+      (None, None) => StarConstraints::default().generate(rng)?,
+    };
+    if let Some(age) = self.age {
+      result.current_age = age;
+    }
+    if let Some(name) = &self.name {
+      result.name = name.clone();
+    }
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+}
+
+impl PlanetSketch {
+  /// Complete this sketch into a full planet at the specified distance.
+  #[named]
+  pub fn complete<R: Rng + ?Sized>(&self, rng: &mut R, host_star: &HostStar, distance: f64) -> Result<Planet, Error> {
+    trace_enter!();
+    trace_var!(self);
+    trace_var!(distance);
+    let kind = self.kind.unwrap_or(match distance >= host_star.get_frost_line() {
+      true => PlanetKind::GasGiant,
+      false => PlanetKind::Terrestrial,
+    });
+    trace_var!(kind);
+    let mut result = match kind {
+      PlanetKind::Terrestrial => {
+        let constraints = TerrestrialPlanetConstraints {
+          minimum_mass: self.mass,
+          maximum_mass: self.mass,
+          minimum_axial_tilt: self.axial_tilt,
+          maximum_axial_tilt: self.axial_tilt,
+          minimum_rotational_period: self.rotation_period,
+          maximum_rotational_period: self.rotation_period,
+          ..TerrestrialPlanetConstraints::default()
+        };
+        trace_var!(constraints);
+        Planet::TerrestrialPlanet(constraints.generate(rng, host_star, distance)?)
+      },
+      PlanetKind::GasGiant => {
+        let constraints = GasGiantPlanetConstraints {
+          minimum_mass: self.mass,
+          maximum_mass: self.mass,
+          minimum_rotational_period: self.rotation_period,
+          maximum_rotational_period: self.rotation_period,
+          minimum_axial_tilt: self.axial_tilt,
+          maximum_axial_tilt: self.axial_tilt,
+          ..GasGiantPlanetConstraints::default()
+        };
+        trace_var!(constraints);
+        Planet::GasGiantPlanet(constraints.generate(rng, host_star, distance)?)
+      },
+      PlanetKind::IceGiant => {
+        let constraints = IceGiantPlanetConstraints {
+          minimum_mass: self.mass,
+          maximum_mass: self.mass,
+          minimum_rotational_period: self.rotation_period,
+          maximum_rotational_period: self.rotation_period,
+        };
+        trace_var!(constraints);
+        Planet::IceGiantPlanet(constraints.generate(rng, host_star, distance)?)
+      },
+    };
+    if let Some(eccentricity) = self.eccentricity {
+      use Planet::*;
+      match &mut result {
+        TerrestrialPlanet(terrestrial_planet) => terrestrial_planet.orbit.eccentricity = eccentricity,
+        GasGiantPlanet(gas_giant_planet) => gas_giant_planet.orbit.eccentricity = eccentricity,
+        IceGiantPlanet(ice_giant_planet) => ice_giant_planet.orbit.eccentricity = eccentricity,
+      }
+    }
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+}
+
+impl SystemSketch {
+  /// Complete this sketch into a full planetary system.
+  ///
+  /// Planets without a distance are assigned orbits the usual way, skipping
+  /// any that would crowd a planet whose distance was specified.
+  #[named]
+  pub fn complete<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<PlanetarySystem, Error> {
+    trace_enter!();
+    trace_var!(self);
+    let host_star = HostStar::Star(self.star.complete(rng)?);
+    trace_var!(host_star);
+    let distances: Vec<f64> = self.planets.iter().filter_map(|planet| planet.distance).collect();
+    trace_var!(distances);
+    if distances
+      .iter()
+      .any(|distance| !distance.is_finite() || *distance <= 0.0)
+    {
+      return Err(Error::InvalidDistance);
+    }
+    let mut orbits = SatelliteSystemsConstraints::default()
+      .generate_orbits(rng, &host_star)?
+      .into_iter()
+      .filter(|orbit| {
+        !distances
+          .iter()
+          .any(|distance| *orbit > 0.8 * distance && *orbit < 1.25 * distance)
+      });
+    let mut sketches = Vec::new();
+    for planet in self.planets.iter() {
+      let distance = match planet.distance {
+        Some(distance) => distance,
+        None => orbits.next().ok_or(Error::NoOrbitsAvailable)?,
+      };
+      sketches.push((distance, planet));
+    }
+    sketches.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut satellite_systems = Vec::new();
+    for (distance, sketch) in sketches.into_iter() {
+      let planet = sketch.complete(rng, &host_star, distance)?;
+      trace_var!(planet);
+      let moons = MoonsConstraints::default().generate(rng, &host_star, distance, &planet)?;
+      trace_var!(moons);
+      let ring_system = RingSystemConstraints::default().generate(rng, &host_star, distance, &planet);
+      trace_var!(ring_system);
+      let trojan_swarms = TrojanSwarmConstraints::default().generate(rng, &host_star, &planet);
+      trace_var!(trojan_swarms);
+      satellite_systems.push(SatelliteSystem {
+        planet,
+        moons,
+        ring_system,
+        trojan_swarms,
+      });
+    }
+    let mut satellite_systems = SatelliteSystems { satellite_systems };
+    satellite_systems.name_planets(rng, &host_star.get_name(), PlanetNaming::Systematic);
+    trace_var!(satellite_systems);
+    let asteroid_belt = AsteroidBeltConstraints::default().generate(rng, &host_star, &satellite_systems);
+    trace_var!(asteroid_belt);
+    let zodiacal_dust = asteroid_belt.as_ref().map(|asteroid_belt| {
+      ZodiacalDust::from_belt(
+        asteroid_belt,
+        host_star.get_luminosity(),
+        &satellite_systems.get_semi_major_axes(),
+      )
+    });
+    trace_var!(zodiacal_dust);
+    let kuiper_belt = KuiperBeltConstraints::default().generate(rng, &host_star, &satellite_systems);
+    trace_var!(kuiper_belt);
+    let oort_cloud = OortCloudConstraints::default().generate(rng, &host_star);
+    trace_var!(oort_cloud);
+    let result = PlanetarySystem {
+      host_star,
+      satellite_systems,
+      asteroid_belt,
+      zodiacal_dust,
+      kuiper_belt,
+      oort_cloud,
+    };
+    trace_var!(result);
+    trace_exit!();
+    Ok(result)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_complete() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let sketch = SystemSketch {
+      star: StarSketch {
+        name: Some("Tarantula".to_string()),
+        class: Some("G2V".to_string()),
+        ..StarSketch::default()
+      },
+      planets: vec![
+        PlanetSketch {
+          kind: Some(PlanetKind::Terrestrial),
+          mass: Some(1.0),
+          distance: Some(1.0),
+          eccentricity: Some(0.1),
+          ..PlanetSketch::default()
+        },
+        PlanetSketch::default(),
+      ],
+    };
+    let planetary_system = sketch.complete(&mut rng)?;
+    print_var!(planetary_system);
+    assert_eq!(planetary_system.host_star.get_name(), "Tarantula");
+    let satellite_systems = &planetary_system.satellite_systems.satellite_systems;
+    assert_eq!(satellite_systems.len(), 2);
+    let planet = satellite_systems
+      .iter()
+      .map(|satellite_system| &satellite_system.planet)
+      .find(|planet| planet.get_semi_major_axis() == 1.0)
+      .unwrap();
+    match planet {
+      Planet::TerrestrialPlanet(terrestrial_planet) => {
+        assert_approx_eq!(terrestrial_planet.mass, 1.0);
+        assert_approx_eq!(terrestrial_planet.orbit.get_apoapsis(), 1.1);
+      },
+      _ => panic!("expected a terrestrial planet"),
+    }
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_invalid_distance() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    for distance in [f64::NAN, f64::INFINITY, 0.0, -1.0] {
+      let sketch = SystemSketch {
+        planets: vec![
+          PlanetSketch::default(),
+          PlanetSketch {
+            distance: Some(distance),
+            ..PlanetSketch::default()
+          },
+        ],
+        ..SystemSketch::default()
+      };
+      assert_eq!(sketch.complete(&mut rng), Err(Error::InvalidDistance));
+    }
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_unknown_spectral_class() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    let sketch = StarSketch {
+      class: Some("Q".to_string()),
+      ..StarSketch::default()
+    };
+    assert_eq!(
+      sketch.complete(&mut rng),
+      Err(Error::UnknownSpectralClass("Q".to_string()))
+    );
+    trace_exit!();
+  }
+}
@@ -4,18 +4,30 @@
 #[macro_use]
 extern crate assert_approx_eq;
 
+// These are re-exported so the examples can share our logging macros; they
+// are not part of the stable API, which lives in the prelude.
 #[macro_use]
 extern crate honeyholt;
+#[doc(hidden)]
 pub use honeyholt::*;
 
 #[macro_use]
 extern crate volmark;
+#[doc(hidden)]
 pub use volmark::*;
 
 pub mod astronomy;
+pub mod diagnostics;
+pub mod export;
+pub mod generator;
+pub mod import;
+pub(crate) mod math;
+pub mod merge;
+pub mod observables;
+pub mod prelude;
 
 #[cfg(test)]
-pub mod test {
+pub(crate) mod test {
 
   use std::env::set_var;
 
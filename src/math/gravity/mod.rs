@@ -0,0 +1,175 @@
+/// The coefficient of the Roche limit for a rigid satellite.
+///
+/// A solid body holds itself together with more than its own gravity, so it
+/// can come in closer than a rubble pile before it breaks up.
+pub const RIGID_ROCHE_COEFFICIENT: f64 = 1.26;
+
+/// The coefficient of the Roche limit for a fluid satellite.
+///
+/// A body held together by its own gravity alone -- a rubble pile, or a big
+/// moon in hydrostatic equilibrium -- stretches out and comes apart here.
+pub const FLUID_ROCHE_COEFFICIENT: f64 = 2.44;
+
+/// Get the Hill radius of a body at its closest approach to its primary.
+///
+/// Inside this, the body's gravity dominates the primary's, so it's where
+/// its own satellites can orbit.  The result is in the same units as the
+/// semi-major axis, and the masses just need to share units.
+#[named]
+pub fn get_hill_radius(semi_major_axis: f64, orbital_eccentricity: f64, mass: f64, primary_mass: f64) -> f64 {
+  trace_enter!();
+  trace_var!(semi_major_axis);
+  trace_var!(orbital_eccentricity);
+  trace_var!(mass);
+  trace_var!(primary_mass);
+  let periapsis = semi_major_axis * (1.0 - orbital_eccentricity);
+  trace_var!(periapsis);
+  let result = periapsis * (mass / (3.0 * primary_mass)).powf(1.0 / 3.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the mutual Hill radius of two bodies orbiting the same primary.
+///
+/// Neighbors need to be a few of these apart to keep from scattering each
+/// other.  The result is in the same units as the semi-major axes, and the
+/// masses just need to share units.
+#[named]
+pub fn get_mutual_hill_radius(
+  first_semi_major_axis: f64,
+  second_semi_major_axis: f64,
+  first_mass: f64,
+  second_mass: f64,
+  primary_mass: f64,
+) -> f64 {
+  trace_enter!();
+  trace_var!(first_semi_major_axis);
+  trace_var!(second_semi_major_axis);
+  trace_var!(first_mass);
+  trace_var!(second_mass);
+  trace_var!(primary_mass);
+  let mean_semi_major_axis = (first_semi_major_axis + second_semi_major_axis) / 2.0;
+  trace_var!(mean_semi_major_axis);
+  let result = mean_semi_major_axis * ((first_mass + second_mass) / (3.0 * primary_mass)).powf(1.0 / 3.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the Roche limit of a body for a rigid satellite.
+///
+/// The result is in the same units as the radius, and the densities just
+/// need to share units.
+#[named]
+pub fn get_rigid_roche_limit(radius: f64, density: f64, satellite_density: f64) -> f64 {
+  trace_enter!();
+  trace_var!(radius);
+  trace_var!(density);
+  trace_var!(satellite_density);
+  let result = RIGID_ROCHE_COEFFICIENT * radius * (density / satellite_density).powf(1.0 / 3.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the Roche limit of a body for a fluid satellite.
+///
+/// The result is in the same units as the radius, and the densities just
+/// need to share units.
+#[named]
+pub fn get_fluid_roche_limit(radius: f64, density: f64, satellite_density: f64) -> f64 {
+  trace_enter!();
+  trace_var!(radius);
+  trace_var!(density);
+  trace_var!(satellite_density);
+  let result = FLUID_ROCHE_COEFFICIENT * radius * (density / satellite_density).powf(1.0 / 3.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the distances of the five Lagrange points of a pair from the primary.
+///
+/// These are returned in order, L1 through L5.  L1 lies between the pair
+/// and L2 just beyond the secondary; L3 is on the far side of the primary;
+/// L4 and L5 lead and trail the secondary by 60°, at its own distance.  The
+/// result is in the same units as the separation, and the masses just need
+/// to share units.
+#[named]
+pub fn get_lagrange_points(separation: f64, mass: f64, primary_mass: f64) -> [f64; 5] {
+  trace_enter!();
+  trace_var!(separation);
+  trace_var!(mass);
+  trace_var!(primary_mass);
+  let mass_ratio = mass / (mass + primary_mass);
+  trace_var!(mass_ratio);
+  // The usual approximations, good for a secondary much lighter than its
+  // primary.
+  let hill_fraction = (mass_ratio / 3.0).powf(1.0 / 3.0);
+  trace_var!(hill_fraction);
+  let result = [
+    separation * (1.0 - hill_fraction),
+    separation * (1.0 + hill_fraction),
+    separation * (1.0 + 5.0 * mass_ratio / 12.0),
+    separation,
+    separation,
+  ];
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_earth() {
+    init();
+    trace_enter!();
+    // Earth's Hill sphere is about 1.5 million km; the Moon sits well within.
+    let hill_radius = get_hill_radius(1.496E8, 0.0167, 5.972E24, 1.989E30);
+    print_var!(hill_radius);
+    assert_approx_eq!(hill_radius, 1.47E6, 0.05E6f64);
+    let fluid_roche_limit = get_fluid_roche_limit(6_371.0, 5.514, 3.35);
+    print_var!(fluid_roche_limit);
+    assert_approx_eq!(fluid_roche_limit, 18_354.0, 10.0f64);
+    let rigid_roche_limit = get_rigid_roche_limit(6_371.0, 5.514, 3.35);
+    print_var!(rigid_roche_limit);
+    assert!(rigid_roche_limit < fluid_roche_limit);
+    assert_approx_eq!(rigid_roche_limit, 9_478.0, 10.0f64);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_io_and_europa() {
+    init();
+    trace_enter!();
+    // Io and Europa sit about 16 mutual Hill radii apart.
+    let mutual_hill_radius = get_mutual_hill_radius(421_700.0, 671_034.0, 8.93E22, 4.80E22, 1.898E27);
+    print_var!(mutual_hill_radius);
+    assert_approx_eq!((671_034.0 - 421_700.0) / mutual_hill_radius, 15.8, 0.1f64);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_lagrange_points() {
+    init();
+    trace_enter!();
+    // The Sun-Earth L1 and L2 are about 1.5 million km either side of Earth.
+    let lagrange_points = get_lagrange_points(1.496E8, 5.972E24, 1.989E30);
+    print_var!(lagrange_points);
+    assert_approx_eq!(1.496E8 - lagrange_points[0], 1.5E6, 0.05E6f64);
+    assert_approx_eq!(lagrange_points[1] - 1.496E8, 1.5E6, 0.05E6f64);
+    assert!(lagrange_points[2] > 1.496E8);
+    assert_eq!(lagrange_points[3], 1.496E8);
+    assert_eq!(lagrange_points[4], 1.496E8);
+    trace_exit!();
+  }
+}
@@ -0,0 +1,5 @@
+pub mod gravity;
+pub mod noise;
+pub mod probability;
+pub mod range;
+pub mod roman;
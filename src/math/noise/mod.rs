@@ -0,0 +1,231 @@
+use rand::prelude::*;
+
+use crate::generator::seeded_rng;
+
+/// The gradients used by simplex noise; the midpoints of the edges of a cube.
+const GRADIENTS: [(f64, f64, f64); 12] = [
+  (1.0, 1.0, 0.0),
+  (-1.0, 1.0, 0.0),
+  (1.0, -1.0, 0.0),
+  (-1.0, -1.0, 0.0),
+  (1.0, 0.0, 1.0),
+  (-1.0, 0.0, 1.0),
+  (1.0, 0.0, -1.0),
+  (-1.0, 0.0, -1.0),
+  (0.0, 1.0, 1.0),
+  (0.0, -1.0, 1.0),
+  (0.0, 1.0, -1.0),
+  (0.0, -1.0, -1.0),
+];
+
+/// The FNV-1a offset basis.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The FNV-1a prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Derive a seed from an object's ID, such as its name.
+///
+/// The standard library's hashers are free to change between releases, so
+/// this uses FNV-1a, which won't.  To get independent noise for different
+/// features of the same object, salt the ID, e.g. "Sol III:terrain".
+#[named]
+pub fn get_seed(id: &str) -> u64 {
+  trace_enter!();
+  trace_var!(id);
+  let result = id.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+    (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+  });
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// How to layer octaves of noise into fractal Brownian motion (fBm).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FbmOptions {
+  /// The number of octaves to layer.
+  pub octaves: usize,
+  /// How much the frequency grows from one octave to the next.
+  pub lacunarity: f64,
+  /// How much the amplitude shrinks from one octave to the next.
+  pub gain: f64,
+}
+
+impl Default for FbmOptions {
+  fn default() -> Self {
+    Self {
+      octaves: 5,
+      lacunarity: 2.0,
+      gain: 0.5,
+    }
+  }
+}
+
+/// A seeded source of simplex noise.
+///
+/// Everything procedural and texture-ish (the terrain of a terrestrial
+/// planet, the bands on a gas giant, the shape of a supernova's nebula) draws
+/// from one of these, so that the same object always looks the same.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Noise {
+  /// A shuffled permutation of 0..256, repeated to avoid wrapping indices.
+  permutation: Vec<usize>,
+}
+
+impl Noise {
+  /// Create from a seed.
+  #[named]
+  pub fn from_seed(seed: u64) -> Self {
+    trace_enter!();
+    trace_var!(seed);
+    let mut rng = seeded_rng(seed);
+    let mut permutation = (0..256).collect::<Vec<usize>>();
+    permutation.shuffle(&mut rng);
+    permutation.extend_from_within(..);
+    let result = Self { permutation };
+    trace_exit!();
+    result
+  }
+
+  /// Create from an object's ID; see `get_seed()`.
+  #[named]
+  pub fn from_id(id: &str) -> Self {
+    trace_enter!();
+    let result = Self::from_seed(get_seed(id));
+    trace_exit!();
+    result
+  }
+
+  /// Get the gradient for a lattice point.
+  fn get_gradient(&self, i: i64, j: i64, k: i64) -> (f64, f64, f64) {
+    let permutation = &self.permutation;
+    let index = permutation[(i & 255) as usize + permutation[(j & 255) as usize + permutation[(k & 255) as usize]]];
+    GRADIENTS[index % 12]
+  }
+
+  /// Get three-dimensional simplex noise at a point, in -1.0..=1.0.
+  ///
+  /// This is called for every sample, so it isn't traced.
+  pub fn get_3d(&self, x: f64, y: f64, z: f64) -> f64 {
+    let skew = 1.0 / 3.0;
+    let unskew = 1.0 / 6.0;
+    let s = (x + y + z) * skew;
+    let i = (x + s).floor();
+    let j = (y + s).floor();
+    let k = (z + s).floor();
+    let t = (i + j + k) * unskew;
+    let x0 = x - (i - t);
+    let y0 = y - (j - t);
+    let z0 = z - (k - t);
+    // Work out which of the six tetrahedra in the cube we're in.
+    let ((i1, j1, k1), (i2, j2, k2)) = match (x0 >= y0, y0 >= z0, x0 >= z0) {
+      (true, true, _) => ((1.0, 0.0, 0.0), (1.0, 1.0, 0.0)),
+      (true, false, true) => ((1.0, 0.0, 0.0), (1.0, 0.0, 1.0)),
+      (true, false, false) => ((0.0, 0.0, 1.0), (1.0, 0.0, 1.0)),
+      (false, false, _) => ((0.0, 0.0, 1.0), (0.0, 1.0, 1.0)),
+      (false, true, false) => ((0.0, 1.0, 0.0), (0.0, 1.0, 1.0)),
+      (false, true, true) => ((0.0, 1.0, 0.0), (1.0, 1.0, 0.0)),
+    };
+    let corners = [
+      (0.0, 0.0, 0.0, x0, y0, z0),
+      (i1, j1, k1, x0 - i1 + unskew, y0 - j1 + unskew, z0 - k1 + unskew),
+      (
+        i2,
+        j2,
+        k2,
+        x0 - i2 + 2.0 * unskew,
+        y0 - j2 + 2.0 * unskew,
+        z0 - k2 + 2.0 * unskew,
+      ),
+      (
+        1.0,
+        1.0,
+        1.0,
+        x0 - 1.0 + 3.0 * unskew,
+        y0 - 1.0 + 3.0 * unskew,
+        z0 - 1.0 + 3.0 * unskew,
+      ),
+    ];
+    let sum: f64 = corners
+      .iter()
+      .map(|&(di, dj, dk, dx, dy, dz)| {
+        let falloff = 0.6 - dx * dx - dy * dy - dz * dz;
+        if falloff < 0.0 {
+          return 0.0;
+        }
+        let (gx, gy, gz) = self.get_gradient((i + di) as i64, (j + dj) as i64, (k + dk) as i64);
+        falloff.powi(4) * (gx * dx + gy * dy + gz * dz)
+      })
+      .sum();
+    (32.0 * sum).clamp(-1.0, 1.0)
+  }
+
+  /// Get three-dimensional fBm at a point, in -1.0..=1.0.
+  pub fn get_fbm_3d(&self, x: f64, y: f64, z: f64, options: &FbmOptions) -> f64 {
+    self.get_fbm(options, |frequency| {
+      self.get_3d(x * frequency, y * frequency, z * frequency)
+    })
+  }
+
+  /// Sum octaves of noise, normalized by the total amplitude.
+  fn get_fbm<F: Fn(f64) -> f64>(&self, options: &FbmOptions, sample: F) -> f64 {
+    let mut frequency = 1.0;
+    let mut amplitude = 1.0;
+    let mut total = 0.0;
+    let mut total_amplitude = 0.0;
+    for _ in 0..options.octaves {
+      total += amplitude * sample(frequency);
+      total_amplitude += amplitude;
+      frequency *= options.lacunarity;
+      amplitude *= options.gain;
+    }
+    match total_amplitude > 0.0 {
+      true => total / total_amplitude,
+      false => 0.0,
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_seed() {
+    init();
+    trace_enter!();
+    assert_eq!(get_seed(""), FNV_OFFSET_BASIS);
+    assert_eq!(get_seed("a"), 0xaf63_dc4c_8601_ec8c);
+    assert_ne!(get_seed("Sol III:terrain"), get_seed("Sol III:clouds"));
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_noise() {
+    init();
+    trace_enter!();
+    let noise = Noise::from_id("Sol III:terrain");
+    assert_eq!(noise, Noise::from_id("Sol III:terrain"));
+    assert_ne!(noise, Noise::from_id("Sol V:bands"));
+    let options = FbmOptions::default();
+    let mut samples = vec![];
+    for step in 0..500 {
+      let (x, y, z) = (step as f64 * 0.37 - 90.0, step as f64 * 0.11, step as f64 * -0.23);
+      let samples_here = [noise.get_3d(x, y, z), noise.get_fbm_3d(x, y, z, &options)];
+      assert!(samples_here.iter().all(|sample| (-1.0..=1.0).contains(sample)));
+      samples.extend(samples_here);
+    }
+    // It's noise, not a constant.
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    print_var!(mean);
+    assert!(samples.iter().any(|sample| (sample - mean).abs() > 0.1));
+    // It's smooth, though.
+    assert_approx_eq!(noise.get_3d(1.0, 2.0, 3.0), noise.get_3d(1.0, 2.0, 3.0001), 0.01);
+    trace_exit!();
+  }
+}
@@ -0,0 +1,19 @@
+/// Probability-related errors.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+  /// Lower than 0.0.
+  BelowZero,
+  /// Higher than 1.0.
+  AboveOne,
+  /// Not a number at all.
+  NotANumber,
+}
+
+honeyholt_define_brief!(Error, |error: &Error| {
+  use Error::*;
+  match error {
+    BelowZero => "the probability is below zero".to_string(),
+    AboveOne => "the probability is above one".to_string(),
+    NotANumber => "the probability is not a number".to_string(),
+  }
+});
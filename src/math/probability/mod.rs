@@ -0,0 +1,112 @@
+use rand::prelude::*;
+use std::convert::TryFrom;
+
+pub mod error;
+use error::Error;
+
+/// A probability, guaranteed to lie within 0.0..=1.0.
+///
+/// Raw `f64` probabilities fail quietly: a value of 1.5 just means "always",
+/// and a negative value "never", which is rarely what was intended.  Building
+/// one of these checks the value up front instead.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Probability(f64);
+
+impl Probability {
+  /// Never.
+  pub const NEVER: Probability = Probability(0.0);
+
+  /// Always.
+  pub const ALWAYS: Probability = Probability(1.0);
+
+  /// Create a probability, rejecting anything outside of 0.0..=1.0.
+  pub const fn new(value: f64) -> Result<Self, Error> {
+    if value.is_nan() {
+      return Err(Error::NotANumber);
+    }
+    if value < 0.0 {
+      return Err(Error::BelowZero);
+    }
+    if value > 1.0 {
+      return Err(Error::AboveOne);
+    }
+    Ok(Probability(value))
+  }
+
+  /// Create a probability for use in a constant.
+  ///
+  /// This is checked when the constant is evaluated, so an out-of-range value
+  /// fails the build rather than the program.
+  pub const fn from_const(value: f64) -> Self {
+    match Probability::new(value) {
+      Ok(probability) => probability,
+      Err(_) => panic!("probability must lie within 0.0..=1.0"),
+    }
+  }
+
+  /// Get the value of this probability.
+  pub const fn get(&self) -> f64 {
+    self.0
+  }
+
+  /// Roll the dice; returns true with this probability.
+  #[named]
+  pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
+    trace_enter!();
+    let result = rng.gen_bool(self.0);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+impl TryFrom<f64> for Probability {
+  type Error = Error;
+
+  #[named]
+  fn try_from(value: f64) -> Result<Self, Error> {
+    Probability::new(value)
+  }
+}
+
+impl From<Probability> for f64 {
+  #[named]
+  fn from(probability: Probability) -> Self {
+    probability.0
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_new() {
+    init();
+    trace_enter!();
+    assert_eq!(Probability::new(0.25).map(|probability| probability.get()), Ok(0.25));
+    assert_eq!(Probability::new(0.0), Ok(Probability::NEVER));
+    assert_eq!(Probability::new(1.0), Ok(Probability::ALWAYS));
+    assert_eq!(Probability::new(-0.1), Err(Error::BelowZero));
+    assert_eq!(Probability::new(1.1), Err(Error::AboveOne));
+    assert_eq!(Probability::new(f64::NAN), Err(Error::NotANumber));
+    assert_eq!(Probability::try_from(0.5), Ok(Probability::from_const(0.5)));
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_sample() {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    assert!((0..100).all(|_| Probability::ALWAYS.sample(&mut rng)));
+    assert!((0..100).all(|_| !Probability::NEVER.sample(&mut rng)));
+    trace_exit!();
+  }
+}
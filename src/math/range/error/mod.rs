@@ -0,0 +1,24 @@
+/// Range-related errors.
+///
+/// Each carries the name of the offending range, e.g. "mass".
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+  /// One of the ends is not a number.
+  RangeNotANumber(&'static str),
+  /// The minimum is not below the maximum.
+  RangeEmpty(&'static str),
+  /// The minimum is below the lowest sensible value.
+  RangeBelowBound(&'static str),
+  /// The maximum is above the highest sensible value.
+  RangeAboveBound(&'static str),
+}
+
+honeyholt_define_brief!(Error, |error: &Error| {
+  use Error::*;
+  match error {
+    RangeNotANumber(name) => format!("the {} range is not a number", name),
+    RangeEmpty(name) => format!("the {} range is empty", name),
+    RangeBelowBound(name) => format!("the {} range extends too low", name),
+    RangeAboveBound(name) => format!("the {} range extends too high", name),
+  }
+});
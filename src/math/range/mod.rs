@@ -0,0 +1,74 @@
+pub mod error;
+use error::Error;
+
+/// Check a possibly-open range against the bounds of what makes sense.
+///
+/// Either end may be missing, in which case it's left to the generator's
+/// defaults and only the other end is checked.  Ranges are half-open, as
+/// with `Range<f64>`, so a range whose ends meet is empty.
+#[named]
+pub fn check_range(
+  name: &'static str,
+  minimum: Option<f64>,
+  maximum: Option<f64>,
+  bounds: (f64, f64),
+) -> Result<(), Error> {
+  trace_enter!();
+  trace_var!(name);
+  trace_var!(minimum);
+  trace_var!(maximum);
+  trace_var!(bounds);
+  if minimum.is_some_and(f64::is_nan) || maximum.is_some_and(f64::is_nan) {
+    return Err(Error::RangeNotANumber(name));
+  }
+  if let (Some(minimum), Some(maximum)) = (minimum, maximum) {
+    if minimum >= maximum {
+      return Err(Error::RangeEmpty(name));
+    }
+  }
+  if minimum.is_some_and(|minimum| minimum < bounds.0) || maximum.is_some_and(|maximum| maximum < bounds.0) {
+    return Err(Error::RangeBelowBound(name));
+  }
+  if minimum.is_some_and(|minimum| minimum > bounds.1) || maximum.is_some_and(|maximum| maximum > bounds.1) {
+    return Err(Error::RangeAboveBound(name));
+  }
+  trace_exit!();
+  Ok(())
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_check_range() {
+    init();
+    trace_enter!();
+    assert_eq!(check_range("mass", Some(0.5), Some(1.2), (0.0, 10.0)), Ok(()));
+    assert_eq!(check_range("mass", None, Some(1.2), (0.0, 10.0)), Ok(()));
+    assert_eq!(
+      check_range("mass", Some(1.2), Some(0.5), (0.0, 10.0)),
+      Err(Error::RangeEmpty("mass"))
+    );
+    assert_eq!(
+      check_range("mass", Some(1.0), Some(1.0), (0.0, 10.0)),
+      Err(Error::RangeEmpty("mass"))
+    );
+    assert_eq!(
+      check_range("mass", Some(-1.0), None, (0.0, 10.0)),
+      Err(Error::RangeBelowBound("mass"))
+    );
+    assert_eq!(
+      check_range("mass", None, Some(11.0), (0.0, 10.0)),
+      Err(Error::RangeAboveBound("mass"))
+    );
+    assert_eq!(
+      check_range("mass", Some(f64::NAN), None, (0.0, 10.0)),
+      Err(Error::RangeNotANumber("mass"))
+    );
+    trace_exit!();
+  }
+}
@@ -0,0 +1,52 @@
+/// Roman numerals and their values, largest first.
+pub const NUMERALS: &[(usize, &str)] = &[
+  (1000, "M"),
+  (900, "CM"),
+  (500, "D"),
+  (400, "CD"),
+  (100, "C"),
+  (90, "XC"),
+  (50, "L"),
+  (40, "XL"),
+  (10, "X"),
+  (9, "IX"),
+  (5, "V"),
+  (4, "IV"),
+  (1, "I"),
+];
+
+/// Write a positive number in Roman numerals, e.g. 14 as "XIV".
+#[named]
+pub fn get_roman_numeral(number: usize) -> String {
+  trace_enter!();
+  trace_var!(number);
+  let mut number = number;
+  let mut result = String::new();
+  for (value, symbol) in NUMERALS.iter() {
+    while number >= *value {
+      result.push_str(symbol);
+      number -= value;
+    }
+  }
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_get_roman_numeral() {
+    init();
+    trace_enter!();
+    assert_eq!(get_roman_numeral(1), "I");
+    assert_eq!(get_roman_numeral(14), "XIV");
+    assert_eq!(get_roman_numeral(1999), "MCMXCIX");
+    trace_exit!();
+  }
+}
@@ -0,0 +1,89 @@
+/// Something that can be layered atop a base profile.
+///
+/// This is implemented by all of the constraints, so that a campaign can
+/// define a base profile once and then tweak it region by region: anything
+/// the overrides leave unset falls through to the base, and nested
+/// constraints are merged in turn.  Flags work the same way, so a layer can
+/// turn one off by setting it to `Some(false)`.
+pub trait Merge {
+  /// Layer the overrides atop this.
+  fn merged_with(&self, overrides: &Self) -> Self;
+}
+
+impl<T: Merge + Clone> Merge for Option<T> {
+  fn merged_with(&self, overrides: &Self) -> Self {
+    match (self, overrides) {
+      (Some(base), Some(overrides)) => Some(base.merged_with(overrides)),
+      (base, overrides) => overrides.clone().or_else(|| base.clone()),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use super::*;
+  use crate::astronomy::star::constraints::Constraints as StarConstraints;
+  use crate::astronomy::stellar_neighbor::constraints::Constraints as StellarNeighborConstraints;
+  use crate::astronomy::stellar_neighborhood::constraints::Constraints as StellarNeighborhoodConstraints;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_merged_with() {
+    init();
+    trace_enter!();
+    let base = StellarNeighborhoodConstraints {
+      radius: Some(10.0),
+      density: Some(0.004),
+      neighbor_constraints: Some(StellarNeighborConstraints {
+        radius: Some(10.0),
+        system_constraints: None,
+      }),
+      rogue_planet_density: Some(0.008),
+      rogue_planet_constraints: None,
+      density_model: None,
+      name_theme: None,
+    };
+    let overrides = StellarNeighborhoodConstraints {
+      radius: Some(5.0),
+      density: None,
+      neighbor_constraints: Some(StellarNeighborConstraints {
+        radius: None,
+        system_constraints: None,
+      }),
+      rogue_planet_density: None,
+      rogue_planet_constraints: None,
+      density_model: None,
+      name_theme: None,
+    };
+    let merged = base.merged_with(&overrides);
+    print_var!(merged);
+    assert_eq!(merged.radius, Some(5.0));
+    assert_eq!(merged.density, Some(0.004));
+    assert_eq!(merged.neighbor_constraints.unwrap().radius, Some(10.0));
+    assert_eq!(merged.rogue_planet_density, Some(0.008));
+    let empty = StellarNeighborhoodConstraints {
+      radius: None,
+      density: None,
+      neighbor_constraints: None,
+      rogue_planet_density: None,
+      rogue_planet_constraints: None,
+      density_model: None,
+      name_theme: None,
+    };
+    assert_eq!(base.merged_with(&empty), base);
+    let habitable = StarConstraints::default().merged_with(&StarConstraints::habitable());
+    assert_eq!(habitable.make_habitable, Some(true));
+    assert_eq!(
+      habitable.merged_with(&StarConstraints::default()).make_habitable,
+      Some(true)
+    );
+    let uninhabitable = StarConstraints {
+      make_habitable: Some(false),
+      ..StarConstraints::default()
+    };
+    assert_eq!(habitable.merged_with(&uninhabitable).make_habitable, Some(false));
+    trace_exit!();
+  }
+}
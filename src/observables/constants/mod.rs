@@ -0,0 +1,15 @@
+/// Parts per million, for noise amplitudes.
+pub const PPM: f64 = 1.0E-6;
+
+/// Photon noise per exposure, in PPM.
+///
+/// This is roughly what Kepler achieved for a bright (12th magnitude) star
+/// at a thirty-minute cadence.
+pub const DEFAULT_PHOTON_NOISE: f64 = 30.0;
+
+/// The variability of a star with no convective envelope and no spots, such
+/// as a white dwarf: (granulation amplitude in PPM, granulation timescale in
+/// days, spot amplitude in PPM, rotation period in days).
+///
+/// Photon noise swamps what little there is.
+pub const QUIET_STAR_VARIABILITY: (f64, f64, f64, f64) = (1.0, 0.001, 0.0, 1.0);
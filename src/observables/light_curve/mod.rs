@@ -0,0 +1,160 @@
+use std::f64::consts::PI;
+
+use crate::astronomy::orbit::constants::{DAYS_PER_YEAR, KM_PER_AU};
+use crate::astronomy::planet::Planet;
+use crate::astronomy::star::math::distance::{meters_to_au, rsol_to_meters};
+use crate::astronomy::star::Star;
+
+/// A series of brightness measurements of a star.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LightCurve {
+  /// When each measurement was taken, in days.
+  pub times: Vec<f64>,
+  /// The flux at each time, relative to the star's undimmed flux.
+  pub fluxes: Vec<f64>,
+}
+
+/// Get the fractional dip in flux as a planet crosses its star.
+#[named]
+pub fn get_transit_depth(planet_radius: f64, star_radius: f64) -> f64 {
+  trace_enter!();
+  trace_var!(planet_radius);
+  trace_var!(star_radius);
+  let result = (planet_radius / KM_PER_AU / meters_to_au(rsol_to_meters(star_radius))).powf(2.0);
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the orbital period of a planet, in days.
+#[named]
+pub fn get_orbital_period_in_days(semi_major_axis: f64, star_mass: f64) -> f64 {
+  trace_enter!();
+  trace_var!(semi_major_axis);
+  trace_var!(star_mass);
+  let result = DAYS_PER_YEAR * (semi_major_axis.powf(3.0) / star_mass).sqrt();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+/// Get the duration of a central transit, in days.
+///
+/// This is the time from first to last contact, assuming a circular orbit
+/// seen exactly edge-on.
+#[named]
+pub fn get_transit_duration(period: f64, semi_major_axis: f64, star_radius: f64, planet_radius: f64) -> f64 {
+  trace_enter!();
+  trace_var!(period);
+  trace_var!(semi_major_axis);
+  trace_var!(star_radius);
+  trace_var!(planet_radius);
+  let chord = meters_to_au(rsol_to_meters(star_radius)) + planet_radius / KM_PER_AU;
+  trace_var!(chord);
+  let result = period / PI * (chord / semi_major_axis).min(1.0).asin();
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+impl LightCurve {
+  /// Sample a noiseless light curve of a planet transiting its star.
+  ///
+  /// Measurements are taken every `cadence` days over `span` days, with the
+  /// first transit centered halfway through the first orbit.  Ingress and
+  /// egress are linear, which is crude but keeps the shape recognizable.
+  #[named]
+  pub fn from_transit(star: &Star, planet: &Planet, cadence: f64, span: f64) -> Self {
+    trace_enter!();
+    trace_var!(cadence);
+    trace_var!(span);
+    let semi_major_axis = planet.get_semi_major_axis();
+    let planet_radius = planet.get_equatorial_radius_in_km();
+    let depth = get_transit_depth(planet_radius, star.radius);
+    trace_var!(depth);
+    let period = get_orbital_period_in_days(semi_major_axis, star.mass);
+    trace_var!(period);
+    let duration = get_transit_duration(period, semi_major_axis, star.radius, planet_radius);
+    trace_var!(duration);
+    // The planet takes this long to cross its own diameter at the limb.
+    let ingress = duration * depth.sqrt() / (1.0 + depth.sqrt());
+    trace_var!(ingress);
+    let count = (span / cadence).floor() as usize + 1;
+    let times = (0..count).map(|index| index as f64 * cadence).collect::<Vec<f64>>();
+    let fluxes = times
+      .iter()
+      .map(|time| {
+        let phase = (time - period / 2.0).rem_euclid(period);
+        let offset = phase.min(period - phase);
+        let edge = duration / 2.0;
+        match offset {
+          offset if offset >= edge => 1.0,
+          offset if offset <= edge - ingress => 1.0 - depth,
+          offset => 1.0 - depth * (edge - offset) / ingress,
+        }
+      })
+      .collect();
+    let result = Self { times, fluxes };
+    trace_exit!();
+    result
+  }
+
+  /// Get the lowest flux in the curve.
+  #[named]
+  pub fn get_minimum_flux(&self) -> f64 {
+    trace_enter!();
+    let result = self.fluxes.iter().copied().fold(f64::INFINITY, f64::min);
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::gas_giant_planet::constraints::Constraints as GasGiantPlanetConstraints;
+  use crate::astronomy::gas_giant_planet::error::Error;
+  use crate::astronomy::host_star::error::Error as HostStarError;
+  use crate::astronomy::host_star::HostStar;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_transit_depth_and_duration() {
+    init();
+    trace_enter!();
+    // Earth and Jupiter across the Sun.
+    assert_approx_eq!(get_transit_depth(6_371.0, 1.0), 84.0E-6, 1.0E-6);
+    assert_approx_eq!(get_transit_depth(69_911.0, 1.0), 0.0101, 0.0001);
+    // Earth's transit lasts about thirteen hours.
+    let duration = get_transit_duration(365.25, 1.0, 1.0, 6_371.0);
+    print_var!(duration);
+    assert_approx_eq!(duration * 24.0, 13.0, 0.2);
+    trace_exit!();
+  }
+
+  #[named]
+  #[test]
+  pub fn test_from_transit() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    // A hot Jupiter, which makes for a deep and frequent transit.
+    let star = Star::from_mass(&mut rng, 1.0).map_err(HostStarError::from)?;
+    let host_star = HostStar::Star(star.clone());
+    let planet = Planet::GasGiantPlanet(GasGiantPlanetConstraints::default().generate(&mut rng, &host_star, 0.05)?);
+    let period = get_orbital_period_in_days(0.05, star.mass);
+    let light_curve = LightCurve::from_transit(&star, &planet, 1.0 / 48.0, period);
+    let depth = get_transit_depth(planet.get_equatorial_radius_in_km(), star.radius);
+    print_var!(depth);
+    assert_approx_eq!(light_curve.get_minimum_flux(), 1.0 - depth, depth * 0.01);
+    assert_eq!(light_curve.fluxes[0], 1.0);
+    trace_exit!();
+    Ok(())
+  }
+}
@@ -0,0 +1,8 @@
+//! Things an astronomer elsewhere could measure about a generated system.
+//!
+//! For now this is transit photometry: the light curve of a star as a planet
+//! crosses it, and the noise the star itself adds to that curve.
+
+pub mod constants;
+pub mod light_curve;
+pub mod noise;
@@ -0,0 +1,205 @@
+use rand::prelude::*;
+use rand_distr::Normal;
+use std::f64::consts::PI;
+
+use crate::astronomy::star::Star;
+use crate::observables::constants::*;
+use crate::observables::light_curve::LightCurve;
+
+/// How a star varies in brightness on its own, independent of any planet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoiseProfile {
+  /// The RMS amplitude of granulation, in PPM.
+  pub granulation_amplitude: f64,
+  /// How long a granulation pattern persists, in days.
+  pub granulation_timescale: f64,
+  /// The semi-amplitude of rotational modulation by star spots, in PPM.
+  pub spot_amplitude: f64,
+  /// The rotation period of the star, which sets the spot modulation, in days.
+  pub rotation_period: f64,
+  /// The RMS of photon (white) noise per measurement, in PPM.
+  pub photon_noise: f64,
+}
+
+/// Get typical variability for a star of a spectral class.
+///
+/// Returns (granulation amplitude in PPM, granulation timescale in days, spot
+/// amplitude in PPM, rotation period in days) for a star about the age of
+/// the Sun.  Hot stars lack convective envelopes, so they have little of
+/// either; cool stars are spotty and rotate slowly.  White dwarfs (class D)
+/// are quiet, and classes we don't model give `None`.
+#[named]
+pub fn spectral_class_to_variability(class: char) -> Option<(f64, f64, f64, f64)> {
+  trace_enter!();
+  trace_var!(class);
+  let result = match class {
+    'o' | 'O' => Some((5.0, 0.02, 10.0, 1.0)),
+    'b' | 'B' => Some((5.0, 0.02, 10.0, 1.0)),
+    'a' | 'A' => Some((10.0, 0.02, 20.0, 1.5)),
+    'f' | 'F' => Some((80.0, 0.012, 300.0, 8.0)),
+    'g' | 'G' => Some((50.0, 0.01, 1_000.0, 25.0)),
+    'k' | 'K' => Some((30.0, 0.007, 3_000.0, 35.0)),
+    'm' | 'M' => Some((15.0, 0.005, 8_000.0, 50.0)),
+    'd' | 'D' => Some(QUIET_STAR_VARIABILITY),
+    _ => None,
+  };
+  trace_var!(result);
+  trace_exit!();
+  result
+}
+
+impl NoiseProfile {
+  /// Estimate the variability of a star from its class and age.
+  ///
+  /// Stars spin down as they age (Skumanich's law, P ∝ √t), and spottiness
+  /// falls off with the spin.  A star of a class we don't model is treated
+  /// as quiet.
+  #[named]
+  pub fn from_star(star: &Star) -> Self {
+    trace_enter!();
+    let class = star.class.chars().next();
+    trace_var!(class);
+    let (granulation_amplitude, granulation_timescale, solar_age_spot_amplitude, solar_age_rotation_period) = class
+      .and_then(spectral_class_to_variability)
+      .unwrap_or(QUIET_STAR_VARIABILITY);
+    let spin_down = (star.current_age / 4.6).sqrt().clamp(0.1, 2.0);
+    trace_var!(spin_down);
+    let rotation_period = solar_age_rotation_period * spin_down;
+    trace_var!(rotation_period);
+    let spot_amplitude = solar_age_spot_amplitude / spin_down;
+    trace_var!(spot_amplitude);
+    let photon_noise = DEFAULT_PHOTON_NOISE;
+    let result = Self {
+      granulation_amplitude,
+      granulation_timescale,
+      spot_amplitude,
+      rotation_period,
+      photon_noise,
+    };
+    trace_var!(result);
+    trace_exit!();
+    result
+  }
+
+  /// Add this star's noise to a light curve.
+  ///
+  /// Granulation is red noise (an Ornstein-Uhlenbeck process), spots are a
+  /// two-harmonic modulation at the rotation period whose amplitude drifts as
+  /// spots come and go, and photon noise is white.  All of them scale the
+  /// flux, so a transit is dimmed along with everything else.
+  #[named]
+  pub fn apply<R: Rng + ?Sized>(&self, rng: &mut R, light_curve: &LightCurve) -> LightCurve {
+    trace_enter!();
+    trace_var!(self);
+    let standard_normal = Normal::new(0.0, 1.0).unwrap();
+    let phases: [f64; 3] = [
+      rng.gen_range(0.0..2.0 * PI),
+      rng.gen_range(0.0..2.0 * PI),
+      rng.gen_range(0.0..2.0 * PI),
+    ];
+    trace_var!(phases);
+    // Spots typically live for a few rotations.
+    let spot_lifetime = 4.0 * self.rotation_period;
+    let mut granulation = self.granulation_amplitude * standard_normal.sample(rng);
+    let mut previous_time = light_curve.times.first().copied().unwrap_or(0.0);
+    let mut fluxes = Vec::with_capacity(light_curve.fluxes.len());
+    for (time, flux) in light_curve.times.iter().zip(light_curve.fluxes.iter()) {
+      let decay = (-(time - previous_time) / self.granulation_timescale).exp();
+      granulation =
+        granulation * decay + self.granulation_amplitude * (1.0 - decay.powf(2.0)).sqrt() * standard_normal.sample(rng);
+      previous_time = *time;
+      let rotation = 2.0 * PI * time / self.rotation_period;
+      let envelope = 1.0 + 0.3 * (2.0 * PI * time / spot_lifetime + phases[2]).sin();
+      let spots = self.spot_amplitude
+        * envelope
+        * (0.8 * (rotation + phases[0]).sin() + 0.2 * (2.0 * rotation + phases[1]).sin());
+      let photons = self.photon_noise * standard_normal.sample(rng);
+      fluxes.push(flux * (1.0 + (granulation + spots + photons) * PPM));
+    }
+    let times = light_curve.times.clone();
+    let result = LightCurve { times, fluxes };
+    trace_exit!();
+    result
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::star::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_apply() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let flat = LightCurve {
+      times: (0..2_000).map(|index| index as f64 / 48.0).collect(),
+      fluxes: vec![1.0; 2_000],
+    };
+    let get_rms = |light_curve: &LightCurve| {
+      let count = light_curve.fluxes.len() as f64;
+      (light_curve
+        .fluxes
+        .iter()
+        .map(|flux| (flux - 1.0).powf(2.0))
+        .sum::<f64>()
+        / count)
+        .sqrt()
+        / PPM
+    };
+    let sun_like = NoiseProfile::from_star(&Star::from_mass(&mut rng, 1.0)?);
+    print_var!(sun_like);
+    let sun_like_rms = get_rms(&sun_like.apply(&mut rng, &flat));
+    print_var!(sun_like_rms);
+    let quiet = NoiseProfile {
+      spot_amplitude: 0.0,
+      ..sun_like
+    };
+    let quiet_rms = get_rms(&quiet.apply(&mut rng, &flat));
+    print_var!(quiet_rms);
+    // Without spots, only granulation and photon noise remain.
+    let expected = (quiet.granulation_amplitude.powf(2.0) + quiet.photon_noise.powf(2.0)).sqrt();
+    assert_approx_eq!(quiet_rms, expected, 0.3 * expected);
+    assert!(sun_like_rms > quiet_rms);
+    trace_exit!();
+    Ok(())
+  }
+
+  #[named]
+  #[test]
+  pub fn test_from_evolved_star() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let star = Star::from_mass(&mut rng, 1.0)?;
+    let flat = LightCurve {
+      times: (0..100).map(|index| index as f64 / 48.0).collect(),
+      fluxes: vec![1.0; 100],
+    };
+    for age in [0.5, 1.15, 1.5] {
+      let evolved = star.clone().at_age(age * star.life_expectancy);
+      let noise_profile = NoiseProfile::from_star(&evolved);
+      print_var!((&evolved.class, &noise_profile));
+      let light_curve = noise_profile.apply(&mut rng, &flat);
+      assert!(light_curve.fluxes.iter().all(|flux| flux.is_finite()));
+    }
+    let white_dwarf = star.clone().at_age(1.5 * star.life_expectancy);
+    assert!(white_dwarf.class.starts_with('D'));
+    assert_eq!(NoiseProfile::from_star(&white_dwarf).spot_amplitude, 0.0);
+    let unknown = Star {
+      class: "Y0".to_string(),
+      ..star.clone()
+    };
+    assert_eq!(NoiseProfile::from_star(&unknown).spot_amplitude, 0.0);
+    trace_exit!();
+    Ok(())
+  }
+}
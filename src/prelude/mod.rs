@@ -0,0 +1,97 @@
+//! The types most callers will want, under consistent names.
+//!
+//! Everything here is considered stable API.  Each object is exported under
+//! its own name, and its constraints as `<Object>Constraints`; constraints
+//! that can work from an RNG alone implement `Generator`.  `Probability`,
+//! `RangeError`, and `Merge` are here because constraints are built from them,
+//! and `ProbabilityError` because building a `Probability` can fail.  `Time`
+//! is here because snapshots and orbital positions are taken at one.
+//!
+//! Anything else, like the kinds and views hanging off the objects, lives in
+//! its object's module.
+
+pub use crate::astronomy::asteroid_belt::constraints::Constraints as AsteroidBeltConstraints;
+pub use crate::astronomy::asteroid_belt::AsteroidBelt;
+pub use crate::astronomy::brown_dwarf::constraints::Constraints as BrownDwarfConstraints;
+pub use crate::astronomy::brown_dwarf::BrownDwarf;
+pub use crate::astronomy::close_binary_star::constraints::Constraints as CloseBinaryStarConstraints;
+pub use crate::astronomy::close_binary_star::CloseBinaryStar;
+pub use crate::astronomy::distant_binary_star::constraints::Constraints as DistantBinaryStarConstraints;
+pub use crate::astronomy::distant_binary_star::DistantBinaryStar;
+pub use crate::astronomy::dwarf_planet::constraints::Constraints as DwarfPlanetConstraints;
+pub use crate::astronomy::dwarf_planet::DwarfPlanet;
+pub use crate::astronomy::galaxy::constraints::Constraints as GalaxyConstraints;
+pub use crate::astronomy::galaxy::Galaxy;
+pub use crate::astronomy::gas_giant_planet::constraints::Constraints as GasGiantPlanetConstraints;
+pub use crate::astronomy::gas_giant_planet::GasGiantPlanet;
+pub use crate::astronomy::host_star::constraints::Constraints as HostStarConstraints;
+pub use crate::astronomy::host_star::HostStar;
+pub use crate::astronomy::ice_giant_planet::constraints::Constraints as IceGiantPlanetConstraints;
+pub use crate::astronomy::ice_giant_planet::IceGiantPlanet;
+pub use crate::astronomy::kuiper_belt::constraints::Constraints as KuiperBeltConstraints;
+pub use crate::astronomy::kuiper_belt::KuiperBelt;
+pub use crate::astronomy::moon::constraints::Constraints as MoonConstraints;
+pub use crate::astronomy::moon::Moon;
+pub use crate::astronomy::moons::constraints::Constraints as MoonsConstraints;
+pub use crate::astronomy::moons::Moons;
+pub use crate::astronomy::oort_cloud::constraints::Constraints as OortCloudConstraints;
+pub use crate::astronomy::oort_cloud::OortCloud;
+pub use crate::astronomy::planet::constraints::Constraints as PlanetConstraints;
+pub use crate::astronomy::planet::Planet;
+pub use crate::astronomy::planetary_system::constraints::Constraints as PlanetarySystemConstraints;
+pub use crate::astronomy::planetary_system::PlanetarySystem;
+pub use crate::astronomy::ring_system::constraints::Constraints as RingSystemConstraints;
+pub use crate::astronomy::ring_system::RingSystem;
+pub use crate::astronomy::rogue_planet::constraints::Constraints as RoguePlanetConstraints;
+pub use crate::astronomy::rogue_planet::RoguePlanet;
+pub use crate::astronomy::satellite_system::constraints::Constraints as SatelliteSystemConstraints;
+pub use crate::astronomy::satellite_system::SatelliteSystem;
+pub use crate::astronomy::satellite_systems::constraints::Constraints as SatelliteSystemsConstraints;
+pub use crate::astronomy::satellite_systems::SatelliteSystems;
+pub use crate::astronomy::star::constraints::Constraints as StarConstraints;
+pub use crate::astronomy::star::Star;
+pub use crate::astronomy::star_subsystem::constraints::Constraints as StarSubsystemConstraints;
+pub use crate::astronomy::star_subsystem::StarSubsystem;
+pub use crate::astronomy::star_system::constraints::Constraints as StarSystemConstraints;
+pub use crate::astronomy::star_system::StarSystem;
+pub use crate::astronomy::stellar_neighbor::constraints::Constraints as StellarNeighborConstraints;
+pub use crate::astronomy::stellar_neighbor::StellarNeighbor;
+pub use crate::astronomy::stellar_neighborhood::constraints::Constraints as StellarNeighborhoodConstraints;
+pub use crate::astronomy::stellar_neighborhood::StellarNeighborhood;
+pub use crate::astronomy::stellar_remnant::constraints::Constraints as StellarRemnantConstraints;
+pub use crate::astronomy::stellar_remnant::StellarRemnant;
+pub use crate::astronomy::terrestrial_planet::constraints::Constraints as TerrestrialPlanetConstraints;
+pub use crate::astronomy::terrestrial_planet::TerrestrialPlanet;
+pub use crate::astronomy::time::Time;
+pub use crate::astronomy::trojan_swarm::constraints::Constraints as TrojanSwarmConstraints;
+pub use crate::astronomy::trojan_swarm::TrojanSwarm;
+pub use crate::astronomy::zodiacal_dust::ZodiacalDust;
+pub use crate::generator::{seeded_rng, GenerationStrategy, Generator};
+pub use crate::math::probability::error::Error as ProbabilityError;
+pub use crate::math::probability::Probability;
+pub use crate::math::range::error::Error as RangeError;
+pub use crate::merge::Merge;
+
+#[cfg(test)]
+pub mod test {
+
+  use rand::prelude::*;
+
+  use super::*;
+  use crate::astronomy::galaxy::error::Error;
+  use crate::test::*;
+
+  #[named]
+  #[test]
+  pub fn test_prelude() -> Result<(), Error> {
+    init();
+    trace_enter!();
+    let mut rng = thread_rng();
+    trace_var!(rng);
+    let galaxy: Galaxy = Generator::generate(&GalaxyConstraints::default(), &mut rng)?;
+    print_var!(galaxy);
+    assert_eq!(Probability::new(1.5), Err(ProbabilityError::AboveOne));
+    trace_exit!();
+    Ok(())
+  }
+}